@@ -0,0 +1,41 @@
+//! Per-frame rendering cost of each background style.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use ratatui::{buffer::Buffer, layout::Rect};
+use sigye_background::BackgroundState;
+use sigye_core::{AnimationSpeed, BackgroundStyle, SystemMetrics};
+
+fn render_backgrounds(c: &mut Criterion) {
+    let area = Rect::new(0, 0, 200, 60);
+    let metrics = SystemMetrics {
+        cpu_usage: 0.6,
+        memory_usage: 0.5,
+        network_rx_rate: 0.3,
+        network_tx_rate: 0.2,
+        ..Default::default()
+    };
+
+    let mut style = BackgroundStyle::None.next();
+    while style != BackgroundStyle::None {
+        let mut state = BackgroundState::new();
+        let mut buf = Buffer::empty(area);
+        let mut elapsed_ms = 0;
+        c.bench_function(&format!("render_200x60/{style:?}"), |b| {
+            b.iter(|| {
+                elapsed_ms += 100;
+                state.render(
+                    area,
+                    &mut buf,
+                    style,
+                    elapsed_ms,
+                    AnimationSpeed::Medium,
+                    Some(&metrics),
+                );
+            })
+        });
+        style = style.next();
+    }
+}
+
+criterion_group!(benches, render_backgrounds);
+criterion_main!(benches);
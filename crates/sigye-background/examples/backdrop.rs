@@ -0,0 +1,72 @@
+//! A minimal app drawing its own text over a sigye background.
+//!
+//! Run with `cargo run -p sigye-background --example backdrop [style]`, e.g.
+//! `matrix-rain`, `aurora` or `snowfall`; press any key to quit.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    crossterm::event::{self, Event},
+    layout::{Constraint, Layout},
+    widgets::{Block, Paragraph},
+};
+use sigye_background::{BackgroundState, BackgroundWidget};
+use sigye_core::BackgroundStyle;
+
+fn main() -> std::io::Result<()> {
+    let style = std::env::args()
+        .nth(1)
+        .and_then(|name| BackgroundStyle::by_name(&name))
+        .unwrap_or(BackgroundStyle::MatrixRain);
+
+    let mut terminal = ratatui::init();
+    // One state for the whole run, so stateful styles keep animating
+    let mut state = BackgroundState::new();
+    let start = Instant::now();
+
+    let result = loop {
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        let drawn = terminal.draw(|frame| {
+            let area = frame.area();
+            frame.render_stateful_widget(
+                BackgroundWidget::new(style, elapsed_ms),
+                area,
+                &mut state,
+            );
+
+            // The app's own content, drawn over the background
+            let [_, middle, _] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(3),
+                Constraint::Fill(1),
+            ])
+            .areas(area);
+            let [_, pane, _] = Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Length(32),
+                Constraint::Fill(1),
+            ])
+            .areas(middle);
+            frame.render_widget(
+                Paragraph::new(format!("{style:?} background")).block(Block::bordered()),
+                pane,
+            );
+        });
+        if let Err(e) = drawn {
+            break Err(e);
+        }
+
+        match event::poll(Duration::from_millis(33)) {
+            Ok(true) => {
+                if let Ok(Event::Key(_)) = event::read() {
+                    break Ok(());
+                }
+            }
+            Ok(false) => {}
+            Err(e) => break Err(e),
+        }
+    };
+
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,253 @@
+//! Constellations now and then among the stars of the starfield: a known
+//! pattern of bright stars joined by faint dotted lines (stateful).
+
+use std::collections::HashMap;
+
+use ratatui::style::Color;
+
+use crate::animations::{Glyph, mix};
+
+/// How long each constellation shows before the sky moves on to the next.
+pub const CONSTELLATION_CYCLE_MS: u64 = 90_000;
+
+/// One cycle in this many shows no constellation, leaving plain stars.
+const EMPTY_CYCLE_ODDS: u64 = 3;
+
+/// Character of a constellation's stars.
+const STAR_CHAR: char = '✦';
+
+/// Color of a constellation's stars, brighter than the starfield's.
+const STAR_COLOR: Color = Color::Rgb(210, 210, 250);
+
+/// Character of the lines joining the stars.
+const LINE_CHAR: char = '·';
+
+/// Color of the lines joining the stars, barely above the background.
+const LINE_COLOR: Color = Color::Rgb(45, 45, 65);
+
+/// A constellation as stars at cell offsets from its top left corner, and
+/// the pairs of stars joined by lines.
+///
+/// Cells are about twice as tall as wide, so columns are spread out twice
+/// as far as rows.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Constellation {
+    /// Name of the constellation.
+    pub name: &'static str,
+    /// Column and row of each star.
+    pub stars: &'static [(u16, u16)],
+    /// Indices into `stars` of the ends of each line.
+    pub lines: &'static [(usize, usize)],
+}
+
+impl Constellation {
+    /// Columns and rows the pattern takes up.
+    pub fn size(&self) -> (u16, u16) {
+        self.stars.iter().fold((0, 0), |(width, height), &(x, y)| {
+            (width.max(x + 1), height.max(y + 1))
+        })
+    }
+}
+
+/// The constellations drawn, each easy to pick out.
+pub const CONSTELLATIONS: [Constellation; 3] = [
+    Constellation {
+        name: "Orion",
+        // Betelgeuse, Bellatrix, the belt, Saiph and Rigel
+        stars: &[(0, 0), (12, 1), (4, 6), (6, 5), (8, 4), (2, 10), (13, 9)],
+        lines: &[(0, 1), (0, 2), (1, 4), (2, 3), (3, 4), (2, 5), (4, 6)],
+    },
+    Constellation {
+        name: "Big Dipper",
+        // Alkaid, Mizar, Alioth and Megrez along the handle, then the bowl
+        stars: &[(0, 3), (3, 1), (6, 1), (9, 1), (10, 4), (16, 4), (16, 0)],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 3)],
+    },
+    Constellation {
+        name: "Cassiopeia",
+        stars: &[(0, 0), (3, 3), (6, 1), (9, 3), (12, 0)],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4)],
+    },
+];
+
+/// Top left corner to draw `constellation` at in a `width` x `height` area,
+/// picked from `seed`, or `None` when it doesn't fit.
+pub fn place(
+    constellation: &Constellation,
+    seed: u64,
+    width: u16,
+    height: u16,
+) -> Option<(u16, u16)> {
+    let (pattern_width, pattern_height) = constellation.size();
+    if pattern_width > width || pattern_height > height {
+        return None;
+    }
+    let spots_x = u64::from(width - pattern_width) + 1;
+    let spots_y = u64::from(height - pattern_height) + 1;
+    Some((
+        (mix(seed, 1, 0) % spots_x) as u16,
+        (mix(seed, 2, 0) % spots_y) as u16,
+    ))
+}
+
+/// Cells between two stars on the line joining them, without the stars.
+fn line_cells((x0, y0): (u16, u16), (x1, y1): (u16, u16)) -> Vec<(u16, u16)> {
+    let steps = x0.abs_diff(x1).max(y0.abs_diff(y1));
+    let lerp = |from: u16, to: u16, step: u16| {
+        let offset = (f32::from(to) - f32::from(from)) * f32::from(step) / f32::from(steps);
+        (f32::from(from) + offset).round() as u16
+    };
+    (1..steps)
+        .map(|step| (lerp(x0, x1, step), lerp(y0, y1, step)))
+        .collect()
+}
+
+/// The constellation on screen and its cells.
+#[derive(Debug, Clone, Default)]
+pub struct ConstellationState {
+    /// Cycle and area size the cells were placed for.
+    placed_for: Option<(u64, u16, u16)>,
+    /// Glyph of each cell of the constellation, empty between constellations.
+    cells: HashMap<(u16, u16), Glyph>,
+}
+
+/// Pick and place the constellation of the current cycle, again when the
+/// cycle changes or the area is resized.
+///
+/// The constellation and its spot follow from `seed` and the cycle, so the
+/// same seed shows the same sky. Some cycles show none, and constellations
+/// that don't fit the area are skipped.
+pub fn update_constellation(
+    state: &mut ConstellationState,
+    seed: u64,
+    elapsed_ms: u64,
+    width: u16,
+    height: u16,
+) {
+    let cycle = elapsed_ms / CONSTELLATION_CYCLE_MS;
+    if state.placed_for == Some((cycle, width, height)) {
+        return;
+    }
+    state.placed_for = Some((cycle, width, height));
+    state.cells.clear();
+
+    let pick = mix(seed, cycle, 0);
+    if pick.is_multiple_of(EMPTY_CYCLE_ODDS) {
+        return;
+    }
+    let constellation = &CONSTELLATIONS[(pick / EMPTY_CYCLE_ODDS) as usize % CONSTELLATIONS.len()];
+    let Some((left, top)) = place(constellation, pick, width, height) else {
+        return;
+    };
+    let star = |index: usize| {
+        let (x, y) = constellation.stars[index];
+        (left + x, top + y)
+    };
+    for &(from, to) in constellation.lines {
+        for cell in line_cells(star(from), star(to)) {
+            state.cells.insert(cell, Glyph::new(LINE_CHAR, LINE_COLOR));
+        }
+    }
+    // Stars go over the lines that meet at them
+    for index in 0..constellation.stars.len() {
+        state
+            .cells
+            .insert(star(index), Glyph::new(STAR_CHAR, STAR_COLOR));
+    }
+}
+
+/// Render the constellation cell at a position, if it covers it.
+pub fn render_constellation_char(state: &ConstellationState, x: u16, y: u16) -> Option<Glyph> {
+    state.cells.get(&(x, y)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_are_well_formed() {
+        for constellation in &CONSTELLATIONS {
+            let (width, height) = constellation.size();
+            assert!(width <= 20 && height <= 12, "{}", constellation.name);
+            for &(from, to) in constellation.lines {
+                assert!(from < constellation.stars.len() && to < constellation.stars.len());
+            }
+            // Every star is joined to the pattern
+            for index in 0..constellation.stars.len() {
+                assert!(
+                    constellation
+                        .lines
+                        .iter()
+                        .any(|&(from, to)| from == index || to == index),
+                    "{} star {index}",
+                    constellation.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_place_fits_in_the_area() {
+        for constellation in &CONSTELLATIONS {
+            let (pattern_width, pattern_height) = constellation.size();
+            for (width, height) in [
+                (80, 24),
+                (pattern_width, pattern_height),
+                (200, 60),
+                (21, 13),
+            ] {
+                for seed in 0..200 {
+                    let (x, y) = place(constellation, seed, width, height).unwrap();
+                    assert!(x + pattern_width <= width && y + pattern_height <= height);
+                }
+            }
+            // Too small an area gets none
+            assert_eq!(place(constellation, 7, pattern_width - 1, 50), None);
+            assert_eq!(place(constellation, 7, 200, pattern_height - 1), None);
+        }
+    }
+
+    #[test]
+    fn test_place_is_stable_for_a_seed() {
+        let orion = &CONSTELLATIONS[0];
+        assert_eq!(place(orion, 42, 80, 24), place(orion, 42, 80, 24));
+        let spots: std::collections::HashSet<_> =
+            (0..50).map(|seed| place(orion, seed, 80, 24)).collect();
+        assert!(spots.len() > 10, "seeds should spread the pattern around");
+    }
+
+    #[test]
+    fn test_line_cells() {
+        assert_eq!(line_cells((0, 0), (4, 0)), vec![(1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line_cells((0, 0), (4, 2)), vec![(1, 1), (2, 1), (3, 2)]);
+        assert_eq!(line_cells((3, 3), (2, 4)), vec![]);
+    }
+
+    #[test]
+    fn test_update_places_stars_and_lines() {
+        // Find a cycle with a constellation for this seed
+        let mut state = ConstellationState::default();
+        let shown = (0..20)
+            .map(|cycle| {
+                update_constellation(&mut state, 42, cycle * CONSTELLATION_CYCLE_MS, 80, 24);
+                state.cells.clone()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect::<Vec<_>>();
+        assert!(!shown.is_empty() && shown.len() < 20);
+
+        let cells = &shown[0];
+        let stars = cells.values().filter(|glyph| glyph.ch == STAR_CHAR).count();
+        assert!((5..=7).contains(&stars));
+        assert!(cells.values().any(|glyph| glyph.ch == LINE_CHAR));
+        assert!(cells.keys().all(|&(x, y)| x < 80 && y < 24));
+
+        // Re-placed to fit after a resize, and nothing in a tiny area
+        update_constellation(&mut state, 42, 0, 30, 14);
+        assert!(state.cells.keys().all(|&(x, y)| x < 30 && y < 14));
+        update_constellation(&mut state, 42, 0, 8, 4);
+        assert!(state.cells.is_empty());
+        assert_eq!(render_constellation_char(&state, 0, 0), None);
+    }
+}
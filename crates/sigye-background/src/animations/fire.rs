@@ -0,0 +1,195 @@
+//! Fireplace flame animation (stateful heat diffusion).
+
+use ratatui::style::Color;
+use sigye_core::AnimationSpeed;
+
+use crate::animations::{Glyph, next_random};
+use crate::chars::FIRE_CHARS;
+use crate::color::hsl_to_rgb;
+
+/// Fraction of the screen height the flames may reach, keeping the digits readable.
+pub const FIRE_HEIGHT_RATIO: f32 = 0.4;
+
+/// Heat below which a cell is left blank.
+const MIN_VISIBLE_HEAT: f32 = 0.08;
+
+/// Maximum diffusion steps per update, so a long pause doesn't stall a frame.
+const MAX_STEPS_PER_UPDATE: u64 = 8;
+
+/// Heat grid for the flames, covering the bottom of the screen.
+#[derive(Debug, Clone)]
+pub struct FireState {
+    /// Heat per cell (0.0 - 1.0), row by row from the top of the flame area.
+    pub heat: Vec<f32>,
+    /// Width of the grid in cells.
+    pub width: usize,
+    /// Number of rows in the flame area.
+    pub rows: usize,
+    /// Heat lost per row as it rises.
+    pub cooling: f32,
+    /// Time not yet consumed by a diffusion step.
+    pub pending_ms: u64,
+    /// Random state for seeding heat at the bottom row.
+    pub seed: u64,
+}
+
+/// Initialize a cold heat grid for the given dimensions.
+pub fn init_fire(width: u16, height: u16, init_seed: u64) -> FireState {
+    let width = width as usize;
+    let rows = ((height as f32 * FIRE_HEIGHT_RATIO).round() as usize).max(1);
+    FireState {
+        heat: vec![0.0; width * rows],
+        width,
+        rows,
+        // Heat from the hottest seeds fades out just below the top of the area
+        cooling: 1.2 / rows as f32,
+        pending_ms: 0,
+        // Xorshift needs a non-zero state
+        seed: init_seed | 1,
+    }
+}
+
+/// Advance the flames by as many diffusion steps as the elapsed time allows.
+pub fn update_fire(state: &mut FireState, delta_ms: u64, speed: AnimationSpeed) {
+    let step_ms = speed.fire_step_ms();
+    state.pending_ms += delta_ms;
+
+    let steps = (state.pending_ms / step_ms).min(MAX_STEPS_PER_UPDATE);
+    state.pending_ms %= step_ms;
+
+    for _ in 0..steps {
+        seed_heat(state);
+        diffuse(&mut state.heat, state.width, state.cooling);
+    }
+}
+
+/// Put random heat into the bottom row of the grid.
+fn seed_heat(state: &mut FireState) {
+    let bottom = (state.rows - 1) * state.width;
+    for x in 0..state.width {
+        let roll = next_random(&mut state.seed);
+        let fraction = (roll % 1000) as f32 / 1000.0;
+        // Most cells burn hot, the rest flicker with embers
+        state.heat[bottom + x] = if roll % 10 < 6 {
+            0.7 + 0.3 * fraction
+        } else {
+            0.3 * fraction
+        };
+    }
+}
+
+/// Propagate heat one row upward, losing `cooling` along the way.
+///
+/// Each cell becomes the average of itself and the three cells below it, minus
+/// the cooling. The bottom row keeps its seeded heat.
+pub fn diffuse(heat: &mut [f32], width: usize, cooling: f32) {
+    if width == 0 {
+        return;
+    }
+    let rows = heat.len() / width;
+
+    // Rows are updated top-down, so the row below is still from the previous step
+    for y in 0..rows.saturating_sub(1) {
+        for x in 0..width {
+            let below = (y + 1) * width;
+            let left = below + x.saturating_sub(1);
+            let right = below + (x + 1).min(width - 1);
+            let sum = heat[y * width + x] + heat[left] + heat[below + x] + heat[right];
+            heat[y * width + x] = (sum / 4.0 - cooling).max(0.0);
+        }
+    }
+}
+
+/// Render a fireplace character.
+pub fn render_fire_char(state: &FireState, x: u16, y: u16, height: u16) -> Option<Glyph> {
+    let x = x as usize;
+    let top = (height as usize).checked_sub(state.rows)?;
+    let row = (y as usize).checked_sub(top)?;
+    if x >= state.width || row >= state.rows {
+        return None;
+    }
+
+    let heat = state.heat[row * state.width + x];
+    if heat < MIN_VISIBLE_HEAT {
+        return None;
+    }
+
+    let idx = ((heat * FIRE_CHARS.len() as f32) as usize).min(FIRE_CHARS.len() - 1);
+    Some(Glyph::new(FIRE_CHARS[idx], fire_color(heat)))
+}
+
+/// Map heat to a flame color: dark red → orange → yellow → white.
+fn fire_color(heat: f32) -> Color {
+    let heat = heat.clamp(0.0, 1.0);
+    let hue = 60.0 * heat.min(0.8) / 0.8;
+    // The hottest cells wash out toward white
+    let white = ((heat - 0.8) / 0.2).max(0.0);
+    let saturation = 1.0 - 0.6 * white;
+    let lightness = 0.2 + 0.4 * heat + 0.3 * white;
+    hsl_to_rgb(hue, saturation, lightness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(heat: &[f32]) -> f32 {
+        heat.iter().sum()
+    }
+
+    #[test]
+    fn test_diffusion_without_cooling_conserves_uniform_heat() {
+        let mut heat = vec![0.5; 6 * 4];
+        diffuse(&mut heat, 6, 0.0);
+        assert!(heat.iter().all(|&h| (h - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_diffusion_decays_heat() {
+        let mut heat = vec![0.5; 6 * 4];
+        let before = total(&heat);
+        diffuse(&mut heat, 6, 0.1);
+
+        // Upper rows lose exactly the cooling, the bottom row is untouched
+        for &h in &heat[..6 * 3] {
+            assert!((h - 0.4).abs() < 1e-6, "{h}");
+        }
+        assert!(heat[6 * 3..].iter().all(|&h| h == 0.5));
+        assert!(total(&heat) < before);
+    }
+
+    #[test]
+    fn test_heat_rises_from_the_bottom() {
+        let mut heat = vec![0.0; 5 * 3];
+        heat[5 * 2 + 2] = 1.0;
+
+        diffuse(&mut heat, 5, 0.0);
+        // One step reaches only the row directly above
+        assert_eq!(&heat[..5], &[0.0; 5]);
+        assert_eq!(&heat[5..10], &[0.0, 0.25, 0.25, 0.25, 0.0]);
+
+        diffuse(&mut heat, 5, 0.0);
+        assert!(heat[2] > 0.0);
+        // Heat spreads but never exceeds its source
+        assert!(heat.iter().all(|&h| h <= 1.0));
+    }
+
+    #[test]
+    fn test_flames_stay_in_the_bottom_of_the_screen() {
+        let (width, height) = (40, 30);
+        let mut state = init_fire(width, height, 42);
+        update_fire(&mut state, 10_000, AnimationSpeed::Fast);
+        for _ in 0..50 {
+            update_fire(&mut state, 100, AnimationSpeed::Fast);
+        }
+
+        let top = height - state.rows as u16;
+        assert!(top as f32 >= height as f32 * (1.0 - FIRE_HEIGHT_RATIO) - 1.0);
+        for y in 0..top {
+            for x in 0..width {
+                assert_eq!(render_fire_char(&state, x, y, height), None);
+            }
+        }
+        assert!((0..width).any(|x| render_fire_char(&state, x, height - 1, height).is_some()));
+    }
+}
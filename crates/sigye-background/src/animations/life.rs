@@ -0,0 +1,275 @@
+//! Conway's Game of Life animation (stateful cellular automaton).
+
+use ratatui::style::Color;
+use sigye_core::AnimationSpeed;
+
+use crate::animations::{Glyph, next_random};
+use crate::chars::LIFE_CHARS;
+use crate::color::hsl_to_rgb;
+
+/// Generations with an unchanged population before the board is reseeded.
+pub const STAGNATION_GENERATIONS: u32 = 50;
+
+/// Chance of a cell starting alive when the board is seeded, in percent.
+const SEED_DENSITY_PERCENT: u64 = 30;
+
+/// Age in generations at which a cell reaches its dimmest color.
+const OLD_AGE: u16 = 24;
+
+/// Maximum generations per update, so a long pause doesn't stall a frame.
+const MAX_STEPS_PER_UPDATE: u64 = 8;
+
+/// Game of Life board covering the whole screen, wrapping at the edges.
+#[derive(Debug, Clone)]
+pub struct LifeState {
+    /// Whether each cell is alive, row by row from the top.
+    pub cells: Vec<bool>,
+    /// Generations each live cell has survived (0 for newborn or dead cells).
+    pub ages: Vec<u16>,
+    /// Width of the board in cells.
+    pub width: usize,
+    /// Height of the board in cells.
+    pub height: usize,
+    /// Population after the previous generation.
+    pub population: usize,
+    /// Consecutive generations the population has stayed the same.
+    pub stable_generations: u32,
+    /// Time not yet consumed by a generation step.
+    pub pending_ms: u64,
+    /// Random state for reseeding the board.
+    pub seed: u64,
+}
+
+/// Initialize a randomly seeded board for the given dimensions.
+pub fn init_life(width: u16, height: u16, init_seed: u64) -> LifeState {
+    let width = width as usize;
+    let height = height as usize;
+    let mut state = LifeState {
+        cells: vec![false; width * height],
+        ages: vec![0; width * height],
+        width,
+        height,
+        population: 0,
+        stable_generations: 0,
+        pending_ms: 0,
+        // Xorshift needs a non-zero state
+        seed: init_seed | 1,
+    };
+    reseed(&mut state);
+    state
+}
+
+/// Advance the board by as many generations as the elapsed time allows.
+///
+/// The board is reseeded once everything has died or the population has
+/// stayed the same for [`STAGNATION_GENERATIONS`] generations.
+pub fn update_life(state: &mut LifeState, delta_ms: u64, speed: AnimationSpeed) {
+    if state.cells.is_empty() {
+        return;
+    }
+
+    let step_ms = speed.life_step_ms();
+    state.pending_ms += delta_ms;
+
+    let steps = (state.pending_ms / step_ms).min(MAX_STEPS_PER_UPDATE);
+    state.pending_ms %= step_ms;
+
+    for _ in 0..steps {
+        let next = step(&state.cells, state.width, state.height);
+        for (age, &alive) in state.ages.iter_mut().zip(&next) {
+            *age = if alive { age.saturating_add(1) } else { 0 };
+        }
+        state.cells = next;
+
+        let population = state.cells.iter().filter(|&&alive| alive).count();
+        if population == state.population {
+            state.stable_generations += 1;
+        } else {
+            state.stable_generations = 0;
+        }
+        state.population = population;
+
+        if population == 0 || state.stable_generations >= STAGNATION_GENERATIONS {
+            reseed(state);
+        }
+    }
+}
+
+/// Compute the next generation of a board that wraps at the edges.
+///
+/// `cells` holds `width * height` cells row by row. A live cell survives with
+/// two or three live neighbours, and a dead cell is born with exactly three.
+pub fn step(cells: &[bool], width: usize, height: usize) -> Vec<bool> {
+    let mut next = vec![false; cells.len()];
+    if width == 0 || height == 0 {
+        return next;
+    }
+
+    for y in 0..height {
+        // Neighbouring rows and columns, wrapped around the board
+        let rows = [(y + height - 1) % height, y, (y + 1) % height];
+        for x in 0..width {
+            let columns = [(x + width - 1) % width, x, (x + 1) % width];
+            let mut neighbours = 0;
+            for (i, &row) in rows.iter().enumerate() {
+                for (j, &column) in columns.iter().enumerate() {
+                    if (i, j) != (1, 1) && cells[row * width + column] {
+                        neighbours += 1;
+                    }
+                }
+            }
+
+            let alive = cells[y * width + x];
+            next[y * width + x] = neighbours == 3 || (alive && neighbours == 2);
+        }
+    }
+    next
+}
+
+/// Fill the board with random live cells.
+fn reseed(state: &mut LifeState) {
+    for (cell, age) in state.cells.iter_mut().zip(&mut state.ages) {
+        *cell = next_random(&mut state.seed) % 100 < SEED_DENSITY_PERCENT;
+        *age = 0;
+    }
+    state.population = state.cells.iter().filter(|&&alive| alive).count();
+    state.stable_generations = 0;
+}
+
+/// Render a Game of Life character.
+pub fn render_life_char(state: &LifeState, x: u16, y: u16) -> Option<Glyph> {
+    let (x, y) = (x as usize, y as usize);
+    if x >= state.width || y >= state.height {
+        return None;
+    }
+
+    let idx = y * state.width + x;
+    if !state.cells[idx] {
+        return None;
+    }
+
+    let age = state.ages[idx].min(OLD_AGE);
+    let char_idx = age as usize * LIFE_CHARS.len() / (OLD_AGE as usize + 1);
+    Some(Glyph::new(LIFE_CHARS[char_idx], life_color(age)))
+}
+
+/// Map cell age to a color: newborn cells are bright, old ones fade to dim teal.
+fn life_color(age: u16) -> Color {
+    let maturity = age.min(OLD_AGE) as f32 / OLD_AGE as f32;
+    let hue = 140.0 + 40.0 * maturity;
+    let saturation = 0.7 - 0.3 * maturity;
+    let lightness = 0.55 - 0.35 * maturity;
+    hsl_to_rgb(hue, saturation, lightness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a board from rows of `#` (alive) and `.` (dead).
+    fn board(rows: &[&str]) -> (Vec<bool>, usize, usize) {
+        let cells = rows
+            .iter()
+            .flat_map(|row| row.chars().map(|c| c == '#'))
+            .collect();
+        (cells, rows[0].len(), rows.len())
+    }
+
+    fn one_generation_ms() -> u64 {
+        AnimationSpeed::Medium.life_step_ms()
+    }
+
+    #[test]
+    fn test_blinker_oscillates() {
+        let (horizontal, width, height) = board(&[".....", ".....", ".###.", ".....", "....."]);
+        let (vertical, ..) = board(&[".....", "..#..", "..#..", "..#..", "....."]);
+
+        let next = step(&horizontal, width, height);
+        assert_eq!(next, vertical);
+        assert_eq!(step(&next, width, height), horizontal);
+    }
+
+    #[test]
+    fn test_block_is_still() {
+        let (block, width, height) = board(&["....", ".##.", ".##.", "...."]);
+        assert_eq!(step(&block, width, height), block);
+    }
+
+    #[test]
+    fn test_glider_moves_diagonally() {
+        let (mut cells, width, height) =
+            board(&[".#....", "..#...", "###...", "......", "......", "......"]);
+        for _ in 0..4 {
+            cells = step(&cells, width, height);
+        }
+
+        let (moved, ..) = board(&["......", "..#...", "...#..", ".###..", "......", "......"]);
+        assert_eq!(cells, moved);
+    }
+
+    #[test]
+    fn test_edges_wrap_around() {
+        // A blinker split across the left and right edges
+        let (split, width, height) = board(&[".....", ".....", "##..#", ".....", "....."]);
+        let (vertical, ..) = board(&[".....", "#....", "#....", "#....", "....."]);
+        assert_eq!(step(&split, width, height), vertical);
+
+        // A glider crosses the corner and returns after travelling the full board
+        let (glider, width, height) =
+            board(&["......", "......", "......", "....#.", ".....#", "...###"]);
+        let mut cells = glider.clone();
+        for _ in 0..4 * width {
+            cells = step(&cells, width, height);
+        }
+        assert_eq!(cells, glider);
+    }
+
+    #[test]
+    fn test_dead_board_is_reseeded() {
+        let mut state = init_life(20, 10, 42);
+        state.cells.fill(false);
+        state.population = 0;
+
+        update_life(&mut state, one_generation_ms(), AnimationSpeed::Medium);
+        assert!(state.population > 0);
+        assert!(state.ages.iter().all(|&age| age == 0));
+    }
+
+    #[test]
+    fn test_stagnant_board_is_reseeded() {
+        let mut state = init_life(8, 8, 42);
+        state.cells.fill(false);
+        // A lone block never changes
+        for idx in [9, 10, 17, 18] {
+            state.cells[idx] = true;
+        }
+        state.population = 4;
+
+        for _ in 0..STAGNATION_GENERATIONS - 1 {
+            update_life(&mut state, one_generation_ms(), AnimationSpeed::Medium);
+        }
+        assert_eq!(state.population, 4);
+        assert_eq!(state.ages[9], (STAGNATION_GENERATIONS - 1) as u16);
+
+        update_life(&mut state, one_generation_ms(), AnimationSpeed::Medium);
+        assert_eq!(state.stable_generations, 0);
+        assert!(state.ages.iter().all(|&age| age == 0));
+    }
+
+    #[test]
+    fn test_old_cells_are_dimmer() {
+        let mut state = init_life(3, 3, 42);
+        state.cells = vec![true; 9];
+        state.ages[0] = 0;
+        state.ages[1] = OLD_AGE;
+
+        let newborn = render_life_char(&state, 0, 0).unwrap();
+        let old = render_life_char(&state, 1, 0).unwrap();
+        let brightness = |glyph: Glyph| match glyph.fg {
+            Color::Rgb(r, g, b) => r as u32 + g as u32 + b as u32,
+            _ => 0,
+        };
+        assert!(brightness(newborn) > brightness(old));
+        assert_eq!(render_life_char(&state, 3, 0), None);
+    }
+}
@@ -1,13 +1,15 @@
 //! Matrix rain animation (stateful).
 
-use ratatui::{
-    style::{Color, Style},
-    text::Span,
-};
+use ratatui::style::Color;
 use sigye_core::AnimationSpeed;
 
+use crate::animations::{Glyph, mix, next_random};
 use crate::chars::MATRIX_CHARS;
 
+/// One in this many column resets spells out the time: on an 80x24 terminal
+/// at medium speed, about every 15 seconds.
+const MESSAGE_ODDS: u64 = 240;
+
 /// State for a single matrix rain column.
 #[derive(Debug, Clone)]
 pub struct MatrixColumn {
@@ -19,30 +21,73 @@ pub struct MatrixColumn {
     pub trail_length: usize,
     /// Seed for character generation.
     pub char_seed: usize,
+    /// Characters spelled out above the head instead of random glyphs, if any.
+    pub message: Vec<char>,
 }
 
 /// Initialize matrix columns for the given dimensions.
-pub fn init_columns(width: u16, height: u16) -> Vec<MatrixColumn> {
-    (0..width)
-        .map(|x| {
-            let x = x as usize;
-            let stagger = ((x * 7 + 3) % (height as usize * 2)) as f32;
-            MatrixColumn {
-                // Stagger start positions so columns don't all start at top
-                y: -stagger,
-                // Vary speeds between columns
-                speed: 0.3 + ((x * 13) % 10) as f32 / 15.0,
-                // Vary trail lengths
-                trail_length: 4 + (x * 11) % 8,
-                // Seed for character selection
-                char_seed: x * 17,
-            }
-        })
+pub fn init_columns(width: u16, height: u16, init_seed: u64) -> Vec<MatrixColumn> {
+    (0..width as usize)
+        .map(|x| column(x, height, init_seed))
         .collect()
 }
 
+/// Fit existing columns to resized dimensions without restarting them.
+pub fn resize_columns(
+    columns: &mut Vec<MatrixColumn>,
+    width: u16,
+    old_height: u16,
+    height: u16,
+    init_seed: u64,
+) {
+    super::resize_columns(
+        columns,
+        width,
+        old_height,
+        height,
+        |col| &mut col.y,
+        |x| column(x, height, init_seed),
+    );
+}
+
+/// Create the column at index `x`.
+fn column(x: usize, height: u16, init_seed: u64) -> MatrixColumn {
+    let random = |salt| mix(init_seed, x as u64, salt);
+    let stagger = (random(0) % (u64::from(height) * 2).max(1)) as f32;
+    MatrixColumn {
+        // Stagger start positions so columns don't all start at top
+        y: -stagger,
+        // Vary speeds between columns
+        speed: 0.3 + (random(1) % 10) as f32 / 15.0,
+        // Vary trail lengths
+        trail_length: 4 + (random(2) % 8) as usize,
+        // Seed for character selection
+        char_seed: random(3) as usize,
+        message: Vec::new(),
+    }
+}
+
+/// Check if a column reset with `char_seed` carries a message.
+///
+/// Deterministic from the seed, so the same column run always does or doesn't.
+pub fn carries_message(char_seed: usize) -> bool {
+    let mut state = char_seed as u64 | 1;
+    // A couple of rounds so neighbouring seeds land far apart
+    next_random(&mut state);
+    next_random(&mut state).is_multiple_of(MESSAGE_ODDS)
+}
+
 /// Update matrix column positions.
-pub fn update(columns: &mut [MatrixColumn], delta_ms: u64, height: u16, speed: AnimationSpeed) {
+///
+/// A column that starts over occasionally spells out `message`, e.g. the
+/// digits of the time, when its trail is long enough to hold it.
+pub fn update(
+    columns: &mut [MatrixColumn],
+    delta_ms: u64,
+    height: u16,
+    speed: AnimationSpeed,
+    message: &[char],
+) {
     let fall_speed = speed.matrix_fall_speed();
     let delta_y = (delta_ms as f32 / 50.0) * fall_speed;
 
@@ -52,17 +97,24 @@ pub fn update(columns: &mut [MatrixColumn], delta_ms: u64, height: u16, speed: A
         if col.y > (height as f32 + col.trail_length as f32) {
             col.y = -(col.trail_length as f32);
             col.char_seed = col.char_seed.wrapping_add(1);
+            col.message.clear();
+            if !message.is_empty()
+                && message.len() < col.trail_length
+                && carries_message(col.char_seed)
+            {
+                col.message.extend_from_slice(message);
+            }
         }
     }
 }
 
 /// Render a matrix rain character.
-pub fn render_char(columns: &[MatrixColumn], x: u16, y: u16) -> Span<'static> {
+pub fn render_char(columns: &[MatrixColumn], x: u16, y: u16) -> Option<Glyph> {
     let x = x as usize;
     let y = y as f32;
 
     if x >= columns.len() {
-        return Span::raw(" ");
+        return None;
     }
 
     let col = &columns[x];
@@ -74,6 +126,13 @@ pub fn render_char(columns: &[MatrixColumn], x: u16, y: u16) -> Span<'static> {
         let distance_from_head = head_y - y;
         let intensity = 1.0 - (distance_from_head / col.trail_length as f32);
 
+        // The message sits in the rows right above the head, read top to bottom
+        let rows_above_head = distance_from_head as usize;
+        if (1..=col.message.len()).contains(&rows_above_head) {
+            let ch = col.message[col.message.len() - rows_above_head];
+            return Some(Glyph::new(ch, Color::Rgb(170, 255, 170)));
+        }
+
         // Select character based on position and seed
         let char_idx = (col.char_seed.wrapping_add(y as usize)) % MATRIX_CHARS.len();
         let ch = MATRIX_CHARS[char_idx];
@@ -86,8 +145,97 @@ pub fn render_char(columns: &[MatrixColumn], x: u16, y: u16) -> Span<'static> {
             Color::Rgb(0, g, 0)
         };
 
-        Span::styled(ch.to_string(), Style::new().fg(color))
+        Some(Glyph::new(ch, color))
     } else {
-        Span::raw(" ")
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_column(columns: &[MatrixColumn], height: u16) -> Vec<Option<char>> {
+        (0..height)
+            .map(|y| render_char(columns, 0, y).map(|glyph| glyph.ch))
+            .collect()
+    }
+
+    #[test]
+    fn test_message_renders_above_the_head() {
+        let mut columns = init_columns(1, 12, 7);
+        columns[0].y = 8.0;
+        columns[0].trail_length = 7;
+        columns[0].message = "2147".chars().collect();
+
+        let rows = render_column(&columns, 12);
+        assert_eq!(rows[4..8], [Some('2'), Some('1'), Some('4'), Some('7')]);
+        // The head and the rest of the trail stay random glyphs
+        for y in [1, 2, 3, 8] {
+            assert!(
+                rows[y].is_some_and(|ch| MATRIX_CHARS.contains(&ch)),
+                "row {y}"
+            );
+        }
+        assert_eq!(rows[0], None);
+        assert_eq!(rows[9], None);
+
+        // Brighter than the trail around it
+        let green = |y| match render_char(&columns, 0, y) {
+            Some(Glyph {
+                fg: Color::Rgb(_, g, _),
+                ..
+            }) => g,
+            _ => 0,
+        };
+        assert!(green(7) > green(3) && green(4) > green(3));
+    }
+
+    #[test]
+    fn test_message_moves_with_the_head() {
+        let mut columns = init_columns(1, 12, 7);
+        columns[0].y = 3.0;
+        columns[0].trail_length = 7;
+        columns[0].message = "0915".chars().collect();
+
+        // Partly above the top of the area
+        let rows = render_column(&columns, 12);
+        assert_eq!(rows[0..3], [Some('9'), Some('1'), Some('5')]);
+    }
+
+    #[test]
+    fn test_messages_are_assigned_deterministically() {
+        let run = || {
+            let mut columns = init_columns(80, 24, 42);
+            let digits: Vec<char> = "214705".chars().collect();
+            let mut messages = Vec::new();
+            for _ in 0..(60_000 / 50) {
+                update(&mut columns, 50, 24, AnimationSpeed::Medium, &digits);
+                for (x, col) in columns.iter().enumerate() {
+                    if !col.message.is_empty() && col.y < 0.0 {
+                        assert_eq!(col.message, digits);
+                        assert!(col.trail_length > digits.len());
+                        assert!(carries_message(col.char_seed));
+                        messages.push((x, col.char_seed));
+                    }
+                }
+            }
+            messages.sort_unstable();
+            messages.dedup();
+            messages
+        };
+        let messages = run();
+        assert_eq!(messages, run());
+        // Every 10-20 seconds or so over a minute
+        assert!((2..=12).contains(&messages.len()), "{messages:?}");
+    }
+
+    #[test]
+    fn test_no_message_without_digits() {
+        let mut columns = init_columns(80, 24, 7);
+        for _ in 0..(60_000 / 50) {
+            update(&mut columns, 50, 24, AnimationSpeed::Fast, &[]);
+            assert!(columns.iter().all(|col| col.message.is_empty()));
+        }
     }
 }
@@ -0,0 +1,297 @@
+//! Shooting stars streaking across the starfield (stateful).
+
+use ratatui::style::Color;
+use sigye_core::AnimationSpeed;
+
+use crate::animations::{Glyph, next_random};
+use crate::color::scale_color;
+
+/// How long a meteor takes to cross its path.
+pub const METEOR_DURATION_MS: u64 = 600;
+
+/// Narrowest area that meteors spawn in.
+pub const MIN_METEOR_WIDTH: u16 = 10;
+
+/// Shortest and longest tail, in cells including the head.
+const TAIL_LENGTH: (u16, u16) = (4, 8);
+
+/// Rows a meteor drops per column it moves; cells are about twice as tall as
+/// wide, so this is a 45 degree streak.
+const SLOPE: f32 = 0.5;
+
+/// A single shooting star.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meteor {
+    /// Time the meteor appeared.
+    pub spawn_ms: u64,
+    /// Column it started from.
+    pub x: i32,
+    /// Row it started from.
+    pub y: i32,
+    /// Columns per millisecond, negative when moving left.
+    pub velocity: f32,
+    /// Tail length in cells, including the head.
+    pub length: u16,
+}
+
+impl Meteor {
+    /// Columns the head has moved from the origin at `elapsed_ms`.
+    fn head_offset(&self, elapsed_ms: u64) -> i32 {
+        let age = elapsed_ms
+            .saturating_sub(self.spawn_ms)
+            .min(METEOR_DURATION_MS);
+        (self.velocity.abs() * age as f32) as i32
+    }
+
+    /// Check if the meteor has finished crossing at `elapsed_ms`.
+    fn is_done(&self, elapsed_ms: u64) -> bool {
+        elapsed_ms.saturating_sub(self.spawn_ms) >= METEOR_DURATION_MS
+    }
+}
+
+/// Shooting stars in flight and when the next one appears.
+#[derive(Debug, Clone)]
+pub struct MeteorState {
+    /// Meteors currently crossing the screen.
+    pub meteors: Vec<Meteor>,
+    /// Time of the next spawn, scheduled on the first update.
+    pub next_spawn_ms: Option<u64>,
+    /// Random state for spawn times and paths.
+    pub seed: u64,
+}
+
+/// Initialize meteor state with no meteors in flight.
+pub fn init_meteors(init_seed: u64) -> MeteorState {
+    MeteorState {
+        meteors: Vec::new(),
+        next_spawn_ms: None,
+        // Xorshift needs a non-zero state
+        seed: init_seed | 1,
+    }
+}
+
+/// Drop finished meteors and spawn a new one when it is due.
+///
+/// Meteors appear every [`AnimationSpeed::meteor_interval_ms`], but never in
+/// areas narrower than [`MIN_METEOR_WIDTH`].
+pub fn update_meteors(
+    state: &mut MeteorState,
+    elapsed_ms: u64,
+    width: u16,
+    height: u16,
+    speed: AnimationSpeed,
+) {
+    state.meteors.retain(|meteor| !meteor.is_done(elapsed_ms));
+
+    let next_spawn_ms = *state
+        .next_spawn_ms
+        .get_or_insert_with(|| elapsed_ms + spawn_interval(&mut state.seed, speed));
+    if elapsed_ms < next_spawn_ms {
+        return;
+    }
+    if width >= MIN_METEOR_WIDTH && height > 0 {
+        let meteor = spawn_meteor(&mut state.seed, elapsed_ms, width, height);
+        state.meteors.push(meteor);
+    }
+    state.next_spawn_ms = Some(elapsed_ms + spawn_interval(&mut state.seed, speed));
+}
+
+/// Random time until the next meteor.
+fn spawn_interval(seed: &mut u64, speed: AnimationSpeed) -> u64 {
+    let (min_interval, max_interval) = speed.meteor_interval_ms();
+    min_interval + next_random(seed) % (max_interval - min_interval + 1)
+}
+
+/// Create a meteor in the top half, crossing a third of the width downwards.
+fn spawn_meteor(seed: &mut u64, elapsed_ms: u64, width: u16, height: u16) -> Meteor {
+    let x = (next_random(seed) % width as u64) as i32;
+    let y = (next_random(seed) % (height as u64).div_ceil(2)) as i32;
+    // Head towards the wider side so most of the streak is on screen
+    let direction = if x < i32::from(width / 2) { 1.0 } else { -1.0 };
+    let travel = f32::from((width / 3).clamp(6, 40));
+    let (min_length, max_length) = TAIL_LENGTH;
+    let length = min_length + (next_random(seed) % u64::from(max_length - min_length + 1)) as u16;
+    Meteor {
+        spawn_ms: elapsed_ms,
+        x,
+        y,
+        velocity: direction * travel / METEOR_DURATION_MS as f32,
+        length,
+    }
+}
+
+/// Render the meteor cell at a position, if a head or tail covers it.
+///
+/// The tail fades from the head back, and the whole meteor dims as it ends.
+/// Cells past the edges are never asked for, so meteors clip at the edges.
+pub fn render_meteor_char(state: &MeteorState, x: u16, y: u16, elapsed_ms: u64) -> Option<Glyph> {
+    state.meteors.iter().find_map(|meteor| {
+        if meteor.is_done(elapsed_ms) || elapsed_ms < meteor.spawn_ms {
+            return None;
+        }
+        // Columns moved from the origin to this cell, along the direction of travel
+        let offset = (i32::from(x) - meteor.x) * meteor.velocity.signum() as i32;
+        let behind = meteor.head_offset(elapsed_ms) - offset;
+        if offset < 0 || behind < 0 || behind >= i32::from(meteor.length) {
+            return None;
+        }
+        if meteor.y + (offset as f32 * SLOPE).round() as i32 != i32::from(y) {
+            return None;
+        }
+
+        let progress = (elapsed_ms - meteor.spawn_ms) as f32 / METEOR_DURATION_MS as f32;
+        let tail_fade = 1.0 - behind as f32 / f32::from(meteor.length);
+        let ch = match behind {
+            0 => '✦',
+            1 | 2 => '•',
+            _ => '·',
+        };
+        let color = scale_color(
+            Color::Rgb(225, 230, 255),
+            tail_fade * (1.0 - 0.6 * progress),
+        );
+        Some(Glyph::new(ch, color))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Times at which meteors spawn over a minute, updating every 50ms.
+    fn spawn_times(seed: u64, width: u16, speed: AnimationSpeed) -> Vec<u64> {
+        let mut state = init_meteors(seed);
+        let mut spawns = Vec::new();
+        for elapsed_ms in (0..60_000).step_by(50) {
+            let before = state.meteors.len();
+            update_meteors(&mut state, elapsed_ms, width, 20, speed);
+            if state.meteors.len() > before {
+                spawns.push(elapsed_ms);
+            }
+        }
+        spawns
+    }
+
+    /// Rasterize the meteors at a moment: '@' head, '#' tail.
+    fn render(state: &MeteorState, width: u16, height: u16, elapsed_ms: u64) -> Vec<String> {
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| match render_meteor_char(state, x, y, elapsed_ms) {
+                        Some(glyph) if glyph.ch == '✦' => '@',
+                        Some(_) => '#',
+                        None => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn meteor(x: i32, y: i32, velocity: f32, length: u16) -> MeteorState {
+        MeteorState {
+            meteors: vec![Meteor {
+                spawn_ms: 1000,
+                x,
+                y,
+                velocity,
+                length,
+            }],
+            next_spawn_ms: None,
+            seed: 1,
+        }
+    }
+
+    #[test]
+    fn test_spawns_are_deterministic() {
+        let spawns = spawn_times(42, 60, AnimationSpeed::Medium);
+        assert_eq!(spawns, spawn_times(42, 60, AnimationSpeed::Medium));
+        assert_ne!(spawns, spawn_times(44, 60, AnimationSpeed::Medium));
+
+        // Gaps stay within the range for the speed
+        assert!(spawns.len() >= 2, "{spawns:?}");
+        let (min_interval, max_interval) = AnimationSpeed::Medium.meteor_interval_ms();
+        for gap in spawns.windows(2).map(|pair| pair[1] - pair[0]) {
+            assert!((min_interval..=max_interval + 50).contains(&gap), "{gap}");
+        }
+        assert!(spawns[0] >= min_interval);
+    }
+
+    #[test]
+    fn test_paths_are_deterministic() {
+        let run = |seed| {
+            let mut state = init_meteors(seed);
+            let mut meteors: Vec<Meteor> = Vec::new();
+            for elapsed_ms in (0..120_000).step_by(100) {
+                update_meteors(&mut state, elapsed_ms, 80, 24, AnimationSpeed::Fast);
+                meteors.extend(state.meteors.iter().filter(|m| m.spawn_ms == elapsed_ms));
+            }
+            meteors
+        };
+        let meteors = run(7);
+        assert_eq!(meteors, run(7));
+        for meteor in &meteors {
+            assert!((0..80).contains(&meteor.x) && (0..12).contains(&meteor.y));
+            assert!((4..=8).contains(&meteor.length));
+        }
+    }
+
+    #[test]
+    fn test_faster_speed_spawns_more() {
+        let slow = spawn_times(5, 60, AnimationSpeed::Slow).len();
+        let fast = spawn_times(5, 60, AnimationSpeed::Fast).len();
+        assert!(fast > slow, "{fast} <= {slow}");
+    }
+
+    #[test]
+    fn test_no_meteors_on_narrow_terminals() {
+        assert!(spawn_times(42, MIN_METEOR_WIDTH - 1, AnimationSpeed::Fast).is_empty());
+        assert!(!spawn_times(42, MIN_METEOR_WIDTH, AnimationSpeed::Fast).is_empty());
+    }
+
+    #[test]
+    fn test_streak_with_fading_tail() {
+        // 12 columns in 600ms, right and down; halfway through the head is 6 along
+        let state = meteor(2, 1, 0.02, 4);
+        assert_eq!(
+            render(&state, 16, 6, 1300),
+            [
+                "                ",
+                "                ",
+                "                ",
+                "     ##         ",
+                "       #@       ",
+                "                ",
+            ]
+        );
+        let brightness = |x, y| match render_meteor_char(&state, x, y, 1300) {
+            Some(Glyph {
+                fg: Color::Rgb(r, _, _),
+                ..
+            }) => r,
+            _ => 0,
+        };
+        assert!(brightness(8, 4) > brightness(7, 4));
+        assert!(brightness(7, 4) > brightness(5, 3));
+
+        // Gone once it has crossed, and not there before it appears
+        assert!(render(&state, 16, 6, 1600).concat().trim().is_empty());
+        assert!(render(&state, 16, 6, 999).concat().trim().is_empty());
+    }
+
+    #[test]
+    fn test_clips_at_the_edges() {
+        // The head has already left past the left edge
+        let state = meteor(3, 0, -0.02, 8);
+        let lines = render(&state, 10, 4, 1300);
+        assert_eq!(
+            lines,
+            ["   #      ", " ##       ", "#         ", "          "]
+        );
+        // And past the bottom
+        assert_eq!(render(&state, 10, 2, 1300), ["   #      ", " ##       "]);
+
+        // Off to the right of a narrow area: nothing drawn, nothing out of bounds
+        let state = meteor(20, 0, 0.02, 8);
+        assert!(render(&state, 10, 4, 1300).concat().trim().is_empty());
+    }
+}
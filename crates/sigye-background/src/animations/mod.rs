@@ -1,6 +1,141 @@
 //! Animation rendering modules.
 
+pub mod constellation;
+pub mod fire;
+pub mod life;
 pub mod matrix;
+pub mod meteor;
+pub mod moon;
 pub mod reactive;
 pub mod stateless;
 pub mod weather;
+
+use ratatui::{buffer::Buffer, style::Color};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A single rendered background character with its foreground color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    /// Character to draw.
+    pub ch: char,
+    /// Foreground color.
+    pub fg: Color,
+}
+
+impl Glyph {
+    /// Create a new glyph.
+    pub const fn new(ch: char, fg: Color) -> Self {
+        Self { ch, fg }
+    }
+}
+
+/// Write a glyph into the buffer without allocating.
+///
+/// `None` and blank glyphs are transparent and leave the cell untouched, so
+/// layered backgrounds show through. Returns the number of columns consumed,
+/// so callers can skip the cells covered by wide characters. Glyphs wider
+/// than `max_width` are not drawn, and a wide glyph from a lower layer that
+/// the new glyph would cut in half is cleared, so rows never overflow.
+pub fn put_glyph(buf: &mut Buffer, x: u16, y: u16, glyph: Option<Glyph>, max_width: u16) -> u16 {
+    let Some(glyph) = glyph.filter(|glyph| glyph.ch != ' ') else {
+        return 1;
+    };
+
+    let width = glyph.ch.width().unwrap_or(1).max(1) as u16;
+    if width > max_width {
+        return 1;
+    }
+
+    if let Some(left) = x.checked_sub(1).and_then(|left| buf.cell_mut((left, y)))
+        && left.symbol().width() > 1
+    {
+        left.reset();
+    }
+
+    if let Some(cell) = buf.cell_mut((x, y)) {
+        cell.set_char(glyph.ch).set_fg(glyph.fg);
+    }
+    // Reset cells hidden behind a wide character
+    for offset in 1..width {
+        if let Some(cell) = buf.cell_mut((x + offset, y)) {
+            cell.reset();
+        }
+    }
+    width
+}
+
+/// Fit per-column state to a new width, keeping the existing columns moving.
+///
+/// Columns past `width` are dropped and missing ones are built with
+/// `new_column(x)`. The `y` position of each kept column is scaled by the
+/// height change, so drops stay at the same relative height.
+pub fn resize_columns<T>(
+    columns: &mut Vec<T>,
+    width: u16,
+    old_height: u16,
+    height: u16,
+    y: impl Fn(&mut T) -> &mut f32,
+    new_column: impl Fn(usize) -> T,
+) {
+    let scale = scale_factor(old_height, height);
+    columns.truncate(width as usize);
+    for column in columns.iter_mut() {
+        *y(column) *= scale;
+    }
+    let kept = columns.len();
+    columns.extend((kept..width as usize).map(new_column));
+}
+
+/// Factor to scale positions along an axis by when its size changes.
+pub fn scale_factor(old_size: u16, size: u16) -> f32 {
+    if old_size == 0 {
+        1.0
+    } else {
+        size as f32 / old_size as f32
+    }
+}
+
+/// Mix a seed and two values, e.g. a cell position, into a pseudo-random number.
+///
+/// Every animation draws its per-cell and per-column randomness from this, so
+/// the same inputs always give the same number and nearby inputs land far apart.
+pub fn mix(seed: u64, a: u64, b: u64) -> u64 {
+    // SplitMix64 finalizer over the combined inputs
+    let mut z = seed
+        .wrapping_add(a.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .wrapping_add(b.wrapping_mul(0xc2b2_ae3d_27d4_eb4f));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Xorshift64 step for animations that keep their own random state.
+///
+/// The state must be non-zero.
+pub fn next_random(state: &mut u64) -> u64 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    *state = x;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_spreads_neighbours() {
+        assert_eq!(mix(7, 3, 4), mix(7, 3, 4));
+        assert_ne!(mix(7, 3, 4), mix(8, 3, 4));
+        assert_ne!(mix(7, 3, 4), mix(7, 4, 3));
+
+        // Roughly 3% of a grid of neighbouring cells, like the starfield
+        let hits = (0..100u64)
+            .flat_map(|x| (0..100u64).map(move |y| mix(0, x, y)))
+            .filter(|value| value % 100 < 3)
+            .count();
+        assert!((200..400).contains(&hits), "{hits}");
+    }
+}
@@ -0,0 +1,208 @@
+//! Moonlight: the moon in its current phase over a faint starfield.
+
+use ratatui::style::Color;
+use sigye_core::{AnimationSpeed, moon::moon_illumination};
+
+use crate::animations::Glyph;
+use crate::animations::stateless::render_starfield_char;
+use crate::color::scale_color;
+
+/// Cells are about twice as tall as they are wide, so the disc is twice as
+/// wide in columns as it is tall in rows.
+const CELL_ASPECT: f32 = 2.0;
+
+/// Margin added to the disc when placing the terminator.
+const RIM_MARGIN: f32 = 1e-3;
+
+/// Blank space kept around the disc, as a multiple of its radius.
+const HALO: f32 = 1.4;
+
+/// Whether a cell of the moon disc is sunlit or in shadow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonShade {
+    /// Sunlit part.
+    Lit,
+    /// Part in shadow, faintly lit by the Earth.
+    Dark,
+}
+
+/// Shade of the cell `dx` columns and `dy` rows from the center of a moon
+/// disc `radius` rows tall, at `phase` through the synodic cycle.
+///
+/// Returns `None` outside the disc. The waxing moon is lit from the right and
+/// the waning moon from the left, as seen from the northern hemisphere. The
+/// terminator is the half-ellipse where the lit part ends on each row.
+pub fn moon_shade(dx: f32, dy: f32, radius: f32, phase: f32) -> Option<MoonShade> {
+    if radius <= 0.0 {
+        return None;
+    }
+    let x = dx / (radius * CELL_ASPECT);
+    let y = dy / radius;
+    if x * x + y * y > 1.0 {
+        return None;
+    }
+    // Widened a little so cells on the rim count as lit at the full moon
+    let half_width = (1.0 - y * y).sqrt() + RIM_MARGIN;
+    let terminator = half_width * (phase * std::f32::consts::TAU).cos();
+    let lit = if phase < 0.5 {
+        x > terminator
+    } else {
+        x < -terminator
+    };
+    Some(if lit { MoonShade::Lit } else { MoonShade::Dark })
+}
+
+/// Radius in rows of the moon for an area, `None` when the area is too small for it.
+fn moon_radius(width: u16, height: u16) -> Option<f32> {
+    let radius = (height / 6).clamp(2, 5);
+    let fits = height >= 2 * radius + 3 && width >= 4 * radius + 6;
+    fits.then_some(radius as f32)
+}
+
+/// Render a moonlight character: the moon in the top-right corner, with
+/// stars that fade as the moon grows brighter.
+pub fn render_moonlight_char(
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    elapsed_ms: u64,
+    speed: AnimationSpeed,
+    phase: f32,
+) -> Option<Glyph> {
+    let illumination = moon_illumination(phase);
+
+    if let Some(radius) = moon_radius(width, height) {
+        // Center of the disc, a cell in from the corner
+        let center_x = width as f32 - radius * CELL_ASPECT - 2.0;
+        let center_y = radius + 1.0;
+        let dx = x as f32 - center_x;
+        let dy = y as f32 - center_y;
+
+        match moon_shade(dx, dy, radius, phase) {
+            Some(MoonShade::Lit) => {
+                // A full moon shines bright, a crescent is paler
+                let color = scale_color(Color::Rgb(240, 236, 210), 0.55 + 0.45 * illumination);
+                return Some(Glyph::new('█', color));
+            }
+            Some(MoonShade::Dark) => return Some(Glyph::new('░', Color::Rgb(38, 40, 58))),
+            None => {}
+        }
+
+        // Keep the sky around the moon clear
+        let halo_x = dx / (radius * CELL_ASPECT * HALO);
+        let halo_y = dy / (radius * HALO);
+        if halo_x * halo_x + halo_y * halo_y <= 1.0 {
+            return None;
+        }
+    }
+
+    // Half the stars of the starfield, dimmed by the moonlight
+    if (x as usize * 7 + y as usize * 13).is_multiple_of(2) {
+        return None;
+    }
+    render_starfield_char(x, y, elapsed_ms, speed).map(|glyph| Glyph {
+        fg: scale_color(glyph.fg, 0.9 - 0.5 * illumination),
+        ..glyph
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rasterize a disc of `radius` rows: '#' lit, '.' dark, ' ' outside.
+    fn disc(radius: u16, phase: f32) -> Vec<String> {
+        let r = radius as i32;
+        (-r..=r)
+            .map(|dy| {
+                (-2 * r..=2 * r)
+                    .map(
+                        |dx| match moon_shade(dx as f32, dy as f32, radius as f32, phase) {
+                            Some(MoonShade::Lit) => '#',
+                            Some(MoonShade::Dark) => '.',
+                            None => ' ',
+                        },
+                    )
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn count(lines: &[String], ch: char) -> usize {
+        lines.iter().map(|line| line.matches(ch).count()).sum()
+    }
+
+    #[test]
+    fn test_full_and_new_moon() {
+        assert_eq!(
+            disc(2, 0.5),
+            [
+                "    #    ",
+                " ####### ",
+                "#########",
+                " ####### ",
+                "    #    ",
+            ]
+        );
+        assert_eq!(count(&disc(3, 0.0), '#'), 0);
+        assert_eq!(count(&disc(3, 0.0), '.'), count(&disc(3, 0.5), '#'));
+    }
+
+    #[test]
+    fn test_quarters_light_opposite_halves() {
+        let first = disc(3, 0.25);
+        let last = disc(3, 0.75);
+        assert_eq!(first[3][7..], *"######");
+        assert_eq!(first[3][..6], *"......");
+        assert_eq!(last[3][..6], *"######");
+        assert_eq!(last[3][7..], *"......");
+        // Mirror images of each other, apart from the column on the terminator
+        for (first, last) in first.iter().zip(&last) {
+            let mirrored: String = last.chars().rev().collect();
+            assert_eq!(first[7..], mirrored[7..]);
+            assert_eq!(first[..6], mirrored[..6]);
+        }
+    }
+
+    #[test]
+    fn test_lit_part_grows_with_illumination() {
+        let lit = |phase| count(&disc(4, phase), '#');
+        let waxing: Vec<usize> = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5].map(lit).to_vec();
+        assert!(
+            waxing.windows(2).all(|pair| pair[0] < pair[1]),
+            "{waxing:?}"
+        );
+        let waning: Vec<usize> = [0.5, 0.6, 0.7, 0.8, 0.9].map(lit).to_vec();
+        assert!(
+            waning.windows(2).all(|pair| pair[0] > pair[1]),
+            "{waning:?}"
+        );
+        // A crescent is a thin sliver on the lit side
+        let crescent = disc(4, 0.08);
+        assert!(crescent.iter().all(|row| !row[..8].contains('#')));
+        assert!(crescent[4].ends_with('#'));
+    }
+
+    #[test]
+    fn test_moon_sits_in_the_top_right_corner() {
+        let (width, height) = (60, 18);
+        let lit: Vec<(u16, u16)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                render_moonlight_char(x, y, width, height, 0, AnimationSpeed::Medium, 0.5)
+                    .is_some_and(|glyph| glyph.ch == '█')
+            })
+            .collect();
+        assert!(!lit.is_empty());
+        assert!(lit.iter().all(|&(x, y)| x >= width / 2 && y < height / 2));
+
+        // Too small for the moon: only stars
+        for y in 0..4 {
+            for x in 0..10 {
+                let glyph = render_moonlight_char(x, y, 10, 4, 0, AnimationSpeed::Medium, 0.5);
+                assert!(glyph.is_none_or(|glyph| glyph.ch != '█'));
+            }
+        }
+    }
+}
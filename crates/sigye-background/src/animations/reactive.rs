@@ -1,23 +1,20 @@
 //! Reactive background animations that respond to system metrics.
 
-use ratatui::{
-    Frame,
-    style::Style,
-    text::{Line, Span},
-    widgets::Paragraph,
-};
-use sigye_core::{AnimationSpeed, SystemMetrics};
+use ratatui::{buffer::Buffer, layout::Rect};
+use sigye_core::{AnimationSpeed, SystemMetrics, history::MetricsHistory};
 
-use crate::color::resource_to_color;
+use crate::animations::{Glyph, mix, put_glyph};
+use crate::chars::LOWER_BLOCK_CHARS;
+use crate::color::{intensity_cell, resource_to_color, scale_color};
 
-/// Render system pulse background - CPU drives pulse rate and size.
+/// Render system pulse background - CPU drives pulse rate and size, low battery turns it red.
 pub fn render_system_pulse(
-    frame: &mut Frame,
+    area: Rect,
+    buf: &mut Buffer,
     elapsed_ms: u64,
     speed: AnimationSpeed,
     metrics: &SystemMetrics,
 ) {
-    let area = frame.area();
     let width = area.width as f32;
     let height = area.height as f32;
 
@@ -34,107 +31,88 @@ pub fn render_system_pulse(
     let phase = (elapsed_ms as f32 % period) / period;
     let pulse = (phase * 2.0 * std::f32::consts::PI).sin() * 0.5 + 0.5;
 
-    let color = resource_to_color(cpu);
+    // Tint fully red while running low on battery
+    let battery_low = metrics.is_battery_low();
 
     // Render pulsing effect from center
-    let lines: Vec<Line> = (0..area.height)
-        .map(|y| {
-            let spans: Vec<Span> = (0..area.width)
-                .map(|x| {
-                    let dx = x as f32 - width / 2.0;
-                    let dy = (y as f32 - height / 2.0) * 2.0; // Adjust for terminal aspect ratio
-                    let dist = (dx * dx + dy * dy).sqrt();
-                    let max_dist = (width * width / 4.0 + height * height).sqrt();
-                    let normalized = dist / max_dist;
-
-                    // Pulse expands from center
-                    let intensity = (1.0 - normalized) * pulse * (0.3 + cpu * 0.7);
-
-                    if intensity > 0.05 {
-                        let ch = if intensity > 0.6 {
-                            '█'
-                        } else if intensity > 0.4 {
-                            '▓'
-                        } else if intensity > 0.2 {
-                            '▒'
-                        } else if intensity > 0.1 {
-                            '░'
-                        } else {
-                            '·'
-                        };
-                        Span::styled(ch.to_string(), Style::new().fg(color))
-                    } else {
-                        Span::raw(" ")
-                    }
-                })
-                .collect();
-            Line::from(spans)
-        })
-        .collect();
-
-    frame.render_widget(Paragraph::new(lines), area);
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let glyph = {
+                let dx = x as f32 - width / 2.0;
+                let dy = (y as f32 - height / 2.0) * 2.0; // Adjust for terminal aspect ratio
+                let dist = (dx * dx + dy * dy).sqrt();
+                let max_dist = (width * width / 4.0 + height * height).sqrt().max(1.0);
+                let normalized = dist / max_dist;
+
+                // Pulse expands from center
+                let intensity = (1.0 - normalized) * pulse * (0.3 + cpu * 0.7);
+
+                // Rings run hot in the middle and cool towards the edges
+                let heat = if battery_low {
+                    1.0
+                } else {
+                    cpu * 0.6 + (1.0 - normalized) * 0.4
+                };
+                intensity_cell(intensity, heat).map(|(ch, color)| Glyph::new(ch, color))
+            };
+            put_glyph(buf, area.x + x, area.y + y, glyph, 1);
+        }
+    }
 }
 
 /// Render resource wave background - memory drives wave amplitude.
 pub fn render_resource_wave(
-    frame: &mut Frame,
+    area: Rect,
+    buf: &mut Buffer,
     elapsed_ms: u64,
     speed: AnimationSpeed,
     metrics: &SystemMetrics,
 ) {
-    let area = frame.area();
     let width = area.width as f32;
     let height = area.height as f32;
 
     // Memory controls wave amplitude
     let mem = metrics.memory_usage;
     let amplitude = mem * (height / 3.0);
-    let color = resource_to_color(mem);
 
     let period = speed.wave_period_ms();
     let time_phase = (elapsed_ms % period) as f32 / period as f32;
 
-    let lines: Vec<Line> = (0..area.height)
-        .map(|y| {
-            let spans: Vec<Span> = (0..area.width)
-                .map(|x| {
-                    let x_norm = x as f32 / width;
-                    let wave_y = (height / 2.0)
-                        + amplitude
-                            * ((x_norm * 4.0 + time_phase * 2.0 * std::f32::consts::PI).sin());
-
-                    let dist = (y as f32 - wave_y).abs();
-
-                    if dist < 3.0 {
-                        let ch = if dist < 0.5 {
-                            '█'
-                        } else if dist < 1.5 {
-                            '▓'
-                        } else {
-                            '░'
-                        };
-                        Span::styled(ch.to_string(), Style::new().fg(color))
-                    } else {
-                        Span::raw(" ")
-                    }
-                })
-                .collect();
-            Line::from(spans)
-        })
-        .collect();
-
-    frame.render_widget(Paragraph::new(lines), area);
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let glyph = {
+                let x_norm = x as f32 / width.max(1.0);
+                let wave_y = (height / 2.0)
+                    + amplitude * ((x_norm * 4.0 + time_phase * 2.0 * std::f32::consts::PI).sin());
+
+                let dist = (y as f32 - wave_y).abs();
+                let intensity = 1.0 - dist / 3.0;
+
+                // Crests run warmer than troughs
+                let height_factor = 1.0 - y as f32 / height.max(1.0);
+                let heat = mem * 0.6 + height_factor * 0.4;
+                intensity_cell(intensity, heat).map(|(ch, color)| Glyph::new(ch, color))
+            };
+            put_glyph(buf, area.x + x, area.y + y, glyph, 1);
+        }
+    }
 }
 
+/// Rows at the bottom of the Data Flow background given to the sparkline, at most.
+const SPARKLINE_ROWS: u16 = 3;
+
 /// Render data flow background - network I/O drives particle density and speed.
+///
+/// A faint sparkline of the last minute of throughput runs along the bottom
+/// rows, so short spikes stay visible after they pass.
 pub fn render_data_flow(
-    frame: &mut Frame,
+    area: Rect,
+    buf: &mut Buffer,
     elapsed_ms: u64,
     speed: AnimationSpeed,
     metrics: &SystemMetrics,
+    history: &MetricsHistory,
 ) {
-    let area = frame.area();
-
     // Network rate controls particle density and speed
     let net_combined = (metrics.network_rx_rate + metrics.network_tx_rate) / 2.0;
     let color = resource_to_color(net_combined);
@@ -146,44 +124,62 @@ pub fn render_data_flow(
     };
     let flow_speed = base_speed + net_combined * 2.0;
 
-    let lines: Vec<Line> = (0..area.height)
-        .map(|y| {
-            let spans: Vec<Span> = (0..area.width)
-                .map(|x| {
-                    // Flowing particles based on position and time
-                    let seed = (x as usize)
-                        .wrapping_mul(17)
-                        .wrapping_add((y as usize).wrapping_mul(31));
-                    let particle_phase =
-                        ((elapsed_ms as f32 * flow_speed / 100.0) + seed as f32) % 100.0;
-
-                    // Show particle if it's in the "visible" part of its cycle
-                    // Higher network = more particles visible
-                    let threshold = 95.0 - (net_combined * 70.0);
-                    if particle_phase > threshold && seed % 15 < 2 {
-                        let chars = ['·', '•', '○', '●'];
-                        let ch = chars[seed % chars.len()];
-                        Span::styled(ch.to_string(), Style::new().fg(color))
-                    } else {
-                        Span::raw(" ")
-                    }
-                })
-                .collect();
-            Line::from(spans)
-        })
-        .collect();
-
-    frame.render_widget(Paragraph::new(lines), area);
+    // Leave the sparkline out of short areas
+    let band_rows = SPARKLINE_ROWS.min(area.height / 4);
+    let sparkline = history.downsample(area.width as usize);
+
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let row_from_bottom = area.height - 1 - y;
+            let spark = (row_from_bottom < band_rows)
+                .then(|| sparkline_glyph(sparkline[x as usize], row_from_bottom, band_rows))
+                .flatten();
+            let glyph = spark.or_else(|| {
+                // Flowing particles based on position and time
+                let seed = mix(0, x.into(), y.into()) as usize;
+                let particle_phase =
+                    ((elapsed_ms as f32 * flow_speed / 100.0) + (seed % 100) as f32) % 100.0;
+
+                // Show particle if it's in the "visible" part of its cycle
+                // Higher network = more particles visible
+                let threshold = 95.0 - (net_combined * 70.0);
+                if particle_phase > threshold && seed % 15 < 2 {
+                    let chars = ['·', '•', '○', '●'];
+                    let ch = chars[seed % chars.len()];
+                    Some(Glyph::new(ch, color))
+                } else {
+                    None
+                }
+            });
+            put_glyph(buf, area.x + x, area.y + y, glyph, 1);
+        }
+    }
+}
+
+/// Glyph of a sparkline column `rows` tall, `row` rows up from its bottom.
+///
+/// Recorded columns always show at least a thin baseline; `None` columns have
+/// no samples yet and stay blank.
+fn sparkline_glyph(value: Option<f32>, row: u16, rows: u16) -> Option<Glyph> {
+    let value = value?.clamp(0.0, 1.0);
+    let steps = LOWER_BLOCK_CHARS.len();
+    let eighths = ((value * (usize::from(rows) * steps) as f32).round() as usize).max(1);
+    let level = eighths.saturating_sub(usize::from(row) * steps).min(steps);
+    if level == 0 {
+        return None;
+    }
+    let color = scale_color(resource_to_color(value), 0.7);
+    Some(Glyph::new(LOWER_BLOCK_CHARS[level - 1], color))
 }
 
 /// Render heat map background - combined metrics drive heat intensity.
 pub fn render_heat_map(
-    frame: &mut Frame,
+    area: Rect,
+    buf: &mut Buffer,
     elapsed_ms: u64,
     speed: AnimationSpeed,
     metrics: &SystemMetrics,
 ) {
-    let area = frame.area();
     let width = area.width;
     let height = area.height;
 
@@ -197,44 +193,151 @@ pub fn render_heat_map(
     let period = speed.gradient_scroll_period_ms();
     let time_phase = (elapsed_ms % period) as f32 / period as f32;
 
-    let lines: Vec<Line> = (0..height)
-        .map(|y| {
-            let spans: Vec<Span> = (0..width)
-                .map(|x| {
-                    // Heat spreads from edges
-                    let edge_dist = (x.min(width - 1 - x).min(y).min(height - 1 - y)) as f32;
-                    let max_edge = (width.min(height) / 2) as f32;
-                    let edge_factor = 1.0 - (edge_dist / max_edge.max(1.0)).min(1.0);
-
-                    // Add some noise/variation
-                    let noise =
-                        (x as f32 * 0.1 + y as f32 * 0.15 + time_phase * 10.0).sin() * 0.3 + 0.7;
-
-                    let heat = edge_factor * (0.2 + combined * 0.8) * noise;
-                    let color = resource_to_color(heat);
-
-                    let ch = if heat > 0.5 {
-                        '█'
-                    } else if heat > 0.35 {
-                        '▓'
-                    } else if heat > 0.2 {
-                        '▒'
-                    } else if heat > 0.1 {
-                        '░'
-                    } else {
-                        ' '
-                    };
-
-                    if ch == ' ' {
-                        Span::raw(" ")
-                    } else {
-                        Span::styled(ch.to_string(), Style::new().fg(color))
-                    }
-                })
-                .collect();
-            Line::from(spans)
-        })
-        .collect();
-
-    frame.render_widget(Paragraph::new(lines), area);
+    for y in 0..height {
+        for x in 0..width {
+            let glyph = {
+                // Heat spreads from edges
+                let edge_dist = x
+                    .min(width.saturating_sub(x + 1))
+                    .min(y)
+                    .min(height.saturating_sub(y + 1)) as f32;
+                let max_edge = (width.min(height) / 2) as f32;
+                let edge_factor = 1.0 - (edge_dist / max_edge.max(1.0)).min(1.0);
+
+                // Add some noise/variation
+                let noise =
+                    (x as f32 * 0.1 + y as f32 * 0.15 + time_phase * 10.0).sin() * 0.3 + 0.7;
+
+                let heat = edge_factor * (0.2 + combined * 0.8) * noise;
+                // Faint heat leaves the middle clear rather than dotting it
+                if heat > 0.1 {
+                    intensity_cell(heat, heat).map(|(ch, color)| Glyph::new(ch, color))
+                } else {
+                    None
+                }
+            };
+            put_glyph(buf, area.x + x, area.y + y, glyph, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use ratatui::style::Color;
+
+    use super::*;
+
+    type Renderer = fn(Rect, &mut Buffer, u64, AnimationSpeed, &SystemMetrics);
+
+    /// Distinct colors drawn in a frame.
+    fn colors(render: Renderer, metrics: &SystemMetrics, elapsed_ms: u64) -> HashSet<Color> {
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        render(area, &mut buf, elapsed_ms, AnimationSpeed::Medium, metrics);
+        buf.content
+            .iter()
+            .filter(|cell| cell.symbol() != " ")
+            .map(|cell| cell.fg)
+            .collect()
+    }
+
+    fn busy() -> SystemMetrics {
+        SystemMetrics {
+            cpu_usage: 0.6,
+            memory_usage: 0.7,
+            network_rx_rate: 0.4,
+            network_tx_rate: 0.2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_system_pulse_blends_per_cell() {
+        // A quarter into the pulse, when it is at its widest
+        assert!(colors(render_system_pulse, &busy(), 500).len() >= 8);
+    }
+
+    #[test]
+    fn test_system_pulse_low_battery_is_red() {
+        let metrics = SystemMetrics {
+            battery_level: Some(0.1),
+            battery_charging: Some(false),
+            ..busy()
+        };
+        let colors = colors(render_system_pulse, &metrics, 500);
+        assert!(!colors.is_empty());
+        for color in colors {
+            let Color::Rgb(r, g, b) = color else {
+                panic!("{color:?}");
+            };
+            assert!(r > g && r > b, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn test_resource_wave_blends_per_cell() {
+        assert!(colors(render_resource_wave, &busy(), 0).len() >= 8);
+    }
+
+    /// Symbols of a Data Flow frame, one string per row.
+    fn data_flow_rows(width: u16, height: u16, history: &MetricsHistory) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        let metrics = SystemMetrics::default();
+        render_data_flow(area, &mut buf, 0, AnimationSpeed::Medium, &metrics, history);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    fn is_block(ch: char) -> bool {
+        LOWER_BLOCK_CHARS.contains(&ch)
+    }
+
+    #[test]
+    fn test_data_flow_sparkline() {
+        // A quiet minute with one spike, drawn one sample per column
+        let mut history = MetricsHistory::default();
+        for i in 0..60 {
+            history.push(if i == 30 { 1.0 } else { 0.0 });
+        }
+        let rows = data_flow_rows(60, 20, &history);
+
+        // The spike fills all three rows; quiet columns keep a thin baseline
+        for row in &rows[17..] {
+            assert_eq!(row.chars().nth(30), Some('█'), "{row}");
+        }
+        assert!(rows[19].chars().all(|ch| ch == '▁' || ch == '█'));
+        for row in &rows[..17] {
+            assert!(!row.chars().any(is_block), "{row}");
+        }
+    }
+
+    #[test]
+    fn test_data_flow_sparkline_fills_in_from_the_right() {
+        let mut history = MetricsHistory::default();
+        for _ in 0..15 {
+            history.push(0.5);
+        }
+        let rows = data_flow_rows(60, 20, &history);
+        let bottom: Vec<char> = rows[19].chars().collect();
+        assert!(bottom[45..].iter().all(|&ch| ch == '█'));
+        assert!(!bottom[..45].iter().copied().any(is_block));
+        // Half of three rows: one and a half rows up
+        assert_eq!(rows[18].chars().nth(59), Some('▄'));
+        assert_eq!(rows[17].chars().nth(59), Some(' '));
+
+        // No samples yet, or no room: no sparkline
+        let empty = data_flow_rows(60, 20, &MetricsHistory::default());
+        assert!(!empty.concat().chars().any(is_block));
+        let short = data_flow_rows(60, 3, &history);
+        assert!(!short.concat().chars().any(is_block));
+    }
+
+    #[test]
+    fn test_heat_map_blends_per_cell() {
+        assert!(colors(render_heat_map, &busy(), 0).len() >= 12);
+    }
 }
@@ -1,48 +1,104 @@
 //! Stateless background animations (computed from position and time only).
 
-use ratatui::{
-    style::{Color, Style},
-    text::Span,
-};
+use ratatui::style::Color;
 use sigye_core::AnimationSpeed;
 
-use crate::chars::{FROST_CHARS, STAR_CHARS};
+use crate::animations::{Glyph, mix};
+use crate::chars::{FAINT_STAR_CHARS, FROST_CHARS, STAR_CHARS};
 use crate::color::hsl_to_rgb;
 
-/// Render a starfield character using pseudo-random twinkling.
-pub fn render_starfield_char(
+/// A depth layer of the starfield.
+struct StarLayer {
+    /// Salt of the layer's star positions, so layers don't line up.
+    salt: u64,
+    /// Stars per thousand cells.
+    permille: u64,
+    /// Twinkle periods per brightness change, `None` for steady stars.
+    twinkle_periods: Option<u64>,
+    /// Twinkle periods per column the layer drifts left, `None` for fixed stars.
+    drift_periods: Option<u64>,
+    /// Characters of the layer's stars.
+    chars: &'static [char],
+    /// Brightness levels of the layer's stars, dimmest first.
+    colors: &'static [Color],
+}
+
+/// Layers from far to near: the nearer, the fewer, brighter, livelier and
+/// faster drifting the stars, for a sense of depth.
+const STAR_LAYERS: [StarLayer; 3] = [
+    // Far: faint dust that stays put
+    StarLayer {
+        salt: 0x5eed_0001,
+        permille: 15,
+        twinkle_periods: None,
+        drift_periods: None,
+        chars: FAINT_STAR_CHARS,
+        colors: &[Color::Rgb(50, 50, 70), Color::Rgb(60, 60, 80)],
+    },
+    // Middle: slow twinkle and drift
+    StarLayer {
+        salt: 0x5eed_0002,
+        permille: 10,
+        twinkle_periods: Some(4),
+        drift_periods: Some(120),
+        chars: &['·', '+', '*'],
+        colors: &[Color::Rgb(80, 80, 110), Color::Rgb(100, 100, 140)],
+    },
+    // Near: bright, quick twinkle and the fastest drift
+    StarLayer {
+        salt: 0x5eed_0003,
+        permille: 5,
+        twinkle_periods: Some(1),
+        drift_periods: Some(40),
+        chars: STAR_CHARS,
+        colors: &[
+            Color::Rgb(100, 100, 140),
+            Color::Rgb(150, 150, 200),
+            Color::Rgb(200, 200, 240),
+        ],
+    },
+];
+
+/// Render the star of one depth layer at a position, if it has one there.
+fn render_star_layer(
+    layer: &StarLayer,
     x: u16,
     y: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
-    let x = x as usize;
-    let y = y as usize;
+) -> Option<Glyph> {
     let period = speed.star_twinkle_period_ms();
-    let frame_num = elapsed_ms / period;
-
-    // Use deterministic "random" based on position and time
-    let seed = (x.wrapping_mul(31))
-        .wrapping_add(y.wrapping_mul(17))
-        .wrapping_add(frame_num as usize);
-
-    // Only show stars at ~3% of positions
-    if seed % 100 < 3 {
-        let char_idx = seed % STAR_CHARS.len();
-        let ch = STAR_CHARS[char_idx];
-
-        // Vary brightness based on position
-        let brightness = (seed % 3) as u8;
-        let color = match brightness {
-            0 => Color::Rgb(60, 60, 80),    // Dim
-            1 => Color::Rgb(100, 100, 140), // Medium
-            _ => Color::Rgb(150, 150, 200), // Bright
-        };
-
-        Span::styled(ch.to_string(), Style::new().fg(color))
-    } else {
-        Span::raw(" ")
+    // Stars sit at fixed spots of the layer, which slides left under the screen
+    let drift = layer
+        .drift_periods
+        .map_or(0, |periods| elapsed_ms / (period * periods));
+    let star = mix(layer.salt, u64::from(x) + drift, y.into());
+    if star % 1000 >= layer.permille {
+        return None;
     }
+
+    // Each star twinkles in its own phase
+    let step = layer
+        .twinkle_periods
+        .map_or(0, |periods| elapsed_ms / (period * periods));
+    let twinkle = mix(star, step, 0);
+    let ch = layer.chars[(twinkle % layer.chars.len() as u64) as usize];
+    let color = layer.colors[((twinkle >> 8) % layer.colors.len() as u64) as usize];
+    Some(Glyph::new(ch, color))
+}
+
+/// Render a starfield character: layers of stars at different depths, the
+/// nearest in front.
+pub fn render_starfield_char(
+    x: u16,
+    y: u16,
+    elapsed_ms: u64,
+    speed: AnimationSpeed,
+) -> Option<Glyph> {
+    STAR_LAYERS
+        .iter()
+        .rev()
+        .find_map(|layer| render_star_layer(layer, x, y, elapsed_ms, speed))
 }
 
 /// Render a gradient wave character.
@@ -53,7 +109,7 @@ pub fn render_gradient_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let period = speed.gradient_scroll_period_ms();
     let time_phase = (elapsed_ms % period) as f32 / period as f32;
 
@@ -82,13 +138,27 @@ pub fn render_gradient_char(
     let color = hsl_to_rgb(base_hue, 0.7, 0.15 + intensity * 0.2);
 
     if ch == ' ' {
-        Span::raw(" ")
+        None
     } else {
-        Span::styled(ch.to_string(), Style::new().fg(color))
+        Some(Glyph::new(ch, color))
     }
 }
 
+/// Coldest temperature in °C the frost keeps growing with.
+const DEEPEST_FROST_C: f32 = -25.0;
+
+/// How severe frost is at a temperature in °C, from 0.0 at freezing to 1.0
+/// at [`DEEPEST_FROST_C`] or below.
+pub fn frost_severity(temp_c: f32) -> f32 {
+    (temp_c / DEEPEST_FROST_C).clamp(0.0, 1.0)
+}
+
 /// Render a frost crystal character.
+///
+/// A `severity` (0.0 - 1.0) from [`frost_severity`] scales how far the frost
+/// reaches and how dense it grows: a mild frost only rims the corners, a deep
+/// freeze covers a third of the screen from every edge. Without one, it grows
+/// as deep as a freeze.
 pub fn render_frost_char(
     x: u16,
     y: u16,
@@ -96,16 +166,20 @@ pub fn render_frost_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+    severity: Option<f32>,
+) -> Option<Glyph> {
+    let severity = severity.map_or(1.0, |severity| severity.clamp(0.0, 1.0));
     let x_f = x as f32;
     let y_f = y as f32;
     let w_f = width as f32;
     let h_f = height as f32;
 
-    // Calculate distance from nearest edge
-    let edge_dist_x = x_f.min(w_f - 1.0 - x_f);
-    let edge_dist_y = y_f.min(h_f - 1.0 - y_f);
+    // Distance from the nearest edge, or from the nearest corner for a mild frost
+    let edge_dist_x = x_f.min((w_f - 1.0 - x_f).max(0.0));
+    let edge_dist_y = y_f.min((h_f - 1.0 - y_f).max(0.0));
     let edge_dist = edge_dist_x.min(edge_dist_y * 2.0);
+    let corner_dist = edge_dist_x.max(edge_dist_y * 2.0);
+    let edge_dist = corner_dist + (edge_dist - corner_dist) * severity;
 
     // Frost growth from edges - controlled by time
     let growth_period = speed.frost_growth_period_ms();
@@ -113,21 +187,21 @@ pub fn render_frost_char(
         ((elapsed_ms % growth_period) as f32 / growth_period as f32) * std::f32::consts::PI;
     let growth_factor = growth_phase.sin() * 0.3 + 0.7;
 
-    let max_frost_depth = (w_f.min(h_f) / 3.0) * growth_factor;
+    let max_frost_depth = (w_f.min(h_f) / 3.0) * growth_factor * (0.4 + 0.6 * severity);
 
-    if edge_dist > max_frost_depth {
-        return Span::raw(" ");
+    // Also covers an empty area, where the depth is zero
+    if edge_dist > max_frost_depth || max_frost_depth <= 0.0 {
+        return None;
     }
 
     // Crystal pattern using pseudo-random based on position
-    let seed = (x as usize)
-        .wrapping_mul(31)
-        .wrapping_add((y as usize).wrapping_mul(17));
+    let seed = mix(0, x.into(), y.into()) as usize;
 
     // Density decreases toward center
     let density_threshold = ((edge_dist / max_frost_depth) * 85.0) as usize;
-    if seed % 100 > (100 - density_threshold).max(15) {
-        return Span::raw(" ");
+    let cutoff = (100 - density_threshold).max(15) as f32 * (0.5 + 0.5 * severity);
+    if seed % 100 > cutoff as usize {
+        return None;
     }
 
     // Character selection
@@ -150,10 +224,13 @@ pub fn render_frost_char(
     let g = (base_color.1 as f32 * shimmer) as u8;
     let b = (base_color.2 as f32 * shimmer) as u8;
 
-    Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(r, g, b)))
+    Some(Glyph::new(ch, Color::Rgb(r, g, b)))
 }
 
 /// Render an aurora borealis character.
+///
+/// `activity` scales how bright the curtains are and how far down they
+/// reach: 1.0 for a typical night, up to 1.5 for a geomagnetic storm.
 pub fn render_aurora_char(
     x: u16,
     y: u16,
@@ -161,7 +238,8 @@ pub fn render_aurora_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+    activity: f32,
+) -> Option<Glyph> {
     let x_norm = x as f32 / width.max(1) as f32;
     let y_norm = y as f32 / height.max(1) as f32;
 
@@ -176,14 +254,14 @@ pub fn render_aurora_char(
     // Combine waves
     let combined_wave = wave1 * 0.5 + wave2 * 0.3 + wave3 * 0.2;
 
-    // Vertical falloff (aurora is brighter at top)
-    let vertical_factor = 1.0 - y_norm.powf(0.5);
+    // Vertical falloff (aurora is brighter at top), stretched by activity
+    let vertical_factor = (1.0 - (y_norm / activity.max(0.1)).powf(0.5)).max(0.0);
 
     // Final intensity
-    let intensity = combined_wave * vertical_factor;
+    let intensity = combined_wave * vertical_factor * activity;
 
     if intensity < 0.15 {
-        return Span::raw(" ");
+        return None;
     }
 
     // Select character based on intensity
@@ -194,7 +272,7 @@ pub fn render_aurora_char(
     } else if intensity > 0.3 {
         '░'
     } else {
-        return Span::raw(" ");
+        return None;
     };
 
     // Aurora colors - cycle through greens, blues, purples
@@ -228,7 +306,7 @@ pub fn render_aurora_char(
     let g = (g as f32 * dimming) as u8;
     let b = (b as f32 * dimming) as u8;
 
-    Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(r, g, b)))
+    Some(Glyph::new(ch, Color::Rgb(r, g, b)))
 }
 
 /// Render a twilight dawn background character (golden hour - sunrise).
@@ -240,7 +318,7 @@ pub fn render_twilight_dawn_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let y_norm = y as f32 / height.max(1) as f32;
     let x_norm = x as f32 / width.max(1) as f32;
 
@@ -288,9 +366,7 @@ pub fn render_twilight_dawn_char(
             + 0.92;
 
     // Pseudo-random seed for patterns
-    let seed = (x as usize)
-        .wrapping_mul(31)
-        .wrapping_add((y as usize).wrapping_mul(17));
+    let seed = mix(0, x.into(), y.into()) as usize;
 
     // Dawn-specific patterns: fading stars at top, clouds in mid, horizontal rays at horizon
     let ch = if y_norm < 0.2 {
@@ -298,7 +374,7 @@ pub fn render_twilight_dawn_char(
         if seed % 100 < 3 {
             '·'
         } else {
-            return Span::raw(" ");
+            return None;
         }
     } else if y_norm < 0.6 {
         // Soft wispy clouds in mid-sky
@@ -308,7 +384,7 @@ pub fn render_twilight_dawn_char(
         if seed % 100 < cloud_threshold {
             '░'
         } else {
-            return Span::raw(" ");
+            return None;
         }
     } else {
         // Horizontal light rays emanating from horizon
@@ -318,7 +394,7 @@ pub fn render_twilight_dawn_char(
         if seed % 100 < ray_threshold {
             '─'
         } else {
-            return Span::raw(" ");
+            return None;
         }
     };
 
@@ -326,7 +402,7 @@ pub fn render_twilight_dawn_char(
     let g = (g as f32 * shimmer) as u8;
     let b = (b as f32 * shimmer) as u8;
 
-    Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(r, g, b)))
+    Some(Glyph::new(ch, Color::Rgb(r, g, b)))
 }
 
 /// Render a twilight dusk background character (sunset).
@@ -338,7 +414,7 @@ pub fn render_twilight_dusk_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let y_norm = y as f32 / height.max(1) as f32;
     let x_norm = x as f32 / width.max(1) as f32;
 
@@ -381,10 +457,8 @@ pub fn render_twilight_dusk_char(
             * 0.06
             + 0.94;
 
-    // Pseudo-random seed for patterns (different primes from dawn)
-    let seed = (x as usize)
-        .wrapping_mul(37)
-        .wrapping_add((y as usize).wrapping_mul(19));
+    // Pseudo-random seed for patterns (a different one from dawn)
+    let seed = mix(1, x.into(), y.into()) as usize;
 
     // Dusk-specific patterns: emerging stars at top, vertical striations, color bands at horizon
     let ch = if y_norm < 0.2 {
@@ -392,7 +466,7 @@ pub fn render_twilight_dusk_char(
         if seed % 100 < 5 {
             if seed.is_multiple_of(3) { '✦' } else { '·' }
         } else {
-            return Span::raw(" ");
+            return None;
         }
     } else if y_norm < 0.5 {
         // Vertical striations for atmospheric depth
@@ -402,7 +476,7 @@ pub fn render_twilight_dusk_char(
         if seed % 100 < striation_threshold {
             '│'
         } else {
-            return Span::raw(" ");
+            return None;
         }
     } else {
         // Color bands near horizon - layered sunset effect
@@ -412,7 +486,7 @@ pub fn render_twilight_dusk_char(
         if seed % 100 < band_threshold {
             if seed.is_multiple_of(3) { '▒' } else { '░' }
         } else {
-            return Span::raw(" ");
+            return None;
         }
     };
 
@@ -420,5 +494,147 @@ pub fn render_twilight_dusk_char(
     let g = (g as f32 * shimmer) as u8;
     let b = (b as f32 * shimmer) as u8;
 
-    Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(r, g, b)))
+    Some(Glyph::new(ch, Color::Rgb(r, g, b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Share of the cells of a 200x100 screen with a star at `elapsed_ms`.
+    fn star_density(elapsed_ms: u64, speed: AnimationSpeed) -> f32 {
+        let stars = (0..100)
+            .flat_map(|y| (0..200).map(move |x| (x, y)))
+            .filter(|&(x, y)| render_starfield_char(x, y, elapsed_ms, speed).is_some())
+            .count();
+        stars as f32 / 20_000.0
+    }
+
+    #[test]
+    fn test_star_density_stays_sparse() {
+        for speed in [
+            AnimationSpeed::Slow,
+            AnimationSpeed::Medium,
+            AnimationSpeed::Fast,
+        ] {
+            for elapsed_ms in [0, 1_000, 60_000, 3_600_000] {
+                let density = star_density(elapsed_ms, speed);
+                assert!(
+                    (0.02..0.04).contains(&density),
+                    "{density} at {elapsed_ms} ms, {speed:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_far_stars_hold_still_and_near_ones_twinkle() {
+        let [far, _, near] = &STAR_LAYERS;
+        let speed = AnimationSpeed::Medium;
+        let cells = || (0..40).flat_map(|y| (0..80).map(move |x| (x, y)));
+        assert!(cells().all(|(x, y)| {
+            render_star_layer(far, x, y, 0, speed) == render_star_layer(far, x, y, 600_000, speed)
+        }));
+        // Within a drift step, near stars stay in place but change their look
+        let period = speed.star_twinkle_period_ms();
+        let near_at = |elapsed_ms| {
+            cells()
+                .map(|(x, y)| render_star_layer(near, x, y, elapsed_ms, speed))
+                .collect::<Vec<_>>()
+        };
+        let (before, after) = (near_at(0), near_at(period));
+        assert!(
+            before
+                .iter()
+                .zip(&after)
+                .all(|(a, b)| a.is_some() == b.is_some())
+        );
+        assert_ne!(before, after);
+        // A drift step later they have moved a column left
+        let drifted = near_at(period * 40);
+        assert!(
+            cells()
+                .zip(&drifted)
+                .filter(|((x, _), _)| *x < 79)
+                .all(|((x, y), star)| {
+                    star.is_some() == render_star_layer(near, x + 1, y, 0, speed).is_some()
+                })
+        );
+    }
+
+    /// Aurora cells drawn on an 80x24 screen at an activity.
+    fn aurora_cells(activity: f32) -> Vec<(u16, u16)> {
+        (0..24)
+            .flat_map(|y| (0..80).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                render_aurora_char(x, y, 80, 24, 0, AnimationSpeed::Medium, activity).is_some()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aurora_grows_with_activity() {
+        let quiet = aurora_cells(0.6);
+        let typical = aurora_cells(1.0);
+        let storm = aurora_cells(1.5);
+        assert!(!quiet.is_empty());
+        assert!(
+            quiet.len() < typical.len(),
+            "{} vs {}",
+            quiet.len(),
+            typical.len()
+        );
+        assert!(
+            typical.len() < storm.len(),
+            "{} vs {}",
+            typical.len(),
+            storm.len()
+        );
+
+        // A storm reaches further down the screen
+        let lowest = |cells: &[(u16, u16)]| cells.iter().map(|&(_, y)| y).max().unwrap();
+        assert!(lowest(&quiet) < lowest(&typical));
+        assert!(lowest(&typical) < lowest(&storm));
+    }
+
+    /// Frost cells drawn on an 80x24 screen at a severity.
+    fn frost_cells(severity: Option<f32>) -> Vec<(u16, u16)> {
+        (0..24)
+            .flat_map(|y| (0..80).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                render_frost_char(x, y, 80, 24, 0, AnimationSpeed::Medium, severity).is_some()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_frost_severity() {
+        assert_eq!(frost_severity(5.0), 0.0);
+        assert_eq!(frost_severity(0.0), 0.0);
+        assert_eq!(frost_severity(-5.0), 0.2);
+        assert_eq!(frost_severity(-12.5), 0.5);
+        assert_eq!(frost_severity(-25.0), 1.0);
+        assert_eq!(frost_severity(-40.0), 1.0);
+    }
+
+    #[test]
+    fn test_frost_depth_follows_severity() {
+        let mild = frost_cells(Some(0.0));
+        let hard = frost_cells(Some(0.5));
+        let deep = frost_cells(Some(1.0));
+        assert!(!mild.is_empty());
+        assert!(mild.len() < hard.len(), "{} vs {}", mild.len(), hard.len());
+        assert!(hard.len() < deep.len(), "{} vs {}", hard.len(), deep.len());
+        // Without a severity, frost grows as deep as a freeze
+        assert_eq!(frost_cells(None), deep);
+
+        // A mild frost stays in the corners, a deep freeze rims every edge
+        let mid_edge = |cells: &[(u16, u16)]| cells.iter().any(|&(x, y)| x == 40 && y <= 1);
+        assert!(!mid_edge(&mild));
+        assert!(mid_edge(&deep));
+        assert!(
+            mild.iter()
+                .all(|&(x, y)| !(10..70).contains(&x) && !(4..20).contains(&y))
+        );
+    }
 }
@@ -1,181 +1,435 @@
 //! Weather animation effects (stateful and stateless).
 
-use ratatui::{
-    style::{Color, Style},
-    text::Span,
-};
+use ratatui::style::Color;
 use sigye_core::AnimationSpeed;
+use sigye_core::precipitation::{self, clamp_intensity};
 
+use crate::animations::{Glyph, mix, next_random};
 use crate::chars::{
-    CLOUD_CHARS, FOG_CHARS, RAIN_CHARS, SNOW_CHARS, STORM_RAIN_CHARS, SUN_CHARS, WIND_CHARS,
+    CLOUD_CHARS, FOG_CHARS, LOWER_BLOCK_CHARS, RAIN_CHARS, SNOW_CHARS, STORM_RAIN_CHARS, SUN_CHARS,
+    WIND_CHARS,
 };
+use crate::color::scale_color;
+
+// ========== WIND DRIFT ==========
+
+/// Wind speed in km/h at which rain slants one column per row of fall.
+const WIND_KMPH_PER_COLUMN: f32 = 30.0;
+
+/// Strongest drift in columns per row, so a gale still reads as rain.
+const MAX_WIND_DRIFT: f32 = 1.5;
+
+/// Period of the slow gusts that vary the drift.
+const GUST_PERIOD_MS: f32 = 7000.0;
+
+/// How much gusts vary the drift, as a share of it.
+const GUST_STRENGTH: f32 = 0.3;
+
+/// Snowflakes are light, so the wind pushes them further than raindrops.
+const SNOW_DRIFT_FACTOR: f32 = 2.0;
+
+/// Drift below which rain keeps its straight characters.
+const SLANT_THRESHOLD: f32 = 0.25;
+
+/// Horizontal drift of falling rain in columns per row, for a wind speed.
+///
+/// Positive drift blows to the right. Gusts slowly vary it by up to
+/// [`GUST_STRENGTH`] either way, and a calm day has no drift at all.
+pub fn wind_drift(wind_kmph: f32, elapsed_ms: u64) -> f32 {
+    let base = (wind_kmph / WIND_KMPH_PER_COLUMN).clamp(0.0, MAX_WIND_DRIFT);
+    let gust = (elapsed_ms as f32 / GUST_PERIOD_MS * std::f32::consts::TAU).sin();
+    base * (1.0 + GUST_STRENGTH * gust)
+}
+
+/// Wrap a horizontal position back into the width after leaving either side.
+fn wrap_x(x: f32, width: u16) -> f32 {
+    if width == 0 {
+        x
+    } else {
+        x.rem_euclid(width as f32)
+    }
+}
+
+/// Column of a particle at row `y`, for one whose head is at (`x`, `head_y`).
+///
+/// Rows above the head are where it came from, further upwind.
+fn column_at(x: f32, head_y: f32, y: f32, drift: f32) -> i32 {
+    (x + (y - head_y) * drift).round() as i32
+}
+
+// ========== PRECIPITATION INTENSITY ==========
+
+/// Share of the drops or flakes falling at a precipitation `intensity`.
+///
+/// The others wait above the screen until they start over, so a drizzle is
+/// sparse and a downpour fills every column.
+fn active_share(intensity: f32) -> f32 {
+    0.2 + 0.8 * clamp_intensity(intensity)
+}
+
+/// Fall speed multiplier at a precipitation `intensity`, 1.0 when moderate.
+fn intensity_speed(intensity: f32) -> f32 {
+    0.7 + 0.6 * clamp_intensity(intensity)
+}
+
+/// Roll between 0.0 and 1.0 for a particle, from its character seed.
+fn roll(char_seed: usize, salt: u64) -> f32 {
+    (mix(char_seed as u64, salt, 0) % 1000) as f32 / 1000.0
+}
+
+/// Check if a particle falls visibly at a precipitation `intensity`.
+fn is_active(char_seed: usize, intensity: f32) -> bool {
+    roll(char_seed, 0) < active_share(intensity)
+}
+
+/// Weight of a particle (0=light, 1=medium, 2=heavy), heavier on average
+/// at a higher precipitation `intensity`.
+fn weight(char_seed: usize, intensity: f32) -> u8 {
+    ((clamp_intensity(intensity) + roll(char_seed, 1)) * 1.5).min(2.0) as u8
+}
 
 // ========== RAIN STATE (Stateful) ==========
 
-/// State for a single rain column.
+/// State for a single raindrop.
 #[derive(Debug, Clone)]
-pub struct RainColumn {
+pub struct RainDrop {
+    /// Current x position of the raindrop.
+    pub x: f32,
     /// Current y position of the raindrop.
     pub y: f32,
-    /// Speed multiplier for this column.
+    /// Speed multiplier for this drop.
     pub speed: f32,
     /// Character seed for variety.
     pub char_seed: usize,
-    /// Intensity (0=light, 1=medium, 2=heavy).
-    pub intensity: u8,
-}
-
-/// Initialize rain columns.
-pub fn init_rain_columns(width: u16, height: u16, init_seed: u64) -> Vec<RainColumn> {
-    (0..width)
-        .map(|x| {
-            let x = x as usize;
-            let mixed = x.wrapping_mul(29).wrapping_add(init_seed as usize);
-            let stagger = ((mixed.wrapping_mul(13)) % (height as usize * 2)) as f32;
-            RainColumn {
-                y: -stagger,
-                speed: 0.8 + ((mixed.wrapping_mul(17)) % 10) as f32 / 25.0,
-                char_seed: mixed.wrapping_mul(23),
-                intensity: ((mixed.wrapping_mul(7)) % 3) as u8,
-            }
-        })
-        .collect()
+    /// Weight (0=light, 1=medium, 2=heavy).
+    pub weight: u8,
+    /// Whether the drop is drawn, or waits unseen at a lower intensity.
+    pub active: bool,
 }
 
-/// Update rain column positions.
-pub fn update_rain(columns: &mut [RainColumn], delta_ms: u64, height: u16, speed: AnimationSpeed) {
-    let fall_speed = speed.rain_fall_speed();
-    let delta_y = (delta_ms as f32 / 40.0) * fall_speed;
+/// Initialize raindrops at a precipitation `intensity`, one starting above each column.
+pub fn init_raindrops(width: u16, height: u16, init_seed: u64, intensity: f32) -> Vec<RainDrop> {
+    (0..width as usize)
+        .map(|x| raindrop(x, height, init_seed, intensity))
+        .collect()
+}
 
-    for col in columns {
-        col.y += delta_y * col.speed;
-        if col.y > height as f32 + 1.0 {
-            col.y = -1.0;
-            col.char_seed = col.char_seed.wrapping_add(1);
-        }
+/// Fit existing raindrops to resized dimensions without restarting them.
+pub fn resize_raindrops(
+    drops: &mut Vec<RainDrop>,
+    old_width: u16,
+    old_height: u16,
+    width: u16,
+    height: u16,
+    init_seed: u64,
+    intensity: f32,
+) {
+    let x_scale = super::scale_factor(old_width, width);
+    super::resize_columns(
+        drops,
+        width,
+        old_height,
+        height,
+        |drop| &mut drop.y,
+        |x| raindrop(x, height, init_seed, intensity),
+    );
+    for drop in drops.iter_mut().take(old_width as usize) {
+        drop.x = wrap_x(drop.x * x_scale, width);
     }
 }
 
-/// Render a rain character.
-pub fn render_rain_char(columns: &[RainColumn], x: u16, y: u16) -> Span<'static> {
-    let x_idx = x as usize;
-    let y_f = y as f32;
-
-    if x_idx >= columns.len() {
-        return Span::raw(" ");
+/// Create the raindrop starting above column `x`.
+fn raindrop(x: usize, height: u16, init_seed: u64, intensity: f32) -> RainDrop {
+    let random = |salt| mix(init_seed, x as u64, salt);
+    let stagger = (random(0) % (u64::from(height) * 2).max(1)) as f32;
+    let char_seed = random(2) as usize;
+    RainDrop {
+        x: x as f32,
+        y: -stagger,
+        speed: 0.8 + (random(1) % 10) as f32 / 25.0,
+        char_seed,
+        weight: weight(char_seed, intensity),
+        active: is_active(char_seed, intensity),
     }
+}
 
-    let col = &columns[x_idx];
-    let distance = (y_f - col.y).abs();
-
-    if distance < 0.6 {
-        let char_idx = col.char_seed % RAIN_CHARS.len();
-        let ch = RAIN_CHARS[char_idx];
+/// Update raindrop positions, blown sideways by `drift` columns per row.
+///
+/// Drops leaving the bottom start over at the top, and drops blown past
+/// either side come back in on the other. A drop starting over is drawn or
+/// not, light or heavy, anew for the precipitation `intensity`.
+pub fn update_rain(
+    drops: &mut [RainDrop],
+    delta_ms: u64,
+    width: u16,
+    height: u16,
+    speed: AnimationSpeed,
+    drift: f32,
+    intensity: f32,
+) {
+    let fall_speed = speed.rain_fall_speed() * intensity_speed(intensity);
+    let delta_y = (delta_ms as f32 / 40.0) * fall_speed;
 
-        // Blue-gray rain colors
-        let color = match col.intensity {
-            0 => Color::Rgb(100, 120, 150), // Light rain
-            1 => Color::Rgb(80, 100, 140),  // Medium rain
-            _ => Color::Rgb(60, 80, 120),   // Heavy rain
-        };
+    for drop in drops {
+        let fall = delta_y * drop.speed;
+        drop.y += fall;
+        drop.x = wrap_x(drop.x + fall * drift, width);
+        if drop.y > height as f32 + 1.0 {
+            drop.y = -1.0;
+            drop.char_seed = drop.char_seed.wrapping_add(1);
+            drop.weight = weight(drop.char_seed, intensity);
+            drop.active = is_active(drop.char_seed, intensity);
+        }
+    }
+}
 
-        Span::styled(ch.to_string(), Style::new().fg(color))
+/// Character for a raindrop, slanted with the wind when it is strong enough.
+fn rain_char(char_seed: usize, chars: &[char], drift: f32) -> char {
+    if drift >= SLANT_THRESHOLD {
+        '\\'
+    } else if drift <= -SLANT_THRESHOLD {
+        '/'
     } else {
-        Span::raw(" ")
+        chars[char_seed % chars.len()]
     }
 }
 
+/// Render a rain character.
+pub fn render_rain_char(drops: &[RainDrop], x: u16, y: u16, drift: f32) -> Option<Glyph> {
+    let y_f = y as f32;
+    let drop = drops.iter().find(|drop| {
+        drop.active
+            && (y_f - drop.y).abs() < 0.6
+            && column_at(drop.x, drop.y, y_f, drift) == i32::from(x)
+    })?;
+
+    // Three neighbouring characters for each weight, thin for light rain
+    let chars = &RAIN_CHARS[drop.weight as usize * 2..][..3];
+    let ch = rain_char(drop.char_seed, chars, drift);
+
+    // Blue-gray rain colors
+    let color = match drop.weight {
+        0 => Color::Rgb(100, 120, 150), // Light rain
+        1 => Color::Rgb(80, 100, 140),  // Medium rain
+        _ => Color::Rgb(60, 80, 120),   // Heavy rain
+    };
+
+    Some(Glyph::new(ch, color))
+}
+
 // ========== SNOW STATE (Stateful) ==========
 
-/// State for a single snowfall column.
+/// State for a single snowflake.
 #[derive(Debug, Clone)]
-pub struct SnowColumn {
+pub struct Snowflake {
+    /// Current x position of the snowflake.
+    pub x: f32,
     /// Current y position of the snowflake.
     pub y: f32,
-    /// Speed multiplier for this column.
+    /// Speed multiplier for this flake.
     pub speed: f32,
-    /// Horizontal drift phase offset.
+    /// Sway phase offset.
     pub drift_phase: f32,
     /// Size category (0=small, 1=medium, 2=large).
     pub size: u8,
     /// Seed for character generation.
     pub char_seed: usize,
+    /// Whether the flake is drawn, or waits unseen at a lower intensity.
+    pub active: bool,
 }
 
-/// Initialize snowfall columns for the given dimensions.
-pub fn init_snow_columns(width: u16, height: u16, init_seed: u64) -> Vec<SnowColumn> {
-    (0..width)
-        .map(|x| {
-            let x = x as usize;
-            // Mix column index with time-based seed for better randomness
-            let mixed = x.wrapping_mul(31).wrapping_add(init_seed as usize);
-            let stagger = ((mixed.wrapping_mul(11).wrapping_add(7)) % (height as usize * 3)) as f32;
-            SnowColumn {
-                y: -stagger,
-                speed: 0.2 + ((mixed.wrapping_mul(17)) % 10) as f32 / 20.0,
-                drift_phase: ((mixed.wrapping_mul(23)) % 100) as f32 / 100.0,
-                size: ((mixed.wrapping_mul(13)) % 3) as u8,
-                char_seed: mixed.wrapping_mul(19),
-            }
-        })
+/// Initialize snowflakes at a precipitation `intensity`, one starting above each column.
+pub fn init_snowflakes(width: u16, height: u16, init_seed: u64, intensity: f32) -> Vec<Snowflake> {
+    (0..width as usize)
+        .map(|x| snowflake(x, height, init_seed, intensity))
         .collect()
 }
 
-/// Update snowfall column positions.
-pub fn update_snow(columns: &mut [SnowColumn], delta_ms: u64, height: u16, speed: AnimationSpeed) {
-    let fall_speed = speed.snow_fall_speed();
+/// Fit existing snowflakes to resized dimensions without restarting them.
+pub fn resize_snowflakes(
+    flakes: &mut Vec<Snowflake>,
+    old_width: u16,
+    old_height: u16,
+    width: u16,
+    height: u16,
+    init_seed: u64,
+    intensity: f32,
+) {
+    let x_scale = super::scale_factor(old_width, width);
+    super::resize_columns(
+        flakes,
+        width,
+        old_height,
+        height,
+        |flake| &mut flake.y,
+        |x| snowflake(x, height, init_seed, intensity),
+    );
+    for flake in flakes.iter_mut().take(old_width as usize) {
+        flake.x = wrap_x(flake.x * x_scale, width);
+    }
+}
+
+/// Create the snowflake starting above column `x`.
+fn snowflake(x: usize, height: u16, init_seed: u64, intensity: f32) -> Snowflake {
+    let random = |salt| mix(init_seed, x as u64, salt);
+    let stagger = (random(0) % (u64::from(height) * 3).max(1)) as f32;
+    let char_seed = random(4) as usize;
+    Snowflake {
+        x: x as f32,
+        y: -stagger,
+        speed: 0.2 + (random(1) % 10) as f32 / 20.0,
+        drift_phase: (random(2) % 100) as f32 / 100.0,
+        size: weight(char_seed, intensity),
+        char_seed,
+        active: is_active(char_seed, intensity),
+    }
+}
+
+/// Start a flake over above the screen, drawn or not and sized anew for the
+/// precipitation `intensity`.
+fn restart_flake(flake: &mut Snowflake, intensity: f32) {
+    flake.y = -2.0;
+    flake.char_seed = flake.char_seed.wrapping_add(1);
+    flake.size = weight(flake.char_seed, intensity);
+    flake.active = is_active(flake.char_seed, intensity);
+}
+
+/// Update snowflake positions, blown sideways by the rain `drift`.
+///
+/// Flakes are pushed [`SNOW_DRIFT_FACTOR`] times as far as rain, and wrap
+/// around and start over like raindrops at the precipitation `intensity`.
+pub fn update_snow(
+    flakes: &mut [Snowflake],
+    delta_ms: u64,
+    width: u16,
+    height: u16,
+    speed: AnimationSpeed,
+    drift: f32,
+    intensity: f32,
+) {
+    let fall_speed = speed.snow_fall_speed() * intensity_speed(intensity);
     let delta_y = (delta_ms as f32 / 80.0) * fall_speed;
 
-    for col in columns {
-        col.y += delta_y * col.speed;
-        if col.y > height as f32 + 2.0 {
-            col.y = -2.0;
-            col.char_seed = col.char_seed.wrapping_add(1);
+    for flake in flakes {
+        let fall = delta_y * flake.speed;
+        flake.y += fall;
+        flake.x = wrap_x(flake.x + fall * drift * SNOW_DRIFT_FACTOR, width);
+        if flake.y > height as f32 + 2.0 {
+            restart_flake(flake, intensity);
         }
     }
 }
 
 /// Render a snowfall character.
-pub fn render_snow_char(columns: &[SnowColumn], x: u16, y: u16, elapsed_ms: u64) -> Span<'static> {
-    let x_idx = x as usize;
+pub fn render_snow_char(flakes: &[Snowflake], x: u16, y: u16, elapsed_ms: u64) -> Option<Glyph> {
     let y_f = y as f32;
 
-    if x_idx >= columns.len() {
-        return Span::raw(" ");
-    }
+    let flake = flakes.iter().filter(|flake| flake.active).find(|flake| {
+        // Calculate the sway for visual effect
+        let drift_period = 3000.0;
+        let sway =
+            ((elapsed_ms as f32 / drift_period + flake.drift_phase) * 2.0 * std::f32::consts::PI)
+                .sin()
+                * 1.5;
+
+        // Check if snowflake is at this position (applying the sway)
+        let flake_y = flake.y + sway * 0.1;
+        (y_f - flake_y).abs() < 0.8 && flake.x.round() as i32 == i32::from(x)
+    })?;
+
+    // Select character based on size
+    let char_idx = match flake.size {
+        0 => flake.char_seed % 3,
+        1 => 3 + flake.char_seed % 3,
+        _ => 6 + flake.char_seed % 3,
+    };
+    let ch = SNOW_CHARS[char_idx % SNOW_CHARS.len()];
+
+    // Color based on size - using deeper blues visible on both light and dark themes
+    let color = match flake.size {
+        0 => Color::Rgb(70, 100, 160), // Small - dark steel blue
+        1 => Color::Rgb(65, 105, 225), // Medium - royal blue
+        _ => Color::Rgb(30, 144, 255), // Large - dodger blue
+    };
 
-    let col = &columns[x_idx];
+    Some(Glyph::new(ch, color))
+}
 
-    // Calculate horizontal drift for visual effect
-    let drift_period = 3000.0;
-    let drift = ((elapsed_ms as f32 / drift_period + col.drift_phase) * 2.0 * std::f32::consts::PI)
-        .sin()
-        * 1.5;
+// ========== SNOW PILE (Stateful) ==========
 
-    // Check if snowflake is at this position (applying drift effect)
-    let flake_y = col.y + drift * 0.1;
-    let distance = (y_f - flake_y).abs();
+/// Deepest the settled snow gets, in rows.
+pub const MAX_PILE_ROWS: f32 = 3.0;
 
-    if distance < 0.8 {
-        // Select character based on size
-        let char_idx = match col.size {
-            0 => col.char_seed % 3,
-            1 => 3 + col.char_seed % 3,
-            _ => 6 + col.char_seed % 3,
-        };
-        let ch = SNOW_CHARS[char_idx % SNOW_CHARS.len()];
+/// Depth a landed flake adds to the pile: one step of the partial blocks.
+const FLAKE_DEPTH: f32 = 1.0 / 8.0;
 
-        // Color based on size - using deeper blues visible on both light and dark themes
-        let color = match col.size {
-            0 => Color::Rgb(70, 100, 160), // Small - dark steel blue
-            1 => Color::Rgb(65, 105, 225), // Medium - royal blue
-            _ => Color::Rgb(30, 144, 255), // Large - dodger blue
-        };
+/// Depth the pile melts per millisecond: a full pile melts in ten minutes.
+const MELT_PER_MS: f32 = MAX_PILE_ROWS / 600_000.0;
 
-        Span::styled(ch.to_string(), Style::new().fg(color))
-    } else {
-        Span::raw(" ")
+/// Land the flakes that reach the snow at their column and melt the pile.
+///
+/// `pile` holds the depth of settled snow in each column, in rows, on top of
+/// the `ground` row. A flake that touches the surface adds [`FLAKE_DEPTH`] to
+/// its column, up to [`MAX_PILE_ROWS`], and starts over above the screen at
+/// the precipitation `intensity`; unseen flakes start over without adding
+/// any. Every column melts a little with the time passed.
+pub fn settle_snow(
+    flakes: &mut [Snowflake],
+    pile: &mut [f32],
+    delta_ms: u64,
+    ground: u16,
+    intensity: f32,
+) {
+    if pile.is_empty() {
+        return;
+    }
+    for depth in pile.iter_mut() {
+        *depth = (*depth - MELT_PER_MS * delta_ms as f32).max(0.0);
+    }
+    // No room for snow to settle in
+    if ground == 0 {
+        return;
+    }
+    for flake in flakes {
+        let column = (flake.x.round() as usize).min(pile.len() - 1);
+        let surface = f32::from(ground) - pile[column];
+        if flake.y + 1.0 >= surface {
+            if flake.active {
+                pile[column] = (pile[column] + FLAKE_DEPTH).min(MAX_PILE_ROWS);
+            }
+            restart_flake(flake, intensity);
+        }
+    }
+}
+
+/// Fit the snow pile to a new width, keeping the depth of the kept columns.
+pub fn resize_snow_pile(pile: &mut Vec<f32>, width: u16) {
+    pile.resize(width as usize, 0.0);
+}
+
+/// Render the settled snow at a position, as a partial block for its depth.
+///
+/// Nothing is drawn on or below the `ground` row.
+pub fn render_snow_pile_char(pile: &[f32], x: u16, y: u16, ground: u16) -> Option<Glyph> {
+    let depth = *pile.get(x as usize)?;
+    if y >= ground {
+        return None;
+    }
+    // Share of this cell below the surface, filled from the bottom up
+    let surface = f32::from(ground) - depth;
+    let fill = (f32::from(y) + 1.0 - surface).min(1.0);
+    if fill <= 0.0 {
+        return None;
     }
+    let steps = LOWER_BLOCK_CHARS.len();
+    let level = ((fill * steps as f32).ceil() as usize).clamp(1, steps);
+    // The surface is bright, deeper snow a little bluer
+    let color = if fill < 1.0 {
+        Color::Rgb(215, 225, 245)
+    } else {
+        Color::Rgb(170, 190, 230)
+    };
+    Some(Glyph::new(LOWER_BLOCK_CHARS[level - 1], color))
 }
 
 // ========== STORM STATE (Stateful - extends Rain) ==========
@@ -183,8 +437,8 @@ pub fn render_snow_char(columns: &[SnowColumn], x: u16, y: u16, elapsed_ms: u64)
 /// State for storm lightning.
 #[derive(Debug, Clone)]
 pub struct StormState {
-    /// Rain columns (reuses rain logic).
-    pub rain_columns: Vec<RainColumn>,
+    /// Raindrops (reuses rain logic).
+    pub raindrops: Vec<RainDrop>,
     /// Time of last lightning flash.
     pub last_lightning_ms: u64,
     /// Duration of current lightning flash (0 = no flash).
@@ -193,19 +447,56 @@ pub struct StormState {
     pub next_lightning_interval: u64,
     /// Lightning flash intensity (0.0-1.0).
     pub flash_intensity: f32,
-    /// Seed for lightning randomness.
+    /// Random state for lightning timing and bolts.
     pub lightning_seed: u64,
+    /// Column of the lightning bolt on each row from the top, empty between flashes.
+    pub bolt: Vec<u16>,
+}
+
+/// How long a lightning flash fills the whole sky, in milliseconds.
+pub const FLASH_FILL_MS: u64 = 80;
+
+/// Share of the height the lightning bolt reaches down to.
+const BOLT_REACH: f32 = 0.6;
+
+/// Generate a jagged lightning bolt from the top row down to 60% of the height.
+///
+/// Returns the bolt's column on each row. It starts in the middle half of the
+/// width and moves at most one column between rows, staying inside the width.
+pub fn lightning_bolt(seed: u64, width: u16, height: u16) -> Vec<u16> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    // Xorshift needs a non-zero state
+    let mut seed = seed | 1;
+    let rows = ((height as f32 * BOLT_REACH).ceil() as usize).max(1);
+    let mut x = width / 4 + (next_random(&mut seed) % u64::from(width / 2).max(1)) as u16;
+    (0..rows)
+        .map(|row| {
+            if row > 0 {
+                match next_random(&mut seed) % 3 {
+                    0 => x = x.saturating_sub(1),
+                    1 => x = (x + 1).min(width - 1),
+                    _ => {}
+                }
+            }
+            x
+        })
+        .collect()
 }
 
 /// Initialize storm state.
 pub fn init_storm(width: u16, height: u16, init_seed: u64) -> StormState {
+    // Xorshift needs a non-zero state
+    let mut lightning_seed = init_seed | 1;
     StormState {
-        rain_columns: init_rain_columns(width, height, init_seed),
+        raindrops: init_raindrops(width, height, init_seed, precipitation::HEAVY),
         last_lightning_ms: 0,
         lightning_duration_ms: 0,
-        next_lightning_interval: 2000 + (init_seed % 3000),
+        next_lightning_interval: 2000 + next_random(&mut lightning_seed) % 3000,
         flash_intensity: 0.0,
-        lightning_seed: init_seed,
+        lightning_seed,
+        bolt: Vec::new(),
     }
 }
 
@@ -214,11 +505,21 @@ pub fn update_storm(
     state: &mut StormState,
     elapsed_ms: u64,
     delta_ms: u64,
+    width: u16,
     height: u16,
     speed: AnimationSpeed,
+    drift: f32,
 ) {
     // Update rain
-    update_rain(&mut state.rain_columns, delta_ms, height, speed);
+    update_rain(
+        &mut state.raindrops,
+        delta_ms,
+        width,
+        height,
+        speed,
+        drift,
+        precipitation::HEAVY,
+    );
 
     // Handle lightning
     let time_since_flash = elapsed_ms.saturating_sub(state.last_lightning_ms);
@@ -228,11 +529,11 @@ pub fn update_storm(
         if time_since_flash > state.lightning_duration_ms {
             state.lightning_duration_ms = 0;
             state.flash_intensity = 0.0;
+            state.bolt.clear();
             // Schedule next lightning
             let (min_interval, max_interval) = speed.lightning_interval_ms();
             state.next_lightning_interval = min_interval
-                + (state.lightning_seed.wrapping_mul(17) % (max_interval - min_interval));
-            state.lightning_seed = state.lightning_seed.wrapping_add(elapsed_ms);
+                + next_random(&mut state.lightning_seed) % (max_interval - min_interval);
         } else {
             // Decay flash intensity
             let progress = time_since_flash as f32 / state.lightning_duration_ms as f32;
@@ -241,31 +542,41 @@ pub fn update_storm(
     } else if time_since_flash > state.next_lightning_interval {
         // Trigger new lightning
         state.last_lightning_ms = elapsed_ms;
-        state.lightning_duration_ms = 100 + (state.lightning_seed % 151); // 100-250ms
+        state.lightning_duration_ms = 100 + next_random(&mut state.lightning_seed) % 151; // 100-250ms
         state.flash_intensity = 1.0;
+        state.bolt = lightning_bolt(next_random(&mut state.lightning_seed), width, height);
     }
 }
 
 /// Render a storm character.
-pub fn render_storm_char(state: &StormState, x: u16, y: u16, _elapsed_ms: u64) -> Span<'static> {
-    let x_idx = x as usize;
-    let y_f = y as f32;
-
-    if x_idx >= state.rain_columns.len() {
-        return Span::raw(" ");
+///
+/// While lightning flashes, the bolt is drawn over the rain, and for the first
+/// [`FLASH_FILL_MS`] the whole sky lights up with a shaded fill.
+pub fn render_storm_char(
+    state: &StormState,
+    x: u16,
+    y: u16,
+    elapsed_ms: u64,
+    drift: f32,
+) -> Option<Glyph> {
+    if let Some(glyph) = render_bolt_char(state, x, y) {
+        return Some(glyph);
     }
 
-    let col = &state.rain_columns[x_idx];
-    let distance = (y_f - col.y).abs();
+    let y_f = y as f32;
 
-    // Rain with trail effect (2-3 char vertical streak)
+    // Rain with trail effect (2-3 char streak, slanted by the wind)
     let trail_length = 2.5;
-    let in_trail = distance < trail_length && y_f >= col.y;
+    let drop = state.raindrops.iter().find(|drop| {
+        y_f >= drop.y
+            && y_f - drop.y < trail_length
+            && column_at(drop.x, drop.y, y_f, drift) == i32::from(x)
+    });
 
-    if in_trail {
+    if let Some(drop) = drop {
         // Use storm rain characters
-        let char_idx = col.char_seed % STORM_RAIN_CHARS.len();
-        let ch = STORM_RAIN_CHARS[char_idx];
+        let ch = rain_char(drop.char_seed, STORM_RAIN_CHARS, drift);
+        let distance = y_f - drop.y;
 
         // Calculate trail fade (brighter at head)
         let trail_pos = distance / trail_length;
@@ -295,26 +606,44 @@ pub fn render_storm_char(state: &StormState, x: u16, y: u16, _elapsed_ms: u64) -
             )
         };
 
-        Span::styled(ch.to_string(), Style::new().fg(color))
+        Some(Glyph::new(ch, color))
+    } else if state.flash_intensity > 0.0
+        && elapsed_ms.saturating_sub(state.last_lightning_ms) < FLASH_FILL_MS
+    {
+        // The flash lights up the whole sky, fading with the flash
+        let color = scale_color(Color::Rgb(170, 180, 230), state.flash_intensity * 0.6);
+        Some(Glyph::new('░', color))
     } else if state.flash_intensity > 0.3 {
         // Lightning ambient glow - sparse flicker effect
-        let seed = (x as usize).wrapping_mul(17).wrapping_add(y as usize * 31);
+        let seed = mix(0, x.into(), y.into());
         if seed % 8 < 3 {
             let brightness = (state.flash_intensity * 80.0) as u8;
-            Span::styled(
-                "·".to_string(),
-                Style::new().fg(Color::Rgb(
-                    brightness + 40,
-                    brightness + 50,
-                    brightness + 80,
-                )),
-            )
+            Some(Glyph::new(
+                '·',
+                Color::Rgb(brightness + 40, brightness + 50, brightness + 80),
+            ))
         } else {
-            Span::raw(" ")
+            None
         }
     } else {
-        Span::raw(" ")
+        None
+    }
+}
+
+/// Render the lightning bolt character at a position, if the bolt crosses it.
+fn render_bolt_char(state: &StormState, x: u16, y: u16) -> Option<Glyph> {
+    let row = y as usize;
+    if state.bolt.get(row) != Some(&x) {
+        return None;
     }
+    // Lean toward where the bolt goes on the next row
+    let ch = match state.bolt.get(row + 1) {
+        Some(&next) if next > x => '╲',
+        Some(&next) if next < x => '╱',
+        _ => '│',
+    };
+    let color = scale_color(Color::Rgb(235, 240, 255), 0.5 + 0.5 * state.flash_intensity);
+    Some(Glyph::new(ch, color))
 }
 
 // ========== WIND STATE (Stateful) ==========
@@ -336,22 +665,53 @@ pub struct WindStreak {
 
 /// Initialize wind streaks.
 pub fn init_wind_streaks(width: u16, height: u16, init_seed: u64) -> Vec<WindStreak> {
-    let num_streaks = ((width as usize * height as usize) / 40).clamp(10, 200);
-    (0..num_streaks)
-        .map(|i| {
-            let mixed = i.wrapping_mul(37).wrapping_add(init_seed as usize);
-            let start_offset = ((mixed.wrapping_mul(19)) % (width as usize * 2)) as f32;
-            WindStreak {
-                x: -start_offset,
-                y: ((mixed.wrapping_mul(23)) % height as usize) as u16,
-                speed: 0.5 + ((mixed.wrapping_mul(13)) % 10) as f32 / 10.0,
-                length: 3 + ((mixed.wrapping_mul(7)) % 6) as u8,
-                char_seed: mixed.wrapping_mul(31),
-            }
-        })
+    (0..wind_streak_count(width, height))
+        .map(|i| wind_streak(i, width, height, init_seed))
         .collect()
 }
 
+/// Fit existing wind streaks to resized dimensions without restarting them.
+///
+/// Streaks keep their relative position, and streaks are dropped or added so
+/// the density stays the same.
+pub fn resize_wind_streaks(
+    streaks: &mut Vec<WindStreak>,
+    old_width: u16,
+    old_height: u16,
+    width: u16,
+    height: u16,
+    init_seed: u64,
+) {
+    let x_scale = super::scale_factor(old_width, width);
+    let y_scale = super::scale_factor(old_height, height);
+    let count = wind_streak_count(width, height);
+    streaks.truncate(count);
+    for streak in streaks.iter_mut() {
+        streak.x *= x_scale;
+        streak.y = ((streak.y as f32 * y_scale) as u16).min(height.saturating_sub(1));
+    }
+    let kept = streaks.len();
+    streaks.extend((kept..count).map(|i| wind_streak(i, width, height, init_seed)));
+}
+
+/// Number of wind streaks for the given dimensions.
+fn wind_streak_count(width: u16, height: u16) -> usize {
+    ((width as usize * height as usize) / 40).clamp(10, 200)
+}
+
+/// Create the wind streak at index `i`.
+fn wind_streak(i: usize, width: u16, height: u16, init_seed: u64) -> WindStreak {
+    let random = |salt| mix(init_seed, i as u64, salt);
+    let start_offset = (random(0) % (u64::from(width) * 2).max(1)) as f32;
+    WindStreak {
+        x: -start_offset,
+        y: (random(1) % u64::from(height).max(1)) as u16,
+        speed: 0.5 + (random(2) % 10) as f32 / 10.0,
+        length: 3 + (random(3) % 6) as u8,
+        char_seed: random(4) as usize,
+    }
+}
+
 /// Update wind streak positions.
 pub fn update_wind(
     streaks: &mut [WindStreak],
@@ -368,14 +728,14 @@ pub fn update_wind(
         if streak.x > width as f32 + streak.length as f32 {
             streak.x = -(streak.length as f32);
             // Move to new random row
-            streak.y = ((streak.char_seed.wrapping_mul(17)) % height as usize) as u16;
+            streak.y = (mix(0, streak.char_seed as u64, 0) % u64::from(height).max(1)) as u16;
             streak.char_seed = streak.char_seed.wrapping_add(1);
         }
     }
 }
 
 /// Render wind at position.
-pub fn render_wind_char(streaks: &[WindStreak], x: u16, y: u16, elapsed_ms: u64) -> Span<'static> {
+pub fn render_wind_char(streaks: &[WindStreak], x: u16, y: u16, elapsed_ms: u64) -> Option<Glyph> {
     let x_f = x as f32;
 
     for streak in streaks {
@@ -400,14 +760,11 @@ pub fn render_wind_char(streaks: &[WindStreak], x: u16, y: u16, elapsed_ms: u64)
             let shimmer = ((elapsed_ms as f32 / 200.0 + x_f * 0.5).sin() * 20.0) as i16;
             let r = (base as i16 + shimmer).clamp(40, 180) as u8;
 
-            return Span::styled(
-                ch.to_string(),
-                Style::new().fg(Color::Rgb(r, base + 10, base + 20)),
-            );
+            return Some(Glyph::new(ch, Color::Rgb(r, base + 10, base + 20)));
         }
     }
 
-    Span::raw(" ")
+    None
 }
 
 // ========== SUNNY (Stateless) ==========
@@ -420,7 +777,7 @@ pub fn render_sunny_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let x_f = x as f32;
     let y_f = y as f32;
     let w_f = width.max(1) as f32;
@@ -440,10 +797,7 @@ pub fn render_sunny_char(
     if distance < sun_radius {
         let core_intensity = 1.0 - (distance / sun_radius);
         let brightness = (200.0 + core_intensity * 55.0) as u8;
-        return Span::styled(
-            "●".to_string(),
-            Style::new().fg(Color::Rgb(255, brightness, 100)),
-        );
+        return Some(Glyph::new('●', Color::Rgb(255, brightness, 100)));
     }
 
     // Rays emanating from sun
@@ -473,18 +827,18 @@ pub fn render_sunny_char(
             let g = (180.0 + combined_intensity * 50.0) as u8;
             let b = (50.0 + combined_intensity * 30.0) as u8;
 
-            return Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(r, g, b)));
+            return Some(Glyph::new(ch, Color::Rgb(r, g, b)));
         }
     }
 
     // Background warmth - subtle warm tint
-    let seed = (x as usize).wrapping_mul(31).wrapping_add(y as usize * 17);
+    let seed = mix(0, x.into(), y.into());
     if seed % 150 < 2 {
-        let ch = SUN_CHARS[seed % 3]; // Small sparkle
-        return Span::styled(ch.to_string(), Style::new().fg(Color::Rgb(200, 180, 80)));
+        let ch = SUN_CHARS[(seed % 3) as usize]; // Small sparkle
+        return Some(Glyph::new(ch, Color::Rgb(200, 180, 80)));
     }
 
-    Span::raw(" ")
+    None
 }
 
 // ========== CLOUDY (Stateless) ==========
@@ -506,7 +860,7 @@ pub fn render_cloudy_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let x_norm = x as f32 / width.max(1) as f32;
     let y_norm = y as f32 / height.max(1) as f32;
 
@@ -526,7 +880,7 @@ pub fn render_cloudy_char(
     let final_density = density * vertical_factor;
 
     if final_density < 0.2 {
-        return Span::raw(" ");
+        return None;
     }
 
     // Character based on density
@@ -544,7 +898,7 @@ pub fn render_cloudy_char(
     let gray = (120.0 + final_density * 60.0) as u8;
     let color = Color::Rgb(gray, gray + 5, gray + 10);
 
-    Span::styled(ch.to_string(), Style::new().fg(color))
+    Some(Glyph::new(ch, color))
 }
 
 // ========== FOGGY (Stateless) ==========
@@ -569,7 +923,7 @@ pub fn render_foggy_char(
     height: u16,
     elapsed_ms: u64,
     speed: AnimationSpeed,
-) -> Span<'static> {
+) -> Option<Glyph> {
     let w_f = width.max(1) as f32;
     let h_f = height.max(1) as f32;
 
@@ -598,16 +952,14 @@ pub fn render_foggy_char(
 
     // Sparse fog - only show when density is high enough
     if final_density < 0.35 {
-        return Span::raw(" ");
+        return None;
     }
 
     // Further sparsity based on position hash for natural gaps
-    let seed = (x as usize)
-        .wrapping_mul(31)
-        .wrapping_add((y as usize).wrapping_mul(17));
+    let seed = mix(0, x.into(), y.into()) as usize;
     let threshold = 0.35 + ((seed % 30) as f32 / 100.0);
     if final_density < threshold {
-        return Span::raw(" ");
+        return None;
     }
 
     // Character selection - use softer chars for lighter fog
@@ -625,5 +977,370 @@ pub fn render_foggy_char(
     let gray = (100.0 + intensity * 55.0) as u8;
     let color = Color::Rgb(gray, gray + 8, gray + 20);
 
-    Span::styled(ch.to_string(), Style::new().fg(color))
+    Some(Glyph::new(ch, color))
+}
+
+#[cfg(test)]
+mod tests {
+    use sigye_core::precipitation::MODERATE;
+
+    use super::*;
+
+    fn drop_at(x: f32, y: f32) -> RainDrop {
+        RainDrop {
+            x,
+            y,
+            speed: 1.0,
+            char_seed: 0,
+            weight: 0,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_wind_drift() {
+        assert_eq!(wind_drift(0.0, 1234), 0.0);
+        // A quarter of a gust period in, the gust is at its strongest
+        let peak = (GUST_PERIOD_MS / 4.0) as u64;
+        assert!((wind_drift(30.0, 0) - 1.0).abs() < 1e-6);
+        assert!((wind_drift(30.0, peak) - 1.3).abs() < 1e-3);
+        assert!(wind_drift(15.0, 0) < wind_drift(30.0, 0));
+        // Gales are capped
+        assert_eq!(wind_drift(200.0, 0), wind_drift(45.0, 0));
+        for elapsed_ms in (0..20_000).step_by(250) {
+            let drift = wind_drift(30.0, elapsed_ms);
+            assert!((0.7 - 1e-3..=1.3 + 1e-3).contains(&drift), "{drift}");
+        }
+    }
+
+    #[test]
+    fn test_rain_drifts_with_the_wind() {
+        // 400ms at medium speed falls 15 rows, and half a column per row drifts 7.5
+        let mut drops = vec![drop_at(10.0, 0.0)];
+        update_rain(
+            &mut drops,
+            400,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            0.5,
+            MODERATE,
+        );
+        assert_eq!(drops[0].y, 15.0);
+        assert_eq!(drops[0].x, 17.5);
+
+        // Wind from the other side, and no wind
+        let mut drops = [drop_at(10.0, 0.0), drop_at(10.0, 0.0)];
+        update_rain(
+            &mut drops[..1],
+            400,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            -0.5,
+            MODERATE,
+        );
+        update_rain(
+            &mut drops[1..],
+            400,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            0.0,
+            MODERATE,
+        );
+        assert_eq!(drops[0].x, 2.5);
+        assert_eq!(drops[1].x, 10.0);
+    }
+
+    #[test]
+    fn test_heavy_rain_is_denser() {
+        use sigye_core::precipitation::{HEAVY, LIGHT};
+
+        let active = |drops: &[RainDrop]| drops.iter().filter(|drop| drop.active).count();
+        let light = init_raindrops(200, 30, 5, LIGHT);
+        let heavy = init_raindrops(200, 30, 5, HEAVY);
+        assert!(
+            active(&heavy) >= 2 * active(&light),
+            "{} vs {}",
+            active(&heavy),
+            active(&light)
+        );
+        assert_eq!(active(&heavy), 200);
+        let flakes = |intensity| {
+            init_snowflakes(200, 30, 5, intensity)
+                .iter()
+                .filter(|flake| flake.active)
+                .count()
+        };
+        assert!(flakes(HEAVY) >= 2 * flakes(LIGHT));
+
+        // Heavier rain looks heavier and falls faster
+        let weight =
+            |drops: &[RainDrop]| drops.iter().map(|drop| u32::from(drop.weight)).sum::<u32>();
+        assert!(weight(&heavy) > weight(&light));
+        let mut drops = [drop_at(10.0, 0.0), drop_at(10.0, 0.0)];
+        update_rain(
+            &mut drops[..1],
+            400,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            0.0,
+            LIGHT,
+        );
+        update_rain(
+            &mut drops[1..],
+            400,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            0.0,
+            HEAVY,
+        );
+        assert!(drops[1].y > drops[0].y);
+
+        // Unseen drops aren't drawn
+        let mut drop = drop_at(10.0, 5.0);
+        drop.active = false;
+        assert_eq!(render_rain_char(&[drop], 10, 5, 0.0), None);
+    }
+
+    #[test]
+    fn test_snow_lands_in_another_column() {
+        let mut flakes = init_snowflakes(40, 20, 9, MODERATE);
+        let start = flakes[5].x;
+        flakes[5].y = 0.0;
+        flakes[5].speed = 0.5;
+        // 800ms at medium speed falls 3 rows, pushed twice as far as rain
+        update_snow(
+            &mut flakes[5..6],
+            800,
+            40,
+            20,
+            AnimationSpeed::Medium,
+            0.5,
+            MODERATE,
+        );
+        assert!((flakes[5].y - 3.0).abs() < 1e-5);
+        assert!((flakes[5].x - (start + 3.0)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_particles_wrap_sideways() {
+        let mut drops = [drop_at(39.0, 0.0), drop_at(0.5, 0.0)];
+        update_rain(
+            &mut drops[..1],
+            40,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            1.0,
+            MODERATE,
+        );
+        update_rain(
+            &mut drops[1..],
+            40,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            -1.0,
+            MODERATE,
+        );
+        assert_eq!(drops[0].x, 0.5);
+        assert_eq!(drops[1].x, 39.0);
+
+        // Falling off the bottom starts over at the top, where it left off sideways
+        let mut drops = vec![drop_at(20.0, 30.5)];
+        update_rain(
+            &mut drops,
+            40,
+            40,
+            30,
+            AnimationSpeed::Medium,
+            1.0,
+            MODERATE,
+        );
+        assert_eq!((drops[0].x, drops[0].y), (21.5, -1.0));
+    }
+
+    #[test]
+    fn test_rain_slants_with_the_wind() {
+        let drops = [drop_at(10.0, 5.0)];
+        assert_eq!(render_rain_char(&drops, 10, 5, 0.5).unwrap().ch, '\\');
+        assert_eq!(render_rain_char(&drops, 10, 5, -0.5).unwrap().ch, '/');
+        assert!(RAIN_CHARS.contains(&render_rain_char(&drops, 10, 5, 0.1).unwrap().ch));
+
+        // Storm streaks trail downwind of the drop
+        let mut storm = init_storm(20, 10, 1);
+        storm.raindrops = vec![drop_at(10.0, 5.0)];
+        let streak: Vec<u16> = (5..8)
+            .map(|y| {
+                (0..20)
+                    .find(|&x| render_storm_char(&storm, x, y, 0, 1.0).is_some())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(streak, [10, 11, 12]);
+    }
+
+    fn flake_at(x: f32, y: f32) -> Snowflake {
+        Snowflake {
+            x,
+            y,
+            speed: 1.0,
+            drift_phase: 0.0,
+            size: 0,
+            char_seed: 0,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_flakes_settle_on_the_pile() {
+        let mut pile = vec![0.0, 1.0, 0.0];
+        // Touching the ground, touching the pile, and still falling
+        let mut flakes = [flake_at(0.0, 9.0), flake_at(1.2, 8.0), flake_at(2.0, 5.0)];
+        settle_snow(&mut flakes, &mut pile, 0, 10, MODERATE);
+        assert_eq!(pile, [FLAKE_DEPTH, 1.0 + FLAKE_DEPTH, 0.0]);
+        assert_eq!(flakes[0].y, -2.0);
+        assert_eq!(flakes[1].y, -2.0);
+        assert_eq!(flakes[2].y, 5.0);
+
+        // The pile stops growing at its maximum depth
+        let mut pile = vec![MAX_PILE_ROWS];
+        settle_snow(&mut [flake_at(0.0, 7.0)], &mut pile, 0, 10, MODERATE);
+        assert_eq!(pile, [MAX_PILE_ROWS]);
+
+        // No room above the ground
+        let mut pile = vec![0.0];
+        let mut flakes = [flake_at(0.0, 3.0)];
+        settle_snow(&mut flakes, &mut pile, 0, 0, MODERATE);
+        assert_eq!((pile[0], flakes[0].y), (0.0, 3.0));
+    }
+
+    #[test]
+    fn test_pile_melts_slowly() {
+        let mut pile = vec![MAX_PILE_ROWS, 0.5, 0.0];
+        settle_snow(&mut [], &mut pile, 60_000, 10, MODERATE);
+        assert!((pile[0] - MAX_PILE_ROWS * 0.9).abs() < 1e-4, "{pile:?}");
+        assert!(pile[1] > 0.0 && pile[1] < 0.5);
+        assert_eq!(pile[2], 0.0);
+
+        // Ten minutes melt even the deepest snow
+        for _ in 0..9 {
+            settle_snow(&mut [], &mut pile, 60_000, 10, MODERATE);
+        }
+        assert!(pile.iter().all(|&depth| depth < 1e-4), "{pile:?}");
+    }
+
+    #[test]
+    fn test_pile_is_drawn_in_partial_blocks() {
+        let pile = [0.5, 1.25, 0.0];
+        let ch = |x, y| render_snow_pile_char(&pile, x, y, 10).map(|glyph| glyph.ch);
+        assert_eq!(ch(0, 9), Some('▄'));
+        assert_eq!(ch(0, 8), None);
+        assert_eq!(ch(1, 9), Some('█'));
+        assert_eq!(ch(1, 8), Some('▂'));
+        assert_eq!(ch(2, 9), None);
+        // Never on or below the ground, nor past the pile
+        assert_eq!(ch(0, 10), None);
+        assert_eq!(ch(3, 9), None);
+    }
+
+    #[test]
+    fn test_pile_resizes_with_the_width() {
+        let mut pile = vec![1.0, 2.0, 3.0];
+        resize_snow_pile(&mut pile, 5);
+        assert_eq!(pile, [1.0, 2.0, 3.0, 0.0, 0.0]);
+        resize_snow_pile(&mut pile, 2);
+        assert_eq!(pile, [1.0, 2.0]);
+    }
+
+    /// A storm in the middle of a flash that struck at 1000ms, with the rain out of view.
+    fn flashing_storm(width: u16, height: u16) -> StormState {
+        let mut state = init_storm(width, height, 7);
+        for drop in &mut state.raindrops {
+            drop.y = -100.0;
+        }
+        state.last_lightning_ms = 1000;
+        state.lightning_duration_ms = 200;
+        state.flash_intensity = 1.0;
+        state.bolt = lightning_bolt(7, width, height);
+        state
+    }
+
+    #[test]
+    fn test_bolt_stays_inside_and_continuous() {
+        for seed in 0..200 {
+            for (width, height) in [(1, 10), (2, 5), (80, 24), (200, 60)] {
+                let bolt = lightning_bolt(seed, width, height);
+                assert_eq!(bolt.len(), (f32::from(height) * 0.6).ceil() as usize);
+                assert!(bolt.iter().all(|&x| x < width), "{seed}: {bolt:?}");
+                assert!(
+                    bolt.windows(2).all(|pair| pair[0].abs_diff(pair[1]) <= 1),
+                    "{seed}: {bolt:?}"
+                );
+            }
+        }
+        assert!(lightning_bolt(1, 0, 10).is_empty());
+        assert!(lightning_bolt(1, 10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_bolt_follows_the_seed() {
+        assert_eq!(lightning_bolt(42, 80, 24), lightning_bolt(42, 80, 24));
+        let bolts: Vec<Vec<u16>> = (0..10)
+            .map(|seed| lightning_bolt(seed * 2, 80, 24))
+            .collect();
+        assert!(bolts.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_bolt_is_drawn_during_the_flash() {
+        let state = flashing_storm(40, 10);
+        for (row, window) in state.bolt.windows(2).enumerate() {
+            let glyph = render_storm_char(&state, window[0], row as u16, 1150, 0.0).unwrap();
+            let expected = match window[1].cmp(&window[0]) {
+                std::cmp::Ordering::Greater => '╲',
+                std::cmp::Ordering::Less => '╱',
+                std::cmp::Ordering::Equal => '│',
+            };
+            assert_eq!(glyph.ch, expected, "row {row}");
+        }
+        // Nothing below the bolt once the sky fill has passed
+        assert_eq!(render_storm_char(&state, 0, 9, 1150, 0.0), None);
+    }
+
+    #[test]
+    fn test_flash_fills_the_sky_briefly() {
+        let mut state = flashing_storm(40, 10);
+        let empty = (0..40).find(|&x| state.bolt[0] != x).unwrap();
+        let fill = render_storm_char(&state, empty, 0, 1000 + FLASH_FILL_MS - 1, 0.0).unwrap();
+        assert_eq!(fill.ch, '░');
+        assert_ne!(
+            render_storm_char(&state, empty, 9, 1000 + FLASH_FILL_MS, 0.0).map(|glyph| glyph.ch),
+            Some('░')
+        );
+
+        // The fill dims as the flash fades
+        state.flash_intensity = 0.4;
+        let faded = render_storm_char(&state, empty, 0, 1040, 0.0).unwrap();
+        let red = |glyph: Glyph| match glyph.fg {
+            Color::Rgb(r, _, _) => r,
+            _ => 0,
+        };
+        assert!(red(faded) < red(fill));
+    }
+
+    #[test]
+    fn test_flash_ends_with_the_bolt() {
+        let mut state = init_storm(40, 10, 3);
+        update_storm(&mut state, 10_000, 50, 40, 10, AnimationSpeed::Medium, 0.0);
+        assert!(state.flash_intensity > 0.0);
+        assert_eq!(state.bolt.len(), 6);
+        update_storm(&mut state, 10_400, 50, 40, 10, AnimationSpeed::Medium, 0.0);
+        assert_eq!(state.flash_intensity, 0.0);
+        assert!(state.bolt.is_empty());
+    }
 }
@@ -1,24 +1,40 @@
-//! Character constants for background animations.
+//! Character constants for background animations, with ASCII fallbacks.
+
+use ratatui::buffer::Buffer;
 
 /// Characters used for starfield background.
 pub const STAR_CHARS: &[char] = &['.', '*', '+', '·', '✦', '✧'];
 
+/// Characters used for the faint, far away stars of the starfield.
+pub const FAINT_STAR_CHARS: &[char] = &['.', '·'];
+
 /// Characters used for matrix rain.
+///
+/// Halfwidth katakana, so every column is one cell wide like the other glyphs.
 pub const MATRIX_CHARS: &[char] = &[
-    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
-    'チ', 'ツ', 'テ', 'ト', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+    'ｱ', 'ｲ', 'ｳ', 'ｴ', 'ｵ', 'ｶ', 'ｷ', 'ｸ', 'ｹ', 'ｺ', 'ｻ', 'ｼ', 'ｽ', 'ｾ', 'ｿ', 'ﾀ', 'ﾁ', 'ﾂ', 'ﾃ',
+    'ﾄ', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
 ];
 
+/// Characters used for fireplace flames, from faint to intense.
+pub const FIRE_CHARS: &[char] = &['░', '▒', '▓', '█'];
+
+/// Characters used for Game of Life cells, from newborn to old.
+pub const LIFE_CHARS: &[char] = &['█', '▓', '▒'];
+
 /// Characters used for snowfall background.
 pub const SNOW_CHARS: &[char] = &['*', '·', '•', '❄', '❅', '❆', '✦', '✧', '°'];
 
+/// Lower blocks from an eighth to a full cell, for settled snow and sparklines.
+pub const LOWER_BLOCK_CHARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
 /// Characters used for frost crystals.
 pub const FROST_CHARS: &[char] = &['·', '•', '*', '×', '✕', '✱', '░'];
 
 // Weather character constants
 
-/// Characters used for rain drops - vertical streaks.
-pub const RAIN_CHARS: &[char] = &['│', '|', '¦', '┃', '╏', '┊', '┆'];
+/// Characters used for rain drops - vertical streaks, from the lightest to the heaviest.
+pub const RAIN_CHARS: &[char] = &['┊', '┆', '¦', '╏', '|', '│', '┃'];
 
 /// Characters used for heavy storm rain - more intense.
 pub const STORM_RAIN_CHARS: &[char] = &['┃', '║', '│', '|', '/', '\\'];
@@ -34,3 +50,143 @@ pub const CLOUD_CHARS: &[char] = &['░', '▒', '▓', '·', '•', '○', '◌
 
 /// Characters used for fog/mist - soft wisps and dots.
 pub const FOG_CHARS: &[char] = &['·', '.', '\'', ':', '°', '∙', ','];
+
+/// Replacement for glyphs that have no close ASCII look-alike.
+const UNKNOWN_ASCII: char = '?';
+
+/// ASCII stand-ins for katakana, picked by code point so columns still vary.
+const KATAKANA_ASCII: &[char] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'R', 'S', 'T', 'U', 'V',
+    'W', 'X', 'Y', 'Z', '$', '%', '&', '=', '<', '>',
+];
+
+/// Pure-ASCII look-alike for a glyph, for terminals or fonts without Unicode support.
+///
+/// Covers every character in the tables above, box drawing and block elements
+/// (block cells become `#`), and the symbols used in text lines. ASCII passes
+/// through unchanged and anything else becomes `?`.
+pub fn ascii_fallback(ch: char) -> char {
+    if ch.is_ascii() {
+        return ch;
+    }
+    match ch {
+        // Dots and small bullets
+        '·' | '•' | '∙' | '⋅' | '・' | '…' | '․' => '.',
+        // Circles and degrees
+        '°' | '○' | '◌' | '◦' | '●' | '◯' | 'ø' => 'o',
+        // Stars, sparkles and snowflakes
+        '✦' | '✧' | '✱' | '❄' | '❅' | '❆' | '★' | '☆' | '☀' | '⁂' => '*',
+        '×' | '✕' => 'x',
+        '∼' | '≈' | '〜' => '~',
+        '¦' => '|',
+        '‐' | '‑' | '–' | '—' | '―' | '−' => '-',
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '←' | '◀' => '<',
+        '→' | '▶' => '>',
+        '↑' => '^',
+        '↓' => 'v',
+        'µ' => 'u',
+        // Shades from faint to dense, then solid and partial blocks
+        '░' => '.',
+        '▒' => ':',
+        '▓' => '%',
+        '▁' | '▂' => '_',
+        '\u{2580}'..='\u{259F}' | '■' | '▪' => '#',
+        '\u{2500}'..='\u{257F}' => box_drawing_fallback(ch),
+        // Fullwidth forms of ASCII
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0).unwrap_or(UNKNOWN_ASCII),
+        // Katakana, full and halfwidth
+        '\u{30A0}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9D}' => {
+            KATAKANA_ASCII[ch as usize % KATAKANA_ASCII.len()]
+        }
+        _ => UNKNOWN_ASCII,
+    }
+}
+
+/// ASCII look-alike for a box drawing character.
+fn box_drawing_fallback(ch: char) -> char {
+    match ch {
+        '═' => '=',
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            '-'
+        }
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            '|'
+        }
+        '╱' => '/',
+        '╲' => '\\',
+        '╳' => 'X',
+        // Corners, tees and crosses
+        _ => '+',
+    }
+}
+
+/// Replace every non-ASCII glyph in `buf` with its [`ascii_fallback`].
+pub fn to_ascii(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        let symbol = cell.symbol();
+        if symbol.is_ascii() {
+            continue;
+        }
+        let ch = symbol.chars().next().map_or(' ', ascii_fallback);
+        cell.set_char(ch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sigye_core::BackgroundStyle;
+
+    use super::*;
+    use crate::snapshot::{buffer_to_string, render_at};
+
+    #[test]
+    fn test_tables_have_ascii_fallbacks() {
+        let tables = [
+            STAR_CHARS,
+            FAINT_STAR_CHARS,
+            MATRIX_CHARS,
+            FIRE_CHARS,
+            LIFE_CHARS,
+            SNOW_CHARS,
+            LOWER_BLOCK_CHARS,
+            FROST_CHARS,
+            RAIN_CHARS,
+            STORM_RAIN_CHARS,
+            SUN_CHARS,
+            WIND_CHARS,
+            CLOUD_CHARS,
+            FOG_CHARS,
+        ];
+        for &ch in tables.iter().copied().flatten() {
+            let fallback = ascii_fallback(ch);
+            assert!(fallback.is_ascii(), "{ch}");
+            assert_ne!(fallback, UNKNOWN_ASCII, "{ch} has no look-alike");
+        }
+    }
+
+    #[test]
+    fn test_ascii_fallback() {
+        assert_eq!(ascii_fallback('7'), '7');
+        assert_eq!(ascii_fallback('█'), '#');
+        assert_eq!(ascii_fallback('▀'), '#');
+        assert_eq!(ascii_fallback('▁'), '_');
+        assert_eq!(ascii_fallback('═'), '=');
+        assert_eq!(ascii_fallback('║'), '|');
+        assert_eq!(ascii_fallback('╗'), '+');
+        assert_eq!(ascii_fallback('╭'), '+');
+        assert_eq!(ascii_fallback('３'), '3');
+        assert_eq!(ascii_fallback('🌦'), '?');
+    }
+
+    #[test]
+    fn test_rendered_backgrounds_are_ascii() {
+        for style in BackgroundStyle::all() {
+            let mut buf = render_at(style, 40, 12, 2000);
+            to_ascii(&mut buf);
+            let text = buffer_to_string(&buf);
+            assert!(text.is_ascii(), "{style:?} left non-ASCII glyphs:\n{text}");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Color utility functions for background animations.
 
-use ratatui::style::Color;
+use ratatui::{buffer::Buffer, style::Color};
 
 /// Map a resource value (0.0-1.0) to a color from cool blue to warm red.
 pub fn resource_to_color(value: f32) -> Color {
@@ -16,44 +16,327 @@ pub fn resource_to_color(value: f32) -> Color {
     hsl_to_rgb(hue, saturation, lightness)
 }
 
+/// Shade glyphs of the reactive backgrounds, solid first, with the intensity each needs.
+const INTENSITY_SHADES: [(f32, char); 5] =
+    [(0.6, '█'), (0.4, '▓'), (0.2, '▒'), (0.1, '░'), (0.05, '·')];
+
+/// Glyph and color of a reactive background cell, `None` when too faint to draw.
+///
+/// `intensity` (0.0 - 1.0) picks how solid the shade is and brightens it a
+/// little; `heat` (0.0 - 1.0) picks the hue on the same cool blue to warm red
+/// ramp as [`resource_to_color`], so neighbouring cells blend smoothly.
+pub fn intensity_cell(intensity: f32, heat: f32) -> Option<(char, Color)> {
+    let intensity = intensity.clamp(0.0, 1.0);
+    let &(_, ch) = INTENSITY_SHADES
+        .iter()
+        .find(|&&(threshold, _)| intensity > threshold)?;
+
+    let heat = heat.clamp(0.0, 1.0);
+    let hue = 240.0 - (heat * 240.0);
+    let saturation = 0.6 + (heat * 0.4);
+    let lightness = 0.15 + (heat * 0.2) + (intensity * 0.15);
+    Some((ch, hsl_to_rgb(hue, saturation, lightness)))
+}
+
+/// Map a temperature in Celsius to a text color from cold blue to hot red.
+/// The scale runs from -10°C (blue) to 35°C (red).
+pub fn temperature_to_color(temp_c: f32) -> Color {
+    let value = ((temp_c + 10.0) / 45.0).clamp(0.0, 1.0);
+
+    // Same hue ramp as resource_to_color, but bright enough for text
+    let hue = 240.0 - (value * 240.0);
+    hsl_to_rgb(hue, 0.85, 0.6)
+}
+
+/// RGB the terminal's default foreground is assumed to have, for dimming text
+/// drawn without a color of its own: xterm's light gray.
+pub const DEFAULT_FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+
+/// RGB of the 16 named ANSI colors, in palette order, as xterm draws them.
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Approximate RGB of a color, `None` for `Reset`, whose color is up to the terminal.
+///
+/// Named colors use xterm's palette, and indexed ones its 6x6x6 cube and gray ramp.
+pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    Some(match index {
+        0..=15 => ANSI_RGB[usize::from(index)],
+        16..=231 => {
+            let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+            let cube = index - 16;
+            (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+/// Scale a color's brightness by `factor` (0.0 = black, 1.0 = unchanged).
+///
+/// Named and indexed colors become their approximate RGB from [`color_to_rgb`]
+/// once dimmed, and are kept as they are at full brightness. `Reset` is left
+/// to the terminal.
+pub fn scale_color(color: Color, factor: f32) -> Color {
+    let factor = factor.clamp(0.0, 1.0);
+    if factor >= 1.0 {
+        return color;
+    }
+    let Some((r, g, b)) = color_to_rgb(color) else {
+        return color;
+    };
+    let scale = |c: u8| (c as f32 * factor).round() as u8;
+    Color::Rgb(scale(r), scale(g), scale(b))
+}
+
 /// Convert HSL to RGB color.
 pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Color {
-    if s == 0.0 {
-        let v = (l * 255.0) as u8;
-        return Color::Rgb(v, v, v);
+    let (r, g, b) = sigye_core::hsl_to_rgb(h, s, l);
+    Color::Rgb(r, g, b)
+}
+
+/// Drop all colors from `buf`, keeping glyphs and modifiers, for monochrome output.
+pub fn strip_colors(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
     }
+}
 
-    let q = if l < 0.5 {
-        l * (1.0 + s)
-    } else {
-        l + s - l * s
-    };
-    let p = 2.0 * l - q;
+/// Paint `color` behind every cell of `buf` without a background of its own,
+/// for a solid background instead of the terminal's.
+///
+/// Everything else draws only foreground colors, so without this the
+/// terminal's background, transparent or not, shows behind the whole frame.
+pub fn fill_background(buf: &mut Buffer, color: Color) {
+    for cell in &mut buf.content {
+        if cell.bg == Color::Reset {
+            cell.set_bg(color);
+        }
+    }
+}
 
-    let h = h / 360.0;
+/// Scale every color in `buf` by `factor` with [`scale_color`], for a dimmer display.
+///
+/// Text in the terminal's default foreground is dimmed from
+/// [`DEFAULT_FOREGROUND`]; a default background is left to the terminal.
+pub fn dim_colors(buf: &mut Buffer, factor: f32) {
+    if factor >= 1.0 {
+        return;
+    }
+    let (r, g, b) = DEFAULT_FOREGROUND;
+    for cell in &mut buf.content {
+        let fg = match cell.fg {
+            Color::Reset => Color::Rgb(r, g, b),
+            fg => fg,
+        };
+        let (fg, bg) = (scale_color(fg, factor), scale_color(cell.bg, factor));
+        cell.set_fg(fg).set_bg(bg);
+    }
+}
 
-    let r = hue_to_rgb(p, q, h + 1.0 / 3.0);
-    let g = hue_to_rgb(p, q, h);
-    let b = hue_to_rgb(p, q, h - 1.0 / 3.0);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Color::Rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
-}
+    #[test]
+    fn test_strip_colors_keeps_glyphs_and_modifiers() {
+        use ratatui::{layout::Rect, style::Modifier};
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf[(0, 0)]
+            .set_char('#')
+            .set_fg(Color::Rgb(255, 0, 0))
+            .set_bg(Color::Blue)
+            .modifier
+            .insert(Modifier::REVERSED);
 
-fn hue_to_rgb(p: f32, q: f32, mut t: f32) -> f32 {
-    if t < 0.0 {
-        t += 1.0;
+        strip_colors(&mut buf);
+        let cell = &buf[(0, 0)];
+        assert_eq!(cell.symbol(), "#");
+        assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        assert!(cell.modifier.contains(Modifier::REVERSED));
     }
-    if t > 1.0 {
-        t -= 1.0;
+
+    #[test]
+    fn test_fill_background_keeps_set_backgrounds() {
+        use ratatui::layout::Rect;
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf[(1, 0)].set_bg(Color::Blue);
+        fill_background(&mut buf, Color::Black);
+        assert_eq!(buf[(0, 0)].bg, Color::Black);
+        assert_eq!(buf[(1, 0)].bg, Color::Blue);
+    }
+
+    #[test]
+    fn test_dim_colors_scales_fg_and_bg() {
+        use ratatui::layout::Rect;
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buf[(0, 0)]
+            .set_char('#')
+            .set_fg(Color::Rgb(200, 100, 50))
+            .set_bg(Color::White);
+        buf[(1, 0)].set_char('#').set_fg(Color::Indexed(46));
+
+        dim_colors(&mut buf, 0.5);
+        let cell = &buf[(0, 0)];
+        assert_eq!(cell.symbol(), "#");
+        assert_eq!(cell.fg, Color::Rgb(100, 50, 25));
+        assert_eq!(cell.bg, Color::Rgb(128, 128, 128));
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(0, 128, 0));
+        // Default text dims too, while the terminal's own background stays
+        assert_eq!(
+            (buf[(2, 0)].fg, buf[(2, 0)].bg),
+            (Color::Rgb(115, 115, 115), Color::Reset)
+        );
+    }
+
+    #[test]
+    fn test_dim_colors_full_brightness_keeps_colors() {
+        use ratatui::layout::Rect;
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf[(0, 0)].set_fg(Color::Cyan).set_bg(Color::Indexed(42));
+        let before = buf.clone();
+        dim_colors(&mut buf, 1.0);
+        assert_eq!(buf, before);
+    }
+
+    #[test]
+    fn test_color_to_rgb() {
+        assert_eq!(color_to_rgb(Color::Reset), None);
+        assert_eq!(color_to_rgb(Color::Rgb(1, 2, 3)), Some((1, 2, 3)));
+        assert_eq!(color_to_rgb(Color::Cyan), Some((0, 205, 205)));
+        assert_eq!(color_to_rgb(Color::White), Some((255, 255, 255)));
+        // The first 16 indexes are the named colors
+        assert_eq!(color_to_rgb(Color::Indexed(6)), color_to_rgb(Color::Cyan));
+        assert_eq!(color_to_rgb(Color::Indexed(16)), Some((0, 0, 0)));
+        assert_eq!(color_to_rgb(Color::Indexed(46)), Some((0, 255, 0)));
+        assert_eq!(color_to_rgb(Color::Indexed(110)), Some((135, 175, 215)));
+        assert_eq!(color_to_rgb(Color::Indexed(231)), Some((255, 255, 255)));
+        assert_eq!(color_to_rgb(Color::Indexed(232)), Some((8, 8, 8)));
+        assert_eq!(color_to_rgb(Color::Indexed(255)), Some((238, 238, 238)));
+    }
+
+    #[test]
+    fn test_scale_color_approximates_named_and_indexed_colors() {
+        assert_eq!(scale_color(Color::LightRed, 0.5), Color::Rgb(128, 0, 0));
+        assert_eq!(scale_color(Color::Cyan, 0.5), Color::Rgb(0, 103, 103));
+        assert_eq!(scale_color(Color::Indexed(46), 0.5), Color::Rgb(0, 128, 0));
+        // Nearly full brightness stays close to the terminal's own color
+        assert_eq!(scale_color(Color::Cyan, 0.9), Color::Rgb(0, 185, 185));
+        assert_eq!(scale_color(Color::Cyan, 1.0), Color::Cyan);
+        assert_eq!(scale_color(Color::Indexed(42), 1.0), Color::Indexed(42));
+    }
+
+    #[test]
+    fn test_scale_color_scales_rgb() {
+        assert_eq!(
+            scale_color(Color::Rgb(200, 100, 50), 0.3),
+            Color::Rgb(60, 30, 15)
+        );
+        assert_eq!(
+            scale_color(Color::Rgb(200, 100, 50), 1.0),
+            Color::Rgb(200, 100, 50)
+        );
+        assert_eq!(
+            scale_color(Color::Rgb(200, 100, 50), 0.0),
+            Color::Rgb(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_scale_color_clamps_factor() {
+        assert_eq!(
+            scale_color(Color::Rgb(10, 20, 30), 2.0),
+            Color::Rgb(10, 20, 30)
+        );
+        assert_eq!(
+            scale_color(Color::Rgb(10, 20, 30), -1.0),
+            Color::Rgb(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_intensity_cell_shades() {
+        let ch = |intensity| intensity_cell(intensity, 0.5).map(|(ch, _)| ch);
+        assert_eq!(ch(0.0), None);
+        assert_eq!(ch(0.05), None);
+        assert_eq!(ch(0.08), Some('·'));
+        assert_eq!(ch(0.15), Some('░'));
+        assert_eq!(ch(0.3), Some('▒'));
+        assert_eq!(ch(0.5), Some('▓'));
+        assert_eq!(ch(0.9), Some('█'));
+        // Out of range values clamp
+        assert_eq!(ch(7.0), Some('█'));
+        assert_eq!(ch(-1.0), None);
+    }
+
+    #[test]
+    fn test_intensity_cell_colors() {
+        let rgb = |intensity, heat| match intensity_cell(intensity, heat) {
+            Some((_, Color::Rgb(r, g, b))) => (r, g, b),
+            other => panic!("{other:?}"),
+        };
+        // Cool is blue, hot is red
+        let (r, _, b) = rgb(0.5, 0.0);
+        assert!(b > r);
+        let (r, _, b) = rgb(0.5, 1.0);
+        assert!(r > b);
+
+        // Heat blends smoothly: every step is a new color
+        let steps: Vec<_> = (0..=10).map(|i| rgb(0.5, i as f32 / 10.0)).collect();
+        assert!(steps.windows(2).all(|pair| pair[0] != pair[1]));
+
+        // More intense cells are brighter
+        let brightness = |(r, g, b): (u8, u8, u8)| u16::from(r) + u16::from(g) + u16::from(b);
+        assert!(brightness(rgb(0.9, 0.5)) > brightness(rgb(0.2, 0.5)));
+        assert_eq!(rgb(2.0, 3.0), rgb(1.0, 1.0));
     }
 
-    if t < 1.0 / 6.0 {
-        p + (q - p) * 6.0 * t
-    } else if t < 1.0 / 2.0 {
-        q
-    } else if t < 2.0 / 3.0 {
-        p + (q - p) * (2.0 / 3.0 - t) * 6.0
-    } else {
-        p
+    #[test]
+    fn test_scale_color_named_and_reset() {
+        assert_eq!(scale_color(Color::White, 0.5), Color::Rgb(128, 128, 128));
+        assert_eq!(scale_color(Color::Reset, 0.5), Color::Reset);
     }
 }
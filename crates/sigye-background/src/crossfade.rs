@@ -0,0 +1,116 @@
+//! Crossfading between background styles when they change.
+//!
+//! The outgoing styles dim while the incoming ones brighten, drawn over them.
+//! Reactive styles fill the whole frame and would hide the other side, so a
+//! change to or from one fades through black instead: out first, then in.
+
+use sigye_core::BackgroundStyle;
+
+/// How long a crossfade between background styles takes.
+pub const CROSSFADE_MS: u64 = 800;
+
+/// A crossfade away from the styles shown before a change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossfade {
+    /// Styles fading out.
+    pub from: Vec<BackgroundStyle>,
+    /// Elapsed time when the styles changed.
+    start_ms: u64,
+}
+
+impl Crossfade {
+    /// Start fading out `from` at `start_ms`.
+    pub fn new(from: Vec<BackgroundStyle>, start_ms: u64) -> Self {
+        Self { from, start_ms }
+    }
+
+    /// Fraction of the crossfade done at `elapsed_ms` (0.0 - 1.0).
+    pub fn progress(&self, elapsed_ms: u64) -> f32 {
+        let since = elapsed_ms.saturating_sub(self.start_ms);
+        (since as f32 / CROSSFADE_MS as f32).min(1.0)
+    }
+
+    /// Check if the crossfade is over at `elapsed_ms`.
+    pub fn is_done(&self, elapsed_ms: u64) -> bool {
+        self.progress(elapsed_ms) >= 1.0
+    }
+}
+
+/// Brightness of the outgoing and incoming styles at `progress` (0.0 - 1.0)
+/// through a crossfade.
+///
+/// Eased at both ends, so the fade neither starts nor lands abruptly. Through
+/// black, the outgoing styles are gone halfway and the incoming ones start there.
+pub fn fade_factors(progress: f32, through_black: bool) -> (f32, f32) {
+    let ease = |t: f32| {
+        let t = t.clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+    if through_black {
+        (1.0 - ease(progress * 2.0), ease(progress * 2.0 - 1.0))
+    } else {
+        let fade = ease(progress);
+        (1.0 - fade, fade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_progress() {
+        let fade = Crossfade::new(vec![BackgroundStyle::Starfield], 1_000);
+        assert_close(fade.progress(1_000), 0.0);
+        assert_close(fade.progress(1_000 + CROSSFADE_MS / 4), 0.25);
+        assert!(!fade.is_done(1_000 + CROSSFADE_MS - 1));
+        assert!(fade.is_done(1_000 + CROSSFADE_MS));
+        assert_close(fade.progress(60_000), 1.0);
+        // A clock behind the start counts as just started
+        assert_close(fade.progress(0), 0.0);
+    }
+
+    #[test]
+    fn test_crossfade_curve() {
+        assert_eq!(fade_factors(0.0, false), (1.0, 0.0));
+        assert_eq!(fade_factors(1.0, false), (0.0, 1.0));
+        let (out, into) = fade_factors(0.5, false);
+        assert_close(out, 0.5);
+        assert_close(into, 0.5);
+        // Eased: slow at the ends, fast in the middle
+        assert!(fade_factors(0.1, false).1 < 0.1);
+        assert!(fade_factors(0.9, false).1 > 0.9);
+
+        let mut last = 0.0;
+        for step in 1..=100 {
+            let (out, into) = fade_factors(step as f32 / 100.0, false);
+            assert!(into >= last, "step {step}");
+            assert_close(out + into, 1.0);
+            last = into;
+        }
+    }
+
+    #[test]
+    fn test_fade_through_black() {
+        assert_eq!(fade_factors(0.0, true), (1.0, 0.0));
+        assert_eq!(fade_factors(0.5, true), (0.0, 0.0));
+        assert_eq!(fade_factors(1.0, true), (0.0, 1.0));
+        // Only one side is ever visible
+        for step in 0..=100 {
+            let (out, into) = fade_factors(step as f32 / 100.0, true);
+            assert!(out == 0.0 || into == 0.0, "step {step}");
+        }
+        assert_close(fade_factors(0.25, true).0, 0.5);
+        assert_close(fade_factors(0.75, true).1, 0.5);
+    }
+
+    #[test]
+    fn test_out_of_range_progress() {
+        assert_eq!(fade_factors(-1.0, false), (1.0, 0.0));
+        assert_eq!(fade_factors(2.0, true), (0.0, 1.0));
+    }
+}
@@ -4,11 +4,42 @@
 //! including both stateless animations (computed from position/time) and
 //! stateful animations (matrix rain, snowfall) as well as reactive
 //! backgrounds that respond to system metrics.
+//!
+//! Backgrounds render directly into a ratatui [`Buffer`](ratatui::buffer::Buffer)
+//! area, so they can be drawn headlessly or embedded in other ratatui apps.
+//!
+//! # Embedding
+//!
+//! [`BackgroundWidget`] is a ratatui `StatefulWidget` with a [`BackgroundState`]
+//! as its state; see `examples/backdrop.rs` for a minimal app. The contract:
+//!
+//! - Keep one state per background area and pass it to every frame. Stateful
+//!   styles such as matrix rain live in it and start over with a new state.
+//!   It also notices when the styles change and crossfades to the new ones;
+//!   [`BackgroundState::is_crossfading`] tells when frames keep changing because of it.
+//! - Take `elapsed_ms` from a monotonic clock started once, e.g. an
+//!   [`Instant`](std::time::Instant). Stateless styles derive their phase from
+//!   it and stateful ones advance by the time since the last frame, at most
+//!   [`MAX_FRAME_DELTA_MS`].
+//! - Only the reactive styles read [`SystemMetrics`](sigye_core::SystemMetrics);
+//!   without metrics they draw nothing, and every other style ignores them.
+//! - Render the background first and draw your content over it.
 
 mod animations;
 mod chars;
 mod color;
+mod crossfade;
 mod state;
+mod widget;
+
+#[cfg(test)]
+mod snapshot;
 
-pub use color::{hsl_to_rgb, resource_to_color};
-pub use state::BackgroundState;
+pub use chars::{LOWER_BLOCK_CHARS, ascii_fallback, to_ascii};
+pub use color::{
+    DEFAULT_FOREGROUND, color_to_rgb, dim_colors, fill_background, hsl_to_rgb, resource_to_color,
+    scale_color, strip_colors, temperature_to_color,
+};
+pub use crossfade::CROSSFADE_MS;
+pub use state::{BackgroundState, ClearZones, MAX_FRAME_DELTA_MS, MAX_LAYERS, RenderParams};
+pub use widget::BackgroundWidget;
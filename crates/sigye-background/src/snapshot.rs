@@ -0,0 +1,137 @@
+//! Snapshot tests pinning down what each background style draws.
+//!
+//! Styles are rendered headlessly with a fixed seed and compared as character
+//! grids against the golden files in `snapshots/`. After an intended visual
+//! change, regenerate them with `UPDATE_SNAPSHOTS=1 cargo test -p sigye-background`.
+
+use std::path::PathBuf;
+
+use ratatui::{buffer::Buffer, layout::Rect};
+use sigye_core::{AnimationSpeed, BackgroundStyle};
+
+use crate::BackgroundState;
+
+/// Seed used for every snapshot.
+const SEED: u64 = 42;
+
+/// Frame interval used to advance stateful styles up to the snapshot time.
+const FRAME_MS: u64 = 50;
+
+/// Render `style` into a `width` x `height` buffer as it looks at `elapsed_ms`.
+///
+/// Stateful styles are advanced frame by frame from zero, so the result only
+/// depends on the arguments.
+pub fn render_at(style: BackgroundStyle, width: u16, height: u16, elapsed_ms: u64) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut state = BackgroundState::with_seed(SEED);
+    let mut buf = Buffer::empty(area);
+    let frames = (0..elapsed_ms)
+        .step_by(FRAME_MS as usize)
+        .chain([elapsed_ms]);
+    for frame_ms in frames {
+        buf.reset();
+        state.render(
+            area,
+            &mut buf,
+            style,
+            frame_ms,
+            AnimationSpeed::Medium,
+            None,
+        );
+    }
+    buf
+}
+
+/// Convert a buffer into lines of plain text, one per row.
+pub fn buffer_to_string(buf: &Buffer) -> String {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compare `actual` with the golden file `snapshots/<name>.txt`, or rewrite it
+/// when `UPDATE_SNAPSHOTS` is set.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{name}.txt"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{actual}\n")).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "missing snapshot {}: {e}; run with UPDATE_SNAPSHOTS=1",
+            path.display()
+        )
+    });
+    assert!(
+        expected.trim_end_matches('\n') == actual,
+        "snapshot {name} changed:\n--- expected\n{expected}\n--- actual\n{actual}\n"
+    );
+}
+
+/// Snapshot `style` at each of the given times.
+fn check_style(style: BackgroundStyle, times_ms: &[u64]) {
+    for &elapsed_ms in times_ms {
+        let buf = render_at(style, 48, 14, elapsed_ms);
+        let name = format!("{style:?}_{elapsed_ms}ms").to_lowercase();
+        assert_snapshot(&name, &buffer_to_string(&buf));
+    }
+}
+
+#[test]
+fn test_render_is_deterministic() {
+    for style in BackgroundStyle::all() {
+        let first = render_at(style, 20, 8, 1500);
+        let second = render_at(style, 20, 8, 1500);
+        assert_eq!(first, second, "{style:?}");
+    }
+}
+
+#[test]
+fn test_buffer_to_string() {
+    let mut buf = Buffer::empty(Rect::new(0, 0, 3, 2));
+    buf[(1, 0)].set_char('*');
+    buf[(2, 1)].set_char('#');
+    assert_eq!(buffer_to_string(&buf), " * \n  #");
+}
+
+#[test]
+fn test_starfield_snapshots() {
+    check_style(BackgroundStyle::Starfield, &[1000, 4000]);
+}
+
+#[test]
+fn test_matrix_rain_snapshots() {
+    check_style(BackgroundStyle::MatrixRain, &[1000, 4000]);
+}
+
+#[test]
+fn test_snowfall_snapshots() {
+    check_style(BackgroundStyle::Snowfall, &[1000, 4000]);
+}
+
+#[test]
+fn test_aurora_snapshots() {
+    check_style(BackgroundStyle::Aurora, &[1000, 4000]);
+}
+
+#[test]
+fn test_moonlight_snapshots() {
+    check_style(BackgroundStyle::Moonlight, &[1000, 4000]);
+}
+
+#[test]
+fn test_rainy_snapshots() {
+    check_style(BackgroundStyle::Rainy, &[1000, 4000]);
+}
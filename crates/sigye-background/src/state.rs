@@ -1,33 +1,130 @@
 //! Background animation state management.
 
 use ratatui::{
-    Frame,
-    text::{Line, Span},
-    widgets::Paragraph,
+    buffer::Buffer,
+    layout::{Rect, Size},
 };
-use sigye_core::{AnimationSpeed, BackgroundStyle, SystemMetrics};
+use sigye_core::{
+    AnimationSpeed, BackgroundStyle, ClearZone, SystemMetrics, history::MetricsHistory,
+    precipitation,
+};
+
+use crate::animations::{
+    Glyph, constellation, fire, life, matrix, meteor, moon, put_glyph, reactive, stateless, weather,
+};
+use crate::color::scale_color;
+use crate::crossfade::{Crossfade, fade_factors};
+
+/// Maximum number of background styles that can be layered.
+pub const MAX_LAYERS: usize = 3;
+
+/// Longest time stateful animations advance in one frame, so a stall or the
+/// first frame doesn't make everything jump.
+pub const MAX_FRAME_DELTA_MS: u64 = 200;
 
-use crate::animations::{matrix, reactive, stateless, weather};
+/// Wind speed in km/h assumed until one is set: a light breeze.
+const DEFAULT_WIND_KMPH: f32 = 8.0;
+
+/// What a frame of the background is drawn with besides its area and styles.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderParams<'a> {
+    /// Milliseconds since the app started, from a monotonic clock.
+    pub elapsed_ms: u64,
+    /// Animation speed.
+    pub speed: AnimationSpeed,
+    /// System metrics for the reactive styles.
+    pub metrics: Option<&'a SystemMetrics>,
+}
+
+/// Areas kept readable behind foreground content such as the clock digits.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClearZones<'a> {
+    /// Areas of the buffer to keep clear.
+    pub rects: &'a [Rect],
+    /// How background cells inside the areas are treated.
+    pub mode: ClearZone,
+    /// Brightness kept for [`ClearZone::Dim`] (0.0 - 1.0).
+    pub dim_factor: f32,
+}
+
+impl ClearZones<'_> {
+    /// Blank or dim the background cells inside the clear areas.
+    fn apply(&self, area: Rect, buf: &mut Buffer) {
+        if self.mode == ClearZone::Off {
+            return;
+        }
+        for rect in self.rects {
+            let rect = rect.intersection(area);
+            for position in rect.positions() {
+                let Some(cell) = buf.cell_mut(position) else {
+                    continue;
+                };
+                match self.mode {
+                    ClearZone::Off => {}
+                    ClearZone::Clear => {
+                        cell.reset();
+                    }
+                    ClearZone::Dim => {
+                        cell.fg = scale_color(cell.fg, self.dim_factor);
+                    }
+                }
+            }
+        }
+    }
+}
 
 /// Background animation state.
 #[derive(Debug)]
 pub struct BackgroundState {
     /// Matrix rain column states.
     matrix_columns: Vec<matrix::MatrixColumn>,
+    /// Fireplace heat grid.
+    fire_state: Option<fire::FireState>,
+    /// Game of Life board.
+    life_state: Option<life::LifeState>,
     /// Snowfall column states.
-    snow_columns: Vec<weather::SnowColumn>,
+    snowflakes: Vec<weather::Snowflake>,
+    /// Depth of settled snow in each column, in rows (for Snowfall background).
+    snow_pile: Vec<f32>,
     /// Rain column states (for Rainy background).
-    rain_columns: Vec<weather::RainColumn>,
+    raindrops: Vec<weather::RainDrop>,
     /// Storm state (for Stormy background).
     storm_state: Option<weather::StormState>,
     /// Wind streak states (for Windy background).
     wind_streaks: Vec<weather::WindStreak>,
+    /// Shooting stars (for Starfield background).
+    meteor_state: Option<meteor::MeteorState>,
+    /// Constellation among the stars (for Starfield background).
+    constellation_state: constellation::ConstellationState,
     /// Last known terminal width.
     last_width: u16,
     /// Last known terminal height.
     last_height: u16,
-    /// Last update time in milliseconds.
-    last_update_ms: u64,
+    /// Elapsed time at the last update, `None` before the first render.
+    last_update_ms: Option<u64>,
+    /// Moon phase drawn by Moonlight (0.0 new - 0.5 full - 1.0 new).
+    moon_phase: f32,
+    /// Wind speed in km/h blowing rain and snow sideways.
+    wind_kmph: f32,
+    /// How heavily rain and snow fall (0.0 - 1.0).
+    precipitation: f32,
+    /// Outside temperature in °C that Frost grows with, `None` until set.
+    temperature_c: Option<f32>,
+    /// How strong and tall the Aurora is, 1.0 for a typical night.
+    aurora_activity: f32,
+    /// Rows at the bottom of the area that snow doesn't settle on.
+    ground_margin: u16,
+    /// Digits of the time that Matrix rain columns occasionally spell out.
+    time_digits: Vec<char>,
+    /// Recent network throughput drawn as a sparkline by Data Flow.
+    network_history: MetricsHistory,
+    /// Styles drawn by the last render, `None` before the first one.
+    layers: Option<Vec<BackgroundStyle>>,
+    /// Crossfade from the styles shown before the last change.
+    ///
+    /// Both sides keep their animation state until it ends, since each
+    /// style has its own.
+    crossfade: Option<Crossfade>,
     /// Seed captured at initialization for randomness.
     init_seed: u64,
 }
@@ -49,146 +146,628 @@ impl BackgroundState {
             .map(|d| d.as_nanos() as u64)
             .unwrap_or(0);
 
+        Self::with_seed(init_seed)
+    }
+
+    /// Create a background state with a fixed seed, so stateful styles render reproducibly.
+    pub fn with_seed(init_seed: u64) -> Self {
         Self {
             matrix_columns: Vec::new(),
-            snow_columns: Vec::new(),
-            rain_columns: Vec::new(),
+            fire_state: None,
+            life_state: None,
+            snowflakes: Vec::new(),
+            snow_pile: Vec::new(),
+            raindrops: Vec::new(),
             storm_state: None,
             wind_streaks: Vec::new(),
+            meteor_state: None,
+            constellation_state: constellation::ConstellationState::default(),
             last_width: 0,
             last_height: 0,
-            last_update_ms: 0,
+            last_update_ms: None,
+            moon_phase: 0.5,
+            wind_kmph: DEFAULT_WIND_KMPH,
+            precipitation: precipitation::MODERATE,
+            temperature_c: None,
+            aurora_activity: 1.0,
+            ground_margin: 0,
+            time_digits: Vec::new(),
+            network_history: MetricsHistory::default(),
+            layers: None,
+            crossfade: None,
             init_seed,
         }
     }
 
-    /// Render the background to the frame.
+    /// Skip the time since the last render, e.g. while the display was paused.
+    ///
+    /// Stateful styles pick up where they stopped on the next render instead of
+    /// advancing by the whole gap.
+    pub fn skip_gap(&mut self) {
+        self.last_update_ms = None;
+    }
+
+    /// Fit the running animations to a new area size, e.g. right when the
+    /// terminal is resized, so the next render already fills the whole area.
+    ///
+    /// Rendering at a different size does the same, this just does it sooner.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        // Nothing to fit before the first render, or to an empty area
+        if self.layers.is_none() || width == 0 || height == 0 {
+            return;
+        }
+        self.fit_to(&self.shown_stateful(), width, height);
+    }
+
+    /// Seed the stateful styles are randomized with.
+    pub fn seed(&self) -> u64 {
+        self.init_seed
+    }
+
+    /// Set the moon phase drawn by Moonlight, e.g. from
+    /// [`moon_phase`](sigye_core::moon::moon_phase); a full moon until set.
+    pub fn set_moon_phase(&mut self, phase: f32) {
+        self.moon_phase = phase;
+    }
+
+    /// Set the wind speed that slants rain and blows snow sideways, e.g. from
+    /// the weather; `None` falls back to a light breeze.
+    pub fn set_wind_kmph(&mut self, wind_kmph: Option<u32>) {
+        self.wind_kmph = wind_kmph.map_or(DEFAULT_WIND_KMPH, |kmph| kmph as f32);
+    }
+
+    /// Set how heavily rain and snow fall (0.0 - 1.0), e.g. from the weather;
+    /// `None` falls back to moderate.
+    ///
+    /// Drops and flakes already falling pick it up as they start over.
+    pub fn set_precipitation(&mut self, intensity: Option<f32>) {
+        self.precipitation =
+            intensity.map_or(precipitation::MODERATE, precipitation::clamp_intensity);
+    }
+
+    /// Set the outside temperature that Frost grows with, e.g. from the
+    /// weather; `None` grows it as deep as a freeze.
+    pub fn set_temperature_c(&mut self, temp_c: Option<i32>) {
+        self.temperature_c = temp_c.map(|temp_c| temp_c as f32);
+    }
+
+    /// Set how strong and tall the Aurora is, e.g. from the geomagnetic
+    /// activity; `None` falls back to a typical night.
+    pub fn set_aurora_activity(&mut self, activity: Option<f32>) {
+        self.aurora_activity = activity.unwrap_or(1.0);
+    }
+
+    /// Keep `rows` at the bottom of the area clear of settled snow, e.g. for a
+    /// help line drawn over the background.
+    pub fn set_ground_margin(&mut self, rows: u16) {
+        self.ground_margin = rows;
+    }
+
+    /// Set the digits of the time, e.g. "214705", that Matrix rain columns
+    /// occasionally spell out; none until set.
+    pub fn set_time_digits(&mut self, digits: &str) {
+        self.time_digits.clear();
+        self.time_digits.extend(digits.chars());
+    }
+
+    /// Set the recent network throughput that Data Flow draws as a sparkline,
+    /// e.g. from the system monitor; empty until set.
+    pub fn set_network_history(&mut self, history: &MetricsHistory) {
+        self.network_history.clone_from(history);
+    }
+
+    /// Check if the background is crossfading between styles, so it changes
+    /// with every frame even if the styles themselves are still.
+    pub fn is_crossfading(&self) -> bool {
+        self.crossfade.is_some()
+    }
+
+    /// Row that snow settles on top of, in an area `height` rows tall.
+    fn ground(&self, height: u16) -> u16 {
+        height.saturating_sub(self.ground_margin)
+    }
+
+    /// Sideways drift of rain at `elapsed_ms`, in columns per row.
+    fn wind_drift(&self, elapsed_ms: u64) -> f32 {
+        weather::wind_drift(self.wind_kmph, elapsed_ms)
+    }
+
+    /// Render the background into the given area of a buffer.
+    ///
+    /// `elapsed_ms` is the time since the app started, taken from a monotonic
+    /// clock such as [`Instant`](std::time::Instant). Stateless styles derive
+    /// their phase from it; stateful ones advance by the time since the last
+    /// render, at most [`MAX_FRAME_DELTA_MS`].
     pub fn render(
         &mut self,
-        frame: &mut Frame,
+        area: Rect,
+        buf: &mut Buffer,
         style: BackgroundStyle,
         elapsed_ms: u64,
         speed: AnimationSpeed,
         metrics: Option<&SystemMetrics>,
     ) {
-        if style == BackgroundStyle::None {
-            return;
-        }
+        self.render_layers(
+            area,
+            buf,
+            std::slice::from_ref(&style),
+            RenderParams {
+                elapsed_ms,
+                speed,
+                metrics,
+            },
+            ClearZones::default(),
+        );
+    }
 
-        let area = frame.area();
+    /// Render up to [`MAX_LAYERS`] background styles composited in order.
+    ///
+    /// Later layers only overwrite cells where they draw a character, so earlier
+    /// layers stay visible through their blank cells. Reactive backgrounds fill
+    /// the whole frame and can't be layered: one in first position is rendered
+    /// alone, and later ones are skipped.
+    ///
+    /// When the styles change, the old ones fade out under the new ones over
+    /// [`CROSSFADE_MS`](crate::CROSSFADE_MS); styles on both sides stay as they are.
+    ///
+    /// Background cells inside `clear_zones` are blanked or dimmed afterwards.
+    pub fn render_layers(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        layers: &[BackgroundStyle],
+        params: RenderParams,
+        clear_zones: ClearZones,
+    ) {
+        let area = area.intersection(buf.area);
+        self.render_layers_unclipped(area, buf, layers, params);
+        clear_zones.apply(area, buf);
+    }
+
+    /// Composite the layers without applying clear zones.
+    fn render_layers_unclipped(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        layers: &[BackgroundStyle],
+        params: RenderParams,
+    ) {
+        let RenderParams {
+            elapsed_ms, speed, ..
+        } = params;
         let width = area.width;
         let height = area.height;
 
-        // Handle reactive backgrounds separately
-        if style.is_reactive() {
-            if let Some(m) = metrics {
-                self.render_reactive(frame, style, elapsed_ms, speed, m);
-            }
+        // Nothing to draw, and the animations divide by the area size
+        if area.is_empty() {
             return;
         }
 
-        // Reinitialize if dimensions changed or columns not initialized
-        let dimensions_changed = width != self.last_width || height != self.last_height;
+        let layers = drawn_layers(layers);
+        self.follow_layers(&layers, elapsed_ms);
+        let (fading, progress) = self.crossfade.as_ref().map_or((Vec::new(), 1.0), |fade| {
+            (fade.from.clone(), fade.progress(elapsed_ms))
+        });
 
-        if style == BackgroundStyle::MatrixRain
-            && (dimensions_changed || self.matrix_columns.is_empty())
-        {
-            self.matrix_columns = matrix::init_columns(width, height);
+        // Free the state of styles that are no longer shown
+        let mut shown = layers.clone();
+        shown.extend(fading.iter().filter(|style| !layers.contains(style)));
+        self.release_unused(&shown);
+
+        // Carry running animations over to the new size, then create any missing state
+        let stateful: Vec<BackgroundStyle> = shown
+            .into_iter()
+            .filter(|style| !style.is_reactive())
+            .collect();
+        self.fit_to(&stateful, width, height);
+        for &style in &stateful {
+            self.init_style(style, width, height);
         }
-        if style == BackgroundStyle::Snowfall
-            && (dimensions_changed || self.snow_columns.is_empty())
-        {
-            self.snow_columns = weather::init_snow_columns(width, height, self.init_seed);
+
+        // Calculate delta time for stateful animations; the first frame starts the clock
+        let delta_ms = self
+            .last_update_ms
+            .map_or(0, |last| elapsed_ms.saturating_sub(last))
+            .min(MAX_FRAME_DELTA_MS);
+        self.last_update_ms = Some(elapsed_ms);
+
+        for &style in &stateful {
+            self.update_style(style, elapsed_ms, delta_ms, width, height, speed);
         }
-        // Weather animation initialization
-        if style == BackgroundStyle::Rainy && (dimensions_changed || self.rain_columns.is_empty()) {
-            self.rain_columns = weather::init_rain_columns(width, height, self.init_seed);
+
+        // Reactive styles would cover the other side, so those fade through black
+        let through_black = layers
+            .iter()
+            .chain(&fading)
+            .any(|style| style.is_reactive());
+        let (fade_out, fade_in) = fade_factors(progress, through_black);
+        for &style in fading.iter().filter(|style| !layers.contains(style)) {
+            self.draw_style(area, buf, style, params, fade_out);
         }
-        if style == BackgroundStyle::Stormy && (dimensions_changed || self.storm_state.is_none()) {
-            self.storm_state = Some(weather::init_storm(width, height, self.init_seed));
+        for &style in &layers {
+            let brightness = if fading.contains(&style) {
+                1.0
+            } else {
+                fade_in
+            };
+            self.draw_style(area, buf, style, params, brightness);
         }
-        if style == BackgroundStyle::Windy && (dimensions_changed || self.wind_streaks.is_empty()) {
-            self.wind_streaks = weather::init_wind_streaks(width, height, self.init_seed);
+    }
+
+    /// Stateful styles drawn by the last render, fading ones included.
+    fn shown_stateful(&self) -> Vec<BackgroundStyle> {
+        let fading = self.crossfade.iter().flat_map(|fade| fade.from.iter());
+        let mut shown: Vec<BackgroundStyle> = Vec::new();
+        for &style in self.layers.iter().flatten().chain(fading) {
+            if !style.is_reactive() && !shown.contains(&style) {
+                shown.push(style);
+            }
         }
+        shown
+    }
 
-        if dimensions_changed {
-            self.last_width = width;
-            self.last_height = height;
+    /// Resize the state of `styles` if the area size changed since the last fit.
+    fn fit_to(&mut self, styles: &[BackgroundStyle], width: u16, height: u16) {
+        if width == self.last_width && height == self.last_height {
+            return;
+        }
+        for &style in styles {
+            self.resize_style(style, width, height);
         }
+        self.last_width = width;
+        self.last_height = height;
+    }
 
-        // Calculate delta time for stateful animations
-        let delta_ms = elapsed_ms.saturating_sub(self.last_update_ms);
-        self.last_update_ms = elapsed_ms;
+    /// Start a crossfade when the drawn styles change, and end it once it's over.
+    ///
+    /// A change in the middle of a crossfade fades out the styles it was fading in.
+    fn follow_layers(&mut self, layers: &[BackgroundStyle], elapsed_ms: u64) {
+        if let Some(shown) = self.layers.take_if(|shown| shown != layers) {
+            self.crossfade = Some(Crossfade::new(shown, elapsed_ms));
+        }
+        self.layers = Some(layers.to_vec());
+        if self
+            .crossfade
+            .as_ref()
+            .is_some_and(|fade| fade.is_done(elapsed_ms))
+        {
+            self.crossfade = None;
+        }
+    }
 
-        // Update animation states
-        if style == BackgroundStyle::MatrixRain {
-            matrix::update(&mut self.matrix_columns, delta_ms, height, speed);
+    /// Draw one style over the buffer with its colors scaled by `brightness`.
+    fn draw_style(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        style: BackgroundStyle,
+        params: RenderParams,
+        brightness: f32,
+    ) {
+        let RenderParams {
+            elapsed_ms,
+            speed,
+            metrics,
+        } = params;
+        if brightness <= 0.0 {
+            return;
         }
-        if style == BackgroundStyle::Snowfall {
-            weather::update_snow(&mut self.snow_columns, delta_ms, height, speed);
+        let dimmed = brightness < 1.0;
+        if style.is_reactive() {
+            let Some(metrics) = metrics else {
+                return;
+            };
+            self.render_reactive(area, buf, style, elapsed_ms, speed, metrics);
+            if dimmed {
+                for position in area.positions() {
+                    let cell = &mut buf[position];
+                    let (fg, bg) = (
+                        scale_color(cell.fg, brightness),
+                        scale_color(cell.bg, brightness),
+                    );
+                    cell.set_fg(fg).set_bg(bg);
+                }
+            }
+            return;
         }
-        // Weather animation updates
-        if style == BackgroundStyle::Rainy {
-            weather::update_rain(&mut self.rain_columns, delta_ms, height, speed);
+
+        let (width, height) = (area.width, area.height);
+        for y in 0..height {
+            let mut x = 0;
+            while x < width {
+                let glyph = self
+                    .render_char(x, y, area.as_size(), style, elapsed_ms, speed)
+                    .map(|glyph| {
+                        if dimmed {
+                            Glyph::new(glyph.ch, scale_color(glyph.fg, brightness))
+                        } else {
+                            glyph
+                        }
+                    });
+                // Wide glyphs cover the following cell as well
+                x += put_glyph(buf, area.x + x, area.y + y, glyph, width - x);
+            }
         }
-        if style == BackgroundStyle::Stormy
-            && let Some(ref mut storm) = self.storm_state
-        {
-            weather::update_storm(storm, elapsed_ms, delta_ms, height, speed);
+    }
+
+    /// Create the animation state for a stateful style if it is missing.
+    fn init_style(&mut self, style: BackgroundStyle, width: u16, height: u16) {
+        match style {
+            BackgroundStyle::MatrixRain if self.matrix_columns.is_empty() => {
+                self.matrix_columns = matrix::init_columns(width, height, self.init_seed);
+            }
+            BackgroundStyle::Fireplace if self.fire_state.is_none() => {
+                self.fire_state = Some(fire::init_fire(width, height, self.init_seed));
+            }
+            BackgroundStyle::GameOfLife if self.life_state.is_none() => {
+                self.life_state = Some(life::init_life(width, height, self.init_seed));
+            }
+            BackgroundStyle::Snowfall if self.snowflakes.is_empty() => {
+                self.snowflakes =
+                    weather::init_snowflakes(width, height, self.init_seed, self.precipitation);
+                self.snow_pile = vec![0.0; width as usize];
+            }
+            BackgroundStyle::Rainy if self.raindrops.is_empty() => {
+                self.raindrops =
+                    weather::init_raindrops(width, height, self.init_seed, self.precipitation);
+            }
+            BackgroundStyle::Stormy if self.storm_state.is_none() => {
+                self.storm_state = Some(weather::init_storm(width, height, self.init_seed));
+            }
+            BackgroundStyle::Windy if self.wind_streaks.is_empty() => {
+                self.wind_streaks = weather::init_wind_streaks(width, height, self.init_seed);
+            }
+            BackgroundStyle::Starfield if self.meteor_state.is_none() => {
+                self.meteor_state = Some(meteor::init_meteors(self.init_seed));
+            }
+            _ => {}
         }
-        if style == BackgroundStyle::Windy {
-            weather::update_wind(&mut self.wind_streaks, delta_ms, width, height, speed);
+    }
+
+    /// Fit the animation state of a stateful style to new dimensions.
+    ///
+    /// Falling columns and wind streaks keep moving from their scaled positions.
+    /// The fire and Game of Life grids are sized to the area, so they start over.
+    fn resize_style(&mut self, style: BackgroundStyle, width: u16, height: u16) {
+        let (old_width, old_height) = (self.last_width, self.last_height);
+        let seed = self.init_seed;
+        match style {
+            BackgroundStyle::MatrixRain => {
+                matrix::resize_columns(&mut self.matrix_columns, width, old_height, height, seed);
+            }
+            BackgroundStyle::Fireplace => self.fire_state = None,
+            BackgroundStyle::GameOfLife => self.life_state = None,
+            BackgroundStyle::Snowfall => {
+                weather::resize_snowflakes(
+                    &mut self.snowflakes,
+                    old_width,
+                    old_height,
+                    width,
+                    height,
+                    seed,
+                    self.precipitation,
+                );
+                weather::resize_snow_pile(&mut self.snow_pile, width);
+            }
+            BackgroundStyle::Rainy => {
+                weather::resize_raindrops(
+                    &mut self.raindrops,
+                    old_width,
+                    old_height,
+                    width,
+                    height,
+                    seed,
+                    self.precipitation,
+                );
+            }
+            BackgroundStyle::Stormy => {
+                if let Some(ref mut storm) = self.storm_state {
+                    weather::resize_raindrops(
+                        &mut storm.raindrops,
+                        old_width,
+                        old_height,
+                        width,
+                        height,
+                        seed,
+                        precipitation::HEAVY,
+                    );
+                }
+            }
+            BackgroundStyle::Windy => {
+                weather::resize_wind_streaks(
+                    &mut self.wind_streaks,
+                    old_width,
+                    old_height,
+                    width,
+                    height,
+                    seed,
+                );
+            }
+            _ => {}
         }
+    }
 
-        let lines: Vec<Line> = (0..height)
-            .map(|y| {
-                let spans: Vec<Span> = (0..width)
-                    .map(|x| self.render_char(x, y, width, height, style, elapsed_ms, speed))
-                    .collect();
-                Line::from(spans)
-            })
-            .collect();
+    /// Drop the animation state of stateful styles not among `layers`.
+    fn release_unused(&mut self, layers: &[BackgroundStyle]) {
+        let unused = |style| !layers.contains(&style);
+        if unused(BackgroundStyle::MatrixRain) {
+            self.matrix_columns = Vec::new();
+        }
+        if unused(BackgroundStyle::Fireplace) {
+            self.fire_state = None;
+        }
+        if unused(BackgroundStyle::GameOfLife) {
+            self.life_state = None;
+        }
+        if unused(BackgroundStyle::Snowfall) {
+            self.snowflakes = Vec::new();
+            self.snow_pile = Vec::new();
+        }
+        if unused(BackgroundStyle::Rainy) {
+            self.raindrops = Vec::new();
+        }
+        if unused(BackgroundStyle::Stormy) {
+            self.storm_state = None;
+        }
+        if unused(BackgroundStyle::Windy) {
+            self.wind_streaks = Vec::new();
+        }
+        if unused(BackgroundStyle::Starfield) {
+            self.meteor_state = None;
+            self.constellation_state = constellation::ConstellationState::default();
+        }
+    }
 
-        frame.render_widget(Paragraph::new(lines), area);
+    /// Advance the animation state of a stateful style.
+    fn update_style(
+        &mut self,
+        style: BackgroundStyle,
+        elapsed_ms: u64,
+        delta_ms: u64,
+        width: u16,
+        height: u16,
+        speed: AnimationSpeed,
+    ) {
+        let drift = self.wind_drift(elapsed_ms);
+        match style {
+            BackgroundStyle::MatrixRain => {
+                matrix::update(
+                    &mut self.matrix_columns,
+                    delta_ms,
+                    height,
+                    speed,
+                    &self.time_digits,
+                );
+            }
+            BackgroundStyle::Fireplace => {
+                if let Some(ref mut fire) = self.fire_state {
+                    fire::update_fire(fire, delta_ms, speed);
+                }
+            }
+            BackgroundStyle::GameOfLife => {
+                if let Some(ref mut life) = self.life_state {
+                    life::update_life(life, delta_ms, speed);
+                }
+            }
+            BackgroundStyle::Snowfall => {
+                let intensity = self.precipitation;
+                weather::update_snow(
+                    &mut self.snowflakes,
+                    delta_ms,
+                    width,
+                    height,
+                    speed,
+                    drift,
+                    intensity,
+                );
+                let ground = self.ground(height);
+                weather::settle_snow(
+                    &mut self.snowflakes,
+                    &mut self.snow_pile,
+                    delta_ms,
+                    ground,
+                    intensity,
+                );
+            }
+            BackgroundStyle::Rainy => {
+                weather::update_rain(
+                    &mut self.raindrops,
+                    delta_ms,
+                    width,
+                    height,
+                    speed,
+                    drift,
+                    self.precipitation,
+                );
+            }
+            BackgroundStyle::Stormy => {
+                if let Some(ref mut storm) = self.storm_state {
+                    weather::update_storm(storm, elapsed_ms, delta_ms, width, height, speed, drift);
+                }
+            }
+            BackgroundStyle::Windy => {
+                weather::update_wind(&mut self.wind_streaks, delta_ms, width, height, speed);
+            }
+            BackgroundStyle::Starfield => {
+                if let Some(ref mut meteors) = self.meteor_state {
+                    meteor::update_meteors(meteors, elapsed_ms, width, height, speed);
+                }
+                constellation::update_constellation(
+                    &mut self.constellation_state,
+                    self.init_seed,
+                    elapsed_ms,
+                    width,
+                    height,
+                );
+            }
+            _ => {}
+        }
     }
 
-    /// Render a single background character at the given position.
+    /// Render a single background character at the given position of an area of `size`.
     fn render_char(
         &self,
         x: u16,
         y: u16,
-        width: u16,
-        height: u16,
+        size: Size,
         style: BackgroundStyle,
         elapsed_ms: u64,
         speed: AnimationSpeed,
-    ) -> Span<'static> {
+    ) -> Option<Glyph> {
+        let Size { width, height } = size;
         match style {
-            BackgroundStyle::None => Span::raw(" "),
-            BackgroundStyle::Starfield => stateless::render_starfield_char(x, y, elapsed_ms, speed),
+            BackgroundStyle::None => None,
+            // Shooting stars pass in front of the constellation and the twinkling stars
+            BackgroundStyle::Starfield => self
+                .meteor_state
+                .as_ref()
+                .and_then(|meteors| meteor::render_meteor_char(meteors, x, y, elapsed_ms))
+                .or_else(|| {
+                    constellation::render_constellation_char(&self.constellation_state, x, y)
+                })
+                .or_else(|| stateless::render_starfield_char(x, y, elapsed_ms, speed)),
             BackgroundStyle::MatrixRain => matrix::render_char(&self.matrix_columns, x, y),
             BackgroundStyle::GradientWave => {
                 stateless::render_gradient_char(x, y, width, height, elapsed_ms, speed)
             }
+            BackgroundStyle::Fireplace => self
+                .fire_state
+                .as_ref()
+                .and_then(|fire| fire::render_fire_char(fire, x, y, height)),
+            BackgroundStyle::GameOfLife => self
+                .life_state
+                .as_ref()
+                .and_then(|life| life::render_life_char(life, x, y)),
+            // Settled snow in front of the flakes landing on it
             BackgroundStyle::Snowfall => {
-                weather::render_snow_char(&self.snow_columns, x, y, elapsed_ms)
+                weather::render_snow_pile_char(&self.snow_pile, x, y, self.ground(height))
+                    .or_else(|| weather::render_snow_char(&self.snowflakes, x, y, elapsed_ms))
             }
             BackgroundStyle::Frost => {
-                stateless::render_frost_char(x, y, width, height, elapsed_ms, speed)
-            }
-            BackgroundStyle::Aurora => {
-                stateless::render_aurora_char(x, y, width, height, elapsed_ms, speed)
+                let severity = self.temperature_c.map(stateless::frost_severity);
+                stateless::render_frost_char(x, y, width, height, elapsed_ms, speed, severity)
             }
+            BackgroundStyle::Aurora => stateless::render_aurora_char(
+                x,
+                y,
+                width,
+                height,
+                elapsed_ms,
+                speed,
+                self.aurora_activity,
+            ),
             // Weather backgrounds
             BackgroundStyle::Sunny => {
                 weather::render_sunny_char(x, y, width, height, elapsed_ms, speed)
             }
-            BackgroundStyle::Rainy => weather::render_rain_char(&self.rain_columns, x, y),
+            BackgroundStyle::Rainy => {
+                weather::render_rain_char(&self.raindrops, x, y, self.wind_drift(elapsed_ms))
+            }
             BackgroundStyle::Stormy => {
                 if let Some(ref storm) = self.storm_state {
-                    weather::render_storm_char(storm, x, y, elapsed_ms)
+                    weather::render_storm_char(storm, x, y, elapsed_ms, self.wind_drift(elapsed_ms))
                 } else {
-                    Span::raw(" ")
+                    None
                 }
             }
             BackgroundStyle::Windy => {
@@ -200,6 +779,9 @@ impl BackgroundState {
             BackgroundStyle::Foggy => {
                 weather::render_foggy_char(x, y, width, height, elapsed_ms, speed)
             }
+            BackgroundStyle::Moonlight => {
+                moon::render_moonlight_char(x, y, width, height, elapsed_ms, speed, self.moon_phase)
+            }
             // Weather style should be resolved by main app before rendering.
             // If it reaches here, fallback to Starfield.
             BackgroundStyle::Weather => stateless::render_starfield_char(x, y, elapsed_ms, speed),
@@ -214,14 +796,15 @@ impl BackgroundState {
             BackgroundStyle::SystemPulse
             | BackgroundStyle::ResourceWave
             | BackgroundStyle::DataFlow
-            | BackgroundStyle::HeatMap => Span::raw(" "),
+            | BackgroundStyle::HeatMap => None,
         }
     }
 
     /// Render reactive backgrounds that respond to system metrics.
     fn render_reactive(
         &mut self,
-        frame: &mut Frame,
+        area: Rect,
+        buf: &mut Buffer,
         style: BackgroundStyle,
         elapsed_ms: u64,
         speed: AnimationSpeed,
@@ -229,18 +812,838 @@ impl BackgroundState {
     ) {
         match style {
             BackgroundStyle::SystemPulse => {
-                reactive::render_system_pulse(frame, elapsed_ms, speed, metrics)
+                reactive::render_system_pulse(area, buf, elapsed_ms, speed, metrics)
             }
             BackgroundStyle::ResourceWave => {
-                reactive::render_resource_wave(frame, elapsed_ms, speed, metrics)
-            }
-            BackgroundStyle::DataFlow => {
-                reactive::render_data_flow(frame, elapsed_ms, speed, metrics)
+                reactive::render_resource_wave(area, buf, elapsed_ms, speed, metrics)
             }
+            BackgroundStyle::DataFlow => reactive::render_data_flow(
+                area,
+                buf,
+                elapsed_ms,
+                speed,
+                metrics,
+                &self.network_history,
+            ),
             BackgroundStyle::HeatMap => {
-                reactive::render_heat_map(frame, elapsed_ms, speed, metrics)
+                reactive::render_heat_map(area, buf, elapsed_ms, speed, metrics)
             }
             _ => {}
         }
     }
 }
+
+/// The styles actually drawn for `layers`: a reactive one in first position
+/// alone, otherwise up to [`MAX_LAYERS`] without the blank and reactive ones.
+fn drawn_layers(layers: &[BackgroundStyle]) -> Vec<BackgroundStyle> {
+    match layers.first() {
+        Some(&style) if style.is_reactive() => vec![style],
+        _ => layers
+            .iter()
+            .take(MAX_LAYERS)
+            .filter(|&&style| style != BackgroundStyle::None && !style.is_reactive())
+            .copied()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{style::Style, text::Span};
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+    use crate::CROSSFADE_MS;
+
+    fn render(state: &mut BackgroundState, area: Rect, buf: &mut Buffer, style: BackgroundStyle) {
+        state.render(area, buf, style, 0, AnimationSpeed::Medium, None);
+    }
+
+    #[test]
+    fn test_none_leaves_buffer_untouched() {
+        let area = Rect::new(0, 0, 10, 5);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "keep", Style::new());
+        render(
+            &mut BackgroundState::new(),
+            area,
+            &mut buf,
+            BackgroundStyle::None,
+        );
+        assert_eq!(buf.cell((0, 0)).unwrap().symbol(), "k");
+    }
+
+    #[test]
+    fn test_matrix_trail_in_expected_cells() {
+        let area = Rect::new(0, 0, 4, 10);
+        let mut buf = Buffer::empty(area);
+        let mut state = BackgroundState::new();
+
+        // Initialize columns, then place column 0's head at row 5.
+        render(&mut state, area, &mut buf, BackgroundStyle::MatrixRain);
+        state.matrix_columns[0].y = 5.0;
+        state.matrix_columns[0].trail_length = 3;
+        buf.reset();
+        render(&mut state, area, &mut buf, BackgroundStyle::MatrixRain);
+
+        for y in 0..10 {
+            let cell = buf.cell((0, y)).unwrap();
+            let in_trail = (2..=5).contains(&y);
+            assert_eq!(cell.symbol() != " ", in_trail, "row {y}");
+        }
+        // Head is drawn bright.
+        assert_eq!(
+            buf.cell((0, 5)).unwrap().fg,
+            ratatui::style::Color::Rgb(200, 255, 200)
+        );
+    }
+
+    /// Render `layers` into a fresh buffer.
+    fn render_layers(
+        state: &mut BackgroundState,
+        area: Rect,
+        layers: &[BackgroundStyle],
+    ) -> Buffer {
+        render_layers_at(state, area, layers, 0)
+    }
+
+    /// Render `layers` at `elapsed_ms` into a fresh buffer.
+    fn render_layers_at(
+        state: &mut BackgroundState,
+        area: Rect,
+        layers: &[BackgroundStyle],
+        elapsed_ms: u64,
+    ) -> Buffer {
+        let mut buf = Buffer::empty(area);
+        state.render_layers(
+            area,
+            &mut buf,
+            layers,
+            RenderParams {
+                elapsed_ms,
+                speed: AnimationSpeed::Medium,
+                metrics: None,
+            },
+            ClearZones::default(),
+        );
+        buf
+    }
+
+    /// Render a full-screen heat map with the given clear zone around `rect`.
+    fn render_clear_zone(rect: Rect, mode: ClearZone) -> Buffer {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(area);
+        let metrics = SystemMetrics {
+            cpu_usage: 1.0,
+            memory_usage: 1.0,
+            ..Default::default()
+        };
+        BackgroundState::new().render_layers(
+            area,
+            &mut buf,
+            &[BackgroundStyle::HeatMap],
+            RenderParams {
+                elapsed_ms: 0,
+                speed: AnimationSpeed::Medium,
+                metrics: Some(&metrics),
+            },
+            ClearZones {
+                rects: &[rect],
+                mode,
+                dim_factor: 0.3,
+            },
+        );
+        buf
+    }
+
+    #[test]
+    fn test_clear_zone_blanks_cells() {
+        let rect = Rect::new(5, 3, 8, 2);
+        let off = render_clear_zone(rect, ClearZone::Off);
+        let clear = render_clear_zone(rect, ClearZone::Clear);
+
+        for position in Rect::new(0, 0, 20, 10).positions() {
+            if rect.contains(position) {
+                assert_eq!(clear[position].symbol(), " ", "{position:?}");
+            } else {
+                assert_eq!(clear[position], off[position], "{position:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clear_zone_dims_cells() {
+        let rect = Rect::new(5, 3, 8, 2);
+        let off = render_clear_zone(rect, ClearZone::Off);
+        let dim = render_clear_zone(rect, ClearZone::Dim);
+
+        for position in Rect::new(0, 0, 20, 10).positions() {
+            let expected = if rect.contains(position) {
+                scale_color(off[position].fg, 0.3)
+            } else {
+                off[position].fg
+            };
+            assert_eq!(dim[position].symbol(), off[position].symbol());
+            assert_eq!(dim[position].fg, expected, "{position:?}");
+        }
+    }
+
+    #[test]
+    fn test_snow_pile_keeps_clear_of_the_margin() {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut state = BackgroundState::new();
+        state.set_ground_margin(1);
+        render_layers(&mut state, area, &[BackgroundStyle::Snowfall]);
+        state.snow_pile.fill(1.0);
+
+        let buf = render_layers(&mut state, area, &[BackgroundStyle::Snowfall]);
+        for x in 0..area.width {
+            assert_eq!(buf[(x, 8)].symbol(), "█");
+            assert_eq!(buf[(x, 9)].symbol(), " ");
+        }
+
+        // Switching away melts it once the crossfade is over
+        render_layers(&mut state, area, &[BackgroundStyle::Rainy]);
+        assert!(!state.snow_pile.is_empty());
+        render_layers_at(&mut state, area, &[BackgroundStyle::Rainy], CROSSFADE_MS);
+        assert!(state.snow_pile.is_empty());
+    }
+
+    #[test]
+    fn test_layers_composite_in_order() {
+        let area = Rect::new(0, 0, 40, 20);
+        // Flakes start above the screen; drop one into view in every column
+        let render_with_flakes = |layers: &[BackgroundStyle]| {
+            let mut state = BackgroundState::with_seed(7);
+            render_layers(&mut state, area, layers);
+            for (x, flake) in state.snowflakes.iter_mut().enumerate() {
+                flake.y = (x % area.height as usize) as f32;
+            }
+            render_layers(&mut state, area, layers)
+        };
+
+        // Each from its own state, so switching styles doesn't crossfade
+        let stars = render_with_flakes(&[BackgroundStyle::Starfield]);
+        let snow = render_with_flakes(&[BackgroundStyle::Snowfall]);
+        let both = render_with_flakes(&[BackgroundStyle::Starfield, BackgroundStyle::Snowfall]);
+
+        let (mut star_cells, mut snow_cells) = (0, 0);
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let composite = both.cell((x, y)).unwrap();
+                let snow_cell = snow.cell((x, y)).unwrap();
+                if snow_cell.symbol() != " " {
+                    // The top layer wins where it draws
+                    assert_eq!(composite, snow_cell, "({x}, {y})");
+                    snow_cells += 1;
+                } else {
+                    // Blank top-layer cells show the layer below
+                    assert_eq!(composite, stars.cell((x, y)).unwrap(), "({x}, {y})");
+                    if composite.symbol() != " " {
+                        star_cells += 1;
+                    }
+                }
+            }
+        }
+        assert!(star_cells > 0, "no stars visible through the snow");
+        assert!(snow_cells > 0, "no snowflakes drawn");
+    }
+
+    #[test]
+    fn test_widening_keeps_existing_columns() {
+        let mut state = BackgroundState::with_seed(42);
+        let layers = [
+            BackgroundStyle::MatrixRain,
+            BackgroundStyle::Snowfall,
+            BackgroundStyle::Rainy,
+        ];
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+        let render = |state: &mut BackgroundState, buf: &mut Buffer, width, elapsed_ms| {
+            state.render_layers(
+                Rect::new(0, 0, width, 30),
+                buf,
+                &layers,
+                RenderParams {
+                    elapsed_ms,
+                    speed: AnimationSpeed::Medium,
+                    metrics: None,
+                },
+                ClearZones::default(),
+            );
+        };
+        render(&mut state, &mut buf, 80, 0);
+        render(&mut state, &mut buf, 80, 2000);
+
+        let matrix: Vec<f32> = state.matrix_columns.iter().map(|col| col.y).collect();
+        let snow: Vec<f32> = state.snowflakes.iter().map(|col| col.y).collect();
+        let rain: Vec<f32> = state.raindrops.iter().map(|col| col.y).collect();
+
+        // Same elapsed time, so nothing moves apart from the resize itself
+        render(&mut state, &mut buf, 100, 2000);
+        assert_eq!(state.matrix_columns.len(), 100);
+        assert_eq!(state.snowflakes.len(), 100);
+        assert_eq!(state.raindrops.len(), 100);
+        let kept = |ys: Vec<f32>| ys[..80].to_vec();
+        assert_eq!(
+            kept(state.matrix_columns.iter().map(|col| col.y).collect()),
+            matrix
+        );
+        assert_eq!(
+            kept(state.snowflakes.iter().map(|col| col.y).collect()),
+            snow
+        );
+        assert_eq!(
+            kept(state.raindrops.iter().map(|col| col.y).collect()),
+            rain
+        );
+
+        // New columns start like freshly initialized ones
+        let fresh = weather::init_raindrops(100, 30, 42, precipitation::MODERATE);
+        assert_eq!(state.raindrops[90].y, fresh[90].y);
+
+        // Narrowing drops the extra columns
+        render(&mut state, &mut buf, 60, 2000);
+        assert_eq!(state.matrix_columns.len(), 60);
+        assert_eq!(state.matrix_columns[59].y, matrix[59]);
+    }
+
+    #[test]
+    fn test_height_change_scales_positions() {
+        let mut state = BackgroundState::with_seed(42);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 40));
+        let style = BackgroundStyle::MatrixRain;
+        let speed = AnimationSpeed::Medium;
+        state.render(Rect::new(0, 0, 40, 20), &mut buf, style, 0, speed, None);
+        state.matrix_columns[0].y = 10.0;
+
+        state.render(Rect::new(0, 0, 40, 40), &mut buf, style, 0, speed, None);
+        assert_eq!(state.matrix_columns[0].y, 20.0);
+    }
+
+    #[test]
+    fn test_resize_refits_before_the_next_frame() {
+        let layers = [BackgroundStyle::MatrixRain, BackgroundStyle::Rainy];
+        let render = |state: &mut BackgroundState, width, height| {
+            let area = Rect::new(0, 0, width, height);
+            let mut buf = Buffer::empty(area);
+            state.render_layers(
+                area,
+                &mut buf,
+                &layers,
+                RenderParams {
+                    elapsed_ms: 0,
+                    speed: AnimationSpeed::Medium,
+                    metrics: None,
+                },
+                ClearZones::default(),
+            );
+            buf
+        };
+
+        // Before the first render there is nothing to fit
+        let mut state = BackgroundState::with_seed(42);
+        state.resize(120, 40);
+        assert!(state.raindrops.is_empty());
+
+        render(&mut state, 80, 24);
+        state.resize(120, 40);
+        assert_eq!(state.matrix_columns.len(), 120);
+        assert_eq!(state.raindrops.len(), 120);
+
+        // The columns beyond the old width draw just like a fresh start at the new size
+        let resized = render(&mut state, 120, 40);
+        let fresh = render(&mut BackgroundState::with_seed(42), 120, 40);
+        for y in 0..40 {
+            for x in 80..120 {
+                assert_eq!(resized[(x, y)], fresh[(x, y)], "stale cell at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_frame_does_not_jump() {
+        let area = Rect::new(0, 0, 40, 20);
+        let mut buf = Buffer::empty(area);
+        let fresh = matrix::init_columns(40, 20, 42);
+
+        // A late first frame starts from the initial positions
+        let mut state = BackgroundState::with_seed(42);
+        let style = BackgroundStyle::MatrixRain;
+        let speed = AnimationSpeed::Fast;
+        state.render(area, &mut buf, style, 10_000_000, speed, None);
+        for (col, start) in state.matrix_columns.iter().zip(&fresh) {
+            assert_eq!(col.y, start.y);
+        }
+
+        // A long gap advances by at most one clamped step
+        let mut clamped = fresh.clone();
+        matrix::update(&mut clamped, MAX_FRAME_DELTA_MS, 20, speed, &[]);
+        state.render(area, &mut buf, style, 20_000_000, speed, None);
+        for (col, expected) in state.matrix_columns.iter().zip(&clamped) {
+            assert_eq!(col.y, expected.y);
+        }
+    }
+
+    #[test]
+    fn test_skipped_gap_does_not_advance() {
+        let area = Rect::new(0, 0, 40, 20);
+        let mut buf = Buffer::empty(area);
+        let style = BackgroundStyle::MatrixRain;
+        let speed = AnimationSpeed::Fast;
+        let mut state = BackgroundState::with_seed(42);
+        state.render(area, &mut buf, style, 0, speed, None);
+        state.render(area, &mut buf, style, 100, speed, None);
+        let paused = state.matrix_columns.clone();
+
+        // Resuming after a pause continues from the paused positions
+        state.skip_gap();
+        state.render(area, &mut buf, style, 60_100, speed, None);
+        for (col, before) in state.matrix_columns.iter().zip(&paused) {
+            assert_eq!(col.y, before.y);
+        }
+
+        // ...and then advances normally from the new time
+        let mut expected = paused.clone();
+        matrix::update(&mut expected, 50, 20, speed, &[]);
+        state.render(area, &mut buf, style, 60_150, speed, None);
+        for (col, expected) in state.matrix_columns.iter().zip(&expected) {
+            assert_eq!(col.y, expected.y);
+        }
+
+        // Without skipping, the same gap advances a clamped step
+        let mut unskipped = BackgroundState::with_seed(42);
+        unskipped.render(area, &mut buf, style, 0, speed, None);
+        unskipped.render(area, &mut buf, style, 100, speed, None);
+        unskipped.render(area, &mut buf, style, 60_100, speed, None);
+        assert!(
+            unskipped
+                .matrix_columns
+                .iter()
+                .zip(&paused)
+                .any(|(col, before)| col.y != before.y)
+        );
+    }
+
+    #[test]
+    fn test_switching_styles_releases_state() {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut state = BackgroundState::new();
+        render_layers(&mut state, area, &[BackgroundStyle::MatrixRain]);
+        render_layers_at(&mut state, area, &[BackgroundStyle::Fireplace], 100);
+        // Both animate while crossfading
+        assert!(!state.matrix_columns.is_empty());
+        assert!(state.fire_state.is_some());
+        assert!(state.is_crossfading());
+
+        // Only the new style's state is left afterwards
+        render_layers_at(&mut state, area, &[BackgroundStyle::Fireplace], 900);
+        assert!(state.matrix_columns.is_empty());
+        assert!(state.fire_state.is_some());
+        assert!(!state.is_crossfading());
+
+        render_layers_at(&mut state, area, &[BackgroundStyle::Starfield], 1_000);
+        render_layers_at(&mut state, area, &[BackgroundStyle::Starfield], 5_000);
+        assert!(state.fire_state.is_none());
+    }
+
+    #[test]
+    fn test_crossfade_blends_old_under_new() {
+        let area = Rect::new(0, 0, 30, 10);
+        let (from, to) = (BackgroundStyle::GradientWave, BackgroundStyle::Starfield);
+        let old = render_layers_at(&mut BackgroundState::with_seed(7), area, &[from], 400);
+        let new = render_layers_at(&mut BackgroundState::with_seed(7), area, &[to], 400);
+
+        let mut state = BackgroundState::with_seed(7);
+        render_layers(&mut state, area, &[from]);
+        render_layers(&mut state, area, &[to]);
+        let halfway = render_layers_at(&mut state, area, &[to], CROSSFADE_MS / 2);
+        let (fade_out, fade_in) = fade_factors(0.5, false);
+        for position in area.positions() {
+            let (cell, new_cell) = (&halfway[position], &new[position]);
+            if new_cell.symbol() != " " {
+                assert_eq!(cell.symbol(), new_cell.symbol(), "{position:?}");
+                assert_eq!(cell.fg, scale_color(new_cell.fg, fade_in), "{position:?}");
+            } else {
+                assert_eq!(cell.symbol(), old[position].symbol(), "{position:?}");
+                assert_eq!(
+                    cell.fg,
+                    scale_color(old[position].fg, fade_out),
+                    "{position:?}"
+                );
+            }
+        }
+
+        // Once over, the new style draws as if it had been there all along
+        let done = render_layers_at(&mut state, area, &[to], 400 + CROSSFADE_MS);
+        let after = render_layers_at(
+            &mut BackgroundState::with_seed(7),
+            area,
+            &[to],
+            400 + CROSSFADE_MS,
+        );
+        assert_eq!(done, after);
+    }
+
+    #[test]
+    fn test_reactive_styles_fade_through_black() {
+        let area = Rect::new(0, 0, 20, 10);
+        let metrics = SystemMetrics {
+            cpu_usage: 1.0,
+            memory_usage: 1.0,
+            ..Default::default()
+        };
+        let render_at = |state: &mut BackgroundState, style, elapsed_ms| {
+            let mut buf = Buffer::empty(area);
+            state.render_layers(
+                area,
+                &mut buf,
+                &[style],
+                RenderParams {
+                    elapsed_ms,
+                    speed: AnimationSpeed::Medium,
+                    metrics: Some(&metrics),
+                },
+                ClearZones::default(),
+            );
+            buf
+        };
+        let mut state = BackgroundState::with_seed(7);
+        render_at(&mut state, BackgroundStyle::Aurora, 0);
+        render_at(&mut state, BackgroundStyle::HeatMap, 0);
+        let black = render_at(&mut state, BackgroundStyle::HeatMap, CROSSFADE_MS / 2);
+        assert_eq!(black, Buffer::empty(area));
+
+        let late = render_at(&mut state, BackgroundStyle::HeatMap, CROSSFADE_MS * 3 / 4);
+        let full = render_at(
+            &mut BackgroundState::with_seed(7),
+            BackgroundStyle::HeatMap,
+            600,
+        );
+        let (_, fade_in) = fade_factors(0.75, true);
+        for position in area.positions() {
+            assert_eq!(late[position].fg, scale_color(full[position].fg, fade_in));
+            assert_eq!(late[position].bg, scale_color(full[position].bg, fade_in));
+        }
+    }
+
+    #[test]
+    fn test_reactive_styles_are_not_layered() {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut state = BackgroundState::new();
+        let stars = render_layers(&mut state, area, &[BackgroundStyle::Starfield]);
+        let layered = render_layers(
+            &mut state,
+            area,
+            &[BackgroundStyle::Starfield, BackgroundStyle::HeatMap],
+        );
+        assert_eq!(layered, stars);
+    }
+
+    #[test]
+    fn test_same_seed_renders_identically() {
+        let area = Rect::new(0, 0, 40, 12);
+        let metrics = SystemMetrics {
+            cpu_usage: 0.6,
+            memory_usage: 0.3,
+            ..Default::default()
+        };
+        // Irregular frame times, with a stall and a long gap
+        let timestamps = [0, 16, 49, 120, 700, 701, 2_500, 2_533, 9_000, 9_016];
+        let run = |seed, layers: &[BackgroundStyle]| {
+            let mut state = BackgroundState::with_seed(seed);
+            timestamps
+                .iter()
+                .map(|&elapsed_ms| {
+                    let mut buf = Buffer::empty(area);
+                    state.render_layers(
+                        area,
+                        &mut buf,
+                        layers,
+                        RenderParams {
+                            elapsed_ms,
+                            speed: AnimationSpeed::Fast,
+                            metrics: Some(&metrics),
+                        },
+                        ClearZones::default(),
+                    );
+                    buf
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut stacks: Vec<Vec<BackgroundStyle>> =
+            BackgroundStyle::all().map(|style| vec![style]).collect();
+        stacks.push(vec![BackgroundStyle::Stormy, BackgroundStyle::Snowfall]);
+        for layers in &stacks {
+            assert_eq!(run(1234, layers), run(1234, layers), "{layers:?}");
+        }
+
+        // The seed is what varies the stateful styles
+        for style in [
+            BackgroundStyle::MatrixRain,
+            BackgroundStyle::Snowfall,
+            BackgroundStyle::GameOfLife,
+        ] {
+            assert_ne!(run(1, &[style]), run(2, &[style]), "{style:?}");
+        }
+    }
+
+    /// Assert every row of `buf` is exactly as wide as the buffer on screen:
+    /// no wide glyph hangs over the right edge, and no glyph hides behind one.
+    fn assert_rows_fill_width(buf: &Buffer, context: &str) {
+        let area = buf.area;
+        for y in area.top()..area.bottom() {
+            let mut x = area.left();
+            while x < area.right() {
+                let width = buf[(x, y)].symbol().width().max(1) as u16;
+                for hidden in x + 1..(x + width).min(area.right()) {
+                    assert_eq!(buf[(hidden, y)].symbol(), " ", "{context} ({hidden}, {y})");
+                }
+                x += width;
+            }
+            assert_eq!(x, area.right(), "{context} row {y}");
+        }
+    }
+
+    #[test]
+    fn test_rows_fill_exactly_the_width() {
+        let area = Rect::new(0, 0, 41, 12);
+        let metrics = SystemMetrics {
+            cpu_usage: 0.7,
+            memory_usage: 0.4,
+            ..Default::default()
+        };
+        let mut stacks: Vec<Vec<BackgroundStyle>> =
+            BackgroundStyle::all().map(|style| vec![style]).collect();
+        // Layers drawn over each other, with the rain both below and on top
+        stacks.extend(
+            BackgroundStyle::all()
+                .filter(|style| style.can_layer())
+                .flat_map(|style| {
+                    [
+                        vec![BackgroundStyle::MatrixRain, style],
+                        vec![style, BackgroundStyle::MatrixRain],
+                    ]
+                }),
+        );
+        for layers in stacks {
+            let mut state = BackgroundState::with_seed(7);
+            let mut buf = Buffer::empty(area);
+            for frame in 0..40 {
+                buf.reset();
+                state.render_layers(
+                    area,
+                    &mut buf,
+                    &layers,
+                    RenderParams {
+                        elapsed_ms: frame * 150,
+                        speed: AnimationSpeed::Fast,
+                        metrics: Some(&metrics),
+                    },
+                    ClearZones::default(),
+                );
+                assert_rows_fill_width(&buf, &format!("{layers:?} frame {frame}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_narrow_glyph_clears_the_wide_one_it_cuts() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+        let wide = Some(Glyph::new('３', ratatui::style::Color::White));
+        let narrow = Some(Glyph::new('*', ratatui::style::Color::White));
+        assert_eq!(put_glyph(&mut buf, 2, 0, wide, 4), 2);
+        assert_eq!(put_glyph(&mut buf, 3, 0, narrow, 3), 1);
+        assert_eq!(buf[(2, 0)].symbol(), " ");
+        assert_eq!(buf[(3, 0)].symbol(), "*");
+        // A wide glyph that would hang over the edge is skipped
+        assert_eq!(put_glyph(&mut buf, 5, 0, wide, 1), 1);
+        assert_rows_fill_width(&buf, "put_glyph");
+    }
+
+    #[test]
+    fn test_tiny_areas_do_not_panic() {
+        let metrics = SystemMetrics {
+            cpu_usage: 0.5,
+            memory_usage: 0.5,
+            ..Default::default()
+        };
+        for size in [(0, 0), (1, 1), (2, 1), (1, 50), (50, 1), (0, 10), (10, 0)] {
+            let area = Rect::new(0, 0, size.0, size.1);
+            for style in BackgroundStyle::all() {
+                let mut state = BackgroundState::new();
+                let mut buf = Buffer::empty(area);
+                // Several frames so stateful styles initialize, move and wrap
+                for frame in 0..20 {
+                    state.render(
+                        area,
+                        &mut buf,
+                        style,
+                        frame * 250,
+                        AnimationSpeed::Fast,
+                        Some(&metrics),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_style_draws() {
+        let area = Rect::new(0, 0, 40, 12);
+        let metrics = SystemMetrics {
+            cpu_usage: 0.7,
+            memory_usage: 0.5,
+            ..Default::default()
+        };
+        for style in BackgroundStyle::all().filter(|&style| style != BackgroundStyle::None) {
+            let mut state = BackgroundState::with_seed(7);
+            // Falling styles start above the area, so give them a few seconds
+            let drew = (0..40).any(|frame| {
+                let mut buf = Buffer::empty(area);
+                state.render(
+                    area,
+                    &mut buf,
+                    style,
+                    frame * 150,
+                    AnimationSpeed::Fast,
+                    Some(&metrics),
+                );
+                buf.content().iter().any(|cell| cell.symbol() != " ")
+            });
+            assert!(drew, "{style:?} drew nothing");
+        }
+    }
+
+    /// Draw `style` the way backgrounds were drawn before glyphs were written
+    /// straight into the buffer: a `Span` per cell, placed with `set_span`.
+    fn render_with_spans(
+        state: &BackgroundState,
+        area: Rect,
+        buf: &mut Buffer,
+        style: BackgroundStyle,
+        elapsed_ms: u64,
+        speed: AnimationSpeed,
+    ) {
+        for y in 0..area.height {
+            let mut x = 0;
+            while x < area.width {
+                let span = match state.render_char(x, y, area.as_size(), style, elapsed_ms, speed) {
+                    Some(glyph) => {
+                        Span::styled(glyph.ch.to_string(), Style::default().fg(glyph.fg))
+                    }
+                    None => Span::raw(" "),
+                };
+                let (next_x, _) = buf.set_span(area.x + x, area.y + y, &span, area.width - x);
+                x = (next_x - area.x).max(x + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_output_matches_span_rendering() {
+        // Offset inside a larger buffer, so positions are checked as well
+        let area = Rect::new(3, 2, 40, 12);
+        for style in BackgroundStyle::all().filter(|style| !style.is_reactive()) {
+            for speed in [AnimationSpeed::Slow, AnimationSpeed::Fast] {
+                let mut state = BackgroundState::with_seed(11);
+                for elapsed_ms in [0, 150, 1_000, 4_321, 60_000] {
+                    let mut direct = Buffer::empty(Rect::new(0, 0, 46, 16));
+                    state.render(area, &mut direct, style, elapsed_ms, speed, None);
+                    // Drawn from the state the frame above left, without advancing it
+                    let mut spans = Buffer::empty(direct.area);
+                    render_with_spans(&state, area, &mut spans, style, elapsed_ms, speed);
+                    assert_eq!(direct, spans, "{style:?} {speed:?} at {elapsed_ms} ms");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_blank_cells_keep_what_is_underneath() {
+        let area = Rect::new(0, 0, 40, 12);
+        let metrics = SystemMetrics {
+            cpu_usage: 0.7,
+            memory_usage: 0.5,
+            ..Default::default()
+        };
+        for style in BackgroundStyle::all() {
+            let mut state = BackgroundState::with_seed(7);
+            let mut buf = Buffer::empty(area);
+            for frame in 0..20 {
+                // Whatever the terminal shows underneath, e.g. a wallpaper
+                for y in 0..area.height {
+                    buf.set_string(0, y, "~".repeat(area.width as usize), Style::default());
+                }
+                state.render(
+                    area,
+                    &mut buf,
+                    style,
+                    frame * 150,
+                    AnimationSpeed::Fast,
+                    Some(&metrics),
+                );
+                for (i, cell) in buf.content().iter().enumerate() {
+                    assert_eq!(
+                        cell.bg,
+                        ratatui::style::Color::Reset,
+                        "{style:?} set a background"
+                    );
+                    // Only the cells behind a wide glyph may be blanked
+                    let behind_wide =
+                        i % area.width as usize > 0 && buf.content()[i - 1].symbol().width() > 1;
+                    assert!(
+                        cell.symbol() != " " || behind_wide,
+                        "{style:?} painted a blank over cell {i}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stateful_init_with_empty_area() {
+        for (width, height) in [(0, 0), (5, 0), (0, 5)] {
+            matrix::init_columns(width, height, 42);
+            fire::init_fire(width, height, 42);
+            life::init_life(width, height, 42);
+            weather::init_snowflakes(width, height, 42, precipitation::MODERATE);
+            weather::init_raindrops(width, height, 42, precipitation::MODERATE);
+            weather::init_storm(width, height, 42);
+            let mut streaks = weather::init_wind_streaks(width, height, 42);
+            weather::update_wind(&mut streaks, 60_000, width, height, AnimationSpeed::Fast);
+        }
+    }
+
+    #[test]
+    fn test_renders_only_inside_area() {
+        let full = Rect::new(0, 0, 20, 10);
+        let area = Rect::new(5, 2, 6, 4);
+        let mut buf = Buffer::empty(full);
+        let metrics = SystemMetrics {
+            cpu_usage: 1.0,
+            memory_usage: 1.0,
+            ..Default::default()
+        };
+
+        let mut state = BackgroundState::new();
+        for style in [BackgroundStyle::HeatMap, BackgroundStyle::GradientWave] {
+            buf.reset();
+            state.render(
+                area,
+                &mut buf,
+                style,
+                1234,
+                AnimationSpeed::Medium,
+                Some(&metrics),
+            );
+            for y in 0..full.height {
+                for x in 0..full.width {
+                    if !area.contains((x, y).into()) {
+                        assert_eq!(buf.cell((x, y)).unwrap().symbol(), " ", "{style:?}");
+                    }
+                }
+            }
+        }
+    }
+}
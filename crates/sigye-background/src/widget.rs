@@ -0,0 +1,201 @@
+//! Ratatui widget drawing a background with a [`BackgroundState`].
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+use sigye_core::{AnimationSpeed, BackgroundStyle, SystemMetrics};
+
+use crate::state::{BackgroundState, ClearZones, MAX_LAYERS, RenderParams};
+
+/// An animated background, drawn with a [`BackgroundState`] that is kept
+/// between frames.
+///
+/// Render it before the content it sits behind; content drawn afterwards
+/// simply overwrites the background cells.
+#[derive(Debug, Clone, Copy)]
+pub struct BackgroundWidget<'a> {
+    /// Styles composited in order, the first `len` of them used.
+    styles: [BackgroundStyle; MAX_LAYERS],
+    /// Number of styles.
+    len: usize,
+    /// Milliseconds since the app started, from a monotonic clock.
+    elapsed_ms: u64,
+    /// Animation speed.
+    speed: AnimationSpeed,
+    /// System metrics for the reactive styles.
+    metrics: Option<&'a SystemMetrics>,
+    /// Areas kept readable behind foreground content.
+    clear_zones: ClearZones<'a>,
+}
+
+impl<'a> BackgroundWidget<'a> {
+    /// Background of one style at `elapsed_ms`, at medium speed.
+    pub fn new(style: BackgroundStyle, elapsed_ms: u64) -> Self {
+        Self::layered(&[style], elapsed_ms)
+    }
+
+    /// Background of up to [`MAX_LAYERS`] styles composited in order.
+    ///
+    /// See [`BackgroundState::render_layers`] for how layers combine.
+    pub fn layered(styles: &[BackgroundStyle], elapsed_ms: u64) -> Self {
+        let mut widget = Self {
+            styles: [BackgroundStyle::None; MAX_LAYERS],
+            len: 0,
+            elapsed_ms,
+            speed: AnimationSpeed::default(),
+            metrics: None,
+            clear_zones: ClearZones::default(),
+        };
+        for &style in styles {
+            widget = widget.layer(style);
+        }
+        widget
+    }
+
+    /// Add a style on top, ignored once there are [`MAX_LAYERS`].
+    pub fn layer(mut self, style: BackgroundStyle) -> Self {
+        if self.len < MAX_LAYERS {
+            self.styles[self.len] = style;
+            self.len += 1;
+        }
+        self
+    }
+
+    /// Set the animation speed.
+    pub fn speed(mut self, speed: AnimationSpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the system metrics that the reactive styles visualize.
+    pub fn metrics(mut self, metrics: Option<&'a SystemMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Keep areas readable behind foreground content.
+    pub fn clear_zones(mut self, clear_zones: ClearZones<'a>) -> Self {
+        self.clear_zones = clear_zones;
+        self
+    }
+}
+
+impl StatefulWidget for BackgroundWidget<'_> {
+    type State = BackgroundState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.render_layers(
+            area,
+            buf,
+            &self.styles[..self.len],
+            RenderParams {
+                elapsed_ms: self.elapsed_ms,
+                speed: self.speed,
+                metrics: self.metrics,
+            },
+            self.clear_zones,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Style;
+    use sigye_core::ClearZone;
+
+    use super::*;
+
+    fn symbols(buf: &Buffer) -> String {
+        buf.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    /// Render a few frames of a style with the widget and with the state directly.
+    fn render_both(styles: &[BackgroundStyle], area: Rect) -> (Buffer, Buffer) {
+        let mut widget_state = BackgroundState::with_seed(7);
+        let mut direct_state = BackgroundState::with_seed(7);
+        let mut widget_buf = Buffer::empty(Rect::new(0, 0, 40, 12));
+        let mut direct_buf = widget_buf.clone();
+        for elapsed_ms in [0, 100, 200, 1_000] {
+            let widget = BackgroundWidget::layered(styles, elapsed_ms).speed(AnimationSpeed::Fast);
+            widget.render(area, &mut widget_buf, &mut widget_state);
+            direct_state.render_layers(
+                area,
+                &mut direct_buf,
+                styles,
+                RenderParams {
+                    elapsed_ms,
+                    speed: AnimationSpeed::Fast,
+                    metrics: None,
+                },
+                ClearZones::default(),
+            );
+        }
+        (widget_buf, direct_buf)
+    }
+
+    #[test]
+    fn test_widget_matches_state_rendering() {
+        let area = Rect::new(0, 0, 40, 12);
+        for style in [
+            BackgroundStyle::Starfield,
+            BackgroundStyle::MatrixRain,
+            BackgroundStyle::Aurora,
+        ] {
+            let (widget_buf, direct_buf) = render_both(&[style], area);
+            assert_eq!(widget_buf, direct_buf, "{style:?}");
+            assert!(
+                !symbols(&widget_buf).trim().is_empty(),
+                "{style:?} drew nothing"
+            );
+        }
+    }
+
+    #[test]
+    fn test_widget_stays_inside_its_area() {
+        // A pane in the middle of the screen, with the app's own text around it
+        let pane = Rect::new(10, 3, 20, 6);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 12));
+        for y in 0..12 {
+            buf.set_string(0, y, ".".repeat(40), Style::default());
+        }
+        let mut state = BackgroundState::with_seed(7);
+        for elapsed_ms in [0, 500, 5_000] {
+            BackgroundWidget::new(BackgroundStyle::Snowfall, elapsed_ms)
+                .render(pane, &mut buf, &mut state);
+        }
+        for position in buf.area.positions() {
+            if !pane.contains(position) {
+                assert_eq!(buf[position].symbol(), ".", "{position:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_layers_and_clear_zones() {
+        let area = Rect::new(0, 0, 40, 12);
+        let styles = [BackgroundStyle::GradientWave, BackgroundStyle::Snowfall];
+        let (widget_buf, direct_buf) = render_both(&styles, area);
+        assert_eq!(widget_buf, direct_buf);
+
+        // Styles past the layer limit are ignored
+        let widget = BackgroundWidget::new(BackgroundStyle::Starfield, 0)
+            .layer(BackgroundStyle::Snowfall)
+            .layer(BackgroundStyle::Rainy)
+            .layer(BackgroundStyle::MatrixRain);
+        assert_eq!(widget.len, MAX_LAYERS);
+
+        // A cleared zone is left blank
+        let zone = Rect::new(5, 2, 10, 3);
+        let mut buf = Buffer::empty(area);
+        BackgroundWidget::new(BackgroundStyle::GradientWave, 1_000)
+            .clear_zones(ClearZones {
+                rects: &[zone],
+                mode: ClearZone::Clear,
+                dim_factor: 0.0,
+            })
+            .render(area, &mut buf, &mut BackgroundState::with_seed(7));
+        assert!(
+            zone.positions()
+                .all(|position| buf[position].symbol() == " ")
+        );
+        assert!(!symbols(&buf).trim().is_empty());
+    }
+}
@@ -1,11 +1,18 @@
 //! Configuration management for the sigye clock application.
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use sigye_core::{AnimationSpeed, AnimationStyle, BackgroundStyle, ColorTheme, TimeFormat};
+use sigye_core::{
+    Accessibility, AmPmStyle, AnimationSpeed, AnimationStyle, BackgroundStyle, BannerPosition,
+    ClearZone, ClockBorder, ClockPosition, ColorTheme, DateFormat, DisplayMode, MetricsProvider,
+    PaneLayout, SecondsRing, SubsecondPrecision, TemperatureUnit, TimeFormat, WeatherSource,
+    brightness::AutoDim, fuzzy::FuzzyGranularity, preset::Preset, progress::ProgressBar,
+    theme::ThemeOverrides,
+};
 
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,10 +25,58 @@ pub struct Config {
     #[serde(default)]
     pub color_theme: ColorTheme,
 
+    /// Brightness of the Auto color theme at night (0.0 - 1.0).
+    #[serde(default = "default_night_brightness")]
+    pub night_brightness: f32,
+
     /// Time format (12h or 24h).
     #[serde(default)]
     pub time_format: TimeFormat,
 
+    /// How AM/PM is drawn in 12-hour format: Large, SmallText or Hidden.
+    #[serde(default)]
+    pub am_pm_style: AmPmStyle,
+
+    /// Draw a leading zero for 12-hour times like 07:30 instead of a blank.
+    #[serde(default)]
+    pub pad_hour: bool,
+
+    /// Blank columns between two clock digits.
+    #[serde(default)]
+    pub digit_gap: usize,
+
+    /// Blank columns on each side of a clock colon.
+    #[serde(default)]
+    pub colon_gap: usize,
+
+    /// Spaces before the AM/PM marker.
+    #[serde(default = "default_am_pm_gap")]
+    pub am_pm_gap: usize,
+
+    /// Characters painted over the clock digits, e.g. "@" or "▓░" (empty = the font's own).
+    #[serde(default)]
+    pub digit_fill: String,
+
+    /// Character drawn in the blank cells of filled digits, e.g. "·" (empty = left blank).
+    #[serde(default)]
+    pub digit_blank: String,
+
+    /// Times the clock digits are blown up (0 = the largest that fits).
+    #[serde(default = "default_clock_scale")]
+    pub clock_scale: usize,
+
+    /// Show the time as digits or spelled out in words.
+    #[serde(default)]
+    pub display_mode: DisplayMode,
+
+    /// Rounding of the time in words: Exact or FiveMinutes.
+    #[serde(default)]
+    pub fuzzy_granularity: FuzzyGranularity,
+
+    /// Sub-second digits under the clock: Off, Tenths or Hundredths.
+    #[serde(default)]
+    pub subsecond_precision: SubsecondPrecision,
+
     /// Animation style.
     #[serde(default)]
     pub animation_style: AnimationStyle,
@@ -38,26 +93,473 @@ pub struct Config {
     #[serde(default)]
     pub background_style: BackgroundStyle,
 
+    /// Extra background styles composited over the background, e.g. ["Snowfall"].
+    #[serde(default)]
+    pub background_layers: Vec<BackgroundStyle>,
+
+    /// Keep the background behind the clock digits readable: Off, Clear or Dim.
+    #[serde(default)]
+    pub clear_zone: ClearZone,
+
+    /// Brightness kept for dimmed background cells (0.0 - 1.0).
+    #[serde(default = "default_clear_zone_dim")]
+    pub clear_zone_dim: f32,
+
+    /// Also keep the background behind the date line readable.
+    #[serde(default)]
+    pub clear_zone_date: bool,
+
+    /// Switch to the next color theme whenever the bouncing clock hits an edge.
+    #[serde(default)]
+    pub bounce_cycle_color: bool,
+
+    /// Accessibility mode: Off, HighContrast (white on black thick digits) or
+    /// Plain (the time as a line of text, for screen readers).
+    #[serde(default)]
+    pub accessibility: Accessibility,
+
     /// Weather location for dynamic weather background (empty = auto-detect via IP).
     #[serde(default)]
     pub weather_location: String,
+
+    /// Preferred weather provider; the other one is tried when it fails.
+    #[serde(default)]
+    pub weather_provider: WeatherSource,
+
+    /// Fetch the planetary Kp index with the weather, so the aurora follows real
+    /// geomagnetic activity.
+    #[serde(default = "default_aurora_kp")]
+    pub aurora_kp: bool,
+
+    /// Show the current weather line under the date.
+    #[serde(default)]
+    pub show_weather: bool,
+
+    /// Show a CPU sparkline and memory gauge under the date.
+    #[serde(default)]
+    pub show_load: bool,
+
+    /// Announce a daylight saving change under the date in the day before it.
+    #[serde(default = "default_show_dst_notice")]
+    pub show_dst_notice: bool,
+
+    /// Check the system clock against a time server every few hours.
+    #[serde(default)]
+    pub clock_check: bool,
+
+    /// NTP server for the clock check, as a host with an optional port.
+    #[serde(default = "default_ntp_server")]
+    pub ntp_server: String,
+
+    /// URL whose HTTP Date header is used when the NTP server can't be reached (empty = none).
+    #[serde(default)]
+    pub time_url: String,
+
+    /// Seconds the system clock may be off before a warning shows.
+    #[serde(default = "default_clock_drift_threshold_secs")]
+    pub clock_drift_threshold_secs: f32,
+
+    /// Show the time corrected by the measured drift instead of the system time.
+    #[serde(default)]
+    pub correct_clock: bool,
+
+    /// Paths of `.ics` calendar files whose upcoming events show under the date.
+    #[serde(default)]
+    pub calendar_files: Vec<String>,
+
+    /// Period shown as a progress bar under the date.
+    #[serde(default)]
+    pub progress_bar: ProgressBar,
+
+    /// Unit for displayed temperatures.
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+
+    /// Where the system-reactive backgrounds get their metrics: Local, Command or File.
+    #[serde(default)]
+    pub metrics_provider: MetricsProvider,
+
+    /// Shell command printing the metrics as JSON, for the Command provider.
+    #[serde(default)]
+    pub metrics_command: String,
+
+    /// Path of a JSON metrics file, for the File provider.
+    #[serde(default)]
+    pub metrics_file: String,
+
+    /// Seconds between metrics samples (0.5 - 5.0).
+    #[serde(default = "default_metrics_interval_secs")]
+    pub metrics_interval_secs: f32,
+
+    /// Daily alarms, e.g. "07:30", "7:30 PM" or "07:30=Stand-up".
+    #[serde(default)]
+    pub alarms: Vec<String>,
+
+    /// Whether the terminal bell rings while an alarm is active.
+    #[serde(default = "default_alarm_bell")]
+    pub alarm_bell: bool,
+
+    /// Shell command run when a timer finishes, e.g. "notify-send sigye 'Timer done'",
+    /// with `SIGYE_LABEL` and `SIGYE_DURATION` set (empty = a desktop notification
+    /// in builds with the `notify` feature, else none).
+    #[serde(default)]
+    pub on_timer_complete: String,
+
+    /// Seconds without a key press before the help line hides (0 = always shown).
+    #[serde(default = "default_help_idle_secs")]
+    pub help_idle_secs: u64,
+
+    /// Actions pinned to the help line, by their `[keys]` names, in order.
+    #[serde(default = "default_help_line")]
+    pub help_line: Vec<String>,
+
+    /// Format of the date line: Long, Short or Iso.
+    #[serde(default)]
+    pub date_format: DateFormat,
+
+    /// Where the clock sits: Center, TopLeft, TopCenter, TopRight, BottomLeft, ...
+    #[serde(default)]
+    pub clock_position: ClockPosition,
+
+    /// Border around the clock: None, Plain, Rounded, Double or Thick.
+    #[serde(default)]
+    pub clock_border: ClockBorder,
+
+    /// Title on the clock border (empty = the weather location, if known).
+    #[serde(default)]
+    pub border_title: String,
+
+    /// Color theme of the border (unset = same as the clock).
+    #[serde(default)]
+    pub border_color: Option<ColorTheme>,
+
+    /// Message shown in a box next to the clock, e.g. "ON AIR" (empty = none).
+    #[serde(default)]
+    pub banner: String,
+
+    /// Side of the clock the banner goes on: Above or Below.
+    #[serde(default)]
+    pub banner_position: BannerPosition,
+
+    /// Blink the banner text.
+    #[serde(default)]
+    pub banner_blink: bool,
+
+    /// Ring of second ticks around the time: Off, WithDigits or Only (no seconds digits).
+    #[serde(default)]
+    pub seconds_ring: SecondsRing,
+
+    /// Color the ring's remaining ticks apart from the elapsed ones.
+    #[serde(default = "default_seconds_ring_split")]
+    pub seconds_ring_split: bool,
+
+    /// Grid of panes the screen is split into: "1x1" (one clock), "1x2", "2x1" or "2x2".
+    #[serde(default)]
+    pub pane_layout: PaneLayout,
+
+    /// What each pane shows, in reading order: "local", "utc", "utc+9", "stopwatch",
+    /// "timer 25m" or "blank" (empty = local, utc, stopwatch and a 5 minute timer).
+    #[serde(default)]
+    pub panes: Vec<String>,
+
+    /// Capture the mouse for clicks and scrolling (off keeps native text selection).
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+
+    /// Time ranges the clock is shown in, e.g. "Mon-Fri 08:00-19:00" (empty = always).
+    /// Outside them only the background is drawn.
+    #[serde(default)]
+    pub show_schedule: Vec<String>,
+
+    /// Nudge the clock by a cell or two now and then to prevent burn-in.
+    #[serde(default)]
+    pub burn_in_shift: bool,
+
+    /// Seconds between anti-burn-in nudges.
+    #[serde(default = "default_burn_in_interval_secs")]
+    pub burn_in_interval_secs: u64,
+
+    /// Brightness of everything drawn (0.1 - 1.0).
+    #[serde(default = "default_brightness")]
+    pub brightness: f32,
+
+    /// When to dim the display to `dim_brightness`.
+    #[serde(default)]
+    pub auto_dim: AutoDim,
+
+    /// Brightness at night when dimming automatically (0.1 - 1.0).
+    #[serde(default = "default_dim_brightness")]
+    pub dim_brightness: f32,
+
+    /// Evening time the scheduled night starts, "HH:MM".
+    #[serde(default = "default_dim_start")]
+    pub dim_start: String,
+
+    /// Morning time the scheduled night ends, "HH:MM".
+    #[serde(default = "default_dim_end")]
+    pub dim_end: String,
+
+    /// Background styles for dates, e.g. "12-20..12-31" = "Snowfall", shown instead
+    /// of the weather background. Written as a table, so it follows the plain values.
+    #[serde(default = "default_date_overrides")]
+    pub date_overrides: BTreeMap<String, String>,
+
+    /// Presets for time ranges, e.g. "Mon-Fri 09:00-18:00" = "work", applied as each
+    /// range begins; the most specific range wins where they overlap.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub preset_schedule: BTreeMap<String, String>,
+
+    /// Shell commands run when an alarm goes off, by its time, e.g. "07:30" =
+    /// "paplay wake.ogg", with `SIGYE_LABEL` set. Written as an `[alarm_commands]` table.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub alarm_commands: BTreeMap<String, String>,
+
+    /// Chimes on the hour and optionally each quarter hour, as a `[chime]` table.
+    #[serde(default)]
+    pub chime: ChimeConfig,
+
+    /// Keys for actions by name, replacing their default keys, as a `[keys]` table.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, KeyBinding>,
+
+    /// Hex colors replacing the color theme's for the digits, date, help keys or help text.
+    /// Written as a `[theme]` table, so it follows the plain values.
+    #[serde(default)]
+    pub theme: ThemeOverrides,
+
+    /// User presets by name, added to the built-in ones or replacing them.
+    /// Written as `[presets.<name>]` tables after `[theme]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// Keys bound to an action in the `[keys]` table: one key spec or a list of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    /// A single key, e.g. `quit = "q"`.
+    One(String),
+    /// Several keys, e.g. `cycle_theme = ["j", "k"]`.
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    /// Key specs of the binding, like "q" or "ctrl+c".
+    pub fn specs(&self) -> Vec<&str> {
+        match self {
+            KeyBinding::One(spec) => vec![spec.as_str()],
+            KeyBinding::Many(specs) => specs.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Audible cues on the hour, from the `[chime]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChimeConfig {
+    /// Whether to chime at all.
+    pub enabled: bool,
+    /// Also chime at a quarter past, half past and a quarter to.
+    pub quarters: bool,
+    /// Times the terminal bell rings on the hour; quarters ring once (0 = no bell).
+    pub bells: u32,
+    /// Shell command run on each chime, e.g. "paplay chime.ogg" (empty = none).
+    pub command: String,
+    /// Time range without chimes, like "22:00-07:00" (empty = none).
+    pub quiet_hours: String,
+}
+
+impl Default for ChimeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            quarters: false,
+            bells: 1,
+            command: String::new(),
+            quiet_hours: String::new(),
+        }
+    }
 }
 
 fn default_font() -> String {
     "Standard".to_string()
 }
 
+fn default_night_brightness() -> f32 {
+    0.6
+}
+
+fn default_clear_zone_dim() -> f32 {
+    0.3
+}
+
+fn default_metrics_interval_secs() -> f32 {
+    1.0
+}
+
+fn default_alarm_bell() -> bool {
+    true
+}
+
+fn default_burn_in_interval_secs() -> u64 {
+    180
+}
+
+fn default_brightness() -> f32 {
+    1.0
+}
+
+fn default_dim_brightness() -> f32 {
+    0.4
+}
+
+fn default_dim_start() -> String {
+    "22:00".to_string()
+}
+
+fn default_dim_end() -> String {
+    "07:00".to_string()
+}
+
+fn default_am_pm_gap() -> usize {
+    1
+}
+
+fn default_clock_scale() -> usize {
+    1
+}
+
+fn default_help_idle_secs() -> u64 {
+    10
+}
+
+fn default_help_line() -> Vec<String> {
+    [
+        "quit",
+        "toggle_format",
+        "cycle_precision",
+        "cycle_display_mode",
+        "cycle_theme",
+        "cycle_animation",
+        "cycle_background",
+        "pick_background",
+        "cycle_info_line",
+        "toggle_weather",
+        "toggle_drift",
+        "settings",
+        "help",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_ntp_server() -> String {
+    "pool.ntp.org".to_string()
+}
+
+fn default_clock_drift_threshold_secs() -> f32 {
+    2.0
+}
+
+fn default_aurora_kp() -> bool {
+    true
+}
+
+fn default_show_dst_notice() -> bool {
+    true
+}
+
+fn default_seconds_ring_split() -> bool {
+    true
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_date_overrides() -> BTreeMap<String, String> {
+    BTreeMap::from([("12-20..12-31".to_string(), "Snowfall".to_string())])
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             font_name: default_font(),
             color_theme: ColorTheme::default(),
+            night_brightness: default_night_brightness(),
             time_format: TimeFormat::default(),
+            am_pm_style: AmPmStyle::default(),
+            pad_hour: false,
+            digit_gap: 0,
+            colon_gap: 0,
+            am_pm_gap: default_am_pm_gap(),
+            digit_fill: String::new(),
+            digit_blank: String::new(),
+            clock_scale: default_clock_scale(),
+            display_mode: DisplayMode::default(),
+            fuzzy_granularity: FuzzyGranularity::default(),
+            subsecond_precision: SubsecondPrecision::default(),
             animation_style: AnimationStyle::default(),
             animation_speed: AnimationSpeed::default(),
             colon_blink: false,
             background_style: BackgroundStyle::default(),
+            background_layers: Vec::new(),
+            clear_zone: ClearZone::default(),
+            clear_zone_dim: default_clear_zone_dim(),
+            clear_zone_date: false,
+            bounce_cycle_color: false,
+            accessibility: Accessibility::default(),
             weather_location: String::new(),
+            weather_provider: WeatherSource::default(),
+            aurora_kp: default_aurora_kp(),
+            show_weather: false,
+            show_load: false,
+            show_dst_notice: default_show_dst_notice(),
+            clock_check: false,
+            ntp_server: default_ntp_server(),
+            time_url: String::new(),
+            clock_drift_threshold_secs: default_clock_drift_threshold_secs(),
+            correct_clock: false,
+            calendar_files: Vec::new(),
+            progress_bar: ProgressBar::default(),
+            temperature_unit: TemperatureUnit::default(),
+            metrics_provider: MetricsProvider::default(),
+            metrics_command: String::new(),
+            metrics_file: String::new(),
+            metrics_interval_secs: default_metrics_interval_secs(),
+            alarms: Vec::new(),
+            alarm_bell: default_alarm_bell(),
+            on_timer_complete: String::new(),
+            help_idle_secs: default_help_idle_secs(),
+            help_line: default_help_line(),
+            date_format: DateFormat::default(),
+            clock_position: ClockPosition::default(),
+            clock_border: ClockBorder::default(),
+            border_title: String::new(),
+            border_color: None,
+            banner: String::new(),
+            banner_position: BannerPosition::default(),
+            banner_blink: false,
+            seconds_ring: SecondsRing::default(),
+            seconds_ring_split: default_seconds_ring_split(),
+            pane_layout: PaneLayout::default(),
+            panes: Vec::new(),
+            mouse: default_mouse(),
+            show_schedule: Vec::new(),
+            burn_in_shift: false,
+            burn_in_interval_secs: default_burn_in_interval_secs(),
+            brightness: default_brightness(),
+            auto_dim: AutoDim::default(),
+            dim_brightness: default_dim_brightness(),
+            dim_start: default_dim_start(),
+            dim_end: default_dim_end(),
+            date_overrides: default_date_overrides(),
+            preset_schedule: BTreeMap::new(),
+            alarm_commands: BTreeMap::new(),
+            chime: ChimeConfig::default(),
+            keys: BTreeMap::new(),
+            theme: ThemeOverrides::default(),
+            presets: BTreeMap::new(),
         }
     }
 }
@@ -65,23 +567,53 @@ impl Default for Config {
 impl Config {
     /// Load configuration from file, or return defaults if not found.
     pub fn load() -> Self {
+        Self::try_load().unwrap_or_else(|e| {
+            eprintln!("Warning: {e}");
+            Self::default()
+        })
+    }
+
+    /// Load configuration from file, defaults if not found, or why the file
+    /// couldn't be used.
+    ///
+    /// Without a file, the time format follows the locale.
+    pub fn try_load() -> Result<Self, String> {
         let config_path = Self::config_file_path();
+        if !config_path.exists() {
+            return Ok(Self {
+                time_format: TimeFormat::detect_default(|name| std::env::var(name).ok()),
+                ..Self::default()
+            });
+        }
+        let contents = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read config file: {e}"))?;
+        Self::parse(&contents).map_err(|e| format!("Failed to parse config file: {e}"))
+    }
 
-        if config_path.exists() {
-            match fs::read_to_string(&config_path) {
-                Ok(contents) => match toml::from_str(&contents) {
-                    Ok(config) => return config,
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse config file: {e}");
-                    }
-                },
-                Err(e) => {
-                    eprintln!("Warning: Failed to read config file: {e}");
+    /// Read the config file again after it changed, or why it couldn't be used.
+    ///
+    /// Unlike [`Config::try_load`], a missing file is an error rather than the defaults.
+    pub fn reload() -> Result<Self, String> {
+        let contents = fs::read_to_string(Self::config_file_path()).map_err(|e| e.to_string())?;
+        Self::parse(&contents)
+    }
+
+    /// Parse the contents of a config file, failing with the reason and its
+    /// line, e.g. "invalid type: string \"yes\", expected a boolean (line 3)".
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| {
+            let message = e.message().trim_end();
+            match e.span() {
+                Some(span) => {
+                    let line = contents[..span.start.min(contents.len())]
+                        .matches('\n')
+                        .count()
+                        + 1;
+                    format!("{message} (line {line})")
                 }
+                None => message.to_string(),
             }
-        }
-
-        Self::default()
+        })
     }
 
     /// Save configuration to file.
@@ -117,6 +649,16 @@ impl Config {
     pub fn fonts_dir() -> PathBuf {
         Self::config_dir().join("fonts")
     }
+
+    /// Get the cache directory path (weather data and other non-essential state).
+    pub fn cache_dir() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("com", "sigye", "sigye") {
+            proj_dirs.cache_dir().to_path_buf()
+        } else {
+            // Fallback to home directory
+            dirs_fallback().join(".cache").join("sigye")
+        }
+    }
 }
 
 /// Fallback to get home directory if ProjectDirs fails.
@@ -143,3 +685,244 @@ impl std::fmt::Display for ConfigError {
 }
 
 impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use sigye_core::theme::HexColor;
+
+    use super::*;
+
+    #[test]
+    fn test_partial_theme_overrides() {
+        let config: Config = toml::from_str(
+            r##"
+            color_theme = "Green"
+
+            [theme]
+            date_color = "#808080"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(config.color_theme, ColorTheme::Green);
+        assert_eq!(
+            config.theme,
+            ThemeOverrides {
+                date_color: HexColor::parse("#808080"),
+                ..ThemeOverrides::default()
+            }
+        );
+
+        // Only the overridden colors are written back, after the plain values
+        let saved = toml::to_string(&config).unwrap();
+        assert!(
+            saved.ends_with("[theme]\ndate_color = \"#808080\"\n"),
+            "{saved}"
+        );
+        assert_eq!(
+            toml::from_str::<Config>(&saved).unwrap().theme,
+            config.theme
+        );
+    }
+
+    #[test]
+    fn test_invalid_theme_color_is_an_error() {
+        assert!(toml::from_str::<Config>("[theme]\nmuted_color = \"grey\"\n").is_err());
+        // No table at all keeps every default
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.theme, ThemeOverrides::default());
+    }
+
+    #[test]
+    fn test_date_overrides_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [date_overrides]
+            "12-28..01-02" = "Starfield"
+            "2026-10-31" = "Foggy"
+            "#,
+        )
+        .unwrap();
+        // A table of its own replaces the default snow
+        assert_eq!(
+            config.date_overrides,
+            BTreeMap::from([
+                ("12-28..01-02".to_string(), "Starfield".to_string()),
+                ("2026-10-31".to_string(), "Foggy".to_string()),
+            ])
+        );
+        let saved = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<Config>(&saved).unwrap().date_overrides,
+            config.date_overrides
+        );
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.date_overrides, default_date_overrides());
+    }
+
+    #[test]
+    fn test_preset_schedule_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [preset_schedule]
+            "06:00-09:00" = "sunrise"
+            "Mon-Fri 09:00-18:00" = "work"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.preset_schedule.len(), 2);
+        let saved = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<Config>(&saved).unwrap().preset_schedule,
+            config.preset_schedule
+        );
+        // Left out of saved configs until used
+        assert!(
+            !toml::to_string(&Config::default())
+                .unwrap()
+                .contains("preset_schedule")
+        );
+    }
+
+    #[test]
+    fn test_completion_hooks() {
+        let config: Config = toml::from_str(
+            r#"
+            on_timer_complete = "notify-send sigye 'Timer done'"
+
+            [alarm_commands]
+            "07:30" = "paplay wake.ogg"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.on_timer_complete, "notify-send sigye 'Timer done'");
+        assert_eq!(config.alarm_commands["07:30"], "paplay wake.ogg");
+        let saved = toml::to_string(&config).unwrap();
+        let reloaded = toml::from_str::<Config>(&saved).unwrap();
+        assert_eq!(reloaded.on_timer_complete, config.on_timer_complete);
+        assert_eq!(reloaded.alarm_commands, config.alarm_commands);
+        assert!(
+            !toml::to_string(&Config::default())
+                .unwrap()
+                .contains("alarm_commands")
+        );
+    }
+
+    #[test]
+    fn test_chime_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [chime]
+            enabled = true
+            command = "paplay chime.ogg"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.chime,
+            ChimeConfig {
+                enabled: true,
+                command: "paplay chime.ogg".to_string(),
+                ..ChimeConfig::default()
+            }
+        );
+        let saved = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<Config>(&saved).unwrap().chime,
+            config.chime
+        );
+        assert!(!Config::default().chime.enabled);
+    }
+
+    #[test]
+    fn test_keys_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys]
+            quit = "ctrl+q"
+            cycle_theme = ["j", "k"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.keys["quit"].specs(), ["ctrl+q"]);
+        assert_eq!(config.keys["cycle_theme"].specs(), ["j", "k"]);
+        let saved = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&saved).unwrap().keys, config.keys);
+        assert!(
+            !toml::to_string(&Config::default())
+                .unwrap()
+                .contains("[keys]")
+        );
+    }
+
+    #[test]
+    fn test_partial_presets() {
+        let config: Config = toml::from_str(
+            r##"
+            [presets.calm]
+            background_style = "Aurora"
+
+            [presets.focus]
+            color_theme = "White"
+            font_name = "Small"
+            animation_speed = "Slow"
+            theme = { digit_color = "#ffffff" }
+
+            [presets.empty]
+            "##,
+        )
+        .unwrap();
+        let preset = |name: &str| config.presets[name].clone();
+
+        // Fields left out take their defaults, one by one
+        assert_eq!(
+            preset("calm"),
+            Preset {
+                background_style: BackgroundStyle::Aurora,
+                ..Preset::default()
+            }
+        );
+        assert_eq!(
+            preset("focus"),
+            Preset {
+                color_theme: ColorTheme::White,
+                font_name: "Small".to_string(),
+                animation_speed: AnimationSpeed::Slow,
+                theme: ThemeOverrides {
+                    digit_color: HexColor::parse("#ffffff"),
+                    ..ThemeOverrides::default()
+                },
+                ..Preset::default()
+            }
+        );
+        assert_eq!(preset("empty"), Preset::default());
+        assert_eq!(Preset::default().font_name, default_font());
+
+        // Written back after the plain values, and read back the same
+        let saved = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<Config>(&saved).unwrap().presets,
+            config.presets
+        );
+        assert!(
+            !toml::to_string(&Config::default())
+                .unwrap()
+                .contains("presets")
+        );
+    }
+
+    #[test]
+    fn test_invalid_preset_field_is_an_error() {
+        assert!(toml::from_str::<Config>("[presets.bad]\nbackground_style = \"Lava\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        assert!(Config::parse("show_weather = true\n").unwrap().show_weather);
+        let error = Config::parse("font_name = \"Big\"\n\nshow_weather = \"yes\"\n").unwrap_err();
+        assert!(error.starts_with("invalid type"), "{error}");
+        assert!(error.ends_with("(line 3)"), "{error}");
+        let error = Config::parse("show_weather = \n").unwrap_err();
+        assert!(error.ends_with("(line 1)"), "{error}");
+    }
+}
@@ -0,0 +1,149 @@
+//! Brightness of the whole display, optionally dimmed at night.
+//!
+//! Night is either a fixed evening-to-morning range, ramping down over half
+//! an hour after it starts and back up after it ends, or follows the sun,
+//! with twilight halfway between day and night.
+
+use serde::{Deserialize, Serialize};
+
+use crate::TimeOfDay;
+
+/// Lowest brightness, so the clock never goes fully dark.
+pub const MIN_BRIGHTNESS: f32 = 0.1;
+
+/// Minutes the brightness takes to ramp between day and night.
+pub const RAMP_MINUTES: u32 = 30;
+
+/// Minutes in a day.
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// When the display dims to the night brightness.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoDim {
+    /// Always the configured brightness.
+    #[default]
+    Off,
+    /// Between the configured evening and morning times.
+    Schedule,
+    /// From dusk to dawn.
+    Sun,
+}
+
+/// Keep a brightness within [`MIN_BRIGHTNESS`] and full brightness.
+pub fn clamp_brightness(brightness: f32) -> f32 {
+    if brightness.is_nan() {
+        return 1.0;
+    }
+    brightness.clamp(MIN_BRIGHTNESS, 1.0)
+}
+
+/// How dark it is at `minute` past midnight (0.0 = day, 1.0 = night) for a
+/// night from `start` to `end`, in minutes past midnight.
+///
+/// The night may run past midnight; equal times mean no night at all.
+pub fn night_ramp(minute: u32, start: u32, end: u32) -> f32 {
+    let since = |from: u32, to: u32| {
+        (to % MINUTES_PER_DAY + MINUTES_PER_DAY - from % MINUTES_PER_DAY) % MINUTES_PER_DAY
+    };
+    let ramp = |minutes: u32| (minutes as f32 / RAMP_MINUTES as f32).min(1.0);
+    let night = since(start, end);
+    if night == 0 {
+        return 0.0;
+    }
+    let into = since(start, minute);
+    if into < night {
+        return ramp(into);
+    }
+    // Brightening back up from however dark the night got
+    (ramp(night) - ramp(since(end, minute))).max(0.0)
+}
+
+/// How dark it is at a time of day (0.0 = day, 1.0 = night).
+pub fn sun_ramp(time_of_day: TimeOfDay) -> f32 {
+    match time_of_day {
+        TimeOfDay::Day => 0.0,
+        TimeOfDay::Dawn | TimeOfDay::Dusk => 0.5,
+        TimeOfDay::Night => 1.0,
+    }
+}
+
+/// Brightness between `day` and `night` for a darkness from [`night_ramp`] or [`sun_ramp`].
+pub fn blend(day: f32, night: f32, darkness: f32) -> f32 {
+    let darkness = darkness.clamp(0.0, 1.0);
+    clamp_brightness(day + (night - day) * darkness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn at(hour: u32, minute: u32) -> u32 {
+        hour * 60 + minute
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_clamp_brightness() {
+        assert_eq!(clamp_brightness(0.5), 0.5);
+        assert_eq!(clamp_brightness(0.0), MIN_BRIGHTNESS);
+        assert_eq!(clamp_brightness(3.0), 1.0);
+        assert_eq!(clamp_brightness(f32::NAN), 1.0);
+    }
+
+    #[test]
+    fn test_overnight_ramp() {
+        let ramp = |minute| night_ramp(minute, at(22, 0), at(7, 0));
+        assert_close(ramp(at(12, 0)), 0.0);
+        assert_close(ramp(at(21, 59)), 0.0);
+        // Dimming down over half an hour
+        assert_close(ramp(at(22, 0)), 0.0);
+        assert_close(ramp(at(22, 15)), 0.5);
+        assert_close(ramp(at(22, 30)), 1.0);
+        assert_close(ramp(at(3, 0)), 1.0);
+        assert_close(ramp(at(6, 59)), 1.0);
+        // And back up after the night
+        assert_close(ramp(at(7, 0)), 1.0);
+        assert_close(ramp(at(7, 15)), 0.5);
+        assert_close(ramp(at(7, 30)), 0.0);
+    }
+
+    #[test]
+    fn test_daytime_ramp() {
+        // A "night" that doesn't cross midnight, e.g. for a day sleeper
+        let ramp = |minute| night_ramp(minute, at(9, 0), at(17, 0));
+        assert_close(ramp(at(8, 0)), 0.0);
+        assert_close(ramp(at(12, 0)), 1.0);
+        assert_close(ramp(at(17, 6)), 0.8);
+        assert_close(ramp(at(23, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_short_night_never_fully_dark() {
+        let ramp = |minute| night_ramp(minute, at(23, 50), at(0, 5));
+        assert_close(ramp(at(0, 0)), 10.0 / 30.0);
+        // Brightening starts from how dark it got, without a jump
+        assert_close(ramp(at(0, 5)), 15.0 / 30.0);
+        assert_close(ramp(at(0, 20)), 0.0);
+        assert_close(night_ramp(at(3, 0), at(8, 0), at(8, 0)), 0.0);
+    }
+
+    #[test]
+    fn test_sun_ramp() {
+        assert_eq!(sun_ramp(TimeOfDay::Day), 0.0);
+        assert_eq!(sun_ramp(TimeOfDay::Dusk), 0.5);
+        assert_eq!(sun_ramp(TimeOfDay::Night), 1.0);
+    }
+
+    #[test]
+    fn test_blend() {
+        assert_close(blend(1.0, 0.4, 0.0), 1.0);
+        assert_close(blend(1.0, 0.4, 0.5), 0.7);
+        assert_close(blend(1.0, 0.4, 1.0), 0.4);
+        // Never darker than the minimum, even with a night level below it
+        assert_close(blend(0.8, 0.0, 1.0), MIN_BRIGHTNESS);
+        assert_close(blend(0.8, 0.4, 7.0), 0.4);
+    }
+}
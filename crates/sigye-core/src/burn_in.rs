@@ -0,0 +1,110 @@
+//! Anti-burn-in shifting: nudging the clock a cell or two now and then.
+//!
+//! The offset steps along a fixed loop, one cell per interval, so the bright
+//! digit cells never sit in one place for hours and the clock doesn't jump.
+
+use ratatui::layout::Rect;
+
+/// Offsets (columns, rows) visited in turn: the edge of a 5x3 box around the
+/// clock's place, walked one cell per step.
+const PATTERN: [(i16, i16); 12] = [
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    (2, 0),
+    (2, 1),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-2, 1),
+    (-2, 0),
+];
+
+/// Offset at the Unix time `unix_secs` when shifting every `interval_secs` seconds.
+pub fn offset_at(unix_secs: i64, interval_secs: u64) -> (i16, i16) {
+    let step = unix_secs.max(0) as u64 / interval_secs.max(1);
+    PATTERN[(step % PATTERN.len() as u64) as usize]
+}
+
+/// Move the clock block by `offset`, keeping it inside `area`.
+///
+/// `placed` may be wider than the content (centered positions span the screen),
+/// so the content is first narrowed to `content_width` centered in it; a
+/// full-width rect couldn't move sideways otherwise.
+pub fn shift(placed: Rect, content_width: u16, offset: (i16, i16), area: Rect) -> Rect {
+    let width = content_width.min(placed.width);
+    let x = placed.x + (placed.width - width) / 2;
+    let clamp = |pos: u16, delta: i16, size: u16, start: u16, end: u16| {
+        let max = end.saturating_sub(size).max(start);
+        (pos as i32 + delta as i32).clamp(start as i32, max as i32) as u16
+    };
+    Rect {
+        x: clamp(x, offset.0, width, area.left(), area.right()),
+        y: clamp(placed.y, offset.1, placed.height, area.top(), area.bottom()),
+        width,
+        height: placed.height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_pattern_moves_one_cell_per_step() {
+        for (i, &(x, y)) in PATTERN.iter().enumerate() {
+            let (next_x, next_y) = PATTERN[(i + 1) % PATTERN.len()];
+            assert_eq!((next_x - x).abs() + (next_y - y).abs(), 1, "step {i}");
+            assert!(x.abs() <= 2 && y.abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_offset_changes_every_interval() {
+        assert_eq!(offset_at(0, 180), offset_at(179, 180));
+        assert_ne!(offset_at(179, 180), offset_at(180, 180));
+        // A zero interval is treated as one second rather than dividing by zero
+        assert_eq!(offset_at(5, 0), offset_at(5, 1));
+    }
+
+    #[test]
+    fn test_simulated_hour_stays_in_bounds() {
+        let area = Rect::new(0, 0, 80, 24);
+        let placed = Rect::new(0, 7, 80, 10);
+        let start = 1_760_000_000;
+        let mut seen = HashSet::new();
+        for second in 0..3600 {
+            let offset = offset_at(start + second, 180);
+            let rect = shift(placed, 40, offset, area);
+            assert!(area.contains(rect.as_position()), "{rect:?}");
+            assert!(rect.right() <= area.right() && rect.bottom() <= area.bottom());
+            // Never more than two columns or a row from the centered place
+            assert!((rect.x as i32 - 20).abs() <= 2 && (rect.y as i32 - 7).abs() <= 1);
+            seen.insert((rect.x, rect.y));
+        }
+        assert!(seen.len() >= 12, "visited {seen:?}");
+    }
+
+    #[test]
+    fn test_shift_clamps_to_small_areas() {
+        let area = Rect::new(0, 0, 42, 10);
+        // Content filling the area can't move at all
+        let placed = Rect::new(0, 0, 42, 10);
+        for offset in PATTERN {
+            assert_eq!(shift(placed, 42, offset, area), placed);
+        }
+
+        // A corner position only moves inward
+        let placed = Rect::new(0, 0, 20, 5);
+        assert_eq!(shift(placed, 20, (-2, -1), area), placed);
+        assert_eq!(shift(placed, 20, (2, 1), area), Rect::new(2, 1, 20, 5));
+
+        // Content wider than the area stays at its edge
+        let placed = Rect::new(0, 0, 42, 10);
+        assert_eq!(shift(placed, 60, (2, 1), area), placed);
+    }
+}
@@ -0,0 +1,302 @@
+//! Background styles for particular dates, like snow in late December.
+//!
+//! A date pattern is a day of every year (`12-25`), a specific date
+//! (`2026-12-25`), or an inclusive range of either (`12-20..12-31`,
+//! `2026-12-24..2027-01-02`). A yearly range that ends before it starts wraps
+//! past New Year, so `12-28..01-02` covers the turn of every year.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::BackgroundStyle;
+
+/// Leap year used to check month-days, so February 29 is accepted.
+const LEAP_YEAR: i32 = 2000;
+
+/// A day of the year as (month, day).
+type MonthDay = (u32, u32);
+
+/// Dates a pattern matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePattern {
+    /// Every year from the first day to the last, inclusive.
+    Yearly(MonthDay, MonthDay),
+    /// From the first date to the last, inclusive.
+    Dates(NaiveDate, NaiveDate),
+}
+
+impl DatePattern {
+    /// Parse "MM-DD", "YYYY-MM-DD", or a range of either joined by "..".
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (first, last) = spec.split_once("..").unwrap_or((spec, spec));
+        match (parse_end(first.trim())?, parse_end(last.trim())?) {
+            (End::Yearly(first), End::Yearly(last)) => Ok(Self::Yearly(first, last)),
+            (End::Date(first), End::Date(last)) if first <= last => Ok(Self::Dates(first, last)),
+            (End::Date(_), End::Date(_)) => Err(format!("\"{spec}\" ends before it starts")),
+            _ => Err(format!(
+                "\"{spec}\" mixes MM-DD and YYYY-MM-DD, use one for both ends"
+            )),
+        }
+    }
+
+    /// Check if the pattern matches `date`.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match *self {
+            Self::Yearly(first, last) => {
+                let day = (date.month(), date.day());
+                if first <= last {
+                    (first..=last).contains(&day)
+                } else {
+                    // Wraps past New Year
+                    day >= first || day <= last
+                }
+            }
+            Self::Dates(first, last) => (first..=last).contains(&date),
+        }
+    }
+
+    /// Number of days the pattern spans in a year, so narrower patterns can win.
+    fn span_days(&self) -> i64 {
+        match *self {
+            Self::Yearly(first, last) => {
+                let ordinal = |(month, day)| {
+                    NaiveDate::from_ymd_opt(LEAP_YEAR, month, day).map_or(0, |d| d.ordinal0())
+                };
+                let (first, last) = (ordinal(first), ordinal(last));
+                let year = 366;
+                i64::from((last + year - first) % year + 1)
+            }
+            Self::Dates(first, last) => (last - first).num_days() + 1,
+        }
+    }
+}
+
+/// One end of a date pattern.
+enum End {
+    Yearly(MonthDay),
+    Date(NaiveDate),
+}
+
+/// Parse "MM-DD" or "YYYY-MM-DD".
+fn parse_end(text: &str) -> Result<End, String> {
+    let invalid = || format!("invalid date \"{text}\", expected MM-DD or YYYY-MM-DD");
+    let parts: Vec<&str> = text.split('-').collect();
+    let number = |part: &str| part.parse::<u32>().map_err(|_| invalid());
+    match parts[..] {
+        [month, day] => {
+            let (month, day) = (number(month)?, number(day)?);
+            NaiveDate::from_ymd_opt(LEAP_YEAR, month, day).ok_or_else(invalid)?;
+            Ok(End::Yearly((month, day)))
+        }
+        [year, month, day] => {
+            let year = year.parse::<i32>().map_err(|_| invalid())?;
+            NaiveDate::from_ymd_opt(year, number(month)?, number(day)?)
+                .map(End::Date)
+                .ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// A background style shown on the dates of a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateOverride {
+    /// Dates the style is shown on.
+    pub pattern: DatePattern,
+    /// Background style shown.
+    pub style: BackgroundStyle,
+}
+
+impl DateOverride {
+    /// Parse a pattern like "12-20..12-31" and a background style name like "Snowfall".
+    ///
+    /// Weather and the reactive styles can't be used: they need the weather or
+    /// system monitors that the date alone doesn't start.
+    pub fn parse(pattern: &str, style: &str) -> Result<Self, String> {
+        let pattern = DatePattern::parse(pattern)?;
+        let style = BackgroundStyle::by_name(style)
+            .ok_or_else(|| format!("unknown background style \"{style}\""))?;
+        if style == BackgroundStyle::Weather || style.is_reactive() {
+            return Err(format!("{} can't be used for a date", style.display_name()));
+        }
+        Ok(Self { pattern, style })
+    }
+}
+
+/// Background styles for dates; the narrowest matching pattern wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateOverrides {
+    overrides: Vec<DateOverride>,
+}
+
+impl DateOverrides {
+    /// Create the overrides from a list; among equally narrow patterns the first wins.
+    pub fn new(overrides: Vec<DateOverride>) -> Self {
+        Self { overrides }
+    }
+
+    /// Parse pattern and style pairs, leaving out invalid ones and returning
+    /// each of them with why, so they can be reported at startup.
+    pub fn parse_all<'a>(
+        entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let overrides = entries
+            .into_iter()
+            .filter_map(|(pattern, style)| {
+                DateOverride::parse(pattern, style)
+                    .inspect_err(|e| errors.push(format!("\"{pattern}\" = \"{style}\": {e}")))
+                    .ok()
+            })
+            .collect();
+        (Self::new(overrides), errors)
+    }
+
+    /// Background style for `date`, if any pattern matches it.
+    pub fn style_on(&self, date: NaiveDate) -> Option<BackgroundStyle> {
+        self.overrides
+            .iter()
+            .filter(|entry| entry.pattern.contains(date))
+            .min_by_key(|entry| entry.pattern.span_days())
+            .map(|entry| entry.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn contains(spec: &str, year: i32, month: u32, day: u32) -> bool {
+        DatePattern::parse(spec)
+            .unwrap()
+            .contains(date(year, month, day))
+    }
+
+    #[test]
+    fn test_parse_patterns() {
+        assert_eq!(
+            DatePattern::parse("12-25"),
+            Ok(DatePattern::Yearly((12, 25), (12, 25)))
+        );
+        assert_eq!(
+            DatePattern::parse(" 12-20 .. 12-31 "),
+            Ok(DatePattern::Yearly((12, 20), (12, 31)))
+        );
+        assert_eq!(
+            DatePattern::parse("2026-12-31"),
+            Ok(DatePattern::Dates(date(2026, 12, 31), date(2026, 12, 31)))
+        );
+        assert!(DatePattern::parse("02-29").is_ok());
+        for invalid in [
+            "",
+            "12",
+            "13-01",
+            "12-32",
+            "02-30",
+            "2027-02-29",
+            "christmas",
+            "12-20..",
+            "12-20..2026-12-31",
+            "2027-01-02..2026-12-28",
+            "12-20...12-31",
+        ] {
+            assert!(DatePattern::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_single_days() {
+        assert!(contains("12-25", 2026, 12, 25));
+        assert!(contains("12-25", 2031, 12, 25));
+        assert!(!contains("12-25", 2026, 12, 24));
+        assert!(contains("2026-12-25", 2026, 12, 25));
+        assert!(!contains("2026-12-25", 2027, 12, 25));
+        assert!(contains("02-29", 2028, 2, 29));
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert!(contains("12-20..12-31", 2026, 12, 20));
+        assert!(contains("12-20..12-31", 2026, 12, 31));
+        assert!(!contains("12-20..12-31", 2026, 12, 19));
+        assert!(!contains("12-20..12-31", 2027, 1, 1));
+
+        assert!(contains("2026-12-24..2027-01-02", 2027, 1, 2));
+        assert!(!contains("2026-12-24..2027-01-02", 2027, 12, 24));
+    }
+
+    #[test]
+    fn test_ranges_across_new_year() {
+        for (year, month, day) in [(2026, 12, 28), (2026, 12, 31), (2027, 1, 1), (2027, 1, 2)] {
+            assert!(contains("12-28..01-02", year, month, day), "{month}-{day}");
+        }
+        for (year, month, day) in [(2026, 12, 27), (2027, 1, 3), (2027, 6, 15)] {
+            assert!(!contains("12-28..01-02", year, month, day), "{month}-{day}");
+        }
+    }
+
+    #[test]
+    fn test_parse_override() {
+        let entry = DateOverride::parse("12-20..12-31", "snowfall").unwrap();
+        assert_eq!(entry.style, BackgroundStyle::Snowfall);
+        assert!(DateOverride::parse("12-20..12-31", "Blizzard").is_err());
+        assert!(DateOverride::parse("12-20..12-31", "Weather").is_err());
+        assert!(DateOverride::parse("12-20..12-31", "System Pulse").is_err());
+        assert!(DateOverride::parse("Dec 20", "Snowfall").is_err());
+    }
+
+    #[test]
+    fn test_narrowest_pattern_wins() {
+        let overrides = DateOverrides::new(vec![
+            DateOverride::parse("12-01..01-31", "Frost").unwrap(),
+            DateOverride::parse("12-20..12-31", "Snowfall").unwrap(),
+            DateOverride::parse("12-31", "Starfield").unwrap(),
+        ]);
+        assert_eq!(
+            overrides.style_on(date(2026, 12, 5)),
+            Some(BackgroundStyle::Frost)
+        );
+        assert_eq!(
+            overrides.style_on(date(2026, 12, 24)),
+            Some(BackgroundStyle::Snowfall)
+        );
+        assert_eq!(
+            overrides.style_on(date(2026, 12, 31)),
+            Some(BackgroundStyle::Starfield)
+        );
+        assert_eq!(
+            overrides.style_on(date(2027, 1, 15)),
+            Some(BackgroundStyle::Frost)
+        );
+        assert_eq!(overrides.style_on(date(2027, 6, 1)), None);
+        assert_eq!(DateOverrides::default().style_on(date(2026, 12, 24)), None);
+    }
+
+    #[test]
+    fn test_parse_all_reports_the_invalid_entries() {
+        let (overrides, errors) = DateOverrides::parse_all([
+            ("12-20..12-31", "Snowfall"),
+            ("13-40", "Snowfall"),
+            ("12-31", "Blizzard"),
+        ]);
+        assert_eq!(
+            overrides,
+            DateOverrides::new(vec![
+                DateOverride::parse("12-20..12-31", "Snowfall").unwrap()
+            ])
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(
+            errors[0].starts_with("\"13-40\" = \"Snowfall\": "),
+            "{errors:?}"
+        );
+        assert!(
+            errors[1].starts_with("\"12-31\" = \"Blizzard\": "),
+            "{errors:?}"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+//! Daylight saving time changes: finding when the clocks go forward or back,
+//! so the jump can be announced before it happens.
+
+use chrono::{
+    DateTime, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Timelike, Utc,
+};
+
+use crate::TimeFormat;
+
+/// Changes before this hour are announced for "tonight".
+const NIGHT_END_HOUR: u32 = 6;
+
+/// Change of a time zone's UTC offset, as seen on a wall clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Instant of the change.
+    pub at: DateTime<Utc>,
+    /// Local time the clocks reach just as they change, e.g. 02:00 in spring.
+    pub before: NaiveDateTime,
+    /// Local time the clocks show instead, e.g. 03:00 in spring.
+    pub after: NaiveDateTime,
+}
+
+impl Transition {
+    /// Check if the clocks go forward, skipping a stretch of local time.
+    pub fn is_forward(&self) -> bool {
+        self.after > self.before
+    }
+
+    /// Describe the change for a notice on `today`, e.g. "Clocks go forward
+    /// at 02:00 → 03:00 tonight".
+    pub fn notice(&self, today: NaiveDate, time_format: TimeFormat) -> String {
+        let direction = if self.is_forward() { "forward" } else { "back" };
+        let pattern = match time_format {
+            TimeFormat::TwentyFourHour => "%H:%M",
+            TimeFormat::TwelveHour => "%-I:%M %p",
+        };
+        let when = if self.before.hour() < NIGHT_END_HOUR {
+            "tonight"
+        } else if self.before.date() == today {
+            "today"
+        } else {
+            "tomorrow"
+        };
+        format!(
+            "Clocks go {direction} at {} → {} {when}",
+            self.before.format(pattern),
+            self.after.format(pattern)
+        )
+    }
+}
+
+/// Find the change of UTC offset in `tz` on the local `date`, if any.
+///
+/// The offsets at the start of the day and the next are compared, and the
+/// instant of the change is then narrowed down to the second between them.
+pub fn transition_on<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<Transition> {
+    let start = day_start(tz, date)?;
+    let end = day_start(tz, date.succ_opt()?)?;
+    let offset_at = |timestamp: i64| {
+        DateTime::from_timestamp(timestamp, 0)
+            .map(|utc| tz.offset_from_utc_datetime(&utc.naive_utc()).fix())
+    };
+    let old = offset_at(start.timestamp())?;
+    let new = offset_at(end.timestamp())?;
+    if old == new {
+        return None;
+    }
+
+    // The last second on the old offset and the first on the new
+    let (mut low, mut high) = (start.timestamp(), end.timestamp());
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if offset_at(middle)? == old {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    let at = DateTime::from_timestamp(high, 0)?;
+    Some(Transition {
+        at,
+        before: at.naive_utc() + old,
+        after: at.naive_utc() + new,
+    })
+}
+
+/// Find the next change of UTC offset from `now` until `within` later, if any.
+pub fn upcoming<Tz: TimeZone>(now: &DateTime<Tz>, within: TimeDelta) -> Option<Transition> {
+    let tz = now.timezone();
+    let last_day = (now.clone() + within).date_naive();
+    let today = now.date_naive();
+    let now = now.to_utc();
+    today
+        .iter_days()
+        .take_while(|date| *date <= last_day)
+        .filter_map(|date| transition_on(&tz, date))
+        .find(|transition| transition.at > now && transition.at - now <= within)
+}
+
+/// First instant of the local `date`, the first hour that exists on it when
+/// midnight is skipped.
+fn day_start<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<DateTime<Utc>> {
+    (0..24).find_map(|hour| {
+        let time = NaiveTime::from_hms_opt(hour, 0, 0)?;
+        tz.from_local_datetime(&date.and_time(time))
+            .earliest()
+            .map(|start| start.to_utc())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, FixedOffset, MappedLocalTime, NaiveDate, Timelike, Weekday};
+
+    use super::*;
+    use crate::TimeParts;
+
+    /// Time zones with their daylight saving rules written out, standing in
+    /// for a time zone database.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Zone {
+        /// Europe/Berlin: +1, +2 from the last Sunday of March to the last of
+        /// October, changing at 01:00 UTC.
+        Berlin,
+        /// America/New_York: -5, -4 from the second Sunday of March to the
+        /// first of November, changing at 02:00 local time.
+        NewYork,
+        /// Asia/Tokyo: +9 all year.
+        Tokyo,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct ZoneOffset {
+        zone: Zone,
+        offset: FixedOffset,
+    }
+
+    impl Offset for ZoneOffset {
+        fn fix(&self) -> FixedOffset {
+            self.offset
+        }
+    }
+
+    /// The `n`th `weekday` of a month, counting from the end when negative.
+    fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i8) -> NaiveDate {
+        if n > 0 {
+            NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8).unwrap()
+        } else {
+            NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5).unwrap_or_else(|| {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4).unwrap()
+            })
+        }
+    }
+
+    impl Zone {
+        /// Standard and daylight offsets in hours, and the UTC instants
+        /// daylight time starts and ends in `year`.
+        fn rules(self, year: i32) -> (i32, i32, Option<(NaiveDateTime, NaiveDateTime)>) {
+            let at = |date: NaiveDate, hour| date.and_hms_opt(hour, 0, 0).unwrap();
+            match self {
+                Zone::Berlin => (
+                    1,
+                    2,
+                    Some((
+                        at(nth_weekday(year, 3, Weekday::Sun, -1), 1),
+                        at(nth_weekday(year, 10, Weekday::Sun, -1), 1),
+                    )),
+                ),
+                Zone::NewYork => (
+                    -5,
+                    -4,
+                    Some((
+                        at(nth_weekday(year, 3, Weekday::Sun, 2), 7),
+                        at(nth_weekday(year, 11, Weekday::Sun, 1), 6),
+                    )),
+                ),
+                Zone::Tokyo => (9, 9, None),
+            }
+        }
+
+        fn offset(self, hours: i32) -> ZoneOffset {
+            ZoneOffset {
+                zone: self,
+                offset: FixedOffset::east_opt(hours * 3600).unwrap(),
+            }
+        }
+    }
+
+    impl TimeZone for Zone {
+        type Offset = ZoneOffset;
+
+        fn from_offset(offset: &ZoneOffset) -> Self {
+            offset.zone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ZoneOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ZoneOffset> {
+            let (standard, daylight, _) = self.rules(local.year());
+            // An offset fits when the instant it gives has that offset; the
+            // earlier instant comes first
+            let fits: Vec<ZoneOffset> = [daylight, standard]
+                .into_iter()
+                .map(|hours| self.offset(hours))
+                .filter(|offset| {
+                    let utc = *local - offset.offset;
+                    self.offset_from_utc_datetime(&utc).offset == offset.offset
+                })
+                .collect();
+            match fits[..] {
+                [] => MappedLocalTime::None,
+                [offset] => MappedLocalTime::Single(offset),
+                [first, second, ..] if first.offset == second.offset => {
+                    MappedLocalTime::Single(first)
+                }
+                [first, second, ..] => MappedLocalTime::Ambiguous(first, second),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+            let (standard, daylight, period) = self.rules(utc.year());
+            match period {
+                Some((start, end)) if (start..end).contains(utc) => self.offset(daylight),
+                _ => self.offset(standard),
+            }
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn local(date: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+        date.and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_berlin_transitions() {
+        let spring = transition_on(&Zone::Berlin, date(2026, 3, 29)).unwrap();
+        assert!(spring.is_forward());
+        assert_eq!(spring.at, local(date(2026, 3, 29), 1, 0).and_utc());
+        assert_eq!(spring.before, local(date(2026, 3, 29), 2, 0));
+        assert_eq!(spring.after, local(date(2026, 3, 29), 3, 0));
+
+        let autumn = transition_on(&Zone::Berlin, date(2026, 10, 25)).unwrap();
+        assert!(!autumn.is_forward());
+        assert_eq!(autumn.before, local(date(2026, 10, 25), 3, 0));
+        assert_eq!(autumn.after, local(date(2026, 10, 25), 2, 0));
+
+        // The days around them are ordinary
+        assert_eq!(transition_on(&Zone::Berlin, date(2026, 3, 28)), None);
+        assert_eq!(transition_on(&Zone::Berlin, date(2026, 3, 30)), None);
+        assert_eq!(transition_on(&Zone::Berlin, date(2026, 7, 1)), None);
+    }
+
+    #[test]
+    fn test_new_york_transitions() {
+        let spring = transition_on(&Zone::NewYork, date(2026, 3, 8)).unwrap();
+        assert_eq!(spring.at, local(date(2026, 3, 8), 7, 0).and_utc());
+        assert_eq!(
+            (spring.before, spring.after),
+            (local(date(2026, 3, 8), 2, 0), local(date(2026, 3, 8), 3, 0))
+        );
+
+        let autumn = transition_on(&Zone::NewYork, date(2026, 11, 1)).unwrap();
+        assert_eq!(autumn.at, local(date(2026, 11, 1), 6, 0).and_utc());
+        assert_eq!(
+            (autumn.before, autumn.after),
+            (
+                local(date(2026, 11, 1), 2, 0),
+                local(date(2026, 11, 1), 1, 0)
+            )
+        );
+        assert_eq!(transition_on(&Zone::NewYork, date(2026, 10, 25)), None);
+    }
+
+    #[test]
+    fn test_no_transitions_without_dst() {
+        let start = date(2026, 1, 1);
+        assert!(
+            start
+                .iter_days()
+                .take(365)
+                .all(|day| transition_on(&Zone::Tokyo, day).is_none())
+        );
+        let fixed = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        assert_eq!(transition_on(&fixed, date(2026, 3, 29)), None);
+        assert_eq!(transition_on(&Utc, date(2026, 3, 29)), None);
+    }
+
+    #[test]
+    fn test_upcoming_in_the_day_before() {
+        let day = TimeDelta::hours(24);
+        let at = |date, hour, minute| {
+            Zone::Berlin
+                .from_local_datetime(&local(date, hour, minute))
+                .earliest()
+                .unwrap()
+        };
+        // A day ahead, but not a minute more
+        let saturday = date(2026, 3, 28);
+        assert_eq!(upcoming(&at(saturday, 1, 59), day), None);
+        let transition = upcoming(&at(saturday, 2, 0), day).unwrap();
+        assert_eq!(transition.before, local(date(2026, 3, 29), 2, 0));
+        assert!(upcoming(&at(saturday, 22, 0), day).is_some());
+        // Gone once it happened
+        let sunday = date(2026, 3, 29);
+        assert!(upcoming(&at(sunday, 1, 59), day).is_some());
+        assert_eq!(upcoming(&at(sunday, 3, 0), day), None);
+        assert_eq!(upcoming(&Utc::now(), day), None);
+    }
+
+    #[test]
+    fn test_notice() {
+        let spring = transition_on(&Zone::Berlin, date(2026, 3, 29)).unwrap();
+        assert_eq!(
+            spring.notice(date(2026, 3, 28), TimeFormat::TwentyFourHour),
+            "Clocks go forward at 02:00 → 03:00 tonight"
+        );
+        let autumn = transition_on(&Zone::NewYork, date(2026, 11, 1)).unwrap();
+        assert_eq!(
+            autumn.notice(date(2026, 11, 1), TimeFormat::TwelveHour),
+            "Clocks go back at 2:00 AM → 1:00 AM tonight"
+        );
+        let midday = Transition {
+            at: spring.at,
+            before: local(date(2026, 3, 29), 12, 0),
+            after: local(date(2026, 3, 29), 13, 0),
+        };
+        assert_eq!(
+            midday.notice(date(2026, 3, 28), TimeFormat::TwentyFourHour),
+            "Clocks go forward at 12:00 → 13:00 tomorrow"
+        );
+        assert_eq!(
+            midday.notice(date(2026, 3, 29), TimeFormat::TwentyFourHour),
+            "Clocks go forward at 12:00 → 13:00 today"
+        );
+    }
+
+    #[test]
+    fn test_seconds_across_the_change() {
+        // The seconds tick on through both changes: the wall clock jumps by
+        // the hour, never by a second more or less
+        for (zone, day) in [
+            (Zone::Berlin, date(2026, 3, 29)),
+            (Zone::NewYork, date(2026, 11, 1)),
+        ] {
+            let transition = transition_on(&zone, day).unwrap();
+            let last = (transition.at - TimeDelta::seconds(1)).with_timezone(&zone);
+            let first = transition.at.with_timezone(&zone);
+            let last = TimeParts::from_datetime(&last, TimeFormat::TwentyFourHour);
+            let first = TimeParts::from_datetime(&first, TimeFormat::TwentyFourHour);
+            assert_eq!((last.minutes, last.seconds), (59, 59));
+            assert_eq!((first.minutes, first.seconds), (0, 0));
+            assert_eq!(
+                first.hours,
+                transition.after.hour(),
+                "{zone:?} shows the new hour"
+            );
+        }
+    }
+}
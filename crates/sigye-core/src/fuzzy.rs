@@ -0,0 +1,239 @@
+//! Spelling the time in words ("quarter past seven", "ten to noon").
+
+use serde::{Deserialize, Serialize};
+
+/// How precisely the time is spelled out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FuzzyGranularity {
+    /// Every minute, e.g. "twenty-three minutes to nine".
+    Exact,
+    /// Rounded to the nearest five minutes, e.g. "twenty-five to nine".
+    #[default]
+    FiveMinutes,
+}
+
+impl FuzzyGranularity {
+    /// Toggle between exact and five-minute rounding.
+    pub fn toggle(self) -> Self {
+        match self {
+            FuzzyGranularity::Exact => FuzzyGranularity::FiveMinutes,
+            FuzzyGranularity::FiveMinutes => FuzzyGranularity::Exact,
+        }
+    }
+
+    /// Get the display name for this granularity.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            FuzzyGranularity::Exact => "Exact",
+            FuzzyGranularity::FiveMinutes => "5 Minutes",
+        }
+    }
+}
+
+/// Number words up to the largest minute count spelled out (29).
+const NUMBER_WORDS: [&str; 21] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+/// Spell the time in words, e.g. "quarter past seven" or "one minute to midnight".
+///
+/// `hour` is 0-23 and `minute` 0-59; out-of-range values wrap. Minutes up to
+/// half past count from the current hour, later ones count down to the next.
+/// Multiples of five read naturally ("ten past"), other counts name the
+/// minutes ("seven minutes past"). The hours 0 and 12 read as "midnight" and
+/// "noon", and other full hours end in "o'clock".
+pub fn fuzzy_time(hour: u32, minute: u32, granularity: FuzzyGranularity) -> String {
+    let mut hour = hour % 24;
+    let mut minute = minute % 60;
+
+    if granularity == FuzzyGranularity::FiveMinutes {
+        minute = (minute + 2) / 5 * 5;
+    }
+    if minute == 60 {
+        minute = 0;
+        hour = (hour + 1) % 24;
+    }
+
+    match minute {
+        0 => match hour {
+            0 | 12 => hour_name(hour).to_string(),
+            _ => format!("{} o'clock", hour_name(hour)),
+        },
+        1..=30 => format!("{} past {}", minutes_phrase(minute), hour_name(hour)),
+        _ => format!(
+            "{} to {}",
+            minutes_phrase(60 - minute),
+            hour_name((hour + 1) % 24)
+        ),
+    }
+}
+
+/// Name an hour of the day (0-23), using "midnight" and "noon" for 0 and 12.
+fn hour_name(hour: u32) -> &'static str {
+    match hour {
+        0 => "midnight",
+        12 => "noon",
+        _ => NUMBER_WORDS[(hour % 12) as usize],
+    }
+}
+
+/// Phrase for a minute count between 1 and 30, as used before "past" or "to".
+fn minutes_phrase(minutes: u32) -> String {
+    match minutes {
+        15 => "quarter".to_string(),
+        30 => "half".to_string(),
+        1 => "one minute".to_string(),
+        _ if minutes.is_multiple_of(5) => number_word(minutes),
+        _ => format!("{} minutes", number_word(minutes)),
+    }
+}
+
+/// Spell a number between 0 and 29.
+fn number_word(n: u32) -> String {
+    match n {
+        0..=20 => NUMBER_WORDS[n as usize].to_string(),
+        _ => format!("twenty-{}", NUMBER_WORDS[(n - 20) as usize]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact(hour: u32, minute: u32) -> String {
+        fuzzy_time(hour, minute, FuzzyGranularity::Exact)
+    }
+
+    fn fuzzy(hour: u32, minute: u32) -> String {
+        fuzzy_time(hour, minute, FuzzyGranularity::FiveMinutes)
+    }
+
+    #[test]
+    fn test_full_hours() {
+        assert_eq!(exact(0, 0), "midnight");
+        assert_eq!(exact(12, 0), "noon");
+        assert_eq!(exact(7, 0), "seven o'clock");
+        assert_eq!(exact(19, 0), "seven o'clock");
+        assert_eq!(exact(23, 0), "eleven o'clock");
+        assert_eq!(exact(1, 0), "one o'clock");
+        assert_eq!(exact(13, 0), "one o'clock");
+    }
+
+    #[test]
+    fn test_past_the_hour() {
+        assert_eq!(exact(7, 1), "one minute past seven");
+        assert_eq!(exact(7, 5), "five past seven");
+        assert_eq!(exact(7, 7), "seven minutes past seven");
+        assert_eq!(exact(7, 15), "quarter past seven");
+        assert_eq!(exact(7, 21), "twenty-one minutes past seven");
+        assert_eq!(exact(7, 25), "twenty-five past seven");
+        assert_eq!(exact(0, 15), "quarter past midnight");
+        assert_eq!(exact(12, 10), "ten past noon");
+    }
+
+    #[test]
+    fn test_to_the_next_hour() {
+        assert_eq!(exact(8, 37), "twenty-three minutes to nine");
+        assert_eq!(exact(8, 45), "quarter to nine");
+        assert_eq!(exact(8, 59), "one minute to nine");
+        assert_eq!(exact(11, 50), "ten to noon");
+        assert_eq!(exact(23, 59), "one minute to midnight");
+        assert_eq!(exact(23, 45), "quarter to midnight");
+        assert_eq!(exact(12, 40), "twenty to one");
+    }
+
+    #[test]
+    fn test_half_past_boundary() {
+        assert_eq!(exact(7, 29), "twenty-nine minutes past seven");
+        assert_eq!(exact(7, 30), "half past seven");
+        assert_eq!(exact(7, 31), "twenty-nine minutes to eight");
+        assert_eq!(exact(23, 30), "half past eleven");
+        assert_eq!(exact(11, 31), "twenty-nine minutes to noon");
+    }
+
+    #[test]
+    fn test_five_minute_rounding() {
+        assert_eq!(fuzzy(7, 2), "seven o'clock");
+        assert_eq!(fuzzy(7, 3), "five past seven");
+        assert_eq!(fuzzy(7, 13), "quarter past seven");
+        assert_eq!(fuzzy(7, 32), "half past seven");
+        assert_eq!(fuzzy(7, 33), "twenty-five to eight");
+        assert_eq!(fuzzy(8, 37), "twenty-five to nine");
+        assert_eq!(fuzzy(8, 38), "twenty to nine");
+    }
+
+    #[test]
+    fn test_five_minute_rounding_rolls_over_the_hour() {
+        assert_eq!(fuzzy(7, 58), "eight o'clock");
+        assert_eq!(fuzzy(11, 58), "noon");
+        assert_eq!(fuzzy(23, 58), "midnight");
+        assert_eq!(fuzzy(23, 57), "five to midnight");
+        assert_eq!(fuzzy(0, 2), "midnight");
+    }
+
+    #[test]
+    fn test_every_minute_of_the_day() {
+        for hour in 0..24 {
+            for minute in 0..60 {
+                let words = exact(hour, minute);
+                // Only counts that aren't a multiple of five name the minutes
+                assert_eq!(
+                    words.contains("minute"),
+                    minute % 5 != 0,
+                    "{hour:02}:{minute:02} -> {words}"
+                );
+                assert_eq!(words.contains(" past "), (1..=30).contains(&minute));
+                assert_eq!(words.contains(" to "), minute > 30);
+                assert_eq!(words.ends_with("o'clock"), minute == 0 && hour % 12 != 0);
+                assert!(!words.contains("zero"), "{words}");
+
+                // Rounding reads the same as the exact phrase for the rounded time
+                let rounded = (minute + 2) / 5 * 5;
+                let expected = if rounded == 60 {
+                    exact(hour + 1, 0)
+                } else {
+                    exact(hour, rounded)
+                };
+                assert_eq!(fuzzy(hour, minute), expected, "{hour:02}:{minute:02}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_values_wrap() {
+        assert_eq!(exact(24, 0), "midnight");
+        assert_eq!(exact(7, 75), "quarter past seven");
+    }
+
+    #[test]
+    fn test_granularity_toggle() {
+        assert_eq!(
+            FuzzyGranularity::Exact.toggle(),
+            FuzzyGranularity::FiveMinutes
+        );
+        assert_eq!(
+            FuzzyGranularity::FiveMinutes.toggle(),
+            FuzzyGranularity::Exact
+        );
+    }
+}
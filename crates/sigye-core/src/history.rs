@@ -0,0 +1,153 @@
+//! Rolling history of a metric, e.g. network throughput for the last minute.
+//!
+//! Samples go into a ring buffer of fixed capacity, and can be squeezed or
+//! stretched to a number of columns for drawing a sparkline.
+
+use std::collections::VecDeque;
+
+/// Samples kept by default: a minute at one sample per second.
+pub const HISTORY_SAMPLES: usize = 60;
+
+/// The most recent samples of a metric, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsHistory {
+    /// Recorded samples, at most `capacity` of them.
+    samples: VecDeque<f32>,
+    /// Number of samples kept.
+    capacity: usize,
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(HISTORY_SAMPLES)
+    }
+}
+
+impl MetricsHistory {
+    /// Create an empty history keeping up to `capacity` samples (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record a sample, dropping the oldest once the history is full.
+    pub fn push(&mut self, value: f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// Number of samples kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of samples recorded so far, up to the capacity.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Check if nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Iterate over the samples, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// Fit the whole history window to `width` columns, oldest on the left.
+    ///
+    /// Each column covers an equal share of the capacity and shows the highest
+    /// sample in it, so a short spike survives squeezing. With more columns than
+    /// samples, each sample spans several columns. Until the history is full the
+    /// newest samples sit on the right, and columns covering no sample yet are
+    /// `None`.
+    pub fn downsample(&self, width: usize) -> Vec<Option<f32>> {
+        // Slots before the first recorded sample are still empty
+        let missing = self.capacity - self.samples.len();
+        (0..width)
+            .map(|column| {
+                let start = column * self.capacity / width;
+                let end = ((column + 1) * self.capacity / width).max(start + 1);
+                (start.max(missing)..end)
+                    .map(|slot| self.samples[slot - missing])
+                    .reduce(f32::max)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(capacity: usize, samples: &[f32]) -> MetricsHistory {
+        let mut history = MetricsHistory::new(capacity);
+        for &sample in samples {
+            history.push(sample);
+        }
+        history
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_the_newest() {
+        let mut history = MetricsHistory::default();
+        assert!(history.is_empty());
+        assert_eq!(history.capacity(), HISTORY_SAMPLES);
+        for i in 0..100 {
+            history.push(i as f32);
+        }
+        assert_eq!(history.len(), HISTORY_SAMPLES);
+        assert_eq!(history.iter().next(), Some(40.0));
+        assert_eq!(history.iter().last(), Some(99.0));
+
+        assert_eq!(MetricsHistory::new(0).capacity(), 1);
+    }
+
+    #[test]
+    fn test_downsample_squeezes_with_the_peak() {
+        let history = history(6, &[0.1, 0.5, 0.2, 0.0, 0.9, 0.3]);
+        assert_eq!(history.downsample(3), [Some(0.5), Some(0.2), Some(0.9)]);
+        assert_eq!(history.downsample(1), [Some(0.9)]);
+        assert_eq!(history.downsample(6).len(), 6);
+        assert!(history.downsample(0).is_empty());
+    }
+
+    #[test]
+    fn test_downsample_stretches() {
+        let history = history(3, &[0.1, 0.2, 0.3]);
+        assert_eq!(
+            history.downsample(6),
+            [
+                Some(0.1),
+                Some(0.1),
+                Some(0.2),
+                Some(0.2),
+                Some(0.3),
+                Some(0.3)
+            ]
+        );
+        // Uneven stretch still covers every sample in order
+        let stretched: Vec<f32> = history.downsample(7).into_iter().flatten().collect();
+        assert_eq!(stretched.len(), 7);
+        assert!(stretched.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_downsample_not_yet_full() {
+        // Two of six samples: only the right third is drawn
+        let history = history(6, &[0.4, 0.8]);
+        assert_eq!(history.downsample(3), [None, None, Some(0.8)]);
+        assert_eq!(
+            history.downsample(6),
+            [None, None, None, None, Some(0.4), Some(0.8)]
+        );
+        assert_eq!(MetricsHistory::new(6).downsample(4), [None; 4]);
+    }
+}
@@ -1,6 +1,27 @@
 //! Core types for the sigye clock application.
 
+pub mod brightness;
+pub mod burn_in;
+pub mod date_override;
+pub mod dst;
+pub mod fuzzy;
+pub mod history;
+pub mod moon;
+pub mod precipitation;
+pub mod preset;
+pub mod progress;
+pub mod schedule;
+pub mod solar;
+pub mod theme;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Timelike};
+use ratatui::layout::{Rect, Size};
 use ratatui::style::Color;
+use ratatui::widgets::BorderType;
 use serde::{Deserialize, Serialize};
 
 /// System resource metrics for reactive backgrounds.
@@ -26,6 +47,13 @@ pub struct SystemMetrics {
     pub battery_charging: Option<bool>,
 }
 
+impl SystemMetrics {
+    /// Check if the battery is below 20% and not charging.
+    pub fn is_battery_low(&self) -> bool {
+        self.battery_level.is_some_and(|level| level < 0.2) && self.battery_charging == Some(false)
+    }
+}
+
 /// Time of day for weather-aware rendering.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TimeOfDay {
@@ -46,6 +74,14 @@ pub enum TimeFormat {
     TwelveHour,
 }
 
+/// Territories where clocks usually show 12-hour time, by locale territory code.
+const TWELVE_HOUR_TERRITORIES: &[&str] = &[
+    "US", "PH", "AU", "NZ", "IN", "PK", "BD", "EG", "SA", "JO", "MY",
+];
+
+/// Locales using 12-hour time in a territory that otherwise uses 24-hour time.
+const TWELVE_HOUR_LOCALES: &[&str] = &["en_CA", "en_IE"];
+
 impl TimeFormat {
     /// Toggle between 12-hour and 24-hour format.
     pub fn toggle(&self) -> Self {
@@ -54,6 +90,328 @@ impl TimeFormat {
             TimeFormat::TwelveHour => TimeFormat::TwentyFourHour,
         }
     }
+
+    /// Pick the format usual for the time locale, read with `env` from the
+    /// first non-empty of `LC_ALL`, `LC_TIME` and `LANG`.
+    ///
+    /// Locales like `en_US.UTF-8` in a territory that uses 12-hour time get it;
+    /// everything else, including no locale and `C`, gets 24-hour time.
+    pub fn detect_default(env: impl Fn(&str) -> Option<String>) -> Self {
+        let locale = ["LC_ALL", "LC_TIME", "LANG"]
+            .into_iter()
+            .filter_map(&env)
+            .find(|value| !value.is_empty());
+        let Some(locale) = locale else {
+            return TimeFormat::default();
+        };
+        // "language_TERRITORY.encoding@modifier"
+        let name = locale
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .replace('-', "_");
+        let territory = name.split_once('_').map(|(_, territory)| territory);
+        let twelve_hour = TWELVE_HOUR_LOCALES.contains(&name.as_str())
+            || territory.is_some_and(|territory| {
+                TWELVE_HOUR_TERRITORIES.contains(&territory.to_ascii_uppercase().as_str())
+            });
+        if twelve_hour {
+            TimeFormat::TwelveHour
+        } else {
+            TimeFormat::TwentyFourHour
+        }
+    }
+}
+
+/// Format of the date line under the clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateFormat {
+    /// Weekday and month spelled out, e.g. "Monday, June 03, 2024".
+    #[default]
+    Long,
+    /// Abbreviated without the year, e.g. "Mon, Jun 03".
+    Short,
+    /// ISO 8601, e.g. "2024-06-03".
+    Iso,
+}
+
+impl DateFormat {
+    /// Cycle to the next date format.
+    pub fn next(self) -> Self {
+        match self {
+            DateFormat::Long => DateFormat::Short,
+            DateFormat::Short => DateFormat::Iso,
+            DateFormat::Iso => DateFormat::Long,
+        }
+    }
+
+    /// Get the display name for this format.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            DateFormat::Long => "Long",
+            DateFormat::Short => "Short",
+            DateFormat::Iso => "ISO",
+        }
+    }
+
+    /// Get the chrono format string for this format.
+    pub fn pattern(self) -> &'static str {
+        match self {
+            DateFormat::Long => "%A, %B %d, %Y",
+            DateFormat::Short => "%a, %b %d",
+            DateFormat::Iso => "%Y-%m-%d",
+        }
+    }
+}
+
+/// Where the clock, with the lines under it, sits on screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockPosition {
+    #[default]
+    Center,
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomRight,
+    BottomCenter,
+    BottomLeft,
+}
+
+impl ClockPosition {
+    /// Cycle to the next position: the center, then clockwise from the top left.
+    pub fn next(self) -> Self {
+        match self {
+            ClockPosition::Center => ClockPosition::TopLeft,
+            ClockPosition::TopLeft => ClockPosition::TopCenter,
+            ClockPosition::TopCenter => ClockPosition::TopRight,
+            ClockPosition::TopRight => ClockPosition::BottomRight,
+            ClockPosition::BottomRight => ClockPosition::BottomCenter,
+            ClockPosition::BottomCenter => ClockPosition::BottomLeft,
+            ClockPosition::BottomLeft => ClockPosition::Center,
+        }
+    }
+
+    /// Get the display name for this position.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ClockPosition::Center => "Center",
+            ClockPosition::TopLeft => "Top left",
+            ClockPosition::TopCenter => "Top",
+            ClockPosition::TopRight => "Top right",
+            ClockPosition::BottomRight => "Bottom right",
+            ClockPosition::BottomCenter => "Bottom",
+            ClockPosition::BottomLeft => "Bottom left",
+        }
+    }
+
+    /// Look up a position by name, e.g. "top-left", "TopLeft" or "bottom".
+    pub fn by_name(name: &str) -> Option<Self> {
+        match normalize_name(name).as_str() {
+            "center" => Some(ClockPosition::Center),
+            "topleft" => Some(ClockPosition::TopLeft),
+            "top" | "topcenter" => Some(ClockPosition::TopCenter),
+            "topright" => Some(ClockPosition::TopRight),
+            "bottomright" => Some(ClockPosition::BottomRight),
+            "bottom" | "bottomcenter" => Some(ClockPosition::BottomCenter),
+            "bottomleft" => Some(ClockPosition::BottomLeft),
+            _ => None,
+        }
+    }
+
+    /// Place content of the given size in `area`, shrinking it to fit.
+    ///
+    /// Horizontally centered positions span the full width of `area`, so each
+    /// line of the clock centers on the screen rather than in the block.
+    pub fn place(self, content: Size, area: Rect) -> Rect {
+        let width = content.width.min(area.width);
+        let height = content.height.min(area.height);
+        let (x, width) = match self {
+            ClockPosition::TopLeft | ClockPosition::BottomLeft => (area.x, width),
+            ClockPosition::TopRight | ClockPosition::BottomRight => (area.right() - width, width),
+            ClockPosition::Center | ClockPosition::TopCenter | ClockPosition::BottomCenter => {
+                (area.x, area.width)
+            }
+        };
+        let y = match self {
+            ClockPosition::TopLeft | ClockPosition::TopCenter | ClockPosition::TopRight => area.y,
+            ClockPosition::BottomLeft
+            | ClockPosition::BottomCenter
+            | ClockPosition::BottomRight => area.bottom() - height,
+            ClockPosition::Center => area.y + (area.height - height) / 2,
+        };
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Clock components of a time, as shown in a given [`TimeFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeParts {
+    /// Hours: 0-23 for 24-hour format, 1-12 for 12-hour format.
+    pub hours: u32,
+    /// Minutes (0-59).
+    pub minutes: u32,
+    /// Seconds (0-59, or 60 during a leap second).
+    pub seconds: u32,
+    /// Whether the time is after noon (always false for 24-hour format).
+    pub is_pm: bool,
+}
+
+impl TimeParts {
+    /// Extract the displayed clock components from a date and time.
+    ///
+    /// In 12-hour format midnight is 12 AM and noon is 12 PM.
+    pub fn from_datetime<Tz: TimeZone>(dt: &DateTime<Tz>, format: TimeFormat) -> Self {
+        let (hours, is_pm) = match format {
+            TimeFormat::TwentyFourHour => (dt.hour(), false),
+            // hour12 maps 0 and 12 to 12; the half of the day comes from the hour itself
+            TimeFormat::TwelveHour => (dt.hour12().1, dt.hour() >= 12),
+        };
+        Self {
+            hours,
+            minutes: dt.minute(),
+            // chrono reports leap seconds as the 59th second with extra nanoseconds
+            seconds: dt.second() + dt.nanosecond() / 1_000_000_000,
+            is_pm,
+        }
+    }
+
+    /// Clock text as drawn in big digits, e.g. "09:05:03" or " 9:05:03 PM".
+    pub fn text(&self, format: TimeFormat) -> String {
+        match self.meridiem(format) {
+            Some(meridiem) => format!("{} {meridiem}", self.digits(format)),
+            None => self.digits(format),
+        }
+    }
+
+    /// Clock text without the AM/PM marker, e.g. "09:05:03" or " 9:05:03".
+    pub fn digits(&self, format: TimeFormat) -> String {
+        let Self {
+            hours,
+            minutes,
+            seconds,
+            ..
+        } = *self;
+        match format {
+            TimeFormat::TwentyFourHour => format!("{hours:02}:{minutes:02}:{seconds:02}"),
+            TimeFormat::TwelveHour => format!("{hours:2}:{minutes:02}:{seconds:02}"),
+        }
+    }
+
+    /// "AM" or "PM" in 12-hour format, `None` in 24-hour format.
+    pub fn meridiem(&self, format: TimeFormat) -> Option<&'static str> {
+        match format {
+            TimeFormat::TwentyFourHour => None,
+            TimeFormat::TwelveHour => Some(if self.is_pm { "PM" } else { "AM" }),
+        }
+    }
+}
+
+/// How the AM/PM marker is drawn next to 12-hour digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmPmStyle {
+    /// In the clock font, as big as the digits.
+    #[default]
+    Large,
+    /// In plain text beside the bottom row of the digits.
+    SmallText,
+    /// Left off the digits; the date line ends in the marker instead.
+    Hidden,
+}
+
+impl AmPmStyle {
+    /// Cycle to the next AM/PM style.
+    pub fn next(self) -> Self {
+        match self {
+            AmPmStyle::Large => AmPmStyle::SmallText,
+            AmPmStyle::SmallText => AmPmStyle::Hidden,
+            AmPmStyle::Hidden => AmPmStyle::Large,
+        }
+    }
+
+    /// Get the display name for this style.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            AmPmStyle::Large => "Large",
+            AmPmStyle::SmallText => "Small text",
+            AmPmStyle::Hidden => "Hidden",
+        }
+    }
+}
+
+/// How the time is shown: as digits or spelled out in words.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Digits,
+    /// The time in words, e.g. "quarter past seven".
+    Words,
+}
+
+impl DisplayMode {
+    /// Cycle to the next display mode.
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Digits => DisplayMode::Words,
+            DisplayMode::Words => DisplayMode::Digits,
+        }
+    }
+
+    /// Get the display name for this mode.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            DisplayMode::Digits => "Digits",
+            DisplayMode::Words => "Words",
+        }
+    }
+}
+
+/// Sub-second precision shown under the clock digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubsecondPrecision {
+    #[default]
+    Off,
+    /// Tenths of a second, e.g. ".7".
+    Tenths,
+    /// Hundredths of a second, e.g. ".73".
+    Hundredths,
+}
+
+impl SubsecondPrecision {
+    /// Cycle to the next precision.
+    pub fn next(self) -> Self {
+        match self {
+            SubsecondPrecision::Off => SubsecondPrecision::Tenths,
+            SubsecondPrecision::Tenths => SubsecondPrecision::Hundredths,
+            SubsecondPrecision::Hundredths => SubsecondPrecision::Off,
+        }
+    }
+
+    /// Get the frame interval needed to show the digits smoothly, or `None` when off.
+    ///
+    /// Well below the 100ms granularity of tenths, since polling right at the
+    /// display granularity makes digits visibly stall and skip.
+    pub fn frame_interval(self) -> Option<Duration> {
+        match self {
+            SubsecondPrecision::Off => None,
+            SubsecondPrecision::Tenths | SubsecondPrecision::Hundredths => {
+                Some(Duration::from_millis(30))
+            }
+        }
+    }
+
+    /// Format the fraction of the current second, or `None` when off.
+    ///
+    /// Digits are truncated rather than rounded, so 999ms shows ".9" and never
+    /// rolls over ahead of the seconds digits. Leap second values of 1000ms
+    /// and above are clamped to 999ms.
+    pub fn format(self, subsec_millis: u32) -> Option<String> {
+        let millis = subsec_millis.min(999);
+        match self {
+            SubsecondPrecision::Off => None,
+            SubsecondPrecision::Tenths => Some(format!(".{}", millis / 100)),
+            SubsecondPrecision::Hundredths => Some(format!(".{:02}", millis / 10)),
+        }
+    }
 }
 
 /// Animation style for color themes.
@@ -114,13 +472,15 @@ impl AnimationStyle {
 }
 
 /// Background animation style for the terminal.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub enum BackgroundStyle {
     #[default]
     None,
     Starfield,
     MatrixRain,
     GradientWave,
+    Fireplace,
+    GameOfLife,
     // Winter theme backgrounds
     Snowfall,
     Frost,
@@ -132,6 +492,7 @@ pub enum BackgroundStyle {
     Windy,
     Cloudy,
     Foggy,
+    Moonlight,
     // Dynamic weather background based on real weather data
     Weather,
     // Twilight backgrounds for dawn/dusk
@@ -144,79 +505,274 @@ pub enum BackgroundStyle {
     HeatMap,
 }
 
-/// All background styles for cycling.
-const ALL_BACKGROUND_STYLES: &[BackgroundStyle] = &[
-    BackgroundStyle::None,
-    BackgroundStyle::Starfield,
-    BackgroundStyle::MatrixRain,
-    BackgroundStyle::GradientWave,
-    BackgroundStyle::Snowfall,
-    BackgroundStyle::Frost,
-    BackgroundStyle::Aurora,
-    BackgroundStyle::Sunny,
-    BackgroundStyle::Rainy,
-    BackgroundStyle::Stormy,
-    BackgroundStyle::Windy,
-    BackgroundStyle::Cloudy,
-    BackgroundStyle::Foggy,
-    BackgroundStyle::Weather,
-    BackgroundStyle::TwilightDawn,
-    BackgroundStyle::TwilightDusk,
-    BackgroundStyle::SystemPulse,
-    BackgroundStyle::ResourceWave,
-    BackgroundStyle::DataFlow,
-    BackgroundStyle::HeatMap,
+/// Broad grouping of background styles for UI surfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundCategory {
+    /// Decorative animations that do not depend on outside data.
+    Ambient,
+    /// Weather, seasonal and sky conditions.
+    Weather,
+    /// Visualizations driven by system metrics.
+    Reactive,
+}
+
+impl BackgroundCategory {
+    /// Get display name for the category.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            BackgroundCategory::Ambient => "Ambient",
+            BackgroundCategory::Weather => "Weather",
+            BackgroundCategory::Reactive => "Reactive",
+        }
+    }
+}
+
+/// Metadata describing a single background style.
+#[derive(Debug, Clone, Copy)]
+struct BackgroundInfo {
+    style: BackgroundStyle,
+    display_name: &'static str,
+    description: &'static str,
+    category: BackgroundCategory,
+}
+
+/// All background styles in cycling order, with their metadata.
+const BACKGROUND_INFO: &[BackgroundInfo] = &[
+    BackgroundInfo {
+        style: BackgroundStyle::None,
+        display_name: "None",
+        description: "No background",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Starfield,
+        display_name: "Starfield",
+        description: "Twinkling stars with the occasional shooting star",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::MatrixRain,
+        display_name: "Matrix Rain",
+        description: "Falling green Matrix-style characters",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::GradientWave,
+        display_name: "Gradient Wave",
+        description: "Flowing diagonal color wave",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Fireplace,
+        display_name: "Fireplace",
+        description: "Cozy flames rising from the bottom edge",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::GameOfLife,
+        display_name: "Game of Life",
+        description: "Conway's cellular automaton evolving across the screen",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Snowfall,
+        display_name: "Snowfall",
+        description: "Drifting snowflakes in shades of blue",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Frost,
+        display_name: "Frost",
+        description: "Ice crystals growing from screen edges",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Aurora,
+        display_name: "Aurora",
+        description: "Northern lights in green, cyan, blue and purple",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Sunny,
+        display_name: "Sunny",
+        description: "Radiant sun with animated rays",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Rainy,
+        display_name: "Rainy",
+        description: "Falling rain droplets",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Stormy,
+        display_name: "Stormy",
+        description: "Rain with lightning flashes",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Windy,
+        display_name: "Windy",
+        description: "Horizontal wind streaks",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Cloudy,
+        display_name: "Cloudy",
+        description: "Layered drifting clouds",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Foggy,
+        display_name: "Foggy",
+        description: "Ground-hugging mist",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Moonlight,
+        display_name: "Moonlight",
+        description: "Tonight's moon phase over faint stars",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::Weather,
+        display_name: "Weather",
+        description: "Follows real-time local weather conditions",
+        category: BackgroundCategory::Weather,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::TwilightDawn,
+        display_name: "Dawn",
+        description: "Sunrise gradient with fading stars",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::TwilightDusk,
+        display_name: "Dusk",
+        description: "Sunset gradient with emerging stars",
+        category: BackgroundCategory::Ambient,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::SystemPulse,
+        display_name: "System Pulse",
+        description: "CPU usage drives pulsing rings from the center",
+        category: BackgroundCategory::Reactive,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::ResourceWave,
+        display_name: "Resource Wave",
+        description: "Memory usage controls wave amplitude",
+        category: BackgroundCategory::Reactive,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::DataFlow,
+        display_name: "Data Flow",
+        description: "Network I/O drives particle density",
+        category: BackgroundCategory::Reactive,
+    },
+    BackgroundInfo {
+        style: BackgroundStyle::HeatMap,
+        display_name: "Heat Map",
+        description: "Combined system metrics as color intensity",
+        category: BackgroundCategory::Reactive,
+    },
 ];
 
+/// Normalize a style name for lookup: lowercase with separators removed.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Kebab-case name of an enum variant, e.g. "matrix-rain" for `MatrixRain`.
+fn kebab_name(variant: impl fmt::Debug) -> String {
+    let mut name = String::new();
+    for c in format!("{variant:?}").chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push('-');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// Error for a name that isn't one of `names`, listing them.
+fn unknown_name(kind: &str, name: &str, names: impl Iterator<Item = String>) -> String {
+    let names: Vec<String> = names.collect();
+    format!(
+        "unknown {kind} '{name}', expected one of: {}",
+        names.join(", ")
+    )
+}
+
 impl BackgroundStyle {
+    /// Look up the metadata entry for this style.
+    fn info(self) -> &'static BackgroundInfo {
+        BACKGROUND_INFO
+            .iter()
+            .find(|info| info.style == self)
+            .unwrap_or(&BACKGROUND_INFO[0])
+    }
+
+    /// Iterate over every background style, in cycling order.
+    pub fn all() -> impl Iterator<Item = BackgroundStyle> {
+        BACKGROUND_INFO.iter().map(|info| info.style)
+    }
+
     /// Cycle to the next background style.
     pub fn next(&self) -> Self {
-        let current_idx = ALL_BACKGROUND_STYLES
+        let current_idx = BACKGROUND_INFO
             .iter()
-            .position(|s| s == self)
+            .position(|info| info.style == *self)
             .unwrap_or(0);
-        let next_idx = (current_idx + 1) % ALL_BACKGROUND_STYLES.len();
-        ALL_BACKGROUND_STYLES[next_idx]
+        let next_idx = (current_idx + 1) % BACKGROUND_INFO.len();
+        BACKGROUND_INFO[next_idx].style
     }
 
     /// Cycle to the previous background style.
     pub fn prev(&self) -> Self {
-        let current_idx = ALL_BACKGROUND_STYLES
+        let current_idx = BACKGROUND_INFO
             .iter()
-            .position(|s| s == self)
+            .position(|info| info.style == *self)
             .unwrap_or(0);
         let prev_idx = if current_idx == 0 {
-            ALL_BACKGROUND_STYLES.len() - 1
+            BACKGROUND_INFO.len() - 1
         } else {
             current_idx - 1
         };
-        ALL_BACKGROUND_STYLES[prev_idx]
+        BACKGROUND_INFO[prev_idx].style
     }
 
     /// Get display name for the background style.
     pub fn display_name(self) -> &'static str {
-        match self {
-            BackgroundStyle::None => "None",
-            BackgroundStyle::Starfield => "Starfield",
-            BackgroundStyle::MatrixRain => "Matrix",
-            BackgroundStyle::GradientWave => "Gradient",
-            BackgroundStyle::Snowfall => "Snowfall",
-            BackgroundStyle::Frost => "Frost",
-            BackgroundStyle::Aurora => "Aurora",
-            BackgroundStyle::Sunny => "Sunny",
-            BackgroundStyle::Rainy => "Rainy",
-            BackgroundStyle::Stormy => "Stormy",
-            BackgroundStyle::Windy => "Windy",
-            BackgroundStyle::Cloudy => "Cloudy",
-            BackgroundStyle::Foggy => "Foggy",
-            BackgroundStyle::Weather => "Weather",
-            BackgroundStyle::TwilightDawn => "Dawn",
-            BackgroundStyle::TwilightDusk => "Dusk",
-            BackgroundStyle::SystemPulse => "Sys Pulse",
-            BackgroundStyle::ResourceWave => "Resource",
-            BackgroundStyle::DataFlow => "Data Flow",
-            BackgroundStyle::HeatMap => "Heat Map",
-        }
+        self.info().display_name
+    }
+
+    /// Get a one-line description of the background style.
+    pub fn description(self) -> &'static str {
+        self.info().description
+    }
+
+    /// Get the category the background style belongs to.
+    pub fn category(self) -> BackgroundCategory {
+        self.info().category
+    }
+
+    /// Look up a background style by name, ignoring case and separators.
+    ///
+    /// Accepts the variant name or display name, so "matrix-rain",
+    /// "MatrixRain" and "matrix_rain" all resolve to [`BackgroundStyle::MatrixRain`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        let wanted = normalize_name(name);
+        BACKGROUND_INFO
+            .iter()
+            .find(|info| {
+                normalize_name(&format!("{:?}", info.style)) == wanted
+                    || normalize_name(info.display_name) == wanted
+            })
+            .map(|info| info.style)
     }
 
     /// Check if this background style requires system metrics (reactive).
@@ -230,14 +786,358 @@ impl BackgroundStyle {
         )
     }
 
+    /// Check if this style can be composited as an extra background layer.
+    /// Reactive styles fill the whole frame, and Weather must be resolved first.
+    pub fn can_layer(self) -> bool {
+        !matches!(self, BackgroundStyle::None | BackgroundStyle::Weather) && !self.is_reactive()
+    }
+
     /// Check if this background style requires weather data.
     pub fn requires_weather(self) -> bool {
         matches!(self, BackgroundStyle::Weather)
     }
+
+    /// Check if this background style changes over time and needs continuous redraws.
+    pub fn is_animated(self) -> bool {
+        self != BackgroundStyle::None
+    }
+
+    /// Get how often this background needs a new frame at the given speed.
+    pub fn target_frame_interval(self, speed: AnimationSpeed) -> Duration {
+        if self.is_animated() {
+            speed.frame_interval()
+        } else {
+            Duration::from_millis(1000)
+        }
+    }
 }
 
-/// Animation speed setting.
+impl fmt::Display for BackgroundStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&kebab_name(self))
+    }
+}
+
+impl FromStr for BackgroundStyle {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::by_name(name).ok_or_else(|| {
+            unknown_name(
+                "background style",
+                name,
+                Self::all().map(|style| style.to_string()),
+            )
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for BackgroundStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Simplified weather condition categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Rain,
+    HeavyRain,
+    Thunderstorm,
+    Snow,
+    Fog,
+    Windy,
+    VeryCold,
+}
+
+impl WeatherCondition {
+    /// Get an icon for this condition.
+    pub fn icon(self) -> &'static str {
+        match self {
+            WeatherCondition::Clear => "☀",
+            WeatherCondition::PartlyCloudy => "⛅",
+            WeatherCondition::Cloudy => "☁",
+            WeatherCondition::Rain => "🌦",
+            WeatherCondition::HeavyRain => "🌧",
+            WeatherCondition::Thunderstorm => "⛈",
+            WeatherCondition::Snow => "❄",
+            WeatherCondition::Fog => "🌫",
+            WeatherCondition::Windy => "🌬",
+            WeatherCondition::VeryCold => "🥶",
+        }
+    }
+
+    /// Get a short human-readable label for this condition.
+    pub fn label(self) -> &'static str {
+        match self {
+            WeatherCondition::Clear => "Clear",
+            WeatherCondition::PartlyCloudy => "Partly cloudy",
+            WeatherCondition::Cloudy => "Cloudy",
+            WeatherCondition::Rain => "Rain",
+            WeatherCondition::HeavyRain => "Heavy rain",
+            WeatherCondition::Thunderstorm => "Thunderstorm",
+            WeatherCondition::Snow => "Snow",
+            WeatherCondition::Fog => "Fog",
+            WeatherCondition::Windy => "Windy",
+            WeatherCondition::VeryCold => "Very cold",
+        }
+    }
+}
+
+/// How background cells behind the clock digits are treated for readability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClearZone {
+    /// Background draws right up against the digits.
+    #[default]
+    Off,
+    /// Background cells behind the digits are blanked.
+    Clear,
+    /// Background cells behind the digits are dimmed.
+    Dim,
+}
+
+/// Decorative border drawn around the clock block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockBorder {
+    /// No border.
+    #[default]
+    None,
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+}
+
+impl ClockBorder {
+    /// Cycle to the next border, ending back at none.
+    pub fn next(self) -> Self {
+        match self {
+            ClockBorder::None => ClockBorder::Plain,
+            ClockBorder::Plain => ClockBorder::Rounded,
+            ClockBorder::Rounded => ClockBorder::Double,
+            ClockBorder::Double => ClockBorder::Thick,
+            ClockBorder::Thick => ClockBorder::None,
+        }
+    }
+
+    /// Get the display name for this border.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ClockBorder::None => "None",
+            ClockBorder::Plain => "Plain",
+            ClockBorder::Rounded => "Rounded",
+            ClockBorder::Double => "Double",
+            ClockBorder::Thick => "Thick",
+        }
+    }
+
+    /// Line set used to draw the border, or `None` when there is no border.
+    pub fn border_type(self) -> Option<BorderType> {
+        match self {
+            ClockBorder::None => None,
+            ClockBorder::Plain => Some(BorderType::Plain),
+            ClockBorder::Rounded => Some(BorderType::Rounded),
+            ClockBorder::Double => Some(BorderType::Double),
+            ClockBorder::Thick => Some(BorderType::Thick),
+        }
+    }
+}
+
+/// Accessibility mode of the display, for low vision or screen readers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Accessibility {
+    /// The usual display.
+    #[default]
+    Off,
+    /// White on black thick digits with a shadow, without a background.
+    HighContrast,
+    /// The time and date as plain lines of text, for screen readers.
+    Plain,
+}
+
+impl Accessibility {
+    /// Cycle to the next mode, ending back at off.
+    pub fn next(self) -> Self {
+        match self {
+            Accessibility::Off => Accessibility::HighContrast,
+            Accessibility::HighContrast => Accessibility::Plain,
+            Accessibility::Plain => Accessibility::Off,
+        }
+    }
+
+    /// Cycle to the previous mode.
+    pub fn prev(self) -> Self {
+        match self {
+            Accessibility::Off => Accessibility::Plain,
+            Accessibility::HighContrast => Accessibility::Off,
+            Accessibility::Plain => Accessibility::HighContrast,
+        }
+    }
+
+    /// Get the display name for this mode.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Accessibility::Off => "Off",
+            Accessibility::HighContrast => "High contrast",
+            Accessibility::Plain => "Plain text",
+        }
+    }
+
+    /// Check if the mode leaves out the background.
+    pub fn hides_background(self) -> bool {
+        self != Accessibility::Off
+    }
+}
+
+/// Side of the clock the banner goes on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BannerPosition {
+    #[default]
+    Above,
+    Below,
+}
+
+/// Grid of panes the screen is split into, as rows by columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaneLayout {
+    /// One clock filling the screen.
+    #[default]
+    #[serde(rename = "1x1")]
+    Single,
+    /// Two panes side by side.
+    #[serde(rename = "1x2")]
+    SideBySide,
+    /// Two panes, one above the other.
+    #[serde(rename = "2x1")]
+    Stacked,
+    /// Four panes in a square.
+    #[serde(rename = "2x2")]
+    Grid,
+}
+
+impl PaneLayout {
+    /// Rows and columns of the grid.
+    pub fn shape(self) -> (u16, u16) {
+        match self {
+            PaneLayout::Single => (1, 1),
+            PaneLayout::SideBySide => (1, 2),
+            PaneLayout::Stacked => (2, 1),
+            PaneLayout::Grid => (2, 2),
+        }
+    }
+
+    /// Number of panes in the grid.
+    pub fn pane_count(self) -> usize {
+        let (rows, columns) = self.shape();
+        usize::from(rows * columns)
+    }
+
+    /// Check if the screen is split at all.
+    pub fn is_split(self) -> bool {
+        self != PaneLayout::Single
+    }
+}
+
+/// Ring of 60 ticks around the time, filling up over the minute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecondsRing {
+    /// No ring.
+    #[default]
+    Off,
+    /// Ring around the time, which keeps its seconds digits.
+    WithDigits,
+    /// Ring in place of the seconds digits.
+    Only,
+}
+
+impl SecondsRing {
+    /// Check if the ring is drawn.
+    pub fn is_shown(self) -> bool {
+        self != SecondsRing::Off
+    }
+}
+
+/// Unit for displaying temperatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Convert a Celsius temperature to this unit, rounded to whole degrees.
+    pub fn convert(self, temp_c: i32) -> i32 {
+        match self {
+            TemperatureUnit::Celsius => temp_c,
+            TemperatureUnit::Fahrenheit => (temp_c as f32 * 9.0 / 5.0 + 32.0).round() as i32,
+        }
+    }
+
+    /// Get the unit symbol, e.g. "°C".
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+}
+
+/// Weather data provider for the dynamic weather background.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherSource {
+    /// wttr.in (auto-detects location from IP).
+    #[default]
+    Wttr,
+    /// Open-Meteo (needs a city name or coordinates).
+    OpenMeteo,
+}
+
+impl WeatherSource {
+    /// Get the other provider, used for failover.
+    pub fn fallback(self) -> Self {
+        match self {
+            WeatherSource::Wttr => WeatherSource::OpenMeteo,
+            WeatherSource::OpenMeteo => WeatherSource::Wttr,
+        }
+    }
+
+    /// Get the display name for this provider.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            WeatherSource::Wttr => "wttr.in",
+            WeatherSource::OpenMeteo => "Open-Meteo",
+        }
+    }
+
+    /// Look up a provider by name, e.g. "wttr", "wttr.in" or "open-meteo".
+    pub fn by_name(name: &str) -> Option<Self> {
+        match normalize_name(name).trim_end_matches(".in") {
+            "wttr" => Some(WeatherSource::Wttr),
+            "openmeteo" => Some(WeatherSource::OpenMeteo),
+            _ => None,
+        }
+    }
+}
+
+/// Where the system-reactive backgrounds get their metrics from.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetricsProvider {
+    /// This machine, read with sysinfo.
+    #[default]
+    Local,
+    /// JSON printed by a shell command, e.g. one querying a remote server.
+    Command,
+    /// JSON read from a file kept up to date by another program.
+    File,
+}
+
+/// Animation speed setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub enum AnimationSpeed {
     Slow,
     #[default]
@@ -245,36 +1145,30 @@ pub enum AnimationSpeed {
     Fast,
 }
 
-/// All animation speeds for cycling.
-const ALL_ANIMATION_SPEEDS: &[AnimationSpeed] = &[
-    AnimationSpeed::Slow,
-    AnimationSpeed::Medium,
-    AnimationSpeed::Fast,
-];
-
 impl AnimationSpeed {
+    /// All animation speeds for cycling.
+    pub const ALL: &[AnimationSpeed] = &[
+        AnimationSpeed::Slow,
+        AnimationSpeed::Medium,
+        AnimationSpeed::Fast,
+    ];
+
     /// Cycle to the next speed.
     pub fn next(&self) -> Self {
-        let current_idx = ALL_ANIMATION_SPEEDS
-            .iter()
-            .position(|s| s == self)
-            .unwrap_or(0);
-        let next_idx = (current_idx + 1) % ALL_ANIMATION_SPEEDS.len();
-        ALL_ANIMATION_SPEEDS[next_idx]
+        let current_idx = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        let next_idx = (current_idx + 1) % Self::ALL.len();
+        Self::ALL[next_idx]
     }
 
     /// Cycle to the previous speed.
     pub fn prev(&self) -> Self {
-        let current_idx = ALL_ANIMATION_SPEEDS
-            .iter()
-            .position(|s| s == self)
-            .unwrap_or(0);
+        let current_idx = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
         let prev_idx = if current_idx == 0 {
-            ALL_ANIMATION_SPEEDS.len() - 1
+            Self::ALL.len() - 1
         } else {
             current_idx - 1
         };
-        ALL_ANIMATION_SPEEDS[prev_idx]
+        Self::ALL[prev_idx]
     }
 
     /// Get display name for the speed.
@@ -286,6 +1180,24 @@ impl AnimationSpeed {
         }
     }
 
+    /// Look up a speed by name, ignoring case, e.g. "fast".
+    pub fn by_name(name: &str) -> Option<Self> {
+        let wanted = normalize_name(name);
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|speed| normalize_name(speed.display_name()) == wanted)
+    }
+
+    /// Get the frame interval for animations running at this speed.
+    pub fn frame_interval(self) -> Duration {
+        match self {
+            AnimationSpeed::Slow => Duration::from_millis(100),
+            AnimationSpeed::Medium => Duration::from_millis(66),
+            AnimationSpeed::Fast => Duration::from_millis(33),
+        }
+    }
+
     /// Get the cycle duration in milliseconds for shifting animation.
     pub fn shift_cycle_ms(self) -> u64 {
         match self {
@@ -349,6 +1261,42 @@ impl AnimationSpeed {
         }
     }
 
+    /// Get the time in milliseconds a scrolling banner takes to move one character.
+    pub fn marquee_step_ms(self) -> u64 {
+        match self {
+            AnimationSpeed::Slow => 300,
+            AnimationSpeed::Medium => 200,
+            AnimationSpeed::Fast => 100,
+        }
+    }
+
+    /// Get the horizontal drift speed of the bouncing clock in cells per second.
+    pub fn bounce_cells_per_sec(self) -> f32 {
+        match self {
+            AnimationSpeed::Slow => 2.0,
+            AnimationSpeed::Medium => 4.0,
+            AnimationSpeed::Fast => 8.0,
+        }
+    }
+
+    /// Get the interval between fireplace heat diffusion steps in milliseconds.
+    pub fn fire_step_ms(self) -> u64 {
+        match self {
+            AnimationSpeed::Slow => 120,
+            AnimationSpeed::Medium => 70,
+            AnimationSpeed::Fast => 40,
+        }
+    }
+
+    /// Get the interval between Game of Life generations in milliseconds.
+    pub fn life_step_ms(self) -> u64 {
+        match self {
+            AnimationSpeed::Slow => 400,
+            AnimationSpeed::Medium => 200,
+            AnimationSpeed::Fast => 100,
+        }
+    }
+
     /// Get the snowfall speed multiplier.
     pub fn snow_fall_speed(self) -> f32 {
         match self {
@@ -396,6 +1344,15 @@ impl AnimationSpeed {
         }
     }
 
+    /// Get the interval range between shooting stars in milliseconds (min, max).
+    pub fn meteor_interval_ms(self) -> (u64, u64) {
+        match self {
+            AnimationSpeed::Slow => (8000, 30000),
+            AnimationSpeed::Medium => (5000, 20000),
+            AnimationSpeed::Fast => (2500, 10000),
+        }
+    }
+
     /// Get the wind streak speed multiplier.
     pub fn wind_streak_speed(self) -> f32 {
         match self {
@@ -433,8 +1390,35 @@ impl AnimationSpeed {
     }
 }
 
+impl fmt::Display for AnimationSpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&kebab_name(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for AnimationSpeed {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for AnimationSpeed {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::by_name(name).ok_or_else(|| {
+            unknown_name(
+                "animation speed",
+                name,
+                Self::ALL.iter().map(|speed| speed.to_string()),
+            )
+        })
+    }
+}
+
 /// Color theme for the clock display.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub enum ColorTheme {
     #[default]
     Cyan,
@@ -444,6 +1428,8 @@ pub enum ColorTheme {
     Yellow,
     Red,
     Blue,
+    /// Follows the time of day: amber at dawn, bright by day, dimmed at night.
+    Auto,
     // Dynamic color themes
     Rainbow,
     RainbowVertical,
@@ -458,44 +1444,45 @@ pub enum ColorTheme {
     GradientWinter,
 }
 
-/// All color themes in order for cycling.
-const ALL_THEMES: &[ColorTheme] = &[
-    ColorTheme::Cyan,
-    ColorTheme::Green,
-    ColorTheme::Magenta,
-    ColorTheme::Yellow,
-    ColorTheme::Red,
-    ColorTheme::Blue,
-    ColorTheme::White,
-    ColorTheme::Rainbow,
-    ColorTheme::RainbowVertical,
-    ColorTheme::GradientWarm,
-    ColorTheme::GradientCool,
-    ColorTheme::GradientOcean,
-    ColorTheme::GradientNeon,
-    ColorTheme::GradientFire,
-    ColorTheme::GradientFrost,
-    ColorTheme::GradientAurora,
-    ColorTheme::GradientWinter,
-];
-
 impl ColorTheme {
+    /// All color themes in order for cycling.
+    pub const ALL: &[ColorTheme] = &[
+        ColorTheme::Cyan,
+        ColorTheme::Green,
+        ColorTheme::Magenta,
+        ColorTheme::Yellow,
+        ColorTheme::Red,
+        ColorTheme::Blue,
+        ColorTheme::White,
+        ColorTheme::Auto,
+        ColorTheme::Rainbow,
+        ColorTheme::RainbowVertical,
+        ColorTheme::GradientWarm,
+        ColorTheme::GradientCool,
+        ColorTheme::GradientOcean,
+        ColorTheme::GradientNeon,
+        ColorTheme::GradientFire,
+        ColorTheme::GradientFrost,
+        ColorTheme::GradientAurora,
+        ColorTheme::GradientWinter,
+    ];
+
     /// Cycle to the next color theme.
     pub fn next(&self) -> Self {
-        let current_idx = ALL_THEMES.iter().position(|t| t == self).unwrap_or(0);
-        let next_idx = (current_idx + 1) % ALL_THEMES.len();
-        ALL_THEMES[next_idx]
+        let current_idx = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
+        let next_idx = (current_idx + 1) % Self::ALL.len();
+        Self::ALL[next_idx]
     }
 
     /// Cycle to the previous color theme.
     pub fn prev(&self) -> Self {
-        let current_idx = ALL_THEMES.iter().position(|t| t == self).unwrap_or(0);
+        let current_idx = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
         let prev_idx = if current_idx == 0 {
-            ALL_THEMES.len() - 1
+            Self::ALL.len() - 1
         } else {
             current_idx - 1
         };
-        ALL_THEMES[prev_idx]
+        Self::ALL[prev_idx]
     }
 
     /// Convert theme to Ratatui Color (for static themes).
@@ -508,6 +1495,8 @@ impl ColorTheme {
             ColorTheme::Yellow => Color::Yellow,
             ColorTheme::Red => Color::Red,
             ColorTheme::Blue => Color::Blue,
+            // Auto is resolved per frame with auto_color; use the daytime color here
+            ColorTheme::Auto => auto_color(TimeOfDay::Day, 1.0),
             // Dynamic themes return a default color for backward compatibility
             ColorTheme::Rainbow | ColorTheme::RainbowVertical | ColorTheme::GradientNeon => {
                 Color::Magenta
@@ -751,6 +1740,7 @@ impl ColorTheme {
             ColorTheme::Yellow => "Yellow",
             ColorTheme::Red => "Red",
             ColorTheme::Blue => "Blue",
+            ColorTheme::Auto => "Auto",
             ColorTheme::Rainbow => "Rainbow",
             ColorTheme::RainbowVertical => "Rainbow V",
             ColorTheme::GradientWarm => "Warm",
@@ -763,6 +1753,62 @@ impl ColorTheme {
             ColorTheme::GradientWinter => "Winter",
         }
     }
+
+    /// Look up a theme by name, ignoring case and separators.
+    ///
+    /// Accepts the variant name or display name, so "gradient-warm",
+    /// "GradientWarm" and "warm" all resolve to [`ColorTheme::GradientWarm`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        let wanted = normalize_name(name);
+        Self::ALL.iter().copied().find(|theme| {
+            normalize_name(&format!("{theme:?}")) == wanted
+                || normalize_name(theme.display_name()) == wanted
+        })
+    }
+}
+
+impl fmt::Display for ColorTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&kebab_name(self))
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorTheme {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for ColorTheme {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::by_name(name).ok_or_else(|| {
+            unknown_name(
+                "color theme",
+                name,
+                Self::ALL.iter().map(|theme| theme.to_string()),
+            )
+        })
+    }
+}
+
+/// Resolve the [`ColorTheme::Auto`] color for a time of day.
+///
+/// `brightness` (0.0 - 1.0) dims the night color so the clock isn't glaring in the dark.
+pub fn auto_color(time_of_day: TimeOfDay, brightness: f32) -> Color {
+    match time_of_day {
+        TimeOfDay::Dawn => Color::Rgb(255, 176, 64), // Warm amber
+        TimeOfDay::Day => Color::Rgb(210, 245, 255), // Bright ice white
+        TimeOfDay::Dusk => Color::Rgb(255, 128, 96), // Soft coral
+        TimeOfDay::Night => {
+            // Muted blue, scaled down by the brightness factor
+            let scale = brightness.clamp(0.0, 1.0);
+            let dim = |c: f32| (c * scale).round() as u8;
+            Color::Rgb(dim(150.0), dim(170.0), dim(230.0))
+        }
+    }
 }
 
 /// Apply animation transformations to a color.
@@ -902,8 +1948,8 @@ fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
     (h, s, l)
 }
 
-/// Convert HSL to RGB.
-fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+/// Convert HSL to RGB, with the hue in degrees and saturation and lightness in 0.0 - 1.0.
+pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
     if s == 0.0 {
         let v = (l * 255.0) as u8;
         return (v, v, v);
@@ -950,3 +1996,592 @@ pub fn is_colon_visible(elapsed_ms: u64) -> bool {
     let phase = (elapsed_ms % 1000) as f32 / 1000.0;
     phase < 0.5
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Detect the time format with only the given environment variables set.
+    fn detect(vars: &[(&str, &str)]) -> TimeFormat {
+        TimeFormat::detect_default(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_detect_time_format() {
+        assert_eq!(detect(&[]), TimeFormat::TwentyFourHour);
+        assert_eq!(detect(&[("LANG", "C")]), TimeFormat::TwentyFourHour);
+        assert_eq!(detect(&[("LANG", "POSIX")]), TimeFormat::TwentyFourHour);
+        assert_eq!(detect(&[("LANG", "en_US.UTF-8")]), TimeFormat::TwelveHour);
+        assert_eq!(
+            detect(&[("LANG", "de_DE.UTF-8")]),
+            TimeFormat::TwentyFourHour
+        );
+        assert_eq!(
+            detect(&[("LANG", "en_GB.UTF-8")]),
+            TimeFormat::TwentyFourHour
+        );
+        assert_eq!(detect(&[("LANG", "es_US")]), TimeFormat::TwelveHour);
+        assert_eq!(
+            detect(&[("LANG", "en_AU.utf8@euro")]),
+            TimeFormat::TwelveHour
+        );
+        assert_eq!(detect(&[("LANG", "en-us")]), TimeFormat::TwelveHour);
+        // Canada depends on the language
+        assert_eq!(detect(&[("LANG", "en_CA.UTF-8")]), TimeFormat::TwelveHour);
+        assert_eq!(
+            detect(&[("LANG", "fr_CA.UTF-8")]),
+            TimeFormat::TwentyFourHour
+        );
+    }
+
+    #[test]
+    fn test_detect_time_format_precedence() {
+        // LC_ALL overrides LC_TIME, which overrides LANG; empty ones don't count
+        let us_time = [("LC_TIME", "en_US.UTF-8"), ("LANG", "de_DE.UTF-8")];
+        assert_eq!(detect(&us_time), TimeFormat::TwelveHour);
+        let all_c = [("LC_ALL", "C"), ("LC_TIME", "en_US.UTF-8")];
+        assert_eq!(detect(&all_c), TimeFormat::TwentyFourHour);
+        let empty_all = [("LC_ALL", ""), ("LC_TIME", ""), ("LANG", "en_US.UTF-8")];
+        assert_eq!(detect(&empty_all), TimeFormat::TwelveHour);
+    }
+
+    /// Every variant, listed through an exhaustive match so that adding a
+    /// style fails to compile here until its metadata is considered.
+    fn all_background_styles() -> Vec<BackgroundStyle> {
+        let guard = |style: BackgroundStyle| match style {
+            BackgroundStyle::None
+            | BackgroundStyle::Starfield
+            | BackgroundStyle::MatrixRain
+            | BackgroundStyle::GradientWave
+            | BackgroundStyle::Fireplace
+            | BackgroundStyle::GameOfLife
+            | BackgroundStyle::Snowfall
+            | BackgroundStyle::Frost
+            | BackgroundStyle::Aurora
+            | BackgroundStyle::Sunny
+            | BackgroundStyle::Rainy
+            | BackgroundStyle::Stormy
+            | BackgroundStyle::Windy
+            | BackgroundStyle::Cloudy
+            | BackgroundStyle::Foggy
+            | BackgroundStyle::Moonlight
+            | BackgroundStyle::Weather
+            | BackgroundStyle::TwilightDawn
+            | BackgroundStyle::TwilightDusk
+            | BackgroundStyle::SystemPulse
+            | BackgroundStyle::ResourceWave
+            | BackgroundStyle::DataFlow
+            | BackgroundStyle::HeatMap => style,
+        };
+        [
+            BackgroundStyle::None,
+            BackgroundStyle::Starfield,
+            BackgroundStyle::MatrixRain,
+            BackgroundStyle::GradientWave,
+            BackgroundStyle::Fireplace,
+            BackgroundStyle::GameOfLife,
+            BackgroundStyle::Snowfall,
+            BackgroundStyle::Frost,
+            BackgroundStyle::Aurora,
+            BackgroundStyle::Sunny,
+            BackgroundStyle::Rainy,
+            BackgroundStyle::Stormy,
+            BackgroundStyle::Windy,
+            BackgroundStyle::Cloudy,
+            BackgroundStyle::Foggy,
+            BackgroundStyle::Moonlight,
+            BackgroundStyle::Weather,
+            BackgroundStyle::TwilightDawn,
+            BackgroundStyle::TwilightDusk,
+            BackgroundStyle::SystemPulse,
+            BackgroundStyle::ResourceWave,
+            BackgroundStyle::DataFlow,
+            BackgroundStyle::HeatMap,
+        ]
+        .into_iter()
+        .map(guard)
+        .collect()
+    }
+
+    #[test]
+    fn test_background_metadata_is_exhaustive() {
+        let styles = all_background_styles();
+        assert_eq!(styles.len(), BACKGROUND_INFO.len());
+
+        for style in styles {
+            let entries = BACKGROUND_INFO.iter().filter(|i| i.style == style).count();
+            assert_eq!(entries, 1, "{style:?} needs exactly one metadata entry");
+            assert!(!style.display_name().is_empty(), "{style:?} display name");
+            assert!(!style.description().is_empty(), "{style:?} description");
+        }
+    }
+
+    #[test]
+    fn test_background_categories() {
+        for info in BACKGROUND_INFO {
+            assert_eq!(
+                info.style.is_reactive(),
+                info.category == BackgroundCategory::Reactive,
+                "{:?}",
+                info.style
+            );
+        }
+        assert_eq!(
+            BackgroundStyle::Rainy.category(),
+            BackgroundCategory::Weather
+        );
+    }
+
+    #[test]
+    fn test_background_by_name() {
+        for name in ["matrix-rain", "MatrixRain", "matrix_rain", "Matrix Rain"] {
+            assert_eq!(
+                BackgroundStyle::by_name(name),
+                Some(BackgroundStyle::MatrixRain)
+            );
+        }
+        assert_eq!(
+            BackgroundStyle::by_name("twilight-dawn"),
+            Some(BackgroundStyle::TwilightDawn)
+        );
+        assert_eq!(
+            BackgroundStyle::by_name("dawn"),
+            Some(BackgroundStyle::TwilightDawn)
+        );
+        assert_eq!(BackgroundStyle::by_name("lava"), None);
+
+        for info in BACKGROUND_INFO {
+            assert_eq!(
+                BackgroundStyle::by_name(info.display_name),
+                Some(info.style)
+            );
+        }
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for style in all_background_styles() {
+            assert_eq!(style.to_string().parse(), Ok(style), "{style}");
+        }
+        assert_eq!(BackgroundStyle::MatrixRain.to_string(), "matrix-rain");
+        assert!(
+            "lava"
+                .parse::<BackgroundStyle>()
+                .unwrap_err()
+                .contains("matrix-rain")
+        );
+
+        // Every theme is listed, so the guard fails to compile for a new one
+        let guard = |theme| match theme {
+            ColorTheme::Cyan
+            | ColorTheme::Green
+            | ColorTheme::White
+            | ColorTheme::Magenta
+            | ColorTheme::Yellow
+            | ColorTheme::Red
+            | ColorTheme::Blue
+            | ColorTheme::Auto
+            | ColorTheme::Rainbow
+            | ColorTheme::RainbowVertical
+            | ColorTheme::GradientWarm
+            | ColorTheme::GradientCool
+            | ColorTheme::GradientOcean
+            | ColorTheme::GradientNeon
+            | ColorTheme::GradientFire
+            | ColorTheme::GradientFrost
+            | ColorTheme::GradientAurora
+            | ColorTheme::GradientWinter => theme,
+        };
+        assert_eq!(ColorTheme::ALL.len(), 18);
+        for &theme in ColorTheme::ALL {
+            let theme = guard(theme);
+            assert_eq!(theme.to_string().parse(), Ok(theme), "{theme}");
+            assert_eq!(ColorTheme::by_name(theme.display_name()), Some(theme));
+        }
+        assert_eq!(ColorTheme::GradientWarm.to_string(), "gradient-warm");
+        assert_eq!("WARM".parse(), Ok(ColorTheme::GradientWarm));
+        assert!("plaid".parse::<ColorTheme>().is_err());
+
+        let guard = |speed| match speed {
+            AnimationSpeed::Slow | AnimationSpeed::Medium | AnimationSpeed::Fast => speed,
+        };
+        assert_eq!(AnimationSpeed::ALL.len(), 3);
+        for &speed in AnimationSpeed::ALL {
+            let speed = guard(speed);
+            assert_eq!(speed.to_string().parse(), Ok(speed), "{speed}");
+        }
+        assert!("warp".parse::<AnimationSpeed>().is_err());
+    }
+
+    #[test]
+    fn test_background_cycle_covers_all() {
+        let mut style = BackgroundStyle::None;
+        for _ in 0..BACKGROUND_INFO.len() {
+            style = style.next();
+            assert_eq!(style.prev().next(), style);
+        }
+        assert_eq!(style, BackgroundStyle::None);
+    }
+
+    #[test]
+    fn test_can_layer() {
+        assert!(BackgroundStyle::Starfield.can_layer());
+        assert!(BackgroundStyle::Snowfall.can_layer());
+        assert!(!BackgroundStyle::None.can_layer());
+        assert!(!BackgroundStyle::Weather.can_layer());
+        assert!(!BackgroundStyle::HeatMap.can_layer());
+    }
+
+    #[test]
+    fn test_target_frame_interval() {
+        for speed in [
+            AnimationSpeed::Slow,
+            AnimationSpeed::Medium,
+            AnimationSpeed::Fast,
+        ] {
+            assert_eq!(
+                BackgroundStyle::None.target_frame_interval(speed),
+                Duration::from_millis(1000)
+            );
+        }
+        assert_eq!(
+            BackgroundStyle::MatrixRain.target_frame_interval(AnimationSpeed::Fast),
+            Duration::from_millis(33)
+        );
+        assert_eq!(
+            BackgroundStyle::Starfield.target_frame_interval(AnimationSpeed::Medium),
+            Duration::from_millis(66)
+        );
+
+        // Faster speeds never get a longer frame interval
+        for info in BACKGROUND_INFO {
+            let slow = info.style.target_frame_interval(AnimationSpeed::Slow);
+            let medium = info.style.target_frame_interval(AnimationSpeed::Medium);
+            let fast = info.style.target_frame_interval(AnimationSpeed::Fast);
+            assert!(slow >= medium && medium >= fast, "{:?}", info.style);
+        }
+    }
+
+    #[test]
+    fn test_battery_low() {
+        let metrics = |level, charging| SystemMetrics {
+            battery_level: level,
+            battery_charging: charging,
+            ..Default::default()
+        };
+        assert!(metrics(Some(0.15), Some(false)).is_battery_low());
+        assert!(!metrics(Some(0.15), Some(true)).is_battery_low());
+        assert!(!metrics(Some(0.5), Some(false)).is_battery_low());
+        assert!(!metrics(None, None).is_battery_low());
+    }
+
+    #[test]
+    fn test_weather_source_by_name() {
+        for name in ["wttr", "wttr.in", "Wttr"] {
+            assert_eq!(WeatherSource::by_name(name), Some(WeatherSource::Wttr));
+        }
+        for name in ["open-meteo", "OpenMeteo", "Open-Meteo"] {
+            assert_eq!(WeatherSource::by_name(name), Some(WeatherSource::OpenMeteo));
+        }
+        assert_eq!(WeatherSource::by_name("metoffice"), None);
+        assert_eq!(
+            WeatherSource::Wttr.fallback().fallback(),
+            WeatherSource::Wttr
+        );
+    }
+
+    #[test]
+    fn test_weather_condition_icons_and_labels() {
+        // Exhaustive match so new variants must be added here
+        let guard = |condition: WeatherCondition| match condition {
+            WeatherCondition::Clear
+            | WeatherCondition::PartlyCloudy
+            | WeatherCondition::Cloudy
+            | WeatherCondition::Rain
+            | WeatherCondition::HeavyRain
+            | WeatherCondition::Thunderstorm
+            | WeatherCondition::Snow
+            | WeatherCondition::Fog
+            | WeatherCondition::Windy
+            | WeatherCondition::VeryCold => condition,
+        };
+        let conditions = [
+            (WeatherCondition::Clear, "☀", "Clear"),
+            (WeatherCondition::PartlyCloudy, "⛅", "Partly cloudy"),
+            (WeatherCondition::Cloudy, "☁", "Cloudy"),
+            (WeatherCondition::Rain, "🌦", "Rain"),
+            (WeatherCondition::HeavyRain, "🌧", "Heavy rain"),
+            (WeatherCondition::Thunderstorm, "⛈", "Thunderstorm"),
+            (WeatherCondition::Snow, "❄", "Snow"),
+            (WeatherCondition::Fog, "🌫", "Fog"),
+            (WeatherCondition::Windy, "🌬", "Windy"),
+            (WeatherCondition::VeryCold, "🥶", "Very cold"),
+        ];
+        for (condition, icon, label) in conditions {
+            let condition = guard(condition);
+            assert_eq!(condition.icon(), icon, "{condition:?}");
+            assert_eq!(condition.label(), label, "{condition:?}");
+        }
+    }
+
+    #[test]
+    fn test_temperature_unit_convert() {
+        assert_eq!(TemperatureUnit::Celsius.convert(23), 23);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(0), 32);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(100), 212);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(-40), -40);
+        assert_eq!(TemperatureUnit::Fahrenheit.convert(23), 73);
+        assert_eq!(TemperatureUnit::Fahrenheit.symbol(), "°F");
+    }
+
+    #[test]
+    fn test_auto_color_dawn() {
+        assert_eq!(auto_color(TimeOfDay::Dawn, 0.5), Color::Rgb(255, 176, 64));
+    }
+
+    #[test]
+    fn test_auto_color_day() {
+        assert_eq!(auto_color(TimeOfDay::Day, 0.5), Color::Rgb(210, 245, 255));
+        assert_eq!(ColorTheme::Auto.color(), auto_color(TimeOfDay::Day, 1.0));
+    }
+
+    #[test]
+    fn test_auto_color_dusk() {
+        assert_eq!(auto_color(TimeOfDay::Dusk, 0.5), Color::Rgb(255, 128, 96));
+    }
+
+    #[test]
+    fn test_auto_color_night_brightness() {
+        assert_eq!(auto_color(TimeOfDay::Night, 1.0), Color::Rgb(150, 170, 230));
+        assert_eq!(auto_color(TimeOfDay::Night, 0.5), Color::Rgb(75, 85, 115));
+        // Out-of-range factors are clamped
+        assert_eq!(auto_color(TimeOfDay::Night, 0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(
+            auto_color(TimeOfDay::Night, 3.0),
+            auto_color(TimeOfDay::Night, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_color_theme_cycle_includes_auto() {
+        assert_eq!(ColorTheme::White.next(), ColorTheme::Auto);
+        assert_eq!(ColorTheme::Auto.next(), ColorTheme::Rainbow);
+        assert_eq!(ColorTheme::Auto.prev(), ColorTheme::White);
+        assert!(!ColorTheme::Auto.is_dynamic());
+    }
+
+    #[test]
+    fn test_subsecond_format() {
+        assert_eq!(SubsecondPrecision::Off.format(730), None);
+        assert_eq!(SubsecondPrecision::Tenths.format(730), Some(".7".into()));
+        assert_eq!(
+            SubsecondPrecision::Hundredths.format(730),
+            Some(".73".into())
+        );
+        assert_eq!(SubsecondPrecision::Hundredths.format(5), Some(".00".into()));
+        assert_eq!(
+            SubsecondPrecision::Hundredths.format(50),
+            Some(".05".into())
+        );
+    }
+
+    #[test]
+    fn test_subsecond_rollover() {
+        // The last frame of a second truncates instead of rounding up to the next
+        assert_eq!(SubsecondPrecision::Tenths.format(999), Some(".9".into()));
+        assert_eq!(
+            SubsecondPrecision::Hundredths.format(999),
+            Some(".99".into())
+        );
+        assert_eq!(SubsecondPrecision::Tenths.format(0), Some(".0".into()));
+        assert_eq!(SubsecondPrecision::Hundredths.format(0), Some(".00".into()));
+        // Leap seconds report 1000ms and above
+        assert_eq!(SubsecondPrecision::Tenths.format(1000), Some(".9".into()));
+        assert_eq!(
+            SubsecondPrecision::Hundredths.format(1500),
+            Some(".99".into())
+        );
+    }
+
+    #[test]
+    fn test_subsecond_cycle() {
+        assert_eq!(SubsecondPrecision::Off.next(), SubsecondPrecision::Tenths);
+        assert_eq!(
+            SubsecondPrecision::Tenths.next(),
+            SubsecondPrecision::Hundredths
+        );
+        assert_eq!(
+            SubsecondPrecision::Hundredths.next(),
+            SubsecondPrecision::Off
+        );
+    }
+
+    fn parts(hour: u32, minute: u32, second: u32, format: TimeFormat) -> TimeParts {
+        let dt = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 17, hour, minute, second)
+            .unwrap();
+        TimeParts::from_datetime(&dt, format)
+    }
+
+    #[test]
+    fn test_date_format_cycle() {
+        let mut format = DateFormat::default();
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(date.format(format.pattern()).to_string());
+            format = format.next();
+        }
+        assert_eq!(format, DateFormat::Long);
+        assert_eq!(seen, ["Monday, June 03, 2024", "Mon, Jun 03", "2024-06-03"]);
+    }
+
+    #[test]
+    fn test_clock_position_place() {
+        let area = Rect::new(2, 1, 80, 24);
+        let content = Size::new(40, 10);
+        let cases = [
+            (ClockPosition::Center, Rect::new(2, 8, 80, 10)),
+            (ClockPosition::TopLeft, Rect::new(2, 1, 40, 10)),
+            (ClockPosition::TopCenter, Rect::new(2, 1, 80, 10)),
+            (ClockPosition::TopRight, Rect::new(42, 1, 40, 10)),
+            (ClockPosition::BottomRight, Rect::new(42, 15, 40, 10)),
+            (ClockPosition::BottomCenter, Rect::new(2, 15, 80, 10)),
+            (ClockPosition::BottomLeft, Rect::new(2, 15, 40, 10)),
+        ];
+        for (position, expected) in cases {
+            assert_eq!(position.place(content, area), expected, "{position:?}");
+        }
+    }
+
+    #[test]
+    fn test_clock_position_clamps_oversized_content() {
+        let area = Rect::new(0, 0, 30, 8);
+        let content = Size::new(50, 12);
+        let mut position = ClockPosition::default();
+        loop {
+            let rect = position.place(content, area);
+            assert_eq!(rect, area, "{position:?}");
+            position = position.next();
+            if position == ClockPosition::default() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_clock_position_by_name() {
+        assert_eq!(
+            ClockPosition::by_name("top-left"),
+            Some(ClockPosition::TopLeft)
+        );
+        assert_eq!(
+            ClockPosition::by_name("BottomRight"),
+            Some(ClockPosition::BottomRight)
+        );
+        assert_eq!(
+            ClockPosition::by_name("bottom"),
+            Some(ClockPosition::BottomCenter)
+        );
+        assert_eq!(ClockPosition::by_name("middle"), None);
+    }
+
+    #[test]
+    fn test_time_parts_24h() {
+        let p = parts(0, 5, 9, TimeFormat::TwentyFourHour);
+        assert_eq!((p.hours, p.minutes, p.seconds, p.is_pm), (0, 5, 9, false));
+        let p = parts(23, 59, 59, TimeFormat::TwentyFourHour);
+        assert_eq!(
+            (p.hours, p.minutes, p.seconds, p.is_pm),
+            (23, 59, 59, false)
+        );
+    }
+
+    #[test]
+    fn test_time_parts_12h_midnight_and_noon() {
+        // 00:xx is 12 AM, 12:xx is 12 PM
+        let p = parts(0, 30, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.is_pm), (12, false));
+        let p = parts(12, 30, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.is_pm), (12, true));
+
+        let p = parts(1, 0, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.is_pm), (1, false));
+        let p = parts(11, 59, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.is_pm), (11, false));
+        let p = parts(13, 0, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.is_pm), (1, true));
+        let p = parts(23, 59, 0, TimeFormat::TwelveHour);
+        assert_eq!((p.hours, p.minutes, p.is_pm), (11, 59, true));
+    }
+
+    #[test]
+    fn test_time_parts_text_around_midnight_and_noon() {
+        let cases = [
+            ((0, 0), "00:00:00", "12:00:00 AM"),
+            ((0, 30), "00:30:00", "12:30:00 AM"),
+            ((11, 59), "11:59:00", "11:59:00 AM"),
+            ((12, 0), "12:00:00", "12:00:00 PM"),
+            ((12, 1), "12:01:00", "12:01:00 PM"),
+            ((23, 59), "23:59:00", "11:59:00 PM"),
+        ];
+        for ((hour, minute), twenty_four, twelve) in cases {
+            let text = |format| parts(hour, minute, 0, format).text(format);
+            assert_eq!(text(TimeFormat::TwentyFourHour), twenty_four);
+            assert_eq!(text(TimeFormat::TwelveHour), twelve);
+        }
+    }
+
+    #[test]
+    fn test_time_parts_leap_second() {
+        let dt = chrono::Utc
+            .with_ymd_and_hms(2016, 12, 31, 23, 59, 59)
+            .unwrap()
+            .with_nanosecond(1_500_000_000)
+            .unwrap();
+        let p = TimeParts::from_datetime(&dt, TimeFormat::TwentyFourHour);
+        assert_eq!((p.hours, p.minutes, p.seconds), (23, 59, 60));
+    }
+
+    #[test]
+    fn test_time_parts_text() {
+        let text = |hour, format| parts(hour, 5, 9, format).text(format);
+        assert_eq!(text(0, TimeFormat::TwentyFourHour), "00:05:09");
+        assert_eq!(text(21, TimeFormat::TwentyFourHour), "21:05:09");
+        assert_eq!(text(0, TimeFormat::TwelveHour), "12:05:09 AM");
+        assert_eq!(text(21, TimeFormat::TwelveHour), " 9:05:09 PM");
+
+        let p = parts(21, 5, 9, TimeFormat::TwelveHour);
+        assert_eq!(p.digits(TimeFormat::TwelveHour), " 9:05:09");
+        assert_eq!(p.meridiem(TimeFormat::TwelveHour), Some("PM"));
+        let p = parts(21, 5, 9, TimeFormat::TwentyFourHour);
+        assert_eq!(p.meridiem(TimeFormat::TwentyFourHour), None);
+    }
+
+    #[test]
+    fn test_am_pm_style_cycle() {
+        let mut style = AmPmStyle::default();
+        assert_eq!(style, AmPmStyle::Large);
+        for _ in 0..3 {
+            style = style.next();
+        }
+        assert_eq!(style, AmPmStyle::Large);
+        assert_eq!(AmPmStyle::SmallText.display_name(), "Small text");
+    }
+
+    #[test]
+    fn test_accessibility_cycle() {
+        let mut mode = Accessibility::default();
+        for _ in 0..3 {
+            assert_eq!(mode.next().prev(), mode);
+            mode = mode.next();
+        }
+        assert_eq!(mode, Accessibility::Off);
+        assert!(!Accessibility::Off.hides_background());
+        assert!(Accessibility::Plain.hides_background());
+    }
+}
@@ -0,0 +1,96 @@
+//! Moon phase from the mean synodic month.
+//!
+//! Counts mean lunations from a known new moon, which puts the phase within
+//! about a day of the true one: plenty for drawing the moon.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Mean length of a lunation in days.
+const SYNODIC_MONTH_DAYS: f64 = 29.530_588_853;
+
+/// Seconds in a day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A known new moon: 2000-01-06 18:14 UTC.
+fn reference_new_moon() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 6)
+        .and_then(|date| date.and_hms_opt(18, 14, 0))
+        .unwrap_or_default()
+}
+
+/// Moon phase at noon UTC on `date`, through the synodic cycle.
+///
+/// 0.0 is the new moon, 0.25 the first quarter, 0.5 the full moon and 0.75 the
+/// last quarter; the value stays below 1.0.
+pub fn moon_phase(date: NaiveDate) -> f32 {
+    let noon = date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default());
+    let days = (noon - reference_new_moon()).num_seconds() as f64 / SECONDS_PER_DAY;
+    let phase = (days / SYNODIC_MONTH_DAYS).rem_euclid(1.0) as f32;
+    // Rounding can land exactly on the next cycle
+    if phase >= 1.0 { 0.0 } else { phase }
+}
+
+/// Lit fraction of the moon's disc at `phase` (0.0 - 1.0).
+pub fn moon_illumination(phase: f32) -> f32 {
+    (1.0 - (phase * std::f32::consts::TAU).cos()) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Distance between phases around the cycle.
+    fn distance(a: f32, b: f32) -> f32 {
+        let d = (a - b).rem_euclid(1.0);
+        d.min(1.0 - d)
+    }
+
+    #[test]
+    fn test_known_new_moons() {
+        // Solar eclipses, and the month of the reference itself
+        for new_moon in [date(2000, 1, 6), date(2017, 8, 21), date(2024, 4, 8)] {
+            let phase = moon_phase(new_moon);
+            assert!(distance(phase, 0.0) < 0.04, "{new_moon}: {phase}");
+            assert!(moon_illumination(phase) < 0.02, "{new_moon}");
+        }
+    }
+
+    #[test]
+    fn test_known_full_moons() {
+        // Lunar eclipses
+        for full_moon in [date(2021, 5, 26), date(2022, 11, 8), date(2025, 3, 14)] {
+            let phase = moon_phase(full_moon);
+            assert!(distance(phase, 0.5) < 0.04, "{full_moon}: {phase}");
+            assert!(moon_illumination(phase) > 0.98, "{full_moon}");
+        }
+    }
+
+    #[test]
+    fn test_phase_advances_through_the_cycle() {
+        let start = date(2024, 4, 9);
+        let mut last = moon_phase(start);
+        for day in 1..29 {
+            let phase = moon_phase(start + chrono::Days::new(day));
+            assert!((0.0..1.0).contains(&phase));
+            assert!(phase > last, "day {day}");
+            last = phase;
+        }
+        // A lunation later the cycle starts over
+        assert!(moon_phase(start + chrono::Days::new(30)) < 0.1);
+
+        // Dates before the reference work as well: the 1999 solar eclipse
+        assert!(distance(moon_phase(date(1999, 8, 11)), 0.0) < 0.04);
+    }
+
+    #[test]
+    fn test_illumination() {
+        assert_eq!(moon_illumination(0.0), 0.0);
+        assert!((moon_illumination(0.25) - 0.5).abs() < 1e-6);
+        assert_eq!(moon_illumination(0.5), 1.0);
+        assert!((moon_illumination(0.75) - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,77 @@
+//! How heavily rain or snow falls, from a drizzle (0.0) to a downpour (1.0).
+//!
+//! Weather providers report it in steps; the rain and snow backgrounds scale
+//! how many drops fall, how fast and how heavy they look with it.
+
+use crate::WeatherCondition;
+
+/// Drizzle, patchy or light rain and snow.
+pub const LIGHT: f32 = 0.25;
+
+/// Moderate rain and snow, also assumed when nothing finer is known.
+pub const MODERATE: f32 = 0.5;
+
+/// Heavy rain, torrential showers and blizzards.
+pub const HEAVY: f32 = 1.0;
+
+/// Keep an intensity within 0.0 - 1.0, treating NaN as moderate.
+pub fn clamp_intensity(intensity: f32) -> f32 {
+    if intensity.is_nan() {
+        return MODERATE;
+    }
+    intensity.clamp(0.0, 1.0)
+}
+
+/// Typical intensity for a condition, when the provider gives no finer detail.
+pub fn condition_intensity(condition: WeatherCondition) -> f32 {
+    match condition {
+        WeatherCondition::Rain => LIGHT,
+        WeatherCondition::HeavyRain | WeatherCondition::Thunderstorm => HEAVY,
+        _ => MODERATE,
+    }
+}
+
+/// Parse an intensity between 0.0 and 1.0, e.g. "0.8".
+pub fn parse_intensity(s: &str) -> Result<f32, String> {
+    let intensity: f32 = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid intensity '{s}': expected a number"))?;
+    if !(0.0..=1.0).contains(&intensity) {
+        return Err(format!(
+            "invalid intensity '{s}': expected between 0.0 and 1.0"
+        ));
+    }
+    Ok(intensity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_intensity() {
+        assert_eq!(clamp_intensity(0.3), 0.3);
+        assert_eq!(clamp_intensity(-1.0), 0.0);
+        assert_eq!(clamp_intensity(4.0), 1.0);
+        assert_eq!(clamp_intensity(f32::NAN), MODERATE);
+    }
+
+    #[test]
+    fn test_condition_intensity() {
+        assert_eq!(condition_intensity(WeatherCondition::Rain), LIGHT);
+        assert_eq!(condition_intensity(WeatherCondition::HeavyRain), HEAVY);
+        assert_eq!(condition_intensity(WeatherCondition::Snow), MODERATE);
+        assert_eq!(condition_intensity(WeatherCondition::Clear), MODERATE);
+    }
+
+    #[test]
+    fn test_parse_intensity() {
+        assert_eq!(parse_intensity("0.8"), Ok(0.8));
+        assert_eq!(parse_intensity(" 1 "), Ok(1.0));
+        assert_eq!(parse_intensity("0"), Ok(0.0));
+        assert!(parse_intensity("1.5").is_err());
+        assert!(parse_intensity("-0.1").is_err());
+        assert!(parse_intensity("heavy").is_err());
+    }
+}
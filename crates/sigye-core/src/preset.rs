@@ -0,0 +1,179 @@
+//! Named looks bundling colors, background, font and speed, switchable at once.
+//!
+//! A few presets are built in, and the config can add more or replace a
+//! built-in one by using its name. Fields a preset leaves out take their
+//! default, so `[presets.calm]` with only `background_style = "Aurora"` is a
+//! complete preset.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::theme::{HexColor, ThemeOverrides};
+use crate::{AmPmStyle, AnimationSpeed, BackgroundStyle, ColorTheme, TimeFormat};
+
+/// Font used by presets that don't name one.
+const DEFAULT_PRESET_FONT: &str = "Standard";
+
+/// Everything a preset sets when applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    /// Color theme.
+    pub color_theme: ColorTheme,
+    /// Colors in place of the theme's defaults.
+    pub theme: ThemeOverrides,
+    /// Background style.
+    pub background_style: BackgroundStyle,
+    /// Font of the big digits.
+    pub font_name: String,
+    /// Animation speed.
+    pub animation_speed: AnimationSpeed,
+    /// 12-hour or 24-hour time.
+    pub time_format: TimeFormat,
+    /// How AM/PM is drawn in 12-hour time.
+    pub am_pm_style: AmPmStyle,
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self {
+            color_theme: ColorTheme::default(),
+            theme: ThemeOverrides::default(),
+            background_style: BackgroundStyle::default(),
+            font_name: DEFAULT_PRESET_FONT.to_string(),
+            animation_speed: AnimationSpeed::default(),
+            time_format: TimeFormat::default(),
+            am_pm_style: AmPmStyle::default(),
+        }
+    }
+}
+
+/// The presets that come with sigye, in cycling order.
+pub fn builtin_presets() -> Vec<(String, Preset)> {
+    let amber = ThemeOverrides {
+        digit_color: HexColor::parse("#ffb000"),
+        ..ThemeOverrides::default()
+    };
+    [
+        (
+            "hacker",
+            Preset {
+                color_theme: ColorTheme::Green,
+                background_style: BackgroundStyle::MatrixRain,
+                font_name: "ANSI Regular".to_string(),
+                animation_speed: AnimationSpeed::Fast,
+                ..Preset::default()
+            },
+        ),
+        (
+            "cozy",
+            Preset {
+                color_theme: ColorTheme::Yellow,
+                theme: amber,
+                background_style: BackgroundStyle::Fireplace,
+                animation_speed: AnimationSpeed::Slow,
+                time_format: TimeFormat::TwelveHour,
+                am_pm_style: AmPmStyle::SmallText,
+                ..Preset::default()
+            },
+        ),
+        (
+            "minimal",
+            Preset {
+                color_theme: ColorTheme::White,
+                background_style: BackgroundStyle::None,
+                font_name: "Small".to_string(),
+                ..Preset::default()
+            },
+        ),
+        (
+            "night",
+            Preset {
+                color_theme: ColorTheme::Blue,
+                background_style: BackgroundStyle::Starfield,
+                animation_speed: AnimationSpeed::Slow,
+                ..Preset::default()
+            },
+        ),
+        (
+            "winter",
+            Preset {
+                color_theme: ColorTheme::GradientFrost,
+                background_style: BackgroundStyle::Snowfall,
+                ..Preset::default()
+            },
+        ),
+    ]
+    .into_iter()
+    .map(|(name, preset)| (name.to_string(), preset))
+    .collect()
+}
+
+/// Built-in presets followed by the user's, in cycling order.
+///
+/// A user preset named like a built-in one (ignoring case) replaces it in place.
+pub fn merge_presets(user: &BTreeMap<String, Preset>) -> Vec<(String, Preset)> {
+    let mut presets = builtin_presets();
+    for (name, preset) in user {
+        match presets
+            .iter_mut()
+            .find(|(builtin, _)| builtin.eq_ignore_ascii_case(name))
+        {
+            Some(builtin) => builtin.1 = preset.clone(),
+            None => presets.push((name.clone(), preset.clone())),
+        }
+    }
+    presets
+}
+
+/// Position of the preset called `name` (ignoring case) in `presets`.
+pub fn find_preset(presets: &[(String, Preset)], name: &str) -> Option<usize> {
+    presets
+        .iter()
+        .position(|(preset, _)| preset.eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_names_are_unique() {
+        let presets = builtin_presets();
+        for (i, (name, _)) in presets.iter().enumerate() {
+            assert_eq!(find_preset(&presets, name), Some(i), "{name}");
+        }
+        assert_eq!(
+            presets[find_preset(&presets, " Hacker ").unwrap()]
+                .1
+                .background_style,
+            BackgroundStyle::MatrixRain
+        );
+        assert_eq!(find_preset(&presets, "nope"), None);
+    }
+
+    #[test]
+    fn test_user_presets_replace_and_extend() {
+        let calm = Preset {
+            background_style: BackgroundStyle::Aurora,
+            ..Preset::default()
+        };
+        let user = BTreeMap::from([
+            ("Cozy".to_string(), calm.clone()),
+            ("calm".to_string(), calm.clone()),
+        ]);
+        let builtin = builtin_presets();
+        let presets = merge_presets(&user);
+        assert_eq!(presets.len(), builtin.len() + 1);
+
+        // Replaced in place, keeping the built-in name and position
+        let cozy = find_preset(&presets, "cozy").unwrap();
+        assert_eq!(presets[cozy].0, "cozy");
+        assert_eq!(presets[cozy].1, calm);
+        assert_eq!(find_preset(&builtin, "cozy"), Some(cozy));
+
+        // New ones come after the built-ins
+        assert_eq!(presets.last(), Some(&("calm".to_string(), calm)));
+    }
+}
@@ -0,0 +1,289 @@
+//! How much of the day, week, month or year has gone by, drawn as a bar.
+//!
+//! Periods run from local midnight to local midnight, so a day that changes
+//! to or from summer time is 23 or 25 hours long and still fills the bar
+//! exactly once. Weeks start on Monday, like ISO weeks.
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Partial blocks for an eighth to seven eighths of a cell.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Period the progress bar under the clock shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgressBar {
+    /// No progress bar.
+    #[default]
+    Hidden,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl ProgressBar {
+    /// Cycle to the next period, ending back at hidden.
+    pub fn next(self) -> Self {
+        match self {
+            ProgressBar::Hidden => ProgressBar::Day,
+            ProgressBar::Day => ProgressBar::Week,
+            ProgressBar::Week => ProgressBar::Month,
+            ProgressBar::Month => ProgressBar::Year,
+            ProgressBar::Year => ProgressBar::Hidden,
+        }
+    }
+
+    /// Get the display name for this period.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            ProgressBar::Hidden => "Hidden",
+            ProgressBar::Day => "Day",
+            ProgressBar::Week => "Week",
+            ProgressBar::Month => "Month",
+            ProgressBar::Year => "Year",
+        }
+    }
+
+    /// Check if the progress bar takes up a layout row.
+    pub fn is_visible(self) -> bool {
+        self != ProgressBar::Hidden
+    }
+
+    /// First day of the period containing `date` and the first day after it,
+    /// `None` when hidden.
+    fn period(self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        match self {
+            ProgressBar::Hidden => None,
+            ProgressBar::Day => Some((date, date.succ_opt()?)),
+            ProgressBar::Week => {
+                let start = date.week(Weekday::Mon).first_day();
+                Some((start, start.checked_add_days(Days::new(7))?))
+            }
+            ProgressBar::Month => {
+                let start = date.with_day(1)?;
+                Some((start, start.checked_add_months(Months::new(1))?))
+            }
+            ProgressBar::Year => {
+                let start = date.with_ordinal(1)?;
+                Some((start, start.checked_add_months(Months::new(12))?))
+            }
+        }
+    }
+
+    /// Fraction of the period gone by at `now` (0.0 - 1.0), `None` when hidden.
+    pub fn fraction<Tz: TimeZone>(self, now: &DateTime<Tz>) -> Option<f64> {
+        let (start, end) = self.period(now.date_naive())?;
+        let timezone = now.timezone();
+        let start = local_midnight(&timezone, start)?;
+        let end = local_midnight(&timezone, end)?;
+        let length = (end - start.clone()).num_milliseconds() as f64;
+        let elapsed = (now.clone() - start).num_milliseconds() as f64;
+        Some((elapsed / length).clamp(0.0, 1.0))
+    }
+}
+
+/// Start of `date` in `timezone`: midnight, or the first hour after it that
+/// exists when the clocks skip midnight.
+fn local_midnight<Tz: TimeZone>(timezone: &Tz, date: NaiveDate) -> Option<DateTime<Tz>> {
+    (0..24).find_map(|hour| {
+        timezone
+            .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+    })
+}
+
+/// Bar `cells` wide filled to `fraction`, using partial blocks for eighths of a cell.
+///
+/// The unfilled part is spaces, so the bar always has the same width.
+pub fn progress_bar(fraction: f64, cells: usize) -> String {
+    let eighths = (fraction.clamp(0.0, 1.0) * (cells * 8) as f64).floor() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(part) = (eighths % 8).checked_sub(1) {
+        bar.push(EIGHTHS[part]);
+    }
+    let filled = bar.chars().count();
+    bar.extend(std::iter::repeat_n(' ', cells - filled));
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        FixedOffset, LocalResult, NaiveDateTime, TimeDelta, Utc, offset::MappedLocalTime,
+    };
+
+    use super::*;
+
+    /// Central European time in 2025: summer time from March 30 01:00 UTC
+    /// to October 26 01:00 UTC, the clocks going 02:00 -> 03:00 and 03:00 -> 02:00.
+    #[derive(Debug, Clone, Copy)]
+    struct Cet2025;
+
+    impl Cet2025 {
+        fn offset_at_utc(utc: &NaiveDateTime) -> FixedOffset {
+            let summer_start = date(2025, 3, 30).and_hms_opt(1, 0, 0).unwrap();
+            let summer_end = date(2025, 10, 26).and_hms_opt(1, 0, 0).unwrap();
+            let hours = if (summer_start..summer_end).contains(utc) {
+                2
+            } else {
+                1
+            };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for Cet2025 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Cet2025
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            // Offsets that map the local time back to itself
+            let fits: Vec<FixedOffset> = [1, 2]
+                .into_iter()
+                .map(|hours| FixedOffset::east_opt(hours * 3600).unwrap())
+                .filter(|offset| {
+                    let utc = *local - TimeDelta::seconds(offset.local_minus_utc().into());
+                    Self::offset_at_utc(&utc) == *offset
+                })
+                .collect();
+            match fits[..] {
+                [] => LocalResult::None,
+                [offset] => LocalResult::Single(offset),
+                // The summer time one comes first
+                [winter, summer] => LocalResult::Ambiguous(summer, winter),
+                _ => unreachable!(),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            Self::offset_at_utc(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset_at_utc(utc)
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&date(year, month, day).and_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_hidden_has_no_fraction() {
+        assert_eq!(ProgressBar::Hidden.fraction(&utc(2025, 6, 1, 12, 0)), None);
+        assert!(!ProgressBar::Hidden.is_visible());
+        assert_eq!(ProgressBar::Year.next(), ProgressBar::Hidden);
+    }
+
+    #[test]
+    fn test_day_fraction() {
+        assert_close(ProgressBar::Day.fraction(&utc(2025, 6, 1, 0, 0)), 0.0);
+        assert_close(ProgressBar::Day.fraction(&utc(2025, 6, 1, 6, 0)), 0.25);
+        assert_close(ProgressBar::Day.fraction(&utc(2025, 6, 1, 18, 0)), 0.75);
+    }
+
+    #[test]
+    fn test_dst_days_are_not_24_hours() {
+        // Spring forward: a 23-hour day, noon is 11 hours in
+        let noon = Cet2025.with_ymd_and_hms(2025, 3, 30, 12, 0, 0).unwrap();
+        assert_close(ProgressBar::Day.fraction(&noon), 11.0 / 23.0);
+        let before_midnight = Cet2025.with_ymd_and_hms(2025, 3, 30, 23, 30, 0).unwrap();
+        assert_close(ProgressBar::Day.fraction(&before_midnight), 22.5 / 23.0);
+
+        // Fall back: a 25-hour day, noon is 13 hours in
+        let noon = Cet2025.with_ymd_and_hms(2025, 10, 26, 12, 0, 0).unwrap();
+        assert_close(ProgressBar::Day.fraction(&noon), 13.0 / 25.0);
+
+        // An ordinary day in summer time
+        let noon = Cet2025.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap();
+        assert_close(ProgressBar::Day.fraction(&noon), 0.5);
+    }
+
+    #[test]
+    fn test_week_starts_on_monday() {
+        // 2025-06-02 is a Monday
+        assert_close(ProgressBar::Week.fraction(&utc(2025, 6, 2, 0, 0)), 0.0);
+        assert_close(
+            ProgressBar::Week.fraction(&utc(2025, 6, 5, 12, 0)),
+            3.5 / 7.0,
+        );
+        assert_close(
+            ProgressBar::Week.fraction(&utc(2025, 6, 8, 12, 0)),
+            6.5 / 7.0,
+        );
+    }
+
+    #[test]
+    fn test_month_fraction() {
+        assert_close(
+            ProgressBar::Month.fraction(&utc(2025, 6, 16, 0, 0)),
+            15.0 / 30.0,
+        );
+        assert_close(
+            ProgressBar::Month.fraction(&utc(2025, 2, 15, 0, 0)),
+            14.0 / 28.0,
+        );
+        assert_close(
+            ProgressBar::Month.fraction(&utc(2024, 2, 15, 0, 0)),
+            14.0 / 29.0,
+        );
+        assert_close(
+            ProgressBar::Month.fraction(&utc(2025, 12, 31, 0, 0)),
+            30.0 / 31.0,
+        );
+    }
+
+    #[test]
+    fn test_year_fraction_in_leap_years() {
+        // March 1 is day 60 in common years, 61 in leap years
+        assert_close(
+            ProgressBar::Year.fraction(&utc(2025, 3, 1, 0, 0)),
+            59.0 / 365.0,
+        );
+        assert_close(
+            ProgressBar::Year.fraction(&utc(2024, 3, 1, 0, 0)),
+            60.0 / 366.0,
+        );
+        assert_close(
+            ProgressBar::Year.fraction(&utc(2000, 12, 31, 0, 0)),
+            365.0 / 366.0,
+        );
+        assert_close(
+            ProgressBar::Year.fraction(&utc(1900, 12, 31, 0, 0)),
+            364.0 / 365.0,
+        );
+    }
+
+    #[test]
+    fn test_progress_bar_eighths() {
+        assert_eq!(progress_bar(0.0, 4), "    ");
+        assert_eq!(progress_bar(1.0, 4), "████");
+        assert_eq!(progress_bar(0.5, 4), "██  ");
+        assert_eq!(progress_bar(1.0 / 32.0, 4), "▏   ");
+        assert_eq!(progress_bar(0.25 + 7.0 / 32.0, 4), "█▉  ");
+        assert_eq!(progress_bar(0.3, 0), "");
+        // Out of range fractions stay within the width
+        assert_eq!(progress_bar(1.5, 2), "██");
+        assert_eq!(progress_bar(-0.5, 2), "  ");
+    }
+}
@@ -0,0 +1,490 @@
+//! Daily time ranges, optionally limited to some weekdays: when the clock is
+//! shown, and which preset is applied when.
+//!
+//! A range is written as `[DAYS] HH:MM-HH:MM`, e.g. `08:00-19:00`,
+//! `Mon-Fri 08:00-19:00` or `Sat,Sun 10:00-14:00`. Days are names or
+//! three-letter abbreviations, comma-separated lists, spans like `Fri-Mon`, or
+//! `weekdays` / `weekends`. A range that ends before it starts runs overnight and
+//! belongs to the day it starts on, so `Fri 22:00-06:00` lasts until Saturday
+//! morning. Equal start and end times cover the whole day.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Weekday};
+
+/// Minutes in a day.
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Days of the week, Monday first.
+const WEEK: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Days of the week a range applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Days(u8);
+
+impl Days {
+    const ALL: Days = Days(0b111_1111);
+    const WEEKDAYS: Days = Days(0b001_1111);
+    const WEEKENDS: Days = Days(0b110_0000);
+
+    fn only(day: Weekday) -> Self {
+        Days(1 << day.num_days_from_monday())
+    }
+
+    fn contains(self, day: Weekday) -> bool {
+        self.0 & Self::only(day).0 != 0
+    }
+
+    /// Parse a comma-separated list of days, day spans and `weekdays` / `weekends`.
+    fn parse(spec: &str) -> Result<Self, String> {
+        let mut days = Days(0);
+        for part in spec.split(',').map(str::trim) {
+            days.0 |= match part.to_ascii_lowercase().as_str() {
+                "weekdays" => Self::WEEKDAYS,
+                "weekends" => Self::WEEKENDS,
+                _ => match part.split_once('-') {
+                    Some((first, last)) => {
+                        Self::span(parse_weekday(first.trim())?, parse_weekday(last.trim())?)
+                    }
+                    None => Self::only(parse_weekday(part)?),
+                },
+            }
+            .0;
+        }
+        Ok(days)
+    }
+
+    /// Days from `first` to `last` inclusive, wrapping past Sunday.
+    fn span(first: Weekday, last: Weekday) -> Self {
+        let mut days = Self::only(first);
+        let mut day = first;
+        while day != last {
+            day = day.succ();
+            days.0 |= Self::only(day).0;
+        }
+        days
+    }
+}
+
+/// Parse a weekday name or its three-letter abbreviation.
+fn parse_weekday(name: &str) -> Result<Weekday, String> {
+    let lower = name.to_ascii_lowercase();
+    WEEK.into_iter()
+        .find(|day| {
+            let full = day_name(*day).to_ascii_lowercase();
+            lower == full || lower == full[..3]
+        })
+        .ok_or_else(|| format!("unknown day \"{name}\""))
+}
+
+/// Full English name of a weekday.
+fn day_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Parse "HH:MM" into minutes since midnight; "24:00" is the end of the day.
+pub fn parse_minutes(time: &str) -> Result<u32, String> {
+    let invalid = || format!("invalid time \"{time}\", expected HH:MM");
+    let (hours, minutes) = time.split_once(':').ok_or_else(invalid)?;
+    let hours: u32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+    if minutes >= 60 || hours > 24 || (hours == 24 && minutes > 0) {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// A daily time range the clock is shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    /// Days the range starts on.
+    days: Days,
+    /// Start, in minutes since midnight.
+    start: u32,
+    /// End (exclusive), in minutes since midnight; at or before `start` it is on the next day.
+    /// Midnight may be written as 24:00 (1440).
+    end: u32,
+}
+
+impl TimeRange {
+    /// Parse a range like "08:00-19:00" or "Mon-Fri 08:00-19:00".
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        // Days come first, up to the start time
+        let (days, times) = spec.split_at(spec.find(|c: char| c.is_ascii_digit()).unwrap_or(0));
+        let days = match days.trim() {
+            "" => Days::ALL,
+            days => Days::parse(days)?,
+        };
+        let (start, end) = times
+            .split_once('-')
+            .ok_or_else(|| format!("invalid range \"{spec}\", expected HH:MM-HH:MM"))?;
+        Ok(Self {
+            days,
+            start: parse_minutes(start.trim())?,
+            end: parse_minutes(end.trim())?,
+        })
+    }
+
+    /// Check if the range covers `minute` (since midnight) on `day`.
+    pub fn contains(&self, day: Weekday, minute: u32) -> bool {
+        let start = self.start % MINUTES_PER_DAY;
+        let end = self.end % MINUTES_PER_DAY;
+        if start < end {
+            return self.days.contains(day) && (start..end).contains(&minute);
+        }
+        // Overnight (or all day): the part after midnight belongs to the previous day
+        (minute >= start && self.days.contains(day))
+            || (minute < end && self.days.contains(day.pred()))
+    }
+
+    /// Number of minutes the range lasts, a whole day for equal start and end.
+    fn minutes(&self) -> u32 {
+        match (self.end + MINUTES_PER_DAY - self.start) % MINUTES_PER_DAY {
+            0 => MINUTES_PER_DAY,
+            minutes => minutes,
+        }
+    }
+
+    /// How specific the range is, greater for the one that wins an overlap:
+    /// fewer days, then a shorter range, then a later start.
+    fn specificity(&self) -> impl Ord {
+        (
+            Reverse(self.days.0.count_ones()),
+            Reverse(self.minutes()),
+            self.start % MINUTES_PER_DAY,
+        )
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |minutes: u32| format!("{:02}:{:02}", minutes / 60, minutes % 60);
+        if self.days != Days::ALL {
+            let days: Vec<&str> = WEEK
+                .into_iter()
+                .filter(|day| self.days.contains(*day))
+                .map(|day| &day_name(day)[..3])
+                .collect();
+            write!(f, "{} ", days.join(","))?;
+        }
+        write!(f, "{}-{}", time(self.start), time(self.end))
+    }
+}
+
+/// Time ranges the clock is shown in; the clock is always shown without any.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    ranges: Vec<TimeRange>,
+}
+
+impl Schedule {
+    /// Create a schedule from ranges.
+    pub fn new(ranges: Vec<TimeRange>) -> Self {
+        Self { ranges }
+    }
+
+    /// Parse the ranges of `specs`, leaving out invalid ones and returning
+    /// why each was left out, so they can be reported at startup.
+    pub fn parse_all<S: AsRef<str>>(specs: &[S]) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let ranges = specs
+            .iter()
+            .filter_map(|spec| {
+                TimeRange::parse(spec.as_ref())
+                    .inspect_err(|e| errors.push(e.clone()))
+                    .ok()
+            })
+            .collect();
+        (Self::new(ranges), errors)
+    }
+
+    /// Check if the clock is shown at `now`.
+    pub fn shows_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        let minute = now.hour() * 60 + now.minute();
+        self.ranges.is_empty()
+            || self
+                .ranges
+                .iter()
+                .any(|range| range.contains(now.weekday(), minute))
+    }
+}
+
+/// A preset applied during a time range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledPreset {
+    /// When the preset is applied.
+    pub range: TimeRange,
+    /// Name of the preset.
+    pub preset: String,
+}
+
+impl ScheduledPreset {
+    /// Parse a range like "Mon-Fri 09:00-18:00" for the preset named `preset`.
+    pub fn parse(range: &str, preset: &str) -> Result<Self, String> {
+        Ok(Self {
+            range: TimeRange::parse(range)?,
+            preset: preset.trim().to_string(),
+        })
+    }
+}
+
+/// Presets for times of the day and days of the week.
+///
+/// When ranges overlap, the most specific one wins: the one on fewer days,
+/// then the shorter one, then the one starting later. So `Sat,Sun 08:00-22:00`
+/// beats `07:00-23:00` on weekends, and `18:00-23:00` takes over from
+/// `09:00-20:00` at six. Between the ranges no preset is scheduled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleResolver {
+    rules: Vec<ScheduledPreset>,
+}
+
+impl ScheduleResolver {
+    /// Create the resolver from rules; among identical ranges the last wins.
+    pub fn new(rules: Vec<ScheduledPreset>) -> Self {
+        Self { rules }
+    }
+
+    /// Name of the preset scheduled at `now`, `None` outside every range.
+    pub fn active_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<&str> {
+        let minute = now.hour() * 60 + now.minute();
+        self.rules
+            .iter()
+            .filter(|rule| rule.range.contains(now.weekday(), minute))
+            .max_by_key(|rule| rule.range.specificity())
+            .map(|rule| rule.preset.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn minute(time: &str) -> u32 {
+        parse_minutes(time).unwrap()
+    }
+
+    fn contains(spec: &str, day: Weekday, time: &str) -> bool {
+        TimeRange::parse(spec).unwrap().contains(day, minute(time))
+    }
+
+    #[test]
+    fn test_parse_minutes() {
+        assert_eq!(parse_minutes("00:00"), Ok(0));
+        assert_eq!(parse_minutes("8:05"), Ok(485));
+        assert_eq!(parse_minutes("24:00"), Ok(1440));
+        assert!(parse_minutes("24:01").is_err());
+        assert!(parse_minutes("12:60").is_err());
+        assert!(parse_minutes("12").is_err());
+        assert!(parse_minutes("ab:cd").is_err());
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(Days::parse("Mon"), Ok(Days(0b000_0001)));
+        assert_eq!(Days::parse("sunday"), Ok(Days(0b100_0000)));
+        assert_eq!(Days::parse("Mon-Fri"), Ok(Days::WEEKDAYS));
+        assert_eq!(Days::parse("Sat,Sun"), Ok(Days::WEEKENDS));
+        assert_eq!(Days::parse("weekends"), Ok(Days::WEEKENDS));
+        assert_eq!(Days::parse("Weekdays, Sat"), Ok(Days(0b011_1111)));
+        // Spans wrap past Sunday
+        assert_eq!(Days::parse("Fri-Mon"), Ok(Days(0b111_0001)));
+        assert!(Days::parse("Someday").is_err());
+        assert!(Days::parse("Mo").is_err());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let range = TimeRange::parse("Mon-Fri 08:00-19:00").unwrap();
+        assert_eq!(range.to_string(), "Mon,Tue,Wed,Thu,Fri 08:00-19:00");
+        assert_eq!(
+            TimeRange::parse(" 22:00 - 06:00 ").unwrap().to_string(),
+            "22:00-06:00"
+        );
+        assert_eq!(
+            TimeRange::parse("sat, sun 10:00-24:00")
+                .unwrap()
+                .to_string(),
+            "Sat,Sun 10:00-24:00"
+        );
+        assert!(TimeRange::parse("08:00").is_err());
+        assert!(TimeRange::parse("Mon 08:00-25:00").is_err());
+        assert!(TimeRange::parse("Funday 08:00-19:00").is_err());
+    }
+
+    #[test]
+    fn test_daytime_range() {
+        assert!(contains("08:00-19:00", Weekday::Wed, "08:00"));
+        assert!(contains("08:00-19:00", Weekday::Wed, "18:59"));
+        assert!(!contains("08:00-19:00", Weekday::Wed, "19:00"));
+        assert!(!contains("08:00-19:00", Weekday::Wed, "07:59"));
+        assert!(contains("10:00-24:00", Weekday::Wed, "23:59"));
+        assert!(!contains("10:00-24:00", Weekday::Wed, "00:00"));
+    }
+
+    #[test]
+    fn test_overnight_range() {
+        assert!(contains("22:00-06:00", Weekday::Wed, "22:00"));
+        assert!(contains("22:00-06:00", Weekday::Wed, "23:59"));
+        assert!(contains("22:00-06:00", Weekday::Wed, "00:00"));
+        assert!(contains("22:00-06:00", Weekday::Wed, "05:59"));
+        assert!(!contains("22:00-06:00", Weekday::Wed, "06:00"));
+        assert!(!contains("22:00-06:00", Weekday::Wed, "12:00"));
+    }
+
+    #[test]
+    fn test_overnight_range_belongs_to_start_day() {
+        // Friday night runs into Saturday morning, but Saturday night isn't included
+        assert!(contains("Fri 22:00-06:00", Weekday::Fri, "23:00"));
+        assert!(contains("Fri 22:00-06:00", Weekday::Sat, "05:00"));
+        assert!(!contains("Fri 22:00-06:00", Weekday::Sat, "23:00"));
+        assert!(!contains("Fri 22:00-06:00", Weekday::Fri, "05:00"));
+        // Sunday night wraps to Monday morning
+        assert!(contains("Sun 22:00-06:00", Weekday::Mon, "01:00"));
+    }
+
+    #[test]
+    fn test_weekday_filter() {
+        assert!(contains("Mon-Fri 08:00-19:00", Weekday::Fri, "12:00"));
+        assert!(!contains("Mon-Fri 08:00-19:00", Weekday::Sat, "12:00"));
+        assert!(contains("weekends 10:00-14:00", Weekday::Sun, "10:30"));
+        assert!(!contains("weekends 10:00-14:00", Weekday::Mon, "10:30"));
+    }
+
+    #[test]
+    fn test_whole_day_range() {
+        assert!(contains("00:00-00:00", Weekday::Tue, "00:00"));
+        assert!(contains("00:00-24:00", Weekday::Tue, "23:59"));
+        assert!(contains("Sat 00:00-24:00", Weekday::Sat, "12:00"));
+        assert!(!contains("Sat 00:00-24:00", Weekday::Sun, "12:00"));
+        // The same start and end anywhere else covers one day from then
+        assert!(contains("Sat 09:00-09:00", Weekday::Sun, "08:59"));
+        assert!(!contains("Sat 09:00-09:00", Weekday::Sun, "09:00"));
+    }
+
+    #[test]
+    fn test_schedule_shows_at() {
+        // 2026-10-17 is a Saturday
+        let at = |hour, minute| Utc.with_ymd_and_hms(2026, 10, 17, hour, minute, 0).unwrap();
+        assert!(Schedule::default().shows_at(&at(3, 0)));
+
+        let schedule = Schedule::new(vec![
+            TimeRange::parse("Mon-Fri 08:00-19:00").unwrap(),
+            TimeRange::parse("weekends 10:00-14:00").unwrap(),
+        ]);
+        assert!(schedule.shows_at(&at(10, 0)));
+        assert!(schedule.shows_at(&at(13, 59)));
+        assert!(!schedule.shows_at(&at(9, 0)));
+        assert!(!schedule.shows_at(&at(15, 0)));
+
+        // Invalid ranges are left out and reported
+        let (parsed, errors) =
+            Schedule::parse_all(&["Mon-Fri 08:00-19:00", "nope", "weekends 10:00-14:00"]);
+        assert_eq!(parsed, schedule);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("nope"), "{errors:?}");
+    }
+
+    fn resolver(rules: &[(&str, &str)]) -> ScheduleResolver {
+        ScheduleResolver::new(
+            rules
+                .iter()
+                .map(|(range, preset)| ScheduledPreset::parse(range, preset).unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_scheduled_preset_parse() {
+        let rule = ScheduledPreset::parse("Mon-Fri 09:00-18:00", " work ").unwrap();
+        assert_eq!(rule.range.to_string(), "Mon,Tue,Wed,Thu,Fri 09:00-18:00");
+        assert_eq!(rule.preset, "work");
+        assert!(ScheduledPreset::parse("9-18", "work").is_err());
+    }
+
+    #[test]
+    fn test_resolver_picks_the_active_preset() {
+        let day = resolver(&[
+            ("06:00-09:00", "sunrise"),
+            ("09:00-18:00", "work"),
+            ("18:00-23:00", "cozy"),
+        ]);
+        let overlapping = resolver(&[
+            ("07:00-23:00", "base"),
+            ("Sat,Sun 08:00-22:00", "weekend"),
+            ("Sun 12:00-14:00", "brunch"),
+            ("12:00-13:00", "lunch"),
+            ("09:00-20:00", "work"),
+            ("18:00-23:00", "evening"),
+        ]);
+        let overnight = resolver(&[("22:00-06:00", "night"), ("Fri 20:00-04:00", "party")]);
+        let duplicates = resolver(&[("09:00-17:00", "first"), ("09:00-17:00", "second")]);
+
+        // 2026-10-16 is a Friday
+        let cases = [
+            (&day, 16, "05:59", None),
+            (&day, 16, "06:00", Some("sunrise")),
+            (&day, 16, "08:59", Some("sunrise")),
+            (&day, 16, "09:00", Some("work")),
+            (&day, 16, "18:00", Some("cozy")),
+            (&day, 16, "22:59", Some("cozy")),
+            // Gaps have no preset
+            (&day, 16, "23:00", None),
+            (&day, 17, "03:00", None),
+            // The shorter range wins, then the later start
+            (&overlapping, 16, "07:30", Some("base")),
+            (&overlapping, 16, "10:00", Some("work")),
+            (&overlapping, 16, "12:30", Some("lunch")),
+            (&overlapping, 16, "19:00", Some("evening")),
+            (&overlapping, 16, "22:30", Some("evening")),
+            // Fewer days win over shorter ranges
+            (&overlapping, 17, "10:00", Some("weekend")),
+            (&overlapping, 17, "12:30", Some("weekend")),
+            (&overlapping, 18, "12:30", Some("brunch")),
+            (&overlapping, 18, "22:30", Some("evening")),
+            // Overnight ranges run past midnight, on the day they start
+            (&overnight, 15, "23:00", Some("night")),
+            (&overnight, 16, "05:00", Some("night")),
+            (&overnight, 16, "21:00", Some("party")),
+            (&overnight, 17, "03:00", Some("party")),
+            (&overnight, 17, "05:00", Some("night")),
+            (&overnight, 17, "21:00", None),
+            (&duplicates, 16, "10:00", Some("second")),
+            (&ScheduleResolver::default(), 16, "10:00", None),
+        ];
+        for (resolver, day, time, expected) in cases {
+            let (hour, minute) = time.split_once(':').unwrap();
+            let now = Utc
+                .with_ymd_and_hms(
+                    2026,
+                    10,
+                    day,
+                    hour.parse().unwrap(),
+                    minute.parse().unwrap(),
+                    0,
+                )
+                .unwrap();
+            assert_eq!(resolver.active_at(&now), expected, "day {day} at {time}");
+        }
+    }
+}
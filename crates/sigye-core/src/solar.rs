@@ -0,0 +1,146 @@
+//! Local sunrise/sunset calculation (NOAA sunrise equation).
+//!
+//! Accurate to within a couple of minutes for non-polar latitudes, which is plenty
+//! for choosing between dawn, day, dusk and night.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime};
+
+use crate::TimeOfDay;
+
+/// Julian day of 2000-01-01 12:00 UTC (J2000 epoch).
+const J2000: f64 = 2_451_545.0;
+
+/// Julian day of the Unix epoch.
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+/// Sun altitude at sunrise/sunset, accounting for refraction and the solar disc.
+const SUNRISE_ALTITUDE_DEG: f64 = -0.833;
+
+/// Axial tilt of the Earth in degrees.
+const EARTH_TILT_DEG: f64 = 23.4397;
+
+/// Compute sunrise and sunset in UTC for a location on a date.
+///
+/// `latitude` is north-positive and `longitude` east-positive, in degrees.
+/// `date` is the local calendar date at the location. Sunrise may fall on the
+/// previous UTC day, so it can be later than sunset as a bare time of day.
+/// Returns `None` during polar day or polar night, when the sun doesn't rise or set.
+pub fn sunrise_sunset(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+) -> Option<(NaiveTime, NaiveTime)> {
+    let j2000_date = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+    let days = (date - j2000_date).num_days() as f64 + 0.0008;
+
+    // Mean solar time at the location
+    let mean_solar = days - longitude / 360.0;
+
+    // Solar mean anomaly and equation of the center
+    let anomaly = (357.5291 + 0.985_600_28 * mean_solar).rem_euclid(360.0);
+    let m = anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+
+    // Ecliptic longitude and solar transit (solar noon)
+    let ecliptic = (anomaly + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+    let transit = J2000 + mean_solar + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic).sin();
+
+    // Declination of the sun
+    let sin_declination = ecliptic.sin() * EARTH_TILT_DEG.to_radians().sin();
+    let cos_declination = sin_declination.asin().cos();
+
+    // Hour angle between solar noon and sunrise/sunset
+    let lat = latitude.to_radians();
+    let cos_hour_angle = (SUNRISE_ALTITUDE_DEG.to_radians().sin() - lat.sin() * sin_declination)
+        / (lat.cos() * cos_declination);
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+
+    let sunrise = julian_to_utc_time(transit - hour_angle / 360.0)?;
+    let sunset = julian_to_utc_time(transit + hour_angle / 360.0)?;
+    Some((sunrise, sunset))
+}
+
+/// Time of day during polar day or night, when the sun doesn't rise or set.
+/// Summer months are treated as polar day, winter months as polar night.
+pub fn polar_time_of_day(latitude: f64, date: NaiveDate) -> TimeOfDay {
+    let northern_summer = (4..=9).contains(&date.month());
+    if northern_summer == (latitude >= 0.0) {
+        TimeOfDay::Day
+    } else {
+        TimeOfDay::Night
+    }
+}
+
+/// Convert a Julian day to a UTC time of day.
+fn julian_to_utc_time(julian_day: f64) -> Option<NaiveTime> {
+    let unix_secs = ((julian_day - UNIX_EPOCH_JD) * 86_400.0).round() as i64;
+    DateTime::from_timestamp(unix_secs, 0).map(|dt| dt.time())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    /// Assert two UTC times are within a few minutes (wrapping at midnight).
+    fn assert_close(actual: NaiveTime, expected: NaiveTime) {
+        let diff = (actual - expected).num_minutes().rem_euclid(24 * 60);
+        let diff = diff.min(24 * 60 - diff);
+        assert!(diff <= 3, "expected ~{expected}, got {actual}");
+    }
+
+    #[test]
+    fn test_london_summer_solstice() {
+        // 04:43 / 21:21 BST
+        let (rise, set) = sunrise_sunset(51.5074, -0.1278, date(2024, 6, 21)).unwrap();
+        assert_close(rise, hm(3, 43));
+        assert_close(set, hm(20, 21));
+    }
+
+    #[test]
+    fn test_seoul_winter_solstice() {
+        // 07:43 / 17:17 KST; sunrise is on the previous UTC day
+        let (rise, set) = sunrise_sunset(37.5665, 126.978, date(2024, 12, 21)).unwrap();
+        assert_close(rise, hm(22, 43));
+        assert_close(set, hm(8, 17));
+    }
+
+    #[test]
+    fn test_sydney_southern_summer() {
+        // 05:41 / 20:05 AEDT
+        let (rise, set) = sunrise_sunset(-33.8688, 151.2093, date(2024, 12, 21)).unwrap();
+        assert_close(rise, hm(18, 41));
+        assert_close(set, hm(9, 5));
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        // Tromsø has midnight sun in June and polar night in December
+        assert_eq!(sunrise_sunset(69.6492, 18.9553, date(2024, 6, 21)), None);
+        assert_eq!(sunrise_sunset(69.6492, 18.9553, date(2024, 12, 21)), None);
+
+        assert_eq!(polar_time_of_day(69.6, date(2024, 6, 21)), TimeOfDay::Day);
+        assert_eq!(
+            polar_time_of_day(69.6, date(2024, 12, 21)),
+            TimeOfDay::Night
+        );
+        // Seasons are flipped in the southern hemisphere
+        assert_eq!(
+            polar_time_of_day(-77.8, date(2024, 6, 21)),
+            TimeOfDay::Night
+        );
+        assert_eq!(polar_time_of_day(-77.8, date(2024, 12, 21)), TimeOfDay::Day);
+    }
+}
@@ -0,0 +1,201 @@
+//! Colors of the clock's parts: digits, date, help keys and help text.
+//!
+//! Each [`ColorTheme`] gives defaults, and the config can override any of
+//! them with a hex color.
+
+use std::fmt;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::ColorTheme;
+
+/// Colors of the clock's parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Big digits.
+    pub digit_color: Color,
+    /// Date and the other text lines under the digits.
+    pub date_color: Color,
+    /// Keys in the help line, and highlights in dialogs.
+    pub accent_color: Color,
+    /// Descriptions in the help line.
+    pub muted_color: Color,
+    /// Solid color behind everything, `None` to leave the terminal's own
+    /// background (and any transparency) showing.
+    pub background_color: Option<Color>,
+}
+
+impl Theme {
+    /// Default colors for `theme`, whose digits are drawn in `digit_color`.
+    ///
+    /// `digit_color` is the theme's color as resolved for the moment, e.g. the
+    /// Auto theme dimmed at night. Accents keep the theme's daytime color so the
+    /// help keys stay readable when the digits are dim.
+    pub fn new(theme: ColorTheme, digit_color: Color) -> Self {
+        Self {
+            digit_color,
+            date_color: digit_color,
+            accent_color: theme.color(),
+            muted_color: Color::DarkGray,
+            background_color: None,
+        }
+    }
+
+    /// Replace the colors set in `overrides`.
+    pub fn with_overrides(self, overrides: &ThemeOverrides) -> Self {
+        let pick = |color: Option<HexColor>, default| color.map_or(default, Color::from);
+        Self {
+            digit_color: pick(overrides.digit_color, self.digit_color),
+            date_color: pick(overrides.date_color, self.date_color),
+            accent_color: pick(overrides.accent_color, self.accent_color),
+            muted_color: pick(overrides.muted_color, self.muted_color),
+            background_color: overrides
+                .background_color
+                .map(Color::from)
+                .or(self.background_color),
+        }
+    }
+}
+
+/// Colors set in the config in place of the theme's defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeOverrides {
+    /// Big digits; replaces a gradient theme's gradient too.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digit_color: Option<HexColor>,
+    /// Date and the other text lines under the digits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_color: Option<HexColor>,
+    /// Keys in the help line, and highlights in dialogs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<HexColor>,
+    /// Descriptions in the help line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub muted_color: Option<HexColor>,
+    /// Solid background; unset leaves the terminal's own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<HexColor>,
+}
+
+/// An RGB color written as hex, e.g. "#ffaa00" or "#fa0".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HexColor {
+    /// Red.
+    pub r: u8,
+    /// Green.
+    pub g: u8,
+    /// Blue.
+    pub b: u8,
+}
+
+impl HexColor {
+    /// Parse "#rrggbb" or "#rgb", with or without the `#`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let hex = text.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        match hex.len() {
+            6 => Some(Self {
+                r: channel(&hex[0..2])?,
+                g: channel(&hex[2..4])?,
+                b: channel(&hex[4..6])?,
+            }),
+            // Each digit doubled: "#fa0" is "#ffaa00"
+            3 => Some(Self {
+                r: channel(&hex[0..1])? * 17,
+                g: channel(&hex[1..2])? * 17,
+                b: channel(&hex[2..3])? * 17,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HexColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Self::parse(&text)
+            .ok_or_else(|| format!("invalid hex color {text:?}, expected \"#rrggbb\""))
+    }
+}
+
+impl From<HexColor> for String {
+    fn from(color: HexColor) -> Self {
+        color.to_string()
+    }
+}
+
+impl From<HexColor> for Color {
+    fn from(color: HexColor) -> Self {
+        Color::Rgb(color.r, color.g, color.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        let orange = HexColor {
+            r: 0xff,
+            g: 0xaa,
+            b: 0x00,
+        };
+        assert_eq!(HexColor::parse("#ffaa00"), Some(orange));
+        assert_eq!(HexColor::parse("FFAA00"), Some(orange));
+        assert_eq!(HexColor::parse(" #fa0 "), Some(orange));
+        assert_eq!(orange.to_string(), "#ffaa00");
+        for invalid in ["", "#", "#ffaa0", "#ffaa000", "#gg0000", "orange", "#ffé0"] {
+            assert_eq!(HexColor::parse(invalid), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_partial_overrides() {
+        let date = HexColor::parse("#808080").unwrap();
+        let overrides = ThemeOverrides {
+            date_color: Some(date),
+            ..ThemeOverrides::default()
+        };
+        let theme = Theme::new(ColorTheme::Green, Color::Green).with_overrides(&overrides);
+        assert_eq!(theme.date_color, Color::Rgb(0x80, 0x80, 0x80));
+        // Everything else keeps the theme's defaults
+        assert_eq!(theme.digit_color, Color::Green);
+        assert_eq!(theme.accent_color, Color::Green);
+        assert_eq!(theme.muted_color, Color::DarkGray);
+        assert_eq!(theme.background_color, None);
+    }
+
+    #[test]
+    fn test_every_theme_sets_digits_apart_from_muted_text() {
+        let mut theme = ColorTheme::default();
+        loop {
+            let colors = Theme::new(theme, theme.color());
+            assert_ne!(colors.digit_color, colors.muted_color, "{theme:?}");
+            assert_ne!(colors.accent_color, colors.muted_color, "{theme:?}");
+            theme = theme.next();
+            if theme == ColorTheme::default() {
+                break;
+            }
+        }
+
+        // The Auto theme at night: dim digits, readable accents
+        let night = Theme::new(ColorTheme::Auto, Color::Rgb(40, 50, 90));
+        assert_ne!(night.digit_color, night.muted_color);
+        assert_eq!(night.accent_color, ColorTheme::Auto.color());
+    }
+}
@@ -0,0 +1,85 @@
+//! Text art with the default bundled font, for labels and banners outside the clock.
+
+use std::sync::OnceLock;
+
+use crate::bundled::STANDARD_FLF;
+use crate::font::Font;
+use crate::parser::parse_flf;
+
+/// Name of the default bundled font.
+pub const DEFAULT_FONT_NAME: &str = "Standard";
+
+/// Get the default bundled font, parsed on first use.
+pub fn default_font() -> &'static Font {
+    static FONT: OnceLock<Font> = OnceLock::new();
+    FONT.get_or_init(|| {
+        parse_flf(DEFAULT_FONT_NAME, STANDARD_FLF).expect("Standard font should always parse")
+    })
+}
+
+/// Render text as art with the default font, one string per row.
+///
+/// Letters, digits, punctuation and spaces all have glyphs; any other
+/// character renders as a blank cell of the standard width. Every row has
+/// the same display width.
+pub fn build_text_art(text: &str) -> Vec<String> {
+    default_font().render_text(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+    use crate::bundled::BUNDLED_FONTS;
+
+    /// Mixed strings covering letters, digits, punctuation and unknown characters.
+    const SAMPLES: &[&str] = &[
+        "12:34:56 PM",
+        "Mon 2026/10/17",
+        "MWiIl.-/ ",
+        "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG",
+        "the quick brown fox jumps over the lazy dog",
+        "Seoul · 23°C ☀",
+    ];
+
+    fn assert_rows_line_up(font: &Font, text: &str) {
+        let rows = font.render_text(text);
+        assert_eq!(rows.len(), font.height, "{} {text:?}", font.name);
+        for row in &rows {
+            assert_eq!(row.width(), font.text_width(text), "{} {text:?}", font.name);
+        }
+    }
+
+    #[test]
+    fn test_rows_have_equal_width_in_all_fonts() {
+        for (name, content) in BUNDLED_FONTS {
+            let font = parse_flf(name, content).unwrap();
+            for text in SAMPLES {
+                assert_rows_line_up(&font, text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_text_art_covers_letters() {
+        let font = default_font();
+        for ch in ('A'..='Z').chain('a'..='z').chain(" .-/".chars()) {
+            assert!(font.chars.contains_key(&ch), "{ch}");
+        }
+        assert_eq!(build_text_art("HELLO"), font.render_text("HELLO"));
+        // Proportional glyphs keep their own width
+        assert!(font.char_width('M') > font.char_width('I'));
+    }
+
+    #[test]
+    fn test_unknown_characters_render_blank() {
+        let font = default_font();
+        let rows = build_text_art("☀");
+        assert_eq!(rows.len(), font.height);
+        for row in rows {
+            assert_eq!(row, " ".repeat(font.blank_width()));
+        }
+        assert_eq!(font.char_width('☀'), font.char_width('0'));
+    }
+}
@@ -0,0 +1,182 @@
+//! Custom fill characters painted over the ink of clock art.
+
+use unicode_width::UnicodeWidthChar;
+
+/// Characters painted over every non-blank cell of art, e.g. `@` or `▓░`,
+/// and optionally one drawn in its blank cells.
+///
+/// A pattern of several characters repeats across each row by display
+/// column, so the same character lines up in every row. The shape of the
+/// glyphs is kept, but strokes like `/` and `_` all become the fill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fill {
+    /// Characters of the pattern, each one cell wide.
+    pattern: Vec<char>,
+    /// Character of the blank cells, a space unless set with [`Fill::with_blank`].
+    blank: char,
+}
+
+impl Fill {
+    /// Parse a fill pattern of one or more single-width, non-blank characters.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        if pattern.is_empty() {
+            return Err("fill pattern is empty".to_string());
+        }
+        if let Some(ch) = pattern
+            .iter()
+            .find(|ch| ch.is_whitespace() || ch.width() != Some(1))
+        {
+            return Err(format!(
+                "fill character {ch:?} is not a visible single-width character"
+            ));
+        }
+        Ok(Self {
+            pattern,
+            blank: ' ',
+        })
+    }
+
+    /// Draw `blank`, a single single-width character, in the blank cells
+    /// instead of leaving them empty; an empty string keeps them empty.
+    pub fn with_blank(self, blank: &str) -> Result<Self, String> {
+        let mut chars = blank.chars();
+        let blank = match (chars.next(), chars.next()) {
+            (None, _) => ' ',
+            (Some(ch), None) if ch.width() == Some(1) => ch,
+            _ => {
+                return Err(format!(
+                    "blank {blank:?} is not a single single-width character"
+                ));
+            }
+        };
+        Ok(Self { blank, ..self })
+    }
+
+    /// Paint the pattern over every non-blank cell of `lines`, and the blank
+    /// character over the blank ones. A wide character becomes a fill
+    /// character for each column it covers, so rows keep their width.
+    pub fn apply(&self, lines: &[String]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                let mut column = 0;
+                line.chars()
+                    .flat_map(|ch| {
+                        let start = column;
+                        let width = ch.width().unwrap_or(0);
+                        column += width;
+                        (start..start + width).map(move |x| {
+                            if ch == ' ' {
+                                self.blank
+                            } else {
+                                self.pattern[x % self.pattern.len()]
+                            }
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sigye_core::AmPmStyle;
+
+    use super::*;
+    use crate::bundled::ANSI_REGULAR_FLF;
+    use crate::parser::parse_flf;
+    use crate::{Spacing, build_time_art};
+
+    #[test]
+    fn test_parse() {
+        assert!(Fill::parse("@").is_ok());
+        assert!(Fill::parse("▓░").is_ok());
+        assert!(Fill::parse("").is_err());
+        assert!(Fill::parse("# ").is_err());
+        // Wide characters would stretch the rows
+        assert!(Fill::parse("時").is_err());
+    }
+
+    #[test]
+    fn test_parse_blank() {
+        let fill = Fill::parse("@").unwrap();
+        assert_eq!(fill.clone().with_blank("").unwrap(), fill);
+        assert!(fill.clone().with_blank("·").is_ok());
+        assert!(fill.clone().with_blank("..").is_err());
+        assert!(fill.with_blank("時").is_err());
+    }
+
+    #[test]
+    fn test_block_fill_keeps_block_fonts_unchanged() {
+        // Golden check: ANSI Regular draws its digits in full blocks already
+        let font = parse_flf("ANSI Regular", ANSI_REGULAR_FLF).unwrap();
+        let block = Fill::parse("█").unwrap();
+        let art = |fill| {
+            build_time_art(
+                &font,
+                "12:34",
+                Some("PM"),
+                AmPmStyle::Large,
+                false,
+                Spacing::default(),
+                fill,
+            )
+        };
+        assert_eq!(art(Some(&block)), art(None));
+    }
+
+    #[test]
+    fn test_fill_keeps_the_shape() {
+        let art = ["/_ |".to_string(), " \\| ".to_string()];
+        assert_eq!(Fill::parse("#").unwrap().apply(&art), ["## #", " ## "]);
+        // Patterns repeat by column, lining up across rows
+        assert_eq!(Fill::parse("ab").unwrap().apply(&art), ["ab b", " ba "]);
+        // Blank cells get the blank character
+        let dotted = Fill::parse("#").unwrap().with_blank("·").unwrap();
+        assert_eq!(dotted.apply(&art), ["##·#", "·##·"]);
+    }
+
+    #[test]
+    fn test_wide_characters_fill_every_column() {
+        let art = ["時a".to_string(), "abc".to_string()];
+        assert_eq!(Fill::parse("xyz").unwrap().apply(&art), ["xyz", "xyz"]);
+    }
+
+    #[test]
+    fn test_fill_leaves_the_marker_alone() {
+        let font = crate::default_font();
+        let fill = Fill::parse("@").unwrap().with_blank(".").unwrap();
+        for style in [AmPmStyle::Large, AmPmStyle::SmallText] {
+            let art = |fill| {
+                build_time_art(
+                    font,
+                    "12:34",
+                    Some("PM"),
+                    style,
+                    false,
+                    Spacing::default(),
+                    fill,
+                )
+            };
+            let (plain, filled) = (art(None), art(Some(&fill)));
+            let digits = build_time_art(
+                font,
+                "12:34",
+                None,
+                style,
+                false,
+                Spacing::default(),
+                Some(&fill),
+            );
+            for ((plain, filled), digits) in plain.iter().zip(&filled).zip(&digits) {
+                // The digits are filled and the marker after them is untouched
+                assert!(filled.starts_with(digits.as_str()), "{style:?}");
+                let plain_marker: String = plain.chars().skip(digits.chars().count()).collect();
+                let filled_marker: String = filled.chars().skip(digits.chars().count()).collect();
+                assert_eq!(filled_marker, plain_marker, "{style:?}");
+            }
+        }
+    }
+}
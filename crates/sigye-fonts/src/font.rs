@@ -11,28 +11,28 @@ pub struct Font {
     pub height: usize,
     /// Character definitions.
     pub chars: HashMap<char, Vec<String>>,
+    /// Display width of each character, shared by all of its rows.
+    pub widths: HashMap<char, usize>,
 }
 
 impl Font {
     /// Render text using this font.
     ///
-    /// Returns a vector of strings, one for each line of the output.
+    /// Returns a vector of strings, one for each line of the output. Every
+    /// line has the same display width; characters missing from the font
+    /// render as a blank cell of [`Font::blank_width`].
     pub fn render_text(&self, text: &str) -> Vec<String> {
         let mut lines: Vec<String> = vec![String::new(); self.height];
 
         for ch in text.chars() {
             if let Some(char_lines) = self.chars.get(&ch) {
-                for (i, char_line) in char_lines.iter().enumerate() {
-                    if i < lines.len() {
-                        lines[i].push_str(char_line);
-                    }
+                for (line, char_line) in lines.iter_mut().zip(char_lines) {
+                    line.push_str(char_line);
                 }
-            } else if let Some(space_lines) = self.chars.get(&' ') {
-                // Use space for unknown characters
-                for (i, space_line) in space_lines.iter().enumerate() {
-                    if i < lines.len() {
-                        lines[i].push_str(space_line);
-                    }
+            } else {
+                // Use a blank cell for unknown characters
+                for line in &mut lines {
+                    line.extend(std::iter::repeat_n(' ', self.blank_width()));
                 }
             }
         }
@@ -40,12 +40,21 @@ impl Font {
         lines
     }
 
-    /// Get the width of a character.
+    /// Get the display width of a character, or [`Font::blank_width`] if it is missing.
     pub fn char_width(&self, ch: char) -> usize {
-        self.chars
+        self.widths
             .get(&ch)
-            .and_then(|lines| lines.first())
-            .map(|line| line.chars().count())
-            .unwrap_or(0)
+            .copied()
+            .unwrap_or_else(|| self.blank_width())
+    }
+
+    /// Get the width of the blank cell drawn for unknown characters (the width of a digit).
+    pub fn blank_width(&self) -> usize {
+        self.widths.get(&'0').copied().unwrap_or(0)
+    }
+
+    /// Get the display width of text rendered with this font.
+    pub fn text_width(&self, text: &str) -> usize {
+        text.chars().map(|ch| self.char_width(ch)).sum()
     }
 }
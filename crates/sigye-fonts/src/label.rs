@@ -0,0 +1,246 @@
+//! Compact letter art for labels such as clock titles.
+//!
+//! Glyphs are 5 pixels tall and drawn with half blocks, two pixels per row,
+//! so a label takes [`LABEL_HEIGHT`] rows instead of a full font's 6 or more.
+//! Letters are shown as capitals.
+
+use unicode_width::UnicodeWidthChar;
+
+/// Rows of label art.
+pub const LABEL_HEIGHT: usize = 3;
+
+/// Ellipsis appended to cut-off labels.
+const ELLIPSIS: char = '…';
+
+/// Pixel rows of each glyph, `#` for set pixels.
+const GLYPHS: &[(char, [&str; 5])] = &[
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#"]),
+    ('N', ["#..#", "##.#", "#.##", "#..#", "#..#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#...#", "#...#", "#.#.#", "##.##", "#...#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", ".#.", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "##."]),
+    (' ', ["..", "..", "..", "..", ".."]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('.', [".", ".", ".", ".", "#"]),
+    (',', [".", ".", ".", "#", "#"]),
+    ('\'', ["#", "#", ".", ".", "."]),
+    (':', [".", "#", ".", "#", "."]),
+    ('/', ["..#", "..#", ".#.", "#..", "#.."]),
+    ('?', ["##.", "..#", ".#.", "...", ".#."]),
+    (ELLIPSIS, [".....", ".....", ".....", ".....", "#.#.#"]),
+];
+
+/// Pixel rows of a character's glyph, `?` for characters without one.
+fn glyph(ch: char) -> &'static [&'static str; 5] {
+    let find = |ch| GLYPHS.iter().find(|(c, _)| *c == ch).map(|(_, rows)| rows);
+    find(ch).or_else(|| find('?')).expect("? has a glyph")
+}
+
+/// Width of a glyph in cells.
+fn glyph_width(ch: char) -> usize {
+    glyph(ch)[0].len()
+}
+
+/// Total width of glyphs laid out with a blank column between them.
+fn art_width(chars: &[char]) -> usize {
+    let glyphs: usize = chars.iter().map(|&ch| glyph_width(ch)).sum();
+    glyphs + chars.len().saturating_sub(1)
+}
+
+/// Draw glyphs side by side, one blank column apart.
+fn draw(chars: &[char]) -> Vec<String> {
+    let mut rows = vec![String::new(); LABEL_HEIGHT];
+    for (i, &ch) in chars.iter().enumerate() {
+        let pixels = glyph(ch);
+        for (row_idx, row) in rows.iter_mut().enumerate() {
+            if i > 0 {
+                row.push(' ');
+            }
+            let top = pixels[row_idx * 2].chars();
+            let bottom = pixels.get(row_idx * 2 + 1).map(|row| row.chars());
+            let bottom = bottom
+                .into_iter()
+                .flatten()
+                .map(Some)
+                .chain(std::iter::repeat(None));
+            for (top, bottom) in top.zip(bottom) {
+                row.push(match (top == '#', bottom == Some('#')) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+        }
+    }
+    rows
+}
+
+/// Render a label in compact letters, at most `max_width` cells wide.
+///
+/// Returns [`LABEL_HEIGHT`] rows of equal width. A label that is too wide is
+/// cut after a whole character and ends in an ellipsis. Returns `None` when
+/// not even one character and the ellipsis fit; use [`truncate_label`] then.
+pub fn build_label_art(text: &str, max_width: usize) -> Option<Vec<String>> {
+    let chars: Vec<char> = text.trim().chars().flat_map(char::to_uppercase).collect();
+    if chars.is_empty() {
+        return None;
+    }
+    if art_width(&chars) <= max_width {
+        return Some(draw(&chars));
+    }
+
+    let mut kept = chars;
+    kept.pop();
+    while !kept.is_empty() {
+        let trimmed = kept.len() - kept.iter().rev().take_while(|ch| **ch == ' ').count();
+        kept.truncate(trimmed);
+        let mut cut = kept.clone();
+        cut.push(ELLIPSIS);
+        if !kept.is_empty() && art_width(&cut) <= max_width {
+            return Some(draw(&cut));
+        }
+        kept.pop();
+    }
+    None
+}
+
+/// Plain-text label at most `max_width` cells wide, ending in an ellipsis if cut.
+///
+/// Cuts between characters, so multi-byte and double-width characters are never split.
+pub fn truncate_label(text: &str, max_width: usize) -> String {
+    let text = text.trim();
+    let width = |ch: char| ch.width().unwrap_or(0);
+    if text.chars().map(width).sum::<usize>() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let room = max_width - width(ELLIPSIS);
+    let mut used = 0;
+    let mut label: String = text
+        .chars()
+        .take_while(|&ch| {
+            used += width(ch);
+            used <= room
+        })
+        .collect();
+    label.truncate(label.trim_end().len());
+    label.push(ELLIPSIS);
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+
+    fn assert_rows_line_up(rows: &[String]) {
+        assert_eq!(rows.len(), LABEL_HEIGHT);
+        let width = rows[0].width();
+        for row in rows {
+            assert_eq!(row.width(), width, "{rows:?}");
+        }
+    }
+
+    #[test]
+    fn test_glyphs_are_well_formed() {
+        for (ch, rows) in GLYPHS {
+            let width = rows[0].len();
+            assert!(rows.iter().all(|row| row.len() == width), "{ch:?}");
+            assert!(
+                rows.iter()
+                    .flat_map(|row| row.chars())
+                    .all(|c| c == '#' || c == '.'),
+                "{ch:?}"
+            );
+        }
+        for ch in ('A'..='Z').chain('0'..='9') {
+            assert!(GLYPHS.iter().any(|(c, _)| *c == ch), "{ch:?} has no glyph");
+        }
+    }
+
+    #[test]
+    fn test_label_art() {
+        let rows = build_label_art("Hi 1", 80).unwrap();
+        assert_eq!(rows, ["█ █ ▀█▀    ▄█ ", "█▀█  █      █ ", "▀ ▀ ▀▀▀    ▀▀▀"]);
+        for text in ["NEW YORK", "São Paulo", "Tokyo 2:30", "서울", "l'été"] {
+            assert_rows_line_up(&build_label_art(text, 200).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_label_art_truncates_with_ellipsis() {
+        let full = build_label_art("NEW YORK", 200).unwrap();
+        let width = full[0].width();
+        assert_eq!(build_label_art("NEW YORK", width), Some(full));
+
+        let rows = build_label_art("NEW YORK", width - 1).unwrap();
+        assert_rows_line_up(&rows);
+        assert!(rows[0].width() < width);
+        assert_eq!(rows, draw(&['N', 'E', 'W', ' ', 'Y', 'O', ELLIPSIS]));
+
+        // The space before a cut is dropped
+        let rows = build_label_art("NEW YORK", art_width(&['N', 'E', 'W', ' ', 'Y'])).unwrap();
+        assert_eq!(rows, draw(&['N', 'E', 'W', ELLIPSIS]));
+
+        // Non-ASCII characters are cut whole
+        let rows = build_label_art("ÉTÉ ÀÀÀ", art_width(&['É', 'T', 'É', ELLIPSIS])).unwrap();
+        assert_eq!(rows, draw(&['É', 'T', 'É', ELLIPSIS]));
+    }
+
+    #[test]
+    fn test_label_art_that_cannot_fit() {
+        assert_eq!(build_label_art("NEW YORK", 4), None);
+        assert_eq!(build_label_art("", 80), None);
+        assert_eq!(build_label_art("   ", 80), None);
+    }
+
+    #[test]
+    fn test_truncate_label() {
+        assert_eq!(truncate_label("New York", 8), "New York");
+        assert_eq!(truncate_label("New York", 7), "New Yo…");
+        assert_eq!(truncate_label("New York", 5), "New…");
+        assert_eq!(truncate_label("New York", 1), "…");
+        assert_eq!(truncate_label("New York", 0), "");
+        // Multi-byte and double-width characters are kept whole
+        assert_eq!(truncate_label("São Paulo", 3), "Sã…");
+        assert_eq!(truncate_label("서울특별시", 6), "서울…");
+        assert_eq!(truncate_label("서울특별시", 5), "서울…");
+        assert!(truncate_label("서울특별시", 4).width() <= 4);
+    }
+}
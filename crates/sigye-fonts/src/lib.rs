@@ -1,15 +1,31 @@
 //! ASCII art fonts for the sigye clock application.
 //!
 //! This crate provides FIGlet font parsing and rendering for the terminal clock.
+//! [`build_text_art`] renders any text with the default font, for labels and
+//! banners outside the clock; [`build_label_art`] draws compact 3-row letters
+//! for titles where a full font is too tall. [`build_time_art`] draws the clock
+//! digits with the AM/PM marker in a chosen [`sigye_core::AmPmStyle`], spread
+//! out by a [`Spacing`] and optionally repainted with a [`Fill`]; [`scale_art`]
+//! blows art up for large terminals.
 
+mod art;
 mod bundled;
+mod fill;
 mod font;
+mod label;
 mod parser;
 mod registry;
+mod scale;
+mod time;
 
+pub use art::{DEFAULT_FONT_NAME, build_text_art, default_font};
+pub use fill::Fill;
 pub use font::Font;
+pub use label::{LABEL_HEIGHT, build_label_art, truncate_label};
 pub use parser::{ParseError, parse_flf};
 pub use registry::FontRegistry;
+pub use scale::{fit_scale, scale_art};
+pub use time::{Spacing, build_time_art, clock_chars, glyph_offsets, time_art_width};
 
 // Re-export bundled font constants for direct access
 pub use bundled::BUNDLED_FONTS;
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use unicode_width::UnicodeWidthStr;
+
 use crate::font::Font;
 
 /// Supported font format signatures.
@@ -54,17 +56,20 @@ pub fn parse_flf(name: &str, content: &str) -> Result<Font, ParseError> {
 
     // Parse characters
     let mut chars: HashMap<char, Vec<String>> = HashMap::new();
+    let mut widths: HashMap<char, usize> = HashMap::new();
 
     // Standard ASCII characters start at 32 (space) and go to 126 (~)
     for ascii_code in 32u8..=126 {
-        let char_lines = parse_character(&mut lines, header.height, header.hardblank)?;
+        let (char_lines, width) = parse_character(&mut lines, header.height, header.hardblank)?;
         chars.insert(ascii_code as char, char_lines);
+        widths.insert(ascii_code as char, width);
     }
 
     Ok(Font {
         name: name.to_string(),
         height: header.height,
         chars,
+        widths,
     })
 }
 
@@ -121,11 +126,13 @@ fn parse_header(line: &str) -> Result<FlfHeader, ParseError> {
 }
 
 /// Parse a single character from the FLF file.
+///
+/// Rows are padded to the same display width, which is returned alongside them.
 fn parse_character<'a>(
     lines: &mut impl Iterator<Item = &'a str>,
     height: usize,
     hardblank: char,
-) -> Result<Vec<String>, ParseError> {
+) -> Result<(Vec<String>, usize), ParseError> {
     let mut char_lines = Vec::with_capacity(height);
 
     for i in 0..height {
@@ -147,7 +154,18 @@ fn parse_character<'a>(
         char_lines.push(final_line);
     }
 
-    Ok(char_lines)
+    // Some fonts have ragged rows; pad them so glyphs line up when joined
+    let width = char_lines
+        .iter()
+        .map(|line| line.width())
+        .max()
+        .unwrap_or(0);
+    for line in &mut char_lines {
+        let padding = width - line.width();
+        line.extend(std::iter::repeat_n(' ', padding));
+    }
+
+    Ok((char_lines, width))
 }
 
 #[cfg(test)]
@@ -169,4 +187,12 @@ mod tests {
         assert_eq!(header.height, 8);
         assert_eq!(header.comment_lines, 4);
     }
+
+    #[test]
+    fn test_parse_character_pads_ragged_rows() {
+        let mut lines = ["|\\/|@", "|$|@", "|  |@@"].into_iter();
+        let (char_lines, width) = parse_character(&mut lines, 3, '$').unwrap();
+        assert_eq!(width, 4);
+        assert_eq!(char_lines, ["|\\/|", "| | ", "|  |"]);
+    }
 }
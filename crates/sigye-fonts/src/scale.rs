@@ -0,0 +1,89 @@
+//! Integer scaling of rendered art, for big clocks on big terminals.
+
+/// Scale art rows by `factor` in both directions.
+///
+/// Every character is repeated `factor` times across and every row `factor`
+/// times down, so each cell becomes a `factor` x `factor` block. A factor of
+/// zero is treated as one.
+pub fn scale_art(lines: &[String], factor: usize) -> Vec<String> {
+    let factor = factor.max(1);
+    lines
+        .iter()
+        .flat_map(|line| {
+            let wide: String = line
+                .chars()
+                .flat_map(|ch| std::iter::repeat_n(ch, factor))
+                .collect();
+            std::iter::repeat_n(wide, factor)
+        })
+        .collect()
+}
+
+/// Largest scale, up to `max`, at which art of `width` x `height` cells still
+/// fits in `room_width` x `room_height`; at least one.
+pub fn fit_scale(
+    width: usize,
+    height: usize,
+    room_width: usize,
+    room_height: usize,
+    max: usize,
+) -> usize {
+    let across = room_width.checked_div(width).unwrap_or(max);
+    let down = room_height.checked_div(height).unwrap_or(max);
+    across.min(down).min(max).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+    use crate::{Spacing, build_time_art};
+
+    #[test]
+    fn test_scale_by_two_doubles_both_sides() {
+        let font = crate::default_font();
+        for style in [
+            sigye_core::AmPmStyle::Large,
+            sigye_core::AmPmStyle::SmallText,
+        ] {
+            let art = build_time_art(
+                font,
+                "12:34",
+                Some("PM"),
+                style,
+                false,
+                Spacing::default(),
+                None,
+            );
+            let scaled = scale_art(&art, 2);
+            assert_eq!(scaled.len(), art.len() * 2);
+            for row in &scaled {
+                assert_eq!(row.width(), art[0].width() * 2, "{style:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_makes_blocks() {
+        let art = ["ab".to_string(), "c ".to_string()];
+        assert_eq!(
+            scale_art(&art, 3),
+            ["aaabbb", "aaabbb", "aaabbb", "ccc   ", "ccc   ", "ccc   "]
+        );
+        assert_eq!(scale_art(&art, 1), art);
+        assert_eq!(scale_art(&art, 0), art);
+    }
+
+    #[test]
+    fn test_fit_scale() {
+        // 40 x 7 art in a 170 x 30 room fits four times across and down
+        assert_eq!(fit_scale(40, 7, 170, 30, 8), 4);
+        assert_eq!(fit_scale(40, 7, 170, 30, 3), 3);
+        // Height limits first
+        assert_eq!(fit_scale(40, 7, 400, 20, 8), 2);
+        // Never below one, even when nothing fits
+        assert_eq!(fit_scale(40, 7, 10, 3, 8), 1);
+        assert_eq!(fit_scale(0, 0, 10, 3, 8), 8);
+    }
+}
@@ -0,0 +1,600 @@
+//! Clock time art with the AM/PM marker in a chosen style.
+
+use sigye_core::AmPmStyle;
+
+use crate::fill::Fill;
+use crate::font::Font;
+
+/// Stand-in for a suppressed leading hour zero. Fonts have no glyph for it,
+/// so it renders as a blank cell as wide as a digit.
+const FIGURE_SPACE: char = '\u{2007}';
+
+/// Blank columns between the letters of a large AM/PM marker, so "AM" reads as
+/// two glyphs rather than one.
+const MARKER_LETTER_GAP: usize = 1;
+
+/// Blank columns added between the glyphs of the clock.
+///
+/// The default draws the glyphs side by side as the font spaces them, with one
+/// space before the AM/PM marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spacing {
+    /// Columns between two digits.
+    pub digit_gap: usize,
+    /// Columns on each side of a colon.
+    pub colon_gap: usize,
+    /// Spaces before the AM/PM marker; in [`AmPmStyle::Large`] these are the
+    /// font's space glyph.
+    pub am_pm_gap: usize,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            digit_gap: 0,
+            colon_gap: 0,
+            am_pm_gap: 1,
+        }
+    }
+}
+
+impl Spacing {
+    /// Largest digit and colon gap reachable with [`Spacing::wider`].
+    pub const MAX_GAP: usize = 4;
+
+    /// Widen the digit and colon gaps by a column, up to [`Spacing::MAX_GAP`].
+    pub fn wider(self) -> Self {
+        Self {
+            // Gaps set wider than the maximum in the config are kept
+            digit_gap: (self.digit_gap + 1).min(Self::MAX_GAP.max(self.digit_gap)),
+            colon_gap: (self.colon_gap + 1).min(Self::MAX_GAP.max(self.colon_gap)),
+            ..self
+        }
+    }
+
+    /// Narrow the digit and colon gaps by a column, down to none.
+    pub fn narrower(self) -> Self {
+        Self {
+            digit_gap: self.digit_gap.saturating_sub(1),
+            colon_gap: self.colon_gap.saturating_sub(1),
+            ..self
+        }
+    }
+
+    /// Columns between two neighboring clock characters.
+    fn gap(&self, left: char, right: char) -> usize {
+        if left == ':' || right == ':' {
+            self.colon_gap
+        } else {
+            self.digit_gap
+        }
+    }
+}
+
+/// Column where each character of `chars` starts, with the gaps of `spacing`.
+///
+/// `chars` are the characters actually drawn, as returned by [`clock_chars`].
+pub fn glyph_offsets(font: &Font, chars: &str, spacing: Spacing) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut x = 0;
+    let mut previous = None;
+    for ch in chars.chars() {
+        if let Some(previous) = previous {
+            x += font.char_width(previous) + spacing.gap(previous, ch);
+        }
+        offsets.push(x);
+        previous = Some(ch);
+    }
+    offsets
+}
+
+/// Display width of the art [`build_time_art`] draws for the same arguments.
+pub fn time_art_width(
+    font: &Font,
+    digits: &str,
+    meridiem: Option<&str>,
+    style: AmPmStyle,
+    pad_hour: bool,
+    spacing: Spacing,
+) -> usize {
+    let chars = clock_chars(digits, pad_hour);
+    let digits_width = match (
+        glyph_offsets(font, &chars, spacing).last(),
+        chars.chars().last(),
+    ) {
+        (Some(offset), Some(last)) => offset + font.char_width(last),
+        _ => 0,
+    };
+    let meridiem_width = match (meridiem, style) {
+        (None, _) | (_, AmPmStyle::Hidden) => 0,
+        (Some(meridiem), AmPmStyle::Large) => {
+            let letter_gaps = meridiem.chars().count().saturating_sub(1) * MARKER_LETTER_GAP;
+            spacing.am_pm_gap * font.char_width(' ') + font.text_width(meridiem) + letter_gaps
+        }
+        (Some(meridiem), AmPmStyle::SmallText) => spacing.am_pm_gap + meridiem.chars().count(),
+    };
+    digits_width + meridiem_width
+}
+
+/// Characters to draw for clock digits such as " 7:30:05".
+///
+/// A blank hour tens digit becomes `0` with `pad_hour`, and otherwise a blank
+/// cell as wide as a digit, so the time keeps its width as the hour changes.
+/// Times whose hour already starts with a digit, like 24-hour times, are kept.
+pub fn clock_chars(digits: &str, pad_hour: bool) -> String {
+    match digits.strip_prefix(' ') {
+        Some(rest) if pad_hour => format!("0{rest}"),
+        Some(rest) => format!("{FIGURE_SPACE}{rest}"),
+        None => digits.to_string(),
+    }
+}
+
+/// Render clock digits with their AM/PM marker, one string per row.
+///
+/// `meridiem` is "AM" or "PM" in 12-hour format and `None` otherwise. With
+/// [`AmPmStyle::Large`] the marker is drawn in the font after `am_pm_gap`
+/// space glyphs, its letters a blank column apart; with
+/// [`AmPmStyle::SmallText`] the marker is plain text after the bottom row of the
+/// digits and the other rows are padded with spaces, so every row keeps the
+/// same width; [`AmPmStyle::Hidden`] draws the digits alone. A blank leading
+/// hour digit is drawn as described for [`clock_chars`], and the glyphs are
+/// spread out by `spacing`. A `fill` repaints the digits and colons only,
+/// before the marker is added, so the marker stays readable.
+pub fn build_time_art(
+    font: &Font,
+    digits: &str,
+    meridiem: Option<&str>,
+    style: AmPmStyle,
+    pad_hour: bool,
+    spacing: Spacing,
+    fill: Option<&Fill>,
+) -> Vec<String> {
+    let mut rows = render_spaced(font, &clock_chars(digits, pad_hour), spacing);
+    // Found before filling, which may put ink in the blank descender rows
+    let baseline = baseline_row(&rows);
+    if let Some(fill) = fill {
+        rows = fill.apply(&rows);
+    }
+    let Some(meridiem) = meridiem else {
+        return rows;
+    };
+    match style {
+        AmPmStyle::Large => {
+            let gap = font.render_text(&" ".repeat(spacing.am_pm_gap));
+            let marker = render_marker(font, meridiem);
+            for ((row, gap), marker) in rows.iter_mut().zip(gap).zip(marker) {
+                row.push_str(&gap);
+                row.push_str(&marker);
+            }
+            rows
+        }
+        AmPmStyle::Hidden => rows,
+        AmPmStyle::SmallText => {
+            let suffix = format!("{}{meridiem}", " ".repeat(spacing.am_pm_gap));
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i == baseline {
+                    row.push_str(&suffix);
+                } else {
+                    row.extend(std::iter::repeat_n(' ', suffix.chars().count()));
+                }
+            }
+            rows
+        }
+    }
+}
+
+/// Render clock characters with blank columns between them as `spacing` says.
+fn render_spaced(font: &Font, chars: &str, spacing: Spacing) -> Vec<String> {
+    let mut rows = vec![String::new(); font.height];
+    let mut previous = None;
+    for ch in chars.chars() {
+        if let Some(previous) = previous {
+            let gap = spacing.gap(previous, ch);
+            for row in &mut rows {
+                row.extend(std::iter::repeat_n(' ', gap));
+            }
+        }
+        for (row, glyph) in rows.iter_mut().zip(font.render_text(&ch.to_string())) {
+            row.push_str(&glyph);
+        }
+        previous = Some(ch);
+    }
+    rows
+}
+
+/// Render the letters of a large AM/PM marker [`MARKER_LETTER_GAP`] columns apart.
+fn render_marker(font: &Font, meridiem: &str) -> Vec<String> {
+    let mut rows = vec![String::new(); font.height];
+    for (i, letter) in meridiem.chars().enumerate() {
+        for (row, glyph) in rows.iter_mut().zip(font.render_text(&letter.to_string())) {
+            if i > 0 {
+                row.extend(std::iter::repeat_n(' ', MARKER_LETTER_GAP));
+            }
+            row.push_str(&glyph);
+        }
+    }
+    rows
+}
+
+/// Index of the bottom row of the digits; many fonts end in blank descender rows.
+fn baseline_row(rows: &[String]) -> usize {
+    rows.iter()
+        .rposition(|row| !row.trim().is_empty())
+        .unwrap_or(rows.len().saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+    use crate::bundled::BUNDLED_FONTS;
+    use crate::parser::parse_flf;
+
+    const STYLES: [AmPmStyle; 3] = [AmPmStyle::Large, AmPmStyle::SmallText, AmPmStyle::Hidden];
+
+    /// Digit, colon and AM/PM gaps, including none at all.
+    const SPACINGS: [(usize, usize, usize); 5] =
+        [(0, 0, 1), (0, 0, 0), (1, 0, 2), (0, 2, 1), (3, 3, 3)];
+
+    /// `text` rendered by the font, then the letters of `meridiem` a blank
+    /// column apart, as the default spacing draws a 12-hour time.
+    fn with_marker(font: &Font, text: &str, meridiem: &str) -> Vec<String> {
+        let mut rows = font.render_text(text);
+        for (i, letter) in meridiem.chars().enumerate() {
+            for (row, glyph) in rows.iter_mut().zip(font.render_text(&letter.to_string())) {
+                if i > 0 {
+                    row.push(' ');
+                }
+                row.push_str(&glyph);
+            }
+        }
+        rows
+    }
+
+    fn spacing((digit_gap, colon_gap, am_pm_gap): (usize, usize, usize)) -> Spacing {
+        Spacing {
+            digit_gap,
+            colon_gap,
+            am_pm_gap,
+        }
+    }
+
+    #[test]
+    fn test_rows_have_equal_width_in_every_style() {
+        for (name, content) in BUNDLED_FONTS {
+            let font = parse_flf(name, content).unwrap();
+            for style in STYLES {
+                for meridiem in [Some("AM"), Some("PM"), None] {
+                    for gaps in SPACINGS {
+                        for (digits, pad_hour) in
+                            [("12:34:56", false), (" 7:05", false), (" 7:05", true)]
+                        {
+                            let rows = build_time_art(
+                                &font,
+                                digits,
+                                meridiem,
+                                style,
+                                pad_hour,
+                                spacing(gaps),
+                                None,
+                            );
+                            let context =
+                                format!("{name} {style:?} {meridiem:?} {gaps:?} {digits:?}");
+                            assert_eq!(rows.len(), font.height, "{context}");
+                            let width = time_art_width(
+                                &font,
+                                digits,
+                                meridiem,
+                                style,
+                                pad_hour,
+                                spacing(gaps),
+                            );
+                            for row in &rows {
+                                assert_eq!(row.width(), width, "{context}");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spacing_adds_gaps() {
+        let font = crate::default_font();
+        let base = font.text_width("12:34:56");
+        let width =
+            |gaps, style| time_art_width(font, "12:34:56", Some("PM"), style, false, spacing(gaps));
+
+        assert_eq!(width((0, 0, 0), AmPmStyle::Hidden), base);
+        // Three digit pairs and four colon sides
+        assert_eq!(width((2, 0, 0), AmPmStyle::Hidden), base + 3 * 2);
+        assert_eq!(width((0, 1, 0), AmPmStyle::Hidden), base + 4);
+        assert_eq!(width((2, 1, 0), AmPmStyle::Hidden), base + 3 * 2 + 4);
+        let marker = font.text_width("PM") + 1;
+        assert_eq!(
+            width((0, 0, 3), AmPmStyle::Large),
+            base + 3 * font.char_width(' ') + marker
+        );
+        assert_eq!(width((0, 0, 3), AmPmStyle::SmallText), base + 3 + 2);
+
+        // Each glyph starts after the previous one and its gap, which stays blank
+        let gaps = spacing((2, 1, 1));
+        let rows = build_time_art(font, "12:34", None, AmPmStyle::Large, false, gaps, None);
+        let offsets = glyph_offsets(font, "12:34", gaps);
+        let ends: Vec<usize> = "12:34"
+            .chars()
+            .zip(&offsets)
+            .map(|(ch, offset)| offset + font.char_width(ch))
+            .collect();
+        assert_eq!(offsets[0], 0);
+        assert_eq!(offsets[1] - ends[0], 2);
+        assert_eq!(offsets[2] - ends[1], 1);
+        assert_eq!(offsets[3] - ends[2], 1);
+        assert_eq!(offsets[4] - ends[3], 2);
+        for row in &rows {
+            let cells: Vec<char> = row.chars().collect();
+            for (end, next) in ends.iter().zip(&offsets[1..]) {
+                assert!(
+                    cells[*end..*next].iter().all(|&cell| cell == ' '),
+                    "{row:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_twelve_hour_marker_is_spaced_out() {
+        for (name, content) in BUNDLED_FONTS {
+            let font = parse_flf(name, content).unwrap();
+            for meridiem in ["AM", "PM"] {
+                for am_pm_gap in [0, 1, 3] {
+                    let gaps = spacing((0, 0, am_pm_gap));
+                    let rows = build_time_art(
+                        &font,
+                        "12:34:56",
+                        Some(meridiem),
+                        AmPmStyle::Large,
+                        false,
+                        gaps,
+                        None,
+                    );
+                    let context = format!("{name} {meridiem} {am_pm_gap}");
+                    let width = rows[0].width();
+                    assert!(rows.iter().all(|row| row.width() == width), "{context}");
+
+                    // The marker starts `am_pm_gap` space glyphs after the seconds,
+                    // and its letters sit a blank column apart
+                    let seconds_end = font.text_width("12:34:56");
+                    let first = seconds_end + am_pm_gap * font.char_width(' ');
+                    let mut letters = meridiem.chars();
+                    let letter = letters.next().unwrap();
+                    let second = first + font.char_width(letter) + MARKER_LETTER_GAP;
+                    assert_eq!(second + font.char_width(letters.next().unwrap()), width);
+                    let lead = font.render_text(&format!("12:34:56{}", " ".repeat(am_pm_gap)));
+                    for (row, lead) in rows.iter().zip(&lead) {
+                        let cells: Vec<char> = row.chars().collect();
+                        assert_eq!(
+                            cells[..first].iter().collect::<String>(),
+                            *lead,
+                            "{context}"
+                        );
+                        assert!(
+                            cells[second - MARKER_LETTER_GAP..second]
+                                .iter()
+                                .all(|&cell| cell == ' '),
+                            "{context}: {row:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_spacing_matches_plain_text() {
+        let font = crate::default_font();
+        assert_eq!(
+            build_time_art(
+                font,
+                "12:34:56",
+                Some("AM"),
+                AmPmStyle::Large,
+                false,
+                Spacing::default(),
+                None
+            ),
+            with_marker(font, "12:34:56 ", "AM")
+        );
+    }
+
+    #[test]
+    fn test_wider_and_narrower() {
+        let wide = Spacing::default().wider().wider();
+        assert_eq!((wide.digit_gap, wide.colon_gap, wide.am_pm_gap), (2, 2, 1));
+        let widest = (0..10).fold(wide, |spacing, _| spacing.wider());
+        assert_eq!(
+            (widest.digit_gap, widest.colon_gap),
+            (Spacing::MAX_GAP, Spacing::MAX_GAP)
+        );
+        let narrowest = (0..10).fold(widest, |spacing, _| spacing.narrower());
+        assert_eq!(narrowest, Spacing::default());
+        // A wider gap from the config isn't cut back by widening
+        assert_eq!(
+            Spacing {
+                digit_gap: 9,
+                ..Spacing::default()
+            }
+            .wider()
+            .digit_gap,
+            9
+        );
+    }
+
+    #[test]
+    fn test_styles() {
+        let font = crate::default_font();
+        let digits = font.render_text("09:05:03");
+        let large = build_time_art(
+            font,
+            " 9:05:03",
+            Some("PM"),
+            AmPmStyle::Large,
+            true,
+            Spacing::default(),
+            None,
+        );
+        assert_eq!(large, with_marker(font, "09:05:03 ", "PM"));
+        let hidden = build_time_art(
+            font,
+            " 9:05:03",
+            Some("PM"),
+            AmPmStyle::Hidden,
+            true,
+            Spacing::default(),
+            None,
+        );
+        assert_eq!(hidden, digits);
+        // 24-hour time has no marker in any style
+        for style in STYLES {
+            assert_eq!(
+                build_time_art(
+                    font,
+                    "21:05:03",
+                    None,
+                    style,
+                    true,
+                    Spacing::default(),
+                    None
+                ),
+                font.render_text("21:05:03")
+            );
+        }
+
+        let small = build_time_art(
+            font,
+            " 9:05:03",
+            Some("PM"),
+            AmPmStyle::SmallText,
+            true,
+            Spacing::default(),
+            None,
+        );
+        let baseline = baseline_row(&digits);
+        assert_eq!(small[baseline], format!("{} PM", digits[baseline]));
+        for (i, row) in small.iter().enumerate().filter(|(i, _)| *i != baseline) {
+            assert_eq!(row.trim_end(), digits[i].trim_end());
+        }
+        // The marker sits on the last row with ink, above the blank descender row
+        assert!(!digits[baseline].trim().is_empty());
+        assert!(
+            digits[baseline + 1..]
+                .iter()
+                .all(|row| row.trim().is_empty())
+        );
+    }
+
+    #[test]
+    fn test_twelve_hour_art_around_midnight_and_noon() {
+        use sigye_core::{TimeFormat, TimeParts};
+
+        let font = crate::default_font();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let cases = [
+            ((0, 0), "12:00:00 ", "AM"),
+            ((0, 30), "12:30:00 ", "AM"),
+            ((11, 59), "11:59:00 ", "AM"),
+            ((12, 0), "12:00:00 ", "PM"),
+            ((12, 1), "12:01:00 ", "PM"),
+            ((23, 59), "11:59:00 ", "PM"),
+        ];
+        for ((hour, minute), text, expected_meridiem) in cases {
+            let now = date.and_hms_opt(hour, minute, 0).unwrap().and_utc();
+            let parts = TimeParts::from_datetime(&now, TimeFormat::TwelveHour);
+            let digits = parts.digits(TimeFormat::TwelveHour);
+            let meridiem = parts.meridiem(TimeFormat::TwelveHour);
+            for pad_hour in [true, false] {
+                let rows = build_time_art(
+                    font,
+                    &digits,
+                    meridiem,
+                    AmPmStyle::Large,
+                    pad_hour,
+                    Spacing::default(),
+                    None,
+                );
+                // Midnight and noon draw "12", never "00"
+                assert_eq!(
+                    rows,
+                    with_marker(font, text, expected_meridiem),
+                    "{hour:02}:{minute:02}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpadded_hour_keeps_digit_width() {
+        let font = crate::default_font();
+        let blank = " ".repeat(font.blank_width());
+
+        // 12-hour 7 AM: a blank cell as wide as the zero it replaces
+        let padded = build_time_art(
+            font,
+            " 7:00:00",
+            Some("AM"),
+            AmPmStyle::Hidden,
+            true,
+            Spacing::default(),
+            None,
+        );
+        assert_eq!(padded, font.render_text("07:00:00"));
+        let unpadded = build_time_art(
+            font,
+            " 7:00:00",
+            Some("AM"),
+            AmPmStyle::Hidden,
+            false,
+            Spacing::default(),
+            None,
+        );
+        assert_eq!(unpadded[0].width(), padded[0].width());
+        let rest = font.render_text("7:00:00");
+        for (row, rest) in unpadded.iter().zip(&rest) {
+            assert_eq!(*row, format!("{blank}{rest}"));
+        }
+        assert_eq!(
+            font.text_width(&clock_chars(" 7:00:00", false)),
+            font.text_width("07:00:00")
+        );
+
+        // 12-hour 12 PM has no leading blank to replace
+        for pad_hour in [true, false] {
+            let rows = build_time_art(
+                font,
+                "12:00:00",
+                Some("PM"),
+                AmPmStyle::Hidden,
+                pad_hour,
+                Spacing::default(),
+                None,
+            );
+            assert_eq!(rows, font.render_text("12:00:00"));
+        }
+
+        // 24-hour 07:00 keeps both zeros either way
+        for pad_hour in [true, false] {
+            let rows = build_time_art(
+                font,
+                "07:00:00",
+                None,
+                AmPmStyle::Large,
+                pad_hour,
+                Spacing::default(),
+                None,
+            );
+            assert_eq!(rows, font.render_text("07:00:00"));
+        }
+    }
+}
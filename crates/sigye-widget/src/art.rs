@@ -0,0 +1,273 @@
+//! Painting clock art with a color theme and animation.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier},
+    widgets::Widget,
+};
+use sigye_core::{AnimationSpeed, AnimationStyle, ColorTheme, apply_animation, is_colon_visible};
+use sigye_fonts::{Font, Spacing, glyph_offsets, scale_art};
+
+/// Colors and effects of the clock art.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockStyle {
+    /// Color theme; dynamic themes color each cell by its place in the art.
+    pub theme: ColorTheme,
+    /// Color for static themes, already resolved for themes like Auto.
+    pub color: Color,
+    /// Animation applied on top of the theme colors.
+    pub animation_style: AnimationStyle,
+    /// Speed of the animation.
+    pub animation_speed: AnimationSpeed,
+    /// Milliseconds since the animation started.
+    pub elapsed_ms: u64,
+    /// Strength of the Reactive animation flash (0.0 - 1.0).
+    pub flash_intensity: f32,
+    /// Hide the colons for the second half of every second.
+    pub colon_blink: bool,
+    /// Draw the art in reverse video, e.g. to flash it.
+    pub reversed: bool,
+}
+
+impl Default for ClockStyle {
+    fn default() -> Self {
+        Self {
+            theme: ColorTheme::default(),
+            color: ColorTheme::default().color(),
+            animation_style: AnimationStyle::default(),
+            animation_speed: AnimationSpeed::default(),
+            elapsed_ms: 0,
+            flash_intensity: 0.0,
+            colon_blink: false,
+            reversed: false,
+        }
+    }
+}
+
+/// Rows of clock art, painted centered in an area.
+///
+/// Spaces are skipped rather than drawn, so whatever is already in the
+/// buffer, like a background, shows through between the glyphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockArt {
+    /// Art rows, all of the same width.
+    lines: Vec<String>,
+    /// Columns drawn by colon glyphs, hidden while a blinking colon is off.
+    colons: Vec<bool>,
+    /// Colors and effects.
+    style: ClockStyle,
+}
+
+impl ClockArt {
+    /// Art from pre-rendered rows.
+    pub fn new(lines: Vec<String>, style: ClockStyle) -> Self {
+        Self {
+            lines,
+            colons: Vec::new(),
+            style,
+        }
+    }
+
+    /// Mark the columns of the colons in `text`, the characters the art was
+    /// rendered from with `spacing`.
+    pub fn with_colons(mut self, font: &Font, text: &str, spacing: Spacing) -> Self {
+        self.colons = vec![false; self.width()];
+        for (ch, x) in text.chars().zip(glyph_offsets(font, text, spacing)) {
+            if ch == ':' {
+                for colon in self.colons.iter_mut().skip(x).take(font.char_width(ch)) {
+                    *colon = true;
+                }
+            }
+        }
+        self
+    }
+
+    /// Blow the art up `factor` times, keeping the marked colons.
+    pub fn scaled(mut self, factor: usize) -> Self {
+        let factor = factor.max(1);
+        self.lines = scale_art(&self.lines, factor);
+        self.colons = self
+            .colons
+            .iter()
+            .flat_map(|&colon| std::iter::repeat_n(colon, factor))
+            .collect();
+        self
+    }
+
+    /// Art rows.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Width in cells.
+    pub fn width(&self) -> usize {
+        self.lines.first().map_or(0, |line| line.chars().count())
+    }
+
+    /// Height in rows.
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Check if the art fits in `area` without clipping.
+    pub fn fits(&self, area: Rect) -> bool {
+        self.width() <= area.width as usize && self.height() <= area.height as usize
+    }
+}
+
+impl Widget for &ClockArt {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let style = &self.style;
+        let (width, height) = (self.width(), self.height());
+        let start_x = area.x + area.width.saturating_sub(width as u16) / 2;
+        let start_y = area.y + area.height.saturating_sub(height as u16) / 2;
+        let colons_hidden = style.colon_blink && !is_colon_visible(style.elapsed_ms);
+
+        for (line_idx, line) in self.lines.iter().enumerate() {
+            let y = start_y + line_idx as u16;
+            if y >= area.bottom() {
+                break;
+            }
+            for (char_idx, ch) in line.chars().enumerate() {
+                let x = start_x + char_idx as u16;
+                // Skip spaces to keep the background visible, and cells past the area
+                if ch == ' ' || x >= area.right() {
+                    continue;
+                }
+                if colons_hidden && self.colons.get(char_idx).copied().unwrap_or(false) {
+                    continue;
+                }
+
+                let base_color = if style.theme.is_dynamic() {
+                    style
+                        .theme
+                        .color_at_position(char_idx, line_idx, width, height)
+                } else {
+                    style.color
+                };
+                let color = apply_animation(
+                    base_color,
+                    style.animation_style,
+                    style.animation_speed,
+                    style.elapsed_ms,
+                    char_idx,
+                    width,
+                    style.flash_intensity,
+                );
+
+                if let Some(cell) = buf.cell_mut(Position::new(x, y)) {
+                    cell.set_char(ch);
+                    cell.set_fg(color);
+                    if style.reversed {
+                        cell.modifier.insert(Modifier::REVERSED);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Widget for ClockArt {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Style;
+
+    use super::*;
+
+    fn art(lines: &[&str]) -> ClockArt {
+        ClockArt::new(
+            lines.iter().map(|line| line.to_string()).collect(),
+            ClockStyle::default(),
+        )
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (buf.area.left()..buf.area.right())
+            .map(|x| buf[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_spaces_keep_what_is_underneath() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 1));
+        buf.set_string(0, 0, ".....", Style::default());
+        art(&["# #"]).render(buf.area, &mut buf);
+        assert_eq!(row(&buf, 0), ".#.#.");
+        assert_eq!(buf[(1, 0)].fg, ClockStyle::default().color);
+    }
+
+    #[test]
+    fn test_blinking_colons_hide_in_the_off_phase() {
+        let font = sigye_fonts::default_font();
+        let lines = font.render_text("1:2");
+        let colon_x = font.char_width('1');
+        let style = |elapsed_ms| ClockStyle {
+            colon_blink: true,
+            elapsed_ms,
+            ..ClockStyle::default()
+        };
+        let colon_cells = |elapsed_ms| {
+            let art = ClockArt::new(lines.clone(), style(elapsed_ms)).with_colons(
+                font,
+                "1:2",
+                Spacing::default(),
+            );
+            let mut buf = Buffer::empty(Rect::new(0, 0, art.width() as u16, art.height() as u16));
+            art.render(buf.area, &mut buf);
+            (0..buf.area.height)
+                .flat_map(|y| (0..font.char_width(':')).map(move |dx| (colon_x + dx, y)))
+                .filter(|&(x, y)| buf[(x as u16, y)].symbol() != " ")
+                .count()
+        };
+        assert!(colon_cells(0) > 0);
+        assert_eq!(colon_cells(600), 0);
+    }
+    #[test]
+    fn test_spaced_colons_hide_only_the_colon() {
+        let font = sigye_fonts::default_font();
+        let spacing = Spacing {
+            digit_gap: 1,
+            colon_gap: 3,
+            am_pm_gap: 1,
+        };
+        let lines = sigye_fonts::build_time_art(
+            font,
+            "12:34",
+            None,
+            sigye_core::AmPmStyle::Large,
+            false,
+            spacing,
+            None,
+        );
+        let ink = |lines: &[String]| {
+            lines
+                .iter()
+                .map(|line| line.chars().filter(|&ch| ch != ' ').count())
+                .sum::<usize>()
+        };
+        let colon_ink = ink(&font.render_text(":"));
+        let art = ClockArt::new(
+            lines.clone(),
+            ClockStyle {
+                colon_blink: true,
+                elapsed_ms: 600,
+                ..ClockStyle::default()
+            },
+        )
+        .with_colons(font, "12:34", spacing);
+        let mut buf = Buffer::empty(Rect::new(0, 0, art.width() as u16, art.height() as u16));
+        art.render(buf.area, &mut buf);
+        let drawn = buf
+            .content()
+            .iter()
+            .filter(|cell| cell.symbol() != " ")
+            .count();
+        assert_eq!(drawn, ink(&lines) - colon_ink);
+    }
+}
@@ -0,0 +1,299 @@
+//! The clock widget: the time as big art, or plain text when space is short.
+
+use chrono::{DateTime, TimeZone};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use sigye_core::{AmPmStyle, TimeFormat, TimeParts};
+use sigye_fonts::{Fill, Font, Spacing, build_time_art, clock_chars};
+
+use crate::art::{ClockArt, ClockStyle};
+
+/// What the clock shows and how it looks.
+#[derive(Debug, Clone, Copy)]
+pub struct ClockConfig<'a> {
+    /// Font of the big digits.
+    pub font: &'a Font,
+    /// 12-hour or 24-hour time.
+    pub time_format: TimeFormat,
+    /// How AM/PM is drawn in 12-hour time.
+    pub am_pm_style: AmPmStyle,
+    /// Draw a leading zero for 12-hour times like 07:30.
+    pub pad_hour: bool,
+    /// Show seconds after the minutes.
+    pub show_seconds: bool,
+    /// Gaps between the glyphs of the digits.
+    pub spacing: Spacing,
+    /// Characters painted over the digits instead of the font's own, if any.
+    pub fill: Option<&'a Fill>,
+    /// Colors and effects.
+    pub style: ClockStyle,
+}
+
+impl<'a> ClockConfig<'a> {
+    /// 24-hour time with seconds in `font`, in the default style.
+    pub fn new(font: &'a Font) -> Self {
+        Self {
+            font,
+            time_format: TimeFormat::default(),
+            am_pm_style: AmPmStyle::default(),
+            pad_hour: false,
+            show_seconds: true,
+            spacing: Spacing::default(),
+            fill: None,
+            style: ClockStyle::default(),
+        }
+    }
+}
+
+/// Big clock that centers itself in the area it is rendered to.
+///
+/// When the art doesn't fit, the time is drawn as one line of plain text in
+/// the middle row instead, cut off at the edges of very narrow areas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockWidget {
+    /// The big art.
+    art: ClockArt,
+    /// Plain text shown when the art doesn't fit.
+    text: String,
+    /// Colors and effects, for the plain text.
+    style: ClockStyle,
+}
+
+impl ClockWidget {
+    /// Clock showing the time of `now`.
+    pub fn new<Tz: TimeZone>(config: ClockConfig, now: &DateTime<Tz>) -> Self {
+        let parts = TimeParts::from_datetime(now, config.time_format);
+        let mut digits = parts.digits(config.time_format);
+        if !config.show_seconds {
+            digits.truncate(digits.rfind(':').unwrap_or(digits.len()));
+        }
+        let meridiem = parts.meridiem(config.time_format);
+
+        let lines = build_time_art(
+            config.font,
+            &digits,
+            meridiem,
+            config.am_pm_style,
+            config.pad_hour,
+            config.spacing,
+            config.fill,
+        );
+        let chars = clock_chars(&digits, config.pad_hour);
+        let art =
+            ClockArt::new(lines, config.style).with_colons(config.font, &chars, config.spacing);
+        let plain = if config.pad_hour {
+            chars.as_str()
+        } else {
+            digits.trim_start()
+        };
+        let text = match meridiem.filter(|_| config.am_pm_style != AmPmStyle::Hidden) {
+            Some(meridiem) => format!("{plain} {meridiem}"),
+            None => plain.to_string(),
+        };
+        Self {
+            art,
+            text,
+            style: config.style,
+        }
+    }
+
+    /// Clock showing pre-rendered art, such as the time in words, with `text` as its plain form.
+    pub fn from_art(lines: Vec<String>, text: impl Into<String>, style: ClockStyle) -> Self {
+        Self {
+            art: ClockArt::new(lines, style),
+            text: text.into(),
+            style,
+        }
+    }
+
+    /// Blow the art up `factor` times, e.g. to fill a large terminal.
+    pub fn scaled(mut self, factor: usize) -> Self {
+        self.art = self.art.scaled(factor);
+        self
+    }
+
+    /// The big art.
+    pub fn art(&self) -> &ClockArt {
+        &self.art
+    }
+
+    /// The time as plain text, e.g. "9:05:03 PM".
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Width of the art in cells.
+    pub fn width(&self) -> usize {
+        self.art.width()
+    }
+
+    /// Height of the art in rows.
+    pub fn height(&self) -> usize {
+        self.art.height()
+    }
+}
+
+impl Widget for &ClockWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        if self.art.fits(area) {
+            (&self.art).render(area, buf);
+        } else {
+            ClockArt::new(vec![self.text.clone()], self.style).render(area, buf);
+        }
+    }
+}
+
+impl Widget for ClockWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, 21, 5, 3).unwrap()
+    }
+
+    fn render(clock: &ClockWidget, width: u16, height: u16) -> Vec<String> {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        clock.render(buf.area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_art_is_centered_in_large_areas() {
+        let font = sigye_fonts::default_font();
+        let clock = ClockWidget::new(ClockConfig::new(font), &now());
+        let (width, height) = (clock.width(), clock.height());
+        let art = font.render_text("21:05:03");
+        assert_eq!(clock.art().lines(), art);
+
+        for (area_width, area_height) in [(width as u16, height as u16), (100, 20), (61, 9)] {
+            let rows = render(&clock, area_width, area_height);
+            let left = (area_width as usize - width) / 2;
+            let top = (area_height as usize - height) / 2;
+            for (y, row) in rows.iter().enumerate() {
+                let expected = match y.checked_sub(top).and_then(|i| art.get(i)) {
+                    Some(line) => format!("{:left$}{line:<rest$}", "", rest = row.len() - left),
+                    None => " ".repeat(row.len()),
+                };
+                assert_eq!(*row, expected, "{area_width}x{area_height} row {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_areas_fall_back_to_plain_text() {
+        let font = sigye_fonts::default_font();
+        let config = ClockConfig {
+            time_format: TimeFormat::TwelveHour,
+            ..ClockConfig::new(font)
+        };
+        let clock = ClockWidget::new(config, &now());
+        assert_eq!(clock.text(), "9:05:03 PM");
+
+        // Too narrow for the art
+        let rows = render(&clock, 20, 12);
+        assert_eq!(rows[5], "     9:05:03 PM     ");
+        assert!(
+            rows.iter()
+                .enumerate()
+                .all(|(y, row)| y == 5 || row.trim().is_empty())
+        );
+
+        // Wide enough but too short
+        let rows = render(&clock, 80, 1);
+        assert_eq!(rows[0].trim(), "9:05:03 PM");
+
+        // Narrower than the text: cut off at the edge
+        assert_eq!(render(&clock, 4, 1), ["9:05"]);
+
+        // An empty area draws nothing
+        assert!(render(&clock, 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_config_options() {
+        let font = sigye_fonts::default_font();
+        let clock = |config| ClockWidget::new(config, &now());
+
+        let no_seconds = clock(ClockConfig {
+            show_seconds: false,
+            ..ClockConfig::new(font)
+        });
+        assert_eq!(no_seconds.text(), "21:05");
+        assert_eq!(no_seconds.art().lines(), font.render_text("21:05"));
+
+        let hidden = clock(ClockConfig {
+            time_format: TimeFormat::TwelveHour,
+            am_pm_style: AmPmStyle::Hidden,
+            pad_hour: true,
+            ..ClockConfig::new(font)
+        });
+        assert_eq!(hidden.text(), "09:05:03");
+        assert_eq!(hidden.art().lines(), font.render_text("09:05:03"));
+    }
+
+    #[test]
+    fn test_fill_paints_the_digits() {
+        let font = sigye_fonts::default_font();
+        let fill = Fill::parse("@").unwrap();
+        let plain = ClockWidget::new(ClockConfig::new(font), &now());
+        let filled = ClockWidget::new(
+            ClockConfig {
+                fill: Some(&fill),
+                ..ClockConfig::new(font)
+            },
+            &now(),
+        );
+        assert_eq!(filled.art().lines(), fill.apply(plain.art().lines()));
+        assert_eq!(filled.width(), plain.width());
+
+        // The AM/PM marker beside the digits stays readable
+        let small = ClockWidget::new(
+            ClockConfig {
+                time_format: TimeFormat::TwelveHour,
+                am_pm_style: AmPmStyle::SmallText,
+                fill: Some(&fill),
+                ..ClockConfig::new(font)
+            },
+            &now(),
+        );
+        assert!(
+            small.art().lines().iter().any(|line| line.ends_with(" PM")),
+            "{:?}",
+            small.art().lines()
+        );
+    }
+
+    #[test]
+    fn test_scaled_clock_doubles_the_art() {
+        let font = sigye_fonts::default_font();
+        let config = ClockConfig {
+            time_format: TimeFormat::TwelveHour,
+            ..ClockConfig::new(font)
+        };
+        let clock = ClockWidget::new(config, &now());
+        let (width, height) = (clock.width(), clock.height());
+        let scaled = clock.clone().scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (width * 2, height * 2));
+        assert_eq!(
+            scaled.art().lines(),
+            sigye_fonts::scale_art(clock.art().lines(), 2)
+        );
+        assert_eq!(scaled.text(), clock.text());
+
+        // Falls back to plain text when the doubled art no longer fits
+        let rows = render(&scaled, width as u16, height as u16);
+        assert!(rows.iter().any(|row| row.trim() == "9:05:03 PM"));
+    }
+}
@@ -0,0 +1,23 @@
+//! Big ASCII art clock widget for ratatui apps.
+//!
+//! [`ClockWidget`] draws the time in a FIGlet font from sigye-fonts, centered
+//! in whatever area it is given and falling back to plain text when the art
+//! doesn't fit. It is the clock of the sigye terminal app, so a dashboard can
+//! embed the same clock as one of its panes:
+//!
+//! ```
+//! use chrono::Local;
+//! use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+//! use sigye_widget::{ClockConfig, ClockWidget};
+//!
+//! let font = sigye_fonts::default_font();
+//! let clock = ClockWidget::new(ClockConfig::new(font), &Local::now());
+//! let mut buf = Buffer::empty(Rect::new(0, 0, 80, 10));
+//! clock.render(buf.area, &mut buf);
+//! ```
+
+mod art;
+mod clock;
+
+pub use art::{ClockArt, ClockStyle};
+pub use clock::{ClockConfig, ClockWidget};
@@ -0,0 +1,3 @@
+#!/bin/sh
+# Write the variables a hook command gets to the file named by $1, one per line.
+printf '%s\n%s\n' "$SIGYE_LABEL" "${SIGYE_DURATION-unset}" > "$1"
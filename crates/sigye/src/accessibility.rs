@@ -0,0 +1,205 @@
+//! Accessibility modes: high contrast digits for low vision, and the time as
+//! plain text for screen readers.
+//!
+//! High contrast draws the digits in a font of full blocks with a shadow, and
+//! turns the finished frame white on black. Plain text replaces the display
+//! with the time and the date on lines of their own, redrawn in place, which
+//! terminal screen readers read out like any other text.
+
+use chrono::{DateTime, TimeZone};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Modifier},
+};
+use sigye_core::{DateFormat, TimeFormat};
+
+use crate::alarm::Alarm;
+
+/// Font of the digits in high contrast, made of full blocks.
+pub const HIGH_CONTRAST_FONT: &str = "ANSI Regular";
+
+/// Character of the shadow the digits cast in high contrast.
+const SHADOW_CHAR: char = '░';
+
+/// Cast a shadow one cell down and to the right of each glyph cell in
+/// `clock`, thickening the strokes; cells already drawn are left alone.
+pub fn add_shadow(buf: &mut Buffer, clock: Rect) {
+    let glyphs: Vec<Position> = clock
+        .intersection(buf.area)
+        .positions()
+        .filter(|&position| buf[position].symbol() != " ")
+        .collect();
+    for glyph in glyphs {
+        let shadow = Position::new(glyph.x.saturating_add(1), glyph.y.saturating_add(1));
+        if !buf.area.contains(shadow) || buf[shadow].symbol() != " " {
+            continue;
+        }
+        let fg = buf[glyph].fg;
+        buf[shadow].set_char(SHADOW_CHAR).set_fg(fg);
+    }
+}
+
+/// Turn every cell of `buf` white on black, without dimmed text.
+pub fn to_high_contrast(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        cell.set_fg(Color::White).set_bg(Color::Black);
+        cell.modifier.remove(Modifier::DIM);
+    }
+}
+
+/// Lines of the plain text display: the time, the date and a ringing alarm.
+///
+/// The time is digits, colons and AM/PM only, e.g. "9:05:03 PM", so screen
+/// readers say it as a time.
+pub fn plain_lines<Tz: TimeZone>(
+    now: &DateTime<Tz>,
+    time_format: TimeFormat,
+    date_format: DateFormat,
+    alarm: Option<&Alarm>,
+) -> Vec<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let time = match time_format {
+        TimeFormat::TwentyFourHour => now.format("%H:%M:%S"),
+        TimeFormat::TwelveHour => now.format("%-I:%M:%S %p"),
+    };
+    let mut lines = vec![
+        time.to_string(),
+        now.format(date_format.pattern()).to_string(),
+    ];
+    if let Some(alarm) = alarm {
+        let label = match alarm.label.as_str() {
+            "" => String::new(),
+            label => format!(" {label}"),
+        };
+        lines.push(format!("Alarm {alarm}{label}, press any key"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate};
+    use ratatui::widgets::Widget;
+    use sigye_core::{AnimationStyle, ColorTheme};
+    use sigye_fonts::FontRegistry;
+    use sigye_widget::{ClockConfig, ClockStyle, ClockWidget};
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<FixedOffset> {
+        NaiveDate::from_ymd_opt(2025, 3, 7)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+            .and_utc()
+            .fixed_offset()
+    }
+
+    #[test]
+    fn test_plain_time_is_only_digits_colons_and_letters() {
+        for format in [TimeFormat::TwentyFourHour, TimeFormat::TwelveHour] {
+            for hour in 0..24 {
+                let lines = plain_lines(&at(hour, 5, 9), format, DateFormat::Long, None);
+                let time = &lines[0];
+                assert!(
+                    time.chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || ch == ':' || ch == ' '),
+                    "{time}"
+                );
+                assert!(!time.starts_with(' ') && !time.ends_with(' '), "{time}");
+            }
+        }
+        let lines = plain_lines(
+            &at(21, 5, 3),
+            TimeFormat::TwelveHour,
+            DateFormat::Long,
+            None,
+        );
+        assert_eq!(lines, ["9:05:03 PM", "Friday, March 07, 2025"]);
+        let lines = plain_lines(
+            &at(9, 5, 3),
+            TimeFormat::TwentyFourHour,
+            DateFormat::Iso,
+            None,
+        );
+        assert_eq!(lines, ["09:05:03", "2025-03-07"]);
+    }
+
+    #[test]
+    fn test_plain_alarm_line() {
+        let alarm = Alarm::parse("07:30=Stand-up").unwrap();
+        let lines = plain_lines(
+            &at(7, 30, 0),
+            TimeFormat::TwentyFourHour,
+            DateFormat::Short,
+            Some(&alarm),
+        );
+        assert_eq!(lines[2], "Alarm 07:30 Stand-up, press any key");
+        let alarm = Alarm::parse("07:30").unwrap();
+        let lines = plain_lines(
+            &at(7, 30, 0),
+            TimeFormat::TwentyFourHour,
+            DateFormat::Short,
+            Some(&alarm),
+        );
+        assert_eq!(lines[2], "Alarm 07:30, press any key");
+    }
+
+    #[test]
+    fn test_high_contrast_digits_are_white() {
+        let registry = FontRegistry::new();
+        let style = ClockStyle {
+            theme: ColorTheme::Rainbow,
+            animation_style: AnimationStyle::Wave,
+            elapsed_ms: 1234,
+            ..ClockStyle::default()
+        };
+        let clock = ClockWidget::new(
+            ClockConfig {
+                style,
+                ..ClockConfig::new(registry.get_or_default(HIGH_CONTRAST_FONT))
+            },
+            &at(12, 34, 56),
+        );
+        let area = Rect::new(0, 0, clock.width() as u16 + 2, clock.height() as u16 + 2);
+        let clock_rect = Rect::new(0, 0, clock.width() as u16, clock.height() as u16);
+        let mut buf = Buffer::empty(area);
+        (&clock).render(clock_rect, &mut buf);
+        // The theme colors the digits before the frame is made high contrast
+        assert!(buf.content.iter().any(|cell| cell.fg != Color::White));
+
+        add_shadow(&mut buf, clock_rect);
+        to_high_contrast(&mut buf);
+        let drawn: Vec<_> = buf
+            .content
+            .iter()
+            .filter(|cell| cell.symbol() != " ")
+            .collect();
+        assert!(drawn.iter().any(|cell| cell.symbol() == "█"));
+        assert!(drawn.iter().any(|cell| cell.symbol() == "░"));
+        for cell in &buf.content {
+            assert_eq!((cell.fg, cell.bg), (Color::White, Color::Black));
+        }
+    }
+
+    #[test]
+    fn test_shadow_falls_down_and_right() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 3));
+        buf[(0, 0)].set_char('█');
+        buf[(1, 0)].set_char('█');
+        add_shadow(&mut buf, Rect::new(0, 0, 2, 1));
+        let rows: Vec<String> = (0..3)
+            .map(|y| (0..3).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["██ ", " ░░", "   "]);
+
+        // Nothing is drawn past the edge of the buffer
+        let mut buf = Buffer::empty(Rect::new(0, 0, 1, 1));
+        buf[(0, 0)].set_char('█');
+        add_shadow(&mut buf, Rect::new(0, 0, 1, 1));
+        assert_eq!(buf[(0, 0)].symbol(), "█");
+    }
+}
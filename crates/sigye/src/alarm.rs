@@ -0,0 +1,550 @@
+//! Scheduled alarms that fire once per day at a given local time.
+//!
+//! The next alarm due is saved to a state file, so an alarm that goes off
+//! while the clock is closed still rings on the next start.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use sigye_config::Config;
+
+/// How often the terminal bell repeats while an alarm is ringing.
+const BELL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long ago an alarm can have gone off while the clock was closed and
+/// still ring on the next start; by then it is due again anyway.
+const MAX_MISSED: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A single alarm time with an optional label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alarm {
+    /// Hour in 24-hour format (0-23).
+    pub hour: u32,
+    /// Minute (0-59).
+    pub minute: u32,
+    /// Label shown while the alarm is ringing (may be empty).
+    pub label: String,
+}
+
+impl Alarm {
+    /// Parse an alarm spec like `07:30`, `7:30 PM`, `7:30pm` or `07:30=Stand-up`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (time, label) = match spec.split_once('=') {
+            Some((time, label)) => (time.trim(), label.trim()),
+            None => (spec.trim(), ""),
+        };
+
+        let lower = time.to_ascii_lowercase();
+        let (clock, meridiem) = if let Some(rest) = lower.strip_suffix("am") {
+            (rest.trim_end(), Some(false))
+        } else if let Some(rest) = lower.strip_suffix("pm") {
+            (rest.trim_end(), Some(true))
+        } else {
+            (lower.as_str(), None)
+        };
+
+        let (hour, minute) = clock
+            .split_once(':')
+            .ok_or_else(|| format!("invalid alarm time '{time}': expected HH:MM"))?;
+        let mut hour: u32 = hour
+            .parse()
+            .map_err(|_| format!("invalid alarm hour in '{time}'"))?;
+        let minute: u32 = minute
+            .parse()
+            .map_err(|_| format!("invalid alarm minute in '{time}'"))?;
+
+        if minute > 59 {
+            return Err(format!("invalid alarm minute in '{time}'"));
+        }
+
+        match meridiem {
+            Some(is_pm) => {
+                if !(1..=12).contains(&hour) {
+                    return Err(format!("invalid 12-hour alarm hour in '{time}'"));
+                }
+                if is_pm && hour != 12 {
+                    hour += 12;
+                } else if !is_pm && hour == 12 {
+                    hour = 0;
+                }
+            }
+            None if hour > 23 => return Err(format!("invalid alarm hour in '{time}'")),
+            None => {}
+        }
+
+        Ok(Self {
+            hour,
+            minute,
+            label: label.to_string(),
+        })
+    }
+
+    /// Check if the alarm is due at the given local time (minute resolution).
+    fn matches(&self, now: &NaiveDateTime) -> bool {
+        now.hour() == self.hour && now.minute() == self.minute
+    }
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// The next alarm due, saved so it survives a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedAlarm {
+    /// Hour in 24-hour format (0-23).
+    pub hour: u32,
+    /// Minute (0-59).
+    pub minute: u32,
+    /// Label shown while the alarm is ringing (may be empty).
+    #[serde(default)]
+    pub label: String,
+    /// Unix timestamp (seconds) the alarm goes off at. Wall-clock time, so it
+    /// still holds after a restart or a reboot.
+    pub target: i64,
+}
+
+/// What becomes of a saved alarm on startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resume {
+    /// Still to come: wait for it.
+    Pending,
+    /// Went off while the clock was closed, this long ago: ring now.
+    Missed(Duration),
+    /// Went off too long ago to ring.
+    Stale,
+}
+
+impl SavedAlarm {
+    /// Decide what becomes of the alarm at `now_unix` (seconds).
+    pub fn resume(&self, now_unix: i64) -> Resume {
+        match u64::try_from(now_unix - self.target) {
+            Err(_) => Resume::Pending,
+            Ok(secs) if Duration::from_secs(secs) <= MAX_MISSED => {
+                Resume::Missed(Duration::from_secs(secs))
+            }
+            Ok(_) => Resume::Stale,
+        }
+    }
+
+    /// The alarm that was saved.
+    fn alarm(&self) -> Alarm {
+        Alarm {
+            hour: self.hour,
+            minute: self.minute,
+            label: self.label.clone(),
+        }
+    }
+}
+
+/// Path of the alarm state file.
+pub fn state_file_path() -> PathBuf {
+    Config::cache_dir().join("alarm.json")
+}
+
+/// Load the saved alarm, or `None` if missing or unreadable.
+pub fn load_saved(path: &Path) -> Option<SavedAlarm> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Write the saved alarm, creating its directory if needed, or remove the
+/// file when no alarm is due.
+pub fn save(path: &Path, saved: Option<&SavedAlarm>) -> std::io::Result<()> {
+    let Some(saved) = saved else {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(saved).map_err(std::io::Error::other)?;
+    fs::write(path, content)
+}
+
+/// Describe how long ago an alarm went off, e.g. "25 min ago" or "2 h 5 min ago".
+pub fn format_ago(ago: Duration) -> String {
+    let minutes = ago.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "just now".to_string(),
+        (0, minutes) => format!("{minutes} min ago"),
+        (hours, 0) => format!("{hours} h ago"),
+        (hours, minutes) => format!("{hours} h {minutes} min ago"),
+    }
+}
+
+/// Tracks which alarms have fired today and which one is ringing.
+#[derive(Debug, Default)]
+pub struct AlarmState {
+    /// Scheduled alarms.
+    alarms: Vec<Alarm>,
+    /// Date each alarm last fired (parallel to `alarms`).
+    last_fired: Vec<Option<NaiveDate>>,
+    /// Index of the alarm currently ringing.
+    ringing: Option<usize>,
+    /// When the bell last rang for the current alarm.
+    last_bell: Option<Instant>,
+    /// How long before the start the ringing alarm went off, when it was
+    /// missed while the clock was closed.
+    missed: Option<Duration>,
+}
+
+impl AlarmState {
+    /// Create alarm state for the given alarms.
+    pub fn new(alarms: Vec<Alarm>) -> Self {
+        let last_fired = vec![None; alarms.len()];
+        Self {
+            alarms,
+            last_fired,
+            ringing: None,
+            last_bell: None,
+            missed: None,
+        }
+    }
+
+    /// Check alarms against the current local time.
+    /// Returns true if an alarm started ringing on this check.
+    pub fn check(&mut self, now: NaiveDateTime) -> bool {
+        let today = now.date();
+        let mut fired = false;
+
+        for (idx, alarm) in self.alarms.iter().enumerate() {
+            if alarm.matches(&now) && self.last_fired[idx] != Some(today) {
+                self.last_fired[idx] = Some(today);
+                self.ringing = Some(idx);
+                self.last_bell = None;
+                self.missed = None;
+                fired = true;
+            }
+        }
+
+        fired
+    }
+
+    /// Get the alarm that is currently ringing, if any.
+    pub fn ringing(&self) -> Option<&Alarm> {
+        self.ringing.and_then(|idx| self.alarms.get(idx))
+    }
+
+    /// Get how long before the start the ringing alarm went off, if it was
+    /// missed while the clock was closed.
+    pub fn missed(&self) -> Option<Duration> {
+        self.missed
+    }
+
+    /// Silence the ringing alarm.
+    pub fn dismiss(&mut self) {
+        self.ringing = None;
+        self.last_bell = None;
+        self.missed = None;
+    }
+
+    /// Take up an alarm saved by an earlier run as of `now`: wait for it when
+    /// it is still to come, or ring it at once when it went off while the
+    /// clock was closed. Only alarms still scheduled are taken up, so one
+    /// dropped from the config or a session alarm that wasn't passed again is
+    /// forgotten. Returns true if the alarm started ringing.
+    pub fn restore<Tz: TimeZone>(&mut self, saved: &SavedAlarm, now: &DateTime<Tz>) -> bool {
+        let Resume::Missed(ago) = saved.resume(now.timestamp()) else {
+            // A pending one is already due through `check`
+            return false;
+        };
+        let alarm = saved.alarm();
+        let Some(idx) = self.alarms.iter().position(|a| *a == alarm) else {
+            return false;
+        };
+        // It went off on its day, so it doesn't ring again that day
+        self.last_fired[idx] = now
+            .timezone()
+            .timestamp_opt(saved.target, 0)
+            .single()
+            .map(|target| target.naive_local().date());
+        self.ringing = Some(idx);
+        self.last_bell = None;
+        self.missed = Some(ago);
+        true
+    }
+
+    /// The next alarm due after `now`, to save for the next run, or `None`
+    /// without alarms.
+    pub fn next_due<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<SavedAlarm> {
+        let local = now.naive_local();
+        let today = local.date();
+        self.alarms
+            .iter()
+            .zip(&self.last_fired)
+            .filter_map(|(alarm, fired)| {
+                // Today's, unless it rang already or its minute is over
+                let due_today = *fired != Some(today)
+                    && (alarm.hour, alarm.minute) >= (local.hour(), local.minute());
+                let date = if due_today { today } else { today.succ_opt()? };
+                let time = NaiveTime::from_hms_opt(alarm.hour, alarm.minute, 0)?;
+                let target = now
+                    .timezone()
+                    .from_local_datetime(&date.and_time(time))
+                    .earliest()?;
+                Some(SavedAlarm {
+                    hour: alarm.hour,
+                    minute: alarm.minute,
+                    label: alarm.label.clone(),
+                    target: target.timestamp(),
+                })
+            })
+            .min_by_key(|saved| saved.target)
+    }
+
+    /// Check if the bell should ring now, recording the ring if so.
+    pub fn take_bell(&mut self, now: Instant) -> bool {
+        if self.ringing.is_none() {
+            return false;
+        }
+        let due = self
+            .last_bell
+            .is_none_or(|last| now.duration_since(last) >= BELL_INTERVAL);
+        if due {
+            self.last_bell = Some(now);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_24_hour() {
+        let alarm = Alarm::parse("07:30").unwrap();
+        assert_eq!((alarm.hour, alarm.minute), (7, 30));
+        assert!(alarm.label.is_empty());
+        assert_eq!(Alarm::parse("23:59").unwrap().hour, 23);
+        assert!(Alarm::parse("24:00").is_err());
+        assert!(Alarm::parse("12:60").is_err());
+        assert!(Alarm::parse("noon").is_err());
+    }
+
+    #[test]
+    fn test_parse_12_hour() {
+        assert_eq!(Alarm::parse("7:30 PM").unwrap().hour, 19);
+        assert_eq!(Alarm::parse("7:30pm").unwrap().hour, 19);
+        assert_eq!(Alarm::parse("12:00 AM").unwrap().hour, 0);
+        assert_eq!(Alarm::parse("12:15 pm").unwrap().hour, 12);
+        assert!(Alarm::parse("13:00 PM").is_err());
+        assert!(Alarm::parse("0:30 AM").is_err());
+    }
+
+    #[test]
+    fn test_parse_label() {
+        let alarm = Alarm::parse("13:00 = Stand-up").unwrap();
+        assert_eq!(alarm.to_string(), "13:00");
+        assert_eq!(alarm.label, "Stand-up");
+    }
+
+    #[test]
+    fn test_fires_once_per_day() {
+        let mut state = AlarmState::new(vec![Alarm::parse("07:30").unwrap()]);
+
+        assert!(!state.check(at(1, 7, 29, 59)));
+        assert!(state.check(at(1, 7, 30, 0)));
+        state.dismiss();
+
+        // Still inside the alarm minute: must not fire again after dismissal.
+        assert!(!state.check(at(1, 7, 30, 30)));
+        assert!(state.ringing().is_none());
+
+        // Next day it fires again.
+        assert!(state.check(at(2, 7, 30, 5)));
+        assert_eq!(state.ringing().map(|a| a.minute), Some(30));
+    }
+
+    #[test]
+    fn test_alarm_for_current_minute_at_startup() {
+        let mut state = AlarmState::new(vec![Alarm::parse("09:00=Standup").unwrap()]);
+
+        // Starting mid-minute still rings, exactly once.
+        assert!(state.check(at(1, 9, 0, 42)));
+        assert_eq!(state.ringing().map(|a| a.label.as_str()), Some("Standup"));
+        assert!(!state.check(at(1, 9, 0, 43)));
+    }
+
+    #[test]
+    fn test_bell_repeats_while_ringing() {
+        let mut state = AlarmState::new(vec![Alarm::parse("07:30").unwrap()]);
+        let start = Instant::now();
+        assert!(!state.take_bell(start));
+
+        state.check(at(1, 7, 30, 0));
+        assert!(state.take_bell(start));
+        assert!(!state.take_bell(start + Duration::from_millis(500)));
+        assert!(state.take_bell(start + BELL_INTERVAL));
+
+        state.dismiss();
+        assert!(!state.take_bell(start + BELL_INTERVAL * 2));
+    }
+
+    fn utc(day: u32, hour: u32, minute: u32) -> DateTime<chrono::Utc> {
+        chrono::Utc.from_utc_datetime(&at(day, hour, minute, 0))
+    }
+
+    fn saved(spec: &str, target: DateTime<chrono::Utc>) -> SavedAlarm {
+        let alarm = Alarm::parse(spec).unwrap();
+        SavedAlarm {
+            hour: alarm.hour,
+            minute: alarm.minute,
+            label: alarm.label,
+            target: target.timestamp(),
+        }
+    }
+
+    #[test]
+    fn test_resume_decision() {
+        let alarm = saved("07:30", utc(1, 7, 30));
+        let now = |day, hour, minute| utc(day, hour, minute).timestamp();
+        assert_eq!(alarm.resume(now(1, 7, 0)), Resume::Pending);
+        assert_eq!(alarm.resume(now(1, 7, 29)), Resume::Pending);
+        assert_eq!(alarm.resume(now(1, 7, 30)), Resume::Missed(Duration::ZERO));
+        assert_eq!(
+            alarm.resume(now(1, 7, 55)),
+            Resume::Missed(Duration::from_secs(25 * 60))
+        );
+        assert_eq!(alarm.resume(now(2, 7, 30)), Resume::Missed(MAX_MISSED));
+        assert_eq!(alarm.resume(now(2, 7, 31)), Resume::Stale);
+        assert_eq!(alarm.resume(now(9, 12, 0)), Resume::Stale);
+    }
+
+    #[test]
+    fn test_saved_alarm_round_trip() {
+        let alarm = saved("13:00=Stand-up", utc(1, 13, 0));
+        let json = serde_json::to_string(&alarm).unwrap();
+        assert_eq!(serde_json::from_str::<SavedAlarm>(&json).unwrap(), alarm);
+        assert!(serde_json::from_str::<SavedAlarm>("{\"hour\": 7}").is_err());
+    }
+
+    #[test]
+    fn test_next_due() {
+        let mut state = AlarmState::new(vec![
+            Alarm::parse("07:30").unwrap(),
+            Alarm::parse("13:00=Lunch").unwrap(),
+        ]);
+        assert_eq!(
+            state.next_due(&utc(1, 6, 0)),
+            Some(saved("07:30", utc(1, 7, 30)))
+        );
+        // Due through its minute until it rings
+        assert_eq!(
+            state.next_due(&utc(1, 7, 30)),
+            Some(saved("07:30", utc(1, 7, 30)))
+        );
+        state.check(at(1, 7, 30, 0));
+        assert_eq!(
+            state.next_due(&utc(1, 7, 30)),
+            Some(saved("13:00=Lunch", utc(1, 13, 0)))
+        );
+        // After the last one, tomorrow's first
+        assert_eq!(
+            state.next_due(&utc(1, 14, 0)),
+            Some(saved("07:30", utc(2, 7, 30)))
+        );
+        assert_eq!(AlarmState::default().next_due(&utc(1, 6, 0)), None);
+    }
+
+    #[test]
+    fn test_restore_pending_alarm() {
+        let mut state = AlarmState::new(vec![Alarm::parse("07:30=Run").unwrap()]);
+        assert!(!state.restore(&saved("07:30=Run", utc(1, 7, 30)), &utc(1, 7, 0)));
+        assert!(state.ringing().is_none());
+        // Rings on time, once
+        assert!(state.check(at(1, 7, 30, 0)));
+        assert_eq!(state.ringing().map(|a| a.label.as_str()), Some("Run"));
+        assert_eq!(state.missed(), None);
+        assert_eq!(state.alarms.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_forgets_unscheduled_alarms() {
+        // Dropped from the config, or a session alarm not passed again
+        let mut state = AlarmState::new(vec![Alarm::parse("13:00").unwrap()]);
+        assert!(!state.restore(&saved("07:30=Run", utc(1, 7, 30)), &utc(1, 7, 0)));
+        assert!(!state.restore(&saved("07:30=Run", utc(1, 7, 30)), &utc(1, 7, 55)));
+        assert!(state.ringing().is_none());
+        assert!(!state.check(at(1, 7, 30, 0)));
+        // And so isn't saved again
+        assert_eq!(
+            state.next_due(&utc(1, 7, 55)),
+            Some(saved("13:00", utc(1, 13, 0)))
+        );
+        assert_eq!(AlarmState::default().next_due(&utc(1, 7, 0)), None);
+    }
+
+    #[test]
+    fn test_restore_missed_alarm() {
+        let mut state = AlarmState::new(vec![Alarm::parse("07:30").unwrap()]);
+        assert!(state.restore(&saved("07:30", utc(1, 7, 30)), &utc(1, 7, 55)));
+        assert_eq!(
+            state.ringing().map(ToString::to_string).as_deref(),
+            Some("07:30")
+        );
+        assert_eq!(state.missed(), Some(Duration::from_secs(25 * 60)));
+        // Not added twice, and not ringing again that day
+        assert_eq!(state.alarms.len(), 1);
+        state.dismiss();
+        assert_eq!(state.missed(), None);
+        assert!(!state.check(at(1, 7, 55, 0)));
+        assert_eq!(
+            state.next_due(&utc(1, 8, 0)),
+            Some(saved("07:30", utc(2, 7, 30)))
+        );
+    }
+
+    #[test]
+    fn test_restore_stale_alarm() {
+        let mut state = AlarmState::new(vec![Alarm::parse("07:30").unwrap()]);
+        assert!(!state.restore(&saved("07:30", utc(1, 7, 30)), &utc(3, 9, 0)));
+        assert!(state.ringing().is_none());
+        assert_eq!(
+            state.next_due(&utc(3, 9, 0)),
+            Some(saved("07:30", utc(4, 7, 30)))
+        );
+    }
+
+    #[test]
+    fn test_save_and_remove_state_file() {
+        let path = std::env::temp_dir()
+            .join(format!("sigye-alarm-{}", std::process::id()))
+            .join("alarm.json");
+        let alarm = saved("07:30", utc(1, 7, 30));
+        save(&path, Some(&alarm)).unwrap();
+        assert_eq!(load_saved(&path), Some(alarm));
+        save(&path, None).unwrap();
+        assert_eq!(load_saved(&path), None);
+        // Nothing to remove is fine
+        save(&path, None).unwrap();
+        let _ = fs::remove_dir(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_format_ago() {
+        assert_eq!(format_ago(Duration::from_secs(59)), "just now");
+        assert_eq!(format_ago(Duration::from_secs(25 * 60)), "25 min ago");
+        assert_eq!(format_ago(Duration::from_secs(2 * 3600)), "2 h ago");
+        assert_eq!(
+            format_ago(Duration::from_secs(2 * 3600 + 5 * 60 + 30)),
+            "2 h 5 min ago"
+        );
+    }
+}
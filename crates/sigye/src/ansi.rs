@@ -0,0 +1,158 @@
+//! Serializing a rendered buffer as text colored with ANSI escapes.
+
+use crossterm::style::{Attribute, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use ratatui::backend::IntoCrossterm;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use unicode_width::UnicodeWidthStr;
+
+/// SGR sequence resetting all colors and attributes.
+const RESET: &str = "\x1b[0m";
+
+/// Terminal attributes of each modifier.
+const ATTRIBUTES: [(Modifier, Attribute); 9] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+    (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::HIDDEN, Attribute::Hidden),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// Colors and attributes of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellStyle {
+    /// Foreground color.
+    fg: Color,
+    /// Background color.
+    bg: Color,
+    /// Attributes like bold or reversed.
+    modifier: Modifier,
+}
+
+impl CellStyle {
+    /// The terminal's default look.
+    const PLAIN: Self = Self {
+        fg: Color::Reset,
+        bg: Color::Reset,
+        modifier: Modifier::empty(),
+    };
+
+    fn of(cell: &Cell) -> Self {
+        Self {
+            fg: cell.fg,
+            bg: cell.bg,
+            modifier: cell.modifier,
+        }
+    }
+
+    /// Escapes switching from the default look to this style.
+    fn escapes(self) -> String {
+        let mut escapes = String::new();
+        if self.fg != Color::Reset {
+            escapes.push_str(&SetForegroundColor(self.fg.into_crossterm()).to_string());
+        }
+        if self.bg != Color::Reset {
+            escapes.push_str(&SetBackgroundColor(self.bg.into_crossterm()).to_string());
+        }
+        for (modifier, attribute) in ATTRIBUTES {
+            if self.modifier.contains(modifier) {
+                escapes.push_str(&SetAttribute(attribute).to_string());
+            }
+        }
+        escapes
+    }
+}
+
+/// Rows of a rendered buffer with ANSI escapes for their colors and attributes.
+///
+/// Escapes are only written where the style changes, so a run of cells in one
+/// style costs a single sequence. Each row ends back in the default style, with
+/// trailing unstyled spaces left out.
+pub fn ansi_lines(buf: &Buffer) -> Vec<String> {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            // Cells covered by a wide character before them hold a placeholder
+            let mut cells = Vec::new();
+            let mut skip = 0;
+            for x in area.left()..area.right() {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                let cell = &buf[(x, y)];
+                skip = cell.symbol().width().saturating_sub(1);
+                cells.push(cell);
+            }
+            let end = cells
+                .iter()
+                .rposition(|cell| cell.symbol() != " " || CellStyle::of(cell) != CellStyle::PLAIN)
+                .map_or(0, |last| last + 1);
+
+            let mut line = String::new();
+            let mut current = CellStyle::PLAIN;
+            for cell in &cells[..end] {
+                let style = CellStyle::of(cell);
+                if style != current {
+                    if current != CellStyle::PLAIN {
+                        line.push_str(RESET);
+                    }
+                    line.push_str(&style.escapes());
+                    current = style;
+                }
+                line.push_str(cell.symbol());
+            }
+            if current != CellStyle::PLAIN {
+                line.push_str(RESET);
+            }
+            line
+        })
+        .collect()
+}
+
+/// A rendered buffer as ANSI-colored text, one line per row.
+pub fn buffer_ansi(buf: &Buffer) -> String {
+    ansi_lines(buf)
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use super::*;
+
+    #[test]
+    fn test_runs_share_one_escape() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buf.set_string(0, 0, "ab", Style::new().fg(Color::Cyan));
+        buf.set_string(2, 0, "c", Style::new().fg(Color::Red).bold());
+        buf.set_string(4, 0, "d", Style::new());
+        assert_eq!(
+            buffer_ansi(&buf),
+            "\x1b[38;5;6mab\x1b[0m\x1b[38;5;1m\x1b[1mc\x1b[0m d\n\n"
+        );
+    }
+
+    #[test]
+    fn test_styled_spaces_are_kept() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buf.set_string(0, 0, "x", Style::new());
+        buf.set_string(2, 0, "  ", Style::new().bg(Color::Rgb(0, 0, 255)));
+        assert_eq!(ansi_lines(&buf), ["x \x1b[48;2;0;0;255m  \x1b[0m"]);
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 1));
+        buf.set_string(0, 0, "한☀", Style::new().fg(Color::Yellow));
+        assert_eq!(ansi_lines(&buf), ["\x1b[38;5;3m한☀\x1b[0m"]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Counters about the app itself, served in the Prometheus text format with
+//! `--metrics-listen`.
+//!
+//! The render loop and the weather thread update an [`AppMetrics`] of atomics,
+//! and a background thread answers `GET /metrics` with a snapshot of it, one
+//! connection at a time.
+
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sigye_core::BackgroundStyle;
+
+use crate::worker::{JOIN_TIMEOUT, Worker};
+
+/// How often the listener checks for a connection, and for being stopped.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a connection may take to send its request or read the response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Most bytes of a request read before answering, headers included.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Content type of the Prometheus text format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Counters updated by the render loop and worker threads.
+#[derive(Debug, Default)]
+pub struct AppMetrics {
+    /// Frames drawn on the terminal.
+    frames_rendered: AtomicU64,
+    /// Frames skipped since nothing visible changed.
+    frames_skipped: AtomicU64,
+    /// Time spent drawing frames, in microseconds.
+    render_micros: AtomicU64,
+    /// Weather fetches that succeeded.
+    weather_fetches: AtomicU64,
+    /// Weather fetches that failed on every provider.
+    weather_fetch_failures: AtomicU64,
+    /// Background style of the last drawn frame, `None` before the first.
+    background: Mutex<Option<BackgroundStyle>>,
+}
+
+impl AppMetrics {
+    /// Count a frame drawn in `render_time` with `background` as its style.
+    pub fn record_frame(&self, render_time: Duration, background: BackgroundStyle) {
+        self.frames_rendered.fetch_add(1, Ordering::Relaxed);
+        let micros = u64::try_from(render_time.as_micros()).unwrap_or(u64::MAX);
+        self.render_micros.fetch_add(micros, Ordering::Relaxed);
+        if let Ok(mut shown) = self.background.lock() {
+            *shown = Some(background);
+        }
+    }
+
+    /// Count a frame skipped since nothing visible changed.
+    pub fn record_skipped_frame(&self) {
+        self.frames_skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a weather fetch, successful or failed.
+    pub fn record_weather_fetch(&self, succeeded: bool) {
+        let counter = if succeeded {
+            &self.weather_fetches
+        } else {
+            &self.weather_fetch_failures
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Current values in the Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut text = String::new();
+        let mut family = |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(text, "# HELP {name} {help}");
+            let _ = writeln!(text, "# TYPE {name} {kind}");
+            for (sample, value) in samples {
+                let _ = writeln!(text, "{name}{sample} {value}");
+            }
+        };
+
+        family(
+            "sigye_frames_rendered_total",
+            "counter",
+            "Frames drawn on the terminal.",
+            &[(String::new(), load(&self.frames_rendered).to_string())],
+        );
+        family(
+            "sigye_frames_skipped_total",
+            "counter",
+            "Frames skipped since nothing visible changed.",
+            &[(String::new(), load(&self.frames_skipped).to_string())],
+        );
+        let render_secs = load(&self.render_micros) as f64 / 1_000_000.0;
+        family(
+            "sigye_frame_render_seconds",
+            "summary",
+            "Time spent drawing frames; divide the sum by the count for the average.",
+            &[
+                ("_sum".to_string(), render_secs.to_string()),
+                (
+                    "_count".to_string(),
+                    load(&self.frames_rendered).to_string(),
+                ),
+            ],
+        );
+        family(
+            "sigye_weather_fetches_total",
+            "counter",
+            "Weather fetches by result.",
+            &[
+                (
+                    r#"{result="success"}"#.to_string(),
+                    load(&self.weather_fetches).to_string(),
+                ),
+                (
+                    r#"{result="failure"}"#.to_string(),
+                    load(&self.weather_fetch_failures).to_string(),
+                ),
+            ],
+        );
+        let background = self.background.lock().ok().and_then(|shown| *shown);
+        let styles: Vec<_> = background
+            .map(|style| (format!(r#"{{style="{style}"}}"#), "1".to_string()))
+            .into_iter()
+            .collect();
+        family(
+            "sigye_background_style",
+            "gauge",
+            "Background style of the last drawn frame.",
+            &styles,
+        );
+        text
+    }
+}
+
+/// Serves [`AppMetrics`] over HTTP from a background thread until dropped.
+#[derive(Debug)]
+pub struct MetricsServer {
+    /// Listener thread, stopped when the server is dropped.
+    worker: Option<Worker>,
+}
+
+impl MetricsServer {
+    /// Listen on `addr` and serve `metrics` at `/metrics`.
+    pub fn bind(addr: SocketAddr, metrics: Arc<AppMetrics>) -> io::Result<Self> {
+        Self::start(TcpListener::bind(addr)?, metrics)
+    }
+
+    /// Serve `metrics` at `/metrics` to connections on `listener`.
+    pub fn start(listener: TcpListener, metrics: Arc<AppMetrics>) -> io::Result<Self> {
+        // Polled, so the thread notices being stopped between connections
+        listener.set_nonblocking(true)?;
+        let worker = Worker::spawn(move |stop| {
+            loop {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        let _ = respond(&mut stream, &metrics);
+                    }
+                    Err(_) => {
+                        if stop.wait(ACCEPT_INTERVAL) {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            worker: Some(worker),
+        })
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            worker.stop(JOIN_TIMEOUT);
+        }
+    }
+}
+
+/// Read a request from `stream` and write the response for it.
+fn respond(stream: &mut TcpStream, metrics: &AppMetrics) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    // Read the headers too, since closing with unread data resets the connection
+    let mut request = Vec::new();
+    let mut chunk = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n")
+        && request.len() < MAX_REQUEST_BYTES
+    {
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&chunk[..read]);
+    }
+
+    let (status, body) = response(&String::from_utf8_lossy(&request), metrics);
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// Status line and body answering an HTTP request.
+fn response(request: &str, metrics: &AppMetrics) -> (&'static str, String) {
+    let mut words = request.lines().next().unwrap_or_default().split(' ');
+    let (method, target) = (words.next().unwrap_or_default(), words.next());
+    let path = target.map(|target| target.split('?').next().unwrap_or_default());
+    match (method, path) {
+        ("GET", Some("/metrics")) => ("200 OK", metrics.encode()),
+        ("GET", _) => ("404 Not Found", "Not found; try /metrics\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "Only GET is supported\n".to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_before_any_frame() {
+        let text = AppMetrics::default().encode();
+        assert!(text.contains("# TYPE sigye_frames_rendered_total counter\n"));
+        assert!(text.contains("\nsigye_frames_rendered_total 0\n"));
+        assert!(text.contains("\nsigye_frame_render_seconds_sum 0\n"));
+        assert!(text.contains("\nsigye_weather_fetches_total{result=\"failure\"} 0\n"));
+        // No style until a frame is drawn, but the family is still described
+        assert!(text.contains("# TYPE sigye_background_style gauge\n"));
+        assert!(!text.contains("sigye_background_style{"));
+    }
+
+    #[test]
+    fn test_counters_update() {
+        let metrics = AppMetrics::default();
+        metrics.record_frame(Duration::from_millis(3), BackgroundStyle::Starfield);
+        metrics.record_frame(Duration::from_millis(5), BackgroundStyle::MatrixRain);
+        metrics.record_skipped_frame();
+        metrics.record_weather_fetch(true);
+        metrics.record_weather_fetch(true);
+        metrics.record_weather_fetch(false);
+
+        let text = metrics.encode();
+        for line in [
+            "sigye_frames_rendered_total 2",
+            "sigye_frames_skipped_total 1",
+            "sigye_frame_render_seconds_sum 0.008",
+            "sigye_frame_render_seconds_count 2",
+            "sigye_weather_fetches_total{result=\"success\"} 2",
+            "sigye_weather_fetches_total{result=\"failure\"} 1",
+            "sigye_background_style{style=\"matrix-rain\"} 1",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {line} in\n{text}");
+        }
+        assert!(!text.contains("starfield"));
+    }
+
+    #[test]
+    fn test_encode_is_well_formed() {
+        let metrics = AppMetrics::default();
+        metrics.record_frame(Duration::from_micros(1500), BackgroundStyle::Aurora);
+        let text = metrics.encode();
+        assert!(text.ends_with('\n'));
+        for line in text.lines() {
+            if let Some(comment) = line.strip_prefix("# ") {
+                assert!(comment.starts_with("HELP sigye_") || comment.starts_with("TYPE sigye_"));
+            } else {
+                // A metric name with optional labels, then a number
+                let (name, value) = line.rsplit_once(' ').unwrap();
+                assert!(name.starts_with("sigye_"), "{line}");
+                assert!(value.parse::<f64>().is_ok(), "{line}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_response_routes() {
+        let metrics = AppMetrics::default();
+        let status = |request| response(request, &metrics).0;
+        assert_eq!(status("GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n"), "200 OK");
+        assert_eq!(status("GET /metrics?x=1 HTTP/1.0\r\n\r\n"), "200 OK");
+        assert_eq!(status("GET / HTTP/1.1\r\n\r\n"), "404 Not Found");
+        assert_eq!(
+            status("POST /metrics HTTP/1.1\r\n\r\n"),
+            "405 Method Not Allowed"
+        );
+        assert_eq!(status(""), "405 Method Not Allowed");
+    }
+
+    #[test]
+    fn test_serves_metrics_until_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metrics = Arc::new(AppMetrics::default());
+        metrics.record_skipped_frame();
+        let server = MetricsServer::start(listener, Arc::clone(&metrics)).unwrap();
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(reply.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(reply.ends_with(&metrics.encode()));
+        assert!(reply.contains("\nsigye_frames_skipped_total 1\n"));
+
+        // The port is released once the server is gone
+        drop(server);
+        assert!(TcpStream::connect(addr).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! Background mode: follow the weather, keep a manually picked style, or show nothing.
+
+use sigye_core::BackgroundStyle;
+
+/// How the background style is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundMode {
+    /// Follow the weather, resolved by the weather monitor.
+    Auto,
+    /// A specific style picked by the user, regardless of the weather.
+    Manual(BackgroundStyle),
+    /// No background.
+    Off,
+}
+
+impl BackgroundMode {
+    /// Derive the mode from a stored background style.
+    pub fn from_style(style: BackgroundStyle) -> Self {
+        match style {
+            BackgroundStyle::Weather => BackgroundMode::Auto,
+            BackgroundStyle::None => BackgroundMode::Off,
+            style => BackgroundMode::Manual(style),
+        }
+    }
+
+    /// Background style representing this mode, as stored in the config.
+    pub fn style(self) -> BackgroundStyle {
+        match self {
+            BackgroundMode::Auto => BackgroundStyle::Weather,
+            BackgroundMode::Manual(style) => style,
+            BackgroundMode::Off => BackgroundStyle::None,
+        }
+    }
+
+    /// Cycle Off → Auto → each manual style → Off.
+    pub fn next(self) -> Self {
+        match self {
+            BackgroundMode::Off => BackgroundMode::Auto,
+            BackgroundMode::Auto => BackgroundMode::Off.next_manual(),
+            BackgroundMode::Manual(_) => match self.next_manual() {
+                BackgroundMode::Auto => BackgroundMode::Off,
+                mode => mode,
+            },
+        }
+    }
+
+    /// Pick the next manual style; cycling past the last one returns to Auto.
+    pub fn next_manual(self) -> Self {
+        let mut style = match self {
+            BackgroundMode::Manual(style) => style,
+            BackgroundMode::Auto | BackgroundMode::Off => BackgroundStyle::None,
+        };
+        loop {
+            style = style.next();
+            match style {
+                BackgroundStyle::None => return BackgroundMode::Auto,
+                BackgroundStyle::Weather => continue,
+                style => return BackgroundMode::Manual(style),
+            }
+        }
+    }
+
+    /// Get display name for the mode.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            BackgroundMode::Auto => "Auto",
+            BackgroundMode::Manual(style) => style.display_name(),
+            BackgroundMode::Off => "Off",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_round_trip() {
+        for mode in [
+            BackgroundMode::Auto,
+            BackgroundMode::Off,
+            BackgroundMode::Manual(BackgroundStyle::MatrixRain),
+        ] {
+            assert_eq!(BackgroundMode::from_style(mode.style()), mode);
+        }
+    }
+
+    #[test]
+    fn test_next_manual_returns_to_auto() {
+        let first = BackgroundMode::Auto.next_manual();
+        assert_eq!(first, BackgroundMode::Manual(BackgroundStyle::Starfield));
+
+        let mut mode = first;
+        let mut picked = Vec::new();
+        while let BackgroundMode::Manual(style) = mode {
+            picked.push(style);
+            mode = mode.next_manual();
+        }
+        assert_eq!(mode, BackgroundMode::Auto);
+        assert!(!picked.contains(&BackgroundStyle::Weather));
+        assert!(!picked.contains(&BackgroundStyle::None));
+        assert_eq!(picked.last(), Some(&BackgroundStyle::HeatMap));
+    }
+
+    #[test]
+    fn test_next_cycles_through_off_and_auto() {
+        assert_eq!(BackgroundMode::Off.next(), BackgroundMode::Auto);
+        assert_eq!(
+            BackgroundMode::Auto.next(),
+            BackgroundMode::Manual(BackgroundStyle::Starfield)
+        );
+        assert_eq!(
+            BackgroundMode::Manual(BackgroundStyle::HeatMap).next(),
+            BackgroundMode::Off
+        );
+    }
+
+    #[test]
+    fn test_display_name() {
+        assert_eq!(BackgroundMode::Auto.display_name(), "Auto");
+        assert_eq!(BackgroundMode::Off.display_name(), "Off");
+        assert_eq!(
+            BackgroundMode::Manual(BackgroundStyle::MatrixRain).display_name(),
+            "Matrix Rain"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Message banner in a box above or below the clock, e.g. "ON AIR", scrolling
+//! like a marquee when it is wider than the screen.
+
+use ratatui::layout::Rect;
+use sigye_core::BannerPosition;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Rows the banner takes: its text between the top and bottom of the box.
+pub const BANNER_HEIGHT: u16 = 3;
+
+/// Cells the box adds to the text's width: a side and a space on each end.
+pub const BANNER_PADDING: u16 = 4;
+
+/// Gap between the end of a scrolling message and its start coming round again.
+const MARQUEE_GAP: &str = "   ";
+
+/// Check if `message` is too wide for `width` columns and has to scroll.
+pub fn scrolls(message: &str, width: usize) -> bool {
+    message.width() > width
+}
+
+/// Text of the banner in `width` columns `elapsed_ms` into the animation: the
+/// whole message when it fits, or else a window moving one character along
+/// it every `step_ms`, wrapping round after a gap.
+pub fn marquee(message: &str, width: usize, elapsed_ms: u64, step_ms: u64) -> String {
+    if !scrolls(message, width) {
+        return message.to_string();
+    }
+    let looped: Vec<char> = message.chars().chain(MARQUEE_GAP.chars()).collect();
+    let offset = (elapsed_ms / step_ms.max(1)) as usize % looped.len();
+    let mut text = String::new();
+    let mut used = 0;
+    for &ch in looped.iter().cycle().skip(offset) {
+        used += ch.width().unwrap_or(0);
+        if used > width {
+            break;
+        }
+        text.push(ch);
+    }
+    text
+}
+
+/// Message from the contents of a banner file: its lines trimmed and joined
+/// by spaces, `None` when there is no text.
+pub fn read_message(contents: &str) -> Option<String> {
+    let message = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!message.is_empty()).then_some(message)
+}
+
+/// Split the banner off the top or bottom of `area`, returning the banner box
+/// `width` cells wide, centered across the area, and the room left for the clock.
+pub fn place(area: Rect, width: u16, position: BannerPosition) -> (Rect, Rect) {
+    let height = BANNER_HEIGHT.min(area.height);
+    let rest_height = area.height - height;
+    let (banner_y, rest_y) = match position {
+        BannerPosition::Above => (area.y, area.y + height),
+        BannerPosition::Below => (area.y + rest_height, area.y),
+    };
+    let width = width.min(area.width);
+    let banner = Rect::new(area.x + (area.width - width) / 2, banner_y, width, height);
+    let rest = Rect {
+        y: rest_y,
+        height: rest_height,
+        ..area
+    };
+    (banner, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_messages_stand_still() {
+        assert_eq!(marquee("ON AIR", 6, 0, 200), "ON AIR");
+        assert_eq!(marquee("ON AIR", 20, 12_345, 200), "ON AIR");
+        assert!(!scrolls("ON AIR", 6));
+        assert!(scrolls("ON AIR", 5));
+    }
+
+    #[test]
+    fn test_marquee_offsets() {
+        let message = "BACK AT 3PM";
+        // One character per step, starting at the front
+        assert_eq!(marquee(message, 5, 0, 200), "BACK ");
+        assert_eq!(marquee(message, 5, 199, 200), "BACK ");
+        assert_eq!(marquee(message, 5, 200, 200), "ACK A");
+        assert_eq!(marquee(message, 5, 5 * 200, 200), "AT 3P");
+        // The start comes round again after the gap
+        assert_eq!(marquee(message, 5, 9 * 200, 200), "PM   ");
+        assert_eq!(marquee(message, 5, 11 * 200, 200), "   BA");
+        // A full loop is the message and the gap
+        let period = (message.len() + MARQUEE_GAP.len()) as u64 * 200;
+        assert_eq!(marquee(message, 5, period, 200), "BACK ");
+        assert_eq!(
+            marquee(message, 5, period + 600, 200),
+            marquee(message, 5, 600, 200)
+        );
+    }
+
+    #[test]
+    fn test_marquee_wide_characters() {
+        // Each of these takes two columns; a half is never cut off
+        assert_eq!(marquee("회의 중입니다", 5, 0, 100), "회의 ");
+        assert_eq!(marquee("회의 중입니다", 5, 100, 100), "의 중");
+        assert!(marquee("회의 중입니다", 1, 0, 100).is_empty());
+        // A zero step doesn't divide by zero
+        assert_eq!(marquee("BACK AT 3PM", 4, 1_000, 0).chars().count(), 4);
+    }
+
+    #[test]
+    fn test_read_message() {
+        assert_eq!(read_message("ON AIR\n").as_deref(), Some("ON AIR"));
+        assert_eq!(
+            read_message("  Design review  \r\n\n  until 15:00\n").as_deref(),
+            Some("Design review until 15:00")
+        );
+        assert_eq!(read_message(" \n\t\n"), None);
+    }
+
+    #[test]
+    fn test_place() {
+        let area = Rect::new(10, 5, 40, 12);
+        let (banner, rest) = place(area, 20, BannerPosition::Above);
+        assert_eq!(banner, Rect::new(20, 5, 20, 3));
+        assert_eq!(rest, Rect::new(10, 8, 40, 9));
+
+        let (banner, rest) = place(area, 20, BannerPosition::Below);
+        assert_eq!(banner, Rect::new(20, 14, 20, 3));
+        assert_eq!(rest, Rect::new(10, 5, 40, 9));
+
+        // Clamped to a cramped area
+        let (banner, rest) = place(Rect::new(0, 0, 8, 2), 20, BannerPosition::Above);
+        assert_eq!(banner, Rect::new(0, 0, 8, 2));
+        assert_eq!(rest.height, 0);
+    }
+}
@@ -0,0 +1,94 @@
+//! Decorative border around the clock block.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Widget},
+};
+use sigye_core::ClockBorder;
+
+/// Cells the border adds to the clock block on each axis.
+pub const BORDER_SIZE: u16 = 2;
+
+/// Border rect for a clock block of `content_width` placed in `placed`.
+///
+/// `placed` already has room for the border; it may be wider than the content
+/// when the block is centered across the screen, so the border is centered in it.
+pub fn border_rect(placed: Rect, content_width: u16) -> Rect {
+    let width = (content_width + BORDER_SIZE).min(placed.width);
+    Rect {
+        x: placed.x + (placed.width - width) / 2,
+        width,
+        ..placed
+    }
+}
+
+/// Area inside the border where the clock block is laid out.
+pub fn inner(border: Rect) -> Rect {
+    border.inner(Margin::new(1, 1))
+}
+
+/// Draw the border and its centered title, leaving the cells inside untouched.
+pub fn render(buf: &mut Buffer, rect: Rect, border: ClockBorder, title: &str, color: Color) {
+    let Some(border_type) = border.border_type() else {
+        return;
+    };
+    let mut block = Block::bordered()
+        .border_type(border_type)
+        .border_style(Style::default().fg(color));
+    if !title.is_empty() {
+        block = block.title(Line::from(format!(" {title} ")).centered());
+    }
+    block.render(rect, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_border_rect_hugs_centered_content() {
+        let placed = Rect::new(0, 5, 80, 12);
+        assert_eq!(border_rect(placed, 40), Rect::new(19, 5, 42, 12));
+        assert_eq!(inner(border_rect(placed, 40)), Rect::new(20, 6, 40, 10));
+
+        // Content wider than the screen is clamped to it
+        assert_eq!(border_rect(placed, 100), placed);
+    }
+
+    #[test]
+    fn test_render_border_cells() {
+        let area = Rect::new(0, 0, 12, 4);
+        let mut buf = Buffer::empty(area);
+        buf[(5, 1)].set_char('8');
+
+        render(&mut buf, area, ClockBorder::Rounded, "Oslo", Color::Cyan);
+
+        assert_eq!(buf[(0, 0)].symbol(), "╭");
+        assert_eq!(buf[(11, 0)].symbol(), "╮");
+        assert_eq!(buf[(0, 3)].symbol(), "╰");
+        assert_eq!(buf[(11, 3)].symbol(), "╯");
+        assert_eq!(buf[(0, 1)].symbol(), "│");
+        assert_eq!(buf[(1, 3)].symbol(), "─");
+        assert_eq!(buf[(0, 0)].fg, Color::Cyan);
+        // Title centered on the top edge, content inside left alone
+        let top: String = (0..12).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(top, "╭── Oslo ──╮");
+        assert_eq!(buf[(5, 1)].symbol(), "8");
+    }
+
+    #[test]
+    fn test_render_double_and_none() {
+        let area = Rect::new(0, 0, 4, 3);
+        let mut buf = Buffer::empty(area);
+        render(&mut buf, area, ClockBorder::Double, "", Color::White);
+        assert_eq!(buf[(0, 0)].symbol(), "╔");
+        assert_eq!(buf[(3, 2)].symbol(), "╝");
+
+        let mut buf = Buffer::empty(area);
+        render(&mut buf, area, ClockBorder::None, "Oslo", Color::White);
+        assert_eq!(buf, Buffer::empty(area));
+    }
+}
@@ -0,0 +1,207 @@
+//! Screensaver mode that drifts the clock around the screen, bouncing off the edges.
+
+use ratatui::layout::{Rect, Size};
+use sigye_core::AnimationSpeed;
+
+/// Vertical speed relative to horizontal, since terminal cells are about twice as tall as wide.
+const VERTICAL_SPEED_RATIO: f32 = 0.5;
+
+/// Position and direction of the drifting clock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounce {
+    /// Offset from the top-left of the area, `None` until the first update centers it.
+    position: Option<(f32, f32)>,
+    /// Direction of travel on each axis (-1.0 or 1.0).
+    direction: (f32, f32),
+    /// Elapsed animation time at the last update, `None` to not move on the next one.
+    last_update_ms: Option<u64>,
+}
+
+impl Bounce {
+    /// Start drifting from the center, moving down and to the right.
+    pub fn new(elapsed_ms: u64) -> Self {
+        Self {
+            position: None,
+            direction: (1.0, 1.0),
+            last_update_ms: Some(elapsed_ms),
+        }
+    }
+
+    /// Skip the time until the next update, e.g. after unpausing, so the
+    /// content continues from where it stopped instead of jumping ahead.
+    pub fn skip_gap(&mut self) {
+        self.last_update_ms = None;
+    }
+
+    /// Move the content by the time passed since the last update and place it in `area`.
+    ///
+    /// Bounds are re-derived from `content` and `area` on every call, so the
+    /// content is pulled back inside when the terminal shrinks or the clock
+    /// gets wider. Returns the placed rectangle and whether it hit an edge.
+    pub fn update(
+        &mut self,
+        elapsed_ms: u64,
+        speed: AnimationSpeed,
+        content: Size,
+        area: Rect,
+    ) -> (Rect, bool) {
+        let delta_ms = self
+            .last_update_ms
+            .map_or(0, |last| elapsed_ms.saturating_sub(last));
+        self.last_update_ms = Some(elapsed_ms);
+
+        let max_x = area.width.saturating_sub(content.width) as f32;
+        let max_y = area.height.saturating_sub(content.height) as f32;
+        let (x, y) = self.position.unwrap_or((max_x / 2.0, max_y / 2.0));
+
+        let distance = speed.bounce_cells_per_sec() * delta_ms as f32 / 1000.0;
+        let (x, dx, bounced_x) = advance(x, self.direction.0, distance, max_x);
+        let (y, dy, bounced_y) =
+            advance(y, self.direction.1, distance * VERTICAL_SPEED_RATIO, max_y);
+        self.position = Some((x, y));
+        self.direction = (dx, dy);
+
+        let rect = Rect::new(
+            area.x + x.round() as u16,
+            area.y + y.round() as u16,
+            content.width.min(area.width),
+            content.height.min(area.height),
+        );
+        (rect, bounced_x || bounced_y)
+    }
+}
+
+/// Move along one axis within `0.0..=max`, reflecting off either end.
+///
+/// Returns the new position, the new direction and whether an edge was hit.
+/// A position already out of bounds is clamped first, and an axis without
+/// room to move stays at zero without bouncing.
+pub fn advance(position: f32, direction: f32, distance: f32, max: f32) -> (f32, f32, bool) {
+    if max <= 0.0 {
+        return (0.0, direction, false);
+    }
+
+    let mut position = position.clamp(0.0, max) + direction * distance;
+    let mut direction = direction;
+    let mut bounced = false;
+    if position > max {
+        position = 2.0 * max - position;
+        direction = -direction.abs();
+        bounced = true;
+    } else if position < 0.0 {
+        position = -position;
+        direction = direction.abs();
+        bounced = true;
+    }
+    // A step longer than the free space can't overshoot the other edge
+    (position.clamp(0.0, max), direction, bounced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advance_moves_freely_inside_bounds() {
+        assert_eq!(advance(3.0, 1.0, 2.0, 10.0), (5.0, 1.0, false));
+        assert_eq!(advance(3.0, -1.0, 2.0, 10.0), (1.0, -1.0, false));
+        // Touching the edge is not a bounce yet
+        assert_eq!(advance(8.0, 1.0, 2.0, 10.0), (10.0, 1.0, false));
+    }
+
+    #[test]
+    fn test_advance_reflects_off_edges() {
+        assert_eq!(advance(9.0, 1.0, 3.0, 10.0), (8.0, -1.0, true));
+        assert_eq!(advance(1.0, -1.0, 3.0, 10.0), (2.0, 1.0, true));
+    }
+
+    #[test]
+    fn test_advance_clamps_large_steps() {
+        let (position, direction, bounced) = advance(5.0, 1.0, 50.0, 10.0);
+        assert_eq!(position, 0.0);
+        assert_eq!(direction, -1.0);
+        assert!(bounced);
+    }
+
+    #[test]
+    fn test_advance_clamps_after_shrinking() {
+        // The area shrank so the old position is past the new edge
+        assert_eq!(advance(20.0, -1.0, 1.0, 10.0), (9.0, -1.0, false));
+    }
+
+    #[test]
+    fn test_advance_without_room_stays_put() {
+        assert_eq!(advance(4.0, 1.0, 3.0, 0.0), (0.0, 1.0, false));
+    }
+
+    #[test]
+    fn test_update_starts_centered_and_never_clips() {
+        let area = Rect::new(0, 0, 80, 24);
+        let content = Size::new(40, 8);
+        let mut bounce = Bounce::new(0);
+
+        let (rect, bounced) = bounce.update(0, AnimationSpeed::Medium, content, area);
+        assert_eq!((rect.x, rect.y), (20, 8));
+        assert!(!bounced);
+
+        let mut bounces = 0;
+        for frame in 1..2000 {
+            let (rect, bounced) = bounce.update(frame * 100, AnimationSpeed::Fast, content, area);
+            assert!(area.contains(rect.as_position()));
+            assert!(rect.right() <= area.right() && rect.bottom() <= area.bottom());
+            assert_eq!(rect.as_size(), content);
+            bounces += usize::from(bounced);
+        }
+        assert!(bounces > 0);
+    }
+
+    #[test]
+    fn test_update_follows_resize_and_wider_content() {
+        let mut bounce = Bounce::new(0);
+        bounce.update(
+            0,
+            AnimationSpeed::Medium,
+            Size::new(20, 5),
+            Rect::new(0, 0, 100, 40),
+        );
+
+        // A 12h clock is wider and the terminal got smaller
+        let area = Rect::new(0, 0, 50, 10);
+        let content = Size::new(45, 6);
+        let (rect, _) = bounce.update(10, AnimationSpeed::Medium, content, area);
+        assert!(rect.right() <= area.right() && rect.bottom() <= area.bottom());
+    }
+
+    #[test]
+    fn test_faster_speed_drifts_further() {
+        let area = Rect::new(0, 0, 200, 100);
+        let content = Size::new(10, 5);
+        let drift = |speed| {
+            let mut bounce = Bounce::new(0);
+            let (start, _) = bounce.update(0, speed, content, area);
+            let (end, _) = bounce.update(2000, speed, content, area);
+            end.x - start.x
+        };
+        assert!(drift(AnimationSpeed::Fast) > drift(AnimationSpeed::Slow));
+    }
+
+    #[test]
+    fn test_skipped_gap_does_not_jump() {
+        let area = Rect::new(0, 0, 200, 100);
+        let content = Size::new(10, 5);
+        let mut bounce = Bounce::new(0);
+        bounce.update(0, AnimationSpeed::Medium, content, area);
+        let (before, _) = bounce.update(1000, AnimationSpeed::Medium, content, area);
+
+        // Paused for a minute: the clock stays put, then drifts on from there
+        bounce.skip_gap();
+        let (resumed, _) = bounce.update(61_000, AnimationSpeed::Medium, content, area);
+        assert_eq!(resumed, before);
+        let (next, _) = bounce.update(62_000, AnimationSpeed::Medium, content, area);
+        let mut unpaused = Bounce::new(0);
+        unpaused.update(0, AnimationSpeed::Medium, content, area);
+        unpaused.update(1000, AnimationSpeed::Medium, content, area);
+        let (expected, _) = unpaused.update(2000, AnimationSpeed::Medium, content, area);
+        assert_eq!(next, expected);
+    }
+}
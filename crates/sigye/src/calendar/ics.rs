@@ -0,0 +1,272 @@
+//! Minimal iCalendar (RFC 5545) reader for the start, end and summary of events.
+//!
+//! Times are UTC (`20261017T120000Z`), floating local times (`20261017T140000`)
+//! or whole days (`20261017`). Without a timezone database, times with a
+//! `TZID` are read as local time. Recurrence rules are ignored, so a repeating
+//! event only shows its first occurrence.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone};
+
+/// When an event starts or ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsTime {
+    /// A moment in UTC.
+    Utc(NaiveDateTime),
+    /// A wall clock time wherever the clock is, also used for times with a `TZID`.
+    Floating(NaiveDateTime),
+    /// A whole day.
+    Date(NaiveDate),
+}
+
+impl IcsTime {
+    /// Parse a `DATE` or `DATE-TIME` value, e.g. "20261017", "20261017T140000"
+    /// or "20261017T120000Z".
+    fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid date \"{value}\"");
+        let value = value.trim();
+        if let Some(utc) = value.strip_suffix('Z') {
+            return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+                .map(Self::Utc)
+                .map_err(|_| invalid());
+        }
+        if value.contains('T') {
+            return NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+                .map(Self::Floating)
+                .map_err(|_| invalid());
+        }
+        NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(Self::Date)
+            .map_err(|_| invalid())
+    }
+
+    /// The moment in `tz`; a day starts at midnight.
+    pub fn at<Tz: TimeZone>(&self, tz: &Tz) -> DateTime<Tz> {
+        let local = |naive: NaiveDateTime| {
+            // Times skipped by a clock change are taken as UTC rather than dropped
+            tz.from_local_datetime(&naive)
+                .earliest()
+                .unwrap_or_else(|| tz.from_utc_datetime(&naive))
+        };
+        match *self {
+            Self::Utc(naive) => tz.from_utc_datetime(&naive),
+            Self::Floating(naive) => local(naive),
+            Self::Date(date) => local(date.and_time(NaiveTime::MIN)),
+        }
+    }
+}
+
+/// A calendar event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Title of the event.
+    pub summary: String,
+    /// When the event starts.
+    pub start: IcsTime,
+    /// When the event ends (exclusive).
+    pub end: IcsTime,
+}
+
+impl Event {
+    /// Check if the event lasts whole days.
+    pub fn is_all_day(&self) -> bool {
+        matches!(self.start, IcsTime::Date(_))
+    }
+}
+
+/// Join folded lines: a line starting with a space or tab continues the one before.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Undo the escaping of a text value: `\n`, `\,`, `\;` and `\\`.
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push(' '),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Properties read from a `VEVENT`.
+#[derive(Debug, Default)]
+struct EventProperties {
+    summary: Option<String>,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+}
+
+impl EventProperties {
+    /// The event, with a missing end taken from the start: the next day for a
+    /// whole day, the same moment otherwise.
+    fn into_event(self) -> Option<Event> {
+        let start = self.start?;
+        let end = self.end.unwrap_or(match start {
+            IcsTime::Date(date) => IcsTime::Date(date + TimeDelta::days(1)),
+            time => time,
+        });
+        Some(Event {
+            summary: self.summary.unwrap_or_default(),
+            start,
+            end,
+        })
+    }
+}
+
+/// Read the events from the text of an `.ics` file.
+///
+/// Events without a start, and the contents of components nested in events
+/// such as alarms, are skipped. Fails on a start or end that doesn't parse.
+pub fn parse(text: &str) -> Result<Vec<Event>, String> {
+    let mut events = Vec::new();
+    let mut event: Option<EventProperties> = None;
+    // Components opened inside the current event, e.g. VALARM
+    let mut nested = 0;
+    for line in unfold(text) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Parameters such as TZID or VALUE=DATE follow the name
+        let name = name.split(';').next().unwrap_or(name).to_ascii_uppercase();
+        match (name.as_str(), event.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some(EventProperties::default());
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                events.extend(event.take().and_then(EventProperties::into_event));
+            }
+            (_, Some(_)) if nested > 0 => {}
+            ("SUMMARY", Some(event)) => event.summary = Some(unescape(value)),
+            ("DTSTART", Some(event)) => event.start = Some(IcsTime::parse(value)?),
+            ("DTEND", Some(event)) => event.end = Some(IcsTime::parse(value)?),
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Timelike};
+
+    use super::*;
+
+    fn time(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn fixture(name: &str) -> Vec<Event> {
+        let text = match name {
+            "work.ics" => include_str!("../../fixtures/calendar/work.ics"),
+            "holidays.ics" => include_str!("../../fixtures/calendar/holidays.ics"),
+            _ => unreachable!(),
+        };
+        parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_times() {
+        assert_eq!(
+            IcsTime::parse("20261017T120000Z"),
+            Ok(IcsTime::Utc(time("2026-10-17", "12:00")))
+        );
+        assert_eq!(
+            IcsTime::parse("20261017T140500"),
+            Ok(IcsTime::Floating(time("2026-10-17", "14:05")))
+        );
+        assert_eq!(
+            IcsTime::parse("20261017"),
+            Ok(IcsTime::Date(
+                NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+            ))
+        );
+        for invalid in ["", "2026-10-17", "20261017T1200", "20261317", "tomorrow"] {
+            assert!(IcsTime::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_utc_and_floating_times() {
+        let seoul = FixedOffset::east_opt(9 * 3600).unwrap();
+        let events = fixture("work.ics");
+
+        // UTC moves to the clock's timezone
+        let standup = &events[0];
+        assert_eq!(standup.summary, "Standup");
+        assert_eq!(standup.start.at(&seoul).hour(), 9);
+        assert_eq!(standup.end.at(&seoul).minute(), 15);
+        // Floating times and times with a TZID stay on the wall clock
+        let review = &events[1];
+        assert_eq!(review.start, IcsTime::Floating(time("2026-10-17", "14:00")));
+        assert_eq!(review.start.at(&seoul).hour(), 14);
+        assert_eq!(
+            events[2].start,
+            IcsTime::Floating(time("2026-10-17", "16:30"))
+        );
+    }
+
+    #[test]
+    fn test_folded_and_escaped_lines() {
+        let events = fixture("work.ics");
+        // Folded over three lines, with escaped commas and semicolons
+        assert_eq!(
+            events[1].summary,
+            "Design review: calendar, agenda line; and the rest"
+        );
+        assert_eq!(events[2].summary, "1:1");
+    }
+
+    #[test]
+    fn test_nested_components_and_missing_ends() {
+        let events = fixture("work.ics");
+        // The alarm's own summary doesn't replace the event's
+        assert_eq!(events[2].summary, "1:1");
+        // No end: the event is a moment
+        assert_eq!(events[2].end, events[2].start);
+        // No start: skipped
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_all_day_events() {
+        let events = fixture("holidays.ics");
+        assert_eq!(events.len(), 2);
+        let day = |y, m, d| IcsTime::Date(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        // Folded across CRLF line endings
+        assert_eq!(events[0].summary, "Christmas Day");
+        assert!(events[0].is_all_day());
+        assert_eq!(
+            (events[0].start, events[0].end),
+            (day(2026, 12, 25), day(2026, 12, 26))
+        );
+        // Without an end, the event lasts its one day
+        assert_eq!(
+            (events[1].start, events[1].end),
+            (day(2026, 12, 31), day(2027, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_times_fail() {
+        let text = "BEGIN:VEVENT\nDTSTART:soon\nSUMMARY:Oops\nEND:VEVENT\n";
+        assert!(parse(text).is_err());
+        assert_eq!(parse(""), Ok(Vec::new()));
+    }
+}
@@ -0,0 +1,295 @@
+//! Upcoming events from `.ics` calendar files, shown on a line under the clock.
+//!
+//! The files are read in a background thread every few minutes, so edits and
+//! synced calendars show up without a restart.
+
+mod ics;
+
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, TimeZone};
+use sigye_core::TimeFormat;
+
+use crate::status::Status;
+use crate::worker::{JOIN_TIMEOUT, Worker};
+use ics::Event;
+
+/// How often the calendar files are read again (5 minutes).
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Most events shown on the agenda line.
+const MAX_EVENTS: usize = 3;
+
+/// Events starting this soon are highlighted (10 minutes).
+const SOON: TimeDelta = TimeDelta::minutes(10);
+
+/// Separator between the events on the agenda line.
+const SEPARATOR: &str = " · ";
+
+/// Agenda line text, with the events starting soon to highlight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaLine {
+    /// Text of the line, e.g. "14:00 Standup · 16:30 1:1".
+    pub text: String,
+    /// Character range of the events starting soon, if any.
+    pub soon: Option<Range<usize>>,
+}
+
+impl AgendaLine {
+    /// Build the agenda from `events` as of `now`, or `None` when nothing is
+    /// left to come.
+    ///
+    /// Events show until they end, soonest first. Timed events today show
+    /// their start time, later ones the weekday too; all-day events show only
+    /// their summary, with the weekday when they aren't today.
+    pub fn new<Tz: TimeZone>(
+        events: &[Event],
+        now: &DateTime<Tz>,
+        time_format: TimeFormat,
+    ) -> Option<Self>
+    where
+        Tz::Offset: Copy + fmt::Display,
+    {
+        let tz = now.timezone();
+        let mut upcoming: Vec<(DateTime<Tz>, &Event)> = events
+            .iter()
+            .map(|event| (event.start.at(&tz), event))
+            .filter(|(start, event)| *start >= *now || event.end.at(&tz) > *now)
+            .collect();
+        upcoming.sort_by_key(|(start, _)| *start);
+
+        let today = now.date_naive();
+        let time_pattern = match time_format {
+            TimeFormat::TwentyFourHour => "%H:%M",
+            TimeFormat::TwelveHour => "%-I:%M %p",
+        };
+        let mut text = String::new();
+        let mut soon: Option<Range<usize>> = None;
+        for (start, event) in upcoming.into_iter().take(MAX_EVENTS) {
+            if !text.is_empty() {
+                text.push_str(SEPARATOR);
+            }
+            let first = text.chars().count();
+            if start.date_naive() > today {
+                text.push_str(&start.format("%a ").to_string());
+            }
+            if !event.is_all_day() {
+                text.push_str(&start.format(time_pattern).to_string());
+                text.push(' ');
+            }
+            text.push_str(&event.summary);
+            let last = text.chars().count();
+            if !event.is_all_day() && start >= *now && start - *now <= SOON {
+                soon = Some(soon.map_or(first..last, |range| range.start..last));
+            }
+        }
+        (!text.is_empty()).then_some(Self { text, soon })
+    }
+}
+
+/// Read the events from calendar files, and how well that went.
+///
+/// A file that can't be read or parsed is skipped, degrading the status; the
+/// status fails when no file could be read.
+fn load_events(paths: &[String]) -> (Vec<Event>, Status) {
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        let result = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| ics::parse(&text));
+        match result {
+            Ok(file_events) => events.extend(file_events),
+            Err(e) => errors.push(format!("{path}: {e}")),
+        }
+    }
+    let status = match errors.len() {
+        0 => Status::Ok,
+        failed if failed == paths.len() => Status::Failed(errors.join("; ")),
+        _ => Status::Degraded(errors.join("; ")),
+    };
+    (events, status)
+}
+
+/// Calendar monitor that reads the calendar files in a background thread.
+#[derive(Debug)]
+pub struct CalendarMonitor {
+    /// Paths of the `.ics` files.
+    paths: Vec<String>,
+    /// Events from all files, updated by the background thread.
+    events: Arc<RwLock<Vec<Event>>>,
+    /// How well the last read went, updated by the background thread.
+    status: Arc<RwLock<Status>>,
+    /// Reading thread, while running.
+    worker: Mutex<Option<Worker>>,
+}
+
+impl CalendarMonitor {
+    /// Create a calendar monitor for the `.ics` files at `paths`.
+    pub fn new(paths: Vec<String>) -> Self {
+        Self {
+            paths,
+            events: Arc::new(RwLock::new(Vec::new())),
+            status: Arc::new(RwLock::new(Status::Ok)),
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Start the background reading thread.
+    pub fn start(&self) {
+        let Ok(mut worker) = self.worker.lock() else {
+            return;
+        };
+        if worker.is_some() {
+            return; // Already running
+        }
+
+        let paths = self.paths.clone();
+        let events = self.events.clone();
+        let status = self.status.clone();
+
+        *worker = Some(Worker::spawn(move |stop| {
+            loop {
+                let (new_events, new_status) = load_events(&paths);
+                if let Ok(mut events) = events.write() {
+                    *events = new_events;
+                }
+                if let Ok(mut status) = status.write() {
+                    *status = new_status;
+                }
+
+                if stop.wait(REFRESH_INTERVAL) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Stop the background reading thread, waiting briefly for it to finish.
+    pub fn stop(&self) {
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        if let Some(worker) = worker {
+            worker.stop(JOIN_TIMEOUT);
+        }
+    }
+
+    /// Build the agenda line as of `now`, if any events are left to come.
+    pub fn agenda<Tz: TimeZone>(
+        &self,
+        now: &DateTime<Tz>,
+        time_format: TimeFormat,
+    ) -> Option<AgendaLine>
+    where
+        Tz::Offset: Copy + fmt::Display,
+    {
+        let events = self.events.read().ok()?;
+        AgendaLine::new(&events, now, time_format)
+    }
+
+    /// Get how well the last read of the calendar files went.
+    pub fn status(&self) -> Status {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for CalendarMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate};
+
+    use super::*;
+
+    fn seoul(hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .from_local_datetime(&date.and_hms_opt(hour, minute, 0).unwrap())
+            .unwrap()
+    }
+
+    fn events() -> Vec<Event> {
+        let mut events = ics::parse(include_str!("../../fixtures/calendar/work.ics")).unwrap();
+        events.extend(ics::parse(include_str!("../../fixtures/calendar/holidays.ics")).unwrap());
+        events
+    }
+
+    fn agenda(hour: u32, minute: u32) -> Option<AgendaLine> {
+        AgendaLine::new(&events(), &seoul(hour, minute), TimeFormat::TwentyFourHour)
+    }
+
+    #[test]
+    fn test_agenda_shows_the_next_events() {
+        let line = agenda(8, 0).unwrap();
+        assert_eq!(
+            line.text,
+            "09:00 Standup · 14:00 Design review: calendar, agenda line; and the rest · 16:30 1:1"
+        );
+        assert_eq!(line.soon, None);
+
+        // Running events stay until they end, finished ones go
+        let line = agenda(9, 10).unwrap();
+        assert!(line.text.starts_with("09:00 Standup · 14:00 Design"));
+        let line = agenda(15, 0).unwrap();
+        assert_eq!(
+            line.text,
+            "16:30 1:1 · Fri Christmas Day · Thu New Year's Eve"
+        );
+        assert_eq!(agenda(16, 31), agenda(17, 0));
+
+        // Nothing left to come
+        let now = seoul(12, 0) + TimeDelta::days(90);
+        assert_eq!(
+            AgendaLine::new(&events(), &now, TimeFormat::TwentyFourHour),
+            None
+        );
+    }
+
+    #[test]
+    fn test_agenda_highlights_events_starting_soon() {
+        let line = agenda(8, 50).unwrap();
+        assert_eq!(line.soon, Some(0.."09:00 Standup".len()));
+        // Started already: no longer highlighted
+        assert_eq!(agenda(9, 5).unwrap().soon, None);
+        // Ten minutes ahead, past the events already running
+        let line = agenda(16, 20).unwrap();
+        assert_eq!(
+            line.text,
+            "16:30 1:1 · Fri Christmas Day · Thu New Year's Eve"
+        );
+        assert_eq!(line.soon, Some(0..9));
+        assert_eq!(agenda(16, 19).unwrap().soon, None);
+    }
+
+    #[test]
+    fn test_agenda_time_format() {
+        let line = AgendaLine::new(&events(), &seoul(15, 0), TimeFormat::TwelveHour).unwrap();
+        assert!(line.text.starts_with("4:30 PM 1:1"));
+    }
+
+    #[test]
+    fn test_load_events_status() {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/calendar/work.ics");
+        let missing = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/calendar/missing.ics");
+
+        let (events, status) = load_events(&[fixture.to_string()]);
+        assert_eq!((events.len(), status), (3, Status::Ok));
+        let (events, status) = load_events(&[fixture.to_string(), missing.to_string()]);
+        assert_eq!(events.len(), 3);
+        assert!(matches!(status, Status::Degraded(reason) if reason.contains("missing.ics")));
+        let (events, status) = load_events(&[missing.to_string()]);
+        assert!(events.is_empty());
+        assert!(matches!(status, Status::Failed(_)));
+    }
+}
@@ -0,0 +1,182 @@
+//! Recording rendered frames as an asciinema v2 cast file.
+//!
+//! A cast is a JSON header line followed by one JSON array per event: the
+//! seconds since the start, the event type and its data. Each frame is written
+//! as an output event that redraws the whole screen from the top-left corner.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Size;
+
+use crate::ansi::ansi_lines;
+
+/// Writes frames to a cast file.
+#[derive(Debug)]
+pub struct CastWriter<W: Write> {
+    /// Where the cast is written.
+    out: W,
+    /// Screen size of the last frame.
+    size: Size,
+}
+
+impl<W: Write> CastWriter<W> {
+    /// Start a cast for a screen of `size`, recorded at the Unix time `timestamp`.
+    pub fn new(mut out: W, size: Size, timestamp: i64) -> io::Result<Self> {
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {timestamp}}}",
+            size.width, size.height
+        )?;
+        Ok(Self { out, size })
+    }
+
+    /// Add a frame shown `time` after the start, resizing the screen if it changed.
+    pub fn frame(&mut self, time: Duration, buf: &Buffer) -> io::Result<()> {
+        let size = buf.area.as_size();
+        if size != self.size {
+            self.event(time, "r", &format!("{}x{}", size.width, size.height))?;
+            self.size = size;
+        }
+        // Home the cursor and clear what is left of each row from the last frame
+        let mut data = String::from("\x1b[H");
+        data.push_str(&ansi_lines(buf).join("\x1b[K\r\n"));
+        data.push_str("\x1b[K");
+        self.event(time, "o", &data)
+    }
+
+    /// Write everything buffered so far.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    /// Write one event line.
+    fn event(&mut self, time: Duration, kind: &str, data: &str) -> io::Result<()> {
+        let data = serde_json::to_string(data)?;
+        writeln!(self.out, "[{:.6}, \"{kind}\", {data}]", time.as_secs_f64())
+    }
+}
+
+/// A cast recorded to a file for a fixed time, starting with the first frame.
+#[derive(Debug)]
+pub struct Recording {
+    /// Path of the cast file.
+    path: PathBuf,
+    /// How long to record.
+    duration: Duration,
+    /// Writer and the time of the first frame, once recording has started.
+    started: Option<(CastWriter<BufWriter<File>>, Instant)>,
+}
+
+impl Recording {
+    /// Record to `path` for `duration`; the file is created on the first frame.
+    pub fn new(path: PathBuf, duration: Duration) -> Self {
+        Self {
+            path,
+            duration,
+            started: None,
+        }
+    }
+
+    /// Add a frame, starting the cast with its size if it is the first.
+    pub fn frame(&mut self, buf: &Buffer) -> io::Result<()> {
+        if self.is_done() {
+            return Ok(());
+        }
+        let (writer, start) = match &mut self.started {
+            Some(started) => started,
+            None => {
+                let out = BufWriter::new(File::create(&self.path)?);
+                let timestamp = chrono::Utc::now().timestamp();
+                let writer = CastWriter::new(out, buf.area.as_size(), timestamp)?;
+                self.started.insert((writer, Instant::now()))
+            }
+        };
+        writer.frame(start.elapsed(), buf)
+    }
+
+    /// Check if the recording time is over.
+    pub fn is_done(&self) -> bool {
+        self.started
+            .as_ref()
+            .is_some_and(|(_, start)| start.elapsed() >= self.duration)
+    }
+
+    /// Write out the frames recorded so far.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.started {
+            Some((writer, _)) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn lines(cast: &[u8]) -> Vec<Value> {
+        String::from_utf8(cast.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_header_and_frames() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+        buf.set_string(0, 0, "12", Style::new().fg(Color::Cyan));
+        let mut cast = Vec::new();
+        let mut writer = CastWriter::new(&mut cast, Size::new(4, 2), 1_700_000_000).unwrap();
+        writer.frame(Duration::ZERO, &buf).unwrap();
+        buf.set_string(0, 1, "34", Style::new());
+        writer.frame(Duration::from_millis(1_500), &buf).unwrap();
+
+        let lines = lines(&cast);
+        assert_eq!(
+            lines[0],
+            json!({"version": 2, "width": 4, "height": 2, "timestamp": 1_700_000_000})
+        );
+        assert_eq!(
+            lines[1],
+            json!([0.0, "o", "\x1b[H\x1b[38;5;6m12\x1b[0m\x1b[K\r\n\x1b[K"])
+        );
+        assert_eq!(
+            lines[2],
+            json!([1.5, "o", "\x1b[H\x1b[38;5;6m12\x1b[0m\x1b[K\r\n34\x1b[K"])
+        );
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_resize_event() {
+        let mut cast = Vec::new();
+        let mut writer = CastWriter::new(&mut cast, Size::new(4, 2), 0).unwrap();
+        writer
+            .frame(
+                Duration::from_secs(2),
+                &Buffer::empty(Rect::new(0, 0, 6, 1)),
+            )
+            .unwrap();
+        writer
+            .frame(
+                Duration::from_secs(3),
+                &Buffer::empty(Rect::new(0, 0, 6, 1)),
+            )
+            .unwrap();
+
+        let lines = lines(&cast);
+        assert_eq!(lines[1], json!([2.0, "r", "6x1"]));
+        assert_eq!(lines[2], json!([2.0, "o", "\x1b[H\x1b[K"]));
+        assert_eq!(lines[3][1], "o");
+        assert_eq!(lines.len(), 4);
+    }
+}
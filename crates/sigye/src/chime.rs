@@ -0,0 +1,297 @@
+//! Chimes on the hour and each quarter hour: the terminal bell and an optional command.
+
+use std::process::Stdio;
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Timelike};
+use sigye_core::schedule::TimeRange;
+
+use crate::shell;
+use crate::status::Status;
+
+/// Time between the bells of one chime, so they are heard separately.
+const BELL_SPACING: Duration = Duration::from_millis(600);
+
+/// How many minutes late a chime may still sound, e.g. after a stalled frame.
+/// Older ones are dropped, so waking from sleep doesn't chime for the past.
+const MAX_DELAY_MINUTES: i64 = 1;
+
+/// What a chime marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strike {
+    /// The full hour.
+    Hour,
+    /// A quarter past, half past or a quarter to.
+    Quarter,
+}
+
+impl Strike {
+    /// Get the name passed to the command in `SIGYE_CHIME`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Strike::Hour => "hour",
+            Strike::Quarter => "quarter",
+        }
+    }
+}
+
+/// Decides when to chime and rings the bells.
+#[derive(Debug)]
+pub struct Chime {
+    /// Whether the quarter hours chime too.
+    quarters: bool,
+    /// Times the bell rings on the hour.
+    bells: u32,
+    /// Shell command run on each chime (empty = none).
+    command: String,
+    /// Time range without chimes.
+    quiet_hours: Option<TimeRange>,
+    /// Last minute checked, counted from the epoch in local time.
+    last_minute: Option<i64>,
+    /// Bells still to ring for the latest chime.
+    bells_left: u32,
+    /// When the bell last rang for the latest chime.
+    last_bell: Option<Instant>,
+    /// How the last command went.
+    status: Arc<RwLock<Status>>,
+}
+
+impl Chime {
+    /// Create a chime; nothing sounds until the first boundary after the first check.
+    pub fn new(
+        quarters: bool,
+        bells: u32,
+        command: String,
+        quiet_hours: Option<TimeRange>,
+    ) -> Self {
+        Self {
+            quarters,
+            bells,
+            command,
+            quiet_hours,
+            last_minute: None,
+            bells_left: 0,
+            last_bell: None,
+            status: Arc::new(RwLock::new(Status::Ok)),
+        }
+    }
+
+    /// Check the local time, returning the chime due since the last check.
+    ///
+    /// Every minute since the last check counts, so a boundary isn't missed
+    /// when no frame lands on its first second.
+    pub fn check(&mut self, now: NaiveDateTime) -> Option<Strike> {
+        let minute = now.and_utc().timestamp().div_euclid(60);
+        let last = self.last_minute.replace(minute)?;
+        // Nothing new, or the clock went back
+        if minute <= last {
+            return None;
+        }
+        let first = (last + 1).max(minute - MAX_DELAY_MINUTES);
+        let strike = (first..=minute).rev().find_map(|m| self.strike_at(m))?;
+        self.bells_left = match strike {
+            Strike::Hour => self.bells,
+            Strike::Quarter => self.bells.min(1),
+        };
+        self.last_bell = None;
+        Some(strike)
+    }
+
+    /// The chime due at the start of `minute`, if any and outside the quiet hours.
+    fn strike_at(&self, minute: i64) -> Option<Strike> {
+        let time = DateTime::from_timestamp(minute * 60, 0)?.naive_utc();
+        let strike = match time.minute() {
+            0 => Strike::Hour,
+            15 | 30 | 45 if self.quarters => Strike::Quarter,
+            _ => return None,
+        };
+        let quiet = self
+            .quiet_hours
+            .as_ref()
+            .is_some_and(|range| range.contains(time.weekday(), time.hour() * 60 + time.minute()));
+        (!quiet).then_some(strike)
+    }
+
+    /// Check if a bell should ring now, counting it as rung.
+    pub fn take_bell(&mut self, now: Instant) -> bool {
+        if self.bells_left == 0 {
+            return false;
+        }
+        let due = self
+            .last_bell
+            .is_none_or(|last| now.duration_since(last) >= BELL_SPACING);
+        if due {
+            self.bells_left -= 1;
+            self.last_bell = Some(now);
+        }
+        due
+    }
+
+    /// Run the command for `strike` in the background, if there is one.
+    pub fn run_command(&self, strike: Strike) {
+        if self.command.is_empty() {
+            return;
+        }
+        let command = self.command.clone();
+        let status = Arc::clone(&self.status);
+        thread::spawn(move || {
+            let result = run(&command, strike);
+            if let Ok(mut status) = status.write() {
+                *status = result;
+            }
+        });
+    }
+
+    /// How the last command went.
+    pub fn status(&self) -> Status {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Run `command` for `strike` and wait for it.
+fn run(command: &str, strike: Strike) -> Status {
+    let result = shell::command(command)
+        .env("SIGYE_CHIME", strike.name())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match result {
+        Ok(exit) if exit.success() => Status::Ok,
+        Ok(exit) => Status::Failed(format!("chime command failed: {exit}")),
+        Err(e) => Status::Failed(format!("failed to run chime command: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// 2025-01-15 (a Wednesday) at the given time.
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, 15)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn chime_with(quarters: bool, quiet_hours: &str) -> Chime {
+        let quiet_hours = (!quiet_hours.is_empty()).then(|| TimeRange::parse(quiet_hours).unwrap());
+        Chime::new(quarters, 3, String::new(), quiet_hours)
+    }
+
+    #[test]
+    fn test_no_chime_at_startup() {
+        let mut chime = chime_with(true, "");
+        assert_eq!(chime.check(at(9, 0, 0)), None);
+        assert_eq!(chime.check(at(9, 0, 30)), None);
+        assert!(!chime.take_bell(Instant::now()));
+    }
+
+    #[test]
+    fn test_hour_rollover() {
+        let mut chime = chime_with(false, "");
+        chime.check(at(8, 59, 59));
+        assert_eq!(chime.check(at(9, 0, 0)), Some(Strike::Hour));
+        // Once per hour
+        assert_eq!(chime.check(at(9, 0, 1)), None);
+        assert_eq!(chime.check(at(9, 1, 0)), None);
+    }
+
+    #[test]
+    fn test_missed_frames_still_chime() {
+        // No frame on the full minute
+        let mut chime = chime_with(false, "");
+        chime.check(at(8, 59, 58));
+        assert_eq!(chime.check(at(9, 0, 2)), Some(Strike::Hour));
+
+        // A stall past the boundary minute
+        let mut chime = chime_with(false, "");
+        chime.check(at(8, 59, 58));
+        assert_eq!(chime.check(at(9, 1, 10)), Some(Strike::Hour));
+
+        // Too late, e.g. after sleeping
+        let mut chime = chime_with(false, "");
+        chime.check(at(8, 30, 0));
+        assert_eq!(chime.check(at(9, 5, 0)), None);
+    }
+
+    #[test]
+    fn test_clock_going_back() {
+        let mut chime = chime_with(false, "");
+        chime.check(at(10, 0, 30));
+        assert_eq!(chime.check(at(9, 0, 30)), None);
+        assert_eq!(chime.check(at(9, 1, 0)), None);
+        // The hour chimes again when the clock reaches it again, e.g. when summer time ends
+        assert_eq!(chime.check(at(9, 59, 59)), None);
+        assert_eq!(chime.check(at(10, 0, 0)), Some(Strike::Hour));
+    }
+
+    #[test]
+    fn test_quarters() {
+        let mut without = chime_with(false, "");
+        let mut with = chime_with(true, "");
+        without.check(at(9, 14, 59));
+        with.check(at(9, 14, 59));
+        assert_eq!(without.check(at(9, 15, 0)), None);
+        assert_eq!(with.check(at(9, 15, 0)), Some(Strike::Quarter));
+        with.check(at(9, 29, 59));
+        assert_eq!(with.check(at(9, 30, 0)), Some(Strike::Quarter));
+        with.check(at(9, 59, 59));
+        assert_eq!(with.check(at(10, 0, 0)), Some(Strike::Hour));
+    }
+
+    #[test]
+    fn test_overnight_quiet_hours() {
+        let mut chime = chime_with(true, "22:00-07:00");
+        for (hour, minute, strike) in [
+            (21, 45, Some(Strike::Quarter)),
+            (22, 0, None),
+            (23, 0, None),
+            (0, 0, None),
+            (6, 45, None),
+            (7, 0, Some(Strike::Hour)),
+            (12, 0, Some(Strike::Hour)),
+        ] {
+            chime.check(at(hour, minute, 0) - chrono::Duration::seconds(1));
+            assert_eq!(chime.check(at(hour, minute, 0)), strike, "{hour}:{minute}");
+        }
+    }
+
+    #[test]
+    fn test_bells_are_spaced() {
+        let mut chime = chime_with(true, "");
+        let start = Instant::now();
+        chime.check(at(8, 59, 59));
+        chime.check(at(9, 0, 0));
+        assert!(chime.take_bell(start));
+        assert!(!chime.take_bell(start + BELL_SPACING / 2));
+        assert!(chime.take_bell(start + BELL_SPACING));
+        assert!(chime.take_bell(start + BELL_SPACING * 2));
+        assert!(!chime.take_bell(start + BELL_SPACING * 3));
+
+        // Quarters ring once
+        chime.check(at(9, 14, 59));
+        chime.check(at(9, 15, 0));
+        assert!(chime.take_bell(start));
+        assert!(!chime.take_bell(start + BELL_SPACING));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_status() {
+        assert_eq!(
+            run(r#"test "$SIGYE_CHIME" = hour"#, Strike::Hour),
+            Status::Ok
+        );
+        assert_eq!(
+            run(r#"test "$SIGYE_CHIME" = hour"#, Strike::Quarter),
+            Status::Failed("chime command failed: exit status: 1".to_string())
+        );
+    }
+}
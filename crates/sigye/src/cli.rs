@@ -0,0 +1,208 @@
+//! Command-line arguments.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::NaiveTime;
+use clap::Parser;
+use sigye_core::{BackgroundStyle, ClockPosition, WeatherSource, precipitation};
+
+use crate::alarm::Alarm;
+use crate::weather::Location;
+
+/// A beautiful terminal clock with ASCII art fonts.
+#[derive(Debug, Default, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Add an alarm for this session, e.g. "07:30", "7:30pm" or "07:30=Stand-up" (repeatable).
+    #[arg(long = "alarm", value_name = "TIME[=LABEL]", value_parser = Alarm::parse)]
+    pub alarms: Vec<Alarm>,
+
+    /// Background style for this session, e.g. "matrix-rain" or "Starfield", with an
+    /// optional rain or snow intensity from 0.0 to 1.0, e.g. "rainy:0.8".
+    #[arg(long, value_name = "NAME[:INTENSITY]", value_parser = parse_background_arg)]
+    pub background: Option<BackgroundArg>,
+
+    /// Extra background style drawn over the background, e.g. "snowfall" (repeatable).
+    #[arg(long = "layer", value_name = "NAME", value_parser = parse_layer)]
+    pub layers: Vec<BackgroundStyle>,
+
+    /// Clock position for this session, e.g. "center", "top-left" or "bottom".
+    #[arg(long, value_name = "POSITION", value_parser = parse_position)]
+    pub position: Option<ClockPosition>,
+
+    /// Weather location for this session: a place name or "lat,lon" coordinates.
+    /// Overrides the configured location and IP auto-detection.
+    #[arg(long, value_name = "PLACE|LAT,LON", value_parser = Location::parse)]
+    pub location: Option<Location>,
+
+    /// Preferred weather provider for this session: "wttr" or "open-meteo".
+    #[arg(long, value_name = "PROVIDER", value_parser = parse_weather_provider)]
+    pub weather_provider: Option<WeatherSource>,
+
+    /// Preset to start with, e.g. "hacker", "cozy" or one from the config.
+    /// Other options on the command line win over it.
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// Message shown in a box next to the clock for this session, e.g. "ON AIR".
+    #[arg(long, value_name = "TEXT")]
+    pub banner: Option<String>,
+
+    /// File whose contents are shown as the banner, read again when it changes.
+    #[arg(long, value_name = "PATH", conflicts_with = "banner")]
+    pub banner_file: Option<PathBuf>,
+
+    /// Draw without colors. Also enabled by the NO_COLOR environment variable.
+    #[arg(long)]
+    pub mono: bool,
+
+    /// Draw only ASCII characters. Also enabled by a non-UTF-8 locale.
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Draw thick white digits on black without a background, for low vision.
+    /// Overrides the accessibility mode in the config for this session.
+    #[arg(long, conflicts_with = "plain")]
+    pub high_contrast: bool,
+
+    /// Show the time and date as plain lines of text that screen readers can
+    /// read. Overrides the accessibility mode in the config for this session.
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Print the clock to stdout as plain text and exit, for scripts and banners.
+    #[arg(long)]
+    pub once: bool,
+
+    /// With --once, color the output with ANSI escapes in the configured theme.
+    #[arg(long, requires = "once")]
+    pub color: bool,
+
+    /// With --once, center the output in this many columns.
+    #[arg(long, value_name = "COLUMNS", requires = "once")]
+    pub width: Option<usize>,
+
+    /// With --once, show this time instead of now, e.g. "14:30" or "14:30:05".
+    #[arg(long, value_name = "HH:MM[:SS]", value_parser = parse_time, requires = "once")]
+    pub time: Option<NaiveTime>,
+
+    /// With --once, leave out the date line.
+    #[arg(long, requires = "once")]
+    pub no_date: bool,
+
+    /// Also write the rendered clock as plain text to this file, FIFO or Unix socket
+    /// every second, each frame preceded by its length in bytes on a line of its own.
+    #[arg(long, value_name = "PATH")]
+    pub serve_text: Option<PathBuf>,
+
+    /// Include the background in frames written with --serve-text.
+    #[arg(long, requires = "serve_text")]
+    pub serve_bg: bool,
+
+    /// Serve counters about sigye itself in the Prometheus text format at
+    /// /metrics on this address, e.g. "127.0.0.1:9184".
+    #[arg(long, value_name = "ADDR:PORT", conflicts_with_all = ["once", "export"])]
+    pub metrics_listen: Option<SocketAddr>,
+
+    /// Save one frame at the terminal size to this file and exit: ANSI-colored
+    /// text for a .ans or .ansi file, plain text otherwise.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["once", "record"])]
+    pub export: Option<PathBuf>,
+
+    /// Record the session to this file as an asciinema cast, then exit.
+    #[arg(long, value_name = "FILE", conflicts_with = "once")]
+    pub record: Option<PathBuf>,
+
+    /// With --record, how long to record, e.g. "10s", "2m" or "1m30s".
+    #[arg(long, value_name = "DURATION", default_value = "10s", value_parser = parse_duration, requires = "record")]
+    pub duration: Duration,
+
+    /// Seed the background animations, so runs with the same seed animate the same way.
+    #[arg(long, value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Print the background style names, one per line, and exit.
+    #[arg(long, conflicts_with = "list_themes")]
+    pub list_backgrounds: bool,
+
+    /// Print the color theme names, one per line, and exit.
+    #[arg(long)]
+    pub list_themes: bool,
+}
+
+/// Background style from `--background`, with an optional precipitation intensity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundArg {
+    /// Background style.
+    pub style: BackgroundStyle,
+    /// How heavily rain and snow fall (0.0 - 1.0), instead of the weather's.
+    pub intensity: Option<f32>,
+}
+
+/// Parse a background style name with an optional intensity, e.g. "rainy:0.8".
+fn parse_background_arg(value: &str) -> Result<BackgroundArg, String> {
+    let (name, intensity) = match value.split_once(':') {
+        Some((name, intensity)) => (name, Some(precipitation::parse_intensity(intensity)?)),
+        None => (value, None),
+    };
+    Ok(BackgroundArg {
+        style: name.parse()?,
+        intensity,
+    })
+}
+
+/// Parse a background style that can be used as an extra layer.
+fn parse_layer(name: &str) -> Result<BackgroundStyle, String> {
+    let style: BackgroundStyle = name.parse()?;
+    if style.can_layer() {
+        Ok(style)
+    } else {
+        Err(format!("{} can't be used as a layer", style.display_name()))
+    }
+}
+
+/// Parse a clock position name.
+fn parse_position(name: &str) -> Result<ClockPosition, String> {
+    ClockPosition::by_name(name).ok_or_else(|| {
+        "unknown clock position, expected one of: center, top-left, top, top-right, \
+         bottom-left, bottom, bottom-right"
+            .to_string()
+    })
+}
+
+/// Parse a 24-hour time of day, with or without seconds.
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| "expected a 24-hour time like 14:30 or 14:30:05".to_string())
+}
+
+/// Parse a duration of whole minutes and seconds, e.g. "90s", "2m" or "1m30s";
+/// a bare number is seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let error = || "expected a duration like 10s, 2m or 1m30s".to_string();
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    let mut secs = 0;
+    let mut rest = value;
+    for (unit, scale) in [('m', 60), ('s', 1)] {
+        if let Some((number, after)) = rest.split_once(unit) {
+            secs += number.parse::<u64>().map_err(|_| error())? * scale;
+            rest = after;
+        }
+    }
+    if !rest.is_empty() || secs == 0 {
+        return Err(error());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Parse a weather provider name.
+fn parse_weather_provider(name: &str) -> Result<WeatherSource, String> {
+    WeatherSource::by_name(name)
+        .ok_or_else(|| "unknown weather provider, expected one of: wttr, open-meteo".to_string())
+}
@@ -0,0 +1,250 @@
+//! Checking the system clock against a time server, so a clock that drifted
+//! can be flagged or corrected.
+//!
+//! The check runs in a background thread every few hours, asking an NTP
+//! server and falling back to the `Date` header of an HTTP response. When
+//! both fail the drift is unknown and nothing is shown.
+
+mod sntp;
+
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+use crate::worker::{JOIN_TIMEOUT, Worker};
+
+/// How often the clock is checked (3 hours).
+const CHECK_INTERVAL: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// How soon a failed check is tried again (15 minutes).
+const RETRY_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// How long to wait for a time server's answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// HTTP `Date` headers are cut to the second; the true time is half a second
+/// later on average.
+const DATE_ROUNDING: TimeDelta = TimeDelta::milliseconds(500);
+
+/// Measured offset of the system clock from a reference clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    /// How far the system time must move to be right, positive when behind.
+    pub offset: TimeDelta,
+    /// Server or URL the reference time came from.
+    pub source: String,
+}
+
+impl Drift {
+    /// Indicator for the corner, e.g. " clock 40.2 s slow ", when the offset
+    /// is at least `threshold_secs`.
+    pub fn indicator(&self, threshold_secs: f32, corrected: bool) -> Option<String> {
+        let secs = self.offset.as_seconds_f64();
+        if secs.abs() < f64::from(threshold_secs) {
+            return None;
+        }
+        let direction = if secs > 0.0 { "slow" } else { "fast" };
+        let correction = if corrected { ", corrected" } else { "" };
+        Some(format!(
+            " clock {:.1} s {direction}{correction} ",
+            secs.abs()
+        ))
+    }
+}
+
+/// `now` by the reference clock, when correcting the system time by `drift`.
+pub fn correct<Tz: TimeZone>(now: DateTime<Tz>, drift: Option<&Drift>) -> DateTime<Tz> {
+    match drift {
+        Some(drift) => now + drift.offset,
+        None => now,
+    }
+}
+
+/// Offset of the system clock from the time in an HTTP `Date` header, for a
+/// request sent at `sent` and answered at `received`.
+fn date_offset(
+    date: &str,
+    sent: DateTime<Utc>,
+    received: DateTime<Utc>,
+) -> Result<TimeDelta, String> {
+    let reference = DateTime::parse_from_rfc2822(date)
+        .map_err(|e| format!("bad Date header \"{date}\": {e}"))?
+        .to_utc()
+        + DATE_ROUNDING;
+    let midpoint = sent + (received - sent) / 2;
+    Ok(reference - midpoint)
+}
+
+/// Ask `url` for the time in the `Date` header of its response.
+fn query_http(url: &str) -> Result<TimeDelta, String> {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .new_agent();
+    let sent = Utc::now();
+    let response = agent.head(url).call().map_err(|e| format!("{url}: {e}"))?;
+    let received = Utc::now();
+    let date = response
+        .headers()
+        .get("date")
+        .and_then(|date| date.to_str().ok())
+        .ok_or_else(|| format!("{url}: no Date header"))?;
+    date_offset(date, sent, received).map_err(|e| format!("{url}: {e}"))
+}
+
+/// Measure the drift from `server`, or from `url` when the server can't be
+/// reached and a URL is set.
+fn measure(server: &str, url: &str) -> Result<Drift, String> {
+    match sntp::query(server, REQUEST_TIMEOUT) {
+        Ok(offset) => Ok(Drift {
+            offset,
+            source: server.to_string(),
+        }),
+        Err(e) if url.is_empty() => Err(e),
+        Err(e) => query_http(url)
+            .map(|offset| Drift {
+                offset,
+                source: url.to_string(),
+            })
+            .map_err(|http_e| format!("{e}; {http_e}")),
+    }
+}
+
+/// Clock check that measures the drift in a background thread.
+#[derive(Debug)]
+pub struct ClockCheck {
+    /// NTP server, as a host with an optional port.
+    server: String,
+    /// URL for the HTTP fallback, empty for none.
+    url: String,
+    /// Last measured drift, `None` while unknown.
+    drift: Arc<RwLock<Option<Drift>>>,
+    /// Checking thread, while running.
+    worker: Mutex<Option<Worker>>,
+}
+
+impl ClockCheck {
+    /// Create a clock check against `server`, falling back to `url` if set.
+    pub fn new(server: String, url: String) -> Self {
+        Self {
+            server,
+            url,
+            drift: Arc::new(RwLock::new(None)),
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Start the background checking thread.
+    pub fn start(&self) {
+        let Ok(mut worker) = self.worker.lock() else {
+            return;
+        };
+        if worker.is_some() {
+            return; // Already running
+        }
+
+        let server = self.server.clone();
+        let url = self.url.clone();
+        let drift = self.drift.clone();
+
+        *worker = Some(Worker::spawn(move |stop| {
+            loop {
+                // A failed check leaves the drift unknown rather than stale
+                let measured = measure(&server, &url).ok();
+                let wait = if measured.is_some() {
+                    CHECK_INTERVAL
+                } else {
+                    RETRY_INTERVAL
+                };
+                if let Ok(mut drift) = drift.write() {
+                    *drift = measured;
+                }
+
+                if stop.wait(wait) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Stop the background checking thread, waiting briefly for it to finish.
+    pub fn stop(&self) {
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        if let Some(worker) = worker {
+            worker.stop(JOIN_TIMEOUT);
+        }
+    }
+
+    /// Get the last measured drift, `None` while unknown.
+    pub fn drift(&self) -> Option<Drift> {
+        self.drift.read().ok()?.clone()
+    }
+}
+
+impl Drop for ClockCheck {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Timelike};
+
+    use super::*;
+
+    fn drift(millis: i64) -> Drift {
+        Drift {
+            offset: TimeDelta::milliseconds(millis),
+            source: "pool.ntp.org".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_indicator_threshold() {
+        assert_eq!(drift(1_999).indicator(2.0, false), None);
+        assert_eq!(
+            drift(40_230).indicator(2.0, false).as_deref(),
+            Some(" clock 40.2 s slow ")
+        );
+        assert_eq!(
+            drift(-2_500).indicator(2.0, true).as_deref(),
+            Some(" clock 2.5 s fast, corrected ")
+        );
+        assert_eq!(
+            drift(-1_000).indicator(0.5, false).as_deref(),
+            Some(" clock 1.0 s fast ")
+        );
+    }
+
+    #[test]
+    fn test_correct_moves_the_shown_time() {
+        let offset = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = offset.with_ymd_and_hms(2026, 10, 17, 23, 59, 30).unwrap();
+
+        // A clock 40 s slow shows the next day once corrected
+        let corrected = correct(now, Some(&drift(40_000)));
+        assert_eq!(
+            (corrected.hour(), corrected.minute(), corrected.second()),
+            (0, 0, 10)
+        );
+        assert_eq!(corrected.offset(), now.offset());
+        assert_eq!(correct(now, Some(&drift(-30_000))).minute(), 59);
+        assert_eq!(correct(now, Some(&drift(-30_000))).second(), 0);
+        // Unknown drift leaves the time alone
+        assert_eq!(correct(now, None), now);
+    }
+
+    #[test]
+    fn test_date_offset() {
+        let sent = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let received = sent + TimeDelta::milliseconds(200);
+        // The server's second started 40 s after ours; the midpoint is 0.1 s in
+        let offset = date_offset("Sat, 17 Oct 2026 12:00:40 GMT", sent, received).unwrap();
+        assert_eq!(offset, TimeDelta::milliseconds(40_400));
+        let offset = date_offset("Sat, 17 Oct 2026 11:59:57 +0000", sent, received).unwrap();
+        assert_eq!(offset, TimeDelta::milliseconds(-2_600));
+        assert!(date_offset("yesterday", sent, received).is_err());
+    }
+}
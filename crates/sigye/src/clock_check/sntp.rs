@@ -0,0 +1,264 @@
+//! Minimal SNTP client (RFC 4330): one request, one reply, and the offset of
+//! the system clock worked out from the four timestamps.
+
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Size of an NTP packet without extensions.
+const PACKET_SIZE: usize = 48;
+
+/// Port NTP servers listen on.
+const NTP_PORT: u16 = 123;
+
+/// Seconds from the NTP epoch (1900) to the Unix epoch (1970).
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+/// Seconds in an NTP era, after which the seconds field wraps (next in 2036).
+const NTP_ERA: i64 = 1 << 32;
+
+/// First byte of a request: no leap warning, version 4, client mode.
+const CLIENT_HEADER: u8 = (4 << 3) | 3;
+
+/// Mode of a server's reply.
+const SERVER_MODE: u8 = 4;
+
+/// Leap indicator of a server whose own clock isn't synchronized.
+const UNSYNCHRONIZED: u8 = 3;
+
+/// Encode an instant as a 64-bit NTP timestamp: seconds since 1900 in the
+/// high half, fractions of a second in the low half.
+pub fn to_ntp(time: DateTime<Utc>) -> u64 {
+    let seconds = (time.timestamp() + NTP_UNIX_OFFSET).rem_euclid(NTP_ERA) as u64;
+    let fraction = (u64::from(time.timestamp_subsec_nanos()) << 32) / 1_000_000_000;
+    (seconds << 32) | fraction
+}
+
+/// Decode a 64-bit NTP timestamp, `None` for zero (unset).
+///
+/// Seconds below half an era are taken to be past the 2036 wrap, so times
+/// from 1968 to 2104 decode correctly.
+pub fn from_ntp(timestamp: u64) -> Option<DateTime<Utc>> {
+    if timestamp == 0 {
+        return None;
+    }
+    let mut seconds = (timestamp >> 32) as i64;
+    if seconds < NTP_ERA / 2 {
+        seconds += NTP_ERA;
+    }
+    let nanos = ((timestamp & 0xffff_ffff) * 1_000_000_000) >> 32;
+    DateTime::from_timestamp(seconds - NTP_UNIX_OFFSET, nanos as u32)
+}
+
+/// Build a request sent at `transmit`, which the server echoes back.
+pub fn request(transmit: DateTime<Utc>) -> [u8; PACKET_SIZE] {
+    let mut packet = [0; PACKET_SIZE];
+    packet[0] = CLIENT_HEADER;
+    packet[40..48].copy_from_slice(&to_ntp(transmit).to_be_bytes());
+    packet
+}
+
+/// The parts of a server's reply the offset is worked out from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reply {
+    /// Distance of the server from a reference clock, 1 for a primary server.
+    pub stratum: u8,
+    /// Transmit timestamp of the request, echoed back as is.
+    pub originate: u64,
+    /// When the server received the request.
+    pub receive: DateTime<Utc>,
+    /// When the server sent the reply.
+    pub transmit: DateTime<Utc>,
+}
+
+/// Read the timestamp at `offset` in a packet.
+fn timestamp_at(packet: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&packet[offset..offset + 8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Parse a server's reply, rejecting ones that can't be trusted for the time.
+pub fn parse_reply(packet: &[u8]) -> Result<Reply, String> {
+    if packet.len() < PACKET_SIZE {
+        return Err(format!("short reply ({} bytes)", packet.len()));
+    }
+    let leap = packet[0] >> 6;
+    let mode = packet[0] & 0b111;
+    let stratum = packet[1];
+    if mode != SERVER_MODE {
+        return Err(format!("not a server reply (mode {mode})"));
+    }
+    if stratum == 0 {
+        let code = String::from_utf8_lossy(&packet[12..16]);
+        return Err(format!("server refused the request ({code})"));
+    }
+    if leap == UNSYNCHRONIZED {
+        return Err("server clock is not synchronized".to_string());
+    }
+    let (Some(receive), Some(transmit)) = (
+        from_ntp(timestamp_at(packet, 32)),
+        from_ntp(timestamp_at(packet, 40)),
+    ) else {
+        return Err("reply without timestamps".to_string());
+    };
+    Ok(Reply {
+        stratum,
+        originate: timestamp_at(packet, 24),
+        receive,
+        transmit,
+    })
+}
+
+/// Offset of the reference clock from the system clock: how far the system
+/// time must move to be right, positive when it is behind.
+///
+/// The request went out at `sent` and the reply came in at `received`, both by
+/// the system clock; the network delay is taken to be the same both ways.
+pub fn offset(sent: DateTime<Utc>, reply: &Reply, received: DateTime<Utc>) -> TimeDelta {
+    ((reply.receive - sent) + (reply.transmit - received)) / 2
+}
+
+/// Ask `server`, a host with an optional port, for the offset of the system clock.
+pub fn query(server: &str, timeout: Duration) -> Result<TimeDelta, String> {
+    let address = (server, NTP_PORT)
+        .to_socket_addrs()
+        .or_else(|_| server.to_socket_addrs())
+        .map_err(|e| format!("{server}: {e}"))?
+        .next()
+        .ok_or_else(|| format!("{server}: no address"))?;
+    let local = if address.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local).map_err(|e| e.to_string())?;
+    socket
+        .set_read_timeout(Some(timeout))
+        .map_err(|e| e.to_string())?;
+
+    let sent = Utc::now();
+    let packet = request(sent);
+    socket
+        .send_to(&packet, address)
+        .map_err(|e| format!("{server}: {e}"))?;
+    let mut buf = [0; 128];
+    let (len, _) = socket
+        .recv_from(&mut buf)
+        .map_err(|e| format!("{server}: {e}"))?;
+    let received = Utc::now();
+
+    let reply = parse_reply(&buf[..len]).map_err(|e| format!("{server}: {e}"))?;
+    // A reply to some other request would give a made-up offset
+    if reply.originate != to_ntp(sent) {
+        return Err(format!("{server}: reply doesn't match the request"));
+    }
+    Ok(offset(sent, &reply, received))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    /// 2026-10-17 12:00:00 UTC, when the fixture request was sent.
+    fn sent() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap()
+    }
+
+    /// Reply from a stratum 2 server whose clock is 40.4375 s ahead of ours:
+    /// received at 12:00:40.5 and sent at 12:00:40.625 by its clock.
+    const REPLY: [u8; PACKET_SIZE] = [
+        0x24, 0x02, 0x03, 0xe9, // Version 4, server mode, stratum 2, poll, precision
+        0x00, 0x00, 0x01, 0x20, // Root delay
+        0x00, 0x00, 0x00, 0x4a, // Root dispersion
+        0xc0, 0xa8, 0x01, 0x01, // Reference ID
+        0xee, 0x7d, 0xe1, 0x80, 0x00, 0x00, 0x00, 0x00, // Reference timestamp
+        0xee, 0x7d, 0xe1, 0xc0, 0x00, 0x00, 0x00, 0x00, // Originate: our 12:00:00
+        0xee, 0x7d, 0xe1, 0xe8, 0x80, 0x00, 0x00, 0x00, // Receive: 12:00:40.5
+        0xee, 0x7d, 0xe1, 0xe8, 0xa0, 0x00, 0x00, 0x00, // Transmit: 12:00:40.625
+    ];
+
+    #[test]
+    fn test_request_packet() {
+        let packet = request(sent());
+        assert_eq!(packet[0], 0x23);
+        assert!(packet[1..40].iter().all(|&byte| byte == 0));
+        assert_eq!(packet[40..48], REPLY[24..32]);
+    }
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        assert_eq!(to_ntp(sent()), 0xee7d_e1c0_0000_0000);
+        assert_eq!(
+            from_ntp(0xee7d_e1e8_8000_0000),
+            Some(sent() + TimeDelta::milliseconds(40_500))
+        );
+        assert_eq!(from_ntp(0), None);
+
+        let precise = sent() + TimeDelta::nanoseconds(123_456_789);
+        let back = from_ntp(to_ntp(precise)).unwrap();
+        assert!((back - precise).abs() <= TimeDelta::nanoseconds(1));
+
+        // Past the 2036 wrap of the seconds field
+        let later = Utc.with_ymd_and_hms(2040, 1, 1, 0, 0, 0).unwrap();
+        assert!(to_ntp(later) >> 32 < 1 << 31);
+        assert_eq!(from_ntp(to_ntp(later)), Some(later));
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = parse_reply(&REPLY).unwrap();
+        assert_eq!(reply.stratum, 2);
+        assert_eq!(reply.originate, to_ntp(sent()));
+        assert_eq!(reply.receive, sent() + TimeDelta::milliseconds(40_500));
+        assert_eq!(reply.transmit, sent() + TimeDelta::milliseconds(40_625));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_replies() {
+        assert!(parse_reply(&REPLY[..40]).unwrap_err().contains("short"));
+
+        let mut client = REPLY;
+        client[0] = 0x23;
+        assert!(parse_reply(&client).unwrap_err().contains("mode 3"));
+
+        let mut kiss = REPLY;
+        kiss[1] = 0;
+        kiss[12..16].copy_from_slice(b"RATE");
+        assert!(parse_reply(&kiss).unwrap_err().contains("RATE"));
+
+        let mut unsynchronized = REPLY;
+        unsynchronized[0] |= 0b1100_0000;
+        assert!(
+            parse_reply(&unsynchronized)
+                .unwrap_err()
+                .contains("synchronized")
+        );
+
+        let mut blank = REPLY;
+        blank[40..48].fill(0);
+        assert!(parse_reply(&blank).is_err());
+    }
+
+    #[test]
+    fn test_offset() {
+        let reply = parse_reply(&REPLY).unwrap();
+        // The reply came back a quarter second after the request went out
+        let received = sent() + TimeDelta::milliseconds(250);
+        assert_eq!(
+            offset(sent(), &reply, received),
+            TimeDelta::microseconds(40_437_500)
+        );
+
+        // A clock that's ahead gets a negative offset
+        let ahead = Reply {
+            receive: sent() - TimeDelta::seconds(3),
+            transmit: sent() - TimeDelta::seconds(3),
+            ..reply
+        };
+        assert_eq!(offset(sent(), &ahead, sent()), TimeDelta::seconds(-3));
+    }
+}
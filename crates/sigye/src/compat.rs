@@ -0,0 +1,67 @@
+//! Compatibility modes for terminals without color or Unicode support.
+
+use std::env;
+use std::ffi::OsStr;
+
+/// Check if the `NO_COLOR` convention asks for no color (set and not empty).
+pub fn no_color_requested() -> bool {
+    is_no_color(env::var_os("NO_COLOR").as_deref())
+}
+
+/// Check if the locale rules out UTF-8, so only ASCII can be shown safely.
+pub fn locale_lacks_utf8() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].map(env::var);
+    lacks_utf8(locale.iter().filter_map(|value| value.as_deref().ok()))
+}
+
+/// Whether a `NO_COLOR` value disables color.
+fn is_no_color(value: Option<&OsStr>) -> bool {
+    value.is_some_and(|value| !value.is_empty())
+}
+
+/// Whether the effective locale (the first non-empty of `LC_ALL`, `LC_CTYPE`
+/// and `LANG`) is known and not UTF-8. Without any locale set, UTF-8 is assumed.
+fn lacks_utf8<'a>(values: impl IntoIterator<Item = &'a str>) -> bool {
+    let Some(locale) = values.into_iter().find(|value| !value.is_empty()) else {
+        return false;
+    };
+    let locale = locale.to_ascii_lowercase();
+    !(locale.contains("utf-8") || locale.contains("utf8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use sigye_background::ascii_fallback;
+    use sigye_fonts::FontRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_no_color() {
+        assert!(!is_no_color(None));
+        assert!(!is_no_color(Some(OsStr::new(""))));
+        assert!(is_no_color(Some(OsStr::new("1"))));
+    }
+
+    #[test]
+    fn test_locale_detection() {
+        assert!(!lacks_utf8([]));
+        assert!(!lacks_utf8(["en_US.UTF-8"]));
+        assert!(!lacks_utf8(["", "ko_KR.utf8"]));
+        assert!(lacks_utf8(["C"]));
+        assert!(lacks_utf8(["POSIX", "en_US.UTF-8"]));
+        assert!(lacks_utf8(["", "", "en_US.ISO-8859-1"]));
+    }
+
+    #[test]
+    fn test_fonts_render_as_ascii() {
+        let registry = FontRegistry::new();
+        for name in registry.list_fonts() {
+            let lines = registry.get_or_default(name).render_text("12:34:56 PM");
+            for line in lines {
+                let ascii: String = line.chars().map(ascii_fallback).collect();
+                assert!(ascii.is_ascii(), "{name}: {ascii}");
+            }
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Debug overlay with frame timings, toggled with F12 or `D`.
+//!
+//! A dimmed box in the top right corner, over everything else, shows how fast
+//! frames are drawn and what they cost, next to the background and its seed,
+//! the terminal size, system metrics and the weather status.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    Frame,
+    layout::{Margin, Rect, Size},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use sigye_background::scale_color;
+use sigye_core::{BackgroundStyle, SystemMetrics};
+use unicode_width::UnicodeWidthStr;
+
+use crate::weather::FetchStatus;
+
+/// Frames the rolling stats are taken over.
+const FRAME_WINDOW: usize = 120;
+
+/// How much of its brightness the frame under the box keeps.
+const UNDERLAY_DIM: f32 = 0.3;
+
+/// Narrowest box drawn; smaller terminals leave the overlay out.
+const MIN_WIDTH: u16 = 16;
+
+/// When and how fast the most recent frames were drawn.
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    /// When each frame started drawing and how long it took, oldest first.
+    frames: VecDeque<(Instant, Duration)>,
+}
+
+impl FrameStats {
+    /// Record a frame that started drawing at `started` and took `render_time`,
+    /// dropping the oldest once the window is full.
+    pub fn record(&mut self, started: Instant, render_time: Duration) {
+        if self.frames.len() == FRAME_WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((started, render_time));
+    }
+
+    /// Frames drawn per second over the window, `None` before two frames.
+    pub fn fps(&self) -> Option<f32> {
+        let (first, _) = self.frames.front()?;
+        let (last, _) = self.frames.back()?;
+        let span = last.saturating_duration_since(*first).as_secs_f32();
+        (span > 0.0).then(|| (self.frames.len() - 1) as f32 / span)
+    }
+
+    /// Render time of the latest frame.
+    pub fn last(&self) -> Option<Duration> {
+        self.frames.back().map(|&(_, render_time)| render_time)
+    }
+
+    /// Mean render time over the window.
+    pub fn average(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frames.len()).ok().filter(|&n| n > 0)?;
+        let total: Duration = self
+            .frames
+            .iter()
+            .map(|&(_, render_time)| render_time)
+            .sum();
+        Some(total / count)
+    }
+
+    /// Render time 95% of the frames in the window stay within (nearest rank).
+    pub fn p95(&self) -> Option<Duration> {
+        let mut times: Vec<Duration> = self
+            .frames
+            .iter()
+            .map(|&(_, render_time)| render_time)
+            .collect();
+        times.sort_unstable();
+        let rank = (times.len() * 95).div_ceil(100);
+        times.get(rank.checked_sub(1)?).copied()
+    }
+}
+
+/// Everything the overlay shows.
+#[derive(Debug)]
+pub struct DebugInfo<'a> {
+    /// Timings of the recent frames.
+    pub stats: &'a FrameStats,
+    /// Background style drawn.
+    pub background: BackgroundStyle,
+    /// Seed of the background animations.
+    pub seed: u64,
+    /// Terminal size.
+    pub size: Size,
+    /// Latest system metrics, `None` while not monitored.
+    pub metrics: Option<&'a SystemMetrics>,
+    /// Status of the last weather fetch, `None` while not fetched.
+    pub weather: Option<&'a FetchStatus>,
+}
+
+impl DebugInfo<'_> {
+    /// Label and value of each row.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let millis = |time: Duration| format!("{:.2}", time.as_secs_f64() * 1000.0);
+        let unknown = || "–".to_string();
+        let frame = match (self.stats.last(), self.stats.average(), self.stats.p95()) {
+            (Some(last), Some(average), Some(p95)) => format!(
+                "{} ms (avg {}, p95 {})",
+                millis(last),
+                millis(average),
+                millis(p95)
+            ),
+            _ => unknown(),
+        };
+        let metrics = self.metrics.map_or_else(
+            || "off".to_string(),
+            |metrics| {
+                format!(
+                    "CPU {:.0}% · mem {:.0}% · net ↓{:.0}% ↑{:.0}%",
+                    metrics.cpu_usage * 100.0,
+                    metrics.memory_usage * 100.0,
+                    metrics.network_rx_rate * 100.0,
+                    metrics.network_tx_rate * 100.0
+                )
+            },
+        );
+        let weather = match self.weather {
+            None => "off".to_string(),
+            Some(FetchStatus::Idle) => "waiting".to_string(),
+            Some(FetchStatus::Fetching) => "fetching".to_string(),
+            Some(FetchStatus::Live) => "live".to_string(),
+            Some(FetchStatus::Failed(error)) => format!("failed: {error}"),
+        };
+        vec![
+            (
+                "FPS",
+                self.stats
+                    .fps()
+                    .map_or_else(unknown, |fps| format!("{fps:.1}")),
+            ),
+            ("Frame", frame),
+            (
+                "Background",
+                format!("{} · seed {}", self.background.display_name(), self.seed),
+            ),
+            (
+                "Terminal",
+                format!("{}×{}", self.size.width, self.size.height),
+            ),
+            ("Metrics", metrics),
+            ("Weather", weather),
+        ]
+    }
+}
+
+/// Draw the overlay in the top right corner of `area` over what is there,
+/// dimming it rather than clearing it. Left out if `area` is too small.
+pub fn render_overlay(frame: &mut Frame, area: Rect, info: &DebugInfo, accent_color: Color) {
+    let rows = info.rows();
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| label.width())
+        .max()
+        .unwrap_or(0);
+    let content_width = rows
+        .iter()
+        .map(|(_, value)| label_width + 1 + value.width())
+        .max()
+        .unwrap_or(0);
+    let width = u16::try_from(content_width + 4)
+        .unwrap_or(u16::MAX)
+        .min(area.width);
+    let height = (rows.len() as u16 + 2).min(area.height);
+    if width < MIN_WIDTH || height < 3 {
+        return;
+    }
+    let box_area = Rect::new(area.right() - width, area.y, width, height);
+
+    let buf = frame.buffer_mut();
+    for position in box_area.positions() {
+        if let Some(cell) = buf.cell_mut(position) {
+            cell.fg = scale_color(cell.fg, UNDERLAY_DIM);
+        }
+    }
+
+    // Long values are cut at the border rather than wrapped
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{label:<label_width$} "),
+                    Style::default().fg(accent_color).bold(),
+                ),
+                Span::styled(value, Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+    let block = Block::default()
+        .title(" Debug ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent_color));
+    let inner = block.inner(box_area);
+    frame.render_widget(block, box_area);
+    frame.render_widget(Paragraph::new(lines), inner.inner(Margin::new(1, 0)));
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend, style::Stylize};
+
+    use super::*;
+
+    /// Stats of frames drawn every `interval`, taking `render_times` in turn.
+    fn stats(interval: Duration, render_times: &[u64]) -> FrameStats {
+        let start = Instant::now();
+        let mut stats = FrameStats::default();
+        for (index, &millis) in render_times.iter().enumerate() {
+            stats.record(
+                start + interval * index as u32,
+                Duration::from_millis(millis),
+            );
+        }
+        stats
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let stats = FrameStats::default();
+        assert_eq!(stats.fps(), None);
+        assert_eq!(stats.last(), None);
+        assert_eq!(stats.average(), None);
+        assert_eq!(stats.p95(), None);
+        // One frame has a time but no rate yet
+        let one = self::stats(Duration::from_millis(50), &[4]);
+        assert_eq!(one.fps(), None);
+        assert_eq!(one.p95(), Some(Duration::from_millis(4)));
+    }
+
+    #[test]
+    fn test_fps_and_average() {
+        let stats = stats(Duration::from_millis(50), &[2, 4, 6, 8, 10]);
+        assert!((stats.fps().unwrap() - 20.0).abs() < 0.01);
+        assert_eq!(stats.last(), Some(Duration::from_millis(10)));
+        assert_eq!(stats.average(), Some(Duration::from_millis(6)));
+    }
+
+    #[test]
+    fn test_p95_is_nearest_rank() {
+        // 1..=100 ms in shuffled order: the 95th smallest
+        let times: Vec<u64> = (1..=100).map(|n| (n * 37) % 100 + 1).collect();
+        let stats = stats(Duration::from_millis(10), &times);
+        assert_eq!(stats.p95(), Some(Duration::from_millis(95)));
+        // Too few frames for a tail: the slowest
+        let few = self::stats(Duration::from_millis(10), &[1, 9, 3]);
+        assert_eq!(few.p95(), Some(Duration::from_millis(9)));
+    }
+
+    #[test]
+    fn test_window_keeps_the_latest_frames() {
+        let times: Vec<u64> = (0..FRAME_WINDOW as u64 + 30)
+            .map(|n| if n < 30 { 1000 } else { 2 })
+            .collect();
+        let stats = stats(Duration::from_millis(100), &times);
+        // The slow frames have left the window
+        assert_eq!(stats.frames.len(), FRAME_WINDOW);
+        assert_eq!(stats.average(), Some(Duration::from_millis(2)));
+        assert_eq!(stats.p95(), Some(Duration::from_millis(2)));
+        assert!((stats.fps().unwrap() - 10.0).abs() < 0.01);
+    }
+
+    fn info(stats: &FrameStats) -> DebugInfo<'_> {
+        DebugInfo {
+            stats,
+            background: BackgroundStyle::Starfield,
+            seed: 42,
+            size: Size::new(80, 24),
+            metrics: None,
+            weather: Some(&FetchStatus::Live),
+        }
+    }
+
+    #[test]
+    fn test_rows() {
+        let stats = stats(Duration::from_millis(100), &[1, 3]);
+        let rows = info(&stats).rows();
+        let value = |label| rows.iter().find(|(l, _)| *l == label).unwrap().1.clone();
+        assert_eq!(value("FPS"), "10.0");
+        assert_eq!(value("Frame"), "3.00 ms (avg 2.00, p95 3.00)");
+        assert_eq!(value("Background"), "Starfield · seed 42");
+        assert_eq!(value("Terminal"), "80×24");
+        assert_eq!(value("Metrics"), "off");
+        assert_eq!(value("Weather"), "live");
+    }
+
+    #[test]
+    fn test_overlay_sits_in_the_corner_over_the_frame() {
+        let stats = stats(Duration::from_millis(100), &[1, 3]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let buffer = terminal
+            .draw(|frame| {
+                let text = vec![Line::from("x".repeat(80)); 24];
+                frame.render_widget(Paragraph::new(text).fg(Color::White), frame.area());
+                render_overlay(frame, frame.area(), &info(&stats), Color::Cyan);
+            })
+            .unwrap()
+            .buffer
+            .clone();
+        let row = |y: u16| (0..80).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(row(0).ends_with('┐') && row(0).contains(" Debug "));
+        assert!(row(1).contains("FPS") && row(1).ends_with('│'));
+        assert!(row(7).ends_with('┘'));
+        assert_eq!(buffer[(79, 0)].fg, Color::Cyan);
+        // The frame shows through dimmed beside the text, and untouched outside
+        assert_eq!(buffer[(78, 1)].symbol(), "x");
+        assert_eq!(buffer[(78, 1)].fg, Color::Rgb(77, 77, 77));
+        assert_eq!(buffer[(0, 1)].fg, Color::White);
+        assert_eq!(row(8), "x".repeat(80));
+    }
+
+    #[test]
+    fn test_tiny_terminals_do_not_panic() {
+        let stats = stats(Duration::from_millis(100), &[1, 3]);
+        for (width, height) in [(0, 0), (1, 1), (15, 24), (80, 2), (16, 3), (30, 5)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| render_overlay(frame, frame.area(), &info(&stats), Color::Cyan))
+                .unwrap();
+        }
+    }
+}
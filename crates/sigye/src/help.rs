@@ -0,0 +1,484 @@
+//! Auto-hiding help line and the full key binding overlay.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::KeyCode;
+use unicode_width::UnicodeWidthStr;
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::keys::{Action, ActionGroup, Keymap};
+use crate::status::StatusRegistry;
+
+/// Gap between the two columns of the overlay.
+const COLUMN_GAP: usize = 4;
+
+/// Rows a page key scrolls the overlay by.
+const PAGE_ROWS: i16 = 10;
+
+/// A group's heading in the help overlay and the actions under it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlaySection {
+    /// Group of the actions.
+    pub group: ActionGroup,
+    /// Keys and action of each entry, e.g. ("q / Esc", Quit).
+    pub entries: Vec<(String, Action)>,
+}
+
+/// Sections of the help overlay, one per group with keys bound, each listing
+/// its actions in the order of [`Action::ALL`]. Unbound actions are left out.
+pub fn overlay_sections(keymap: &Keymap) -> Vec<OverlaySection> {
+    ActionGroup::ALL
+        .into_iter()
+        .map(|group| OverlaySection {
+            group,
+            entries: Action::ALL
+                .into_iter()
+                .filter(|action| action.group() == group)
+                .map(|action| (keymap.label(action, " / "), action))
+                .filter(|(keys, _)| !keys.is_empty())
+                .collect(),
+        })
+        .filter(|section| !section.entries.is_empty())
+        .collect()
+}
+
+/// Number of sections of `heights` rows to put in the left column so the
+/// taller column is as short as it can be, with a blank row between sections.
+pub fn column_break(heights: &[usize]) -> usize {
+    let column = |sections: &[usize]| -> usize {
+        sections.iter().sum::<usize>() + sections.len().saturating_sub(1)
+    };
+    (1..=heights.len())
+        .min_by_key(|&split| column(&heights[..split]).max(column(&heights[split..])))
+        .unwrap_or(0)
+}
+
+/// Widths of the keys and of whole entries in a column of `sections`.
+fn column_widths(sections: &[OverlaySection]) -> (usize, usize) {
+    let entries = sections.iter().flat_map(|section| &section.entries);
+    let key_width = entries
+        .clone()
+        .map(|(keys, _)| keys.width())
+        .max()
+        .unwrap_or(0);
+    let text_width = entries
+        .map(|(_, action)| action.description().len())
+        .chain(sections.iter().map(|section| section.group.title().len()))
+        .max()
+        .unwrap_or(0);
+    (key_width, key_width + 2 + text_width)
+}
+
+/// Rows a key scrolls the overlay by, `None` for keys that close it.
+pub fn scroll_step(code: KeyCode) -> Option<i16> {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => Some(-1),
+        KeyCode::Down | KeyCode::Char('j') => Some(1),
+        KeyCode::PageUp => Some(-PAGE_ROWS),
+        KeyCode::PageDown => Some(PAGE_ROWS),
+        KeyCode::Home => Some(i16::MIN),
+        KeyCode::End => Some(i16::MAX),
+        _ => None,
+    }
+}
+
+/// Actions pinned to the help line from their `[keys]` names, with warnings
+/// about names that aren't actions.
+pub fn pinned_actions(names: &[String]) -> (Vec<Action>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut actions = Vec::new();
+    for name in names {
+        match Action::from_name(name) {
+            Some(action) if !actions.contains(&action) => actions.push(action),
+            Some(_) => {}
+            None => warnings.push(format!(
+                "Ignoring help_line entry \"{name}\" from config: unknown action"
+            )),
+        }
+    }
+    (actions, warnings)
+}
+
+/// Visibility of the help line and the help overlay.
+#[derive(Debug)]
+pub struct HelpState {
+    /// When the last key was pressed, or the start before any key.
+    last_input: Instant,
+    /// When the user last pressed a key or clicked, `None` before the first input.
+    last_user_input: Option<Instant>,
+    /// Idle time before the help line hides, zero to always show it.
+    idle_timeout: Duration,
+    /// Whether the key binding overlay is shown.
+    pub overlay_visible: bool,
+    /// Rows the overlay is scrolled down by, when it is taller than the screen.
+    overlay_scroll: u16,
+}
+
+impl HelpState {
+    /// Create a help state that starts with the help line shown.
+    pub fn new(idle_timeout: Duration, now: Instant) -> Self {
+        Self {
+            last_input: now,
+            last_user_input: None,
+            idle_timeout,
+            overlay_visible: false,
+            overlay_scroll: 0,
+        }
+    }
+
+    /// Record a key press, bringing the help line back.
+    pub fn on_input(&mut self, now: Instant) {
+        self.last_input = now;
+        self.last_user_input = Some(now);
+    }
+
+    /// Check if the user pressed a key or clicked less than `window` before `now`.
+    pub fn input_within(&self, window: Duration, now: Instant) -> bool {
+        self.last_user_input
+            .is_some_and(|at| now.saturating_duration_since(at) < window)
+    }
+
+    /// Toggle the key binding overlay, which opens at the top.
+    pub fn toggle_overlay(&mut self) {
+        self.overlay_visible = !self.overlay_visible;
+        self.overlay_scroll = 0;
+    }
+
+    /// Scroll the overlay by `rows`, up when negative; drawing it stops the
+    /// scrolling at its end.
+    pub fn scroll_overlay(&mut self, rows: i16) {
+        self.overlay_scroll = self.overlay_scroll.saturating_add_signed(rows);
+    }
+
+    /// Check if the help line is shown at `now`.
+    pub fn line_visible(&self, now: Instant) -> bool {
+        self.hides_in(now).is_some() || self.idle_timeout.is_zero()
+    }
+
+    /// Time left until the help line hides, or `None` if it is hidden or never hides.
+    pub fn hides_in(&self, now: Instant) -> Option<Duration> {
+        if self.idle_timeout.is_zero() {
+            return None;
+        }
+        let idle = now.saturating_duration_since(self.last_input);
+        self.idle_timeout
+            .checked_sub(idle)
+            .filter(|left| !left.is_zero())
+    }
+
+    /// Render the key binding overlay as a centered popup, if visible.
+    ///
+    /// Bound actions are listed under their group, in two columns when the
+    /// screen is wide enough, scrolling when it is too short. Subsystems that
+    /// aren't working normally are listed below the keys.
+    pub fn render_overlay(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        accent_color: Color,
+        keymap: &Keymap,
+        status: &StatusRegistry,
+    ) {
+        if !self.overlay_visible {
+            return;
+        }
+
+        let sections = overlay_sections(keymap);
+        let (key_width, column_width) = column_widths(&sections);
+
+        // Each section is its heading over its keys, the headings in line with
+        // the descriptions; each column lines up its own keys
+        let column_lines = |sections: &[OverlaySection]| -> Vec<Line<'static>> {
+            let (key_width, _) = column_widths(sections);
+            let mut lines = Vec::new();
+            for (index, section) in sections.iter().enumerate() {
+                if index > 0 {
+                    lines.push(Line::default());
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(" ".repeat(key_width + 2)),
+                    Span::styled(section.group.title(), Style::default().bold()),
+                ]));
+                lines.extend(section.entries.iter().map(|(keys, action)| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{keys:>key_width$}  "),
+                            Style::default().fg(accent_color).bold(),
+                        ),
+                        Span::styled(action.description(), Style::default().dark_gray()),
+                    ])
+                }));
+            }
+            lines
+        };
+
+        // Two columns when they fit beside each other in the popup
+        let heights: Vec<usize> = sections
+            .iter()
+            .map(|section| section.entries.len() + 1)
+            .collect();
+        let split = column_break(&heights);
+        let (left, right) = sections.split_at(split);
+        let (_, left_width) = column_widths(left);
+        let (_, right_width) = column_widths(right);
+        let two_columns_width = left_width + COLUMN_GAP + right_width;
+        let (mut lines, mut content_width) =
+            if !right.is_empty() && two_columns_width + 6 <= usize::from(area.width) {
+                let (left, right) = (column_lines(left), column_lines(right));
+                let rows = (0..left.len().max(right.len()))
+                    .map(|row| {
+                        let mut line = left.get(row).cloned().unwrap_or_default();
+                        if let Some(right) = right.get(row) {
+                            let padding = left_width + COLUMN_GAP - line.width();
+                            line.spans.push(Span::raw(" ".repeat(padding)));
+                            line.spans.extend(right.spans.iter().cloned());
+                        }
+                        line
+                    })
+                    .collect();
+                (rows, two_columns_width)
+            } else {
+                (column_lines(&sections), column_width)
+            };
+
+        // Problems go under the keys, named in the same column as the keys
+        if !status.is_ok() {
+            lines.push(Line::default());
+        }
+        for (subsystem, problem) in status.problems() {
+            let detail = format!(
+                "{}: {}",
+                problem.label(),
+                problem.reason().unwrap_or_default()
+            );
+            content_width = content_width.max(key_width + 2 + detail.len());
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>key_width$}  ", subsystem.display_name()),
+                    Style::default().fg(accent_color).bold(),
+                ),
+                Span::styled(detail, Style::default().yellow()),
+            ]));
+        }
+        let content_width = content_width as u16;
+
+        // Calculate centered popup area
+        let popup_width = (content_width + 6).min(area.width.saturating_sub(4));
+        let popup_height = (lines.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        // Center the list with a blank row above it, scrolled no further than its end
+        let visible_rows = popup_height.saturating_sub(3);
+        let max_scroll = (lines.len() as u16).saturating_sub(visible_rows);
+        self.overlay_scroll = self.overlay_scroll.min(max_scroll);
+        let hint = if max_scroll > 0 {
+            " ↑/↓ to scroll · any other key to close "
+        } else {
+            " any key to close "
+        };
+
+        // Clear the area behind the popup
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Keys ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(hint).centered().dark_gray())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent_color));
+        let inner_area = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [_, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner_area);
+        let list_area = list_area.centered_horizontally(Constraint::Length(content_width));
+        frame.render_widget(
+            Paragraph::new(lines).scroll((self.overlay_scroll, 0)),
+            list_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    fn default_keymap() -> Keymap {
+        Keymap::from_config(&BTreeMap::new()).0
+    }
+
+    #[test]
+    fn test_every_action_is_in_the_overlay_once() {
+        let sections = overlay_sections(&default_keymap());
+        let listed: Vec<Action> = sections
+            .iter()
+            .flat_map(|section| section.entries.iter().map(|(_, action)| *action))
+            .collect();
+        assert_eq!(listed.len(), Action::ALL.len());
+        for action in Action::ALL {
+            assert_eq!(
+                listed.iter().filter(|&&listed| listed == action).count(),
+                1,
+                "{}",
+                action.name()
+            );
+            assert!(!action.description().is_empty(), "{}", action.name());
+        }
+        // Each under its own group, the groups in order
+        for section in &sections {
+            assert!(
+                section
+                    .entries
+                    .iter()
+                    .all(|(_, action)| action.group() == section.group)
+            );
+        }
+        let groups: Vec<ActionGroup> = sections.iter().map(|section| section.group).collect();
+        assert_eq!(groups, ActionGroup::ALL);
+    }
+
+    #[test]
+    fn test_overlay_leaves_out_unbound_actions() {
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            "cycle_background".to_string(),
+            sigye_config::KeyBinding::Many(Vec::new()),
+        );
+        keys.insert(
+            "pick_background".to_string(),
+            sigye_config::KeyBinding::Many(Vec::new()),
+        );
+        let (keymap, _) = Keymap::from_config(&keys);
+        let sections = overlay_sections(&keymap);
+        // A group left without keys has no heading either
+        assert!(
+            sections
+                .iter()
+                .all(|section| section.group != ActionGroup::Backgrounds)
+        );
+    }
+
+    #[test]
+    fn test_column_break_balances_the_columns() {
+        assert_eq!(column_break(&[12, 10, 3, 7]), 1);
+        assert_eq!(column_break(&[5, 5, 5, 5]), 2);
+        assert_eq!(column_break(&[3, 3, 14]), 2);
+        assert_eq!(column_break(&[8]), 1);
+        assert_eq!(column_break(&[]), 0);
+    }
+
+    #[test]
+    fn test_pinned_actions() {
+        let names = ["quit", "Cycle_Theme", "nap", "quit"].map(String::from);
+        let (actions, warnings) = pinned_actions(&names);
+        assert_eq!(actions, vec![Action::Quit, Action::CycleTheme]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("\"nap\""));
+    }
+
+    #[test]
+    fn test_scroll_keys() {
+        assert_eq!(scroll_step(KeyCode::Down), Some(1));
+        assert_eq!(scroll_step(KeyCode::Char('k')), Some(-1));
+        assert_eq!(scroll_step(KeyCode::PageDown), Some(PAGE_ROWS));
+        assert_eq!(scroll_step(KeyCode::Char('q')), None);
+        assert_eq!(scroll_step(KeyCode::Esc), None);
+    }
+
+    #[test]
+    fn test_overlay_scrolls_no_further_than_its_end() {
+        let keymap = default_keymap();
+        let status = StatusRegistry::default();
+        let mut help = HelpState::new(Duration::from_secs(10), Instant::now());
+        help.toggle_overlay();
+        let draw = |help: &mut HelpState, width, height| {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| {
+                    help.render_overlay(frame, frame.area(), Color::Cyan, &keymap, &status)
+                })
+                .unwrap();
+        };
+
+        // A short screen scrolls, stopping with the last row at the bottom
+        help.scroll_overlay(i16::MAX);
+        draw(&mut help, 60, 20);
+        let max_scroll = help.overlay_scroll;
+        assert!(max_scroll > 0);
+        help.scroll_overlay(-1);
+        draw(&mut help, 60, 20);
+        assert_eq!(help.overlay_scroll, max_scroll - 1);
+
+        // Two columns on a wide screen fit without scrolling
+        draw(&mut help, 160, 30);
+        assert_eq!(help.overlay_scroll, 0);
+
+        // Reopening starts at the top
+        help.scroll_overlay(3);
+        help.toggle_overlay();
+        help.toggle_overlay();
+        assert_eq!(help.overlay_scroll, 0);
+    }
+
+    #[test]
+    fn test_help_line_hides_after_idle_timeout() {
+        let start = Instant::now();
+        let help = HelpState::new(Duration::from_secs(10), start);
+
+        assert!(help.line_visible(start));
+        assert_eq!(help.hides_in(start), Some(Duration::from_secs(10)));
+        assert_eq!(
+            help.hides_in(start + Duration::from_secs(4)),
+            Some(Duration::from_secs(6))
+        );
+        assert!(!help.line_visible(start + Duration::from_secs(10)));
+        assert_eq!(help.hides_in(start + Duration::from_secs(30)), None);
+    }
+
+    #[test]
+    fn test_key_press_brings_help_line_back() {
+        let start = Instant::now();
+        let mut help = HelpState::new(Duration::from_secs(10), start);
+        let later = start + Duration::from_secs(60);
+        assert!(!help.line_visible(later));
+
+        help.on_input(later);
+        assert!(help.line_visible(later));
+        assert!(help.line_visible(later + Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn test_input_within() {
+        let start = Instant::now();
+        let mut help = HelpState::new(Duration::from_secs(10), start);
+        // Starting up doesn't count as input
+        assert!(!help.input_within(Duration::from_secs(10), start));
+
+        let later = start + Duration::from_secs(60);
+        help.on_input(later);
+        assert!(help.input_within(Duration::from_secs(10), later));
+        assert!(help.input_within(Duration::from_secs(10), later + Duration::from_secs(9)));
+        assert!(!help.input_within(Duration::from_secs(10), later + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_zero_timeout_always_shows_help_line() {
+        let start = Instant::now();
+        let help = HelpState::new(Duration::ZERO, start);
+        assert!(help.line_visible(start + Duration::from_secs(3600)));
+        assert_eq!(help.hides_in(start), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Hooks run when a timer finishes or an alarm goes off.
+//!
+//! A configured shell command runs in the background with the label and
+//! duration in `SIGYE_LABEL` and `SIGYE_DURATION`. Without one, builds with
+//! the `notify` feature send a desktop notification instead. Failures are
+//! reported back to the app, which shows them as toasts.
+
+use std::process::Stdio;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::alarm::Alarm;
+use crate::shell;
+use crate::status::Status;
+
+/// Whether this build can send desktop notifications.
+pub const NOTIFICATIONS: bool = cfg!(feature = "notify");
+
+/// A timer that finished or an alarm that went off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// What finished, e.g. "Timer 25:00" or an alarm's label.
+    pub label: String,
+    /// How long the timer ran, `None` for alarms.
+    pub duration: Option<Duration>,
+}
+
+impl Completion {
+    /// A timer of `duration` that reached zero.
+    pub fn timer(label: String, duration: Duration) -> Self {
+        Self {
+            label,
+            duration: Some(duration),
+        }
+    }
+
+    /// An alarm that went off, labelled with its time when it has no label.
+    pub fn alarm(alarm: &Alarm) -> Self {
+        let label = if alarm.label.is_empty() {
+            format!("Alarm {alarm}")
+        } else {
+            alarm.label.clone()
+        };
+        Self {
+            label,
+            duration: None,
+        }
+    }
+
+    /// Variables passed to the command: `SIGYE_LABEL`, and `SIGYE_DURATION`
+    /// in whole seconds for timers.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![("SIGYE_LABEL", self.label.clone())];
+        if let Some(duration) = self.duration {
+            env.push(("SIGYE_DURATION", duration.as_secs().to_string()));
+        }
+        env
+    }
+
+    /// Text of the toast and the desktop notification, e.g. "Timer 25:00 done".
+    pub fn message(&self) -> String {
+        match self.duration {
+            Some(_) => format!("{} done", self.label),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// What is done for a completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook<'a> {
+    /// Run this shell command.
+    Command(&'a str),
+    /// Send a desktop notification.
+    Notification,
+    /// Nothing beyond the toast and the bell.
+    None,
+}
+
+impl<'a> Hook<'a> {
+    /// Pick the hook for `command` (empty = none): the command when there is
+    /// one, else a notification when `notifications` are available.
+    pub fn select(command: &'a str, notifications: bool) -> Self {
+        match command.trim() {
+            "" if notifications => Hook::Notification,
+            "" => Hook::None,
+            command => Hook::Command(command),
+        }
+    }
+}
+
+/// Runs hooks in the background and collects their failures.
+#[derive(Debug)]
+pub struct Hooks {
+    /// Whether to notify when no command is configured.
+    notifications: bool,
+    /// Where the hook threads send their failures.
+    sender: Sender<String>,
+    /// Failures not yet shown.
+    failures: Receiver<String>,
+    /// How the last hook went.
+    status: Arc<RwLock<Status>>,
+}
+
+impl Hooks {
+    /// Create hooks that notify without a command if this build can.
+    pub fn new() -> Self {
+        let (sender, failures) = mpsc::channel();
+        Self {
+            notifications: NOTIFICATIONS,
+            sender,
+            failures,
+            status: Arc::new(RwLock::new(Status::Ok)),
+        }
+    }
+
+    /// Run the hook for `completion` in the background, without waiting for it.
+    pub fn fire(&self, command: &str, completion: Completion) {
+        let hook = match Hook::select(command, self.notifications) {
+            Hook::Command(command) => Some(command.to_string()),
+            Hook::Notification => None,
+            Hook::None => return,
+        };
+        let sender = self.sender.clone();
+        let status = Arc::clone(&self.status);
+        thread::spawn(move || {
+            let result = match hook {
+                Some(command) => run(&command, &completion),
+                None => notify(&completion),
+            };
+            if let Ok(mut status) = status.write() {
+                *status = result.clone();
+            }
+            if let Status::Failed(reason) = result {
+                let _ = sender.send(reason);
+            }
+        });
+    }
+
+    /// Failures since the last call, to show as toasts.
+    pub fn take_failures(&self) -> Vec<String> {
+        self.failures.try_iter().collect()
+    }
+
+    /// How the last hook went.
+    pub fn status(&self) -> Status {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Run `command` for `completion` and wait for it.
+fn run(command: &str, completion: &Completion) -> Status {
+    let result = shell::command(command)
+        .envs(completion.env())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match result {
+        Ok(exit) if exit.success() => Status::Ok,
+        Ok(exit) => Status::Failed(format!("hook command failed: {exit}")),
+        Err(e) => Status::Failed(format!("failed to run hook command: {e}")),
+    }
+}
+
+/// Send a desktop notification for `completion`.
+#[cfg(feature = "notify")]
+fn notify(completion: &Completion) -> Status {
+    match notify_rust::Notification::new()
+        .appname("sigye")
+        .summary("sigye")
+        .body(&completion.message())
+        .show()
+    {
+        Ok(_) => Status::Ok,
+        Err(e) => Status::Failed(format!("desktop notification failed: {e}")),
+    }
+}
+
+/// Desktop notifications need the `notify` feature.
+#[cfg(not(feature = "notify"))]
+fn notify(_completion: &Completion) -> Status {
+    Status::Failed("built without desktop notifications".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env() {
+        let timer = Completion::timer("Timer 25:00".to_string(), Duration::from_secs(1500));
+        assert_eq!(
+            timer.env(),
+            vec![
+                ("SIGYE_LABEL", "Timer 25:00".to_string()),
+                ("SIGYE_DURATION", "1500".to_string()),
+            ]
+        );
+        assert_eq!(timer.message(), "Timer 25:00 done");
+
+        // Alarms have no duration, and fall back to their time as the label
+        let alarm = Completion::alarm(&Alarm::parse("07:30=Stand-up").unwrap());
+        assert_eq!(alarm.env(), vec![("SIGYE_LABEL", "Stand-up".to_string())]);
+        assert_eq!(alarm.message(), "Stand-up");
+        let unlabelled = Completion::alarm(&Alarm::parse("7:05 PM").unwrap());
+        assert_eq!(unlabelled.label, "Alarm 19:05");
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(
+            Hook::select("notify-send done", true),
+            Hook::Command("notify-send done")
+        );
+        assert_eq!(
+            Hook::select("  notify-send done ", false),
+            Hook::Command("notify-send done")
+        );
+        // Notifications only stand in when no command is configured
+        assert_eq!(Hook::select("", true), Hook::Notification);
+        assert_eq!(Hook::select("  ", true), Hook::Notification);
+        assert_eq!(Hook::select("", false), Hook::None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_gets_the_env() {
+        let script = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/hooks/record_env.sh");
+        let out = std::env::temp_dir().join(format!("sigye-hook-{}.txt", std::process::id()));
+        let command = format!("sh '{script}' '{}'", out.display());
+
+        let timer = Completion::timer("Timer 5:00".to_string(), Duration::from_secs(300));
+        assert_eq!(run(&command, &timer), Status::Ok);
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "Timer 5:00\n300\n");
+        let alarm = Completion::alarm(&Alarm::parse("07:30").unwrap());
+        assert_eq!(run(&command, &alarm), Status::Ok);
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "Alarm 07:30\nunset\n"
+        );
+        let _ = std::fs::remove_file(&out);
+
+        assert_eq!(
+            run("exit 3", &timer),
+            Status::Failed("hook command failed: exit status: 3".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failures_are_collected() {
+        let hooks = Hooks::new();
+        hooks.fire(
+            "exit 1",
+            Completion::timer("Timer".to_string(), Duration::ZERO),
+        );
+        // The command runs in the background
+        let failures = (0..200)
+            .find_map(|_| {
+                let failures = hooks.take_failures();
+                if failures.is_empty() {
+                    thread::sleep(Duration::from_millis(10));
+                    None
+                } else {
+                    Some(failures)
+                }
+            })
+            .unwrap();
+        assert_eq!(failures, vec!["hook command failed: exit status: 1"]);
+        assert!(matches!(hooks.status(), Status::Failed(_)));
+        assert!(hooks.take_failures().is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Secondary info line shown under the date (ISO week, day of year, timestamp).
+
+use chrono::{DateTime, Datelike, TimeZone};
+
+/// Which items the info line renders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InfoLine {
+    #[default]
+    Hidden,
+    /// ISO week date and day of year.
+    WeekAndDay,
+    /// ISO week date, day of year and Unix timestamp.
+    WithTimestamp,
+}
+
+/// All info line modes for cycling.
+const ALL_INFO_LINES: &[InfoLine] = &[
+    InfoLine::Hidden,
+    InfoLine::WeekAndDay,
+    InfoLine::WithTimestamp,
+];
+
+impl InfoLine {
+    /// Cycle to the next info line mode.
+    pub fn next(&self) -> Self {
+        let current_idx = ALL_INFO_LINES.iter().position(|m| m == self).unwrap_or(0);
+        let next_idx = (current_idx + 1) % ALL_INFO_LINES.len();
+        ALL_INFO_LINES[next_idx]
+    }
+
+    /// Check if the info line takes up a layout row.
+    pub fn is_visible(self) -> bool {
+        self != InfoLine::Hidden
+    }
+
+    /// Format the info line for the given time, or `None` when hidden.
+    pub fn format<Tz: TimeZone>(self, now: &DateTime<Tz>) -> Option<String> {
+        if !self.is_visible() {
+            return None;
+        }
+
+        let week = now.iso_week();
+        let days_in_year = if is_leap_year(now.year()) { 366 } else { 365 };
+        let mut text = format!(
+            "Week {} · {}-W{:02}-{} · Day {} of {}",
+            week.week(),
+            week.year(),
+            week.week(),
+            now.weekday().number_from_monday(),
+            now.ordinal(),
+            days_in_year,
+        );
+
+        if self == InfoLine::WithTimestamp {
+            text.push_str(&format!(" · @{}", now.timestamp()));
+        }
+
+        Some(text)
+    }
+}
+
+/// Check if a year is a Gregorian leap year.
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_hidden_renders_nothing() {
+        assert_eq!(InfoLine::Hidden.format(&at(2024, 6, 1)), None);
+    }
+
+    #[test]
+    fn test_cycle() {
+        assert_eq!(InfoLine::Hidden.next(), InfoLine::WeekAndDay);
+        assert_eq!(InfoLine::WeekAndDay.next(), InfoLine::WithTimestamp);
+        assert_eq!(InfoLine::WithTimestamp.next(), InfoLine::Hidden);
+    }
+
+    #[test]
+    fn test_iso_week_year_boundaries() {
+        // Friday 2021-01-01 still belongs to the last ISO week of 2020.
+        assert_eq!(
+            InfoLine::WeekAndDay.format(&at(2021, 1, 1)).unwrap(),
+            "Week 53 · 2020-W53-5 · Day 1 of 365"
+        );
+        // Monday 2026-12-28 starts week 53 of 2026 (Jan 1 2026 is a Thursday).
+        assert_eq!(
+            InfoLine::WeekAndDay.format(&at(2026, 12, 28)).unwrap(),
+            "Week 53 · 2026-W53-1 · Day 362 of 365"
+        );
+        // Tuesday 2019-12-31 already belongs to week 1 of 2020.
+        assert_eq!(
+            InfoLine::WeekAndDay.format(&at(2019, 12, 31)).unwrap(),
+            "Week 1 · 2020-W01-2 · Day 365 of 365"
+        );
+        // Jan 4 is always in week 1.
+        assert_eq!(
+            InfoLine::WeekAndDay.format(&at(2027, 1, 4)).unwrap(),
+            "Week 1 · 2027-W01-1 · Day 4 of 365"
+        );
+    }
+
+    #[test]
+    fn test_day_of_year_leap() {
+        assert_eq!(
+            InfoLine::WeekAndDay.format(&at(2024, 12, 31)).unwrap(),
+            "Week 1 · 2025-W01-2 · Day 366 of 366"
+        );
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let line = InfoLine::WithTimestamp.format(&at(2021, 1, 1)).unwrap();
+        assert!(line.ends_with(" · @1609502400"));
+    }
+}
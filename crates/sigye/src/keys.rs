@@ -0,0 +1,773 @@
+//! Key bindings of the main screen, changeable with a `[keys]` table in the config.
+//!
+//! Keys are written like `q`, `A`, `space`, `F5`, `ctrl+c` or `alt+shift+left`.
+//! Letters stand for themselves, so `A` is the same as `shift+a`. Binding an
+//! action replaces its default keys; actions left out keep theirs.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use sigye_config::KeyBinding;
+
+/// Heading an action is listed under in the help overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionGroup {
+    /// How the clock looks.
+    Display,
+    /// Lines, screensaver and panes.
+    Modes,
+    /// The animated background.
+    Backgrounds,
+    /// Settings, files and leaving.
+    System,
+}
+
+impl ActionGroup {
+    /// Every group, in the order the help overlay lists them.
+    pub const ALL: [ActionGroup; 4] = [
+        ActionGroup::Display,
+        ActionGroup::Modes,
+        ActionGroup::Backgrounds,
+        ActionGroup::System,
+    ];
+
+    /// Get the heading shown in the help overlay.
+    pub fn title(self) -> &'static str {
+        match self {
+            ActionGroup::Display => "Display",
+            ActionGroup::Modes => "Modes",
+            ActionGroup::Backgrounds => "Backgrounds",
+            ActionGroup::System => "System",
+        }
+    }
+}
+
+/// Something a key does on the main screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    ToggleFormat,
+    CycleAmPm,
+    CycleDisplayMode,
+    CyclePrecision,
+    CycleTheme,
+    CycleAnimation,
+    CycleBackground,
+    PickBackground,
+    CycleInfoLine,
+    CycleProgressBar,
+    ToggleWeather,
+    RefreshWeather,
+    ToggleLoad,
+    ToggleDrift,
+    CyclePosition,
+    CycleBorder,
+    NextPreset,
+    WidenSpacing,
+    NarrowSpacing,
+    Settings,
+    ReloadConfig,
+    Export,
+    TogglePause,
+    FocusPane,
+    StartStopPane,
+    ResetPane,
+    ToggleDebug,
+    Help,
+}
+
+impl Action {
+    /// Every action, in the order the help overlay lists them.
+    pub const ALL: [Action; 29] = [
+        Action::Quit,
+        Action::ToggleFormat,
+        Action::CycleAmPm,
+        Action::CycleDisplayMode,
+        Action::CyclePrecision,
+        Action::CycleTheme,
+        Action::CycleAnimation,
+        Action::CycleBackground,
+        Action::PickBackground,
+        Action::CycleInfoLine,
+        Action::CycleProgressBar,
+        Action::ToggleWeather,
+        Action::RefreshWeather,
+        Action::ToggleLoad,
+        Action::ToggleDrift,
+        Action::CyclePosition,
+        Action::CycleBorder,
+        Action::NextPreset,
+        Action::WidenSpacing,
+        Action::NarrowSpacing,
+        Action::Settings,
+        Action::ReloadConfig,
+        Action::Export,
+        Action::TogglePause,
+        Action::FocusPane,
+        Action::StartStopPane,
+        Action::ResetPane,
+        Action::ToggleDebug,
+        Action::Help,
+    ];
+
+    /// Get the name used in the `[keys]` table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::ToggleFormat => "toggle_format",
+            Action::CycleAmPm => "cycle_am_pm",
+            Action::CycleDisplayMode => "cycle_display_mode",
+            Action::CyclePrecision => "cycle_precision",
+            Action::CycleTheme => "cycle_theme",
+            Action::CycleAnimation => "cycle_animation",
+            Action::CycleBackground => "cycle_background",
+            Action::PickBackground => "pick_background",
+            Action::CycleInfoLine => "cycle_info_line",
+            Action::CycleProgressBar => "cycle_progress_bar",
+            Action::ToggleWeather => "toggle_weather",
+            Action::RefreshWeather => "refresh_weather",
+            Action::ToggleLoad => "toggle_load",
+            Action::ToggleDrift => "toggle_drift",
+            Action::CyclePosition => "cycle_position",
+            Action::CycleBorder => "cycle_border",
+            Action::NextPreset => "next_preset",
+            Action::WidenSpacing => "widen_spacing",
+            Action::NarrowSpacing => "narrow_spacing",
+            Action::Settings => "settings",
+            Action::ReloadConfig => "reload_config",
+            Action::Export => "export",
+            Action::TogglePause => "toggle_pause",
+            Action::FocusPane => "focus_pane",
+            Action::StartStopPane => "start_stop_pane",
+            Action::ResetPane => "reset_pane",
+            Action::ToggleDebug => "toggle_debug",
+            Action::Help => "help",
+        }
+    }
+
+    /// Get the description shown in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::ToggleFormat => "Toggle 12/24 hour format",
+            Action::CycleAmPm => "Cycle AM/PM style",
+            Action::CycleDisplayMode => "Switch between digits and words",
+            Action::CyclePrecision => "Cycle sub-second digits",
+            Action::CycleTheme => "Cycle color theme",
+            Action::CycleAnimation => "Cycle animation style",
+            Action::CycleBackground => "Cycle background mode",
+            Action::PickBackground => "Pick a background style",
+            Action::CycleInfoLine => "Cycle info line",
+            Action::CycleProgressBar => "Cycle progress bar (day, week, month, year)",
+            Action::ToggleWeather => "Toggle weather line",
+            Action::RefreshWeather => "Refresh weather now",
+            Action::ToggleLoad => "Toggle CPU and memory line",
+            Action::ToggleDrift => "Toggle drifting screensaver",
+            Action::CyclePosition => "Cycle clock position",
+            Action::CycleBorder => "Cycle clock border",
+            Action::NextPreset => "Apply the next preset",
+            Action::WidenSpacing => "Widen digit spacing",
+            Action::NarrowSpacing => "Narrow digit spacing",
+            Action::Settings => "Open settings",
+            Action::ReloadConfig => "Reload the config file",
+            Action::Export => "Save the frame as ANSI text",
+            Action::TogglePause => "Pause or resume the display",
+            Action::FocusPane => "Focus the next pane",
+            Action::StartStopPane => "Start or stop the focused stopwatch or timer",
+            Action::ResetPane => "Reset the focused stopwatch or timer",
+            Action::ToggleDebug => "Toggle the debug overlay (FPS, frame times)",
+            Action::Help => "Toggle this help",
+        }
+    }
+
+    /// Get the group the help overlay lists the action under.
+    pub fn group(self) -> ActionGroup {
+        match self {
+            Action::ToggleFormat
+            | Action::CycleAmPm
+            | Action::CycleDisplayMode
+            | Action::CyclePrecision
+            | Action::CycleTheme
+            | Action::CycleAnimation
+            | Action::CyclePosition
+            | Action::CycleBorder
+            | Action::NextPreset
+            | Action::WidenSpacing
+            | Action::NarrowSpacing => ActionGroup::Display,
+            Action::CycleInfoLine
+            | Action::CycleProgressBar
+            | Action::ToggleWeather
+            | Action::ToggleLoad
+            | Action::ToggleDrift
+            | Action::TogglePause
+            | Action::FocusPane
+            | Action::StartStopPane
+            | Action::ResetPane => ActionGroup::Modes,
+            Action::CycleBackground | Action::PickBackground => ActionGroup::Backgrounds,
+            Action::Quit
+            | Action::RefreshWeather
+            | Action::Settings
+            | Action::ReloadConfig
+            | Action::Export
+            | Action::ToggleDebug
+            | Action::Help => ActionGroup::System,
+        }
+    }
+
+    /// Get the short label shown after its key on the help line.
+    pub fn help_label(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::ToggleFormat => "12/24h",
+            Action::CycleAmPm => "am/pm",
+            Action::CycleDisplayMode => "digits",
+            Action::CyclePrecision => "precision",
+            Action::CycleTheme => "color",
+            Action::CycleAnimation => "anim",
+            Action::CycleBackground | Action::PickBackground => "bg",
+            Action::CycleInfoLine => "info",
+            Action::CycleProgressBar => "progress",
+            Action::ToggleWeather => "weather",
+            Action::RefreshWeather => "refresh",
+            Action::ToggleLoad => "load",
+            Action::ToggleDrift => "drift",
+            Action::CyclePosition => "position",
+            Action::CycleBorder => "border",
+            Action::NextPreset => "preset",
+            Action::WidenSpacing => "wider",
+            Action::NarrowSpacing => "narrower",
+            Action::Settings => "settings",
+            Action::ReloadConfig => "reload",
+            Action::Export => "export",
+            Action::TogglePause => "pause",
+            Action::FocusPane => "pane",
+            Action::StartStopPane => "start/stop",
+            Action::ResetPane => "reset",
+            Action::ToggleDebug => "debug",
+            Action::Help => "keys",
+        }
+    }
+
+    /// Keys bound to the action without a `[keys]` entry.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "esc", "ctrl+c"],
+            Action::ToggleFormat => &["t"],
+            Action::CycleAmPm => &["A"],
+            Action::CycleDisplayMode => &["m"],
+            Action::CyclePrecision => &["p"],
+            Action::CycleTheme => &["c"],
+            Action::CycleAnimation => &["a"],
+            Action::CycleBackground => &["b"],
+            Action::PickBackground => &["B"],
+            Action::CycleInfoLine => &["i"],
+            Action::CycleProgressBar => &["g"],
+            Action::ToggleWeather => &["w"],
+            Action::RefreshWeather => &["W"],
+            Action::ToggleLoad => &["l"],
+            Action::ToggleDrift => &["d"],
+            Action::CyclePosition => &["o"],
+            Action::CycleBorder => &["f"],
+            Action::NextPreset => &["P"],
+            Action::WidenSpacing => &["+"],
+            Action::NarrowSpacing => &["-"],
+            Action::Settings => &["s"],
+            Action::ReloadConfig => &["R"],
+            Action::Export => &["e"],
+            Action::TogglePause => &["space"],
+            Action::FocusPane => &["tab"],
+            Action::StartStopPane => &["enter"],
+            Action::ResetPane => &["backspace"],
+            Action::ToggleDebug => &["F12", "D"],
+            Action::Help => &["?"],
+        }
+    }
+
+    /// Find the action with the given `[keys]` name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Keys with a name in key specs, other than characters and function keys.
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("space", KeyCode::Char(' ')),
+    ("esc", KeyCode::Esc),
+    ("escape", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("return", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("del", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("ins", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pgup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("pgdn", KeyCode::PageDown),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("plus", KeyCode::Char('+')),
+    ("minus", KeyCode::Char('-')),
+];
+
+/// Highest function key accepted, e.g. `F24`.
+const MAX_FUNCTION_KEY: u8 = 24;
+
+/// A key with its modifiers, as pressed or as written in a key spec.
+///
+/// Shift is part of the character for letters and symbols (`A`, `?`), so it
+/// is only kept as a modifier for other keys, like `shift+left`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    /// The key itself.
+    code: KeyCode,
+    /// Control, Alt and, for keys other than characters, Shift.
+    modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+    /// Combine a key and modifiers the way terminals report them.
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        match code {
+            KeyCode::Char(ch) => {
+                // Terminals send Ctrl and Alt letters in either case
+                let ch = if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                    ch.to_ascii_lowercase()
+                } else {
+                    ch
+                };
+                Self {
+                    code: KeyCode::Char(ch),
+                    modifiers: modifiers - KeyModifiers::SHIFT,
+                }
+            }
+            // Shift+Tab arrives as BackTab, with or without Shift
+            KeyCode::BackTab => Self {
+                code,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::BackTab,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            code => Self { code, modifiers },
+        }
+    }
+
+    /// The combo of a key press.
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse a key spec like `q`, `A`, `space`, `F5` or `ctrl+shift+left`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let trimmed = spec.trim();
+        // A trailing "+" is the plus key itself, as in "+" or "ctrl++"
+        let (modifier_names, key) = if trimmed == "+" {
+            ("", "+")
+        } else if let Some(modifier_names) = trimmed.strip_suffix("++") {
+            (modifier_names, "+")
+        } else {
+            trimmed.rsplit_once('+').unwrap_or(("", trimmed))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier \"{name}\" in \"{spec}\"")),
+            };
+        }
+
+        let key = key.trim();
+        let code = parse_key(key).ok_or_else(|| format!("unknown key \"{key}\" in \"{spec}\""))?;
+        // Shift on a letter means the capital one
+        let code = match code {
+            KeyCode::Char(ch) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(ch.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+/// Parse a key without modifiers: a single character, a named key or a function key.
+fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(ch));
+    }
+    let lower = key.to_ascii_lowercase();
+    if let Some(&(_, code)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+        return Some(code);
+    }
+    let number: u8 = lower.strip_prefix('f')?.parse().ok()?;
+    (1..=MAX_FUNCTION_KEY)
+        .contains(&number)
+        .then_some(KeyCode::F(number))
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            // Keys a spec can't name
+            code => write!(f, "{code}"),
+        }
+    }
+}
+
+/// Which action each key does.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    /// Action of each bound key.
+    bindings: HashMap<KeyCombo, Action>,
+    /// Bound keys in the order they were bound, for listing them.
+    order: Vec<KeyCombo>,
+}
+
+impl Keymap {
+    /// Build the keymap from the `[keys]` table, returning warnings about
+    /// entries that were ignored and keys bound twice.
+    ///
+    /// An action with a bad key spec keeps its default keys. Configured keys
+    /// win over default ones; between two configured actions the first listed
+    /// in the help overlay wins.
+    pub fn from_config(keys: &BTreeMap<String, KeyBinding>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut configured: HashMap<Action, Vec<KeyCombo>> = HashMap::new();
+        for (name, binding) in keys {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(format!("Ignoring unknown action \"{name}\" in [keys]"));
+                continue;
+            };
+            let combos: Result<Vec<KeyCombo>, String> =
+                binding.specs().into_iter().map(KeyCombo::parse).collect();
+            match combos {
+                Ok(combos) => {
+                    configured.insert(action, combos);
+                }
+                Err(e) => warnings.push(format!(
+                    "Ignoring [keys] {name}: {e}; keeping the default keys"
+                )),
+            }
+        }
+
+        let mut keymap = Self::default();
+        let mut bind = |action: Action, combos: &[KeyCombo], warnings: &mut Vec<String>| {
+            for &combo in combos {
+                match keymap.bindings.get(&combo) {
+                    Some(&bound) if bound == action => {}
+                    Some(&bound) => warnings.push(format!(
+                        "Key {combo} is bound to both {} and {}; using {}",
+                        bound.name(),
+                        action.name(),
+                        bound.name()
+                    )),
+                    None => {
+                        keymap.bindings.insert(combo, action);
+                        keymap.order.push(combo);
+                    }
+                }
+            }
+        };
+        for action in Action::ALL {
+            if let Some(combos) = configured.get(&action) {
+                bind(action, combos, &mut warnings);
+            }
+        }
+        for action in Action::ALL {
+            if !configured.contains_key(&action) {
+                let combos: Vec<KeyCombo> = action
+                    .default_keys()
+                    .iter()
+                    .map(|spec| KeyCombo::parse(spec).expect("default key specs parse"))
+                    .collect();
+                bind(action, &combos, &mut warnings);
+            }
+        }
+        (keymap, warnings)
+    }
+
+    /// The action of a key press, if the key is bound.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyCombo::from_event(key)).copied()
+    }
+
+    /// Keys bound to `action`, defaults first.
+    pub fn keys(&self, action: Action) -> Vec<KeyCombo> {
+        self.order
+            .iter()
+            .copied()
+            .filter(|combo| self.bindings.get(combo) == Some(&action))
+            .collect()
+    }
+
+    /// Label of the keys bound to `action` joined by `separator`, e.g. "q / Esc".
+    pub fn label(&self, action: Action, separator: &str) -> String {
+        self.keys(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(code: KeyCode, modifiers: KeyModifiers) -> KeyCombo {
+        KeyCombo::new(code, modifiers)
+    }
+
+    fn parse(spec: &str) -> KeyCombo {
+        KeyCombo::parse(spec).unwrap()
+    }
+
+    fn build(entries: &[(&str, &[&str])]) -> (Keymap, Vec<String>) {
+        let keys = entries
+            .iter()
+            .map(|(name, specs)| {
+                let specs = specs.iter().map(ToString::to_string).collect();
+                (name.to_string(), KeyBinding::Many(specs))
+            })
+            .collect();
+        Keymap::from_config(&keys)
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_chars() {
+        assert_eq!(parse("q"), combo(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(parse(" ? "), combo(KeyCode::Char('?'), KeyModifiers::NONE));
+        assert_eq!(parse("A"), combo(KeyCode::Char('A'), KeyModifiers::NONE));
+        assert_eq!(parse("shift+a"), parse("A"));
+        assert_eq!(parse("Shift+A"), parse("A"));
+        assert_eq!(parse("+"), combo(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert_eq!(parse("plus"), parse("+"));
+        assert_eq!(parse("-"), combo(KeyCode::Char('-'), KeyModifiers::NONE));
+        assert_eq!(parse("é"), combo(KeyCode::Char('é'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        assert_eq!(
+            parse("ctrl+c"),
+            combo(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(parse("Control+C"), parse("ctrl+c"));
+        assert_eq!(parse("alt+x"), combo(KeyCode::Char('x'), KeyModifiers::ALT));
+        assert_eq!(parse("meta+x"), parse("alt+x"));
+        assert_eq!(
+            parse("ctrl+alt+delete"),
+            combo(KeyCode::Delete, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(parse("alt + ctrl + del"), parse("ctrl+alt+delete"));
+        assert_eq!(
+            parse("ctrl++"),
+            combo(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse("shift+left"),
+            combo(KeyCode::Left, KeyModifiers::SHIFT)
+        );
+        assert_eq!(parse("shift+tab"), parse("backtab"));
+    }
+
+    #[test]
+    fn test_parse_named_and_function_keys() {
+        assert_eq!(
+            parse("space"),
+            combo(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(parse("SPACE"), parse("space"));
+        assert_eq!(parse("Esc"), combo(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(parse("escape"), parse("esc"));
+        assert_eq!(parse("return"), parse("enter"));
+        assert_eq!(parse("pgdn"), combo(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(parse("F5"), combo(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(parse("f12"), combo(KeyCode::F(12), KeyModifiers::NONE));
+        assert_eq!(
+            parse("ctrl+F24"),
+            combo(KeyCode::F(24), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for spec in [
+            "",
+            "ctrl+",
+            "hyper+x",
+            "F0",
+            "F25",
+            "fx",
+            "qq",
+            "ctrl+shift+nope",
+        ] {
+            assert!(KeyCombo::parse(spec).is_err(), "{spec:?}");
+        }
+        assert_eq!(
+            KeyCombo::parse("super+q").unwrap_err(),
+            "unknown modifier \"super\" in \"super+q\""
+        );
+        assert_eq!(
+            KeyCombo::parse("ctrl+nope").unwrap_err(),
+            "unknown key \"nope\" in \"ctrl+nope\""
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for (spec, shown) in [
+            ("q", "q"),
+            ("shift+a", "A"),
+            ("space", "Space"),
+            ("esc", "Esc"),
+            ("ctrl+c", "Ctrl+c"),
+            ("alt+shift+left", "Alt+Shift+Left"),
+            ("F5", "F5"),
+            ("pageup", "PgUp"),
+            ("shift+tab", "Shift+Tab"),
+            ("ctrl+del", "Ctrl+Delete"),
+        ] {
+            assert_eq!(parse(spec).to_string(), shown, "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_key_events_match_specs() {
+        // Terminals report capitals with Shift, and Ctrl letters in either case
+        assert_eq!(
+            KeyCombo::from_event(&press(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            parse("A")
+        );
+        assert_eq!(
+            KeyCombo::from_event(&press(KeyCode::Char('C'), KeyModifiers::CONTROL)),
+            parse("ctrl+c")
+        );
+        assert_eq!(
+            KeyCombo::from_event(&press(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            parse("shift+tab")
+        );
+    }
+
+    #[test]
+    fn test_default_keymap() {
+        let (keymap, warnings) = build(&[]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        for action in Action::ALL {
+            assert!(!keymap.keys(action).is_empty(), "{}", action.name());
+        }
+        let key = |code| keymap.action(&press(code, KeyModifiers::NONE));
+        assert_eq!(key(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(key(KeyCode::Esc), Some(Action::Quit));
+        assert_eq!(key(KeyCode::Char(' ')), Some(Action::TogglePause));
+        assert_eq!(key(KeyCode::Char('x')), None);
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(keymap.label(Action::Quit, " / "), "q / Esc / Ctrl+c");
+    }
+
+    #[test]
+    fn test_configured_keys_replace_defaults() {
+        let (keymap, warnings) = build(&[("cycle_theme", &["j", "k"]), ("quit", &["F10"])]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let key = |ch| keymap.action(&press(KeyCode::Char(ch), KeyModifiers::NONE));
+        assert_eq!(key('j'), Some(Action::CycleTheme));
+        assert_eq!(key('k'), Some(Action::CycleTheme));
+        assert_eq!(key('c'), None);
+        assert_eq!(key('q'), None);
+        assert_eq!(keymap.label(Action::Quit, "/"), "F10");
+    }
+
+    #[test]
+    fn test_bad_entries_warn_and_keep_defaults() {
+        let (keymap, warnings) = build(&[("Cycle_Theme", &["j", "hyper+k"]), ("fly", &["x"])]);
+        assert_eq!(
+            warnings,
+            [
+                "Ignoring [keys] Cycle_Theme: unknown modifier \"hyper\" in \"hyper+k\"; keeping the default keys",
+                "Ignoring unknown action \"fly\" in [keys]",
+            ]
+        );
+        assert_eq!(keymap.label(Action::CycleTheme, "/"), "c");
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('j'), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys_warn() {
+        // Space toggles the format instead of pausing, which is left without a key
+        let (keymap, warnings) = build(&[("toggle_format", &["space"])]);
+        assert_eq!(
+            warnings,
+            ["Key Space is bound to both toggle_format and toggle_pause; using toggle_format"]
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char(' '), KeyModifiers::NONE)),
+            Some(Action::ToggleFormat)
+        );
+        assert!(keymap.keys(Action::TogglePause).is_empty());
+
+        // Between configured keys the first action in the overlay wins
+        let (keymap, warnings) = build(&[("help", &["x"]), ("quit", &["x"])]);
+        assert_eq!(
+            warnings,
+            ["Key x is bound to both quit and help; using quit"]
+        );
+        assert_eq!(
+            keymap.action(&press(KeyCode::Char('x'), KeyModifiers::NONE)),
+            Some(Action::Quit)
+        );
+    }
+}
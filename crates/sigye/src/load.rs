@@ -0,0 +1,108 @@
+//! System load line under the date: a sparkline of recent CPU usage and a
+//! small memory gauge, a quieter alternative to the reactive backgrounds.
+
+use ratatui::style::Color;
+use sigye_background::{LOWER_BLOCK_CHARS, resource_to_color};
+use sigye_core::{history::MetricsHistory, progress::progress_bar};
+
+/// Cells of the memory gauge bar.
+const GAUGE_CELLS: usize = 5;
+
+/// Width of the memory gauge with its label and percentage.
+const GAUGE_WIDTH: usize = " mem ".len() + GAUGE_CELLS + " 100%".len();
+
+/// Sparkline character for a CPU usage (0.0 - 1.0), at least a thin baseline.
+fn spark_char(value: f32) -> char {
+    let steps = LOWER_BLOCK_CHARS.len();
+    let level = ((value.clamp(0.0, 1.0) * steps as f32).ceil() as usize).clamp(1, steps);
+    LOWER_BLOCK_CHARS[level - 1]
+}
+
+/// Characters of a load line `width` cells wide, each with its color, or
+/// `None` for a cell left to the background.
+///
+/// The CPU sparkline fills the room left of the memory gauge, oldest on the
+/// left and each column colored by its usage; columns without samples yet
+/// stay blank. The memory gauge is colored by the memory usage.
+pub fn load_line(cpu: &MetricsHistory, memory: f32, width: usize) -> Vec<Option<(char, Color)>> {
+    let memory = memory.clamp(0.0, 1.0);
+    let gauge = format!(
+        " mem {} {:>3.0}%",
+        progress_bar(memory.into(), GAUGE_CELLS),
+        memory * 100.0
+    );
+    let spark_width = width.saturating_sub(GAUGE_WIDTH);
+    let memory_color = resource_to_color(memory);
+    cpu.downsample(spark_width)
+        .into_iter()
+        .map(|value| value.map(|value| (spark_char(value), resource_to_color(value))))
+        .chain(
+            gauge
+                .chars()
+                .map(|ch| (ch != ' ').then_some((ch, memory_color))),
+        )
+        .take(width)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(samples: &[f32]) -> MetricsHistory {
+        let mut history = MetricsHistory::new(samples.len().max(4));
+        for &sample in samples {
+            history.push(sample);
+        }
+        history
+    }
+
+    /// The characters of a load line, with blanks for background cells.
+    fn text(cells: &[Option<(char, Color)>]) -> String {
+        cells
+            .iter()
+            .map(|cell| cell.map_or(' ', |(ch, _)| ch))
+            .collect()
+    }
+
+    #[test]
+    fn test_spark_char() {
+        assert_eq!(spark_char(0.0), '▁');
+        assert_eq!(spark_char(0.5), '▄');
+        assert_eq!(spark_char(1.0), '█');
+        assert_eq!(spark_char(7.0), '█');
+    }
+
+    #[test]
+    fn test_load_line_columns() {
+        let cpu = history(&[0.0, 0.25, 0.5, 1.0]);
+        let gauge = " mem ██▌    50%";
+        let line = load_line(&cpu, 0.5, 4 + GAUGE_WIDTH);
+        assert_eq!(text(&line), format!("▁▂▄█{gauge}"));
+        // Busier columns are warmer
+        assert_eq!(line[0].unwrap().1, resource_to_color(0.0));
+        assert_eq!(line[3].unwrap().1, resource_to_color(1.0));
+    }
+
+    #[test]
+    fn test_load_line_downsampling() {
+        let cpu = history(&[0.1, 1.0, 0.1, 0.1]);
+        // Squeezed, a spike survives
+        assert!(text(&load_line(&cpu, 0.0, 2 + GAUGE_WIDTH)).starts_with("█▁"));
+        // Stretched, each sample spans several columns
+        assert!(text(&load_line(&cpu, 0.0, 8 + GAUGE_WIDTH)).starts_with("▁▁██▁▁▁▁"));
+        // Until the history fills up, the older columns stay blank
+        let mut filling = MetricsHistory::new(4);
+        filling.push(1.0);
+        assert!(text(&load_line(&filling, 0.0, 4 + GAUGE_WIDTH)).starts_with("   █"));
+    }
+
+    #[test]
+    fn test_load_line_in_tight_room() {
+        let cpu = history(&[0.5; 4]);
+        assert!(load_line(&cpu, 0.3, 0).is_empty());
+        // Without room for the sparkline, only the gauge is drawn
+        let line = load_line(&cpu, 0.3, 6);
+        assert_eq!(text(&line), " mem █");
+    }
+}
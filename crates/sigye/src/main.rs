@@ -1,65 +1,282 @@
 //! sigye - A terminal clock application with configurable fonts.
 
+mod accessibility;
+mod alarm;
+mod ansi;
+mod app_metrics;
+mod background_mode;
+mod banner;
+mod border;
+mod bounce;
+mod calendar;
+mod cast;
+mod chime;
+mod cli;
+mod clock_check;
+mod compat;
+mod debug;
+mod help;
+mod hooks;
+mod info_line;
+mod keys;
+mod load;
+mod mouse;
+mod once;
+mod panes;
+mod redraw;
+mod reload;
+mod seconds_ring;
+mod serve;
 mod settings;
+mod shell;
+mod status;
 mod system_metrics;
+mod text_clock;
+mod toast;
+mod watch;
 mod weather;
-
+mod worker;
+
+use std::fs;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use chrono::Local;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use chrono::{DateTime, Local, TimeDelta, Timelike};
+use clap::Parser;
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::execute;
 use ratatui::{
-    DefaultTerminal, Frame,
-    layout::{Constraint, Layout, Position},
-    style::Stylize,
-    text::Line,
+    DefaultTerminal, Frame, Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Position, Rect, Size},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
 };
 use sigye_config::Config;
 use sigye_core::{
-    AnimationSpeed, AnimationStyle, BackgroundStyle, ColorTheme, TimeFormat, apply_animation,
+    Accessibility, AmPmStyle, AnimationSpeed, AnimationStyle, BackgroundStyle, ClockBorder,
+    ClockPosition, ColorTheme, DateFormat, DisplayMode, SecondsRing, SubsecondPrecision,
+    TimeFormat, TimeParts, WeatherSource, apply_animation, auto_color,
+    brightness::{AutoDim, blend, clamp_brightness, night_ramp, sun_ramp},
+    burn_in,
+    date_override::DateOverrides,
+    dst,
+    fuzzy::fuzzy_time,
+    history::MetricsHistory,
     is_colon_visible,
+    moon::moon_phase,
+    preset::{Preset, find_preset, merge_presets},
+    progress::{ProgressBar, progress_bar},
+    schedule::{Schedule, ScheduleResolver, ScheduledPreset, TimeRange, parse_minutes},
+    theme::{Theme, ThemeOverrides},
+};
+use sigye_fonts::{Fill, Font, FontRegistry, Spacing, fit_scale};
+use sigye_widget::{ClockConfig, ClockStyle, ClockWidget};
+
+use accessibility::HIGH_CONTRAST_FONT;
+use alarm::{Alarm, AlarmState, SavedAlarm};
+use app_metrics::{AppMetrics, MetricsServer};
+use background_mode::BackgroundMode;
+use banner::{BANNER_HEIGHT, BANNER_PADDING};
+use bounce::Bounce;
+use calendar::{AgendaLine, CalendarMonitor};
+use cast::Recording;
+use chime::Chime;
+use cli::Cli;
+use clock_check::{ClockCheck, Drift};
+use debug::{DebugInfo, FrameStats};
+use help::{HelpState, pinned_actions};
+use hooks::{Completion, Hooks};
+use info_line::InfoLine;
+use keys::{Action, Keymap};
+use mouse::{HitAreas, MouseTarget};
+use panes::{PaneFrame, Panes};
+use redraw::FrameKey;
+use reload::ConfigChanges;
+use serve::FrameServer;
+use settings::{DisplaySettings, SettingsDialog};
+use sigye_background::{
+    BackgroundState, BackgroundWidget, ClearZones, MAX_LAYERS, temperature_to_color,
 };
-use sigye_fonts::FontRegistry;
+use status::{Status, StatusRegistry, Subsystem};
+use system_metrics::{SourceSpec, SystemMonitor, sample_interval};
+use toast::Toasts;
+use unicode_width::UnicodeWidthChar;
+use watch::FileWatcher;
+use weather::{Location, WeatherData, WeatherLine, WeatherMonitor, WeatherState};
 
-use settings::SettingsDialog;
-use sigye_background::BackgroundState;
-use system_metrics::SystemMonitor;
-use weather::WeatherMonitor;
+/// How long a key press shows the clock outside the show schedule.
+const CLOCK_REVEAL: Duration = Duration::from_secs(10);
+
+/// Largest scale of the clock digits picked with `clock_scale = 0`.
+const MAX_AUTO_SCALE: usize = 4;
+
+/// How far ahead a daylight saving change is announced (a day).
+const DST_NOTICE_AHEAD: TimeDelta = TimeDelta::hours(24);
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let cli = Cli::parse();
+    if cli.list_backgrounds {
+        BackgroundStyle::all().for_each(|style| println!("{style}"));
+        return Ok(());
+    }
+    if cli.list_themes {
+        ColorTheme::ALL.iter().for_each(|theme| println!("{theme}"));
+        return Ok(());
+    }
+    if cli.once {
+        return once::run(&cli);
+    }
+    if let Some(path) = cli.export.clone() {
+        let size = crossterm::terminal::size().map_or(Size::new(80, 24), |(width, height)| {
+            Size::new(width, height)
+        });
+        let mut app = App::new(cli);
+        app.print_config_warnings();
+        return app.export(&path, size);
+    }
+    let metrics_listen = cli.metrics_listen;
+    let mut app = App::new(cli);
+    // Before the alternate screen, which would hide them
+    app.print_config_warnings();
+    // Fail before taking over the terminal if the address can't be used
+    if let Some(addr) = metrics_listen {
+        app.serve_metrics(addr)
+            .map_err(|e| color_eyre::eyre::eyre!("Can't serve metrics on {addr}: {e}"))?;
+    }
     let terminal = ratatui::init();
-    let result = App::new().run(terminal);
+    restore_mouse_on_panic();
+    let result = app.run(terminal);
+    // Harmless when mouse capture was never enabled
+    let _ = execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
     result
 }
 
+/// Fresh background animation state, seeded with `seed` if given.
+fn new_background_state(seed: Option<u64>) -> BackgroundState {
+    seed.map_or_else(BackgroundState::new, BackgroundState::with_seed)
+}
+
+/// Release the mouse before the terminal restoring panic hooks run.
+fn restore_mouse_on_panic() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = execute!(std::io::stdout(), DisableMouseCapture);
+        hook(info);
+    }));
+}
+
 /// The main application which holds the state and logic of the application.
 pub struct App {
     /// Is the application running?
     running: bool,
     /// Current time format (12h or 24h).
     time_format: TimeFormat,
+    /// How AM/PM is drawn in 12-hour format.
+    am_pm_style: AmPmStyle,
+    /// Format of the date line.
+    date_format: DateFormat,
+    /// Where the clock sits when not drifting.
+    clock_position: ClockPosition,
+    /// Border drawn around the clock.
+    clock_border: ClockBorder,
+    /// High contrast or plain text display.
+    accessibility: Accessibility,
+    /// Gaps between the glyphs of the clock digits.
+    spacing: Spacing,
+    /// Characters painted over the clock digits, `None` for the font's own.
+    digit_fill: Option<Fill>,
+    /// Whether the time is shown as digits or in words.
+    display_mode: DisplayMode,
+    /// Sub-second digits shown under the clock.
+    subsecond_precision: SubsecondPrecision,
     /// Current color theme.
     color_theme: ColorTheme,
+    /// Colors in place of the theme's defaults, from the config or a preset.
+    theme_overrides: ThemeOverrides,
+    /// Built-in and user presets, in cycling order.
+    presets: Vec<(String, Preset)>,
+    /// Position of the last applied preset, `None` before any.
+    preset_index: Option<usize>,
+    /// Presets applied at times of the day, from the config.
+    preset_schedule: ScheduleResolver,
+    /// Preset the schedule applied last, `None` outside its ranges.
+    scheduled_preset: Option<String>,
+    /// Look returned to between scheduled ranges: the one the clock started with.
+    unscheduled_look: Preset,
     /// Current animation style.
     animation_style: AnimationStyle,
     /// Current animation speed.
     animation_speed: AnimationSpeed,
     /// Whether colon blinks.
     colon_blink: bool,
-    /// Current background style.
-    background_style: BackgroundStyle,
+    /// How the background is chosen: following the weather, manual, or off.
+    background_mode: BackgroundMode,
+    /// Extra background styles composited over the background.
+    extra_layers: Vec<BackgroundStyle>,
+    /// Background styles for dates, shown instead of the weather background.
+    date_overrides: DateOverrides,
+    /// Preferred weather provider for the weather background.
+    weather_provider: WeatherSource,
+    /// Where to fetch the weather for.
+    weather_location: Location,
+    /// Whether the current weather line is shown.
+    show_weather: bool,
+    /// Whether the CPU and memory line is shown.
+    show_load: bool,
+    /// Which items the secondary info line shows.
+    info_line: InfoLine,
+    /// Period shown as a progress bar under the date.
+    progress_bar: ProgressBar,
+    /// Drifting position of the clock in screensaver mode, `None` when centered.
+    bounce: Option<Bounce>,
+    /// When the clock is shown; outside it only the background is drawn.
+    schedule: Schedule,
+    /// Scheduled alarms and which one is ringing.
+    alarm_state: AlarmState,
+    /// Next alarm due as last written to the state file.
+    saved_alarm: Option<SavedAlarm>,
+    /// Chimes on the hour, `None` when disabled.
+    chime: Option<Chime>,
+    /// Commands and notifications for finished timers and alarms.
+    hooks: Hooks,
     /// Current font name.
     current_font: String,
     /// Font registry containing all available fonts.
     font_registry: FontRegistry,
     /// Settings dialog state.
     settings_dialog: SettingsDialog,
+    /// Help line auto-hide and key binding overlay.
+    help: HelpState,
+    /// Action of each key on the main screen.
+    keymap: Keymap,
+    /// Actions listed on the help line, in order.
+    help_line: Vec<Action>,
+    /// Transient notifications in the corner.
+    toasts: Toasts,
     /// Configuration for persistence.
     config: Config,
     /// Animation start time.
     animation_start: Instant,
+    /// Time and animation clock frozen by a pause, `None` while the display is live.
+    paused: Option<(DateTime<Local>, u64)>,
+    /// Whether colors are stripped from every frame.
+    mono: bool,
+    /// Whether every frame is reduced to ASCII characters.
+    ascii: bool,
+    /// Night from evening to morning for scheduled dimming, in minutes past midnight.
+    dim_hours: (u32, u32),
     /// Last recorded second (for reactive animation).
     last_second: u32,
     /// Last recorded minute (for reactive animation).
@@ -70,19 +287,227 @@ pub struct App {
     flash_intensity: f32,
     /// When the last flash started (for decay calculation).
     flash_start: Option<Instant>,
+    /// Seed of the background animations from `--seed`, random without one.
+    seed: Option<u64>,
+    /// Rain and snow intensity from `--background`, the weather's without one.
+    precipitation: Option<f32>,
     /// Background animation state.
     background_state: BackgroundState,
     /// System monitor for reactive backgrounds (lazy initialized).
     system_monitor: Option<SystemMonitor>,
     /// Weather monitor for dynamic weather background (lazy initialized).
     weather_monitor: Option<WeatherMonitor>,
+    /// Calendar monitor for the agenda line, when calendar files are set.
+    calendar: Option<CalendarMonitor>,
+    /// Check of the system clock against a time server, when enabled.
+    clock_check: Option<ClockCheck>,
+    /// Latest weather and fetch status, fed by the weather monitor.
+    weather: WeatherState,
+    /// Health of the config, weather and metrics, marked in a corner.
+    status: StatusRegistry,
+    /// Writer of plain text frames for `--serve-text`.
+    frame_server: Option<FrameServer>,
+    /// Whether served frames include the background.
+    serve_background: bool,
+    /// Counters about the app itself, served with `--metrics-listen`.
+    app_metrics: Arc<AppMetrics>,
+    /// Server of the counters for `--metrics-listen`.
+    metrics_server: Option<MetricsServer>,
+    /// Timings of the recently drawn frames, for the debug overlay.
+    frame_stats: FrameStats,
+    /// Whether the debug overlay is shown.
+    debug_visible: bool,
+    /// Cast being recorded with `--record`, ending the session when done.
+    recording: Option<Recording>,
+    /// Clickable regions of the last drawn frame.
+    hit_areas: HitAreas,
+    /// Key of the last drawn frame, `None` to force a redraw.
+    last_frame: Option<FrameKey>,
+    /// Watcher of the config file, reloading it after edits.
+    config_watcher: FileWatcher,
+    /// Message of the banner next to the clock, if any.
+    banner: Option<String>,
+    /// Watcher of the `--banner-file`, reading it again after edits.
+    banner_file: Option<FileWatcher>,
+    /// Whether the banner comes from the command line, so reloads of the
+    /// config leave it alone.
+    banner_from_cli: bool,
+    /// Panes of a split screen, a single pane showing the usual clock.
+    panes: Panes,
+    /// Values ignored from the config, for the status marker.
+    config_warnings: Vec<String>,
+    /// Key bindings ignored from the config, among `config_warnings`.
+    key_warnings: Vec<String>,
+    /// Panes ignored from the config, among `config_warnings`.
+    pane_warnings: Vec<String>,
+    /// Help line entries ignored from the config, among `config_warnings`.
+    help_line_warnings: Vec<String>,
 }
 
 impl App {
     /// Construct a new instance of [`App`].
-    pub fn new() -> Self {
-        // Load configuration
-        let config = Config::load();
+    pub fn new(cli: Cli) -> Self {
+        // Load configuration, keeping what was wrong with it for the status marker
+        let mut config_warnings = Vec::new();
+        let (config, config_error) = match Config::try_load() {
+            Ok(config) => (config, None),
+            Err(e) => {
+                eprintln!("Warning: {e}");
+                (Config::default(), Some(e))
+            }
+        };
+        // On the first run, keep the time format picked from the locale
+        if config_error.is_none()
+            && !Config::config_file_path().exists()
+            && let Err(e) = config.save()
+        {
+            eprintln!("Warning: Failed to save config: {e}");
+        }
+        let background_mode = BackgroundMode::from_style(
+            cli.background
+                .map_or(config.background_style, |arg| arg.style),
+        );
+        let weather_provider = cli.weather_provider.unwrap_or(config.weather_provider);
+        let weather_location = cli.location.unwrap_or_else(|| {
+            Location::parse(&config.weather_location).unwrap_or_else(|e| {
+                config_warning(
+                    &mut config_warnings,
+                    format!("Ignoring weather_location from config: {e}"),
+                );
+                Location::Auto
+            })
+        });
+        let dim_hours = parse_minutes(config.dim_start.trim())
+            .and_then(|start| Ok((start, parse_minutes(config.dim_end.trim())?)))
+            .unwrap_or_else(|e| {
+                config_warning(
+                    &mut config_warnings,
+                    format!("Ignoring dim_start and dim_end from config: {e}"),
+                );
+                (0, 0)
+            });
+        let (keymap, key_warnings) = Keymap::from_config(&config.keys);
+        for warning in &key_warnings {
+            config_warning(&mut config_warnings, warning.clone());
+        }
+        let (help_line, help_line_warnings) = pinned_actions(&config.help_line);
+        for warning in &help_line_warnings {
+            config_warning(&mut config_warnings, warning.clone());
+        }
+        let digit_fill = config_fill(&config).unwrap_or_else(|e| {
+            config_warning(
+                &mut config_warnings,
+                format!("Ignoring digit_fill from config: {e}"),
+            );
+            None
+        });
+
+        // Combine alarms from config with session alarms from the command line
+        let mut alarms: Vec<Alarm> = config
+            .alarms
+            .iter()
+            .filter_map(|spec| match Alarm::parse(spec) {
+                Ok(alarm) => Some(alarm),
+                Err(e) => {
+                    config_warning(
+                        &mut config_warnings,
+                        format!("Ignoring alarm from config: {e}"),
+                    );
+                    None
+                }
+            })
+            .collect();
+        alarms.extend(cli.alarms);
+        for time in config.alarm_commands.keys() {
+            match Alarm::parse(time) {
+                Ok(command_alarm) => {
+                    let scheduled = alarms.iter().any(|alarm| {
+                        (alarm.hour, alarm.minute) == (command_alarm.hour, command_alarm.minute)
+                    });
+                    if !scheduled {
+                        config_warning(
+                            &mut config_warnings,
+                            format!("Ignoring alarm command for {command_alarm}: no alarm then"),
+                        );
+                    }
+                }
+                Err(e) => config_warning(
+                    &mut config_warnings,
+                    format!("Ignoring alarm command from config: {e}"),
+                ),
+            }
+        }
+
+        let chime = config.chime.enabled.then(|| {
+            let quiet_hours = match config.chime.quiet_hours.trim() {
+                "" => None,
+                spec => TimeRange::parse(spec)
+                    .inspect_err(|e| {
+                        config_warning(
+                            &mut config_warnings,
+                            format!("Ignoring chime quiet_hours from config: {e}"),
+                        );
+                    })
+                    .ok(),
+            };
+            Chime::new(
+                config.chime.quarters,
+                config.chime.bells,
+                config.chime.command.clone(),
+                quiet_hours,
+            )
+        });
+
+        let (schedule, schedule_errors) = Schedule::parse_all(&config.show_schedule);
+        for e in schedule_errors {
+            config_warning(
+                &mut config_warnings,
+                format!("Ignoring show_schedule range from config: {e}"),
+            );
+        }
+
+        let (date_overrides, date_override_errors) = DateOverrides::parse_all(
+            config
+                .date_overrides
+                .iter()
+                .map(|(pattern, style)| (pattern.as_str(), style.as_str())),
+        );
+        for e in date_override_errors {
+            config_warning(
+                &mut config_warnings,
+                format!("Ignoring date override from config: {e}"),
+            );
+        }
+
+        // Session layers from the command line replace the configured ones
+        let extra_layers = if cli.layers.is_empty() {
+            config
+                .background_layers
+                .iter()
+                .copied()
+                .filter(|style| {
+                    let ok = style.can_layer();
+                    if !ok {
+                        config_warning(
+                            &mut config_warnings,
+                            format!(
+                                "Ignoring background layer {} from config",
+                                style.display_name()
+                            ),
+                        );
+                    }
+                    ok
+                })
+                .collect()
+        } else {
+            cli.layers
+        };
+        if extra_layers.len() >= MAX_LAYERS {
+            eprintln!(
+                "Warning: Only {} background layers are drawn",
+                MAX_LAYERS - 1
+            );
+        }
 
         // Initialize font registry with bundled fonts
         let mut font_registry = FontRegistry::new();
@@ -103,290 +528,1680 @@ impl App {
         // Get current time for initial state
         let now = chrono::Local::now();
 
-        // Initialize system monitor if reactive background is selected
-        let system_monitor = if config.background_style.is_reactive() {
-            let monitor = SystemMonitor::new();
+        // Initialize system monitor if reactive background or load line is enabled
+        let system_monitor = (background_mode.style().is_reactive() || config.show_load)
+            .then(|| start_system_monitor(&config));
+
+        let app_metrics = Arc::new(AppMetrics::default());
+
+        // Initialize weather monitor if the config needs it, e.g. for the Auto theme
+        let weather_monitor = if needs_weather(
+            background_mode,
+            config.show_weather,
+            config.color_theme,
+            config.auto_dim,
+        ) {
+            let monitor = WeatherMonitor::new(weather_location.clone(), weather_provider)
+                .with_kp_index(config.aurora_kp)
+                .with_metrics(Arc::clone(&app_metrics));
             monitor.start();
             Some(monitor)
         } else {
             None
         };
 
-        // Initialize weather monitor if weather background is selected
-        let weather_monitor = if config.background_style.requires_weather() {
-            let monitor = WeatherMonitor::new(config.weather_location.clone());
+        // Check the system clock in the background if asked to
+        let clock_check = config.clock_check.then(|| {
+            let check = ClockCheck::new(config.ntp_server.clone(), config.time_url.clone());
+            check.start();
+            check
+        });
+
+        // Initialize calendar monitor if calendar files are set
+        let calendar = (!config.calendar_files.is_empty()).then(|| {
+            let monitor = CalendarMonitor::new(config.calendar_files.clone());
             monitor.start();
-            Some(monitor)
-        } else {
-            None
+            monitor
+        });
+
+        // The banner from the command line wins over the config's
+        let banner_from_cli = cli.banner.is_some() || cli.banner_file.is_some();
+        let banner_file = cli.banner_file.map(FileWatcher::new);
+        let banner = match (&cli.banner, &banner_file) {
+            (Some(banner), _) => Some(banner.clone()),
+            (None, Some(watcher)) => read_banner_file(watcher.path()),
+            (None, None) => (!config.banner.is_empty()).then(|| config.banner.clone()),
         };
 
-        Self {
+        let presets = merge_presets(&config.presets);
+        let preset = cli.preset.as_deref().and_then(|name| {
+            let index = find_preset(&presets, name);
+            if index.is_none() {
+                eprintln!("Warning: Unknown preset \"{name}\"");
+            }
+            index
+        });
+        let preset_schedule = ScheduleResolver::new(
+            config
+                .preset_schedule
+                .iter()
+                .filter_map(|(range, name)| {
+                    let rule = ScheduledPreset::parse(range, name).and_then(|rule| {
+                        match find_preset(&presets, &rule.preset) {
+                            Some(_) => Ok(rule),
+                            None => Err(format!("unknown preset \"{name}\"")),
+                        }
+                    });
+                    match rule {
+                        Ok(rule) => Some(rule),
+                        Err(e) => {
+                            config_warning(
+                                &mut config_warnings,
+                                format!(
+                                    "Ignoring preset schedule \"{range}\" = \"{name}\" from config: {e}"
+                                ),
+                            );
+                            None
+                        }
+                    }
+                })
+                .collect(),
+        );
+
+        let (panes, pane_warnings) = Panes::from_config(config.pane_layout, &config.panes);
+        for warning in &pane_warnings {
+            config_warning(&mut config_warnings, warning.clone());
+        }
+
+        let mut status = StatusRegistry::default();
+        status.report(
+            Subsystem::Config,
+            status::config_status(config_error.as_deref(), &config_warnings),
+        );
+
+        let mut app = Self {
             running: false,
             time_format: config.time_format,
+            am_pm_style: config.am_pm_style,
+            date_format: config.date_format,
+            clock_position: cli.position.unwrap_or(config.clock_position),
+            clock_border: config.clock_border,
+            accessibility: if cli.high_contrast {
+                Accessibility::HighContrast
+            } else if cli.plain {
+                Accessibility::Plain
+            } else {
+                config.accessibility
+            },
+            spacing: config_spacing(&config),
+            digit_fill,
+            display_mode: config.display_mode,
+            subsecond_precision: config.subsecond_precision,
             color_theme: config.color_theme,
+            theme_overrides: config.theme,
+            presets,
+            preset_index: None,
+            preset_schedule,
+            scheduled_preset: None,
+            unscheduled_look: Preset::default(),
             animation_style: config.animation_style,
             animation_speed: config.animation_speed,
             colon_blink: config.colon_blink,
-            background_style: config.background_style,
+            background_mode,
+            extra_layers,
+            date_overrides,
+            weather_provider,
+            weather_location,
+            show_weather: config.show_weather,
+            show_load: config.show_load,
+            info_line: InfoLine::default(),
+            progress_bar: config.progress_bar,
+            bounce: None,
+            schedule,
+            alarm_state: AlarmState::new(alarms),
+            saved_alarm: None,
+            chime,
+            hooks: Hooks::new(),
             current_font: config.font_name.clone(),
             font_registry,
             settings_dialog,
+            help: HelpState::new(Duration::from_secs(config.help_idle_secs), Instant::now()),
+            keymap,
+            help_line,
+            toasts: Toasts::default(),
             config,
             animation_start: Instant::now(),
-            last_second: now.format("%S").to_string().parse().unwrap_or(0),
-            last_minute: now.format("%M").to_string().parse().unwrap_or(0),
-            last_hour: now.format("%H").to_string().parse().unwrap_or(0),
+            paused: None,
+            mono: cli.mono || compat::no_color_requested(),
+            ascii: cli.ascii || compat::locale_lacks_utf8(),
+            dim_hours,
+            last_second: now.second(),
+            last_minute: now.minute(),
+            last_hour: now.hour(),
             flash_intensity: 0.0,
             flash_start: None,
-            background_state: BackgroundState::new(),
+            seed: cli.seed,
+            precipitation: cli.background.and_then(|arg| arg.intensity),
+            background_state: new_background_state(cli.seed),
             system_monitor,
             weather_monitor,
+            calendar,
+            clock_check,
+            weather: WeatherState::default(),
+            status,
+            frame_server: cli.serve_text.map(FrameServer::start),
+            serve_background: cli.serve_bg,
+            app_metrics,
+            metrics_server: None,
+            frame_stats: FrameStats::default(),
+            debug_visible: false,
+            recording: cli.record.map(|path| Recording::new(path, cli.duration)),
+            hit_areas: HitAreas::default(),
+            last_frame: None,
+            config_watcher: FileWatcher::new(Config::config_file_path()),
+            banner,
+            banner_file,
+            banner_from_cli,
+            panes,
+            config_warnings,
+            key_warnings,
+            pane_warnings,
+            help_line_warnings,
+        };
+        if let Some(index) = preset {
+            app.apply_preset(index);
+            // Options on the command line win over the preset
+            if let Some(arg) = cli.background {
+                app.background_mode = BackgroundMode::from_style(arg.style);
+                app.update_background_monitors();
+            }
         }
+        // A preset picked on the command line wins over the schedule until its next change
+        app.unscheduled_look = app.current_look();
+        app.scheduled_preset = app
+            .preset_schedule
+            .active_at(&app.display_now())
+            .map(String::from);
+        if preset.is_none()
+            && let Some(index) = app
+                .scheduled_preset
+                .as_deref()
+                .and_then(|name| find_preset(&app.presets, name))
+        {
+            app.apply_preset(index);
+        }
+        app
     }
 
     /// Run the application's main loop.
     pub fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         self.running = true;
+        self.restore_alarm();
+        if self.config.mouse {
+            execute!(std::io::stdout(), EnableMouseCapture)?;
+        }
         while self.running {
-            terminal.draw(|frame| self.render(frame))?;
-            self.handle_crossterm_events()?;
+            self.update_config();
+            self.update_banner();
+            self.update_alarms();
+            self.update_panes();
+            self.update_chime();
+            self.update_hooks();
+            self.update_preset_schedule();
+            self.update_weather();
+            self.update_status();
+            self.update_toasts();
+            if self.paused.is_none() {
+                self.update_flash(&self.now());
+            }
+
+            // Skip drawing when nothing visible changed since the last frame. The
+            // debug overlay's timings are left out of the key, or showing them
+            // would redraw every frame; they update with the frames drawn anyway
+            let frame_key = self.frame_key(terminal.size()?);
+            let timeout = self.frame_timeout(&frame_key);
+            if frame_key.needs_redraw(self.last_frame.as_ref()) {
+                let started = Instant::now();
+                let completed = terminal.draw(|frame| self.render(frame, true))?;
+                let render_time = started.elapsed();
+                self.frame_stats.record(started, render_time);
+                self.app_metrics
+                    .record_frame(render_time, self.effective_background());
+                self.serve_frame(frame_key.second, completed.buffer)?;
+                if let Some(recording) = &mut self.recording {
+                    recording.frame(completed.buffer)?;
+                }
+                self.last_frame = Some(frame_key);
+            } else {
+                self.app_metrics.record_skipped_frame();
+            }
+
+            // A recording session ends once the recording time is over
+            if self.recording.as_ref().is_some_and(Recording::is_done) {
+                self.running = false;
+                break;
+            }
+
+            self.handle_crossterm_events(timeout)?;
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Serve the app's counters at `/metrics` on `addr` until the app exits.
+    fn serve_metrics(&mut self, addr: SocketAddr) -> std::io::Result<()> {
+        self.metrics_server = Some(MetricsServer::bind(addr, Arc::clone(&self.app_metrics))?);
+        Ok(())
+    }
+
+    /// Print the problems found in the config to stderr.
+    fn print_config_warnings(&self) {
+        for warning in &self.config_warnings {
+            eprintln!("Warning: {warning}");
         }
+    }
+
+    /// Render a frame of `size` off screen and write it to `path`: ANSI-colored
+    /// text for a `.ans` or `.ansi` file, plain text otherwise.
+    fn export(&mut self, path: &Path, size: Size) -> color_eyre::Result<()> {
+        let mut terminal = Terminal::new(TestBackend::new(size.width, size.height))?;
+        let completed = terminal.draw(|frame| self.render(frame, true))?;
+        let text = if path
+            .extension()
+            .is_some_and(|extension| extension == "ans" || extension == "ansi")
+        {
+            ansi::buffer_ansi(completed.buffer)
+        } else {
+            serve::buffer_text(completed.buffer)
+        };
+        fs::write(path, text)?;
         Ok(())
     }
 
-    /// Renders the user interface.
-    fn render(&mut self, frame: &mut Frame) {
-        let now = Local::now();
+    /// Renders the user interface, optionally without the background.
+    fn render(&mut self, frame: &mut Frame, background: bool) {
+        if self.accessibility == Accessibility::Plain {
+            self.render_plain(frame);
+            return;
+        }
+        let now = self.display_now();
 
         // Calculate animation elapsed time
-        let elapsed_ms = self.animation_start.elapsed().as_millis() as u64;
+        let elapsed_ms = self.elapsed_ms();
 
         // Get metrics for reactive backgrounds
-        let metrics = self.system_monitor.as_ref().map(|m| m.get_metrics());
-
-        // Resolve weather background to actual style
-        let effective_background = if self.background_style == BackgroundStyle::Weather {
-            self.weather_monitor
-                .as_ref()
-                .map(|m| m.get_background())
-                .unwrap_or(BackgroundStyle::Starfield)
+        let metrics = self
+            .system_monitor
+            .as_ref()
+            .filter(|monitor| monitor.is_enabled())
+            .map(|m| m.get_metrics_interpolated(Instant::now()));
+
+        let area = frame.area();
+        let clock_visible = self.clock_visible();
+
+        let meridiem = TimeParts::from_datetime(&now, self.time_format).meridiem(self.time_format);
+
+        // Format date, ending in the AM/PM marker when it is hidden from the digits
+        let mut date_str = now.format(self.date_format.pattern()).to_string();
+        if let Some(meridiem) = meridiem
+            && self.am_pm_style == AmPmStyle::Hidden
+            && self.display_mode == DisplayMode::Digits
+        {
+            date_str = format!("{date_str} · {meridiem}");
+        }
+        let weather_line = self.weather_line();
+        let load = self
+            .load_history()
+            .map(|history| (history, metrics.as_ref().map_or(0.0, |m| m.memory_usage)));
+        let agenda = self.agenda_line();
+        let info_str = self.info_line.format(&now);
+        let dst_str = self.dst_line();
+        let progress = self.progress_bar.fraction(&now);
+        let alarm_str = self.alarm_state.ringing().map(|alarm| {
+            let mut parts = vec![format!("ALARM {alarm}")];
+            if !alarm.label.is_empty() {
+                parts.push(alarm.label.clone());
+            }
+            // An alarm missed while the clock was closed says when it went off
+            parts.extend(self.alarm_state.missed().map(alarm::format_ago));
+            parts.push("press any key".to_string());
+            parts.join(" · ")
+        });
+        let alarm_flash = alarm_str.is_some() && is_colon_visible(elapsed_ms);
+
+        // The help row goes back to the clock once the help line hides
+        let help_visible = clock_visible && self.help.line_visible(Instant::now());
+        let [body, help_chunk] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(u16::from(help_visible)),
+        ])
+        .areas(area);
+        let extra_lines = [
+            weather_line.as_ref().map(|line| line.text.as_str()),
+            agenda.as_ref().map(|line| line.text.as_str()),
+            info_str.as_deref(),
+            dst_str.as_deref(),
+            alarm_str.as_deref(),
+        ];
+        // Rows below the time; the layout and the block height both come from these
+        let mut line_constraints = vec![
+            Constraint::Length(2), // Spacing
+            Constraint::Length(1), // Date
+        ];
+        if load.is_some() {
+            line_constraints.push(Constraint::Length(1)); // CPU and memory line
+        }
+        if progress.is_some() {
+            line_constraints.push(Constraint::Length(1)); // Progress bar
+        }
+        for _ in extra_lines.iter().flatten() {
+            line_constraints.push(Constraint::Length(1)); // Weather, agenda, info, clock change or alarm line
+        }
+        let lines_height: u16 = line_constraints
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::Length(rows) => *rows,
+                _ => 0,
+            })
+            .sum();
+
+        // A border needs a cell of room on every side
+        let border_size = if self.clock_border == ClockBorder::None {
+            0
+        } else {
+            border::BORDER_SIZE
+        };
+        // The seconds ring takes a cell around the time on every side
+        let ring_size = if self.config.seconds_ring.is_shown() {
+            seconds_ring::RING_SIZE
+        } else {
+            0
+        };
+        // The banner box goes outside the border, above or below it
+        let banner_text = self.banner_text(area.width);
+        let banner_width = self
+            .banner
+            .as_deref()
+            .map(|message| (text_width(message) as u16 + BANNER_PADDING).min(body.width));
+        let banner_height = if banner_width.is_some() {
+            BANNER_HEIGHT
         } else {
-            self.background_style
+            0
         };
 
-        // Render background first (behind everything else)
-        self.background_state.render(
-            frame,
-            effective_background,
+        // Get current font and render
+        let font = self.clock_font();
+        let theme = self.theme();
+        let style = ClockStyle {
+            theme: self.gradient_theme(self.theme_overrides.digit_color.is_some()),
+            color: theme.digit_color,
+            animation_style: self.animation_style,
+            animation_speed: self.animation_speed,
             elapsed_ms,
-            self.animation_speed,
-            metrics.as_ref(),
+            flash_intensity: self.flash_intensity,
+            colon_blink: self.colon_blink,
+            // Flash the digits while an alarm is ringing
+            reversed: alarm_flash,
+        };
+        let clock = match self.display_mode {
+            DisplayMode::Digits => {
+                let clock = ClockWidget::new(
+                    ClockConfig {
+                        font,
+                        time_format: self.time_format,
+                        am_pm_style: self.am_pm_style,
+                        pad_hour: self.config.pad_hour,
+                        show_seconds: self.config.seconds_ring != SecondsRing::Only,
+                        spacing: self.spacing,
+                        fill: self.digit_fill.as_ref(),
+                        style,
+                    },
+                    &now,
+                );
+                // Blow the digits up as far as the room below them allows
+                let max_scale = match self.config.clock_scale {
+                    0 => MAX_AUTO_SCALE,
+                    scale => scale,
+                };
+                let scale = fit_scale(
+                    clock.width(),
+                    clock.height(),
+                    body.width.saturating_sub(border_size + ring_size) as usize,
+                    body.height
+                        .saturating_sub(lines_height + border_size + ring_size + banner_height)
+                        as usize,
+                    max_scale,
+                );
+                clock.scaled(scale)
+            }
+            DisplayMode::Words => {
+                let phrase = fuzzy_time(now.hour(), now.minute(), self.config.fuzzy_granularity);
+                let lines = text_clock::word_art(
+                    font,
+                    &phrase,
+                    body.width.saturating_sub(border_size + ring_size) as usize,
+                    body.height
+                        .saturating_sub(lines_height + border_size + ring_size + banner_height)
+                        as usize,
+                );
+                ClockWidget::from_art(lines, phrase, style)
+            }
+        };
+        let (width, height) = (clock.width(), clock.height());
+
+        // Vertical layout of the clock block: the rows of the art as drawn,
+        // AM/PM marker included, then the lines below it
+        let art_height = height as u16 + ring_size;
+        let mut constraints = vec![Constraint::Length(art_height)]; // Big digits or words
+        constraints.extend(line_constraints);
+        let block_width = extra_lines.into_iter().flatten().map(text_width).fold(
+            (width + usize::from(ring_size)).max(text_width(&date_str)),
+            usize::max,
         );
+        let block_height = art_height + lines_height;
 
-        // Update flash intensity for reactive animation
-        self.update_flash(&now);
-
-        // Get time components
-        let (hours, is_pm) = match self.time_format {
-            TimeFormat::TwentyFourHour => {
-                (now.format("%H").to_string().parse().unwrap_or(0), false)
+        let size = Size::new(
+            (block_width as u16 + border_size).max(banner_width.unwrap_or(0)),
+            block_height + border_size + banner_height,
+        );
+        let placed = match self.bounce.as_mut() {
+            // Drift the block around, keeping it fully on screen
+            Some(bounce) => {
+                let (block, bounced) = bounce.update(elapsed_ms, self.animation_speed, size, body);
+                // Change theme quietly; a toast on every bounce would be noise
+                if bounced && self.config.bounce_cycle_color {
+                    let theme = self.color_theme.next();
+                    // Only the Auto theme needs a monitor, so only entering or leaving it matters
+                    let auto_changed =
+                        (theme == ColorTheme::Auto) != (self.color_theme == ColorTheme::Auto);
+                    self.color_theme = theme;
+                    if auto_changed {
+                        self.update_background_monitors();
+                    }
+                }
+                block
             }
-            TimeFormat::TwelveHour => {
-                let h: u32 = now.format("%I").to_string().parse().unwrap_or(12);
-                let pm = now.format("%p").to_string() == "PM";
-                (h, pm)
+            // Anchor the block at the chosen position, with each line centered in it
+            None => self.clock_position.place(size, body),
+        };
+        // Nudge the block now and then so the digits don't burn into the screen
+        let placed = match self.burn_in_offset() {
+            Some(offset) => burn_in::shift(placed, size.width, offset, body),
+            None => placed,
+        };
+        let (banner_rect, placed) = match banner_width {
+            Some(width) => {
+                let (banner_rect, rest) = banner::place(placed, width, self.config.banner_position);
+                (Some(banner_rect), rest)
             }
+            None => (None, placed),
+        };
+        let border_rect =
+            (border_size > 0).then(|| border::border_rect(placed, block_width as u16));
+        let block = border_rect.map(border::inner).unwrap_or(placed);
+        let chunks = Layout::vertical(constraints).split(block);
+
+        // The time is centered in its chunk, inside the seconds ring if shown
+        let ring_rect = (ring_size > 0).then(|| {
+            let chunk = chunks[0];
+            let ring_width = (width as u16 + ring_size).min(chunk.width);
+            Rect {
+                x: chunk.x + (chunk.width - ring_width) / 2,
+                width: ring_width,
+                ..chunk
+            }
+        });
+        let chunk = ring_rect.map_or(chunks[0], |rect| rect.inner(Margin::new(1, 1)));
+        let art_width = width as u16;
+        let start_x = chunk.x + (chunk.width.saturating_sub(art_width)) / 2;
+
+        let time_rect = Rect::new(
+            start_x,
+            chunk.y,
+            art_width.min(chunk.width),
+            (height as u16).min(chunk.height),
+        );
+        let date_chunk = chunks[2];
+        let date_width = (text_width(&date_str) as u16).min(date_chunk.width);
+        let date_rect = Rect::new(
+            date_chunk.x + (date_chunk.width - date_width) / 2,
+            date_chunk.y,
+            date_width,
+            date_chunk.height.min(1),
+        );
+
+        // A split screen draws its panes instead of the clock block
+        let pane_frames = self.pane_frames();
+        let pane_areas = if pane_frames.is_empty() {
+            Vec::new()
+        } else {
+            self.panes.areas(body)
         };
-        let minutes: u32 = now.format("%M").to_string().parse().unwrap_or(0);
-        let seconds: u32 = now.format("%S").to_string().parse().unwrap_or(0);
+        let single_visible = clock_visible && pane_frames.is_empty();
+
+        // Keep the background behind the digits (and optionally the date) readable
+        let mut clear_rects = Vec::new();
+        if clock_visible {
+            // Each pane is framed, so like a bordered clock it is cleared whole
+            clear_rects.extend(
+                pane_areas
+                    .iter()
+                    .zip(&pane_frames)
+                    .filter(|(_, pane)| pane.time.is_some())
+                    .map(|(area, _)| *area),
+            );
+        }
+        if single_visible {
+            clear_rects.push(time_rect);
+            clear_rects.extend(ring_rect);
+            if self.config.clear_zone_date {
+                clear_rects.push(date_rect);
+            }
+            // Inside a border, the clear zone covers the whole frame
+            clear_rects.extend(border_rect);
+            clear_rects.extend(banner_rect);
+        }
 
-        // Format date
-        let date_str = now.format("%A, %B %d, %Y").to_string();
+        // Resolve weather background to actual style, with any extra layers on top
+        let layers = if background {
+            self.background_layers()
+        } else {
+            Vec::new()
+        };
 
-        let color = self.color_theme.color();
-        let area = frame.area();
+        // Render background first (behind everything else)
+        let background_widget = BackgroundWidget::layered(&layers, elapsed_ms)
+            .speed(self.animation_speed)
+            .metrics(metrics.as_ref())
+            .clear_zones(ClearZones {
+                rects: &clear_rects,
+                mode: self.config.clear_zone,
+                dim_factor: self.config.clear_zone_dim,
+            });
+        self.background_state
+            .set_moon_phase(moon_phase(now.date_naive()));
+        let time = TimeParts::from_datetime(&now, self.time_format);
+        self.background_state.set_time_digits(&format!(
+            "{:02}{:02}{:02}",
+            time.hours, time.minutes, time.seconds
+        ));
+        let weather = self.weather.data().filter(|_| !self.weather.is_stale());
+        self.background_state
+            .set_wind_kmph(weather.map(|data| data.wind_kmph));
+        self.background_state
+            .set_temperature_c(weather.map(|data| data.temp_c));
+        self.background_state
+            .set_precipitation(self.precipitation.or(weather.map(|data| data.intensity)));
+        self.background_state
+            .set_aurora_activity(weather.map(WeatherData::aurora_activity));
+        if let Some(monitor) = self.system_monitor.as_ref().filter(|m| m.is_enabled()) {
+            self.background_state
+                .set_network_history(&monitor.network_history());
+        }
+        // Settled snow stays above the help line
+        self.background_state
+            .set_ground_margin(u16::from(help_visible));
+        frame.render_stateful_widget(background_widget, area, &mut self.background_state);
+
+        if let Some(rect) = border_rect.filter(|_| single_visible) {
+            let title = self.border_title();
+            let border_color = self
+                .config
+                .border_color
+                .map_or(theme.digit_color, |border_theme| {
+                    self.resolve_color(border_theme)
+                });
+            border::render(
+                frame.buffer_mut(),
+                rect,
+                self.clock_border,
+                &title,
+                border_color,
+            );
+        }
 
-        // Build time string
-        let time_str = match self.time_format {
-            TimeFormat::TwentyFourHour => {
-                format!("{hours:02}:{minutes:02}:{seconds:02}")
+        if let Some(rect) = banner_rect.filter(|_| single_visible) {
+            // A box in the accent color, in the clock's border style if it has one
+            let border_style = match self.clock_border {
+                ClockBorder::None => ClockBorder::Rounded,
+                border_style => border_style,
+            };
+            border::render(
+                frame.buffer_mut(),
+                rect,
+                border_style,
+                "",
+                theme.accent_color,
+            );
+            if let Some(text) = banner_text.as_deref() {
+                let text_rect = Rect {
+                    height: rect.height.min(1),
+                    ..border::inner(rect)
+                };
+                let accent = (0..text.chars().count(), theme.accent_color);
+                self.render_text_line(frame, text_rect, text, elapsed_ms, Some(accent));
             }
-            TimeFormat::TwelveHour => {
-                let ampm = if is_pm { "PM" } else { "AM" };
-                format!("{hours:2}:{minutes:02}:{seconds:02} {ampm}")
+        }
+
+        if clock_visible {
+            self.render_panes(frame, &pane_areas, &pane_frames, style);
+        }
+        if single_visible {
+            // The widget skips spaces, so the background shows between the glyphs
+            frame.render_widget(&clock, time_rect);
+            if self.accessibility == Accessibility::HighContrast {
+                accessibility::add_shadow(frame.buffer_mut(), time_rect);
+            }
+            if let Some(rect) = ring_rect {
+                let remaining = if self.config.seconds_ring_split {
+                    theme.muted_color
+                } else {
+                    theme.digit_color
+                };
+                seconds_ring::render(
+                    frame.buffer_mut(),
+                    rect,
+                    now.second(),
+                    theme.digit_color,
+                    remaining,
+                );
             }
-        };
 
-        // Get current font and render
-        let font = self.font_registry.get_or_default(&self.current_font);
-        let time_lines = font.render_text(&time_str);
-        let font_height = font.height as u16;
-
-        // Create vertical layout for centering
-        let chunks = Layout::vertical([
-            Constraint::Fill(1),             // Top padding
-            Constraint::Length(font_height), // Big digits (dynamic height)
-            Constraint::Length(2),           // Spacing
-            Constraint::Length(1),           // Date
-            Constraint::Fill(1),             // Bottom padding
-            Constraint::Length(1),           // Help text
-        ])
-        .split(area);
-
-        // Render big time
-        let height = time_lines.len();
-        let width = time_lines.first().map(|s| s.chars().count()).unwrap_or(0);
-
-        // Build colon position mask for blink effect
-        // Maps x-positions in rendered ASCII art back to colon characters in time_str
-        let colon_positions: Vec<bool> = if self.colon_blink {
-            let mut mask = vec![false; width];
-            let mut x_pos = 0;
-            for ch in time_str.chars() {
-                let char_width = font.char_width(ch);
-                if ch == ':' {
-                    for i in 0..char_width {
-                        if x_pos + i < mask.len() {
-                            mask[x_pos + i] = true;
-                        }
-                    }
-                }
-                x_pos += char_width;
+            // Render sub-second digits in the spacing under the time, from the same `now`
+            if let Some(subsec_str) = self
+                .effective_subsecond_precision()
+                .format(now.timestamp_subsec_millis())
+            {
+                let subsec_chunk = Rect {
+                    height: chunks[1].height.min(1),
+                    ..chunks[1]
+                };
+                self.render_text_line(frame, subsec_chunk, &subsec_str, elapsed_ms, None);
+            }
+
+            // Render date, weather and info lines directly to buffer
+            self.render_text_line(frame, chunks[2], &date_str, elapsed_ms, None);
+            let mut next_row = 3;
+            if let Some((history, memory)) = &load {
+                render_load_line(frame, chunks[next_row], history, *memory);
+                next_row += 1;
+            }
+            if let Some(fraction) = progress {
+                self.render_progress_bar(frame, chunks[next_row], fraction);
+                next_row += 1;
+            }
+            if let Some(weather_line) = &weather_line {
+                // Temperature is colored from cold blue to hot red instead of the theme
+                let accent = weather_line
+                    .temperature
+                    .clone()
+                    .map(|(temp_c, range)| (range, temperature_to_color(temp_c as f32)));
+                self.render_text_line(
+                    frame,
+                    chunks[next_row],
+                    &weather_line.text,
+                    elapsed_ms,
+                    accent,
+                );
+                next_row += 1;
+            }
+            if let Some(agenda) = &agenda {
+                // Events starting soon stand out in the accent color
+                let accent = agenda.soon.clone().map(|range| (range, theme.accent_color));
+                self.render_text_line(frame, chunks[next_row], &agenda.text, elapsed_ms, accent);
+                next_row += 1;
+            }
+            if let Some(info_str) = &info_str {
+                self.render_text_line(frame, chunks[next_row], info_str, elapsed_ms, None);
+                next_row += 1;
             }
-            mask
+            if let Some(dst_str) = &dst_str {
+                // A quiet heads-up, in the muted color
+                let accent = (0..dst_str.chars().count(), theme.muted_color);
+                self.render_text_line(frame, chunks[next_row], dst_str, elapsed_ms, Some(accent));
+                next_row += 1;
+            }
+            if let Some(alarm_str) = &alarm_str {
+                self.render_text_line(frame, chunks[next_row], alarm_str, elapsed_ms, None);
+            }
+        }
+
+        // Render the help line's pinned actions, each entry clickable like its key
+        let hotkeys = self.help_line_entries();
+        let help = Line::from(
+            hotkeys
+                .iter()
+                .flat_map(|(key, _, label)| {
+                    [
+                        Span::from(key.as_str()).bold().fg(theme.accent_color),
+                        Span::from(label.as_str()).fg(theme.muted_color),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        )
+        .centered();
+        frame.render_widget(help, help_chunk);
+
+        let hotkey_widths: Vec<(usize, Action)> = hotkeys
+            .iter()
+            .map(|(key, action, label)| (text_width(key) + text_width(label), *action))
+            .collect();
+        self.hit_areas = HitAreas {
+            time: if single_visible {
+                time_rect
+            } else {
+                Rect::default()
+            },
+            date: if single_visible {
+                date_rect
+            } else {
+                Rect::default()
+            },
+            hotkeys: mouse::hotkey_rects(help_chunk, &hotkey_widths),
+        };
+
+        // Mark a paused display in the corner opposite the toasts
+        if self.paused.is_some() {
+            let label = " paused ";
+            let label_rect = Rect::new(
+                area.x,
+                area.y,
+                (label.len() as u16).min(area.width),
+                area.height.min(1),
+            );
+            frame.render_widget(Clear, label_rect);
+            frame.render_widget(Span::from(label).fg(theme.accent_color), label_rect);
+        }
+
+        // Mark the worst subsystem problem below the paused label, if any
+        if let (Some(marker), Some((_, status))) = (self.status.marker(), self.status.worst()) {
+            let row = u16::from(self.paused.is_some());
+            let marker_rect = Rect::new(
+                area.x,
+                area.y + row,
+                (text_width(&marker) as u16).min(area.width),
+                area.height.saturating_sub(row).min(1),
+            );
+            let color = match status {
+                Status::Failed(_) => Color::Red,
+                _ => Color::Yellow,
+            };
+            frame.render_widget(Clear, marker_rect);
+            frame.render_widget(Span::from(marker).fg(color), marker_rect);
+        }
+
+        // Warn about a drifting system clock in the opposite corner
+        if let Some(indicator) = self.clock_drift_indicator() {
+            let width = (text_width(&indicator) as u16).min(area.width);
+            let indicator_rect = Rect::new(area.right() - width, area.y, width, area.height.min(1));
+            frame.render_widget(Clear, indicator_rect);
+            frame.render_widget(Span::from(indicator).fg(Color::Yellow), indicator_rect);
+        }
+
+        // Render settings dialog and key overlay if visible, then toasts above everything
+        self.settings_dialog.render(frame, area, theme.accent_color);
+        self.help
+            .render_overlay(frame, area, theme.accent_color, &self.keymap, &self.status);
+        self.toasts
+            .render(frame, area, theme.accent_color, Instant::now());
+        if self.debug_visible {
+            let weather = self.weather_monitor.as_ref().map(|_| self.weather.status());
+            let info = DebugInfo {
+                stats: &self.frame_stats,
+                background: self.effective_background(),
+                seed: self.background_state.seed(),
+                size: area.as_size(),
+                metrics: metrics.as_ref(),
+                weather,
+            };
+            debug::render_overlay(frame, area, &info, theme.accent_color);
+        }
+
+        // Compatibility modes, high contrast, the solid background and dimming
+        // rewrite the finished frame rather than each widget
+        if self.accessibility == Accessibility::HighContrast {
+            accessibility::to_high_contrast(frame.buffer_mut());
+        } else if let Some(color) = theme.background_color {
+            sigye_background::fill_background(frame.buffer_mut(), color);
+        }
+        self.finish_frame(frame, &now);
+    }
+
+    /// Draw the plain text display: the time and date as lines of text in the
+    /// top left corner, with only the dialogs and toasts over them.
+    fn render_plain(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let now = self.display_now();
+        let lines: Vec<Line> = accessibility::plain_lines(
+            &now,
+            self.time_format,
+            self.date_format,
+            self.alarm_state.ringing(),
+        )
+        .into_iter()
+        .map(Line::from)
+        .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+        self.hit_areas = HitAreas::default();
+
+        let accent_color = self.theme().accent_color;
+        self.settings_dialog.render(frame, area, accent_color);
+        self.help
+            .render_overlay(frame, area, accent_color, &self.keymap, &self.status);
+        self.toasts
+            .render(frame, area, accent_color, Instant::now());
+        self.finish_frame(frame, &now);
+    }
+
+    /// Apply the compatibility modes and dimming to the finished frame.
+    fn finish_frame(&self, frame: &mut Frame, now: &DateTime<Local>) {
+        if self.ascii {
+            sigye_background::to_ascii(frame.buffer_mut());
+        }
+        // High contrast stays pure white
+        let brightness = self.brightness_at(now);
+        if brightness < 1.0 && self.accessibility != Accessibility::HighContrast {
+            sigye_background::dim_colors(frame.buffer_mut(), brightness);
+        }
+        if self.mono {
+            sigye_background::strip_colors(frame.buffer_mut());
+        }
+    }
+
+    /// Send the frame to the `--serve-text` path once per second.
+    ///
+    /// `drawn` is the frame just drawn on the terminal. Without the background it
+    /// is drawn again off screen, since the background is under the clock cells.
+    fn serve_frame(&mut self, timestamp: i64, drawn: &Buffer) -> color_eyre::Result<()> {
+        if !self
+            .frame_server
+            .as_ref()
+            .is_some_and(|server| server.is_due(timestamp))
+        {
+            return Ok(());
+        }
+        let text = if self.serve_background {
+            serve::buffer_text(drawn)
         } else {
-            vec![]
+            let area = drawn.area;
+            let mut terminal = Terminal::new(TestBackend::new(area.width, area.height))?;
+            let completed = terminal.draw(|frame| self.render(frame, false))?;
+            serve::buffer_text(completed.buffer)
         };
+        if let Some(server) = &mut self.frame_server {
+            server.send(timestamp, text);
+        }
+        Ok(())
+    }
 
-        // Render time directly to buffer, skipping spaces to preserve background
-        let chunk = chunks[1];
-        let text_width = width as u16;
-        let start_x = chunk.x + (chunk.width.saturating_sub(text_width)) / 2;
+    /// Check if the clock is drawn: inside the show schedule, for a while after
+    /// a key press, or while an alarm is ringing.
+    fn clock_visible(&self) -> bool {
+        self.schedule.shows_at(&self.now())
+            || self.help.input_within(CLOCK_REVEAL, Instant::now())
+            || self.alarm_state.ringing().is_some()
+    }
 
-        let buf = frame.buffer_mut();
-        for (line_idx, line) in time_lines.iter().enumerate() {
-            let y_pos = chunk.y + line_idx as u16;
-            if y_pos >= chunk.y + chunk.height {
-                break;
+    /// Current anti-burn-in offset, `None` when off or while the clock drifts anyway.
+    fn burn_in_offset(&self) -> Option<(i16, i16)> {
+        (self.config.burn_in_shift && self.bounce.is_none()).then(|| {
+            burn_in::offset_at(
+                self.display_now().timestamp(),
+                self.config.burn_in_interval_secs,
+            )
+        })
+    }
+
+    /// Font of the clock digits: the current one, or full blocks in high contrast.
+    fn clock_font(&self) -> &Font {
+        let name = match self.accessibility {
+            Accessibility::HighContrast => HIGH_CONTRAST_FONT,
+            _ => &self.current_font,
+        };
+        self.font_registry.get_or_default(name)
+    }
+
+    /// Resolve the background mode to the style that is actually rendered.
+    fn effective_background(&self) -> BackgroundStyle {
+        if self.accessibility.hides_background() {
+            return BackgroundStyle::None;
+        }
+        match self.background_mode {
+            // Dates with a style of their own go before the weather
+            BackgroundMode::Auto => self
+                .date_overrides
+                .style_on(self.display_now().date_naive())
+                .unwrap_or_else(|| self.weather.background(self.display_now().date_naive())),
+            BackgroundMode::Manual(style) => style,
+            BackgroundMode::Off => BackgroundStyle::None,
+        }
+    }
+
+    /// Background styles to composite, the resolved background first.
+    /// Turning the background off also hides the extra layers.
+    fn background_layers(&self) -> Vec<BackgroundStyle> {
+        let base = self.effective_background();
+        if base == BackgroundStyle::None {
+            return Vec::new();
+        }
+        std::iter::once(base)
+            .chain(self.extra_layers.iter().copied())
+            .take(MAX_LAYERS)
+            .collect()
+    }
+
+    /// Capture everything that affects the next frame for dirty-frame detection.
+    fn frame_key(&self, size: Size) -> FrameKey {
+        let elapsed_ms = self.elapsed_ms();
+        FrameKey {
+            second: self.display_now().timestamp(),
+            time_format: self.time_format,
+            am_pm_style: self.am_pm_style,
+            display_mode: self.display_mode,
+            subsecond_precision: self.effective_subsecond_precision(),
+            color_theme: self.color_theme,
+            background_style: self.effective_background(),
+            animation_style: self.animation_style,
+            font: self.current_font.clone(),
+            info_line: self.info_line,
+            weather: self.weather_line().map(|line| line.text),
+            agenda: self.agenda_line(),
+            dst: self.dst_line(),
+            clock_drift: self.clock_drift_indicator(),
+            banner: self.banner_text(size.width),
+            panes: self.pane_frames(),
+            load: self.load_history().is_some(),
+            progress_bar: self.progress_bar,
+            brightness: self.brightness_at(&self.display_now()),
+            colon_visible: !self.colon_blink || is_colon_visible(elapsed_ms),
+            alarm: self
+                .alarm_state
+                .ringing()
+                .map(|_| is_colon_visible(elapsed_ms)),
+            size,
+            flashing: self.flash_start.is_some(),
+            crossfading: self.background_state.is_crossfading(),
+            clock_position: self.clock_position,
+            clock_border: self.clock_border,
+            accessibility: self.accessibility,
+            spacing: self.spacing,
+            clock_visible: self.clock_visible(),
+            burn_in_offset: self.burn_in_offset(),
+            bouncing: self.bounce.is_some(),
+            help_line: self.help.line_visible(Instant::now()),
+            toasts: self.toasts.messages(Instant::now()),
+            toast_fading: self.toasts.is_fading(Instant::now()),
+            paused: self.paused.is_some(),
+            problems: self
+                .status
+                .problems()
+                .map(|(subsystem, status)| (subsystem, status.clone()))
+                .collect(),
+        }
+    }
+
+    /// Brightness of the frame at `now`, dimmed at night when dimming automatically.
+    fn brightness_at(&self, now: &DateTime<Local>) -> f32 {
+        let darkness = match self.config.auto_dim {
+            AutoDim::Off => 0.0,
+            AutoDim::Schedule => {
+                let (start, end) = self.dim_hours;
+                night_ramp(now.hour() * 60 + now.minute(), start, end)
             }
+            AutoDim::Sun => sun_ramp(self.weather.time_of_day()),
+        };
+        blend(
+            clamp_brightness(self.config.brightness),
+            clamp_brightness(self.config.dim_brightness),
+            darkness,
+        )
+    }
 
-            for (char_idx, ch) in line.chars().enumerate() {
-                // Skip spaces to preserve background transparency
-                if ch == ' ' {
-                    continue;
-                }
+    /// Time shown on the clock: the moment of a pause, or now.
+    fn display_now(&self) -> DateTime<Local> {
+        self.paused.map_or_else(|| self.now(), |(now, _)| now)
+    }
 
-                let x_pos = start_x + char_idx as u16;
-                if x_pos >= chunk.x + chunk.width {
-                    continue;
-                }
+    /// Current time, corrected for the system clock's drift when set to.
+    fn now(&self) -> DateTime<Local> {
+        clock_check::correct(Local::now(), self.clock_correction().as_ref())
+    }
 
-                // Apply colon blink by skipping colon characters during "off" phase
-                let is_colon = colon_positions.get(char_idx).copied().unwrap_or(false);
-                let should_hide = self.colon_blink && is_colon && !is_colon_visible(elapsed_ms);
-                if should_hide {
-                    continue;
-                }
+    /// Measured drift the shown time is corrected by, if correcting and known.
+    fn clock_correction(&self) -> Option<Drift> {
+        if !self.config.correct_clock {
+            return None;
+        }
+        self.clock_check.as_ref()?.drift()
+    }
 
-                // Get base color
-                let base_color = if self.color_theme.is_dynamic() {
-                    self.color_theme
-                        .color_at_position(char_idx, line_idx, width, height)
-                } else {
-                    color
-                };
+    /// Warning about a system clock off by more than the threshold, if any.
+    fn clock_drift_indicator(&self) -> Option<String> {
+        let drift = self.clock_check.as_ref()?.drift()?;
+        drift.indicator(
+            self.config.clock_drift_threshold_secs,
+            self.config.correct_clock,
+        )
+    }
 
-                // Apply animation
-                let animated_color = apply_animation(
-                    base_color,
-                    self.animation_style,
-                    self.animation_speed,
-                    elapsed_ms,
-                    char_idx,
-                    width,
-                    self.flash_intensity,
-                );
+    /// Milliseconds of animation time, frozen while paused.
+    fn elapsed_ms(&self) -> u64 {
+        self.paused.map_or_else(
+            || self.animation_start.elapsed().as_millis() as u64,
+            |(_, elapsed_ms)| elapsed_ms,
+        )
+    }
 
-                // Write directly to buffer
-                if let Some(cell) = buf.cell_mut(Position::new(x_pos, y_pos)) {
-                    cell.set_char(ch);
-                    cell.set_fg(animated_color);
+    /// How long to wait for input before the next frame is due.
+    fn frame_timeout(&self, frame_key: &FrameKey) -> Duration {
+        let speed = self.animation_speed;
+        // Nothing moves while paused, so wait as if there were no background
+        let background_style = if frame_key.paused {
+            BackgroundStyle::None
+        } else {
+            frame_key.background_style
+        };
+        let mut timeout = background_style.target_frame_interval(speed);
+        if frame_key.is_animated() {
+            // Text animations and flashes run at the animation rate over any background
+            timeout = timeout.min(speed.frame_interval());
+        }
+
+        // Sub-second digits need a faster cadence than any animation
+        if let Some(interval) = frame_key
+            .subsecond_precision
+            .frame_interval()
+            .filter(|_| !frame_key.paused)
+        {
+            timeout = timeout.min(interval);
+        }
+
+        // ...and when the help line hides
+        if let Some(hides_in) = self.help.hides_in(Instant::now()) {
+            timeout = timeout.min(hides_in);
+        }
+
+        // ...and when a toast starts fading or disappears
+        if let Some(changes_in) = self.toasts.next_change_in(Instant::now()) {
+            timeout = timeout.min(changes_in);
+        }
+
+        // Wake up right as the next second starts, so the digits flip on time
+        timeout = timeout.min(redraw::until_next_second(&self.now()));
+
+        // ...and as the next second of a running stopwatch or timer starts
+        if let Some(tick_in) = self.panes.until_next_tick(Instant::now()) {
+            timeout = timeout.min(tick_in);
+        }
+
+        // ...and for the next step of a scrolling banner
+        if let Some(message) = &self.banner
+            && !frame_key.paused
+            && banner::scrolls(
+                message,
+                frame_key.size.width.saturating_sub(BANNER_PADDING) as usize,
+            )
+        {
+            let step_ms = self.animation_speed.marquee_step_ms();
+            let elapsed_ms = self.elapsed_ms();
+            timeout = timeout.min(Duration::from_millis(step_ms - elapsed_ms % step_ms));
+        }
+
+        // ...and for the next blink phase
+        let banner_blinks = self.config.banner_blink && self.banner.is_some();
+        if self.colon_blink || frame_key.alarm.is_some() || banner_blinks {
+            let elapsed_ms = self.elapsed_ms();
+            timeout = timeout.min(Duration::from_millis(500 - elapsed_ms % 500));
+        }
+
+        timeout
+    }
+
+    /// Banner text as shown across a screen `width` cells wide: scrolling when
+    /// the message doesn't fit, blank in the off phase of a blink, `None`
+    /// without a banner.
+    fn banner_text(&self, width: u16) -> Option<String> {
+        let message = self.banner.as_deref()?;
+        let elapsed_ms = self.elapsed_ms();
+        if self.config.banner_blink && !is_colon_visible(elapsed_ms) {
+            return Some(String::new());
+        }
+        Some(banner::marquee(
+            message,
+            width.saturating_sub(BANNER_PADDING) as usize,
+            elapsed_ms,
+            self.animation_speed.marquee_step_ms(),
+        ))
+    }
+
+    /// Build the current weather line, if enabled.
+    fn weather_line(&self) -> Option<WeatherLine> {
+        if !self.show_weather {
+            return None;
+        }
+        Some(WeatherLine::new(
+            self.weather.data(),
+            self.weather.status(),
+            self.config.temperature_unit,
+            self.weather
+                .city()
+                .or(self.weather_location.place())
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Build the agenda line of upcoming calendar events, if any are left.
+    fn agenda_line(&self) -> Option<AgendaLine> {
+        self.calendar
+            .as_ref()?
+            .agenda(&self.display_now(), self.time_format)
+    }
+
+    /// Build the notice of a daylight saving change in the next day, if any.
+    fn dst_line(&self) -> Option<String> {
+        if !self.config.show_dst_notice {
+            return None;
+        }
+        let now = self.display_now();
+        let transition = dst::upcoming(&now, DST_NOTICE_AHEAD)?;
+        Some(transition.notice(now.date_naive(), self.time_format))
+    }
+
+    /// CPU history for the load line while it is shown, `None` when hidden or
+    /// while metrics are unavailable.
+    fn load_history(&self) -> Option<MetricsHistory> {
+        let monitor = self
+            .system_monitor
+            .as_ref()
+            .filter(|monitor| self.show_load && monitor.is_enabled())?;
+        if matches!(monitor.status(), Status::Failed(_)) {
+            return None;
+        }
+        Some(monitor.cpu_history()).filter(|history| !history.is_empty())
+    }
+
+    /// Render the progress bar across `area` with its percentage at the end,
+    /// skipping the unfilled cells to preserve the background.
+    fn render_progress_bar(&self, frame: &mut Frame, area: Rect, fraction: f64) {
+        let label = format!(" {:>3.0}%", fraction * 100.0);
+        let cells = (area.width as usize).saturating_sub(label.len());
+        let theme = self.theme();
+        let bar = progress_bar(fraction, cells);
+        let chars = bar
+            .chars()
+            .map(|ch| (ch, theme.digit_color))
+            .chain(label.chars().map(|ch| (ch, theme.muted_color)));
+
+        let buf = frame.buffer_mut();
+        for (x, (ch, color)) in (area.x..area.right()).zip(chars) {
+            if ch == ' ' {
+                continue;
+            }
+            if let Some(cell) = buf.cell_mut(Position::new(x, area.y)) {
+                cell.set_char(ch);
+                cell.set_fg(color);
+            }
+        }
+    }
+
+    /// Render a single centered line of themed text, skipping spaces to preserve background.
+    /// Characters in the `accent` range use its fixed color instead of the theme.
+    /// Draw the panes of a split screen in `areas`, each framed with its label,
+    /// the focused one in the accent color.
+    fn render_panes(
+        &self,
+        frame: &mut Frame,
+        areas: &[Rect],
+        panes: &[PaneFrame],
+        style: ClockStyle,
+    ) {
+        let theme = self.theme();
+        let font = self.clock_font();
+        // Panes keep a border even with the clock's off, to tell them apart
+        let border_style = match self.clock_border {
+            ClockBorder::None => ClockBorder::Rounded,
+            border_style => border_style,
+        };
+        for (&area, pane) in areas.iter().zip(panes) {
+            let border_color = if pane.focused {
+                theme.accent_color
+            } else {
+                theme.muted_color
+            };
+            // A blank pane shows only the background, framed while in focus
+            let Some(time) = pane.time else {
+                if pane.focused {
+                    border::render(frame.buffer_mut(), area, border_style, "", border_color);
                 }
+                continue;
+            };
+            border::render(
+                frame.buffer_mut(),
+                area,
+                border_style,
+                &pane.label,
+                border_color,
+            );
+
+            // Stopwatch and timer readings are durations, never AM or PM
+            let time_format = if pane.duration {
+                TimeFormat::TwentyFourHour
+            } else {
+                self.time_format
+            };
+            let clock = ClockWidget::new(
+                ClockConfig {
+                    font,
+                    time_format,
+                    am_pm_style: self.am_pm_style,
+                    pad_hour: self.config.pad_hour,
+                    show_seconds: true,
+                    spacing: self.spacing,
+                    fill: self.digit_fill.as_ref(),
+                    style,
+                },
+                &time,
+            );
+            // The time and, a row below, the caption, centered in the pane;
+            // too small a pane gets the time as plain text
+            let inner = border::inner(area);
+            let scale = fit_scale(
+                clock.width(),
+                clock.height(),
+                inner.width as usize,
+                inner.height.saturating_sub(2) as usize,
+                MAX_AUTO_SCALE,
+            );
+            let clock = clock.scaled(scale);
+            let (width, height) = (
+                (clock.width() as u16).min(inner.width),
+                (clock.height() as u16).min(inner.height),
+            );
+            let block_height = (height + 2).min(inner.height);
+            let top = inner.y + (inner.height - block_height) / 2;
+            let time_rect = Rect::new(inner.x + (inner.width - width) / 2, top, width, height);
+            frame.render_widget(&clock, time_rect);
+            if self.accessibility == Accessibility::HighContrast {
+                accessibility::add_shadow(frame.buffer_mut(), time_rect);
+            }
+            if block_height == height + 2 {
+                let caption_rect = Rect {
+                    y: top + height + 1,
+                    height: 1,
+                    ..inner
+                };
+                self.render_text_line(frame, caption_rect, &pane.caption, style.elapsed_ms, None);
             }
         }
+    }
 
-        // Render date directly to buffer, skipping spaces to preserve background
-        let date_chunk = chunks[3];
-        let date_width = date_str.len() as u16;
-        let date_start_x = date_chunk.x + (date_chunk.width.saturating_sub(date_width)) / 2;
-        let date_y = date_chunk.y;
+    fn render_text_line(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        text: &str,
+        elapsed_ms: u64,
+        accent: Option<(Range<usize>, Color)>,
+    ) {
+        let text_len = text.chars().count();
+        let start_x = area.x + (area.width.saturating_sub(text_width(text) as u16)) / 2;
+        let color = self.theme().date_color;
+        let gradient_theme = self.gradient_theme(self.theme_overrides.date_color.is_some());
 
         let buf = frame.buffer_mut();
-        for (char_idx, ch) in date_str.chars().enumerate() {
+        let mut x_pos = start_x;
+        for (char_idx, ch) in text.chars().enumerate() {
+            let ch_width = ch.width().unwrap_or(0) as u16;
+            let cell_x = x_pos;
+            x_pos += ch_width;
+
             // Skip spaces to preserve background transparency
-            if ch == ' ' {
+            if ch == ' ' || ch_width == 0 {
+                continue;
+            }
+
+            if cell_x + ch_width > area.x + area.width {
+                continue;
+            }
+
+            if let Some((range, accent_color)) = &accent
+                && range.contains(&char_idx)
+            {
+                if let Some(cell) = buf.cell_mut(Position::new(cell_x, area.y)) {
+                    cell.set_char(ch);
+                    cell.set_fg(*accent_color);
+                }
                 continue;
             }
 
-            let x_pos = date_start_x + char_idx as u16;
-            if x_pos >= date_chunk.x + date_chunk.width {
-                continue;
-            }
+            // Get base color
+            let base_color = if gradient_theme.is_dynamic() {
+                gradient_theme.color_at_position(char_idx, 0, text_len, 1)
+            } else {
+                color
+            };
+
+            // Apply animation
+            let animated_color = apply_animation(
+                base_color,
+                self.animation_style,
+                self.animation_speed,
+                elapsed_ms,
+                char_idx,
+                text_len,
+                self.flash_intensity,
+            );
+
+            // Write directly to buffer
+            if let Some(cell) = buf.cell_mut(Position::new(cell_x, area.y)) {
+                cell.set_char(ch);
+                cell.set_fg(animated_color);
+            }
+            // Clear the cells covered by a wide character (emoji icons)
+            for offset in 1..ch_width {
+                if let Some(cell) = buf.cell_mut(Position::new(cell_x + offset, area.y)) {
+                    cell.reset();
+                }
+            }
+        }
+    }
+
+    /// Fire due alarms, running their hooks, and ring the terminal bell while
+    /// one is active.
+    fn update_alarms(&mut self) {
+        let now = self.now();
+        if self.alarm_state.check(now.naive_local())
+            && let Some(alarm) = self.alarm_state.ringing()
+        {
+            let command = alarm_command(&self.config, alarm);
+            self.hooks.fire(command, Completion::alarm(alarm));
+        }
+        self.save_alarm(&now);
+
+        if self.alarm_state.take_bell(Instant::now()) && self.config.alarm_bell {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+
+    /// Announce timers that reached zero, running their hook and ringing the
+    /// bell like an alarm.
+    fn update_panes(&mut self) {
+        let finished = self.panes.take_finished(Instant::now());
+        if finished.is_empty() {
+            return;
+        }
+        for (label, duration) in finished {
+            let completion = Completion::timer(label, duration);
+            self.show_toast(completion.message(), toast::DEFAULT_DURATION);
+            self.hooks.fire(&self.config.on_timer_complete, completion);
+        }
+        if self.config.alarm_bell {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+
+    /// Take up the alarm saved by the last run, ringing it at once and running
+    /// its hook if it went off while the clock was closed.
+    fn restore_alarm(&mut self) {
+        self.saved_alarm = alarm::load_saved(&alarm::state_file_path());
+        if let Some(saved) = &self.saved_alarm
+            && self.alarm_state.restore(saved, &self.now())
+            && let Some(alarm) = self.alarm_state.ringing()
+        {
+            let command = alarm_command(&self.config, alarm);
+            self.hooks.fire(command, Completion::alarm(alarm));
+        }
+    }
+
+    /// Write the next alarm due to the state file when it changed, removing
+    /// the file once no alarm is left.
+    fn save_alarm(&mut self, now: &DateTime<Local>) {
+        let next = self.alarm_state.next_due(now);
+        if next == self.saved_alarm {
+            return;
+        }
+        // The state file is best-effort, like the weather cache
+        let _ = alarm::save(&alarm::state_file_path(), next.as_ref());
+        self.saved_alarm = next;
+    }
+
+    /// Chime on the hour, running the chime command and ringing the bell.
+    fn update_chime(&mut self) {
+        let now = self.now();
+        let Some(chime) = &mut self.chime else {
+            return;
+        };
+        if let Some(strike) = chime.check(now.naive_local()) {
+            chime.run_command(strike);
+        }
+        if chime.take_bell(Instant::now()) {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+
+    /// Show the failures of timer and alarm hooks.
+    fn update_hooks(&mut self) {
+        for failure in self.hooks.take_failures() {
+            self.show_toast(format!("Hook failed: {failure}"), Duration::from_secs(5));
+        }
+    }
+
+    /// Show `message` in the corner for `duration`.
+    fn show_toast(&mut self, message: impl Into<String>, duration: Duration) {
+        self.toasts.push(message, duration, Instant::now());
+    }
+
+    /// Reload the config file when it was written since it was last read.
+    fn update_config(&mut self) {
+        if self.config_watcher.poll(Instant::now()) {
+            self.reload_config(false);
+        }
+    }
+
+    /// Read the banner file again when it was written since it was last read.
+    fn update_banner(&mut self) {
+        if let Some(watcher) = &mut self.banner_file
+            && watcher.poll(Instant::now())
+        {
+            self.banner = read_banner_file(watcher.path());
+        }
+    }
 
-            // Get base color
-            let base_color = if self.color_theme.is_dynamic() {
-                self.color_theme
-                    .color_at_position(char_idx, 0, date_str.len(), 1)
-            } else {
-                color
-            };
+    /// Read the config file again and apply the values that changed, keeping
+    /// the last good config when the file can't be used.
+    ///
+    /// A toast tells about the reload when something changed, or always when
+    /// `announce` is set, as for a reload asked for with a key.
+    fn reload_config(&mut self, announce: bool) {
+        let config = match Config::reload() {
+            Ok(config) => config,
+            Err(e) => {
+                let message = format!("Config reload failed: {e}");
+                self.status.report(
+                    Subsystem::Config,
+                    Status::Degraded(format!("{message}; keeping the last good config")),
+                );
+                self.show_toast(message, Duration::from_secs(5));
+                return;
+            }
+        };
+        let changes = ConfigChanges::between(&self.config, &config);
+        let changed = !changes.is_empty();
+        self.config = config;
+        self.apply_config_changes(changes);
+        self.status.report(
+            Subsystem::Config,
+            status::config_status(None, &self.config_warnings),
+        );
+        if announce || changed {
+            self.show_toast("Config reloaded", toast::DEFAULT_DURATION);
+        }
+        self.last_frame = None;
+    }
 
-            // Apply animation
-            let animated_color = apply_animation(
-                base_color,
-                self.animation_style,
-                self.animation_speed,
-                elapsed_ms,
-                char_idx,
-                date_str.len(),
-                self.flash_intensity,
+    /// Apply changed config values the way the settings dialog does.
+    ///
+    /// The monitors are only started or stopped, and the background only
+    /// changes, when values they depend on changed.
+    fn apply_config_changes(&mut self, changes: ConfigChanges) {
+        let affects_monitors = changes.affects_monitors();
+        if let Some(font_name) = changes.font_name {
+            self.current_font = font_name;
+        }
+        if let Some(color_theme) = changes.color_theme {
+            self.color_theme = color_theme;
+        }
+        if let Some(theme) = changes.theme {
+            self.theme_overrides = theme;
+        }
+        if let Some(time_format) = changes.time_format {
+            self.time_format = time_format;
+        }
+        if let Some(am_pm_style) = changes.am_pm_style {
+            self.am_pm_style = am_pm_style;
+        }
+        if let Some(date_format) = changes.date_format {
+            self.date_format = date_format;
+        }
+        if let Some(display_mode) = changes.display_mode {
+            self.display_mode = display_mode;
+        }
+        if let Some(subsecond_precision) = changes.subsecond_precision {
+            self.subsecond_precision = subsecond_precision;
+        }
+        if let Some(animation_style) = changes.animation_style {
+            self.animation_style = animation_style;
+        }
+        if let Some(animation_speed) = changes.animation_speed {
+            self.animation_speed = animation_speed;
+        }
+        if let Some(colon_blink) = changes.colon_blink {
+            self.colon_blink = colon_blink;
+        }
+        if let Some(background_style) = changes.background_style {
+            self.background_mode = BackgroundMode::from_style(background_style);
+        }
+        if let Some(clock_position) = changes.clock_position {
+            self.clock_position = clock_position;
+        }
+        if let Some(clock_border) = changes.clock_border {
+            self.clock_border = clock_border;
+        }
+        if let Some(accessibility) = changes.accessibility {
+            self.accessibility = accessibility;
+        }
+        if let Some(spacing) = changes.spacing {
+            self.spacing = spacing;
+        }
+        if let Some(show_weather) = changes.show_weather {
+            self.show_weather = show_weather;
+        }
+        if let Some(show_load) = changes.show_load {
+            self.show_load = show_load;
+        }
+        if let Some(progress_bar) = changes.progress_bar {
+            self.progress_bar = progress_bar;
+        }
+        if let Some(banner) = changes.banner
+            && !self.banner_from_cli
+        {
+            self.banner = (!banner.is_empty()).then_some(banner);
+        }
+        if let Some((layout, specs)) = changes.panes {
+            // Stopwatches and timers start over, and the new panes' warnings
+            // replace the old ones'
+            let (panes, pane_warnings) = Panes::from_config(layout, &specs);
+            self.panes = panes;
+            let old_warnings = std::mem::replace(&mut self.pane_warnings, pane_warnings);
+            replace_warnings(
+                &mut self.config_warnings,
+                &old_warnings,
+                &self.pane_warnings,
+            );
+        }
+        if let Some(help_line) = changes.help_line {
+            let (actions, warnings) = pinned_actions(&help_line);
+            self.help_line = actions;
+            let old_warnings = std::mem::replace(&mut self.help_line_warnings, warnings);
+            replace_warnings(
+                &mut self.config_warnings,
+                &old_warnings,
+                &self.help_line_warnings,
             );
+        }
+        if let Some(keys) = changes.keys {
+            // The new bindings' warnings replace the old ones'
+            let (keymap, key_warnings) = Keymap::from_config(&keys);
+            self.keymap = keymap;
+            let old_warnings = std::mem::replace(&mut self.key_warnings, key_warnings);
+            replace_warnings(&mut self.config_warnings, &old_warnings, &self.key_warnings);
+        }
+        if affects_monitors {
+            self.update_background_monitors();
+        }
+        // Between scheduled presets, the clock returns to the reloaded look
+        if self.scheduled_preset.is_none() {
+            self.unscheduled_look = self.current_look();
+        }
+    }
 
-            // Write directly to buffer
-            if let Some(cell) = buf.cell_mut(Position::new(x_pos, date_y)) {
-                cell.set_char(ch);
-                cell.set_fg(animated_color);
-            }
+    /// Apply weather updates from the monitor, surfacing fetch errors as toasts.
+    fn update_weather(&mut self) {
+        let errors = match &self.weather_monitor {
+            Some(monitor) => self.weather.drain(monitor.events()),
+            None => Vec::new(),
+        };
+        for error in errors {
+            self.show_toast(
+                format!("Weather fetch failed: {error}"),
+                Duration::from_secs(5),
+            );
         }
+        self.weather.refresh_time_of_day(Instant::now());
+    }
 
-        // Render help text
-        let help = Line::from(vec![
-            "q".bold().fg(color),
-            " quit  ".dark_gray(),
-            "t".bold().fg(color),
-            " 12/24h  ".dark_gray(),
-            "c".bold().fg(color),
-            " color  ".dark_gray(),
-            "a".bold().fg(color),
-            " anim  ".dark_gray(),
-            "b".bold().fg(color),
-            " bg  ".dark_gray(),
-            "s".bold().fg(color),
-            " settings".dark_gray(),
-        ])
-        .centered();
-        frame.render_widget(help, chunks[5]);
+    /// Collect the latest health of the weather and metrics monitors.
+    fn update_status(&mut self) {
+        let (weather, location) = match self.weather_monitor {
+            Some(_) => (self.weather.health(), self.weather.location_health()),
+            None => (Status::Ok, Status::Ok),
+        };
+        self.status.report(Subsystem::Weather, weather);
+        self.status.report(Subsystem::Geolocation, location);
+        let metrics = self
+            .system_monitor
+            .as_ref()
+            .filter(|monitor| monitor.is_enabled())
+            .map_or(Status::Ok, SystemMonitor::status);
+        self.status.report(Subsystem::Metrics, metrics);
+        let chime = self.chime.as_ref().map_or(Status::Ok, Chime::status);
+        self.status.report(Subsystem::Chime, chime);
+        self.status.report(Subsystem::Hooks, self.hooks.status());
+        let calendar = self
+            .calendar
+            .as_ref()
+            .map_or(Status::Ok, CalendarMonitor::status);
+        self.status.report(Subsystem::Calendar, calendar);
+    }
 
-        // Render settings dialog if visible
-        self.settings_dialog.render(frame, area, color);
+    /// Drop expired toasts.
+    fn update_toasts(&mut self) {
+        self.toasts.prune(Instant::now());
     }
 
     /// Update flash intensity for reactive animation.
     fn update_flash(&mut self, now: &chrono::DateTime<chrono::Local>) {
-        let second: u32 = now.format("%S").to_string().parse().unwrap_or(0);
-        let minute: u32 = now.format("%M").to_string().parse().unwrap_or(0);
-        let hour: u32 = now.format("%H").to_string().parse().unwrap_or(0);
+        let second = now.second();
+        let minute = now.minute();
+        let hour = now.hour();
 
         // Check for time changes and trigger flash
         if hour != self.last_hour {
@@ -421,14 +2236,23 @@ impl App {
     }
 
     /// Reads the crossterm events and updates the state of [`App`].
-    /// Uses polling with timeout for real-time clock updates.
-    fn handle_crossterm_events(&mut self) -> color_eyre::Result<()> {
-        // Poll for events with 100ms timeout for smooth clock updates
-        if event::poll(Duration::from_millis(100))? {
+    /// Waits at most `timeout` (until the next frame is due) so the clock keeps ticking;
+    /// a pending event returns immediately, so keys are handled promptly.
+    fn handle_crossterm_events(&mut self, timeout: Duration) -> color_eyre::Result<()> {
+        if event::poll(timeout)? {
             match event::read()? {
-                Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-                Event::Mouse(_) => {}
-                Event::Resize(_, _) => {}
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // A key press while the schedule hides the clock only reveals it
+                    let clock_visible = self.clock_visible();
+                    self.help.on_input(Instant::now());
+                    if clock_visible {
+                        self.on_key_event(key);
+                    }
+                    // Keys may change state the frame key doesn't track (e.g. the settings dialog)
+                    self.last_frame = None;
+                }
+                Event::Mouse(mouse) if self.config.mouse => self.on_mouse_event(mouse),
+                Event::Resize(width, height) => self.on_resize(width, height),
                 _ => {}
             }
         }
@@ -437,23 +2261,119 @@ impl App {
 
     /// Handles the key events and updates the state of [`App`].
     fn on_key_event(&mut self, key: KeyEvent) {
+        // Any key dismisses a ringing alarm
+        if self.alarm_state.ringing().is_some() {
+            self.alarm_state.dismiss();
+            return;
+        }
+
+        // Arrow and page keys scroll the key overlay, any other key closes it
+        if self.help.overlay_visible {
+            match help::scroll_step(key.code) {
+                Some(rows) => self.help.scroll_overlay(rows),
+                None => self.help.toggle_overlay(),
+            }
+            return;
+        }
+
         // If settings dialog is visible, handle dialog keys
         if self.settings_dialog.visible {
             self.handle_settings_key(key);
             return;
         }
 
-        // Main app keybindings
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Esc | KeyCode::Char('q'))
-            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
-            (_, KeyCode::Char('t')) => self.toggle_time_format(),
-            (_, KeyCode::Char('c')) => self.cycle_color_theme(),
-            (_, KeyCode::Char('a')) => self.cycle_animation(),
-            (_, KeyCode::Char('b')) => self.cycle_background(),
-            (_, KeyCode::Char('s')) => self.open_settings(),
-            _ => {}
+        // Main app keybindings; Ctrl+C quits unless it was bound to something else
+        match self.keymap.action(&key) {
+            Some(action) => self.run_action(action),
+            None if key.modifiers == KeyModifiers::CONTROL
+                && matches!(key.code, KeyCode::Char('c' | 'C')) =>
+            {
+                self.quit()
+            }
+            None => {}
+        }
+    }
+
+    /// Do what a key or a click on the help line asks for.
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.quit(),
+            Action::ToggleFormat => self.toggle_time_format(),
+            Action::CycleAmPm => self.cycle_am_pm_style(),
+            Action::CycleDisplayMode => self.cycle_display_mode(),
+            Action::CyclePrecision => self.cycle_subsecond_precision(),
+            Action::CycleTheme => self.cycle_color_theme(),
+            Action::CycleAnimation => self.cycle_animation(),
+            Action::CycleBackground => self.cycle_background(),
+            Action::PickBackground => self.pick_background(),
+            Action::CycleInfoLine => self.cycle_info_line(),
+            Action::CycleProgressBar => self.cycle_progress_bar(),
+            Action::ToggleWeather => self.toggle_weather_line(),
+            Action::RefreshWeather => self.refresh_weather(),
+            Action::ToggleLoad => self.toggle_load_line(),
+            Action::ToggleDrift => self.toggle_bounce(),
+            Action::CyclePosition => self.cycle_clock_position(),
+            Action::CycleBorder => self.cycle_clock_border(),
+            Action::NextPreset => self.cycle_preset(),
+            Action::WidenSpacing => self.set_spacing(self.spacing.wider()),
+            Action::NarrowSpacing => self.set_spacing(self.spacing.narrower()),
+            Action::Settings => self.open_settings(),
+            Action::ReloadConfig => self.reload_config(true),
+            Action::Export => self.export_frame(),
+            Action::TogglePause => self.toggle_pause(),
+            Action::FocusPane => self.focus_pane(),
+            Action::StartStopPane => self.start_stop_pane(),
+            Action::ResetPane => self.reset_pane(),
+            Action::ToggleDebug => self.debug_visible = !self.debug_visible,
+            Action::Help => self.help.toggle_overlay(),
+        }
+    }
+
+    /// Handles clicks and scrolling on the last drawn frame.
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        // Dialogs, the key overlay and a ringing alarm only take keys
+        if self.alarm_state.ringing().is_some()
+            || self.help.overlay_visible
+            || self.settings_dialog.visible
+        {
+            return;
+        }
+
+        // While the schedule hides the clock, a click only reveals it
+        if !self.clock_visible() {
+            if let MouseEventKind::Down(_) = mouse.kind {
+                self.help.on_input(Instant::now());
+                self.last_frame = None;
+            }
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.help.on_input(Instant::now());
+                match self
+                    .hit_areas
+                    .target_at(Position::new(mouse.column, mouse.row))
+                {
+                    Some(MouseTarget::Time) => self.toggle_time_format(),
+                    Some(MouseTarget::Date) => self.cycle_date_format(),
+                    Some(MouseTarget::Hotkey(action)) => self.run_action(action),
+                    None => {}
+                }
+            }
+            MouseEventKind::ScrollUp => self.set_color_theme(self.color_theme.prev()),
+            MouseEventKind::ScrollDown => self.set_color_theme(self.color_theme.next()),
+            _ => return,
         }
+        self.last_frame = None;
+    }
+
+    /// Fit everything sized to the terminal to its new size and redraw right away.
+    fn on_resize(&mut self, width: u16, height: u16) {
+        self.background_state.resize(width, height);
+        // The recorded regions are from the old layout until the next frame
+        self.hit_areas = HitAreas::default();
+        self.last_frame = None;
     }
 
     /// Handle key events when settings dialog is open.
@@ -491,7 +2411,8 @@ impl App {
         self.animation_style = self.settings_dialog.animation_style;
         self.animation_speed = self.settings_dialog.animation_speed;
         self.colon_blink = self.settings_dialog.colon_blink;
-        self.background_style = self.settings_dialog.background_style;
+        self.accessibility = self.settings_dialog.accessibility;
+        self.background_mode = BackgroundMode::from_style(self.settings_dialog.background_style);
         self.update_background_monitors();
     }
 
@@ -499,12 +2420,15 @@ impl App {
     fn open_settings(&mut self) {
         self.settings_dialog.open(
             &self.current_font,
-            self.color_theme,
-            self.time_format,
-            self.animation_style,
-            self.animation_speed,
-            self.colon_blink,
-            self.background_style,
+            DisplaySettings {
+                color_theme: self.color_theme,
+                time_format: self.time_format,
+                animation_style: self.animation_style,
+                animation_speed: self.animation_speed,
+                colon_blink: self.colon_blink,
+                background_style: self.background_mode.style(),
+                accessibility: self.accessibility,
+            },
         );
     }
 
@@ -517,7 +2441,8 @@ impl App {
         self.config.animation_style = self.animation_style;
         self.config.animation_speed = self.animation_speed;
         self.config.colon_blink = self.colon_blink;
-        self.config.background_style = self.background_style;
+        self.config.background_style = self.background_mode.style();
+        self.config.accessibility = self.accessibility;
 
         if let Err(e) = self.config.save() {
             eprintln!("Warning: Failed to save config: {e}");
@@ -535,7 +2460,9 @@ impl App {
         self.animation_style = self.settings_dialog.original_animation_style();
         self.animation_speed = self.settings_dialog.original_animation_speed();
         self.colon_blink = self.settings_dialog.original_colon_blink();
-        self.background_style = self.settings_dialog.original_background_style();
+        self.accessibility = self.settings_dialog.original_accessibility();
+        self.background_mode =
+            BackgroundMode::from_style(self.settings_dialog.original_background_style());
         self.update_background_monitors();
 
         self.settings_dialog.close();
@@ -544,42 +2471,458 @@ impl App {
     /// Toggle between 12-hour and 24-hour time format.
     fn toggle_time_format(&mut self) {
         self.time_format = self.time_format.toggle();
+        let label = match self.time_format {
+            TimeFormat::TwentyFourHour => "24-hour time",
+            TimeFormat::TwelveHour => "12-hour time",
+        };
+        self.show_toast(label, toast::DEFAULT_DURATION);
+    }
+
+    /// Cycle through AM/PM styles, saving the choice.
+    fn cycle_am_pm_style(&mut self) {
+        self.am_pm_style = self.am_pm_style.next();
+        self.show_toast(
+            format!("AM/PM: {}", self.am_pm_style.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+
+        if Config::config_file_path().exists() {
+            self.config.am_pm_style = self.am_pm_style;
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
+    }
+
+    /// Cycle through date formats.
+    fn cycle_date_format(&mut self) {
+        self.date_format = self.date_format.next();
+        self.show_toast(
+            format!("Date: {}", self.date_format.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+    }
+
+    /// Cycle between the time as digits and in words.
+    fn cycle_display_mode(&mut self) {
+        self.display_mode = self.display_mode.next();
+        self.show_toast(
+            format!("Display: {}", self.display_mode.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+    }
+
+    /// Cycle the sub-second digits: off, tenths, hundredths.
+    fn cycle_subsecond_precision(&mut self) {
+        self.subsecond_precision = self.subsecond_precision.next();
+    }
+
+    /// Sub-second precision actually shown; the time in words has no digits to refine.
+    fn effective_subsecond_precision(&self) -> SubsecondPrecision {
+        match self.display_mode {
+            DisplayMode::Digits => self.subsecond_precision,
+            DisplayMode::Words => SubsecondPrecision::Off,
+        }
     }
 
     /// Cycle through available color themes.
     fn cycle_color_theme(&mut self) {
-        self.color_theme = self.color_theme.next();
+        self.set_color_theme(self.color_theme.next());
+    }
+
+    /// Switch color theme, starting the weather monitor if the Auto theme needs it.
+    fn set_color_theme(&mut self, theme: ColorTheme) {
+        self.color_theme = theme;
+        self.update_background_monitors();
+        self.show_toast(
+            format!("Theme: {}", self.color_theme.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+    }
+
+    /// Apply the next preset, starting with the first.
+    fn cycle_preset(&mut self) {
+        if self.presets.is_empty() {
+            return;
+        }
+        let index = self
+            .preset_index
+            .map_or(0, |index| (index + 1) % self.presets.len());
+        self.apply_preset(index);
+        self.show_toast(
+            format!("Preset: {}", self.presets[index].0),
+            toast::DEFAULT_DURATION,
+        );
+    }
+
+    /// Switch colors, background, font, speed and time format to a preset at once.
+    ///
+    /// The background animation starts over, as after switching styles by hand.
+    fn apply_preset(&mut self, index: usize) {
+        self.preset_index = Some(index);
+        self.background_state = new_background_state(self.seed);
+        self.show_look(self.presets[index].1.clone());
+    }
+
+    /// Apply the preset scheduled for now when it changes, or return to the
+    /// look the clock started with between the scheduled ranges.
+    ///
+    /// The background keeps running, so the old one crossfades into the new.
+    fn update_preset_schedule(&mut self) {
+        let active = self.preset_schedule.active_at(&self.display_now());
+        if active == self.scheduled_preset.as_deref() {
+            return;
+        }
+        let active = active.map(String::from);
+        let index = active
+            .as_deref()
+            .and_then(|name| find_preset(&self.presets, name));
+        let look = match index {
+            Some(index) => self.presets[index].1.clone(),
+            None => self.unscheduled_look.clone(),
+        };
+        self.preset_index = index.or(self.preset_index);
+        self.show_look(look);
+        if let Some(name) = &active {
+            self.show_toast(format!("Preset: {name}"), toast::DEFAULT_DURATION);
+        }
+        self.scheduled_preset = active;
+    }
+
+    /// Colors, background, font, speed and time format shown now, as a preset.
+    fn current_look(&self) -> Preset {
+        Preset {
+            color_theme: self.color_theme,
+            theme: self.theme_overrides,
+            background_style: self.background_mode.style(),
+            font_name: self.current_font.clone(),
+            animation_speed: self.animation_speed,
+            time_format: self.time_format,
+            am_pm_style: self.am_pm_style,
+        }
+    }
+
+    /// Switch colors, background, font, speed and time format at once.
+    fn show_look(&mut self, preset: Preset) {
+        self.color_theme = preset.color_theme;
+        self.theme_overrides = preset.theme;
+        self.background_mode = BackgroundMode::from_style(preset.background_style);
+        self.current_font = preset.font_name;
+        self.animation_speed = preset.animation_speed;
+        self.time_format = preset.time_format;
+        self.am_pm_style = preset.am_pm_style;
+        self.update_background_monitors();
+        self.last_frame = None;
+    }
+
+    /// Resolve the static text color, following the time of day for the Auto theme.
+    fn theme_color(&self) -> Color {
+        self.resolve_color(self.color_theme)
+    }
+
+    /// Colors of the digits, date and help line, with the configured overrides.
+    fn theme(&self) -> Theme {
+        Theme::new(self.color_theme, self.theme_color()).with_overrides(&self.theme_overrides)
+    }
+
+    /// Color theme whose gradient colors an element, dropped when the element's color is
+    /// overridden. A static theme draws with the element's color from [`App::theme`] instead.
+    fn gradient_theme(&self, overridden: bool) -> ColorTheme {
+        if overridden {
+            ColorTheme::White
+        } else {
+            self.color_theme
+        }
+    }
+
+    /// Resolve a theme to a static color, following the time of day for the Auto theme.
+    fn resolve_color(&self, theme: ColorTheme) -> Color {
+        match theme {
+            ColorTheme::Auto => {
+                auto_color(self.weather.time_of_day(), self.config.night_brightness)
+            }
+            theme => theme.color(),
+        }
+    }
+
+    /// Title on the clock border: the configured label, else the weather location.
+    fn border_title(&self) -> String {
+        if !self.config.border_title.is_empty() {
+            return self.config.border_title.clone();
+        }
+        self.weather
+            .city()
+            .or(self.weather_location.place())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Cycle the border around the clock, persisting it when a config file is in use.
+    fn cycle_clock_border(&mut self) {
+        self.clock_border = self.clock_border.next();
+        self.show_toast(
+            format!("Border: {}", self.clock_border.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+
+        if Config::config_file_path().exists() {
+            self.config.clock_border = self.clock_border;
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
+    }
+
+    /// Widen or narrow the gaps between the clock digits, persisting them when a config file is in use.
+    fn set_spacing(&mut self, spacing: Spacing) {
+        self.spacing = spacing;
+        self.show_toast(
+            format!(
+                "Spacing: {} between digits, {} around colons",
+                spacing.digit_gap, spacing.colon_gap
+            ),
+            toast::DEFAULT_DURATION,
+        );
+
+        if Config::config_file_path().exists() {
+            self.config.digit_gap = spacing.digit_gap;
+            self.config.colon_gap = spacing.colon_gap;
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
     }
 
     /// Cycle through animation styles.
     fn cycle_animation(&mut self) {
         self.animation_style = self.animation_style.next();
+        self.show_toast(
+            format!("Animation: {}", self.animation_style.display_name()),
+            toast::DEFAULT_DURATION,
+        );
     }
 
-    /// Cycle through background styles.
+    /// Cycle through background modes: off, following the weather, then each style.
     fn cycle_background(&mut self) {
-        self.background_style = self.background_style.next();
+        self.set_background_mode(self.background_mode.next());
+    }
+
+    /// Pick the next background style manually, returning to Auto after the last one.
+    fn pick_background(&mut self) {
+        self.set_background_mode(self.background_mode.next_manual());
+    }
+
+    /// Switch background mode, persisting it when a config file is in use.
+    fn set_background_mode(&mut self, mode: BackgroundMode) {
+        self.background_mode = mode;
+        self.update_background_monitors();
+        self.show_toast(
+            format!("Background: {}", mode.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+
+        if Config::config_file_path().exists() {
+            self.config.background_style = mode.style();
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
+    }
+
+    /// Cycle through info line modes.
+    fn cycle_info_line(&mut self) {
+        self.info_line = self.info_line.next();
+    }
+
+    /// Cycle the progress bar period, persisting it when a config file is in use.
+    fn cycle_progress_bar(&mut self) {
+        self.progress_bar = self.progress_bar.next();
+        self.show_toast(
+            format!("Progress bar: {}", self.progress_bar.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+
+        if Config::config_file_path().exists() {
+            self.config.progress_bar = self.progress_bar;
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
+    }
+
+    /// Toggle the current weather line.
+    fn toggle_weather_line(&mut self) {
+        self.show_weather = !self.show_weather;
+        self.update_background_monitors();
+    }
+
+    /// Toggle the CPU and memory line, persisting it when a config file is in use.
+    fn toggle_load_line(&mut self) {
+        self.show_load = !self.show_load;
         self.update_background_monitors();
+
+        if Config::config_file_path().exists() {
+            self.config.show_load = self.show_load;
+            if let Err(e) = self.config.save() {
+                eprintln!("Warning: Failed to save config: {e}");
+            }
+        }
+    }
+
+    /// Fetch the weather right away, if it is in use.
+    fn refresh_weather(&mut self) {
+        let Some(monitor) = &self.weather_monitor else {
+            self.show_toast("Weather is not in use", toast::DEFAULT_DURATION);
+            return;
+        };
+        monitor.refresh();
+        self.show_toast("Refreshing weather…", toast::DEFAULT_DURATION);
+    }
+
+    /// Move the clock to the next position on screen.
+    fn cycle_clock_position(&mut self) {
+        self.clock_position = self.clock_position.next();
+        self.show_toast(
+            format!("Position: {}", self.clock_position.display_name()),
+            toast::DEFAULT_DURATION,
+        );
+    }
+
+    /// Toggle between the centered clock and the drifting screensaver mode.
+    fn toggle_bounce(&mut self) {
+        self.bounce = match self.bounce {
+            Some(_) => None,
+            None => Some(Bounce::new(self.elapsed_ms())),
+        };
+        let label = if self.bounce.is_some() {
+            "Drift on"
+        } else {
+            "Drift off"
+        };
+        self.show_toast(label, toast::DEFAULT_DURATION);
+    }
+
+    /// Save the current frame as ANSI-colored text in the working directory.
+    fn export_frame(&mut self) {
+        let Some(size) = self.last_frame.as_ref().map(|frame| frame.size) else {
+            return;
+        };
+        let path = PathBuf::from(Local::now().format("sigye-%Y%m%d-%H%M%S.ans").to_string());
+        let message = match self.export(&path, size) {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(e) => format!("Export failed: {e}"),
+        };
+        self.show_toast(message, toast::DEFAULT_DURATION);
+    }
+
+    /// Freeze the time and animations, e.g. for a screenshot, or resume live time.
+    fn toggle_pause(&mut self) {
+        if self.paused.take().is_some() {
+            // The clock jumps straight to the live time, while stateful
+            // backgrounds and a drifting clock continue from where they stopped
+            self.background_state.skip_gap();
+            if let Some(bounce) = self.bounce.as_mut() {
+                bounce.skip_gap();
+            }
+        } else {
+            self.paused = Some((self.now(), self.elapsed_ms()));
+        }
+    }
+
+    /// Entries of the help line: the first key of each pinned action, the
+    /// action and its label. Unbound actions are left out, and picking a
+    /// background shares the entry of cycling it when both are pinned.
+    fn help_line_entries(&self) -> Vec<(String, Action, String)> {
+        let first_key = |action| self.keymap.keys(action).first().map(ToString::to_string);
+        let shares_background = self.help_line.contains(&Action::CycleBackground);
+        let mut entries: Vec<(String, Action, String)> = self
+            .help_line
+            .iter()
+            .filter(|&&action| !(action == Action::PickBackground && shares_background))
+            .filter_map(|&action| {
+                let mut keys: Vec<String> = first_key(action).into_iter().collect();
+                if action == Action::CycleBackground
+                    && self.help_line.contains(&Action::PickBackground)
+                {
+                    keys.extend(first_key(Action::PickBackground));
+                }
+                let label = match action {
+                    Action::CycleDisplayMode => self.display_mode.display_name().to_lowercase(),
+                    Action::CycleBackground | Action::PickBackground => {
+                        format!("bg: {}", self.background_mode.display_name())
+                    }
+                    action => action.help_label().to_string(),
+                };
+                (!keys.is_empty()).then(|| (keys.join("/"), action, format!(" {label}  ")))
+            })
+            .collect();
+        // No gap after the last entry, so the line stays centered
+        if let Some((_, _, label)) = entries.last_mut() {
+            label.truncate(label.trim_end().len());
+        }
+        entries
+    }
+
+    /// Move the focus to the next pane of a split screen.
+    fn focus_pane(&mut self) {
+        if self.panes.is_split() {
+            self.panes.focus_next();
+        }
+    }
+
+    /// Start or stop the focused pane's stopwatch or timer.
+    fn start_stop_pane(&mut self) {
+        if !self.panes.is_split() || !self.panes.start_stop(Instant::now()) {
+            self.show_toast("No stopwatch or timer in focus", toast::DEFAULT_DURATION);
+        }
+    }
+
+    /// Set the focused pane's stopwatch or timer back to its start.
+    fn reset_pane(&mut self) {
+        if !self.panes.is_split() || !self.panes.reset() {
+            self.show_toast("No stopwatch or timer in focus", toast::DEFAULT_DURATION);
+        }
+    }
+
+    /// What each pane of a split screen shows, empty for the single clock.
+    fn pane_frames(&self) -> Vec<PaneFrame> {
+        if !self.panes.is_split() {
+            return Vec::new();
+        }
+        self.panes.frames(&self.display_now(), Instant::now())
     }
 
     /// Start or stop background monitors based on current background style.
     fn update_background_monitors(&mut self) {
-        // System monitor for reactive backgrounds
-        if self.background_style.is_reactive() && self.system_monitor.is_none() {
-            let monitor = SystemMonitor::new();
-            monitor.start();
-            self.system_monitor = Some(monitor);
-        } else if !self.background_style.is_reactive() && self.system_monitor.is_some() {
-            self.system_monitor = None;
+        // System monitor for reactive backgrounds or the load line, paused
+        // rather than dropped without them so it keeps its normalization
+        let needs_metrics = (self.background_mode.style().is_reactive()
+            && !self.accessibility.hides_background())
+            || self.show_load;
+        match &self.system_monitor {
+            Some(monitor) => monitor.set_enabled(needs_metrics),
+            None if needs_metrics => {
+                self.system_monitor = Some(start_system_monitor(&self.config));
+            }
+            None => {}
         }
 
-        // Weather monitor for weather background
-        if self.background_style.requires_weather() && self.weather_monitor.is_none() {
-            let monitor = WeatherMonitor::new(self.config.weather_location.clone());
+        // Weather monitor for weather background, weather line, Auto theme or dimming with the sun
+        let needs_weather = needs_weather(
+            self.background_mode,
+            self.show_weather,
+            self.color_theme,
+            self.config.auto_dim,
+        );
+        if needs_weather && self.weather_monitor.is_none() {
+            let monitor = WeatherMonitor::new(self.weather_location.clone(), self.weather_provider)
+                .with_kp_index(self.config.aurora_kp)
+                .with_metrics(Arc::clone(&self.app_metrics));
             monitor.start();
             self.weather_monitor = Some(monitor);
-        } else if !self.background_style.requires_weather() && self.weather_monitor.is_some() {
+        } else if !needs_weather && self.weather_monitor.is_some() {
             self.weather_monitor = None;
+            self.weather = WeatherState::default();
         }
     }
 
@@ -589,8 +2932,102 @@ impl App {
     }
 }
 
+/// Render the CPU sparkline and memory gauge across `area`, skipping blank
+/// cells to preserve the background.
+fn render_load_line(frame: &mut Frame, area: Rect, history: &MetricsHistory, memory: f32) {
+    let cells = load::load_line(history, memory, area.width as usize);
+    let buf = frame.buffer_mut();
+    for (x, cell) in (area.x..area.right()).zip(cells) {
+        let Some((ch, color)) = cell else {
+            continue;
+        };
+        if let Some(cell) = buf.cell_mut(Position::new(x, area.y)) {
+            cell.set_char(ch);
+            cell.set_fg(color);
+        }
+    }
+}
+
+/// Start sampling system metrics as configured.
+fn start_system_monitor(config: &Config) -> SystemMonitor {
+    let monitor = SystemMonitor::new(
+        SourceSpec::from_config(config),
+        sample_interval(config.metrics_interval_secs),
+    );
+    monitor.start();
+    monitor
+}
+
+/// Banner message from a `--banner-file`, `None` when it is empty or can't be read.
+fn read_banner_file(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| banner::read_message(&contents))
+}
+
+/// Gaps between the clock glyphs set in the config.
+fn config_spacing(config: &Config) -> Spacing {
+    Spacing {
+        digit_gap: config.digit_gap,
+        colon_gap: config.colon_gap,
+        am_pm_gap: config.am_pm_gap,
+    }
+}
+
+/// Fill of the clock digits set in the config, `None` for the font's own characters.
+fn config_fill(config: &Config) -> Result<Option<Fill>, String> {
+    if config.digit_fill.is_empty() {
+        return Ok(None);
+    }
+    Fill::parse(&config.digit_fill)
+        .and_then(|fill| fill.with_blank(&config.digit_blank))
+        .map(Some)
+}
+
+/// Command from the `[alarm_commands]` table for `alarm`, by its time (empty = none).
+fn alarm_command<'a>(config: &'a Config, alarm: &Alarm) -> &'a str {
+    config
+        .alarm_commands
+        .iter()
+        .find(|(time, _)| {
+            Alarm::parse(time).is_ok_and(|a| (a.hour, a.minute) == (alarm.hour, alarm.minute))
+        })
+        .map_or("", |(_, command)| command.as_str())
+}
+
+/// Whether the weather monitor is needed: for the weather background, the
+/// weather line, the Auto theme or dimming with the sun. Used at startup and
+/// on every change, so both start it for the same settings.
+fn needs_weather(
+    background_mode: BackgroundMode,
+    show_weather: bool,
+    color_theme: ColorTheme,
+    auto_dim: AutoDim,
+) -> bool {
+    background_mode == BackgroundMode::Auto
+        || show_weather
+        || color_theme == ColorTheme::Auto
+        || auto_dim == AutoDim::Sun
+}
+
+/// Keep a problem with the config for stderr and the status marker.
+fn config_warning(warnings: &mut Vec<String>, warning: String) {
+    warnings.push(warning);
+}
+
+/// Swap the `old` warnings of a reloaded value among `warnings` for its `new` ones.
+fn replace_warnings(warnings: &mut Vec<String>, old: &[String], new: &[String]) {
+    warnings.retain(|warning| !old.contains(warning));
+    warnings.extend(new.iter().cloned());
+}
+
+/// Display width of a line of text in terminal cells.
+fn text_width(text: &str) -> usize {
+    text.chars().map(|ch| ch.width().unwrap_or(0)).sum()
+}
+
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(Cli::default())
     }
 }
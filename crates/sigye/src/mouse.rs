@@ -0,0 +1,141 @@
+//! Mouse hit-testing against the layout of the last drawn frame.
+
+use ratatui::layout::{Position, Rect};
+
+use crate::keys::Action;
+
+/// Something on screen that reacts to a click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseTarget {
+    /// The big time digits or words.
+    Time,
+    /// The date line.
+    Date,
+    /// A key label on the help line, doing what its key does.
+    Hotkey(Action),
+}
+
+/// Clickable regions recorded while rendering.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HitAreas {
+    /// Area covered by the time.
+    pub time: Rect,
+    /// Area covered by the date text.
+    pub date: Rect,
+    /// Help line entries and the action of each.
+    pub hotkeys: Vec<(Rect, Action)>,
+}
+
+impl HitAreas {
+    /// Find what is under `position`, if anything.
+    pub fn target_at(&self, position: Position) -> Option<MouseTarget> {
+        if let Some((_, action)) = self
+            .hotkeys
+            .iter()
+            .find(|(rect, _)| rect.contains(position))
+        {
+            return Some(MouseTarget::Hotkey(*action));
+        }
+        if self.time.contains(position) {
+            return Some(MouseTarget::Time);
+        }
+        if self.date.contains(position) {
+            return Some(MouseTarget::Date);
+        }
+        None
+    }
+}
+
+/// Lay out help line entries of the given widths as a centered line in `area`.
+///
+/// Matches how a centered `Line` is drawn, including trimming both ends when it
+/// is wider than `area`. Entries cut off entirely are left out, and partly
+/// visible ones are clipped to `area`.
+pub fn hotkey_rects(area: Rect, entries: &[(usize, Action)]) -> Vec<(Rect, Action)> {
+    let line_width: usize = entries.iter().map(|(width, _)| width).sum();
+    let area_width = area.width as usize;
+    let mut x = area.x as isize + (area_width as isize - line_width as isize) / 2;
+    if line_width > area_width {
+        // A centered line that doesn't fit drops the same amount from each side
+        x = area.x as isize - ((line_width - area_width) / 2) as isize;
+    }
+
+    let mut rects = Vec::new();
+    for &(width, action) in entries {
+        let start = x.max(area.x as isize);
+        let end = (x + width as isize).min(area.right() as isize);
+        if end > start {
+            rects.push((
+                Rect::new(start as u16, area.y, (end - start) as u16, area.height),
+                action,
+            ));
+        }
+        x += width as isize;
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hotkey_rects_centered() {
+        let area = Rect::new(0, 23, 20, 1);
+        let rects = hotkey_rects(area, &[(6, Action::Quit), (4, Action::ToggleFormat)]);
+        assert_eq!(
+            rects,
+            [
+                (Rect::new(5, 23, 6, 1), Action::Quit),
+                (Rect::new(11, 23, 4, 1), Action::ToggleFormat)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hotkey_rects_trimmed_when_too_wide() {
+        let area = Rect::new(0, 0, 10, 1);
+        // 20 columns wide: 5 are dropped on each side
+        let rects = hotkey_rects(
+            area,
+            &[
+                (4, Action::CycleAnimation),
+                (4, Action::CycleBackground),
+                (4, Action::CycleTheme),
+                (4, Action::ToggleDrift),
+                (4, Action::Export),
+            ],
+        );
+        assert_eq!(
+            rects,
+            [
+                (Rect::new(0, 0, 3, 1), Action::CycleBackground),
+                (Rect::new(3, 0, 4, 1), Action::CycleTheme),
+                (Rect::new(7, 0, 3, 1), Action::ToggleDrift),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_at() {
+        let areas = HitAreas {
+            time: Rect::new(10, 5, 40, 6),
+            date: Rect::new(20, 13, 20, 1),
+            hotkeys: vec![(Rect::new(0, 23, 6, 1), Action::Quit)],
+        };
+        assert_eq!(
+            areas.target_at(Position::new(10, 5)),
+            Some(MouseTarget::Time)
+        );
+        assert_eq!(
+            areas.target_at(Position::new(39, 13)),
+            Some(MouseTarget::Date)
+        );
+        assert_eq!(
+            areas.target_at(Position::new(3, 23)),
+            Some(MouseTarget::Hotkey(Action::Quit))
+        );
+        assert_eq!(areas.target_at(Position::new(0, 0)), None);
+        assert_eq!(areas.target_at(Position::new(40, 13)), None);
+    }
+}
@@ -0,0 +1,292 @@
+//! One-shot mode: print the clock to stdout and exit, for scripts and banners.
+
+use std::io::{self, Write};
+
+use chrono::{DateTime, Local, TimeZone};
+use crossterm::style::{ResetColor, SetForegroundColor};
+use ratatui::backend::IntoCrossterm;
+use ratatui::style::Color;
+use sigye_background::ascii_fallback;
+use sigye_config::Config;
+use sigye_core::{AmPmStyle, ColorTheme, TimeParts, auto_color};
+use sigye_fonts::{Font, FontRegistry, build_time_art};
+
+use crate::cli::Cli;
+use crate::compat;
+use crate::text_width;
+use crate::weather::nominal_time_of_day;
+use crate::{config_fill, config_spacing};
+
+/// Print the clock for the command line and configuration, without touching the terminal mode.
+pub fn run(cli: &Cli) -> color_eyre::Result<()> {
+    let config = Config::load();
+    let mut font_registry = FontRegistry::new();
+    font_registry.load_custom_fonts(&Config::fonts_dir());
+    let font = font_registry.get_or_default(&config.font_name);
+
+    let now = Local::now();
+    let now = match cli.time {
+        // A time skipped by a DST change has no local instant; keep the real one then
+        Some(time) => now.with_time(time).earliest().unwrap_or(now),
+        None => now,
+    };
+
+    let date_format = (!cli.no_date).then_some(config.date_format.pattern());
+    let mut lines = clock_lines(font, &now, &config, date_format);
+    if cli.ascii || compat::locale_lacks_utf8() {
+        for line in &mut lines {
+            *line = line.chars().map(ascii_fallback).collect();
+        }
+    }
+    let lines = center(&lines, cli.width.unwrap_or(0));
+
+    let output = if cli.color {
+        let color = match config.color_theme {
+            ColorTheme::Auto => auto_color(nominal_time_of_day(&now), config.night_brightness),
+            theme => theme.color(),
+        };
+        colorize(&lines, config.color_theme, color)
+    } else {
+        lines.join("\n")
+    };
+    match writeln!(io::stdout().lock(), "{output}") {
+        // The reader, e.g. `head`, has all it wants
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Big time art for `now` in the format, spacing and fill of `config`,
+/// followed by a blank line and the date when a format is given.
+///
+/// A hidden AM/PM marker is added to the end of the date instead. An invalid
+/// fill keeps the font's own characters.
+fn clock_lines<Tz: TimeZone>(
+    font: &Font,
+    now: &DateTime<Tz>,
+    config: &Config,
+    date_format: Option<&str>,
+) -> Vec<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let (time_format, am_pm_style) = (config.time_format, config.am_pm_style);
+    let parts = TimeParts::from_datetime(now, time_format);
+    let meridiem = parts.meridiem(time_format);
+    let digits = parts.digits(time_format);
+    let fill = config_fill(config).ok().flatten();
+    let mut lines = build_time_art(
+        font,
+        &digits,
+        meridiem,
+        am_pm_style,
+        config.pad_hour,
+        config_spacing(config),
+        fill.as_ref(),
+    );
+    if let Some(pattern) = date_format {
+        let mut date = now.format(pattern).to_string();
+        if let Some(meridiem) = meridiem
+            && am_pm_style == AmPmStyle::Hidden
+        {
+            date = format!("{date} · {meridiem}");
+        }
+        lines.push(String::new());
+        lines.push(date);
+    }
+    lines
+}
+
+/// Center each line under the widest one, and the whole block in `width` columns.
+///
+/// Lines are padded on the left only, so no line ends in spaces.
+fn center(lines: &[String], width: usize) -> Vec<String> {
+    let block_width = lines.iter().map(|line| text_width(line)).max().unwrap_or(0);
+    let total = block_width.max(width);
+    lines
+        .iter()
+        .map(|line| {
+            let content = line.trim_end();
+            if content.is_empty() {
+                return String::new();
+            }
+            let pad = (total - text_width(line)) / 2;
+            format!("{:pad$}{content}", "")
+        })
+        .collect()
+}
+
+/// Color the non-space characters with ANSI escapes, following the theme gradient if any.
+fn colorize(lines: &[String], theme: ColorTheme, color: Color) -> String {
+    let width = lines.iter().map(|line| text_width(line)).max().unwrap_or(0);
+    let height = lines.len();
+    let mut output = Vec::with_capacity(height);
+    for (y, line) in lines.iter().enumerate() {
+        let mut colored = String::new();
+        let mut current = None;
+        for (x, ch) in line.chars().enumerate() {
+            if ch != ' ' {
+                let cell_color = if theme.is_dynamic() {
+                    theme.color_at_position(x, y, width, height)
+                } else {
+                    color
+                };
+                // Only switch colors between runs, keeping the output small
+                if current != Some(cell_color) {
+                    colored.push_str(&SetForegroundColor(cell_color.into_crossterm()).to_string());
+                    current = Some(cell_color);
+                }
+            }
+            colored.push(ch);
+        }
+        if current.is_some() {
+            colored.push_str(&ResetColor.to_string());
+        }
+        output.push(colored);
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sigye_core::TimeFormat;
+    use sigye_fonts::build_text_art;
+    use sigye_widget::{ClockConfig, ClockWidget};
+
+    use super::*;
+
+    #[test]
+    fn test_clock_lines() {
+        let font = FontRegistry::new();
+        let font = font.get_or_default("Standard");
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 14, 30, 5).unwrap();
+
+        let config = Config {
+            time_format: TimeFormat::TwentyFourHour,
+            am_pm_style: AmPmStyle::Hidden,
+            pad_hour: false,
+            ..Config::default()
+        };
+        let lines = clock_lines(font, &now, &config, None);
+        assert_eq!(lines, build_text_art("14:30:05"));
+
+        let twelve = |am_pm_style| {
+            let config = Config {
+                time_format: TimeFormat::TwelveHour,
+                am_pm_style,
+                pad_hour: true,
+                ..Config::default()
+            };
+            clock_lines(font, &now, &config, Some("%Y-%m-%d"))
+        };
+        let lines = twelve(AmPmStyle::Large);
+        // The marker's letters are a blank column apart
+        let (digits, p, m) = (
+            build_text_art("02:30:05 "),
+            build_text_art("P"),
+            build_text_art("M"),
+        );
+        for (i, line) in lines[..font.height].iter().enumerate() {
+            assert_eq!(*line, format!("{}{} {}", digits[i], p[i], m[i]));
+        }
+        assert_eq!(lines[font.height..], ["", "2026-10-17"]);
+
+        let lines = twelve(AmPmStyle::Hidden);
+        assert_eq!(lines[..font.height], build_text_art("02:30:05"));
+        assert_eq!(lines[font.height..], ["", "2026-10-17 · PM"]);
+
+        let lines = twelve(AmPmStyle::SmallText);
+        assert!(
+            lines[..font.height]
+                .iter()
+                .any(|line| line.ends_with(" PM"))
+        );
+        assert_eq!(lines[font.height..], ["", "2026-10-17"]);
+    }
+
+    #[test]
+    fn test_fill_leaves_the_marker_alone() {
+        let font = FontRegistry::new();
+        let font = font.get_or_default("Standard");
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 14, 30, 5).unwrap();
+        let config = Config {
+            time_format: TimeFormat::TwelveHour,
+            am_pm_style: AmPmStyle::SmallText,
+            digit_fill: "@".to_string(),
+            ..Config::default()
+        };
+        let lines = clock_lines(font, &now, &config, None);
+        assert!(lines.iter().any(|line| line.ends_with(" PM")), "{lines:?}");
+        assert!(lines.iter().all(|line| !line.contains('|')));
+
+        // An invalid fill keeps the font's own characters
+        let invalid = Config {
+            digit_fill: "# ".to_string(),
+            ..config
+        };
+        assert!(
+            clock_lines(font, &now, &invalid, None)
+                .iter()
+                .any(|line| line.contains('|'))
+        );
+    }
+
+    #[test]
+    fn test_clock_lines_match_the_widget() {
+        // --once and the full-screen clock draw their digits through the same
+        // build_time_art, so their art is the same
+        let font = FontRegistry::new();
+        let font = font.get_or_default("Standard");
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 14, 30, 5).unwrap();
+        for (time_format, am_pm_style, digit_fill) in [
+            (TimeFormat::TwentyFourHour, AmPmStyle::Hidden, ""),
+            (TimeFormat::TwelveHour, AmPmStyle::Large, ""),
+            (TimeFormat::TwelveHour, AmPmStyle::SmallText, "@"),
+        ] {
+            let config = Config {
+                time_format,
+                am_pm_style,
+                pad_hour: true,
+                digit_fill: digit_fill.to_string(),
+                ..Config::default()
+            };
+            let fill = config_fill(&config).unwrap();
+            let widget = ClockWidget::new(
+                ClockConfig {
+                    time_format,
+                    am_pm_style,
+                    pad_hour: true,
+                    spacing: config_spacing(&config),
+                    fill: fill.as_ref(),
+                    ..ClockConfig::new(font)
+                },
+                &now,
+            );
+            assert_eq!(
+                clock_lines(font, &now, &config, None),
+                widget.art().lines(),
+                "{time_format:?} {am_pm_style:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_center() {
+        let lines = ["#####  ".to_string(), String::new(), "abc".to_string()];
+        assert_eq!(center(&lines, 0), ["#####", "", "  abc"]);
+        assert_eq!(center(&lines, 11), ["  #####", "", "    abc"]);
+        // A width narrower than the block leaves it as is
+        assert_eq!(center(&lines, 3), center(&lines, 0));
+    }
+
+    #[test]
+    fn test_colorize() {
+        let lines = ["## #".to_string(), String::new()];
+        let output = colorize(&lines, ColorTheme::Cyan, Color::Cyan);
+        assert_eq!(output, "\x1b[38;5;6m## #\x1b[0m\n");
+
+        let output = colorize(&lines, ColorTheme::Rainbow, Color::White);
+        assert!(output.matches("\x1b[38;").count() > 1, "{output:?}");
+    }
+}
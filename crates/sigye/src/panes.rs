@@ -0,0 +1,654 @@
+//! Split layout: a grid of panes, each showing a clock in some time zone, a
+//! stopwatch, a countdown timer or nothing, with one pane focused for keys.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeDelta, Timelike, Utc};
+use ratatui::layout::{Constraint, Layout, Rect};
+use sigye_core::PaneLayout;
+
+use crate::cli::parse_duration;
+
+/// Panes used when the config doesn't list any.
+pub const DEFAULT_PANES: [&str; 4] = ["local", "utc", "stopwatch", "timer 5m"];
+
+/// Time zone a clock pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneZone {
+    /// The system's local time.
+    Local,
+    /// Coordinated Universal Time.
+    Utc,
+    /// A fixed offset from UTC, e.g. UTC+09:00.
+    Fixed(FixedOffset),
+}
+
+impl PaneZone {
+    /// Parse "local", "utc" or an offset like "utc+9", "utc-3:30" or "+05:45".
+    fn parse(spec: &str) -> Option<Self> {
+        let lower = spec.trim().to_ascii_lowercase();
+        if lower == "local" {
+            return Some(PaneZone::Local);
+        }
+        let offset = ["utc", "gmt"]
+            .iter()
+            .find_map(|prefix| lower.strip_prefix(prefix))
+            .unwrap_or(&lower)
+            .trim();
+        if offset.is_empty() {
+            return Some(PaneZone::Utc);
+        }
+        let (sign, rest) = match offset.split_at_checked(1)? {
+            ("+", rest) => (1, rest),
+            ("-", rest) => (-1, rest),
+            _ => return None,
+        };
+        let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+        let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+        if minutes >= 60 {
+            return None;
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(PaneZone::Fixed)
+    }
+
+    /// Name shown on the pane, e.g. "UTC+09:00".
+    fn label(self) -> String {
+        match self {
+            PaneZone::Local => "Local".to_string(),
+            PaneZone::Utc => "UTC".to_string(),
+            PaneZone::Fixed(offset) => format!("UTC{offset}"),
+        }
+    }
+}
+
+/// What a pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneKind {
+    /// The time in a zone.
+    Clock(PaneZone),
+    /// Time counted up from a start.
+    Stopwatch,
+    /// Time counted down to zero from this long.
+    Timer(Duration),
+    /// Nothing but the background.
+    Blank,
+}
+
+impl PaneKind {
+    /// Parse a pane spec from the config, e.g. "utc+9", "stopwatch" or "timer 25m".
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let trimmed = spec.trim();
+        let lower = trimmed.to_ascii_lowercase();
+        match lower.as_str() {
+            "stopwatch" => return Ok(PaneKind::Stopwatch),
+            "blank" | "" => return Ok(PaneKind::Blank),
+            _ => {}
+        }
+        if let Some(duration) = lower.strip_prefix("timer") {
+            return parse_duration(duration)
+                .map(PaneKind::Timer)
+                .map_err(|e| format!("\"{trimmed}\": {e}"));
+        }
+        PaneZone::parse(&lower).map(PaneKind::Clock).ok_or_else(|| {
+            format!(
+                "\"{trimmed}\" is not a pane, expected local, utc, utc+9, stopwatch, \
+                     timer 5m or blank"
+            )
+        })
+    }
+}
+
+/// Start and stop time keeping for stopwatches and timers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stopwatch {
+    /// When the current run started, while running.
+    started: Option<Instant>,
+    /// Time counted in earlier runs.
+    banked: Duration,
+}
+
+impl Stopwatch {
+    /// Start the stopwatch, or stop it while running.
+    pub fn start_stop(&mut self, now: Instant) {
+        match self.started.take() {
+            Some(started) => self.banked += now.saturating_duration_since(started),
+            None => self.started = Some(now),
+        }
+    }
+
+    /// Stop the stopwatch and set it back to zero.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Check if the stopwatch is counting.
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Time counted as of `now`.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.banked
+            + self.started.map_or(Duration::ZERO, |started| {
+                now.saturating_duration_since(started)
+            })
+    }
+}
+
+/// A pane and the state of its stopwatch or timer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pane {
+    /// What the pane shows.
+    pub kind: PaneKind,
+    /// Time kept by a stopwatch or timer pane.
+    stopwatch: Stopwatch,
+    /// Whether a timer reached zero and its end was announced.
+    done: bool,
+}
+
+/// What a pane shows in one frame, compared to skip unchanged frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneFrame {
+    /// Name on the pane's border, e.g. "UTC" or "Timer 5:00".
+    pub label: String,
+    /// Time shown in big digits, to the second, `None` for a blank pane.
+    pub time: Option<DateTime<FixedOffset>>,
+    /// Line under the time, e.g. the date or "running".
+    pub caption: String,
+    /// Whether the time is a stopwatch or timer reading rather than a time of day.
+    pub duration: bool,
+    /// Whether keys act on this pane.
+    pub focused: bool,
+}
+
+impl Pane {
+    /// Pane showing `kind`, with a stopwatch at zero.
+    pub fn new(kind: PaneKind) -> Self {
+        Self {
+            kind,
+            stopwatch: Stopwatch::default(),
+            done: false,
+        }
+    }
+
+    /// Time left on a timer as of `now`, `None` for other panes.
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        match self.kind {
+            PaneKind::Timer(duration) => Some(duration.saturating_sub(self.stopwatch.elapsed(now))),
+            _ => None,
+        }
+    }
+
+    /// Check if a running stopwatch or timer needs the frame redrawn as its
+    /// shown second changes.
+    pub fn is_ticking(&self) -> bool {
+        self.stopwatch.is_running()
+    }
+
+    /// Time until the shown second of a running stopwatch or timer changes.
+    pub fn until_next_tick(&self, now: Instant) -> Option<Duration> {
+        if !self.is_ticking() {
+            return None;
+        }
+        let elapsed = self.stopwatch.elapsed(now);
+        // Timers show the remaining time rounded up, so they change at the same point
+        Some(Duration::from_nanos(
+            1_000_000_000 - u64::from(elapsed.subsec_nanos()),
+        ))
+    }
+
+    /// What the pane shows at `now`, or at `instant` for its stopwatch.
+    fn frame(&self, now: &DateTime<Local>, instant: Instant, focused: bool) -> PaneFrame {
+        let running = if self.stopwatch.is_running() {
+            "running"
+        } else if self.stopwatch.elapsed(instant).is_zero() {
+            "ready"
+        } else {
+            "stopped"
+        };
+        let (label, time, caption) = match self.kind {
+            PaneKind::Clock(zone) => {
+                let offset = match zone {
+                    PaneZone::Local => now.offset().fix(),
+                    PaneZone::Utc => Utc.fix(),
+                    PaneZone::Fixed(offset) => offset,
+                };
+                let time = now.with_timezone(&offset);
+                let caption = time.format("%a %-d %b").to_string();
+                (zone.label(), Some(time), caption)
+            }
+            PaneKind::Stopwatch => (
+                "Stopwatch".to_string(),
+                Some(duration_time(self.stopwatch.elapsed(instant))),
+                running.to_string(),
+            ),
+            PaneKind::Timer(duration) => {
+                let remaining = self.remaining(instant).unwrap_or_default();
+                // Whole seconds left, rounded up so zero shows only when done
+                let shown = Duration::from_secs(remaining.as_secs())
+                    + Duration::from_secs(u64::from(remaining.subsec_nanos() > 0));
+                let caption = if remaining.is_zero() {
+                    "done".to_string()
+                } else {
+                    running.to_string()
+                };
+                (
+                    format!("Timer {}", format_duration(duration)),
+                    Some(duration_time(shown)),
+                    caption,
+                )
+            }
+            PaneKind::Blank => (String::new(), None, String::new()),
+        };
+        PaneFrame {
+            label,
+            time: time.and_then(|time| time.with_nanosecond(0)),
+            caption,
+            duration: matches!(self.kind, PaneKind::Stopwatch | PaneKind::Timer(_)),
+            focused,
+        }
+    }
+}
+
+/// A duration as a time of day, to show it in the clock's digits.
+///
+/// Days are dropped, so a stopwatch wraps round after 24 hours.
+fn duration_time(duration: Duration) -> DateTime<FixedOffset> {
+    let time =
+        DateTime::UNIX_EPOCH + TimeDelta::seconds((duration.as_secs() % (24 * 60 * 60)) as i64);
+    time.fixed_offset()
+}
+
+/// A duration like "5:00" or "1:30:00".
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Areas of the panes of `layout` in `area`, in reading order.
+///
+/// Rows and columns share the area evenly, the first ones getting the odd
+/// cells left over.
+pub fn grid(area: Rect, layout: PaneLayout) -> Vec<Rect> {
+    let (rows, columns) = layout.shape();
+    Layout::vertical(vec![Constraint::Fill(1); usize::from(rows)])
+        .split(area)
+        .iter()
+        .flat_map(|&row| {
+            Layout::horizontal(vec![Constraint::Fill(1); usize::from(columns)])
+                .split(row)
+                .to_vec()
+        })
+        .collect()
+}
+
+/// Index of the pane after `focus` among `count`, wrapping round.
+pub fn next_focus(focus: usize, count: usize) -> usize {
+    if count == 0 { 0 } else { (focus + 1) % count }
+}
+
+/// The panes of a split layout and which one keys act on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Panes {
+    /// Grid the panes are laid out in.
+    layout: PaneLayout,
+    /// One pane per cell of the grid.
+    panes: Vec<Pane>,
+    /// Index of the focused pane.
+    focus: usize,
+}
+
+impl Panes {
+    /// Panes for `layout` from the config's specs, filled up from
+    /// [`DEFAULT_PANES`], returning warnings about specs that were ignored.
+    pub fn from_config(layout: PaneLayout, specs: &[String]) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut kinds: Vec<PaneKind> = specs
+            .iter()
+            .filter_map(|spec| {
+                PaneKind::parse(spec)
+                    .map_err(|e| warnings.push(format!("Ignoring pane from config: {e}")))
+                    .ok()
+            })
+            .collect();
+        if kinds.len() > layout.pane_count() && layout.is_split() {
+            warnings.push(format!(
+                "Ignoring panes from config past the {} of pane_layout",
+                layout.pane_count()
+            ));
+        }
+        let defaults = DEFAULT_PANES
+            .iter()
+            .skip(kinds.len())
+            .filter_map(|spec| PaneKind::parse(spec).ok());
+        kinds.extend(defaults);
+        kinds.resize(layout.pane_count(), PaneKind::Blank);
+        let panes = Self {
+            layout,
+            panes: kinds.into_iter().map(Pane::new).collect(),
+            focus: 0,
+        };
+        (panes, warnings)
+    }
+
+    /// Check if the screen is split into panes rather than one clock.
+    pub fn is_split(&self) -> bool {
+        self.layout.is_split()
+    }
+
+    /// Move the focus to the next pane.
+    pub fn focus_next(&mut self) {
+        self.focus = next_focus(self.focus, self.panes.len());
+    }
+
+    /// Start or stop the stopwatch or timer of the focused pane, returning
+    /// `false` when it has none.
+    pub fn start_stop(&mut self, now: Instant) -> bool {
+        let Some(pane) = self.panes.get_mut(self.focus) else {
+            return false;
+        };
+        match pane.kind {
+            PaneKind::Stopwatch => pane.stopwatch.start_stop(now),
+            // A finished timer starts over
+            PaneKind::Timer(_) if pane.remaining(now).is_some_and(|left| left.is_zero()) => {
+                pane.stopwatch.reset();
+                pane.done = false;
+                pane.stopwatch.start_stop(now);
+            }
+            PaneKind::Timer(_) => pane.stopwatch.start_stop(now),
+            PaneKind::Clock(_) | PaneKind::Blank => return false,
+        }
+        true
+    }
+
+    /// Set the stopwatch or timer of the focused pane back to its start,
+    /// returning `false` when it has none.
+    pub fn reset(&mut self) -> bool {
+        let Some(pane) = self.panes.get_mut(self.focus) else {
+            return false;
+        };
+        if !matches!(pane.kind, PaneKind::Stopwatch | PaneKind::Timer(_)) {
+            return false;
+        }
+        pane.stopwatch.reset();
+        pane.done = false;
+        true
+    }
+
+    /// Stop timers that reached zero by `now`, returning the labels and
+    /// durations of those that just finished.
+    pub fn take_finished(&mut self, now: Instant) -> Vec<(String, Duration)> {
+        let mut finished = Vec::new();
+        for pane in &mut self.panes {
+            if pane.done || !pane.remaining(now).is_some_and(|left| left.is_zero()) {
+                continue;
+            }
+            pane.done = true;
+            if pane.stopwatch.is_running() {
+                pane.stopwatch.start_stop(now);
+            }
+            if let PaneKind::Timer(duration) = pane.kind {
+                finished.push((format!("Timer {}", format_duration(duration)), duration));
+            }
+        }
+        finished
+    }
+
+    /// Time until a running stopwatch or timer shows its next second.
+    pub fn until_next_tick(&self, now: Instant) -> Option<Duration> {
+        self.panes
+            .iter()
+            .filter_map(|pane| pane.until_next_tick(now))
+            .min()
+    }
+
+    /// What each pane shows at `now`, or at `instant` for the stopwatches.
+    pub fn frames(&self, now: &DateTime<Local>, instant: Instant) -> Vec<PaneFrame> {
+        self.panes
+            .iter()
+            .enumerate()
+            .map(|(index, pane)| pane.frame(now, instant, index == self.focus))
+            .collect()
+    }
+
+    /// Areas of the panes in `area`, in the order of [`Panes::frames`].
+    pub fn areas(&self, area: Rect) -> Vec<Rect> {
+        grid(area, self.layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_splits_evenly() {
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(grid(area, PaneLayout::Single), vec![area]);
+        assert_eq!(
+            grid(area, PaneLayout::SideBySide),
+            vec![Rect::new(0, 0, 40, 24), Rect::new(40, 0, 40, 24)]
+        );
+        assert_eq!(
+            grid(area, PaneLayout::Stacked),
+            vec![Rect::new(0, 0, 80, 12), Rect::new(0, 12, 80, 12)]
+        );
+        assert_eq!(
+            grid(area, PaneLayout::Grid),
+            vec![
+                Rect::new(0, 0, 40, 12),
+                Rect::new(40, 0, 40, 12),
+                Rect::new(0, 12, 40, 12),
+                Rect::new(40, 12, 40, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_covers_odd_and_tiny_areas() {
+        for area in [
+            Rect::new(3, 2, 81, 25),
+            Rect::new(0, 0, 3, 1),
+            Rect::new(0, 0, 0, 0),
+        ] {
+            let cells = grid(area, PaneLayout::Grid);
+            assert_eq!(cells.len(), 4);
+            let covered: u32 = cells.iter().map(|cell| cell.area()).sum();
+            assert_eq!(covered, area.area());
+            assert!(cells.iter().all(|cell| area.union(*cell) == area));
+        }
+    }
+
+    #[test]
+    fn test_focus_cycles() {
+        assert_eq!(next_focus(0, 4), 1);
+        assert_eq!(next_focus(3, 4), 0);
+        assert_eq!(next_focus(0, 1), 0);
+        assert_eq!(next_focus(0, 0), 0);
+
+        let (mut panes, _) = Panes::from_config(PaneLayout::SideBySide, &[]);
+        let focused = |panes: &Panes| {
+            let now = Local::now();
+            panes
+                .frames(&now, Instant::now())
+                .iter()
+                .position(|frame| frame.focused)
+        };
+        assert_eq!(focused(&panes), Some(0));
+        panes.focus_next();
+        assert_eq!(focused(&panes), Some(1));
+        panes.focus_next();
+        assert_eq!(focused(&panes), Some(0));
+    }
+
+    #[test]
+    fn test_parse_panes() {
+        assert_eq!(
+            PaneKind::parse("Local"),
+            Ok(PaneKind::Clock(PaneZone::Local))
+        );
+        assert_eq!(PaneKind::parse("utc"), Ok(PaneKind::Clock(PaneZone::Utc)));
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(
+            PaneKind::parse("UTC+9"),
+            Ok(PaneKind::Clock(PaneZone::Fixed(tokyo)))
+        );
+        assert_eq!(
+            PaneKind::parse("+09:00"),
+            Ok(PaneKind::Clock(PaneZone::Fixed(tokyo)))
+        );
+        let newfoundland = FixedOffset::west_opt(3 * 3600 + 1800).unwrap();
+        assert_eq!(
+            PaneKind::parse("gmt-3:30"),
+            Ok(PaneKind::Clock(PaneZone::Fixed(newfoundland)))
+        );
+        assert_eq!(PaneKind::parse("stopwatch"), Ok(PaneKind::Stopwatch));
+        assert_eq!(
+            PaneKind::parse("timer 25m"),
+            Ok(PaneKind::Timer(Duration::from_secs(25 * 60)))
+        );
+        assert_eq!(PaneKind::parse("blank"), Ok(PaneKind::Blank));
+        assert!(PaneKind::parse("timer").is_err());
+        assert!(PaneKind::parse("utc+9:75").is_err());
+        assert!(PaneKind::parse("mars").is_err());
+    }
+
+    #[test]
+    fn test_from_config_fills_the_grid() {
+        let (panes, warnings) = Panes::from_config(PaneLayout::Grid, &["utc+9".to_string()]);
+        assert!(warnings.is_empty());
+        let kinds: Vec<PaneKind> = panes.panes.iter().map(|pane| pane.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PaneKind::Clock(PaneZone::Fixed(FixedOffset::east_opt(9 * 3600).unwrap())),
+                PaneKind::Clock(PaneZone::Utc),
+                PaneKind::Stopwatch,
+                PaneKind::Timer(Duration::from_secs(300)),
+            ]
+        );
+
+        let specs = ["mars", "stopwatch", "utc", "blank"].map(String::from);
+        let (panes, warnings) = Panes::from_config(PaneLayout::SideBySide, &specs);
+        assert_eq!(panes.panes.len(), 2);
+        assert_eq!(panes.panes[0].kind, PaneKind::Stopwatch);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("mars"));
+
+        let (panes, _) = Panes::from_config(PaneLayout::Single, &[]);
+        assert!(!panes.is_split());
+    }
+
+    #[test]
+    fn test_stopwatch() {
+        let start = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        assert_eq!(stopwatch.elapsed(start), Duration::ZERO);
+        stopwatch.start_stop(start);
+        assert_eq!(
+            stopwatch.elapsed(start + Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+        stopwatch.start_stop(start + Duration::from_secs(5));
+        // Stopped: time stands still until started again
+        assert_eq!(
+            stopwatch.elapsed(start + Duration::from_secs(60)),
+            Duration::from_secs(5)
+        );
+        stopwatch.start_stop(start + Duration::from_secs(60));
+        assert_eq!(
+            stopwatch.elapsed(start + Duration::from_secs(62)),
+            Duration::from_secs(7)
+        );
+        stopwatch.reset();
+        assert!(!stopwatch.is_running());
+        assert_eq!(
+            stopwatch.elapsed(start + Duration::from_secs(70)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_timer_counts_down_and_finishes() {
+        let (mut panes, _) = Panes::from_config(PaneLayout::Grid, &[]);
+        let now = Local::now();
+        let start = Instant::now();
+        let at = |secs: f64| start + Duration::from_secs_f64(secs);
+        let timer = |panes: &Panes, instant| panes.frames(&now, instant)[3].clone();
+
+        // Focus the timer and start it
+        panes.focus_next();
+        panes.focus_next();
+        panes.focus_next();
+        assert!(panes.start_stop(start));
+        let frame = timer(&panes, at(0.5));
+        assert_eq!(frame.label, "Timer 5:00");
+        assert_eq!(
+            frame.time.unwrap().format("%H:%M:%S").to_string(),
+            "00:05:00"
+        );
+        assert_eq!(frame.caption, "running");
+        assert_eq!(
+            timer(&panes, at(1.0))
+                .time
+                .unwrap()
+                .format("%M:%S")
+                .to_string(),
+            "04:59"
+        );
+        assert_eq!(
+            panes.until_next_tick(at(1.25)),
+            Some(Duration::from_millis(750))
+        );
+
+        // Zero is announced once, and stops the timer
+        assert!(panes.take_finished(at(299.0)).is_empty());
+        assert_eq!(
+            panes.take_finished(at(300.0)),
+            vec![("Timer 5:00".to_string(), Duration::from_secs(300))]
+        );
+        assert!(panes.take_finished(at(301.0)).is_empty());
+        assert_eq!(timer(&panes, at(400.0)).caption, "done");
+        assert_eq!(panes.until_next_tick(at(400.0)), None);
+
+        // Starting a finished timer starts it over
+        assert!(panes.start_stop(at(500.0)));
+        assert_eq!(
+            timer(&panes, at(560.0))
+                .time
+                .unwrap()
+                .format("%M:%S")
+                .to_string(),
+            "04:00"
+        );
+        assert!(panes.reset());
+        assert_eq!(timer(&panes, at(600.0)).caption, "ready");
+
+        // Clock panes have nothing to start
+        panes.focus_next();
+        assert!(!panes.start_stop(at(600.0)));
+        assert!(!panes.reset());
+    }
+
+    #[test]
+    fn test_clock_panes_show_their_zone() {
+        let (panes, _) = Panes::from_config(
+            PaneLayout::SideBySide,
+            &["utc".to_string(), "utc+9".to_string()],
+        );
+        let now = Local::now();
+        let frames = panes.frames(&now, Instant::now());
+        assert_eq!(frames[0].label, "UTC");
+        assert_eq!(frames[1].label, "UTC+09:00");
+        let (utc, tokyo) = (frames[0].time.unwrap(), frames[1].time.unwrap());
+        assert_eq!(utc, tokyo);
+        assert_eq!(utc.offset().local_minus_utc(), 0);
+        assert_eq!(tokyo.offset().local_minus_utc(), 9 * 3600);
+        assert_eq!(utc.timestamp_subsec_nanos(), 0);
+    }
+}
@@ -0,0 +1,440 @@
+//! Dirty-frame detection so a static clock only redraws when something visible changes.
+
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone};
+use ratatui::layout::Size;
+use sigye_core::{
+    Accessibility, AmPmStyle, AnimationStyle, BackgroundStyle, ClockBorder, ClockPosition,
+    ColorTheme, DisplayMode, SubsecondPrecision, TimeFormat, progress::ProgressBar,
+};
+use sigye_fonts::Spacing;
+
+use crate::calendar::AgendaLine;
+use crate::info_line::InfoLine;
+use crate::panes::PaneFrame;
+use crate::status::{Status, Subsystem};
+
+/// Snapshot of everything that determines what a frame looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameKey {
+    /// Unix timestamp of the displayed second.
+    pub second: i64,
+    /// Time format.
+    pub time_format: TimeFormat,
+    /// How AM/PM is drawn in 12-hour format.
+    pub am_pm_style: AmPmStyle,
+    /// Digits or words.
+    pub display_mode: DisplayMode,
+    /// Sub-second digits shown under the time.
+    pub subsecond_precision: SubsecondPrecision,
+    /// Color theme.
+    pub color_theme: ColorTheme,
+    /// Background style actually rendered (weather already resolved).
+    pub background_style: BackgroundStyle,
+    /// Text animation style.
+    pub animation_style: AnimationStyle,
+    /// Font name.
+    pub font: String,
+    /// Info line mode.
+    pub info_line: InfoLine,
+    /// Current weather line text, if shown.
+    pub weather: Option<String>,
+    /// Upcoming calendar events and which start soon, if shown.
+    pub agenda: Option<AgendaLine>,
+    /// Notice of an upcoming daylight saving change, if shown.
+    pub dst: Option<String>,
+    /// Warning about a drifting system clock, if shown.
+    pub clock_drift: Option<String>,
+    /// Banner text as drawn, blank while blinked off, if shown.
+    pub banner: Option<String>,
+    /// What each pane of a split screen shows, empty for the single clock.
+    pub panes: Vec<PaneFrame>,
+    /// Whether the CPU and memory line is shown.
+    pub load: bool,
+    /// Period of the progress bar.
+    pub progress_bar: ProgressBar,
+    /// Brightness every color is scaled by.
+    pub brightness: f32,
+    /// Whether the colons are currently shown (blink phase).
+    pub colon_visible: bool,
+    /// Whether an alarm is ringing, and whether the digits are currently inverted.
+    pub alarm: Option<bool>,
+    /// Terminal size.
+    pub size: Size,
+    /// Whether the reactive flash is still decaying.
+    pub flashing: bool,
+    /// Whether the background is crossfading to new styles.
+    pub crossfading: bool,
+    /// Where the clock sits when not drifting.
+    pub clock_position: ClockPosition,
+    /// Border drawn around the clock.
+    pub clock_border: ClockBorder,
+    /// High contrast or plain text display.
+    pub accessibility: Accessibility,
+    /// Gaps between the clock glyphs.
+    pub spacing: Spacing,
+    /// Whether the clock is drawn or hidden by the show schedule.
+    pub clock_visible: bool,
+    /// Anti-burn-in offset of the clock, if shifting.
+    pub burn_in_offset: Option<(i16, i16)>,
+    /// Whether the clock is drifting around the screen.
+    pub bouncing: bool,
+    /// Whether the help line is shown.
+    pub help_line: bool,
+    /// Messages of the toasts shown in the corner.
+    pub toasts: Vec<String>,
+    /// Whether a toast is fading out.
+    pub toast_fading: bool,
+    /// Whether the display is paused.
+    pub paused: bool,
+    /// Subsystems that aren't working normally, for the marker and the overlay.
+    pub problems: Vec<(Subsystem, Status)>,
+}
+
+/// Time from `now` until the next wall-clock second starts, when the shown second flips.
+///
+/// Only the fraction of the current second counts, so DST changes, which move
+/// the offset by whole minutes, never shift the boundary. Exactly on a boundary
+/// the next one is a full second away. chrono reports a leap second as over a
+/// second of nanoseconds; its end is the boundary then.
+pub fn until_next_second<Tz: TimeZone>(now: &DateTime<Tz>) -> Duration {
+    let nanos = now.timestamp_subsec_nanos() % 1_000_000_000;
+    Duration::from_nanos(u64::from(1_000_000_000 - nanos))
+}
+
+impl FrameKey {
+    /// Check if the frame changes on its own over time, independent of the key.
+    ///
+    /// A paused frame stays still apart from fading toasts.
+    pub fn is_animated(&self) -> bool {
+        let moving = self.background_style.is_animated()
+            || self.flashing
+            || self.crossfading
+            || self.bouncing
+            || self.subsecond_precision != SubsecondPrecision::Off
+            || matches!(
+                self.animation_style,
+                AnimationStyle::Shifting | AnimationStyle::Pulsing | AnimationStyle::Wave
+            );
+        self.toast_fading || (moving && !self.paused)
+    }
+
+    /// Decide whether this frame must be drawn given the last drawn frame.
+    pub fn needs_redraw(&self, last: Option<&FrameKey>) -> bool {
+        self.is_animated() || last != Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, FixedOffset, NaiveDate, Timelike};
+
+    use super::*;
+
+    /// A time in a fixed UTC offset, given in hours.
+    fn at(
+        offset_hours: i32,
+        (year, month, day): (i32, u32, u32),
+        (hour, minute, second): (u32, u32, u32),
+        millis: u32,
+    ) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt(offset_hours * 3600).unwrap();
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_milli_opt(hour, minute, second, millis)
+            .unwrap()
+            .and_local_timezone(offset)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_until_next_second_rollover() {
+        let now = at(9, (2026, 12, 31), (23, 59, 59), 999);
+        let wait = until_next_second(&now);
+        assert_eq!(wait, Duration::from_millis(1));
+        let next = now + wait;
+        assert_eq!(next.timestamp(), now.timestamp() + 1);
+        assert_eq!((next.ordinal(), next.hour(), next.second()), (1, 0, 0));
+        assert_eq!(next.timestamp_subsec_nanos(), 0);
+
+        // Exactly on a boundary the next one is a second away
+        let now = at(9, (2026, 10, 17), (12, 0, 0), 0);
+        assert_eq!(until_next_second(&now), Duration::from_secs(1));
+        let now = at(9, (2026, 10, 17), (12, 0, 0), 1);
+        assert_eq!(until_next_second(&now), Duration::from_millis(999));
+    }
+
+    #[test]
+    fn test_until_next_second_across_dst_changes() {
+        // US spring forward: 01:59:59 EST is followed by 03:00:00 EDT
+        let before = at(-5, (2026, 3, 8), (1, 59, 59), 250);
+        let wait = until_next_second(&before);
+        assert_eq!(wait, Duration::from_millis(750));
+        let after = (before + wait).with_timezone(&FixedOffset::west_opt(4 * 3600).unwrap());
+        assert_eq!((after.hour(), after.minute(), after.second()), (3, 0, 0));
+        assert_eq!(after.timestamp_subsec_nanos(), 0);
+        // The same instant seen in either offset waits just as long
+        assert_eq!(
+            until_next_second(&before.with_timezone(&after.timezone())),
+            wait
+        );
+
+        // US fall back: 01:59:59 EDT is followed by 01:00:00 EST
+        let before = at(-4, (2026, 11, 1), (1, 59, 59), 900);
+        let wait = until_next_second(&before);
+        assert_eq!(wait, Duration::from_millis(100));
+        let after = (before + wait).with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
+        assert_eq!((after.hour(), after.minute(), after.second()), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_until_next_second_in_a_leap_second() {
+        let leap = at(0, (2016, 12, 31), (23, 59, 59), 1_500);
+        assert_eq!(until_next_second(&leap), Duration::from_millis(500));
+    }
+
+    fn idle() -> FrameKey {
+        FrameKey {
+            second: 1_700_000_000,
+            time_format: TimeFormat::TwentyFourHour,
+            am_pm_style: AmPmStyle::Large,
+            display_mode: DisplayMode::Digits,
+            subsecond_precision: SubsecondPrecision::Off,
+            color_theme: ColorTheme::Cyan,
+            background_style: BackgroundStyle::None,
+            animation_style: AnimationStyle::None,
+            font: "Standard".to_string(),
+            info_line: InfoLine::Hidden,
+            progress_bar: ProgressBar::Hidden,
+            brightness: 1.0,
+            weather: None,
+            agenda: None,
+            dst: None,
+            clock_drift: None,
+            banner: None,
+            panes: Vec::new(),
+            load: false,
+            colon_visible: true,
+            alarm: None,
+            size: Size::new(80, 24),
+            flashing: false,
+            crossfading: false,
+            clock_position: ClockPosition::Center,
+            clock_border: ClockBorder::None,
+            accessibility: Accessibility::Off,
+            spacing: Spacing::default(),
+            clock_visible: true,
+            burn_in_offset: None,
+            bouncing: false,
+            help_line: true,
+            toasts: Vec::new(),
+            toast_fading: false,
+            paused: false,
+            problems: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_first_frame_is_drawn() {
+        assert!(idle().needs_redraw(None));
+    }
+
+    #[test]
+    fn test_unchanged_static_frame_is_skipped() {
+        let last = idle();
+        assert!(!idle().needs_redraw(Some(&last)));
+    }
+
+    #[test]
+    fn test_visible_changes_trigger_redraw() {
+        let last = idle();
+        let changes = [
+            FrameKey {
+                second: last.second + 1,
+                ..idle()
+            },
+            FrameKey {
+                time_format: TimeFormat::TwelveHour,
+                ..idle()
+            },
+            FrameKey {
+                am_pm_style: AmPmStyle::SmallText,
+                ..idle()
+            },
+            FrameKey {
+                display_mode: DisplayMode::Words,
+                ..idle()
+            },
+            FrameKey {
+                color_theme: ColorTheme::Rainbow,
+                ..idle()
+            },
+            FrameKey {
+                font: "Block".to_string(),
+                ..idle()
+            },
+            FrameKey {
+                spacing: Spacing::default().wider(),
+                ..idle()
+            },
+            FrameKey {
+                info_line: InfoLine::WeekAndDay,
+                ..idle()
+            },
+            FrameKey {
+                weather: Some("☀ 23°C, Clear".to_string()),
+                ..idle()
+            },
+            FrameKey {
+                agenda: Some(AgendaLine {
+                    text: "14:00 Standup".to_string(),
+                    soon: None,
+                }),
+                ..idle()
+            },
+            FrameKey {
+                agenda: Some(AgendaLine {
+                    text: "14:00 Standup".to_string(),
+                    soon: Some(0..13),
+                }),
+                ..idle()
+            },
+            FrameKey {
+                dst: Some("Clocks go forward at 02:00 → 03:00 tonight".to_string()),
+                ..idle()
+            },
+            FrameKey {
+                clock_drift: Some(" clock 40.2 s slow ".to_string()),
+                ..idle()
+            },
+            FrameKey {
+                banner: Some("ON AIR".to_string()),
+                ..idle()
+            },
+            FrameKey {
+                banner: Some(String::new()),
+                ..idle()
+            },
+            FrameKey {
+                panes: vec![PaneFrame {
+                    label: "Stopwatch".to_string(),
+                    time: None,
+                    caption: "ready".to_string(),
+                    duration: true,
+                    focused: true,
+                }],
+                ..idle()
+            },
+            FrameKey {
+                load: true,
+                ..idle()
+            },
+            FrameKey {
+                progress_bar: ProgressBar::Day,
+                ..idle()
+            },
+            FrameKey {
+                brightness: 0.5,
+                ..idle()
+            },
+            FrameKey {
+                colon_visible: false,
+                ..idle()
+            },
+            FrameKey {
+                alarm: Some(true),
+                ..idle()
+            },
+            FrameKey {
+                size: Size::new(120, 40),
+                ..idle()
+            },
+            FrameKey {
+                help_line: false,
+                ..idle()
+            },
+            FrameKey {
+                toasts: vec!["Theme: Rainbow".to_string()],
+                ..idle()
+            },
+            FrameKey {
+                paused: true,
+                ..idle()
+            },
+            FrameKey {
+                problems: vec![(
+                    Subsystem::Weather,
+                    Status::Degraded("timed out".to_string()),
+                )],
+                ..idle()
+            },
+        ];
+        for next in changes {
+            assert!(next.needs_redraw(Some(&last)), "{next:?}");
+        }
+    }
+
+    #[test]
+    fn test_animated_frames_always_redraw() {
+        for next in [
+            FrameKey {
+                background_style: BackgroundStyle::Starfield,
+                ..idle()
+            },
+            FrameKey {
+                animation_style: AnimationStyle::Pulsing,
+                ..idle()
+            },
+            FrameKey {
+                flashing: true,
+                ..idle()
+            },
+            FrameKey {
+                crossfading: true,
+                ..idle()
+            },
+            FrameKey {
+                bouncing: true,
+                ..idle()
+            },
+            FrameKey {
+                toast_fading: true,
+                ..idle()
+            },
+            FrameKey {
+                subsecond_precision: SubsecondPrecision::Tenths,
+                ..idle()
+            },
+        ] {
+            assert!(next.needs_redraw(Some(&next.clone())), "{next:?}");
+        }
+
+        // Reactive text only animates while a flash is decaying.
+        let reactive = FrameKey {
+            animation_style: AnimationStyle::Reactive,
+            ..idle()
+        };
+        assert!(!reactive.needs_redraw(Some(&reactive.clone())));
+    }
+
+    #[test]
+    fn test_paused_frames_hold_still() {
+        let paused = FrameKey {
+            background_style: BackgroundStyle::Starfield,
+            animation_style: AnimationStyle::Wave,
+            subsecond_precision: SubsecondPrecision::Hundredths,
+            bouncing: true,
+            paused: true,
+            ..idle()
+        };
+        assert!(!paused.needs_redraw(Some(&paused.clone())));
+
+        // Toasts keep fading over a paused frame
+        let fading = FrameKey {
+            toast_fading: true,
+            ..paused
+        };
+        assert!(fading.needs_redraw(Some(&fading.clone())));
+    }
+}
@@ -0,0 +1,179 @@
+//! Live reload of the config file: working out which values changed in an
+//! edit, so only those are applied.
+
+use std::collections::BTreeMap;
+
+use sigye_config::{Config, KeyBinding};
+use sigye_core::{
+    Accessibility, AmPmStyle, AnimationSpeed, AnimationStyle, BackgroundStyle, ClockBorder,
+    ClockPosition, ColorTheme, DateFormat, DisplayMode, PaneLayout, SubsecondPrecision, TimeFormat,
+    progress::ProgressBar, theme::ThemeOverrides,
+};
+use sigye_fonts::Spacing;
+
+use crate::config_spacing;
+
+/// `new` when it differs from `old`.
+fn changed<T: Clone + PartialEq>(old: &T, new: &T) -> Option<T> {
+    (old != new).then(|| new.clone())
+}
+
+/// Values changed between two configs, each `None` when unchanged.
+///
+/// Values read straight from the config on each frame take effect without
+/// being listed; values parsed once at startup, like alarms, schedules or the
+/// weather location, wait for a restart.
+#[derive(Debug, Default, PartialEq)]
+pub struct ConfigChanges {
+    /// Font of the digits.
+    pub font_name: Option<String>,
+    /// Color theme.
+    pub color_theme: Option<ColorTheme>,
+    /// Colors replacing the theme's.
+    pub theme: Option<ThemeOverrides>,
+    /// 12 or 24-hour time.
+    pub time_format: Option<TimeFormat>,
+    /// How AM/PM is drawn.
+    pub am_pm_style: Option<AmPmStyle>,
+    /// Format of the date line.
+    pub date_format: Option<DateFormat>,
+    /// Digits or words.
+    pub display_mode: Option<DisplayMode>,
+    /// Sub-second digits under the time.
+    pub subsecond_precision: Option<SubsecondPrecision>,
+    /// Animation of the digits.
+    pub animation_style: Option<AnimationStyle>,
+    /// Speed of the animations.
+    pub animation_speed: Option<AnimationSpeed>,
+    /// Whether the colon blinks.
+    pub colon_blink: Option<bool>,
+    /// Background style.
+    pub background_style: Option<BackgroundStyle>,
+    /// Where the clock sits.
+    pub clock_position: Option<ClockPosition>,
+    /// Border around the clock.
+    pub clock_border: Option<ClockBorder>,
+    /// High contrast or plain text display.
+    pub accessibility: Option<Accessibility>,
+    /// Gaps between the digits, colons and AM/PM.
+    pub spacing: Option<Spacing>,
+    /// Whether the weather line is shown.
+    pub show_weather: Option<bool>,
+    /// Whether the CPU and memory line is shown.
+    pub show_load: Option<bool>,
+    /// Period of the progress bar.
+    pub progress_bar: Option<ProgressBar>,
+    /// Message of the banner, empty for none.
+    pub banner: Option<String>,
+    /// Grid of the panes, and what each pane shows.
+    pub panes: Option<(PaneLayout, Vec<String>)>,
+    /// Actions pinned to the help line.
+    pub help_line: Option<Vec<String>>,
+    /// Key bindings of the `[keys]` table.
+    pub keys: Option<BTreeMap<String, KeyBinding>>,
+}
+
+impl ConfigChanges {
+    /// Work out what changed from `old` to `new`.
+    pub fn between(old: &Config, new: &Config) -> Self {
+        Self {
+            font_name: changed(&old.font_name, &new.font_name),
+            color_theme: changed(&old.color_theme, &new.color_theme),
+            theme: changed(&old.theme, &new.theme),
+            time_format: changed(&old.time_format, &new.time_format),
+            am_pm_style: changed(&old.am_pm_style, &new.am_pm_style),
+            date_format: changed(&old.date_format, &new.date_format),
+            display_mode: changed(&old.display_mode, &new.display_mode),
+            subsecond_precision: changed(&old.subsecond_precision, &new.subsecond_precision),
+            animation_style: changed(&old.animation_style, &new.animation_style),
+            animation_speed: changed(&old.animation_speed, &new.animation_speed),
+            colon_blink: changed(&old.colon_blink, &new.colon_blink),
+            background_style: changed(&old.background_style, &new.background_style),
+            clock_position: changed(&old.clock_position, &new.clock_position),
+            clock_border: changed(&old.clock_border, &new.clock_border),
+            accessibility: changed(&old.accessibility, &new.accessibility),
+            spacing: changed(&config_spacing(old), &config_spacing(new)),
+            show_weather: changed(&old.show_weather, &new.show_weather),
+            show_load: changed(&old.show_load, &new.show_load),
+            progress_bar: changed(&old.progress_bar, &new.progress_bar),
+            banner: changed(&old.banner, &new.banner),
+            panes: changed(
+                &(old.pane_layout, old.panes.clone()),
+                &(new.pane_layout, new.panes.clone()),
+            ),
+            help_line: changed(&old.help_line, &new.help_line),
+            keys: changed(&old.keys, &new.keys),
+        }
+    }
+
+    /// Check if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check if a change may start or stop the weather or metrics monitors.
+    pub fn affects_monitors(&self) -> bool {
+        self.background_style.is_some()
+            || self.accessibility.is_some()
+            || self.color_theme.is_some()
+            || self.show_weather.is_some()
+            || self.show_load.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged_config_has_no_changes() {
+        let config = Config::default();
+        let changes = ConfigChanges::between(&config, &config.clone());
+        assert!(changes.is_empty());
+        assert!(!changes.affects_monitors());
+    }
+
+    #[test]
+    fn test_only_changed_values_are_listed() {
+        let old = Config::default();
+        let new = Config {
+            color_theme: ColorTheme::Rainbow,
+            digit_gap: old.digit_gap + 1,
+            // Read on each frame, so not listed
+            clear_zone_dim: 0.9,
+            ..old.clone()
+        };
+        let changes = ConfigChanges::between(&old, &new);
+        assert_eq!(
+            changes,
+            ConfigChanges {
+                color_theme: Some(ColorTheme::Rainbow),
+                spacing: Some(config_spacing(&new)),
+                ..ConfigChanges::default()
+            }
+        );
+        assert!(changes.affects_monitors());
+    }
+
+    #[test]
+    fn test_monitors_only_follow_their_values() {
+        let old = Config::default();
+        let new = Config {
+            font_name: "Big".to_string(),
+            animation_speed: AnimationSpeed::Fast,
+            ..old.clone()
+        };
+        let changes = ConfigChanges::between(&old, &new);
+        assert_eq!(changes.font_name.as_deref(), Some("Big"));
+        assert_eq!(changes.background_style, None);
+        assert!(!changes.affects_monitors());
+
+        let new = Config {
+            background_style: BackgroundStyle::Starfield,
+            ..old.clone()
+        };
+        let changes = ConfigChanges::between(&old, &new);
+        assert_eq!(changes.background_style, Some(BackgroundStyle::Starfield));
+        assert!(changes.affects_monitors());
+    }
+}
@@ -0,0 +1,216 @@
+//! Ring of second ticks drawn around the time, with a marker going round it
+//! once a minute like the second hand of an analog clock.
+
+use std::cmp::Ordering;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::Color,
+};
+
+/// Ticks around the ring, one for each second of the minute.
+pub const TICKS: usize = 60;
+
+/// Cells the ring adds around the time on each axis.
+pub const RING_SIZE: u16 = 2;
+
+/// Marker on the tick of the current second.
+const MARKER: char = '●';
+
+/// Cells along the edge of `rect`, going clockwise from the middle of the top.
+///
+/// Each cell is listed once, so a rect one cell thin is a single line of cells.
+pub fn perimeter(rect: Rect) -> Vec<Position> {
+    if rect.is_empty() {
+        return Vec::new();
+    }
+    let (left, top) = (rect.x, rect.y);
+    let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+
+    let mut cells: Vec<Position> = (left..=right).map(|x| Position::new(x, top)).collect();
+    cells.extend((top + 1..=bottom).map(|y| Position::new(right, y)));
+    if bottom > top {
+        cells.extend((left..right).rev().map(|x| Position::new(x, bottom)));
+    }
+    if right > left {
+        cells.extend((top + 1..bottom).rev().map(|y| Position::new(left, y)));
+    }
+    // Twelve o'clock is the middle of the top edge
+    cells.rotate_left(usize::from(rect.width / 2));
+    cells
+}
+
+/// Index into a perimeter of `len` cells of the cell showing `tick`, spreading
+/// the ticks evenly however many cells there are.
+pub fn tick_index(tick: usize, len: usize) -> usize {
+    tick % TICKS * len / TICKS
+}
+
+/// Box drawing character for the cell at `pos` on the edge of `rect`.
+fn edge_char(rect: Rect, pos: Position) -> char {
+    let (left, top) = (pos.x == rect.x, pos.y == rect.y);
+    let (right, bottom) = (pos.x == rect.right() - 1, pos.y == rect.bottom() - 1);
+    if rect.height == 1 {
+        return '─';
+    }
+    if rect.width == 1 {
+        return '│';
+    }
+    match (left, right, top, bottom) {
+        (true, _, true, _) => '┌',
+        (_, true, true, _) => '┐',
+        (true, _, _, true) => '└',
+        (_, true, _, true) => '┘',
+        (_, _, true, _) | (_, _, _, true) => '─',
+        _ => '│',
+    }
+}
+
+/// Draw the ring on the edge of `rect` for `second` of the minute, with the
+/// ticks up to it in `elapsed` and the rest in `remaining`.
+pub fn render(buf: &mut Buffer, rect: Rect, second: u32, elapsed: Color, remaining: Color) {
+    let rect = rect.intersection(buf.area);
+    let cells = perimeter(rect);
+    let marker = tick_index(second as usize, cells.len());
+    for (index, &pos) in cells.iter().enumerate() {
+        let (ch, color) = match index.cmp(&marker) {
+            Ordering::Less => (edge_char(rect, pos), elapsed),
+            Ordering::Equal => (MARKER, elapsed),
+            Ordering::Greater => (edge_char(rect, pos), remaining),
+        };
+        buf[pos].set_char(ch).set_fg(color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Check the cells are distinct, on the edge, and each next to the last.
+    fn assert_walks_the_edge(rect: Rect) {
+        let cells = perimeter(rect);
+        let unique: HashSet<_> = cells.iter().collect();
+        assert_eq!(unique.len(), cells.len(), "{rect:?} repeats a cell");
+        for pos in &cells {
+            assert!(rect.contains(*pos));
+            let on_edge = pos.x == rect.x
+                || pos.y == rect.y
+                || pos.x == rect.right() - 1
+                || pos.y == rect.bottom() - 1;
+            assert!(on_edge, "{pos:?} is inside {rect:?}");
+        }
+        for pair in cells.windows(2) {
+            let step = pair[0].x.abs_diff(pair[1].x) + pair[0].y.abs_diff(pair[1].y);
+            assert_eq!(
+                step, 1,
+                "{rect:?} jumps from {:?} to {:?}",
+                pair[0], pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_perimeter_goes_clockwise_from_the_top() {
+        let rect = Rect::new(10, 5, 6, 4);
+        let cells = perimeter(rect);
+        assert_eq!(cells.len(), 2 * 6 + 2 * 2);
+        assert_eq!(cells[0], Position::new(13, 5));
+        // Top right, bottom right, bottom left and top left corners in turn
+        assert_eq!(cells[2], Position::new(15, 5));
+        assert_eq!(cells[5], Position::new(15, 8));
+        assert_eq!(cells[10], Position::new(10, 8));
+        assert_eq!(cells[13], Position::new(10, 5));
+        assert_eq!(cells[15], Position::new(12, 5));
+        assert_walks_the_edge(rect);
+    }
+
+    #[test]
+    fn test_perimeter_sizes() {
+        for (width, height) in [(16, 16), (40, 10), (7, 3), (2, 2), (3, 9)] {
+            let rect = Rect::new(1, 2, width, height);
+            let cells = perimeter(rect);
+            assert_eq!(cells.len(), usize::from(2 * width + 2 * height - 4));
+            assert_walks_the_edge(rect);
+        }
+    }
+
+    #[test]
+    fn test_perimeter_of_thin_rects() {
+        // A single row or column is walked once, not there and back
+        let row = Rect::new(0, 0, 5, 1);
+        assert_eq!(perimeter(row).len(), 5);
+        assert_eq!(perimeter(row)[0], Position::new(2, 0));
+        assert_walks_the_edge(Rect::new(0, 0, 1, 4));
+        assert_eq!(perimeter(Rect::new(0, 0, 1, 4)).len(), 4);
+        assert_eq!(perimeter(Rect::new(3, 3, 1, 1)), vec![Position::new(3, 3)]);
+        assert!(perimeter(Rect::new(0, 0, 0, 5)).is_empty());
+        assert!(perimeter(Rect::new(0, 0, 5, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_ticks_spread_evenly() {
+        // Exactly 60 cells: a tick on each
+        let ticks: Vec<usize> = (0..TICKS).map(|tick| tick_index(tick, 60)).collect();
+        assert_eq!(ticks, (0..60).collect::<Vec<_>>());
+
+        // More cells: gaps of two or three, never past the end
+        let ticks: Vec<usize> = (0..TICKS).map(|tick| tick_index(tick, 150)).collect();
+        assert_eq!((ticks[0], ticks[30], ticks[59]), (0, 75, 147));
+        assert!(
+            ticks
+                .windows(2)
+                .all(|pair| (2..=3).contains(&(pair[1] - pair[0])))
+        );
+
+        // Fewer cells: several seconds share a cell, each cell gets some
+        let ticks: HashSet<usize> = (0..TICKS).map(|tick| tick_index(tick, 16)).collect();
+        assert_eq!(ticks, (0..16).collect());
+
+        // Wrapping past the minute and an empty ring
+        assert_eq!(tick_index(61, 60), 1);
+        assert_eq!(tick_index(45, 0), 0);
+    }
+
+    #[test]
+    fn test_render_ring() {
+        let area = Rect::new(0, 0, 16, 16);
+        let mut buf = Buffer::empty(area);
+        render(&mut buf, area, 15, Color::Cyan, Color::DarkGray);
+
+        // Fifteen seconds is a quarter of the way round, halfway down the right
+        assert_eq!(perimeter(area)[15], Position::new(15, 8));
+        assert_eq!(buf[(15, 8)].symbol(), "●");
+        assert_eq!(buf[(0, 0)].symbol(), "┌");
+        assert_eq!(buf[(15, 0)].symbol(), "┐");
+        assert_eq!(buf[(0, 15)].symbol(), "└");
+        assert_eq!(buf[(15, 15)].symbol(), "┘");
+        assert_eq!(buf[(4, 15)].symbol(), "─");
+        assert_eq!(buf[(0, 4)].symbol(), "│");
+        // Elapsed ticks in one color, the rest in the other, the inside untouched
+        assert_eq!(buf[(15, 0)].fg, Color::Cyan);
+        assert_eq!(buf[(15, 8)].fg, Color::Cyan);
+        assert_eq!(buf[(15, 9)].fg, Color::DarkGray);
+        assert_eq!(buf[(8, 0)].fg, Color::Cyan);
+        assert_eq!(buf[(7, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(5, 5)], Buffer::empty(area)[(5, 5)]);
+    }
+
+    #[test]
+    fn test_render_clips_to_the_buffer() {
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        render(
+            &mut buf,
+            Rect::new(2, 1, 20, 20),
+            0,
+            Color::Cyan,
+            Color::Gray,
+        );
+        assert_eq!(buf[(2, 1)].symbol(), "┌");
+        // The ring shrinks to the part on screen, keeping its corners
+        assert_eq!(buf[(7, 2)].symbol(), "┘");
+    }
+}
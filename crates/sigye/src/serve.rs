@@ -0,0 +1,266 @@
+//! Serving the rendered clock as plain text to other programs.
+//!
+//! Every second the frame is written to a path given with `--serve-text`. A FIFO
+//! or Unix socket gets a stream of frames, each sent as its length in bytes on a
+//! line of its own followed by the frame text; a regular file is replaced with
+//! the latest frame in the same form. Frames are written on a background thread
+//! and dropped while it is busy, so a slow consumer never holds up the UI.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+use crate::worker::{JOIN_TIMEOUT, Worker};
+
+/// Sends rendered frames to the serve path from a background thread.
+#[derive(Debug)]
+pub struct FrameServer {
+    /// Frames waiting for the writer; holds at most one.
+    frames: Option<SyncSender<String>>,
+    /// Writer thread, stopped once the frames channel closes.
+    worker: Option<Worker>,
+    /// Timestamp (seconds) of the last frame handed to the writer.
+    last_sent: Option<i64>,
+}
+
+impl FrameServer {
+    /// Start writing frames to `path`.
+    pub fn start(path: PathBuf) -> Self {
+        let (frames, frames_rx) = mpsc::sync_channel(1);
+        let worker = Worker::spawn(move |_| write_frames(&path, frames_rx));
+        Self {
+            frames: Some(frames),
+            worker: Some(worker),
+            last_sent: None,
+        }
+    }
+
+    /// Check if the frame for the second `timestamp` is still to be sent.
+    pub fn is_due(&self, timestamp: i64) -> bool {
+        self.last_sent != Some(timestamp)
+    }
+
+    /// Queue the frame for the second `timestamp`, dropping it if the writer is busy.
+    pub fn send(&mut self, timestamp: i64, frame: String) {
+        self.last_sent = Some(timestamp);
+        if let Some(frames) = &self.frames
+            && let Err(TrySendError::Disconnected(_)) = frames.try_send(frame)
+        {
+            self.frames = None;
+        }
+    }
+}
+
+impl Drop for FrameServer {
+    fn drop(&mut self) {
+        // Closing the channel ends the writer loop
+        self.frames = None;
+        if let Some(worker) = self.worker.take() {
+            worker.stop(JOIN_TIMEOUT);
+        }
+    }
+}
+
+/// Plain text of a rendered buffer, one line per row with trailing spaces trimmed.
+pub fn buffer_text(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // Cells covered by a wide character before them hold a placeholder
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buf[(x, y)].symbol();
+            skip = symbol.width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Frame with its length prefix, as written to the serve path.
+pub fn encode(frame: &str) -> String {
+    format!("{}\n{frame}", frame.len())
+}
+
+/// Where frames are written.
+enum Target {
+    /// A FIFO or socket that frames are streamed to.
+    Stream(Box<dyn Write>),
+    /// A regular file replaced with each frame.
+    File,
+}
+
+/// Open the serve path: streams for FIFOs and sockets, otherwise a plain file.
+///
+/// Opening a FIFO waits until a reader opens the other end.
+fn open_target(path: &Path) -> io::Result<Target> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        use std::os::unix::net::UnixStream;
+
+        if let Ok(metadata) = fs::metadata(path) {
+            let file_type = metadata.file_type();
+            if file_type.is_socket() {
+                return Ok(Target::Stream(Box::new(UnixStream::connect(path)?)));
+            }
+            if file_type.is_fifo() {
+                let fifo = fs::OpenOptions::new().write(true).open(path)?;
+                return Ok(Target::Stream(Box::new(fifo)));
+            }
+        }
+    }
+    Ok(Target::File)
+}
+
+/// Replace the file at `path` with `data`, so readers never see a partial frame.
+fn replace_file(path: &Path, data: &str) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{name}.tmp"));
+    fs::write(&temp_path, data)?;
+    fs::rename(temp_path, path)
+}
+
+/// Write frames until the channel closes, reopening the target after errors.
+fn write_frames(path: &Path, frames: Receiver<String>) {
+    let mut target = None;
+    for frame in frames {
+        if target.is_none() {
+            target = open_target(path).ok();
+        }
+        let data = encode(&frame);
+        let written = match &mut target {
+            Some(Target::Stream(stream)) => stream
+                .write_all(data.as_bytes())
+                .and_then(|()| stream.flush()),
+            Some(Target::File) => replace_file(path, &data),
+            None => continue,
+        };
+        // Reopen on the next frame, e.g. once a new reader is listening
+        if written.is_err() {
+            target = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::thread;
+    use std::time::Duration;
+
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sigye-serve-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    /// Read one length-prefixed frame.
+    fn read_frame(reader: &mut impl BufRead) -> String {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let len: usize = header.trim_end().parse().unwrap();
+        let mut frame = vec![0; len];
+        reader.read_exact(&mut frame).unwrap();
+        String::from_utf8(frame).unwrap()
+    }
+
+    #[test]
+    fn test_buffer_text() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 3));
+        buf.set_string(1, 0, "12:30", Style::default());
+        buf.set_string(0, 2, "☀ 한", Style::default());
+        assert_eq!(buffer_text(&buf), " 12:30\n\n☀ 한\n");
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("ab\n\n"), "4\nab\n\n");
+        assert_eq!(encode("한\n"), "4\n한\n");
+    }
+
+    #[test]
+    fn test_file_holds_latest_frame() {
+        let path = temp_path("frame.txt");
+        let mut server = FrameServer::start(path.clone());
+        server.send(1, "first\n".to_string());
+        drop(server);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "6\nfirst\n");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_is_due_once_per_second() {
+        let path = temp_path("due.txt");
+        let mut server = FrameServer::start(path.clone());
+        assert!(server.is_due(10));
+        server.send(10, "frame\n".to_string());
+        assert!(!server.is_due(10));
+        assert!(server.is_due(11));
+        drop(server);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_socket_reader_splits_frames() {
+        use std::os::unix::net::UnixListener;
+
+        let path = temp_path("frames.sock");
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // Frames with blank lines, so only the length prefix can split them
+        let sent: Vec<String> = (0..3).map(|i| format!("frame {i}\n\n  {i}\n\n")).collect();
+        let expected = sent.clone();
+        let reader = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            (0..expected.len())
+                .map(|_| read_frame(&mut reader))
+                .collect::<Vec<_>>()
+        });
+
+        let mut server = FrameServer::start(path.clone());
+        for (second, frame) in sent.iter().enumerate() {
+            server.send(second as i64, frame.clone());
+            // Give the writer time to take each frame; a busy writer drops them
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        assert_eq!(reader.join().unwrap(), sent);
+        drop(server);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_slow_consumer_drops_frames() {
+        let (frames, frames_rx) = mpsc::sync_channel(1);
+        let mut server = FrameServer {
+            frames: Some(frames),
+            worker: None,
+            last_sent: None,
+        };
+        // Nothing reads the channel, so only the first frame is kept
+        for second in 0..5 {
+            server.send(second, format!("{second}\n"));
+        }
+        assert_eq!(frames_rx.try_iter().collect::<Vec<_>>(), ["0\n"]);
+    }
+}
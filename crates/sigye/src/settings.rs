@@ -7,7 +7,9 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
-use sigye_core::{AnimationSpeed, AnimationStyle, BackgroundStyle, ColorTheme, TimeFormat};
+use sigye_core::{
+    Accessibility, AnimationSpeed, AnimationStyle, BackgroundStyle, ColorTheme, TimeFormat,
+};
 
 /// The settings field currently being edited.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,6 +22,7 @@ pub enum SettingsField {
     Speed,
     Background,
     ColonBlink,
+    Accessibility,
 }
 
 impl SettingsField {
@@ -32,24 +35,45 @@ impl SettingsField {
             Self::Animation => Self::Speed,
             Self::Speed => Self::Background,
             Self::Background => Self::ColonBlink,
-            Self::ColonBlink => Self::Font,
+            Self::ColonBlink => Self::Accessibility,
+            Self::Accessibility => Self::Font,
         }
     }
 
     /// Move to the previous field.
     pub fn prev(self) -> Self {
         match self {
-            Self::Font => Self::ColonBlink,
+            Self::Font => Self::Accessibility,
             Self::Color => Self::Font,
             Self::TimeFormat => Self::Color,
             Self::Animation => Self::TimeFormat,
             Self::Speed => Self::Animation,
             Self::Background => Self::Speed,
             Self::ColonBlink => Self::Background,
+            Self::Accessibility => Self::ColonBlink,
         }
     }
 }
 
+/// Settings the dialog edits besides the font.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplaySettings {
+    /// Color theme of the digits.
+    pub color_theme: ColorTheme,
+    /// 12 or 24 hour time.
+    pub time_format: TimeFormat,
+    /// Animation of the digits.
+    pub animation_style: AnimationStyle,
+    /// Speed of the animations.
+    pub animation_speed: AnimationSpeed,
+    /// Whether the colons blink.
+    pub colon_blink: bool,
+    /// Background style.
+    pub background_style: BackgroundStyle,
+    /// Accessibility mode.
+    pub accessibility: Accessibility,
+}
+
 /// Settings dialog state.
 #[derive(Debug)]
 pub struct SettingsDialog {
@@ -73,6 +97,8 @@ pub struct SettingsDialog {
     pub background_style: BackgroundStyle,
     /// Current colon blink setting.
     pub colon_blink: bool,
+    /// Current accessibility mode.
+    pub accessibility: Accessibility,
     /// Original font index (for cancel/revert).
     original_font_index: usize,
     /// Original color theme (for cancel/revert).
@@ -87,6 +113,8 @@ pub struct SettingsDialog {
     original_background_style: BackgroundStyle,
     /// Original colon blink (for cancel/revert).
     original_colon_blink: bool,
+    /// Original accessibility mode (for cancel/revert).
+    original_accessibility: Accessibility,
 }
 
 impl SettingsDialog {
@@ -103,6 +131,7 @@ impl SettingsDialog {
             animation_speed: AnimationSpeed::default(),
             background_style: BackgroundStyle::default(),
             colon_blink: false,
+            accessibility: Accessibility::default(),
             original_font_index: 0,
             original_color_theme: ColorTheme::default(),
             original_time_format: TimeFormat::default(),
@@ -110,20 +139,21 @@ impl SettingsDialog {
             original_animation_speed: AnimationSpeed::default(),
             original_background_style: BackgroundStyle::default(),
             original_colon_blink: false,
+            original_accessibility: Accessibility::default(),
         }
     }
 
     /// Open dialog with current settings.
-    pub fn open(
-        &mut self,
-        font_name: &str,
-        color_theme: ColorTheme,
-        time_format: TimeFormat,
-        animation_style: AnimationStyle,
-        animation_speed: AnimationSpeed,
-        colon_blink: bool,
-        background_style: BackgroundStyle,
-    ) {
+    pub fn open(&mut self, font_name: &str, settings: DisplaySettings) {
+        let DisplaySettings {
+            color_theme,
+            time_format,
+            animation_style,
+            animation_speed,
+            colon_blink,
+            background_style,
+            accessibility,
+        } = settings;
         self.visible = true;
         self.selected_field = SettingsField::default();
         self.color_theme = color_theme;
@@ -132,6 +162,7 @@ impl SettingsDialog {
         self.animation_speed = animation_speed;
         self.background_style = background_style;
         self.colon_blink = colon_blink;
+        self.accessibility = accessibility;
 
         // Find font index
         self.font_index = self
@@ -148,6 +179,7 @@ impl SettingsDialog {
         self.original_animation_speed = animation_speed;
         self.original_background_style = background_style;
         self.original_colon_blink = colon_blink;
+        self.original_accessibility = accessibility;
     }
 
     /// Close without saving.
@@ -193,6 +225,11 @@ impl SettingsDialog {
         self.original_background_style
     }
 
+    /// Get original accessibility mode (for reverting on cancel).
+    pub fn original_accessibility(&self) -> Accessibility {
+        self.original_accessibility
+    }
+
     /// Move to next field.
     pub fn next_field(&mut self) {
         self.selected_field = self.selected_field.next();
@@ -229,6 +266,9 @@ impl SettingsDialog {
             SettingsField::ColonBlink => {
                 self.colon_blink = !self.colon_blink;
             }
+            SettingsField::Accessibility => {
+                self.accessibility = self.accessibility.next();
+            }
         }
     }
 
@@ -262,6 +302,9 @@ impl SettingsDialog {
             SettingsField::ColonBlink => {
                 self.colon_blink = !self.colon_blink;
             }
+            SettingsField::Accessibility => {
+                self.accessibility = self.accessibility.prev();
+            }
         }
     }
 
@@ -281,7 +324,7 @@ impl SettingsDialog {
 
         // Calculate centered dialog area
         let dialog_width = 40.min(area.width.saturating_sub(4));
-        let dialog_height = 19.min(area.height.saturating_sub(2));
+        let dialog_height = 21.min(area.height.saturating_sub(2));
 
         let dialog_x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let dialog_y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
@@ -317,8 +360,10 @@ impl SettingsDialog {
             Constraint::Length(1), // 11: Background
             Constraint::Length(1), // 12: Spacing
             Constraint::Length(1), // 13: Colon Blink
-            Constraint::Fill(1),   // 14: Bottom space
-            Constraint::Length(1), // 15: Help text
+            Constraint::Length(1), // 14: Spacing
+            Constraint::Length(1), // 15: Accessibility
+            Constraint::Fill(1),   // 16: Bottom space
+            Constraint::Length(1), // 17: Help text
         ])
         .split(inner_area);
 
@@ -412,6 +457,18 @@ impl SettingsDialog {
             chunks[13],
         );
 
+        // Render accessibility field
+        let accessibility_line = self.render_field(
+            "Accessibility",
+            self.accessibility.display_name(),
+            self.selected_field == SettingsField::Accessibility,
+            accent_color,
+        );
+        frame.render_widget(
+            Paragraph::new(accessibility_line).alignment(Alignment::Center),
+            chunks[15],
+        );
+
         // Render help text
         let help = Line::from(vec![
             Span::styled("↑↓", Style::default().fg(accent_color).bold()),
@@ -425,7 +482,7 @@ impl SettingsDialog {
         ]);
         frame.render_widget(
             Paragraph::new(help).alignment(Alignment::Center),
-            chunks[15],
+            chunks[17],
         );
     }
 
@@ -0,0 +1,15 @@
+//! Running commands from the config through the system shell.
+
+use std::process::{Command, Stdio};
+
+/// A command running `script` with `sh -c`, or `cmd /C` on Windows, without input.
+pub fn command(script: &str) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut command = Command::new(shell);
+    command.args([flag, script]).stdin(Stdio::null());
+    command
+}
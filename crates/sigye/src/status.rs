@@ -0,0 +1,253 @@
+//! Health of the subsystems the display depends on, like the weather fetches.
+//!
+//! Each subsystem reports whether it works, limps along or has failed, and the
+//! app marks the worst of them in a corner so a background that looks wrong
+//! has an explanation. The details are listed in the help overlay.
+
+use std::collections::BTreeMap;
+
+/// A part of the app that can fail without stopping the clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Subsystem {
+    /// The config file and the values in it.
+    Config,
+    /// Weather fetches.
+    Weather,
+    /// Detecting the location from the IP address.
+    Geolocation,
+    /// System metrics for the reactive backgrounds.
+    Metrics,
+    /// The command run on each chime.
+    Chime,
+    /// Reading the calendar files.
+    Calendar,
+    /// The commands and notifications for finished timers and alarms.
+    Hooks,
+}
+
+impl Subsystem {
+    /// Get the name shown in the status marker and the help overlay.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Subsystem::Config => "config",
+            Subsystem::Weather => "weather",
+            Subsystem::Geolocation => "location",
+            Subsystem::Metrics => "metrics",
+            Subsystem::Chime => "chime",
+            Subsystem::Calendar => "calendar",
+            Subsystem::Hooks => "hooks",
+        }
+    }
+}
+
+/// How well a subsystem works.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Status {
+    /// Working normally.
+    #[default]
+    Ok,
+    /// Working with a fallback, e.g. showing old data, for this reason.
+    Degraded(String),
+    /// Not working, for this reason.
+    Failed(String),
+}
+
+impl Status {
+    /// Rank for picking the worst status: higher is worse.
+    fn severity(&self) -> u8 {
+        match self {
+            Status::Ok => 0,
+            Status::Degraded(_) => 1,
+            Status::Failed(_) => 2,
+        }
+    }
+
+    /// Check if the subsystem works normally.
+    pub fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+
+    /// Get the label shown before the reason, e.g. "failed".
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Degraded(_) => "degraded",
+            Status::Failed(_) => "failed",
+        }
+    }
+
+    /// Get the reason the subsystem isn't working normally, if it isn't.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Status::Ok => None,
+            Status::Degraded(reason) | Status::Failed(reason) => Some(reason),
+        }
+    }
+}
+
+/// Latest status of each subsystem; subsystems that never reported are fine.
+#[derive(Debug, Default)]
+pub struct StatusRegistry {
+    /// Subsystems that aren't working normally.
+    problems: BTreeMap<Subsystem, Status>,
+}
+
+impl StatusRegistry {
+    /// Record the latest status of a subsystem, replacing its previous one.
+    pub fn report(&mut self, subsystem: Subsystem, status: Status) {
+        if status.is_ok() {
+            self.problems.remove(&subsystem);
+        } else {
+            self.problems.insert(subsystem, status);
+        }
+    }
+
+    /// Check if every subsystem works normally.
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Subsystems that aren't working normally, in a fixed order.
+    pub fn problems(&self) -> impl Iterator<Item = (Subsystem, &Status)> {
+        self.problems
+            .iter()
+            .map(|(&subsystem, status)| (subsystem, status))
+    }
+
+    /// The subsystem in the worst state, the first in order among equals,
+    /// `None` when everything works.
+    pub fn worst(&self) -> Option<(Subsystem, &Status)> {
+        // The last of the maximums is picked, so go through them backwards
+        self.problems
+            .iter()
+            .rev()
+            .max_by_key(|(_, status)| status.severity())
+            .map(|(&subsystem, status)| (subsystem, status))
+    }
+
+    /// Short marker for the corner, e.g. " ! weather " or " ! weather +1 ",
+    /// `None` when everything works.
+    pub fn marker(&self) -> Option<String> {
+        let (subsystem, _) = self.worst()?;
+        let others = self.problems.len() - 1;
+        Some(if others > 0 {
+            format!(" ! {} +{others} ", subsystem.display_name())
+        } else {
+            format!(" ! {} ", subsystem.display_name())
+        })
+    }
+}
+
+/// Status of the config: failed when the file couldn't be used at all,
+/// degraded when some values in it were ignored.
+pub fn config_status(error: Option<&str>, warnings: &[String]) -> Status {
+    if let Some(error) = error {
+        return Status::Failed(format!("{error}; using the defaults"));
+    }
+    match warnings {
+        [] => Status::Ok,
+        [warning] => Status::Degraded(warning.clone()),
+        [first, rest @ ..] => Status::Degraded(format!("{first} (and {} more)", rest.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degraded(reason: &str) -> Status {
+        Status::Degraded(reason.to_string())
+    }
+
+    fn failed(reason: &str) -> Status {
+        Status::Failed(reason.to_string())
+    }
+
+    #[test]
+    fn test_nothing_reported_is_ok() {
+        let registry = StatusRegistry::default();
+        assert!(registry.is_ok());
+        assert_eq!(registry.worst(), None);
+        assert_eq!(registry.marker(), None);
+    }
+
+    #[test]
+    fn test_worst_status_wins() {
+        let mut registry = StatusRegistry::default();
+        registry.report(Subsystem::Config, degraded("ignored an alarm"));
+        registry.report(Subsystem::Metrics, failed("command not found"));
+        registry.report(Subsystem::Weather, degraded("timed out"));
+        assert_eq!(
+            registry.worst(),
+            Some((Subsystem::Metrics, &failed("command not found")))
+        );
+        assert_eq!(registry.marker().as_deref(), Some(" ! metrics +2 "));
+
+        // Among equally bad ones the first subsystem wins
+        registry.report(Subsystem::Metrics, Status::Ok);
+        assert_eq!(
+            registry.worst(),
+            Some((Subsystem::Config, &degraded("ignored an alarm")))
+        );
+        registry.report(Subsystem::Weather, failed("timed out"));
+        assert_eq!(registry.worst().map(|(s, _)| s), Some(Subsystem::Weather));
+    }
+
+    #[test]
+    fn test_reports_replace_and_recover() {
+        let mut registry = StatusRegistry::default();
+        registry.report(Subsystem::Weather, failed("timed out"));
+        registry.report(Subsystem::Weather, degraded("timed out"));
+        assert_eq!(registry.problems().count(), 1);
+        assert_eq!(registry.marker().as_deref(), Some(" ! weather "));
+
+        registry.report(Subsystem::Weather, Status::Ok);
+        assert!(registry.is_ok());
+        assert_eq!(registry.marker(), None);
+    }
+
+    #[test]
+    fn test_problems_in_fixed_order() {
+        let mut registry = StatusRegistry::default();
+        registry.report(Subsystem::Metrics, degraded("stale"));
+        registry.report(Subsystem::Geolocation, failed("offline"));
+        registry.report(Subsystem::Config, degraded("bad alarm"));
+        let order: Vec<Subsystem> = registry.problems().map(|(s, _)| s).collect();
+        assert_eq!(
+            order,
+            [
+                Subsystem::Config,
+                Subsystem::Geolocation,
+                Subsystem::Metrics
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_labels() {
+        assert!(Status::default().is_ok());
+        assert_eq!(Status::Ok.reason(), None);
+        assert_eq!(degraded("old data").label(), "degraded");
+        assert_eq!(failed("offline").reason(), Some("offline"));
+    }
+
+    #[test]
+    fn test_config_status() {
+        assert_eq!(config_status(None, &[]), Status::Ok);
+        assert_eq!(
+            config_status(Some("Failed to parse config file: bad"), &[]),
+            failed("Failed to parse config file: bad; using the defaults")
+        );
+        let warnings = vec!["Ignoring alarm".to_string()];
+        assert_eq!(config_status(None, &warnings), degraded("Ignoring alarm"));
+        let warnings = vec![
+            "Ignoring alarm".to_string(),
+            "Ignoring layer".to_string(),
+            "Ignoring override".to_string(),
+        ];
+        assert_eq!(
+            config_status(None, &warnings),
+            degraded("Ignoring alarm (and 2 more)")
+        );
+    }
+}
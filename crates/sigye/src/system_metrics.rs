@@ -1,11 +1,34 @@
 //! System resource monitoring for reactive backgrounds.
+//!
+//! Metrics come from this machine by default, or as JSON from a command or a
+//! file, so the backgrounds can follow a remote server.
 
-use std::sync::{Arc, RwLock};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use sigye_core::SystemMetrics;
-use sysinfo::{Networks, System};
+use serde::Deserialize;
+use sigye_config::Config;
+use sigye_core::{MetricsProvider, SystemMetrics, history::MetricsHistory};
+use sysinfo::{MINIMUM_CPU_UPDATE_INTERVAL, Networks, System};
+
+use crate::shell;
+use crate::status::Status;
+use crate::worker::{JOIN_TIMEOUT, Wakeup, Worker};
+
+/// How long a metrics command may run before it is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often a running metrics command is checked for having exited.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Directory where Linux exposes power supplies.
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
 
 /// Shared state for tracking max observed values (for normalization).
 #[allow(dead_code)]
@@ -17,171 +40,647 @@ struct MaxValues {
     disk_write: u64,
 }
 
-/// System monitor that polls resource usage in a background thread.
-#[derive(Debug)]
-pub struct SystemMonitor {
-    /// Shared metrics updated by the background thread.
-    metrics: Arc<RwLock<SystemMetrics>>,
-    /// Cached metrics for when lock is contended.
-    cached_metrics: Arc<RwLock<SystemMetrics>>,
-    /// Flag to signal thread termination.
-    running: Arc<RwLock<bool>>,
+/// The two most recent metric samples, used to interpolate between polls.
+#[derive(Debug, Clone)]
+struct MetricSamples {
+    /// Time between samples.
+    interval: Duration,
+    /// Sample before the latest one.
+    previous: Option<(Instant, SystemMetrics)>,
+    /// Most recent sample.
+    latest: Option<(Instant, SystemMetrics)>,
+    /// Combined network throughput of the recent samples.
+    network_history: MetricsHistory,
+    /// CPU usage of the recent samples.
+    cpu_history: MetricsHistory,
 }
 
-impl SystemMonitor {
-    /// Create a new system monitor.
-    pub fn new() -> Self {
+impl MetricSamples {
+    /// Create empty samples taken every `interval`.
+    fn new(interval: Duration) -> Self {
         Self {
-            metrics: Arc::new(RwLock::new(SystemMetrics::default())),
-            cached_metrics: Arc::new(RwLock::new(SystemMetrics::default())),
-            running: Arc::new(RwLock::new(false)),
+            interval,
+            previous: None,
+            latest: None,
+            network_history: MetricsHistory::default(),
+            cpu_history: MetricsHistory::default(),
         }
     }
 
-    /// Start the background monitoring thread.
-    pub fn start(&self) {
-        // Set running flag
-        if let Ok(mut running) = self.running.write() {
-            if *running {
-                return; // Already running
+    /// Record a new sample, keeping the previous one for interpolation.
+    fn push(&mut self, at: Instant, metrics: SystemMetrics) {
+        self.network_history
+            .push((metrics.network_rx_rate + metrics.network_tx_rate) / 2.0);
+        self.cpu_history.push(metrics.cpu_usage);
+        self.previous = self.latest.replace((at, metrics));
+    }
+
+    /// Interpolate from the previous to the latest sample.
+    ///
+    /// Progress is the time since the latest sample as a fraction of the sampling
+    /// interval, so values arrive at the latest sample one interval after it was
+    /// taken and hold there once samples go stale. The gap between the samples
+    /// isn't used, so the first sample after a pause doesn't crawl in.
+    fn interpolate(&self, now: Instant) -> SystemMetrics {
+        match (&self.previous, &self.latest) {
+            (Some((_, prev)), Some((latest_at, latest))) => {
+                let interval = self.interval.as_secs_f32();
+                let t = if interval > 0.0 {
+                    now.saturating_duration_since(*latest_at).as_secs_f32() / interval
+                } else {
+                    1.0
+                };
+                lerp_metrics(prev, latest, t)
             }
-            *running = true;
+            (None, Some((_, latest))) => latest.clone(),
+            _ => SystemMetrics::default(),
         }
+    }
+}
 
-        let metrics = self.metrics.clone();
-        let cached = self.cached_metrics.clone();
-        let running = self.running.clone();
+/// Linearly interpolate every metric from `from` to `to`, with `t` clamped to 0.0 - 1.0.
+fn lerp_metrics(from: &SystemMetrics, to: &SystemMetrics, t: f32) -> SystemMetrics {
+    let t = t.clamp(0.0, 1.0);
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
 
-        thread::spawn(move || {
-            let mut sys = System::new_all();
-            let mut networks = Networks::new_with_refreshed_list();
-            let mut max_values = MaxValues::default();
+    SystemMetrics {
+        cpu_usage: lerp(from.cpu_usage, to.cpu_usage),
+        memory_usage: lerp(from.memory_usage, to.memory_usage),
+        network_rx_rate: lerp(from.network_rx_rate, to.network_rx_rate),
+        network_tx_rate: lerp(from.network_tx_rate, to.network_tx_rate),
+        disk_read_rate: lerp(from.disk_read_rate, to.disk_read_rate),
+        disk_write_rate: lerp(from.disk_write_rate, to.disk_write_rate),
+        battery_level: match (from.battery_level, to.battery_level) {
+            (Some(a), Some(b)) => Some(lerp(a, b)),
+            (_, level) => level,
+        },
+        battery_charging: to.battery_charging,
+    }
+}
 
-            // Initial refresh to get baseline
-            sys.refresh_all();
-            thread::sleep(Duration::from_millis(500));
+/// Minimum network rate the normalization scales to, so idle links stay calm.
+const MIN_NETWORK_RATE: u64 = 1_000_000; // 1 MB/s
 
-            // Track previous network bytes for rate calculation
-            let mut prev_rx: u64 = networks.values().map(|n| n.received()).sum();
-            let mut prev_tx: u64 = networks.values().map(|n| n.transmitted()).sum();
-            let mut prev_time = Instant::now();
+/// Time between samples unless configured otherwise.
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
 
-            loop {
-                // Check if we should stop
-                if let Ok(is_running) = running.read()
-                    && !*is_running
-                {
-                    break;
+/// Shortest and longest configurable time between samples, in seconds.
+const SAMPLE_INTERVAL_RANGE: (f32, f32) = (0.5, 5.0);
+
+/// How long a paused monitor sleeps before checking whether it was resumed,
+/// in case a wake-up was missed.
+const PARKED_WAIT: Duration = Duration::from_secs(60);
+
+/// Time between samples for a configured number of seconds, kept within
+/// [`SAMPLE_INTERVAL_RANGE`].
+pub fn sample_interval(secs: f32) -> Duration {
+    if secs.is_nan() {
+        return DEFAULT_SAMPLE_INTERVAL;
+    }
+    let (min, max) = SAMPLE_INTERVAL_RANGE;
+    Duration::from_secs_f32(secs.clamp(min, max))
+}
+
+/// Something that can be sampled for system metrics once per interval.
+pub trait MetricsSource: Send {
+    /// Take a sample, with every rate normalized to 0.0 - 1.0.
+    fn sample(&mut self) -> SystemMetrics;
+
+    /// Take fresh baseline readings after a pause, so the first rates after
+    /// it don't count everything since the last sample.
+    fn resume(&mut self) {}
+
+    /// How well the last sample went.
+    fn status(&self) -> Status {
+        Status::Ok
+    }
+}
+
+/// Where a system monitor reads its metrics from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SourceSpec {
+    /// This machine, read with sysinfo.
+    #[default]
+    Local,
+    /// JSON printed by a shell command.
+    Command(String),
+    /// JSON read from a file.
+    File(PathBuf),
+}
+
+impl SourceSpec {
+    /// Pick the configured provider, with its command or file path.
+    pub fn from_config(config: &Config) -> Self {
+        match config.metrics_provider {
+            MetricsProvider::Local => Self::Local,
+            MetricsProvider::Command => Self::Command(config.metrics_command.clone()),
+            MetricsProvider::File => Self::File(PathBuf::from(&config.metrics_file)),
+        }
+    }
+
+    /// Open the source, ready for its first sample.
+    fn open(&self) -> Box<dyn MetricsSource> {
+        match self {
+            Self::Local => Box::new(SysinfoSource::new()),
+            Self::Command(command) => Box::new(JsonSource::new(CommandReader::new(command))),
+            Self::File(path) => Box::new(JsonSource::new(FileReader(path.clone()))),
+        }
+    }
+}
+
+/// Metrics of this machine, read with sysinfo.
+struct SysinfoSource {
+    sys: System,
+    networks: Networks,
+    max_values: MaxValues,
+    /// Battery `uevent` files, looked up once.
+    batteries: Vec<PathBuf>,
+    /// Network byte counters and time of the previous sample, for rates.
+    prev_rx: u64,
+    prev_tx: u64,
+    prev_time: Instant,
+}
+
+impl SysinfoSource {
+    /// Take the baseline readings; CPU usage needs a second refresh
+    /// [`MINIMUM_CPU_UPDATE_INTERVAL`] later.
+    fn new() -> Self {
+        let mut sys = System::new_all();
+        let networks = Networks::new_with_refreshed_list();
+        sys.refresh_all();
+
+        Self {
+            sys,
+            // Look for batteries once; desktops without one never poll again
+            batteries: find_batteries(Path::new(POWER_SUPPLY_DIR)),
+            prev_rx: networks.values().map(|n| n.received()).sum(),
+            prev_tx: networks.values().map(|n| n.transmitted()).sum(),
+            prev_time: Instant::now(),
+            networks,
+            max_values: MaxValues::default(),
+        }
+    }
+}
+
+impl MetricsSource for SysinfoSource {
+    fn resume(&mut self) {
+        // CPU usage is measured from this refresh to the next sample's
+        self.sys.refresh_cpu_all();
+        self.networks.refresh(true);
+        self.prev_rx = self.networks.values().map(|n| n.received()).sum();
+        self.prev_tx = self.networks.values().map(|n| n.transmitted()).sum();
+        self.prev_time = Instant::now();
+    }
+
+    fn sample(&mut self) -> SystemMetrics {
+        let sys = &mut self.sys;
+        sys.refresh_cpu_all();
+        sys.refresh_memory();
+        self.networks.refresh(true);
+
+        let now = Instant::now();
+        let elapsed_secs = now.duration_since(self.prev_time).as_secs_f64().max(0.001);
+
+        // Calculate CPU usage (average across all cores)
+        let cpu_usage = sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>()
+            / sys.cpus().len().max(1) as f32
+            / 100.0;
+
+        // Calculate memory usage
+        let memory_usage = if sys.total_memory() > 0 {
+            sys.used_memory() as f32 / sys.total_memory() as f32
+        } else {
+            0.0
+        };
+
+        // Calculate network rates
+        let current_rx: u64 = self.networks.values().map(|n| n.received()).sum();
+        let current_tx: u64 = self.networks.values().map(|n| n.transmitted()).sum();
+
+        let rx_bytes_per_sec =
+            (current_rx.saturating_sub(self.prev_rx) as f64 / elapsed_secs) as u64;
+        let tx_bytes_per_sec =
+            (current_tx.saturating_sub(self.prev_tx) as f64 / elapsed_secs) as u64;
+
+        // Update max values for normalization (with minimum threshold)
+        let max_values = &mut self.max_values;
+        max_values.network_rx = max_values
+            .network_rx
+            .max(rx_bytes_per_sec)
+            .max(MIN_NETWORK_RATE);
+        max_values.network_tx = max_values
+            .network_tx
+            .max(tx_bytes_per_sec)
+            .max(MIN_NETWORK_RATE);
+
+        let network_rx_rate = rx_bytes_per_sec as f32 / max_values.network_rx as f32;
+        let network_tx_rate = tx_bytes_per_sec as f32 / max_values.network_tx as f32;
+
+        self.prev_rx = current_rx;
+        self.prev_tx = current_tx;
+        self.prev_time = now;
+
+        // Disk I/O - use process-level stats as approximation
+        // sysinfo doesn't provide system-wide disk I/O rates easily
+        // We'll use a simplified approach based on available data
+        let disk_read_rate = 0.0; // Placeholder - could be enhanced
+        let disk_write_rate = 0.0; // Placeholder - could be enhanced
+
+        // Battery info (Linux only, None without a battery)
+        let (battery_level, battery_charging) = get_battery_info(&self.batteries);
+
+        SystemMetrics {
+            cpu_usage: cpu_usage.clamp(0.0, 1.0),
+            memory_usage: memory_usage.clamp(0.0, 1.0),
+            network_rx_rate: network_rx_rate.clamp(0.0, 1.0),
+            network_tx_rate: network_tx_rate.clamp(0.0, 1.0),
+            disk_read_rate,
+            disk_write_rate,
+            battery_level,
+            battery_charging,
+        }
+    }
+}
+
+/// Metrics as JSON, e.g. `{"cpu_usage": 0.42, "memory_usage": 0.7}`.
+///
+/// Fields are named as in [`SystemMetrics`] and every one is optional: missing
+/// usages and rates are 0.0, and a missing battery means there is none. Values
+/// are fractions, clamped to 0.0 - 1.0.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetricsJson {
+    cpu_usage: f32,
+    memory_usage: f32,
+    network_rx_rate: f32,
+    network_tx_rate: f32,
+    disk_read_rate: f32,
+    disk_write_rate: f32,
+    battery_level: Option<f32>,
+    battery_charging: Option<bool>,
+}
+
+/// Parse metrics from JSON text, clamping every value to 0.0 - 1.0.
+fn parse_metrics_json(text: &str) -> Result<SystemMetrics, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| format!("invalid metrics JSON: {e}"))?;
+    if !value.is_object() {
+        return Err("metrics JSON must be an object".to_string());
+    }
+    let json: MetricsJson =
+        serde_json::from_value(value).map_err(|e| format!("invalid metrics JSON: {e}"))?;
+
+    let unit = |value: f32| value.clamp(0.0, 1.0);
+    Ok(SystemMetrics {
+        cpu_usage: unit(json.cpu_usage),
+        memory_usage: unit(json.memory_usage),
+        network_rx_rate: unit(json.network_rx_rate),
+        network_tx_rate: unit(json.network_tx_rate),
+        disk_read_rate: unit(json.disk_read_rate),
+        disk_write_rate: unit(json.disk_write_rate),
+        battery_level: json.battery_level.map(unit),
+        battery_charging: json.battery_charging,
+    })
+}
+
+/// Reads the JSON text of one sample.
+trait JsonReader: Send {
+    fn read(&mut self) -> Result<String, String>;
+}
+
+/// Runs a shell command and reads what it prints, killing it if it hangs.
+struct CommandReader {
+    /// Shell command printing the JSON.
+    command: String,
+    /// How long the command may run.
+    timeout: Duration,
+}
+
+impl CommandReader {
+    /// Run `command` with the default timeout.
+    fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            timeout: COMMAND_TIMEOUT,
+        }
+    }
+}
+
+impl JsonReader for CommandReader {
+    fn read(&mut self) -> Result<String, String> {
+        let mut child = shell::command(&self.command)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("failed to run metrics command: {e}"))?;
+        // Read on another thread, so a command printing a lot can't fill the
+        // pipe and block before it exits
+        let stdout = child.stdout.take();
+        let output = thread::spawn(move || -> std::io::Result<Vec<u8>> {
+            let mut text = Vec::new();
+            if let Some(mut stdout) = stdout {
+                stdout.read_to_end(&mut text)?;
+            }
+            Ok(text)
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => thread::sleep(COMMAND_POLL_INTERVAL),
+                Ok(None) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "metrics command timed out after {}s",
+                        self.timeout.as_secs_f32()
+                    ));
                 }
+                Err(e) => return Err(format!("failed to wait for metrics command: {e}")),
+            }
+        };
+        if !status.success() {
+            return Err(format!("metrics command failed: {status}"));
+        }
+        let text = output
+            .join()
+            .map_err(|_| "failed to read metrics command output".to_string())?
+            .map_err(|e| format!("failed to read metrics command output: {e}"))?;
+        String::from_utf8(text).map_err(|e| e.to_string())
+    }
+}
 
-                // Refresh system info
-                sys.refresh_cpu_all();
-                sys.refresh_memory();
-                networks.refresh(true);
+/// Reads a file written by another program.
+struct FileReader(PathBuf);
 
-                let now = Instant::now();
-                let elapsed_secs = now.duration_since(prev_time).as_secs_f64().max(0.001);
+impl JsonReader for FileReader {
+    fn read(&mut self) -> Result<String, String> {
+        fs::read_to_string(&self.0).map_err(|e| format!("{}: {e}", self.0.display()))
+    }
+}
 
-                // Calculate CPU usage (average across all cores)
-                let cpu_usage = sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>()
-                    / sys.cpus().len().max(1) as f32
-                    / 100.0;
+/// Metrics read as JSON, holding the last good sample while reads fail.
+struct JsonSource<R> {
+    reader: R,
+    /// Last sample read successfully, `None` before the first.
+    last_good: Option<SystemMetrics>,
+    /// Error of the last read, if it failed.
+    error: Option<String>,
+}
 
-                // Calculate memory usage
-                let memory_usage = if sys.total_memory() > 0 {
-                    sys.used_memory() as f32 / sys.total_memory() as f32
-                } else {
-                    0.0
-                };
+impl<R: JsonReader> JsonSource<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            last_good: None,
+            error: None,
+        }
+    }
+}
+
+impl<R: JsonReader> MetricsSource for JsonSource<R> {
+    fn sample(&mut self) -> SystemMetrics {
+        // A command that fails now and then or a file caught mid-write keeps
+        // the last reading rather than dropping to zero
+        match self
+            .reader
+            .read()
+            .and_then(|text| parse_metrics_json(&text))
+        {
+            Ok(metrics) => {
+                self.last_good = Some(metrics);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        self.last_good.clone().unwrap_or_default()
+    }
+
+    fn status(&self) -> Status {
+        match (&self.error, &self.last_good) {
+            (None, _) => Status::Ok,
+            (Some(error), Some(_)) => {
+                Status::Degraded(format!("{error}; showing the last reading"))
+            }
+            (Some(error), None) => Status::Failed(error.clone()),
+        }
+    }
+}
 
-                // Calculate network rates
-                let current_rx: u64 = networks.values().map(|n| n.received()).sum();
-                let current_tx: u64 = networks.values().map(|n| n.transmitted()).sum();
-
-                let rx_bytes_per_sec =
-                    (current_rx.saturating_sub(prev_rx) as f64 / elapsed_secs) as u64;
-                let tx_bytes_per_sec =
-                    (current_tx.saturating_sub(prev_tx) as f64 / elapsed_secs) as u64;
-
-                // Update max values for normalization (with minimum threshold)
-                const MIN_NETWORK_RATE: u64 = 1_000_000; // 1 MB/s minimum scale
-                max_values.network_rx = max_values
-                    .network_rx
-                    .max(rx_bytes_per_sec)
-                    .max(MIN_NETWORK_RATE);
-                max_values.network_tx = max_values
-                    .network_tx
-                    .max(tx_bytes_per_sec)
-                    .max(MIN_NETWORK_RATE);
-
-                let network_rx_rate = rx_bytes_per_sec as f32 / max_values.network_rx as f32;
-                let network_tx_rate = tx_bytes_per_sec as f32 / max_values.network_tx as f32;
-
-                prev_rx = current_rx;
-                prev_tx = current_tx;
-                prev_time = now;
-
-                // Disk I/O - use process-level stats as approximation
-                // sysinfo doesn't provide system-wide disk I/O rates easily
-                // We'll use a simplified approach based on available data
-                let disk_read_rate = 0.0; // Placeholder - could be enhanced
-                let disk_write_rate = 0.0; // Placeholder - could be enhanced
-
-                // Battery info (macOS/Linux support varies)
-                let (battery_level, battery_charging) = get_battery_info();
-
-                // Update metrics
-                let new_metrics = SystemMetrics {
-                    cpu_usage: cpu_usage.clamp(0.0, 1.0),
-                    memory_usage: memory_usage.clamp(0.0, 1.0),
-                    network_rx_rate: network_rx_rate.clamp(0.0, 1.0),
-                    network_tx_rate: network_tx_rate.clamp(0.0, 1.0),
-                    disk_read_rate,
-                    disk_write_rate,
-                    battery_level,
-                    battery_charging,
+/// What the polling thread did in one step.
+#[derive(Debug, Clone)]
+enum Poll {
+    /// Took a sample.
+    Sampled(SystemMetrics),
+    /// Took fresh baseline readings, with the first sample due after
+    /// [`MINIMUM_CPU_UPDATE_INTERVAL`].
+    Resumed,
+    /// Paused, sampling nothing.
+    Parked,
+}
+
+/// Samples a source while enabled, starting over from a fresh baseline after
+/// each pause (and at the start).
+struct Poller {
+    source: Box<dyn MetricsSource>,
+    /// Whether the last step sampled or resumed, so the baseline is current.
+    running: bool,
+}
+
+impl Poller {
+    fn new(source: Box<dyn MetricsSource>) -> Self {
+        Self {
+            source,
+            running: false,
+        }
+    }
+
+    /// Take the next step while the monitor is `enabled` or paused.
+    fn poll(&mut self, enabled: bool) -> Poll {
+        if !enabled {
+            self.running = false;
+            Poll::Parked
+        } else if !self.running {
+            self.source.resume();
+            self.running = true;
+            Poll::Resumed
+        } else {
+            Poll::Sampled(self.source.sample())
+        }
+    }
+}
+
+/// System monitor that polls resource usage in a background thread.
+///
+/// Disabled, the thread stays parked with the source open, so the
+/// normalization maxima survive until it is enabled again.
+#[derive(Debug)]
+pub struct SystemMonitor {
+    /// Where the metrics come from.
+    source: SourceSpec,
+    /// Time between samples.
+    interval: Duration,
+    /// Whether the thread samples or stays parked.
+    enabled: Arc<AtomicBool>,
+    /// Shared samples updated by the background thread.
+    metrics: Arc<RwLock<MetricSamples>>,
+    /// Cached samples for when lock is contended.
+    cached_metrics: Arc<RwLock<MetricSamples>>,
+    /// How well the last sample went, updated by the background thread.
+    status: Arc<RwLock<Status>>,
+    /// Polling thread, while running.
+    worker: Mutex<Option<Worker>>,
+}
+
+impl SystemMonitor {
+    /// Create a system monitor reading from `source` every `interval`.
+    pub fn new(source: SourceSpec, interval: Duration) -> Self {
+        Self {
+            source,
+            interval,
+            enabled: Arc::new(AtomicBool::new(true)),
+            metrics: Arc::new(RwLock::new(MetricSamples::new(interval))),
+            cached_metrics: Arc::new(RwLock::new(MetricSamples::new(interval))),
+            status: Arc::new(RwLock::new(Status::Ok)),
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Start the background monitoring thread.
+    pub fn start(&self) {
+        let Ok(mut worker) = self.worker.lock() else {
+            return;
+        };
+        if worker.is_some() {
+            return; // Already running
+        }
+
+        let metrics = self.metrics.clone();
+        let cached = self.cached_metrics.clone();
+        let status = self.status.clone();
+        let spec = self.source.clone();
+        let enabled = self.enabled.clone();
+        let interval = self.interval;
+
+        *worker = Some(Worker::spawn(move |stop| {
+            let mut poller = Poller::new(spec.open());
+
+            loop {
+                let new_metrics = match poller.poll(enabled.load(Ordering::Relaxed)) {
+                    Poll::Sampled(metrics) => metrics,
+                    // sysinfo's CPU usage needs two refreshes apart; the other
+                    // sources don't mind the short wait
+                    Poll::Resumed => {
+                        if stop.wait(MINIMUM_CPU_UPDATE_INTERVAL) {
+                            break;
+                        }
+                        continue;
+                    }
+                    // Sleep until enabled again, or stopped
+                    Poll::Parked => {
+                        if stop.wait_for_wakeup(PARKED_WAIT) == Wakeup::Stop {
+                            break;
+                        }
+                        continue;
+                    }
                 };
+                let now = Instant::now();
+                if let Ok(mut status) = status.write() {
+                    *status = poller.source.status();
+                }
 
-                // Update shared metrics
+                // Update shared samples
                 if let Ok(mut m) = metrics.write() {
-                    *m = new_metrics.clone();
+                    m.push(now, new_metrics.clone());
                 }
                 // Also update cache
                 if let Ok(mut c) = cached.write() {
-                    *c = new_metrics;
+                    c.push(now, new_metrics);
                 }
 
-                thread::sleep(Duration::from_secs(1));
+                // Sleep until the next sample, waking at once when stopped
+                // or paused
+                if stop.wait(interval) {
+                    break;
+                }
             }
-        });
+        }));
+    }
+
+    /// Resume or pause sampling, e.g. as a reactive background comes and goes.
+    ///
+    /// A paused thread sleeps without touching the source, and the first sample
+    /// after resuming measures rates from then on rather than across the pause.
+    pub fn set_enabled(&self, enabled: bool) {
+        if self.enabled.swap(enabled, Ordering::Relaxed) == enabled {
+            return;
+        }
+        if let Ok(worker) = self.worker.lock()
+            && let Some(worker) = worker.as_ref()
+        {
+            worker.wake();
+        }
     }
 
-    /// Stop the background monitoring thread.
+    /// Check if the monitor samples rather than staying paused.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Stop the background monitoring thread, waiting briefly for it to finish.
     pub fn stop(&self) {
-        if let Ok(mut running) = self.running.write() {
-            *running = false;
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        if let Some(worker) = worker {
+            worker.stop(JOIN_TIMEOUT);
         }
     }
 
-    /// Get the current system metrics.
+    /// Get system metrics smoothly interpolated between the last two samples.
     /// Uses try_read with fallback to cached values to avoid blocking.
-    pub fn get_metrics(&self) -> SystemMetrics {
+    pub fn get_metrics_interpolated(&self, now: Instant) -> SystemMetrics {
         // Try non-blocking read first
         if let Ok(m) = self.metrics.try_read() {
-            return m.clone();
+            return m.interpolate(now);
         }
-        // Fall back to cached metrics
+        // Fall back to cached samples
         if let Ok(c) = self.cached_metrics.read() {
-            return c.clone();
+            return c.interpolate(now);
         }
         // Last resort: return defaults
         SystemMetrics::default()
     }
+
+    /// How well the last sample went.
+    pub fn status(&self) -> Status {
+        self.status
+            .read()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the combined network throughput of the last minute of samples.
+    pub fn network_history(&self) -> MetricsHistory {
+        self.metrics
+            .try_read()
+            .or_else(|_| self.cached_metrics.read())
+            .map(|samples| samples.network_history.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the CPU usage of the last minute of samples.
+    pub fn cpu_history(&self) -> MetricsHistory {
+        self.metrics
+            .try_read()
+            .or_else(|_| self.cached_metrics.read())
+            .map(|samples| samples.cpu_history.clone())
+            .unwrap_or_default()
+    }
 }
 
 impl Default for SystemMonitor {
     fn default() -> Self {
-        Self::new()
+        Self::new(SourceSpec::default(), DEFAULT_SAMPLE_INTERVAL)
     }
 }
 
@@ -191,13 +690,93 @@ impl Drop for SystemMonitor {
     }
 }
 
-/// Get battery information from the system.
+/// Battery state parsed from a power supply `uevent` file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BatteryReading {
+    /// Charge level (0.0 - 1.0).
+    level: f32,
+    /// Whether the battery is charging (or full on AC power).
+    charging: bool,
+}
+
+/// Find system batteries under a power supply directory.
+/// Peripheral batteries (mice, keyboards) are skipped.
+fn find_batteries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut batteries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join("uevent"))
+        .filter(|path| {
+            fs::read_to_string(path)
+                .map(|uevent| is_system_battery(&uevent))
+                .unwrap_or(false)
+        })
+        .collect();
+    batteries.sort();
+    batteries
+}
+
+/// Check if a `uevent` describes a system battery rather than AC or a peripheral.
+fn is_system_battery(uevent: &str) -> bool {
+    uevent_value(uevent, "TYPE") == Some("Battery")
+        && uevent_value(uevent, "SCOPE") != Some("Device")
+}
+
+/// Parse a battery's charge level and status from its `uevent` file.
+fn parse_battery_uevent(uevent: &str) -> Option<BatteryReading> {
+    let level = match uevent_value(uevent, "CAPACITY") {
+        Some(capacity) => capacity.parse::<f32>().ok()? / 100.0,
+        // Some batteries only report energy (µWh) or charge (µAh) counters
+        None => {
+            let (now, full) = uevent_value(uevent, "ENERGY_NOW")
+                .zip(uevent_value(uevent, "ENERGY_FULL"))
+                .or_else(|| {
+                    uevent_value(uevent, "CHARGE_NOW").zip(uevent_value(uevent, "CHARGE_FULL"))
+                })?;
+            let full = full.parse::<f32>().ok().filter(|full| *full > 0.0)?;
+            now.parse::<f32>().ok()? / full
+        }
+    };
+
+    // "Not charging" means plugged in but held at a charge threshold
+    let charging = uevent_value(uevent, "STATUS") != Some("Discharging");
+
+    Some(BatteryReading {
+        level: level.clamp(0.0, 1.0),
+        charging,
+    })
+}
+
+/// Look up a `POWER_SUPPLY_<KEY>=value` entry in a `uevent` file.
+fn uevent_value<'a>(uevent: &'a str, key: &str) -> Option<&'a str> {
+    uevent.lines().find_map(|line| {
+        line.strip_prefix("POWER_SUPPLY_")?
+            .strip_prefix(key)?
+            .strip_prefix('=')
+            .map(str::trim)
+    })
+}
+
+/// Get battery information from the given battery `uevent` files.
 /// Returns (level, is_charging) or (None, None) if no battery.
-fn get_battery_info() -> (Option<f32>, Option<bool>) {
-    // sysinfo doesn't provide battery info directly
-    // On macOS, we could use IOKit, but for simplicity we'll return None
-    // This could be enhanced with platform-specific code or the `battery` crate
-    (None, None)
+/// Multiple batteries are averaged and count as charging if any is.
+fn get_battery_info(batteries: &[PathBuf]) -> (Option<f32>, Option<bool>) {
+    let readings: Vec<BatteryReading> = batteries
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|uevent| parse_battery_uevent(&uevent))
+        .collect();
+
+    if readings.is_empty() {
+        return (None, None);
+    }
+
+    let level = readings.iter().map(|r| r.level).sum::<f32>() / readings.len() as f32;
+    let charging = readings.iter().any(|r| r.charging);
+    (Some(level), Some(charging))
 }
 
 #[cfg(test)]
@@ -212,10 +791,452 @@ mod tests {
         assert!(metrics.battery_level.is_none());
     }
 
+    const LAPTOP_BATTERY: &str = "\
+POWER_SUPPLY_NAME=BAT0
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Discharging
+POWER_SUPPLY_PRESENT=1
+POWER_SUPPLY_CAPACITY=17
+POWER_SUPPLY_CAPACITY_LEVEL=Low
+POWER_SUPPLY_SCOPE=System
+";
+
+    const ENERGY_ONLY_BATTERY: &str = "\
+POWER_SUPPLY_NAME=BAT1
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Charging
+POWER_SUPPLY_ENERGY_FULL=50000000
+POWER_SUPPLY_ENERGY_NOW=40000000
+";
+
+    const MOUSE_BATTERY: &str = "\
+POWER_SUPPLY_NAME=hidpp_battery_0
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_SCOPE=Device
+POWER_SUPPLY_CAPACITY=90
+";
+
+    const AC_ADAPTER: &str = "\
+POWER_SUPPLY_NAME=AC
+POWER_SUPPLY_TYPE=Mains
+POWER_SUPPLY_ONLINE=1
+";
+
+    #[test]
+    fn test_parse_battery_capacity() {
+        let reading = parse_battery_uevent(LAPTOP_BATTERY).unwrap();
+        assert!((reading.level - 0.17).abs() < 1e-6);
+        assert!(!reading.charging);
+    }
+
+    #[test]
+    fn test_parse_battery_energy_fallback() {
+        let reading = parse_battery_uevent(ENERGY_ONLY_BATTERY).unwrap();
+        assert!((reading.level - 0.8).abs() < 1e-6);
+        assert!(reading.charging);
+    }
+
+    #[test]
+    fn test_parse_battery_status() {
+        for (status, charging) in [
+            ("Charging", true),
+            ("Full", true),
+            ("Not charging", true),
+            ("Discharging", false),
+        ] {
+            let uevent = format!("POWER_SUPPLY_CAPACITY=50\nPOWER_SUPPLY_STATUS={status}\n");
+            assert_eq!(
+                parse_battery_uevent(&uevent).map(|r| r.charging),
+                Some(charging),
+                "{status}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_battery_without_level() {
+        assert_eq!(parse_battery_uevent("POWER_SUPPLY_STATUS=Full\n"), None);
+        assert_eq!(parse_battery_uevent("POWER_SUPPLY_CAPACITY=lots\n"), None);
+    }
+
+    #[test]
+    fn test_system_battery_detection() {
+        assert!(is_system_battery(LAPTOP_BATTERY));
+        assert!(is_system_battery(ENERGY_ONLY_BATTERY));
+        assert!(!is_system_battery(MOUSE_BATTERY));
+        assert!(!is_system_battery(AC_ADAPTER));
+    }
+
+    #[test]
+    fn test_no_battery() {
+        assert_eq!(get_battery_info(&[]), (None, None));
+        assert!(find_batteries(Path::new("/nonexistent/power_supply")).is_empty());
+    }
+
     #[test]
     fn test_monitor_creation() {
-        let monitor = SystemMonitor::new();
-        let metrics = monitor.get_metrics();
+        let monitor = SystemMonitor::new(SourceSpec::Local, DEFAULT_SAMPLE_INTERVAL);
+        let metrics = monitor.get_metrics_interpolated(Instant::now());
         assert_eq!(metrics.cpu_usage, 0.0);
     }
+
+    #[test]
+    fn test_monitor_stop_releases_thread() {
+        let monitor = SystemMonitor::new(SourceSpec::Local, DEFAULT_SAMPLE_INTERVAL);
+        monitor.start();
+        assert!(monitor.worker.lock().unwrap().is_some());
+
+        let start = Instant::now();
+        monitor.stop();
+        assert!(start.elapsed() <= JOIN_TIMEOUT + Duration::from_millis(50));
+        assert!(monitor.worker.lock().unwrap().is_none());
+    }
+
+    /// Source counting its samples and resumes.
+    #[derive(Default)]
+    struct CountingSource {
+        samples: Arc<Mutex<(u32, u32)>>,
+    }
+
+    impl MetricsSource for CountingSource {
+        fn sample(&mut self) -> SystemMetrics {
+            self.samples.lock().unwrap().0 += 1;
+            SystemMetrics::default()
+        }
+
+        fn resume(&mut self) {
+            self.samples.lock().unwrap().1 += 1;
+        }
+    }
+
+    #[test]
+    fn test_poller_pauses_and_resumes() {
+        let source = CountingSource::default();
+        let counts = source.samples.clone();
+        let mut poller = Poller::new(Box::new(source));
+        let mut poll = |enabled| match poller.poll(enabled) {
+            Poll::Sampled(_) => "sampled",
+            Poll::Resumed => "resumed",
+            Poll::Parked => "parked",
+        };
+
+        // A fresh baseline before the first sample
+        assert_eq!(poll(true), "resumed");
+        assert_eq!(poll(true), "sampled");
+        assert_eq!(poll(true), "sampled");
+        // Paused: nothing is sampled however long it lasts
+        assert_eq!(poll(false), "parked");
+        assert_eq!(poll(false), "parked");
+        assert_eq!(*counts.lock().unwrap(), (2, 1));
+        // And a fresh baseline again before sampling after it
+        assert_eq!(poll(true), "resumed");
+        assert_eq!(poll(true), "sampled");
+        assert_eq!(*counts.lock().unwrap(), (3, 2));
+    }
+
+    #[test]
+    fn test_resume_recaptures_network_baseline() {
+        let mut source = SysinfoSource::new();
+        source.max_values.network_rx = 50_000_000;
+        // As if paused for an hour with a stale counter
+        source.prev_rx = 0;
+        source.prev_time = Instant::now() - Duration::from_secs(3600);
+
+        source.resume();
+        let current_rx: u64 = source.networks.values().map(|n| n.received()).sum();
+        assert_eq!(source.prev_rx, current_rx);
+        assert!(source.prev_time.elapsed() < Duration::from_secs(5));
+        // The normalization maxima survive the pause
+        assert_eq!(source.max_values.network_rx, 50_000_000);
+    }
+
+    #[test]
+    fn test_monitor_enable_toggle() {
+        let monitor = SystemMonitor::new(SourceSpec::Local, DEFAULT_SAMPLE_INTERVAL);
+        assert!(monitor.is_enabled());
+        monitor.start();
+        monitor.set_enabled(false);
+        assert!(!monitor.is_enabled());
+        monitor.set_enabled(false);
+        monitor.set_enabled(true);
+        assert!(monitor.is_enabled());
+
+        // A paused thread still stops promptly
+        monitor.set_enabled(false);
+        let start = Instant::now();
+        monitor.stop();
+        assert!(start.elapsed() <= JOIN_TIMEOUT + Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_sample_interval() {
+        assert_eq!(sample_interval(1.0), Duration::from_secs(1));
+        assert_eq!(sample_interval(2.5), Duration::from_millis(2500));
+        assert_eq!(sample_interval(0.1), Duration::from_millis(500));
+        assert_eq!(sample_interval(60.0), Duration::from_secs(5));
+        assert_eq!(sample_interval(f32::NAN), DEFAULT_SAMPLE_INTERVAL);
+    }
+
+    #[test]
+    fn test_interpolate_after_pause() {
+        // Samples an hour apart still arrive within one interval
+        let start = Instant::now();
+        let mut samples = MetricSamples::new(Duration::from_secs(2));
+        samples.push(start, cpu(0.0));
+        let resumed = start + Duration::from_secs(3600);
+        samples.push(resumed, cpu(1.0));
+        let at = |ms| {
+            samples
+                .interpolate(resumed + Duration::from_millis(ms))
+                .cpu_usage
+        };
+        assert!((at(1000) - 0.5).abs() < 1e-3);
+        assert_eq!(at(2000), 1.0);
+    }
+
+    fn cpu(cpu_usage: f32) -> SystemMetrics {
+        SystemMetrics {
+            cpu_usage,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lerp_metrics() {
+        let from = SystemMetrics {
+            cpu_usage: 0.2,
+            battery_level: Some(0.5),
+            battery_charging: Some(false),
+            ..Default::default()
+        };
+        let to = SystemMetrics {
+            cpu_usage: 0.6,
+            memory_usage: 1.0,
+            battery_level: Some(0.3),
+            battery_charging: Some(true),
+            ..Default::default()
+        };
+
+        let mid = lerp_metrics(&from, &to, 0.5);
+        assert!((mid.cpu_usage - 0.4).abs() < 1e-6);
+        assert!((mid.memory_usage - 0.5).abs() < 1e-6);
+        assert!((mid.battery_level.unwrap() - 0.4).abs() < 1e-6);
+        assert_eq!(mid.battery_charging, Some(true));
+
+        // Out-of-range progress is clamped to the endpoints
+        assert_eq!(lerp_metrics(&from, &to, -1.0).cpu_usage, 0.2);
+        assert_eq!(lerp_metrics(&from, &to, 3.0).cpu_usage, 0.6);
+    }
+
+    #[test]
+    fn test_interpolate_between_samples() {
+        let start = Instant::now();
+        let mut samples = MetricSamples::new(DEFAULT_SAMPLE_INTERVAL);
+        samples.push(start, cpu(0.0));
+        samples.push(start + Duration::from_secs(1), cpu(1.0));
+
+        let at = |ms| {
+            samples
+                .interpolate(start + Duration::from_millis(ms))
+                .cpu_usage
+        };
+        assert_eq!(at(1000), 0.0);
+        assert!((at(1250) - 0.25).abs() < 1e-3);
+        assert!((at(1750) - 0.75).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_interpolate_stale_samples() {
+        let start = Instant::now();
+        let mut samples = MetricSamples::new(DEFAULT_SAMPLE_INTERVAL);
+        samples.push(start, cpu(0.0));
+        samples.push(start + Duration::from_secs(1), cpu(0.8));
+
+        // Long after the last sample the latest value holds
+        let stale = samples.interpolate(start + Duration::from_secs(30));
+        assert_eq!(stale.cpu_usage, 0.8);
+    }
+
+    #[test]
+    fn test_samples_record_network_history() {
+        let start = Instant::now();
+        let mut samples = MetricSamples::new(DEFAULT_SAMPLE_INTERVAL);
+        for (i, (rx, tx)) in [(0.2, 0.4), (1.0, 0.0), (0.0, 0.0)].into_iter().enumerate() {
+            let metrics = SystemMetrics {
+                network_rx_rate: rx,
+                network_tx_rate: tx,
+                ..Default::default()
+            };
+            samples.push(start + Duration::from_secs(i as u64), metrics);
+        }
+        let history: Vec<f32> = samples.network_history.iter().collect();
+        assert_eq!(history.len(), 3);
+        assert!((history[0] - 0.3).abs() < 1e-6);
+        assert_eq!(history[1..], [0.5, 0.0]);
+        assert_eq!(samples.cpu_history.len(), 3);
+    }
+
+    #[test]
+    fn test_interpolate_first_sample() {
+        let start = Instant::now();
+        let mut samples = MetricSamples::new(DEFAULT_SAMPLE_INTERVAL);
+        assert_eq!(samples.interpolate(start).cpu_usage, 0.0);
+
+        // With no previous sample the first one is used as-is
+        samples.push(start, cpu(0.6));
+        assert_eq!(samples.interpolate(start).cpu_usage, 0.6);
+        assert_eq!(
+            samples
+                .interpolate(start + Duration::from_millis(500))
+                .cpu_usage,
+            0.6
+        );
+    }
+
+    #[test]
+    fn test_parse_metrics_json() {
+        let metrics = parse_metrics_json(
+            r#"{"cpu_usage": 0.42, "memory_usage": 0.7, "network_rx_rate": 0.1,
+                "battery_level": 0.55, "battery_charging": false, "hostname": "db1"}"#,
+        )
+        .unwrap();
+        assert_eq!(metrics.cpu_usage, 0.42);
+        assert_eq!(metrics.memory_usage, 0.7);
+        assert_eq!(metrics.network_rx_rate, 0.1);
+        assert_eq!(metrics.battery_level, Some(0.55));
+
+        // Missing fields are zero, or no battery
+        let metrics = parse_metrics_json(r#"{"cpu_usage": 1}"#).unwrap();
+        assert_eq!(metrics.cpu_usage, 1.0);
+        assert_eq!(metrics.disk_write_rate, 0.0);
+        assert_eq!(metrics.battery_level, None);
+        assert!(parse_metrics_json("{}").is_ok());
+    }
+
+    #[test]
+    fn test_parse_metrics_json_clamps() {
+        let metrics = parse_metrics_json(
+            r#"{"cpu_usage": 42, "memory_usage": -0.5, "disk_read_rate": 1e9, "battery_level": 1.5}"#,
+        )
+        .unwrap();
+        assert_eq!(metrics.cpu_usage, 1.0);
+        assert_eq!(metrics.memory_usage, 0.0);
+        assert_eq!(metrics.disk_read_rate, 1.0);
+        assert_eq!(metrics.battery_level, Some(1.0));
+    }
+
+    #[test]
+    fn test_parse_malformed_metrics_json() {
+        for malformed in [
+            "",
+            "cpu 42%",
+            r#"{"cpu_usage": 0.4"#,
+            "[0.4, 0.7]",
+            "0.4",
+            "null",
+            r#"{"cpu_usage": "high"}"#,
+            r#"{"battery_charging": "yes"}"#,
+        ] {
+            assert!(parse_metrics_json(malformed).is_err(), "{malformed:?}");
+        }
+    }
+
+    /// Hands out queued reads in order.
+    struct QueuedReader(Vec<Result<String, String>>);
+
+    impl JsonReader for QueuedReader {
+        fn read(&mut self) -> Result<String, String> {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_json_source_keeps_last_good_sample() {
+        let mut source = JsonSource::new(QueuedReader(vec![
+            Err("not started yet".to_string()),
+            Ok(r#"{"cpu_usage": 0.3}"#.to_string()),
+            Ok(r#"{"cpu_usage": "#.to_string()),
+            Err("connection refused".to_string()),
+            Ok(r#"{"cpu_usage": 0.9}"#.to_string()),
+        ]));
+        let mut sample = || {
+            let cpu = source.sample().cpu_usage;
+            (cpu, source.status().label())
+        };
+        assert_eq!(sample(), (0.0, "failed"));
+        assert_eq!(sample(), (0.3, "ok"));
+        assert_eq!(sample(), (0.3, "degraded"));
+        assert_eq!(sample(), (0.3, "degraded"));
+        assert_eq!(sample(), (0.9, "ok"));
+    }
+
+    #[test]
+    fn test_file_source() {
+        let path =
+            std::env::temp_dir().join(format!("sigye-metrics-test-{}.json", std::process::id()));
+        let mut source = SourceSpec::File(path.clone()).open();
+
+        fs::write(&path, r#"{"cpu_usage": 0.25, "memory_usage": 0.5}"#).unwrap();
+        let metrics = source.sample();
+        assert_eq!((metrics.cpu_usage, metrics.memory_usage), (0.25, 0.5));
+
+        // A half-written file, then a missing one, keep the last reading
+        fs::write(&path, r#"{"cpu_usage": 0."#).unwrap();
+        assert_eq!(source.sample().cpu_usage, 0.25);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(source.sample().cpu_usage, 0.25);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_source() {
+        let mut source = SourceSpec::Command(r#"echo '{"cpu_usage": 0.75}'"#.to_string()).open();
+        assert_eq!(source.sample().cpu_usage, 0.75);
+
+        // Failing commands print nothing usable
+        let mut failing =
+            JsonSource::new(CommandReader::new(r#"echo '{"cpu_usage": 0.75}'; exit 3"#));
+        assert!(failing.reader.read().is_err());
+        assert_eq!(failing.sample().cpu_usage, 0.0);
+        assert_eq!(
+            failing.status(),
+            Status::Failed("metrics command failed: exit status: 3".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hanging_command_is_killed() {
+        let mut hanging = JsonSource::new(CommandReader {
+            command: "exec sleep 30".to_string(),
+            timeout: Duration::from_millis(200),
+        });
+        let started = Instant::now();
+        assert_eq!(hanging.sample().cpu_usage, 0.0);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            hanging.status(),
+            Status::Failed("metrics command timed out after 0.2s".to_string())
+        );
+    }
+
+    #[test]
+    fn test_source_from_config() {
+        let mut config = Config {
+            metrics_command: "ssh db1 sigye-metrics".to_string(),
+            metrics_file: "/run/metrics.json".to_string(),
+            ..Config::default()
+        };
+        assert_eq!(SourceSpec::from_config(&config), SourceSpec::Local);
+        config.metrics_provider = MetricsProvider::Command;
+        assert_eq!(
+            SourceSpec::from_config(&config),
+            SourceSpec::Command("ssh db1 sigye-metrics".to_string())
+        );
+        config.metrics_provider = MetricsProvider::File;
+        assert_eq!(
+            SourceSpec::from_config(&config),
+            SourceSpec::File(PathBuf::from("/run/metrics.json"))
+        );
+    }
 }
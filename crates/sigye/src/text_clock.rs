@@ -0,0 +1,130 @@
+//! Layout of the time in words, as big font text when it fits or plain text otherwise.
+
+use sigye_fonts::Font;
+
+/// Lay out `phrase` within `max_width` x `max_height` cells.
+///
+/// Words are wrapped onto as few lines as fit and rendered with `font`. When a
+/// word is too wide for the font or the lines are too tall, the phrase is
+/// wrapped as plain text instead. Every returned row has the same width, with
+/// shorter lines centered, so the block can be placed like the digits.
+pub fn word_art(font: &Font, phrase: &str, max_width: usize, max_height: usize) -> Vec<String> {
+    let big_width = |text: &str| font.text_width(text);
+    if let Some(lines) = wrap(phrase, max_width, big_width)
+        && lines.len() * font.height <= max_height
+    {
+        let rows = lines
+            .iter()
+            .flat_map(|line| font.render_text(line))
+            .collect();
+        return pad_centered(rows);
+    }
+
+    let plain_width = |text: &str| text.chars().count();
+    let lines = wrap(phrase, max_width, plain_width).unwrap_or_else(|| vec![phrase.to_string()]);
+    pad_centered(lines)
+}
+
+/// Greedily wrap words onto lines no wider than `max_width`.
+///
+/// Hyphenated words that don't fit on a line are broken after the hyphen.
+/// Returns `None` if a single word is still wider than `max_width`.
+fn wrap(text: &str, max_width: usize, width: impl Fn(&str) -> usize) -> Option<Vec<String>> {
+    let words = text.split_whitespace().flat_map(|word| {
+        if width(word) > max_width {
+            word.split_inclusive('-').collect()
+        } else {
+            vec![word]
+        }
+    });
+
+    let mut lines: Vec<String> = Vec::new();
+    for word in words {
+        if width(word) > max_width {
+            return None;
+        }
+        match lines.last_mut() {
+            // The rest of a broken hyphenated word follows without a space
+            Some(line) if line.ends_with('-') && width(&format!("{line}{word}")) <= max_width => {
+                line.push_str(word);
+            }
+            Some(line) if width(&format!("{line} {word}")) <= max_width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    Some(lines)
+}
+
+/// Pad rows to a common width, centering the shorter ones.
+fn pad_centered(rows: Vec<String>) -> Vec<String> {
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    rows.into_iter()
+        .map(|row| {
+            let len = row.chars().count();
+            let left = (width - len) / 2;
+            format!(
+                "{}{row}{}",
+                " ".repeat(left),
+                " ".repeat(width - len - left)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigye_fonts::FontRegistry;
+
+    fn standard() -> Font {
+        FontRegistry::new().get_or_default("Standard").clone()
+    }
+
+    #[test]
+    fn test_wrap_fills_lines_greedily() {
+        let lines = wrap("quarter past seven", 12, |s| s.len()).unwrap();
+        assert_eq!(lines, ["quarter past", "seven"]);
+        assert_eq!(wrap("quarter", 3, |s| s.len()), None);
+
+        let lines = wrap("twenty-three minutes", 10, |s| s.len()).unwrap();
+        assert_eq!(lines, ["twenty-", "three", "minutes"]);
+    }
+
+    #[test]
+    fn test_big_text_when_it_fits() {
+        let font = standard();
+        let rows = word_art(&font, "ten to noon", 200, 40);
+        assert_eq!(rows.len(), font.height);
+        assert_eq!(rows, pad_centered(font.render_text("ten to noon")));
+    }
+
+    #[test]
+    fn test_big_text_wraps_to_width() {
+        let font = standard();
+        let rows = word_art(&font, "twenty-three minutes to nine", 80, 40);
+        assert!(rows.len() > font.height);
+        assert_eq!(rows.len() % font.height, 0);
+        let width = rows[0].chars().count();
+        assert!(width <= 80);
+        assert!(rows.iter().all(|row| row.chars().count() == width));
+    }
+
+    #[test]
+    fn test_plain_text_when_too_small() {
+        let font = standard();
+        // Too short for even one line of big text
+        let rows = word_art(&font, "quarter past seven", 80, 3);
+        assert_eq!(rows, ["quarter past seven"]);
+
+        // Too narrow for a single big word
+        let rows = word_art(&font, "quarter past seven", 14, 40);
+        assert_eq!(rows, ["quarter past", "   seven    "]);
+    }
+}
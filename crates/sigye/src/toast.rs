@@ -0,0 +1,207 @@
+//! Short-lived notifications stacked in the top-right corner.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Clear, Paragraph},
+};
+use sigye_background::scale_color;
+
+/// Most toasts shown at once; older ones are dropped first.
+pub const MAX_TOASTS: usize = 3;
+
+/// How long a toast is shown unless told otherwise.
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(2);
+
+/// Time at the end of a toast's life during which it fades.
+const FADE_DURATION: Duration = Duration::from_millis(500);
+
+/// Brightness a toast has faded to just before it disappears.
+const FADED_BRIGHTNESS: f32 = 0.3;
+
+/// A message shown until it expires.
+#[derive(Debug, Clone, PartialEq)]
+struct Toast {
+    message: String,
+    expires_at: Instant,
+}
+
+impl Toast {
+    /// Brightness at `now`: full until the last [`FADE_DURATION`], then fading to dim.
+    fn brightness(&self, now: Instant) -> f32 {
+        let left = self.expires_at.saturating_duration_since(now);
+        if left >= FADE_DURATION {
+            return 1.0;
+        }
+        let progress = left.as_secs_f32() / FADE_DURATION.as_secs_f32();
+        FADED_BRIGHTNESS + (1.0 - FADED_BRIGHTNESS) * progress
+    }
+}
+
+/// Queue of toasts, oldest first.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Show `message` for `duration`, dropping the oldest toast if the stack is full.
+    pub fn push(&mut self, message: impl Into<String>, duration: Duration, now: Instant) {
+        self.prune(now);
+        self.queue.push_back(Toast {
+            message: message.into(),
+            expires_at: now + duration,
+        });
+        while self.queue.len() > MAX_TOASTS {
+            self.queue.pop_front();
+        }
+    }
+
+    /// Remove toasts that have expired by `now`.
+    pub fn prune(&mut self, now: Instant) {
+        self.queue.retain(|toast| toast.expires_at > now);
+    }
+
+    /// Messages shown at `now`, oldest first.
+    pub fn messages(&self, now: Instant) -> Vec<String> {
+        self.visible(now)
+            .map(|toast| toast.message.clone())
+            .collect()
+    }
+
+    /// Check if any toast is fading at `now`, which needs a redraw every frame.
+    pub fn is_fading(&self, now: Instant) -> bool {
+        self.visible(now).any(|toast| toast.brightness(now) < 1.0)
+    }
+
+    /// Time until a toast starts fading or disappears, or `None` if there are none.
+    pub fn next_change_in(&self, now: Instant) -> Option<Duration> {
+        self.visible(now)
+            .map(|toast| {
+                let left = toast.expires_at.saturating_duration_since(now);
+                left.checked_sub(FADE_DURATION)
+                    .filter(|until_fade| !until_fade.is_zero())
+                    .unwrap_or(left)
+            })
+            .min()
+    }
+
+    /// Render the toasts over the top-right corner of `area` without moving anything else.
+    pub fn render(&self, frame: &mut Frame, area: Rect, color: Color, now: Instant) {
+        for (row, toast) in self.visible(now).enumerate() {
+            let y = area.y + row as u16;
+            if y >= area.bottom() {
+                break;
+            }
+
+            let text = format!(" {} ", toast.message);
+            let width = (text.chars().count() as u16).min(area.width);
+            let toast_area = Rect::new(area.right() - width, y, width, 1);
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(
+                Paragraph::new(text)
+                    .style(Style::default().fg(scale_color(color, toast.brightness(now)))),
+                toast_area,
+            );
+        }
+    }
+
+    /// Toasts that haven't expired at `now`.
+    fn visible(&self, now: Instant) -> impl Iterator<Item = &Toast> {
+        self.queue
+            .iter()
+            .filter(move |toast| toast.expires_at > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_expires() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push("Theme: Cyan", Duration::from_secs(2), start);
+
+        assert_eq!(toasts.messages(start), ["Theme: Cyan"]);
+        assert_eq!(
+            toasts.messages(start + Duration::from_millis(1999)),
+            ["Theme: Cyan"]
+        );
+        assert!(toasts.messages(start + Duration::from_secs(2)).is_empty());
+
+        toasts.prune(start + Duration::from_secs(3));
+        assert!(toasts.queue.is_empty());
+    }
+
+    #[test]
+    fn test_toasts_stack_up_to_limit() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        for (i, message) in ["one", "two", "three", "four"].iter().enumerate() {
+            toasts.push(
+                *message,
+                DEFAULT_DURATION,
+                start + Duration::from_millis(i as u64 * 10),
+            );
+        }
+
+        let now = start + Duration::from_millis(50);
+        assert_eq!(toasts.messages(now), ["two", "three", "four"]);
+    }
+
+    #[test]
+    fn test_expired_toasts_free_their_slot() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push("short", Duration::from_secs(1), start);
+        toasts.push("long", Duration::from_secs(10), start);
+        toasts.push("long too", Duration::from_secs(10), start);
+
+        // The short toast has expired, so nothing else is dropped
+        let later = start + Duration::from_secs(2);
+        toasts.push("new", Duration::from_secs(10), later);
+        assert_eq!(toasts.messages(later), ["long", "long too", "new"]);
+    }
+
+    #[test]
+    fn test_toast_fades_at_the_end() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        toasts.push("fading", Duration::from_secs(2), start);
+        let toast = &toasts.queue[0];
+
+        assert_eq!(toast.brightness(start), 1.0);
+        assert_eq!(toast.brightness(start + Duration::from_millis(1500)), 1.0);
+        assert!(!toasts.is_fading(start + Duration::from_millis(1400)));
+
+        let fading = toast.brightness(start + Duration::from_millis(1750));
+        assert!(FADED_BRIGHTNESS < fading && fading < 1.0);
+        assert!(toasts.is_fading(start + Duration::from_millis(1750)));
+        assert!(toast.brightness(start + Duration::from_millis(1999)) < fading);
+    }
+
+    #[test]
+    fn test_next_change_in() {
+        let start = Instant::now();
+        let mut toasts = Toasts::default();
+        assert_eq!(toasts.next_change_in(start), None);
+
+        toasts.push("a", Duration::from_secs(2), start);
+        assert_eq!(
+            toasts.next_change_in(start),
+            Some(Duration::from_millis(1500))
+        );
+        // While fading, the next change is the toast disappearing
+        assert_eq!(
+            toasts.next_change_in(start + Duration::from_millis(1800)),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(toasts.next_change_in(start + Duration::from_secs(2)), None);
+    }
+}
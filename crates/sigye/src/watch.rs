@@ -0,0 +1,96 @@
+//! Noticing when files like the config or the banner file are written.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often a watched file's modification time is checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watcher noticing when a file is written, by polling its modification time.
+///
+/// Checks are spaced at least [`POLL_INTERVAL`] apart, so a burst of writes in
+/// between is noticed once.
+#[derive(Debug)]
+pub struct FileWatcher {
+    /// Path of the watched file.
+    path: PathBuf,
+    /// Modification time last seen, `None` while the file is missing.
+    modified: Option<SystemTime>,
+    /// When the modification time was last checked.
+    last_check: Instant,
+}
+
+impl FileWatcher {
+    /// Create a watcher for the file at `path`, taking it as read now.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            modified: modified(&path),
+            path,
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Path of the watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check if the file was written since it was last seen, looking at most
+    /// every [`POLL_INTERVAL`]. A file going missing isn't a change.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.last_check) < POLL_INTERVAL {
+            return false;
+        }
+        self.last_check = now;
+        let modified = modified(&self.path);
+        let changed = modified.is_some() && modified != self.modified;
+        self.modified = modified;
+        changed
+    }
+}
+
+/// Modification time of a file, `None` when it can't be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Move the modification time on, as file systems may not tell apart
+    /// writes in quick succession.
+    fn touch_later(path: &Path) {
+        let file = fs::File::options().append(true).open(path).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(10);
+        file.set_modified(later).unwrap();
+    }
+
+    #[test]
+    fn test_notices_writes() {
+        let path = std::env::temp_dir().join(format!("sigye-watch-{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = FileWatcher::new(path.clone());
+        let start = Instant::now();
+
+        // Missing, then written
+        assert!(!watcher.poll(start + POLL_INTERVAL));
+        fs::write(&path, "show_weather = true\n").unwrap();
+        // Not looked at again before the interval is up
+        assert!(!watcher.poll(start + POLL_INTERVAL));
+        assert!(watcher.poll(start + POLL_INTERVAL * 2));
+        assert!(!watcher.poll(start + POLL_INTERVAL * 3));
+
+        // Writes between two checks are noticed once
+        fs::write(&path, "show_weather = false\n").unwrap();
+        fs::write(&path, "show_weather = true\n").unwrap();
+        touch_later(&path);
+        assert!(watcher.poll(start + POLL_INTERVAL * 4));
+        assert!(!watcher.poll(start + POLL_INTERVAL * 5));
+
+        // Going missing isn't a change
+        fs::remove_file(&path).unwrap();
+        assert!(!watcher.poll(start + POLL_INTERVAL * 6));
+    }
+}
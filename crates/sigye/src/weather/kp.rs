@@ -0,0 +1,119 @@
+//! Planetary Kp index from NOAA's Space Weather Prediction Center (no API key required).
+//!
+//! The Kp index (0 - 9) measures geomagnetic activity: the higher it is, the
+//! brighter the aurora and the farther from the poles it can be seen.
+
+use serde::Deserialize;
+
+use super::http_agent;
+
+/// Estimated Kp index for each minute of the last few hours, oldest first.
+const KP_URL: &str = "https://services.swpc.noaa.gov/json/planetary_k_index_1m.json";
+
+/// Latitude beyond which clear nights show the aurora while the Kp index is
+/// unknown or quiet.
+pub const AURORA_LATITUDE: f32 = 55.0;
+
+/// Kp index up to which the aurora stays beyond [`AURORA_LATITUDE`].
+const QUIET_KP: f32 = 5.0;
+
+/// Degrees of latitude the aurora reaches toward the equator per Kp above quiet,
+/// so Kp 7 is visible from 50° and Kp 9 from 45°.
+const DEGREES_PER_KP: f32 = 2.5;
+
+/// Latitude below which even the strongest storm shows no aurora, so the Kp
+/// index isn't worth fetching.
+pub const MIN_AURORA_LATITUDE: f32 = AURORA_LATITUDE - (9.0 - QUIET_KP) * DEGREES_PER_KP;
+
+/// One minute of the NOAA response (partial).
+#[derive(Debug, Deserialize)]
+struct KpSample {
+    /// Kp index rounded to a whole number.
+    kp_index: Option<f32>,
+    /// Kp index estimated to a third, missing for the latest minutes at times.
+    estimated_kp: Option<f32>,
+}
+
+/// Fetch the current planetary Kp index.
+pub fn fetch() -> Result<f32, String> {
+    let body = http_agent()
+        .get(KP_URL)
+        .call()
+        .map_err(|e| format!("HTTP error: {e}"))?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| format!("HTTP error: {e}"))?;
+    parse(&body)
+}
+
+/// Parse the latest Kp index from the NOAA JSON, preferring the finer estimate.
+fn parse(json: &str) -> Result<f32, String> {
+    let samples: Vec<KpSample> =
+        serde_json::from_str(json).map_err(|e| format!("JSON parse error: {e}"))?;
+    samples
+        .iter()
+        .rev()
+        .find_map(|sample| sample.estimated_kp.or(sample.kp_index))
+        .map(|kp| kp.clamp(0.0, 9.0))
+        .ok_or_else(|| "No Kp index reported".to_string())
+}
+
+/// Latitude beyond which clear nights show the aurora, nearer the equator the
+/// stormier the Kp index; [`AURORA_LATITUDE`] without one.
+pub fn aurora_latitude(kp: Option<f32>) -> f32 {
+    let storm = kp.map_or(0.0, |kp| (kp - QUIET_KP).max(0.0));
+    (AURORA_LATITUDE - storm * DEGREES_PER_KP).max(MIN_AURORA_LATITUDE)
+}
+
+/// How strong and tall the aurora animation is for a Kp index, 1.0 for a
+/// typical Kp 4 and without one.
+pub fn aurora_activity(kp: Option<f32>) -> f32 {
+    kp.map_or(1.0, |kp| (0.6 + kp * 0.1).clamp(0.6, 1.5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixture() {
+        // The May 2024 storm; the last minute has no estimate yet
+        let json = include_str!("../../fixtures/weather/planetary_k_index_1m.json");
+        assert_eq!(parse(json), Ok(8.0));
+        assert_eq!(
+            parse(r#"[{"kp_index": 2, "estimated_kp": 2.33}]"#),
+            Ok(2.33)
+        );
+        assert_eq!(parse(r#"[{"kp_index": 12}]"#), Ok(9.0));
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_or_malformed() {
+        assert!(parse("[]").is_err());
+        assert!(parse(r#"[{"kp_index": null, "estimated_kp": null}]"#).is_err());
+        assert!(parse("<html>").unwrap_err().starts_with("JSON parse error"));
+    }
+
+    #[test]
+    fn test_aurora_latitude() {
+        assert_eq!(aurora_latitude(None), AURORA_LATITUDE);
+        // Quiet activity keeps the latitude-only rule
+        assert_eq!(aurora_latitude(Some(0.0)), AURORA_LATITUDE);
+        assert_eq!(aurora_latitude(Some(5.0)), AURORA_LATITUDE);
+        // Storms bring it toward the equator
+        assert_eq!(aurora_latitude(Some(6.0)), 52.5);
+        assert_eq!(aurora_latitude(Some(7.0)), 50.0);
+        assert_eq!(aurora_latitude(Some(9.0)), MIN_AURORA_LATITUDE);
+        assert_eq!(aurora_latitude(Some(15.0)), MIN_AURORA_LATITUDE);
+        assert_eq!(MIN_AURORA_LATITUDE, 45.0);
+    }
+
+    #[test]
+    fn test_aurora_activity() {
+        assert_eq!(aurora_activity(None), 1.0);
+        assert!((aurora_activity(Some(4.0)) - 1.0).abs() < 1e-6);
+        assert_eq!(aurora_activity(Some(0.0)), 0.6);
+        assert_eq!(aurora_activity(Some(9.0)), 1.5);
+        assert!(aurora_activity(Some(7.0)) > aurora_activity(Some(3.0)));
+    }
+}
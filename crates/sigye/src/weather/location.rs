@@ -0,0 +1,356 @@
+//! Weather location: parsing the configured one and detecting it from the IP.
+//!
+//! An empty location is detected with IP geolocation (ipinfo.io) in the weather
+//! thread. The result is cached on disk, since it rarely changes and the lookup
+//! would otherwise delay the first fetch on every start.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sigye_config::Config;
+
+use super::http_agent;
+
+/// How long a detected location is reused before looking it up again (1 day).
+const MAX_LOCATION_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Where to fetch the weather for.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Location {
+    /// Detect the location from the IP address.
+    #[default]
+    Auto,
+    /// Explicit coordinates in degrees.
+    Coordinates { latitude: f32, longitude: f32 },
+    /// A place name like "Seoul", looked up by the provider.
+    Place(String),
+}
+
+impl Location {
+    /// Parse a location: empty for auto-detect, "lat,lon" coordinates, or a place name.
+    ///
+    /// Anything that looks like two numbers is taken as coordinates and must be in range.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(Self::Auto);
+        }
+
+        let Some((latitude, longitude)) = value.split_once(',').and_then(|(lat, lon)| {
+            Some((
+                lat.trim().parse::<f32>().ok()?,
+                lon.trim().parse::<f32>().ok()?,
+            ))
+        }) else {
+            return Ok(Self::Place(value.to_string()));
+        };
+
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(format!("latitude {latitude} is not between -90 and 90"));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(format!("longitude {longitude} is not between -180 and 180"));
+        }
+        Ok(Self::Coordinates {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Location as passed to weather providers (empty for auto-detect).
+    pub fn query(&self) -> String {
+        match self {
+            Self::Auto => String::new(),
+            Self::Coordinates {
+                latitude,
+                longitude,
+            } => format!("{latitude},{longitude}"),
+            Self::Place(name) => name.clone(),
+        }
+    }
+
+    /// Place name, if the location was given as one.
+    pub fn place(&self) -> Option<&str> {
+        match self {
+            Self::Place(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a location given directly as "lat,lon", e.g. "37.57,126.98".
+pub fn parse_coordinates(location: &str) -> Option<(f32, f32)> {
+    match Location::parse(location) {
+        Ok(Location::Coordinates {
+            latitude,
+            longitude,
+        }) => Some((latitude, longitude)),
+        _ => None,
+    }
+}
+
+/// Location detected from the IP address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub latitude: f32,
+    pub longitude: f32,
+    /// City name, if known.
+    pub city: Option<String>,
+}
+
+impl GeoLocation {
+    /// Coordinates as passed to weather providers.
+    pub fn query(&self) -> String {
+        Location::Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+        .query()
+    }
+}
+
+/// A source of the current location.
+pub trait Geolocator: Send {
+    /// Look up where this machine is.
+    fn locate(&self) -> Result<GeoLocation, String>;
+}
+
+/// Looks up the location of the public IP address with ipinfo.io.
+#[derive(Debug, Default)]
+pub struct IpInfo;
+
+/// ipinfo.io response (partial).
+#[derive(Debug, Deserialize)]
+struct IpInfoResponse {
+    city: Option<String>,
+    /// Coordinates as "lat,lon".
+    loc: Option<String>,
+}
+
+impl Geolocator for IpInfo {
+    fn locate(&self) -> Result<GeoLocation, String> {
+        let response: IpInfoResponse = http_agent()
+            .get("https://ipinfo.io/json")
+            .call()
+            .map_err(|e| format!("HTTP error: {e}"))?
+            .body_mut()
+            .read_json()
+            .map_err(|e| format!("JSON parse error: {e}"))?;
+        parse_ipinfo(response)
+    }
+}
+
+/// Extract the location from an ipinfo.io response.
+fn parse_ipinfo(response: IpInfoResponse) -> Result<GeoLocation, String> {
+    let (latitude, longitude) = response
+        .loc
+        .as_deref()
+        .and_then(parse_coordinates)
+        .ok_or("No coordinates in geolocation response")?;
+    Ok(GeoLocation {
+        latitude,
+        longitude,
+        city: response.city.filter(|city| !city.trim().is_empty()),
+    })
+}
+
+/// On-disk form of a detected location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedLocation {
+    #[serde(flatten)]
+    location: GeoLocation,
+    /// Unix timestamp (seconds) of the lookup.
+    detected_at: u64,
+}
+
+impl CachedLocation {
+    /// Check if the cached location is young enough to reuse.
+    fn is_valid(&self, now_unix: u64) -> bool {
+        now_unix
+            .checked_sub(self.detected_at)
+            .is_some_and(|age| Duration::from_secs(age) < MAX_LOCATION_AGE)
+    }
+}
+
+/// Path of the detected location cache file.
+pub fn cache_file_path() -> PathBuf {
+    Config::cache_dir().join("location.json")
+}
+
+/// Detect the location, reusing the cached result while it is recent.
+pub fn detect(
+    geolocator: &dyn Geolocator,
+    cache_path: &Path,
+    now_unix: u64,
+) -> Result<GeoLocation, String> {
+    if let Some(cached) = load_cache(cache_path)
+        && cached.is_valid(now_unix)
+    {
+        return Ok(cached.location);
+    }
+
+    let location = geolocator.locate()?;
+    // The cache is best-effort; without it the lookup is just repeated next time
+    let _ = save_cache(
+        cache_path,
+        &CachedLocation {
+            location: location.clone(),
+            detected_at: now_unix,
+        },
+    );
+    Ok(location)
+}
+
+/// Load the cached location, or `None` if missing or unreadable.
+fn load_cache(path: &Path) -> Option<CachedLocation> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Write the location to the cache file, creating its directory if needed.
+fn save_cache(path: &Path, cached: &CachedLocation) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(cached).map_err(std::io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    /// Geolocator returning a fixed result and counting lookups.
+    struct FakeGeolocator {
+        result: Result<GeoLocation, String>,
+        calls: AtomicU32,
+    }
+
+    impl FakeGeolocator {
+        fn new(result: Result<GeoLocation, String>) -> Self {
+            Self {
+                result,
+                calls: AtomicU32::new(0),
+            }
+        }
+    }
+
+    impl Geolocator for FakeGeolocator {
+        fn locate(&self) -> Result<GeoLocation, String> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.result.clone()
+        }
+    }
+
+    fn seoul() -> GeoLocation {
+        GeoLocation {
+            latitude: 37.57,
+            longitude: 126.98,
+            city: Some("Seoul".to_string()),
+        }
+    }
+
+    fn temp_cache_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("sigye-location-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(Location::parse(""), Ok(Location::Auto));
+        assert_eq!(Location::parse("  "), Ok(Location::Auto));
+        assert_eq!(
+            Location::parse("37.57,126.98"),
+            Ok(Location::Coordinates {
+                latitude: 37.57,
+                longitude: 126.98
+            })
+        );
+        assert_eq!(
+            Location::parse(" -33.9 , 18.4 "),
+            Ok(Location::Coordinates {
+                latitude: -33.9,
+                longitude: 18.4
+            })
+        );
+        assert_eq!(
+            Location::parse("Seoul, Korea"),
+            Ok(Location::Place("Seoul, Korea".to_string()))
+        );
+        assert_eq!(
+            Location::parse(" Oslo "),
+            Ok(Location::Place("Oslo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_location_out_of_range() {
+        assert!(Location::parse("95,10").is_err());
+        assert!(Location::parse("10,-181").is_err());
+        assert_eq!(parse_coordinates("95,10"), None);
+        assert_eq!(parse_coordinates("Seoul"), None);
+    }
+
+    #[test]
+    fn test_location_query() {
+        assert_eq!(Location::Auto.query(), "");
+        assert_eq!(Location::parse("37.5,127").unwrap().query(), "37.5,127");
+        assert_eq!(Location::Place("Oslo".to_string()).query(), "Oslo");
+        assert_eq!(seoul().query(), "37.57,126.98");
+    }
+
+    #[test]
+    fn test_parse_ipinfo() {
+        let response: IpInfoResponse =
+            serde_json::from_str(r#"{"ip":"1.2.3.4","city":"Seoul","loc":"37.5660,126.9784"}"#)
+                .unwrap();
+        let location = parse_ipinfo(response).unwrap();
+        assert_eq!(location.latitude, 37.566);
+        assert_eq!(location.longitude, 126.9784);
+        assert_eq!(location.city.as_deref(), Some("Seoul"));
+
+        let response: IpInfoResponse = serde_json::from_str(r#"{"ip":"1.2.3.4"}"#).unwrap();
+        assert!(parse_ipinfo(response).is_err());
+    }
+
+    #[test]
+    fn test_detect_caches_location() {
+        let path = temp_cache_path("detect.json");
+        let geolocator = FakeGeolocator::new(Ok(seoul()));
+
+        assert_eq!(detect(&geolocator, &path, 1000), Ok(seoul()));
+        assert_eq!(detect(&geolocator, &path, 2000), Ok(seoul()));
+        assert_eq!(geolocator.calls.load(Ordering::Relaxed), 1);
+
+        // Looked up again once the cache is a day old
+        let later = 1000 + MAX_LOCATION_AGE.as_secs();
+        assert_eq!(detect(&geolocator, &path, later), Ok(seoul()));
+        assert_eq!(geolocator.calls.load(Ordering::Relaxed), 2);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_detect_failure_is_not_cached() {
+        let path = temp_cache_path("failure.json");
+        let failing = FakeGeolocator::new(Err("offline".to_string()));
+        assert_eq!(detect(&failing, &path, 1000), Err("offline".to_string()));
+        assert!(load_cache(&path).is_none());
+    }
+
+    #[test]
+    fn test_cached_location_validity() {
+        let cached = CachedLocation {
+            location: seoul(),
+            detected_at: 1000,
+        };
+        assert!(cached.is_valid(1000));
+        assert!(!cached.is_valid(1000 + MAX_LOCATION_AGE.as_secs()));
+        // A lookup time in the future is never trusted
+        assert!(!cached.is_valid(999));
+    }
+}
@@ -0,0 +1,1488 @@
+//! Weather data fetching for dynamic weather background.
+//!
+//! Fetches weather data from wttr.in or Open-Meteo (failing over between them),
+//! along with the planetary Kp index for the aurora, and maps conditions to
+//! background styles. The last successful fetch is cached on disk
+//! so startup shows the right weather.
+
+mod kp;
+mod location;
+mod open_meteo;
+mod wttr;
+
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local, NaiveDate, Offset, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use sigye_config::Config;
+use sigye_core::{
+    BackgroundStyle, TemperatureUnit, TimeOfDay, WeatherCondition, WeatherSource,
+    moon::{moon_illumination, moon_phase},
+    precipitation, solar,
+};
+
+use crate::app_metrics::AppMetrics;
+use crate::status::Status;
+use crate::worker::{JOIN_TIMEOUT, Wakeup, Worker};
+pub use location::Location;
+use location::{GeoLocation, Geolocator, IpInfo};
+use open_meteo::OpenMeteo;
+use wttr::Wttr;
+
+/// How often to fetch new weather data (30 minutes).
+const FETCH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// How long weather data stays usable, fetched or cached (2 hours).
+const MAX_DATA_AGE: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// First retry delay after a failed fetch, doubled on each further failure.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+/// How often the time of day is recomputed between fetches.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Timeout for HTTP requests.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Civil twilight duration in minutes (~30 minutes before sunrise / after sunset).
+const CIVIL_TWILIGHT_MINUTES: u32 = 30;
+
+/// Sunrise and sunset (minutes since midnight) assumed before any weather data arrives.
+const NOMINAL_SUN_TIMES: (u32, u32) = (6 * 60, 18 * 60);
+
+/// Minutes in a day, for wrapping times of day.
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// Lit fraction of the moon above which a clear night shows Moonlight.
+const MOONLIT_ILLUMINATION: f32 = 0.4;
+
+/// Weather data parsed from a provider's response.
+#[derive(Debug, Clone)]
+pub struct WeatherData {
+    /// Current weather condition.
+    pub condition: WeatherCondition,
+    /// Temperature in Celsius.
+    pub temp_c: i32,
+    /// Wind speed in km/h (for the condition override and blowing rain and snow).
+    pub wind_kmph: u32,
+    /// How heavily rain or snow falls (0.0 - 1.0), moderate when dry.
+    pub intensity: f32,
+    /// Time of day for weather-aware rendering.
+    pub time_of_day: TimeOfDay,
+    /// Latitude (for aurora calculation).
+    pub latitude: f32,
+    /// Longitude, if known (for the local sunrise/sunset calculation).
+    pub longitude: Option<f32>,
+    /// Sunrise and sunset in minutes since midnight, if reported.
+    pub sun_times: Option<(u32, u32)>,
+    /// Place name reported by the provider, if any.
+    pub location_name: Option<String>,
+    /// Planetary Kp index (0 - 9), if fetched (for the aurora).
+    pub kp_index: Option<f32>,
+    /// Timestamp when this data was fetched.
+    pub fetched_at: Instant,
+}
+
+impl WeatherData {
+    /// Build freshly fetched weather data, deriving wind override and time of day.
+    ///
+    /// The intensity is typical for the condition until set with [`Self::with_intensity`].
+    fn new(
+        condition: WeatherCondition,
+        temp_c: i32,
+        wind_kmph: u32,
+        latitude: f32,
+        longitude: Option<f32>,
+        sun_times: Option<(u32, u32)>,
+        location_name: Option<String>,
+    ) -> Self {
+        // Check for high wind override
+        let condition = if wind_kmph > 50
+            && !matches!(
+                condition,
+                WeatherCondition::Thunderstorm | WeatherCondition::HeavyRain
+            ) {
+            WeatherCondition::Windy
+        } else {
+            condition
+        };
+
+        Self {
+            intensity: precipitation::condition_intensity(condition),
+            condition,
+            temp_c,
+            wind_kmph,
+            time_of_day: time_of_day_at(&Local::now(), latitude, longitude, sun_times),
+            latitude,
+            longitude,
+            sun_times,
+            location_name,
+            kp_index: None,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    /// Set how heavily rain or snow falls, e.g. from the provider's weather code.
+    fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = precipitation::clamp_intensity(intensity);
+        self
+    }
+
+    /// Recompute the time of day for the current clock.
+    fn refresh_time_of_day(&mut self) {
+        self.time_of_day =
+            time_of_day_at(&Local::now(), self.latitude, self.longitude, self.sun_times);
+    }
+
+    /// How strong and tall the aurora is with the fetched Kp index, 1.0 without one.
+    pub fn aurora_activity(&self) -> f32 {
+        kp::aurora_activity(self.kp_index)
+    }
+
+    /// Check if this weather data is still usable (less than 2 hours old).
+    pub fn is_fresh(&self) -> bool {
+        self.fetched_at.elapsed() < MAX_DATA_AGE
+    }
+}
+
+impl Default for WeatherData {
+    fn default() -> Self {
+        Self {
+            condition: WeatherCondition::Clear,
+            temp_c: 20,
+            wind_kmph: 0,
+            intensity: precipitation::MODERATE,
+            time_of_day: TimeOfDay::Day,
+            latitude: 0.0,
+            longitude: None,
+            sun_times: None,
+            location_name: None,
+            kp_index: None,
+            fetched_at: Instant::now(),
+        }
+    }
+}
+
+/// Text line describing the current weather, e.g. "☀ 23°C, Clear — Seoul".
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherLine {
+    /// Full line text.
+    pub text: String,
+    /// Temperature in Celsius and its char range in `text`, for coloring.
+    pub temperature: Option<(i32, Range<usize>)>,
+}
+
+impl WeatherLine {
+    /// Format the weather line, or a placeholder while nothing has been fetched yet.
+    /// `location` is shown when the provider didn't report a place name, and a
+    /// failed last fetch is marked as offline.
+    pub fn new(
+        data: Option<&WeatherData>,
+        status: &FetchStatus,
+        unit: TemperatureUnit,
+        location: &str,
+    ) -> Self {
+        let Some(data) = data else {
+            let text = match status {
+                FetchStatus::Failed(_) => "Weather unavailable",
+                _ => "Fetching weather…",
+            };
+            return Self {
+                text: text.to_string(),
+                temperature: None,
+            };
+        };
+
+        let condition = data.condition;
+        let prefix = format!("{} ", condition.icon());
+        let temp = format!("{}{}", unit.convert(data.temp_c), unit.symbol());
+        let mut text = format!("{prefix}{temp}, {}", condition.label());
+
+        let place = data.location_name.as_deref().unwrap_or(location).trim();
+        if !place.is_empty() {
+            text.push_str(" — ");
+            text.push_str(place);
+        }
+        if matches!(status, FetchStatus::Failed(_)) {
+            text.push_str(" (offline)");
+        }
+
+        let start = prefix.chars().count();
+        Self {
+            text,
+            temperature: Some((data.temp_c, start..start + temp.chars().count())),
+        }
+    }
+}
+
+/// On-disk form of [`WeatherData`], using wall-clock time instead of `Instant`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedWeather {
+    /// Location the data was fetched for (empty for auto-detect).
+    location: String,
+    condition: WeatherCondition,
+    temp_c: i32,
+    wind_kmph: u32,
+    /// How heavily rain or snow falls, if cached by a version that knew.
+    #[serde(default)]
+    intensity: Option<f32>,
+    latitude: f32,
+    #[serde(default)]
+    longitude: Option<f32>,
+    /// Sunrise and sunset in minutes since midnight, if reported.
+    sun_times: Option<(u32, u32)>,
+    /// Place name reported by the provider, if any.
+    #[serde(default)]
+    location_name: Option<String>,
+    /// Planetary Kp index, if fetched.
+    #[serde(default)]
+    kp_index: Option<f32>,
+    /// Unix timestamp (seconds) of the fetch.
+    fetched_at: u64,
+}
+
+impl CachedWeather {
+    /// Capture fetched weather data for the cache.
+    fn from_data(location: &str, data: &WeatherData, now_unix: u64) -> Self {
+        Self {
+            location: location.to_string(),
+            condition: data.condition,
+            temp_c: data.temp_c,
+            wind_kmph: data.wind_kmph,
+            intensity: Some(data.intensity),
+            latitude: data.latitude,
+            longitude: data.longitude,
+            sun_times: data.sun_times,
+            location_name: data.location_name.clone(),
+            kp_index: data.kp_index,
+            fetched_at: now_unix.saturating_sub(data.fetched_at.elapsed().as_secs()),
+        }
+    }
+
+    /// Age of the cached data, or `None` if it claims to come from the future.
+    fn age(&self, now_unix: u64) -> Option<Duration> {
+        now_unix
+            .checked_sub(self.fetched_at)
+            .map(Duration::from_secs)
+    }
+
+    /// Check if the cache is for this location and young enough to use.
+    fn is_valid(&self, location: &str, now_unix: u64) -> bool {
+        self.location == location && self.age(now_unix).is_some_and(|age| age < MAX_DATA_AGE)
+    }
+
+    /// Rebuild weather data, recomputing time of day for the current clock.
+    fn to_data(&self, now_unix: u64) -> WeatherData {
+        let age = self.age(now_unix).unwrap_or_default();
+        let now = Instant::now();
+        let mut data = WeatherData {
+            condition: self.condition,
+            temp_c: self.temp_c,
+            wind_kmph: self.wind_kmph,
+            intensity: self.intensity.map_or_else(
+                || precipitation::condition_intensity(self.condition),
+                precipitation::clamp_intensity,
+            ),
+            time_of_day: TimeOfDay::Day,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            sun_times: self.sun_times,
+            location_name: self.location_name.clone(),
+            kp_index: self.kp_index,
+            fetched_at: now.checked_sub(age).unwrap_or(now),
+        };
+        data.refresh_time_of_day();
+        data
+    }
+}
+
+/// A source of current weather data.
+trait WeatherProvider: Send {
+    /// Provider name for error messages.
+    fn name(&self) -> &'static str;
+
+    /// Fetch current weather for a location (empty for auto-detect).
+    fn fetch(&self, location: &str) -> Result<WeatherData, String>;
+}
+
+/// Create the provider for a configured weather source.
+fn provider(source: WeatherSource) -> Box<dyn WeatherProvider> {
+    match source {
+        WeatherSource::Wttr => Box::new(Wttr),
+        WeatherSource::OpenMeteo => Box::new(OpenMeteo),
+    }
+}
+
+/// Fetch weather from each provider in turn until one succeeds.
+fn fetch_with_failover(
+    providers: &[Box<dyn WeatherProvider>],
+    location: &str,
+) -> Result<WeatherData, String> {
+    let mut errors = Vec::new();
+    for provider in providers {
+        match provider.fetch(location) {
+            Ok(data) => return Ok(data),
+            Err(e) => errors.push(format!("{}: {e}", provider.name())),
+        }
+    }
+    Err(errors.join("; "))
+}
+
+/// Build an HTTP agent with the request timeout applied.
+fn http_agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .new_agent()
+}
+
+/// Progress of the most recent weather fetch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// Nothing has been fetched yet.
+    #[default]
+    Idle,
+    /// A fetch is in progress.
+    Fetching,
+    /// The last fetch succeeded, or valid data was loaded from the cache.
+    Live,
+    /// The last fetch failed with this error.
+    Failed(String),
+}
+
+/// Update sent from the fetch thread to the app.
+#[derive(Debug, Clone)]
+pub enum WeatherEvent {
+    /// A fetch has started.
+    Fetching,
+    /// New weather data arrived, fetched or loaded from the cache.
+    Fetched(WeatherData),
+    /// A fetch failed with this error.
+    FetchFailed(String),
+    /// The location was detected from the IP address.
+    Located(GeoLocation),
+    /// Detecting the location failed with this error.
+    LocateFailed(String),
+}
+
+/// The app's view of the weather: the latest data and how the last fetch went.
+#[derive(Debug, Default)]
+pub struct WeatherState {
+    /// Latest weather data, if any arrived.
+    data: Option<WeatherData>,
+    /// Status of the most recent fetch.
+    status: FetchStatus,
+    /// Location detected from the IP address, if auto-detecting.
+    located: Option<GeoLocation>,
+    /// Error of the last fetch, until one succeeds.
+    fetch_error: Option<String>,
+    /// Error of detecting the location, if it failed.
+    locate_error: Option<String>,
+    /// When the time of day was last recomputed.
+    refreshed_at: Option<Instant>,
+}
+
+impl WeatherState {
+    /// Apply all pending events, returning the errors of failed fetches.
+    pub fn drain(&mut self, events: &Receiver<WeatherEvent>) -> Vec<String> {
+        events
+            .try_iter()
+            .filter_map(|event| self.apply(event))
+            .collect()
+    }
+
+    /// Apply one event, returning the error if it reports a failed fetch.
+    fn apply(&mut self, event: WeatherEvent) -> Option<String> {
+        match event {
+            WeatherEvent::Fetching => self.status = FetchStatus::Fetching,
+            WeatherEvent::Fetched(data) => {
+                self.data = Some(data);
+                self.status = FetchStatus::Live;
+                self.fetch_error = None;
+                self.refreshed_at = Some(Instant::now());
+            }
+            WeatherEvent::FetchFailed(error) => {
+                self.status = FetchStatus::Failed(error.clone());
+                self.fetch_error = Some(error.clone());
+                return Some(error);
+            }
+            WeatherEvent::Located(location) => {
+                self.located = Some(location);
+                self.locate_error = None;
+            }
+            WeatherEvent::LocateFailed(error) => self.locate_error = Some(error),
+        }
+        None
+    }
+
+    /// Keep dawn/day/dusk/night current between fetches, even offline.
+    /// Recomputes at most every [`CHECK_INTERVAL`].
+    pub fn refresh_time_of_day(&mut self, now: Instant) {
+        if self
+            .refreshed_at
+            .is_some_and(|at| now.saturating_duration_since(at) < CHECK_INTERVAL)
+        {
+            return;
+        }
+        if let Some(data) = &mut self.data {
+            data.refresh_time_of_day();
+        }
+        self.refreshed_at = Some(now);
+    }
+
+    /// Latest weather data, if any.
+    pub fn data(&self) -> Option<&WeatherData> {
+        self.data.as_ref()
+    }
+
+    /// Status of the most recent fetch.
+    pub fn status(&self) -> &FetchStatus {
+        &self.status
+    }
+
+    /// Health of the weather: degraded while failing fetches leave earlier data
+    /// in use, failed once there is none young enough.
+    pub fn health(&self) -> Status {
+        match &self.fetch_error {
+            None => Status::Ok,
+            Some(error) if !self.is_stale() => {
+                Status::Degraded(format!("{error}; showing earlier weather"))
+            }
+            Some(error) => Status::Failed(error.clone()),
+        }
+    }
+
+    /// Health of detecting the location; without it the provider guesses from
+    /// the IP address on its own.
+    pub fn location_health(&self) -> Status {
+        match &self.locate_error {
+            None => Status::Ok,
+            Some(error) => Status::Degraded(format!("{error}; the provider guesses instead")),
+        }
+    }
+
+    /// City of the detected location, if known.
+    pub fn city(&self) -> Option<&str> {
+        self.located.as_ref()?.city.as_deref()
+    }
+
+    /// Check if there is no weather data young enough to use.
+    pub fn is_stale(&self) -> bool {
+        self.data.as_ref().is_none_or(|data| !data.is_fresh())
+    }
+
+    /// Background style for the current weather on `date`, or the starfield
+    /// without usable data.
+    pub fn background(&self, date: NaiveDate) -> BackgroundStyle {
+        match &self.data {
+            Some(data) if !self.is_stale() => map_weather_to_background(data, date),
+            _ => BackgroundStyle::Starfield,
+        }
+    }
+
+    /// Current time of day for weather-aware rendering.
+    /// Until weather data is available, assumes a nominal 06:00 - 18:00 day.
+    pub fn time_of_day(&self) -> TimeOfDay {
+        self.data
+            .as_ref()
+            .map(|data| data.time_of_day)
+            .unwrap_or_else(|| nominal_time_of_day(&Local::now()))
+    }
+}
+
+/// Weather monitor that fetches weather data in a background thread.
+///
+/// Results arrive as [`WeatherEvent`]s on [`WeatherMonitor::events`], to be
+/// applied to a [`WeatherState`].
+#[derive(Debug)]
+pub struct WeatherMonitor {
+    /// Where to fetch the weather for.
+    location: Location,
+    /// Preferred weather provider.
+    source: WeatherSource,
+    /// Whether to fetch the Kp index along with the weather.
+    kp_index: bool,
+    /// Counters of fetches that succeeded and failed.
+    metrics: Arc<AppMetrics>,
+    /// Sending side of the event channel, cloned into the fetch thread.
+    sender: Sender<WeatherEvent>,
+    /// Events not yet applied by the app.
+    events: Receiver<WeatherEvent>,
+    /// Fetch thread, while running.
+    worker: Mutex<Option<Worker>>,
+}
+
+impl WeatherMonitor {
+    /// Create a new weather monitor using `source` first, failing over to the other provider.
+    pub fn new(location: Location, source: WeatherSource) -> Self {
+        let (sender, events) = mpsc::channel();
+        Self {
+            location,
+            source,
+            kp_index: true,
+            metrics: Arc::default(),
+            sender,
+            events,
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Fetch the Kp index along with the weather (the default), or leave the
+    /// aurora to the latitude alone.
+    pub fn with_kp_index(mut self, enabled: bool) -> Self {
+        self.kp_index = enabled;
+        self
+    }
+
+    /// Count fetches in `metrics`, e.g. the app's.
+    pub fn with_metrics(mut self, metrics: Arc<AppMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Start the background fetching thread.
+    pub fn start(&self) {
+        let Ok(mut worker) = self.worker.lock() else {
+            return;
+        };
+        if worker.is_some() {
+            return; // Already running
+        }
+
+        let sender = self.sender.clone();
+        let location = self.location.clone();
+        // Cached weather is keyed by the configured location, not the detected one
+        let cache_key = location.query();
+        let providers = [provider(self.source), provider(self.source.fallback())];
+        let kp_index = self.kp_index;
+        let metrics = Arc::clone(&self.metrics);
+
+        // Show cached weather right away instead of the fallback
+        let mut next_fetch = Instant::now();
+        if let Some(cached) = load_cache(&cache_file_path())
+            && cached.is_valid(&cache_key, unix_now())
+        {
+            let data = cached.to_data(unix_now());
+            // Only refetch immediately if the cache is older than the fetch interval
+            next_fetch = data.fetched_at + FETCH_INTERVAL;
+            let _ = sender.send(WeatherEvent::Fetched(data));
+        }
+
+        *worker = Some(Worker::spawn(move |stop| {
+            let mut failures = 0;
+            let query = match location {
+                Location::Auto => resolve_auto_location(&IpInfo, &sender),
+                _ => location.query(),
+            };
+
+            loop {
+                // Fetch new data once due, backing off after failures
+                if Instant::now() >= next_fetch {
+                    if fetch_and_send(&providers, &query, &cache_key, kp_index, &metrics, &sender) {
+                        failures = 0;
+                        next_fetch = Instant::now() + FETCH_INTERVAL;
+                    } else {
+                        failures += 1;
+                        next_fetch = Instant::now() + retry_delay(failures, jitter());
+                    }
+                }
+
+                // Wait until the next fetch, waking at once to refresh or stop
+                let until_fetch = next_fetch.saturating_duration_since(Instant::now());
+                match stop.wait_for_wakeup(until_fetch) {
+                    Wakeup::Stop => break,
+                    Wakeup::Woken => next_fetch = Instant::now(),
+                    Wakeup::Timeout => {}
+                }
+            }
+        }));
+    }
+
+    /// Stop the background thread, joining it unless it's stuck in a request.
+    pub fn stop(&self) {
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        if let Some(worker) = worker {
+            worker.stop(JOIN_TIMEOUT);
+        }
+    }
+
+    /// Ask the fetch thread to fetch right away instead of waiting for the next interval.
+    pub fn refresh(&self) {
+        if let Ok(worker) = self.worker.lock()
+            && let Some(worker) = worker.as_ref()
+        {
+            worker.wake();
+        }
+    }
+
+    /// Events sent by the fetch thread, to be drained into a [`WeatherState`].
+    pub fn events(&self) -> &Receiver<WeatherEvent> {
+        &self.events
+    }
+}
+
+impl Default for WeatherMonitor {
+    fn default() -> Self {
+        Self::new(Location::Auto, WeatherSource::default())
+    }
+}
+
+impl Drop for WeatherMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Detect the location for auto-detect mode, returning the query for providers.
+///
+/// Falls back to an empty query, which lets wttr.in geolocate server-side.
+fn resolve_auto_location(geolocator: &dyn Geolocator, events: &Sender<WeatherEvent>) -> String {
+    match location::detect(geolocator, &location::cache_file_path(), unix_now()) {
+        Ok(located) => {
+            let query = located.query();
+            let _ = events.send(WeatherEvent::Located(located));
+            query
+        }
+        Err(e) => {
+            let _ = events.send(WeatherEvent::LocateFailed(e));
+            String::new()
+        }
+    }
+}
+
+/// Fetch weather data for `location`, and the Kp index with `kp_index` where
+/// the aurora may show, save it to the disk cache under `cache_key` and report
+/// progress on `events`, counting the fetch in `metrics`. Returns true if the
+/// weather fetch succeeded.
+fn fetch_and_send(
+    providers: &[Box<dyn WeatherProvider>],
+    location: &str,
+    cache_key: &str,
+    kp_index: bool,
+    metrics: &AppMetrics,
+    events: &Sender<WeatherEvent>,
+) -> bool {
+    let _ = events.send(WeatherEvent::Fetching);
+    let result = fetch_with_failover(providers, location);
+    metrics.record_weather_fetch(result.is_ok());
+    match result {
+        Ok(mut data) => {
+            // Without the Kp index the aurora follows the latitude alone
+            if kp_index && data.latitude.abs() >= kp::MIN_AURORA_LATITUDE {
+                data.kp_index = kp::fetch().ok();
+            }
+
+            // The cache is best-effort; a read-only cache dir just means no cache
+            let cached = CachedWeather::from_data(cache_key, &data, unix_now());
+            let _ = save_cache(&cache_file_path(), &cached);
+
+            let _ = events.send(WeatherEvent::Fetched(data));
+            true
+        }
+        Err(e) => {
+            let _ = events.send(WeatherEvent::FetchFailed(e));
+            false
+        }
+    }
+}
+
+/// Delay before retrying after `failures` consecutive failed fetches.
+///
+/// Doubles from [`RETRY_BASE_DELAY`] up to [`FETCH_INTERVAL`], scaled by
+/// `jitter` (0.0 - 1.0) into 50-100% of that so clients don't retry in lockstep.
+fn retry_delay(failures: u32, jitter: f32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(1 << exponent)
+        .min(FETCH_INTERVAL);
+    delay.mul_f32(0.5 + jitter.clamp(0.0, 1.0) * 0.5)
+}
+
+/// Cheap jitter source (0.0 - 1.0) from the sub-second clock.
+fn jitter() -> f32 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    nanos as f32 / 1_000_000_000.0
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Path of the weather cache file.
+fn cache_file_path() -> PathBuf {
+    Config::cache_dir().join("weather.json")
+}
+
+/// Load cached weather, or `None` if missing or unreadable.
+fn load_cache(path: &Path) -> Option<CachedWeather> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Write weather to the cache file, creating its directory if needed.
+fn save_cache(path: &Path, cached: &CachedWeather) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(cached).map_err(std::io::Error::other)?;
+    fs::write(path, content)
+}
+
+/// Time of day at `now` without weather data, assuming sunrise at 6:00 and sunset at 18:00.
+pub fn nominal_time_of_day<Tz: TimeZone>(now: &DateTime<Tz>) -> TimeOfDay {
+    time_of_day_at(now, 0.0, None, Some(NOMINAL_SUN_TIMES))
+}
+
+/// Determine the time of day at `now`.
+///
+/// Sunrise and sunset are computed locally from the coordinates when the longitude
+/// is known, falling back to the provider's reported `sun_times` otherwise.
+fn time_of_day_at<Tz: TimeZone>(
+    now: &DateTime<Tz>,
+    latitude: f32,
+    longitude: Option<f32>,
+    sun_times: Option<(u32, u32)>,
+) -> TimeOfDay {
+    let current_minutes = now.hour() * 60 + now.minute();
+
+    if let Some(longitude) = longitude {
+        let date = now.date_naive();
+        return match solar::sunrise_sunset(latitude.into(), longitude.into(), date) {
+            Some((sunrise, sunset)) => {
+                // Shift the UTC times into the clock's time zone
+                let offset = now.offset().fix().local_minus_utc() / 60;
+                let to_local = |t: chrono::NaiveTime| {
+                    (t.hour() as i32 * 60 + t.minute() as i32 + offset).rem_euclid(MINUTES_PER_DAY)
+                        as u32
+                };
+                classify_time_of_day(current_minutes, to_local(sunrise), to_local(sunset))
+            }
+            None => solar::polar_time_of_day(latitude.into(), date),
+        };
+    }
+
+    match sun_times {
+        Some((sunrise_mins, sunset_mins)) => {
+            classify_time_of_day(current_minutes, sunrise_mins, sunset_mins)
+        }
+        None => TimeOfDay::Day, // Default to day
+    }
+}
+
+/// Classify a time (minutes since midnight) against sunrise and sunset.
+/// Handles days that wrap past midnight in the clock's time zone.
+fn classify_time_of_day(current_minutes: u32, sunrise_mins: u32, sunset_mins: u32) -> TimeOfDay {
+    let since = |from: u32, to: u32| (to as i32 - from as i32).rem_euclid(MINUTES_PER_DAY) as u32;
+
+    if (1..=CIVIL_TWILIGHT_MINUTES).contains(&since(current_minutes, sunrise_mins)) {
+        TimeOfDay::Dawn
+    } else if since(sunset_mins, current_minutes) < CIVIL_TWILIGHT_MINUTES {
+        TimeOfDay::Dusk
+    } else if since(sunrise_mins, current_minutes) < since(sunrise_mins, sunset_mins) {
+        TimeOfDay::Day
+    } else {
+        TimeOfDay::Night
+    }
+}
+
+/// Map weather data to the appropriate background style.
+fn map_weather_to_background(weather: &WeatherData, date: NaiveDate) -> BackgroundStyle {
+    // Twilight for clear or partly cloudy conditions during dawn/dusk
+    if weather.time_of_day == TimeOfDay::Dawn
+        && matches!(
+            weather.condition,
+            WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+        )
+    {
+        return BackgroundStyle::TwilightDawn;
+    }
+    if weather.time_of_day == TimeOfDay::Dusk
+        && matches!(
+            weather.condition,
+            WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+        )
+    {
+        return BackgroundStyle::TwilightDusk;
+    }
+
+    // Special case: Aurora for clear nights at high latitudes (> 55°, lower
+    // during geomagnetic storms)
+    if weather.time_of_day == TimeOfDay::Night
+        && weather.condition == WeatherCondition::Clear
+        && weather.latitude.abs() > kp::aurora_latitude(weather.kp_index)
+    {
+        return BackgroundStyle::Aurora;
+    }
+
+    // Night + Clear = Moonlight while the moon is bright, Starfield otherwise
+    if weather.time_of_day == TimeOfDay::Night && weather.condition == WeatherCondition::Clear {
+        if moon_illumination(moon_phase(date)) > MOONLIT_ILLUMINATION {
+            return BackgroundStyle::Moonlight;
+        }
+        return BackgroundStyle::Starfield;
+    }
+
+    // Dry days below freezing get Frost, as deep as it is cold
+    if weather.temp_c < 0
+        && matches!(
+            weather.condition,
+            WeatherCondition::Clear | WeatherCondition::PartlyCloudy | WeatherCondition::Cloudy
+        )
+    {
+        return BackgroundStyle::Frost;
+    }
+
+    // Map by condition
+    match weather.condition {
+        WeatherCondition::Clear => BackgroundStyle::Sunny,
+        WeatherCondition::PartlyCloudy => BackgroundStyle::Cloudy,
+        WeatherCondition::Cloudy => BackgroundStyle::Cloudy,
+        WeatherCondition::Rain => BackgroundStyle::Rainy,
+        WeatherCondition::HeavyRain => BackgroundStyle::Stormy,
+        WeatherCondition::Thunderstorm => BackgroundStyle::Stormy,
+        WeatherCondition::Snow => BackgroundStyle::Snowfall,
+        WeatherCondition::Fog => BackgroundStyle::Foggy,
+        WeatherCondition::Windy => BackgroundStyle::Windy,
+        WeatherCondition::VeryCold => BackgroundStyle::Frost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use super::*;
+
+    /// A new moon (the 2024 solar eclipse), when clear nights stay starry.
+    fn new_moon() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 4, 8).unwrap()
+    }
+
+    #[test]
+    fn test_clear_nights_follow_the_moon() {
+        let clear_night = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Night,
+            temp_c: 15,
+            latitude: 40.0,
+            ..Default::default()
+        };
+        let background = |year, month, day| {
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            map_weather_to_background(&clear_night, date)
+        };
+        // Full moon, and a waxing gibbous three days before it
+        assert_eq!(background(2025, 3, 14), BackgroundStyle::Moonlight);
+        assert_eq!(background(2025, 3, 11), BackgroundStyle::Moonlight);
+        // Thin crescent a few days after the new moon
+        assert_eq!(background(2024, 4, 11), BackgroundStyle::Starfield);
+
+        // The aurora still wins far north, and cloud hides the moon
+        let aurora_night = WeatherData {
+            latitude: 65.0,
+            ..clear_night.clone()
+        };
+        let full_moon = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        assert_eq!(
+            map_weather_to_background(&aurora_night, full_moon),
+            BackgroundStyle::Aurora
+        );
+        let cloudy_night = WeatherData {
+            condition: WeatherCondition::Cloudy,
+            ..clear_night
+        };
+        assert_eq!(
+            map_weather_to_background(&cloudy_night, full_moon),
+            BackgroundStyle::Cloudy
+        );
+    }
+
+    #[test]
+    fn test_map_weather_to_background() {
+        let sunny_day = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Day,
+            temp_c: 25,
+            latitude: 40.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&sunny_day, new_moon()),
+            BackgroundStyle::Sunny
+        );
+
+        let clear_night = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Night,
+            temp_c: 15,
+            latitude: 40.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&clear_night, new_moon()),
+            BackgroundStyle::Starfield
+        );
+
+        let aurora_night = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Night,
+            temp_c: -5,
+            latitude: 65.0, // High latitude
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&aurora_night, new_moon()),
+            BackgroundStyle::Aurora
+        );
+
+        let very_cold = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Day,
+            temp_c: -2, // Below freezing
+            latitude: 40.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&very_cold, new_moon()),
+            BackgroundStyle::Frost
+        );
+        let frosty_overcast = WeatherData {
+            condition: WeatherCondition::Cloudy,
+            ..very_cold.clone()
+        };
+        assert_eq!(
+            map_weather_to_background(&frosty_overcast, new_moon()),
+            BackgroundStyle::Frost
+        );
+        // Snow keeps falling however cold it is
+        let cold_snow = WeatherData {
+            condition: WeatherCondition::Snow,
+            temp_c: -15,
+            ..very_cold.clone()
+        };
+        assert_eq!(
+            map_weather_to_background(&cold_snow, new_moon()),
+            BackgroundStyle::Snowfall
+        );
+
+        // Twilight tests
+        let dawn = WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Dawn,
+            temp_c: 15,
+            latitude: 40.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&dawn, new_moon()),
+            BackgroundStyle::TwilightDawn
+        );
+
+        let dusk = WeatherData {
+            condition: WeatherCondition::PartlyCloudy,
+            time_of_day: TimeOfDay::Dusk,
+            temp_c: 20,
+            latitude: 40.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            map_weather_to_background(&dusk, new_moon()),
+            BackgroundStyle::TwilightDusk
+        );
+    }
+
+    /// Local time in a fixed-offset time zone.
+    fn at(offset_hours: i32, y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(offset_hours * 3600)
+            .unwrap()
+            .with_ymd_and_hms(y, m, d, h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_aurora_follows_the_kp_index() {
+        let clear_night = |latitude, kp_index| WeatherData {
+            condition: WeatherCondition::Clear,
+            time_of_day: TimeOfDay::Night,
+            latitude,
+            kp_index,
+            ..Default::default()
+        };
+        let background = |weather| map_weather_to_background(&weather, new_moon());
+
+        // Without the Kp index only the latitude counts
+        assert_eq!(background(clear_night(56.0, None)), BackgroundStyle::Aurora);
+        assert_eq!(
+            background(clear_night(51.0, None)),
+            BackgroundStyle::Starfield
+        );
+        // A Kp 7 storm is visible from 50°, in either hemisphere
+        assert_eq!(
+            background(clear_night(51.0, Some(7.0))),
+            BackgroundStyle::Aurora
+        );
+        assert_eq!(
+            background(clear_night(-51.0, Some(7.0))),
+            BackgroundStyle::Aurora
+        );
+        assert_eq!(
+            background(clear_night(48.0, Some(7.0))),
+            BackgroundStyle::Starfield
+        );
+        // Quiet nights keep the latitude-only rule
+        assert_eq!(
+            background(clear_night(56.0, Some(1.0))),
+            BackgroundStyle::Aurora
+        );
+        assert_eq!(
+            background(clear_night(53.0, Some(3.0))),
+            BackgroundStyle::Starfield
+        );
+    }
+
+    #[test]
+    fn test_time_of_day_from_solar_calculation() {
+        // Seoul on the winter solstice: sunrise ~07:43, sunset ~17:17 KST
+        let seoul = |h, m| time_of_day_at(&at(9, 2024, 12, 21, h, m), 37.57, Some(126.98), None);
+        assert_eq!(seoul(7, 30), TimeOfDay::Dawn);
+        assert_eq!(seoul(12, 0), TimeOfDay::Day);
+        assert_eq!(seoul(17, 30), TimeOfDay::Dusk);
+        assert_eq!(seoul(23, 0), TimeOfDay::Night);
+
+        // The local calculation wins over the provider's astronomy
+        let reported = Some((10 * 60, 14 * 60));
+        assert_eq!(
+            time_of_day_at(&at(9, 2024, 12, 21, 9, 0), 37.57, Some(126.98), reported),
+            TimeOfDay::Day
+        );
+    }
+
+    #[test]
+    fn test_time_of_day_in_utc_clock() {
+        // Seoul viewed from a UTC clock: the day spans midnight (22:43 - 08:17)
+        let seoul = |h, m| time_of_day_at(&at(0, 2024, 12, 21, h, m), 37.57, Some(126.98), None);
+        assert_eq!(seoul(3, 0), TimeOfDay::Day);
+        assert_eq!(seoul(12, 0), TimeOfDay::Night);
+    }
+
+    #[test]
+    fn test_time_of_day_polar() {
+        // Tromsø: midnight sun in June, polar night in December
+        let tromso = |m| time_of_day_at(&at(1, 2024, m, 21, 0, 0), 69.65, Some(18.96), None);
+        assert_eq!(tromso(6), TimeOfDay::Day);
+        assert_eq!(tromso(12), TimeOfDay::Night);
+    }
+
+    #[test]
+    fn test_time_of_day_falls_back_to_sun_times() {
+        let noon = at(0, 2024, 6, 21, 12, 0);
+        let sun_times = Some((6 * 60, 18 * 60));
+        assert_eq!(time_of_day_at(&noon, 40.0, None, sun_times), TimeOfDay::Day);
+        assert_eq!(
+            time_of_day_at(&at(0, 2024, 6, 21, 5, 45), 40.0, None, sun_times),
+            TimeOfDay::Dawn
+        );
+        assert_eq!(time_of_day_at(&noon, 40.0, None, None), TimeOfDay::Day);
+    }
+
+    fn cached(location: &str, fetched_at: u64) -> CachedWeather {
+        CachedWeather {
+            location: location.to_string(),
+            condition: WeatherCondition::Snow,
+            temp_c: -3,
+            wind_kmph: 12,
+            intensity: Some(precipitation::HEAVY),
+            latitude: 59.9,
+            longitude: Some(10.75),
+            sun_times: Some((8 * 60 + 15, 15 * 60 + 40)),
+            location_name: Some("Oslo".to_string()),
+            kp_index: Some(3.67),
+            fetched_at,
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("sigye-weather-test-{}", std::process::id()))
+            .join("weather.json");
+        let original = cached("Oslo", 1_700_000_000);
+
+        save_cache(&path, &original).unwrap();
+        let loaded = load_cache(&path);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(loaded, Some(original));
+        assert_eq!(load_cache(&path), None);
+    }
+
+    #[test]
+    fn test_cache_staleness() {
+        let fetched_at = 1_700_000_000;
+        let entry = cached("Oslo", fetched_at);
+
+        assert!(entry.is_valid("Oslo", fetched_at));
+        assert!(entry.is_valid("Oslo", fetched_at + 60 * 60));
+        assert!(!entry.is_valid("Oslo", fetched_at + 2 * 60 * 60));
+        // Different location or a fetch time in the future are never used
+        assert!(!entry.is_valid("Seoul", fetched_at));
+        assert!(!entry.is_valid("Oslo", fetched_at - 1));
+    }
+
+    #[test]
+    fn test_cache_to_data_keeps_age() {
+        let fetched_at = 1_700_000_000;
+        let data = cached("Oslo", fetched_at).to_data(fetched_at + 600);
+
+        assert_eq!(data.condition, WeatherCondition::Snow);
+        assert_eq!(data.temp_c, -3);
+        assert_eq!(data.intensity, precipitation::HEAVY);
+        assert_eq!(data.kp_index, Some(3.67));
+        assert!(data.fetched_at.elapsed() >= Duration::from_secs(600));
+        assert!(data.is_fresh());
+        assert_eq!(
+            CachedWeather::from_data("Oslo", &data, fetched_at + 600).fetched_at,
+            fetched_at
+        );
+
+        // Caches from before intensities were kept fall back to the condition's
+        let mut old = cached("Oslo", fetched_at);
+        old.intensity = None;
+        assert_eq!(
+            old.to_data(fetched_at).intensity,
+            precipitation::condition_intensity(WeatherCondition::Snow)
+        );
+    }
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        assert_eq!(retry_delay(1, 1.0), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(2, 1.0), RETRY_BASE_DELAY * 2);
+        assert_eq!(retry_delay(3, 1.0), RETRY_BASE_DELAY * 4);
+        assert_eq!(retry_delay(1, 0.0), RETRY_BASE_DELAY / 2);
+
+        // Never waits longer than a regular fetch interval
+        assert_eq!(retry_delay(50, 1.0), FETCH_INTERVAL);
+        assert!(retry_delay(u32::MAX, 0.5) <= FETCH_INTERVAL);
+    }
+
+    /// Provider returning a canned result.
+    struct FakeProvider(Result<WeatherCondition, &'static str>);
+
+    impl WeatherProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn fetch(&self, _location: &str) -> Result<WeatherData, String> {
+            self.0
+                .map(|condition| WeatherData::new(condition, 10, 0, 0.0, None, None, None))
+                .map_err(String::from)
+        }
+    }
+
+    #[test]
+    fn test_failover_to_second_provider() {
+        let providers: [Box<dyn WeatherProvider>; 2] = [
+            Box::new(FakeProvider(Err("rate limited"))),
+            Box::new(FakeProvider(Ok(WeatherCondition::Snow))),
+        ];
+        let data = fetch_with_failover(&providers, "Oslo").unwrap();
+        assert_eq!(data.condition, WeatherCondition::Snow);
+
+        let failing: [Box<dyn WeatherProvider>; 2] = [
+            Box::new(FakeProvider(Err("rate limited"))),
+            Box::new(FakeProvider(Err("offline"))),
+        ];
+        assert_eq!(
+            fetch_with_failover(&failing, "Oslo").unwrap_err(),
+            "fake: rate limited; fake: offline"
+        );
+    }
+
+    #[test]
+    fn test_failed_fetch_sends_events() {
+        let (sender, events) = mpsc::channel();
+        let failing: [Box<dyn WeatherProvider>; 1] = [Box::new(FakeProvider(Err("offline")))];
+        let metrics = AppMetrics::default();
+        assert!(!fetch_and_send(
+            &failing, "Oslo", "Oslo", true, &metrics, &sender
+        ));
+        assert!(
+            metrics
+                .encode()
+                .contains("sigye_weather_fetches_total{result=\"failure\"} 1\n")
+        );
+
+        let mut state = WeatherState::default();
+        assert_eq!(state.drain(&events), ["fake: offline"]);
+        assert_eq!(
+            state.status(),
+            &FetchStatus::Failed("fake: offline".to_string())
+        );
+        assert!(state.drain(&events).is_empty());
+    }
+
+    #[test]
+    fn test_weather_state_events() {
+        let (sender, events) = mpsc::channel();
+        let mut state = WeatherState::default();
+        assert_eq!(state.status(), &FetchStatus::Idle);
+        assert!(state.is_stale());
+
+        sender.send(WeatherEvent::Fetching).unwrap();
+        assert!(state.drain(&events).is_empty());
+        assert_eq!(state.status(), &FetchStatus::Fetching);
+        assert_eq!(state.background(new_moon()), BackgroundStyle::Starfield);
+
+        let data = WeatherData {
+            condition: WeatherCondition::Snow,
+            time_of_day: TimeOfDay::Day,
+            ..Default::default()
+        };
+        sender.send(WeatherEvent::Fetched(data)).unwrap();
+        assert!(state.drain(&events).is_empty());
+        assert_eq!(state.status(), &FetchStatus::Live);
+        assert!(!state.is_stale());
+        assert_eq!(state.background(new_moon()), BackgroundStyle::Snowfall);
+
+        // A failed refetch keeps the fresh data but reports the error
+        sender.send(WeatherEvent::Fetching).unwrap();
+        sender
+            .send(WeatherEvent::FetchFailed("timed out".to_string()))
+            .unwrap();
+        assert_eq!(state.drain(&events), ["timed out"]);
+        assert_eq!(
+            state.status(),
+            &FetchStatus::Failed("timed out".to_string())
+        );
+        assert_eq!(state.data().unwrap().condition, WeatherCondition::Snow);
+        assert_eq!(state.background(new_moon()), BackgroundStyle::Snowfall);
+    }
+
+    #[test]
+    fn test_weather_state_located() {
+        let (sender, events) = mpsc::channel();
+        let mut state = WeatherState::default();
+        assert_eq!(state.city(), None);
+
+        sender
+            .send(WeatherEvent::Located(GeoLocation {
+                latitude: 59.91,
+                longitude: 10.75,
+                city: Some("Oslo".to_string()),
+            }))
+            .unwrap();
+        assert!(state.drain(&events).is_empty());
+        assert_eq!(state.city(), Some("Oslo"));
+        assert_eq!(state.status(), &FetchStatus::Idle);
+    }
+
+    #[test]
+    fn test_weather_health() {
+        let (sender, events) = mpsc::channel();
+        let mut state = WeatherState::default();
+        assert_eq!(state.health(), Status::Ok);
+
+        // Failing before any data arrived leaves nothing to show
+        sender
+            .send(WeatherEvent::FetchFailed("offline".to_string()))
+            .unwrap();
+        state.drain(&events);
+        assert_eq!(state.health(), Status::Failed("offline".to_string()));
+
+        sender
+            .send(WeatherEvent::Fetched(WeatherData::default()))
+            .unwrap();
+        state.drain(&events);
+        assert_eq!(state.health(), Status::Ok);
+
+        // Failing later keeps showing the earlier weather, even while refetching
+        sender
+            .send(WeatherEvent::FetchFailed("timed out".to_string()))
+            .unwrap();
+        sender.send(WeatherEvent::Fetching).unwrap();
+        state.drain(&events);
+        assert_eq!(
+            state.health(),
+            Status::Degraded("timed out; showing earlier weather".to_string())
+        );
+    }
+
+    #[test]
+    fn test_location_health() {
+        let (sender, events) = mpsc::channel();
+        let mut state = WeatherState::default();
+        sender
+            .send(WeatherEvent::LocateFailed("ipinfo.io: offline".to_string()))
+            .unwrap();
+        state.drain(&events);
+        assert!(
+            matches!(state.location_health(), Status::Degraded(reason) if reason.starts_with("ipinfo.io: offline"))
+        );
+        // Weather can still work without it
+        assert_eq!(state.health(), Status::Ok);
+
+        sender
+            .send(WeatherEvent::Located(GeoLocation {
+                latitude: 59.91,
+                longitude: 10.75,
+                city: None,
+            }))
+            .unwrap();
+        state.drain(&events);
+        assert_eq!(state.location_health(), Status::Ok);
+    }
+
+    #[test]
+    fn test_weather_state_falls_back_when_stale() {
+        let (sender, events) = mpsc::channel();
+        let mut state = WeatherState::default();
+        let fetched_at = Instant::now()
+            .checked_sub(MAX_DATA_AGE + Duration::from_secs(1))
+            .unwrap();
+        let data = WeatherData {
+            condition: WeatherCondition::Snow,
+            fetched_at,
+            ..Default::default()
+        };
+        sender.send(WeatherEvent::Fetched(data)).unwrap();
+        state.drain(&events);
+
+        assert!(state.is_stale());
+        assert_eq!(state.background(new_moon()), BackgroundStyle::Starfield);
+    }
+
+    #[test]
+    fn test_high_wind_override() {
+        let windy = WeatherData::new(WeatherCondition::Clear, 10, 60, 0.0, None, None, None);
+        assert_eq!(windy.condition, WeatherCondition::Windy);
+        let storm = WeatherData::new(
+            WeatherCondition::Thunderstorm,
+            10,
+            80,
+            0.0,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(storm.condition, WeatherCondition::Thunderstorm);
+    }
+
+    #[test]
+    fn test_weather_line() {
+        let data = WeatherData {
+            condition: WeatherCondition::Clear,
+            temp_c: 23,
+            location_name: Some("Seoul".to_string()),
+            ..Default::default()
+        };
+
+        let line = WeatherLine::new(
+            Some(&data),
+            &FetchStatus::Live,
+            TemperatureUnit::Celsius,
+            "",
+        );
+        assert_eq!(line.text, "☀ 23°C, Clear — Seoul");
+        let (temp_c, range) = line.temperature.unwrap();
+        assert_eq!(temp_c, 23);
+        let temp: String = line
+            .text
+            .chars()
+            .skip(range.start)
+            .take(range.len())
+            .collect();
+        assert_eq!(temp, "23°C");
+
+        let line = WeatherLine::new(
+            Some(&data),
+            &FetchStatus::Live,
+            TemperatureUnit::Fahrenheit,
+            "",
+        );
+        assert_eq!(line.text, "☀ 73°F, Clear — Seoul");
+    }
+
+    #[test]
+    fn test_weather_line_location_fallback() {
+        let data = WeatherData {
+            condition: WeatherCondition::Snow,
+            temp_c: -2,
+            ..Default::default()
+        };
+        assert_eq!(
+            WeatherLine::new(
+                Some(&data),
+                &FetchStatus::Live,
+                TemperatureUnit::Celsius,
+                "Oslo"
+            )
+            .text,
+            "❄ -2°C, Snow — Oslo"
+        );
+        assert_eq!(
+            WeatherLine::new(
+                Some(&data),
+                &FetchStatus::Live,
+                TemperatureUnit::Celsius,
+                ""
+            )
+            .text,
+            "❄ -2°C, Snow"
+        );
+    }
+
+    #[test]
+    fn test_weather_line_before_first_fetch() {
+        let line = WeatherLine::new(
+            None,
+            &FetchStatus::Fetching,
+            TemperatureUnit::Celsius,
+            "Seoul",
+        );
+        assert_eq!(line.text, "Fetching weather…");
+        assert_eq!(line.temperature, None);
+
+        let failed = FetchStatus::Failed("offline".to_string());
+        let line = WeatherLine::new(None, &failed, TemperatureUnit::Celsius, "Seoul");
+        assert_eq!(line.text, "Weather unavailable");
+    }
+
+    #[test]
+    fn test_weather_line_after_failed_fetch() {
+        let data = WeatherData {
+            condition: WeatherCondition::Rain,
+            temp_c: 12,
+            ..Default::default()
+        };
+        let failed = FetchStatus::Failed("offline".to_string());
+        assert_eq!(
+            WeatherLine::new(Some(&data), &failed, TemperatureUnit::Celsius, "Oslo").text,
+            "🌦 12°C, Rain — Oslo (offline)"
+        );
+    }
+
+    #[test]
+    fn test_weather_monitor_creation() {
+        let monitor = WeatherMonitor::new(
+            Location::Place("Seoul".to_string()),
+            WeatherSource::OpenMeteo,
+        );
+        assert!(monitor.events().try_recv().is_err());
+        // Refreshing before the thread starts does nothing
+        monitor.refresh();
+    }
+}
@@ -0,0 +1,252 @@
+//! Open-Meteo weather provider (no API key required).
+
+use serde::Deserialize;
+use sigye_core::{WeatherCondition, precipitation};
+
+use super::location::parse_coordinates;
+use super::{WeatherData, WeatherProvider, http_agent};
+
+/// Fetches weather from Open-Meteo, geocoding city names to coordinates first.
+#[derive(Debug, Default)]
+pub struct OpenMeteo;
+
+/// Geocoding API response (partial).
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    results: Option<Vec<GeocodingResult>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f32,
+    longitude: f32,
+}
+
+/// Forecast API response (partial).
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    current: Current,
+    daily: Option<Daily>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Current {
+    temperature_2m: f32,
+    weather_code: u8,
+    /// Wind speed in km/h (the API default unit).
+    wind_speed_10m: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Daily {
+    /// Local ISO 8601 times like "2024-01-15T08:15".
+    sunrise: Vec<String>,
+    sunset: Vec<String>,
+}
+
+impl WeatherProvider for OpenMeteo {
+    fn name(&self) -> &'static str {
+        "Open-Meteo"
+    }
+
+    fn fetch(&self, location: &str) -> Result<WeatherData, String> {
+        let (latitude, longitude, location_name) = match parse_coordinates(location) {
+            Some((latitude, longitude)) => (latitude, longitude, None),
+            None => {
+                let place = geocode(location)?;
+                (place.latitude, place.longitude, Some(place.name))
+            }
+        };
+
+        let response: ForecastResponse = http_agent()
+            .get("https://api.open-meteo.com/v1/forecast")
+            .query("latitude", latitude.to_string())
+            .query("longitude", longitude.to_string())
+            .query("current", "temperature_2m,weather_code,wind_speed_10m")
+            .query("daily", "sunrise,sunset")
+            .query("timezone", "auto")
+            .query("forecast_days", "1")
+            .call()
+            .map_err(|e| format!("HTTP error: {e}"))?
+            .body_mut()
+            .read_json()
+            .map_err(|e| format!("JSON parse error: {e}"))?;
+
+        let sun_times = response.daily.as_ref().and_then(|daily| {
+            let sunrise = parse_iso_minutes(daily.sunrise.first()?)?;
+            let sunset = parse_iso_minutes(daily.sunset.first()?)?;
+            Some((sunrise, sunset))
+        });
+
+        Ok(WeatherData::new(
+            map_wmo_code(response.current.weather_code),
+            response.current.temperature_2m.round() as i32,
+            response.current.wind_speed_10m.max(0.0).round() as u32,
+            latitude,
+            Some(longitude),
+            sun_times,
+            location_name,
+        )
+        .with_intensity(map_wmo_intensity(response.current.weather_code)))
+    }
+}
+
+/// Look up coordinates for a city name with the Open-Meteo geocoding API.
+fn geocode(location: &str) -> Result<GeocodingResult, String> {
+    // Open-Meteo has no IP geolocation, so auto-detect is left to other providers
+    if location.trim().is_empty() {
+        return Err("Open-Meteo needs a weather_location".to_string());
+    }
+
+    // The search matches place names only, so drop qualifiers like ", Korea"
+    let name = location.split(',').next().unwrap_or(location).trim();
+
+    let response: GeocodingResponse = http_agent()
+        .get("https://geocoding-api.open-meteo.com/v1/search")
+        .query("name", name)
+        .query("count", "1")
+        .call()
+        .map_err(|e| format!("HTTP error: {e}"))?
+        .body_mut()
+        .read_json()
+        .map_err(|e| format!("JSON parse error: {e}"))?;
+
+    response
+        .results
+        .and_then(|results| results.into_iter().next())
+        .ok_or_else(|| format!("Unknown location: {location}"))
+}
+
+/// Parse the time of an ISO 8601 local datetime ("2024-01-15T08:15") to minutes since midnight.
+fn parse_iso_minutes(datetime: &str) -> Option<u32> {
+    let (_, time) = datetime.split_once('T')?;
+    let mut parts = time.split(':');
+    let hours: u32 = parts.next()?.parse().ok()?;
+    let minutes: u32 = parts.next()?.parse().ok()?;
+    Some(hours * 60 + minutes)
+}
+
+/// Map a WMO weather interpretation code to our simplified condition.
+/// See: https://open-meteo.com/en/docs (WMO Weather interpretation codes)
+fn map_wmo_code(code: u8) -> WeatherCondition {
+    match code {
+        // Clear sky, mainly clear
+        0 | 1 => WeatherCondition::Clear,
+
+        // Partly cloudy
+        2 => WeatherCondition::PartlyCloudy,
+
+        // Overcast
+        3 => WeatherCondition::Cloudy,
+
+        // Fog and depositing rime fog
+        45 | 48 => WeatherCondition::Fog,
+
+        // Drizzle, freezing drizzle, slight/moderate rain, freezing rain, rain showers
+        51 | 53 | 55 | 56 | 57 | 61 | 63 | 66 | 67 | 80 | 81 => WeatherCondition::Rain,
+
+        // Heavy rain, violent rain showers
+        65 | 82 => WeatherCondition::HeavyRain,
+
+        // Snow fall, snow grains, snow showers
+        71 | 73 | 75 | 77 | 85 | 86 => WeatherCondition::Snow,
+
+        // Thunderstorm, with slight or heavy hail
+        95 | 96 | 99 => WeatherCondition::Thunderstorm,
+
+        // Default to cloudy for unknown codes
+        _ => WeatherCondition::Cloudy,
+    }
+}
+
+/// Map a WMO weather interpretation code to how heavily rain or snow falls.
+fn map_wmo_intensity(code: u8) -> f32 {
+    match code {
+        // Light drizzle, freezing drizzle and freezing rain, slight rain, snow and showers
+        51 | 53 | 56 | 61 | 66 | 71 | 77 | 80 | 85 => precipitation::LIGHT,
+
+        // Heavy rain, freezing rain and snow, violent showers, thunderstorms with hail
+        65 | 67 | 75 | 82 | 86 | 96 | 99 => precipitation::HEAVY,
+
+        _ => precipitation::MODERATE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_wmo_code_table() {
+        let table = [
+            (0, WeatherCondition::Clear),
+            (1, WeatherCondition::Clear),
+            (2, WeatherCondition::PartlyCloudy),
+            (3, WeatherCondition::Cloudy),
+            (45, WeatherCondition::Fog),
+            (48, WeatherCondition::Fog),
+            (51, WeatherCondition::Rain),
+            (53, WeatherCondition::Rain),
+            (55, WeatherCondition::Rain),
+            (56, WeatherCondition::Rain),
+            (57, WeatherCondition::Rain),
+            (61, WeatherCondition::Rain),
+            (63, WeatherCondition::Rain),
+            (65, WeatherCondition::HeavyRain),
+            (66, WeatherCondition::Rain),
+            (67, WeatherCondition::Rain),
+            (71, WeatherCondition::Snow),
+            (73, WeatherCondition::Snow),
+            (75, WeatherCondition::Snow),
+            (77, WeatherCondition::Snow),
+            (80, WeatherCondition::Rain),
+            (81, WeatherCondition::Rain),
+            (82, WeatherCondition::HeavyRain),
+            (85, WeatherCondition::Snow),
+            (86, WeatherCondition::Snow),
+            (95, WeatherCondition::Thunderstorm),
+            (96, WeatherCondition::Thunderstorm),
+            (99, WeatherCondition::Thunderstorm),
+        ];
+        for (code, condition) in table {
+            assert_eq!(map_wmo_code(code), condition, "WMO code {code}");
+        }
+    }
+
+    #[test]
+    fn test_map_wmo_code_unknown() {
+        for code in [4, 50, 100, 255] {
+            assert_eq!(map_wmo_code(code), WeatherCondition::Cloudy);
+        }
+    }
+
+    #[test]
+    fn test_map_wmo_intensity() {
+        // Slight, moderate and heavy rain
+        assert_eq!(map_wmo_intensity(61), precipitation::LIGHT);
+        assert_eq!(map_wmo_intensity(63), precipitation::MODERATE);
+        assert_eq!(map_wmo_intensity(65), precipitation::HEAVY);
+        // Slight, moderate and heavy snow
+        assert_eq!(map_wmo_intensity(71), precipitation::LIGHT);
+        assert_eq!(map_wmo_intensity(73), precipitation::MODERATE);
+        assert_eq!(map_wmo_intensity(75), precipitation::HEAVY);
+        // Showers from slight to violent
+        assert_eq!(map_wmo_intensity(80), precipitation::LIGHT);
+        assert_eq!(map_wmo_intensity(82), precipitation::HEAVY);
+        // Dry weather
+        assert_eq!(map_wmo_intensity(0), precipitation::MODERATE);
+    }
+
+    #[test]
+    fn test_parse_iso_minutes() {
+        assert_eq!(parse_iso_minutes("2024-01-15T08:15"), Some(8 * 60 + 15));
+        assert_eq!(parse_iso_minutes("2024-06-21T21:47"), Some(21 * 60 + 47));
+        assert_eq!(parse_iso_minutes("08:15"), None);
+    }
+
+    #[test]
+    fn test_geocode_requires_location() {
+        assert!(geocode("").is_err());
+    }
+}
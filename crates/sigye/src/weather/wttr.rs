@@ -0,0 +1,243 @@
+//! wttr.in weather provider.
+
+use serde::Deserialize;
+use sigye_core::{WeatherCondition, precipitation};
+
+use super::{WeatherData, WeatherProvider, http_agent};
+
+/// Fetches weather from wttr.in, which auto-detects the location from the IP.
+#[derive(Debug, Default)]
+pub struct Wttr;
+
+/// wttr.in JSON response structure (partial - only fields we need).
+#[derive(Debug, Deserialize)]
+struct WttrResponse {
+    current_condition: Vec<CurrentCondition>,
+    nearest_area: Option<Vec<NearestArea>>,
+    weather: Option<Vec<DailyWeather>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentCondition {
+    #[serde(rename = "weatherCode")]
+    weather_code: String,
+    #[serde(rename = "temp_C")]
+    temp_c: String,
+    #[serde(rename = "windspeedKmph")]
+    windspeed_kmph: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NearestArea {
+    latitude: String,
+    longitude: String,
+    #[serde(rename = "areaName")]
+    area_name: Option<Vec<AreaName>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AreaName {
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyWeather {
+    astronomy: Vec<Astronomy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Astronomy {
+    sunrise: String,
+    sunset: String,
+}
+
+impl WeatherProvider for Wttr {
+    fn name(&self) -> &'static str {
+        "wttr.in"
+    }
+
+    fn fetch(&self, location: &str) -> Result<WeatherData, String> {
+        let url = if location.is_empty() {
+            "https://wttr.in/?format=j1".to_string()
+        } else {
+            format!("https://wttr.in/{}?format=j1", url_encode(location))
+        };
+
+        let response: WttrResponse = http_agent()
+            .get(&url)
+            .call()
+            .map_err(|e| format!("HTTP error: {e}"))?
+            .body_mut()
+            .read_json()
+            .map_err(|e| format!("JSON parse error: {e}"))?;
+
+        // Extract current condition
+        let current = response
+            .current_condition
+            .first()
+            .ok_or("No current condition")?;
+
+        let temp_c = current.temp_c.parse().unwrap_or(15);
+        let wind_kmph: u32 = current.windspeed_kmph.parse().unwrap_or(0);
+        let condition = parse_weather_code(&current.weather_code);
+
+        // Get coordinates for aurora and sunrise calculation, and the place name for display
+        let area = response
+            .nearest_area
+            .as_ref()
+            .and_then(|areas| areas.first());
+        let latitude = area
+            .and_then(|area| area.latitude.parse().ok())
+            .unwrap_or(0.0);
+        let longitude = area.and_then(|area| area.longitude.parse().ok());
+        let location_name = area
+            .and_then(|area| area.area_name.as_ref()?.first())
+            .map(|name| name.value.clone())
+            .filter(|name| !name.is_empty());
+
+        Ok(WeatherData::new(
+            condition,
+            temp_c,
+            wind_kmph,
+            latitude,
+            longitude,
+            parse_sun_times(&response),
+            location_name,
+        )
+        .with_intensity(parse_intensity_code(&current.weather_code)))
+    }
+}
+
+/// Extract sunrise and sunset (minutes since midnight) from the response.
+fn parse_sun_times(response: &WttrResponse) -> Option<(u32, u32)> {
+    let weather = response.weather.as_ref().and_then(|w| w.first())?;
+    let astronomy = weather.astronomy.first()?;
+
+    // Parse times (format: "06:45 AM")
+    let sunrise_mins = parse_time_to_minutes(&astronomy.sunrise).unwrap_or(6 * 60);
+    let sunset_mins = parse_time_to_minutes(&astronomy.sunset).unwrap_or(18 * 60);
+    Some((sunrise_mins, sunset_mins))
+}
+
+/// Parse time string like "06:45 AM" to minutes since midnight.
+fn parse_time_to_minutes(time_str: &str) -> Option<u32> {
+    let parts: Vec<&str> = time_str.split_whitespace().collect();
+    if parts.len() != 2 {
+        return None;
+    }
+
+    let time_parts: Vec<&str> = parts[0].split(':').collect();
+    if time_parts.len() != 2 {
+        return None;
+    }
+
+    let mut hours: u32 = time_parts[0].parse().ok()?;
+    let minutes: u32 = time_parts[1].parse().ok()?;
+    let is_pm = parts[1].to_uppercase() == "PM";
+
+    if is_pm && hours != 12 {
+        hours += 12;
+    } else if !is_pm && hours == 12 {
+        hours = 0;
+    }
+
+    Some(hours * 60 + minutes)
+}
+
+/// Simple URL encoding for location strings.
+fn url_encode(s: &str) -> String {
+    s.replace(' ', "+").replace(',', "%2C")
+}
+
+/// Map wttr.in weather code to our simplified condition.
+/// See: https://www.worldweatheronline.com/developer/api/docs/weather-icons.aspx
+fn parse_weather_code(code: &str) -> WeatherCondition {
+    match code {
+        // Clear/Sunny
+        "113" => WeatherCondition::Clear,
+
+        // Partly cloudy
+        "116" => WeatherCondition::PartlyCloudy,
+
+        // Cloudy/Overcast
+        "119" | "122" => WeatherCondition::Cloudy,
+
+        // Fog/Mist
+        "143" | "248" | "260" => WeatherCondition::Fog,
+
+        // Light rain/drizzle
+        "176" | "263" | "266" | "293" | "296" | "353" => WeatherCondition::Rain,
+
+        // Heavy rain
+        "299" | "302" | "305" | "308" | "356" | "359" => WeatherCondition::HeavyRain,
+
+        // Thunderstorm
+        "200" | "386" | "389" | "392" | "395" => WeatherCondition::Thunderstorm,
+
+        // Snow (various types)
+        "179" | "182" | "185" | "227" | "230" | "281" | "284" | "311" | "314" | "317" | "320"
+        | "323" | "326" | "329" | "332" | "335" | "338" | "350" | "362" | "365" | "368" | "371"
+        | "374" | "377" => WeatherCondition::Snow,
+
+        // Default to cloudy for unknown codes
+        _ => WeatherCondition::Cloudy,
+    }
+}
+
+/// Map a wttr.in weather code to how heavily rain or snow falls.
+fn parse_intensity_code(code: &str) -> f32 {
+    match code {
+        // Patchy, light and freezing drizzle, light rain, sleet and snow
+        "176" | "179" | "182" | "185" | "263" | "266" | "281" | "293" | "296" | "311" | "317"
+        | "323" | "326" | "353" | "362" | "368" | "374" | "386" | "392" => precipitation::LIGHT,
+
+        // Heavy and torrential rain, blizzards, heavy snow, with or without thunder
+        "230" | "305" | "308" | "335" | "338" | "359" | "389" | "395" => precipitation::HEAVY,
+
+        _ => precipitation::MODERATE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weather_code() {
+        assert_eq!(parse_weather_code("113"), WeatherCondition::Clear);
+        assert_eq!(parse_weather_code("116"), WeatherCondition::PartlyCloudy);
+        assert_eq!(parse_weather_code("200"), WeatherCondition::Thunderstorm);
+        assert_eq!(parse_weather_code("227"), WeatherCondition::Snow);
+        assert_eq!(parse_weather_code("999"), WeatherCondition::Cloudy); // Unknown
+    }
+
+    #[test]
+    fn test_parse_intensity_code() {
+        // Light and heavy rain are both rain, but fall differently
+        assert_eq!(parse_weather_code("296"), WeatherCondition::Rain);
+        assert_eq!(parse_intensity_code("296"), precipitation::LIGHT);
+        assert_eq!(parse_intensity_code("302"), precipitation::MODERATE);
+        assert_eq!(parse_intensity_code("308"), precipitation::HEAVY);
+        // Snow from patchy to a blizzard
+        assert_eq!(parse_intensity_code("323"), precipitation::LIGHT);
+        assert_eq!(parse_intensity_code("332"), precipitation::MODERATE);
+        assert_eq!(parse_intensity_code("230"), precipitation::HEAVY);
+        // Dry weather and unknown codes
+        assert_eq!(parse_intensity_code("113"), precipitation::MODERATE);
+        assert_eq!(parse_intensity_code("999"), precipitation::MODERATE);
+    }
+
+    #[test]
+    fn test_parse_time_to_minutes() {
+        assert_eq!(parse_time_to_minutes("06:45 AM"), Some(6 * 60 + 45));
+        assert_eq!(parse_time_to_minutes("12:00 PM"), Some(12 * 60));
+        assert_eq!(parse_time_to_minutes("12:00 AM"), Some(0));
+        assert_eq!(parse_time_to_minutes("06:30 PM"), Some(18 * 60 + 30));
+    }
+
+    #[test]
+    fn test_url_encode() {
+        assert_eq!(url_encode("New York"), "New+York");
+        assert_eq!(url_encode("Seoul, Korea"), "Seoul%2C+Korea");
+    }
+}
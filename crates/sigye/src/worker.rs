@@ -0,0 +1,145 @@
+//! Background threads that stop promptly and are joined on shutdown.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How long shutting down waits for a worker to finish before leaving it behind.
+pub const JOIN_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Message sent to a worker thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signal {
+    /// Finish and return.
+    Stop,
+    /// Cut the current wait short.
+    Wake,
+}
+
+/// Why a worker's wait ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wakeup {
+    /// The full timeout passed.
+    Timeout,
+    /// [`Worker::wake`] was called.
+    Woken,
+    /// The thread was asked to stop.
+    Stop,
+}
+
+/// Handle to a running background thread.
+#[derive(Debug)]
+pub struct Worker {
+    /// Dropping or sending [`Signal::Stop`] on this asks the thread to stop.
+    signal: Sender<Signal>,
+    /// Disconnects once the thread has finished.
+    done: Receiver<()>,
+    /// The thread itself.
+    handle: JoinHandle<()>,
+}
+
+impl Worker {
+    /// Run `work` on a new thread, handing it the signal to watch for stopping.
+    pub fn spawn(work: impl FnOnce(StopSignal) + Send + 'static) -> Self {
+        let (signal, signal_rx) = mpsc::channel();
+        let (done_tx, done) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || {
+            // Dropped when the work returns or panics, waking up `stop()`
+            let _done = done_tx;
+            work(StopSignal(signal_rx));
+        });
+        Self {
+            signal,
+            done,
+            handle,
+        }
+    }
+
+    /// Cut the thread's current wait short, e.g. to do its work right away.
+    pub fn wake(&self) {
+        let _ = self.signal.send(Signal::Wake);
+    }
+
+    /// Ask the thread to stop and join it if it finishes within `timeout`.
+    ///
+    /// A thread stuck in a blocking call (e.g. an HTTP request) is left to
+    /// finish on its own. Returns whether the thread was joined.
+    pub fn stop(self, timeout: Duration) -> bool {
+        let _ = self.signal.send(Signal::Stop);
+        match self.done.recv_timeout(timeout) {
+            Err(RecvTimeoutError::Timeout) => false,
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                let _ = self.handle.join();
+                true
+            }
+        }
+    }
+}
+
+/// Stop request seen by a worker thread.
+#[derive(Debug)]
+pub struct StopSignal(Receiver<Signal>);
+
+impl StopSignal {
+    /// Wait up to `timeout`, returning early with `true` once a stop is requested.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.wait_for_wakeup(timeout) == Wakeup::Stop
+    }
+
+    /// Wait up to `timeout` for a stop request or a [`Worker::wake`].
+    pub fn wait_for_wakeup(&self, timeout: Duration) -> Wakeup {
+        match self.0.recv_timeout(timeout) {
+            Ok(Signal::Stop) | Err(RecvTimeoutError::Disconnected) => Wakeup::Stop,
+            Ok(Signal::Wake) => Wakeup::Woken,
+            Err(RecvTimeoutError::Timeout) => Wakeup::Timeout,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_stop_wakes_and_joins_promptly() {
+        let worker = Worker::spawn(|stop| while !stop.wait(Duration::from_secs(60)) {});
+
+        let start = Instant::now();
+        assert!(worker.stop(JOIN_TIMEOUT));
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_stop_gives_up_on_busy_thread() {
+        let worker = Worker::spawn(|_| thread::sleep(Duration::from_millis(500)));
+
+        let start = Instant::now();
+        assert!(!worker.stop(Duration::from_millis(20)));
+        assert!(start.elapsed() < Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_wake_cuts_wait_short() {
+        let (tx, rx) = mpsc::channel();
+        let worker = Worker::spawn(move |stop| {
+            loop {
+                match stop.wait_for_wakeup(Duration::from_secs(60)) {
+                    Wakeup::Woken => tx.send(()).unwrap(),
+                    Wakeup::Stop => break,
+                    Wakeup::Timeout => {}
+                }
+            }
+        });
+
+        worker.wake();
+        assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+        assert!(worker.stop(JOIN_TIMEOUT));
+    }
+
+    #[test]
+    fn test_finished_worker_joins_immediately() {
+        let worker = Worker::spawn(|_| {});
+        assert!(worker.stop(JOIN_TIMEOUT));
+    }
+}
@@ -0,0 +1,155 @@
+//! Tests for `sigye --once`, run against the built binary.
+
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use chrono::NaiveDate;
+use sigye_fonts::build_text_art;
+
+/// Home directory of the test runs, without any configuration.
+fn test_home() -> PathBuf {
+    std::env::temp_dir().join(format!("sigye-once-test-{}", std::process::id()))
+}
+
+/// Command running sigye with a fresh default configuration and a UTF-8 locale.
+fn command(args: &[&str]) -> Command {
+    let home = test_home();
+    let mut command = Command::new(env!("CARGO_BIN_EXE_sigye"));
+    command
+        .args(args)
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        .env("LC_ALL", "C.UTF-8")
+        .env_remove("NO_COLOR");
+    command
+}
+
+/// Run sigye with a fresh default configuration and a UTF-8 locale.
+fn sigye(args: &[&str]) -> Output {
+    command(args).output().expect("sigye should run")
+}
+
+/// Stdout of a successful run, one string per line.
+fn stdout_lines(args: &[&str]) -> Vec<String> {
+    let output = sigye(args);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Default font art with the trailing spaces the output leaves out.
+fn art(text: &str) -> Vec<String> {
+    build_text_art(text)
+        .iter()
+        .map(|row| row.trim_end().to_string())
+        .collect()
+}
+
+#[test]
+fn test_once_prints_time_art() {
+    let lines = stdout_lines(&["--once", "--time", "14:30:05", "--no-date"]);
+    assert_eq!(lines, art("14:30:05"));
+}
+
+#[test]
+fn test_once_time_without_seconds() {
+    let lines = stdout_lines(&["--once", "--time", "09:05", "--no-date"]);
+    assert_eq!(lines, art("09:05:00"));
+}
+
+#[test]
+fn test_once_with_date() {
+    let lines = stdout_lines(&["--once", "--time", "14:30:05"]);
+    let (date, time) = lines.split_last().unwrap();
+    assert_eq!(time[time.len() - 1], "");
+    NaiveDate::parse_from_str(date.trim(), "%A, %B %d, %Y")
+        .unwrap_or_else(|e| panic!("{date:?} is not a long date: {e}"));
+
+    // The date is centered under the time
+    let art_width = build_text_art("14:30:05")[0].len();
+    let pad = date.len() - date.trim_start().len();
+    assert_eq!(pad, (art_width - date.trim().len()) / 2);
+}
+
+#[test]
+fn test_once_width_centers_output() {
+    let plain = stdout_lines(&["--once", "--time", "14:30:05", "--no-date"]);
+    let centered = stdout_lines(&[
+        "--once",
+        "--time",
+        "14:30:05",
+        "--no-date",
+        "--width",
+        "100",
+    ]);
+    let pad = (100 - build_text_art("14:30:05")[0].len()) / 2;
+    assert_eq!(plain.len(), centered.len());
+    for (line, centered) in plain.iter().zip(&centered) {
+        if line.is_empty() {
+            // Blank rows aren't padded
+            assert_eq!(centered, "");
+        } else {
+            assert_eq!(*centered, format!("{}{line}", " ".repeat(pad)));
+        }
+    }
+}
+
+#[test]
+fn test_once_closed_stdout_exits_quietly() {
+    // Wider than a pipe buffer holds, so the write fails however early the
+    // reader goes away, like `sigye --once | head -2`
+    let mut child = command(&["--once", "--no-date", "--width", "60000"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("sigye should run");
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
+
+#[test]
+fn test_once_plain_and_color() {
+    let args = ["--once", "--time", "14:30:05"];
+    let output = sigye(&args);
+    assert!(output.status.success());
+    assert!(!output.stdout.contains(&0x1b), "plain output has escapes");
+
+    let output = sigye(&[&args[..], &["--color"]].concat());
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\x1b["), "{stdout:?}");
+    assert!(stdout.trim_end().ends_with("\x1b[0m"), "{stdout:?}");
+}
+
+#[test]
+fn test_once_options_require_once() {
+    for args in [
+        &["--color"][..],
+        &["--width", "80"],
+        &["--time", "14:30"],
+        &["--no-date"],
+    ] {
+        let output = sigye(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+    }
+}
+
+#[test]
+fn test_once_rejects_bad_time() {
+    let output = sigye(&["--once", "--time", "25:00"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("24-hour time"));
+}
+
+#[test]
+fn test_once_does_not_write_config() {
+    let output = sigye(&["--once"]);
+    assert!(output.status.success());
+    assert!(!test_home().join("config/sigye/config.toml").exists());
+}
@@ -0,0 +1,57 @@
+//! Checks on the binary's sources: backgrounds are drawn by sigye-background only.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Items that only sigye-background may define, so a local copy can't drift from it.
+const BACKGROUND_ITEMS: &[&str] = &[
+    "struct BackgroundState",
+    "struct MatrixColumn",
+    "struct SnowColumn",
+    "fn hsl_to_rgb",
+    "fn render_char",
+    "fn render_reactive",
+];
+
+/// Every Rust source file under `dir`.
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(rust_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+#[test]
+fn test_no_background_module_in_binary() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    assert!(!src.join("background.rs").exists());
+    assert!(!src.join("background").exists());
+
+    for path in rust_files(&src) {
+        let source = fs::read_to_string(&path).unwrap();
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim_start();
+            let line = ["pub(crate) ", "pub "]
+                .iter()
+                .find_map(|vis| line.strip_prefix(vis))
+                .unwrap_or(line);
+            let at = format!("{}:{}", path.display(), i + 1);
+            assert!(
+                !line.starts_with("mod background;") && !line.starts_with("mod background {"),
+                "{at}: backgrounds belong in sigye-background"
+            );
+            for item in BACKGROUND_ITEMS {
+                assert!(
+                    !line.starts_with(item),
+                    "{at}: `{item}` is sigye-background's, use it from there"
+                );
+            }
+        }
+    }
+}